@@ -0,0 +1,72 @@
+use hollywood::actors::Periodic;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+
+/// Length of the chain of moving average actors.
+const CHAIN_LENGTH: usize = 50;
+
+/// Runs a long chain of small, chatty actors for a fixed duration - either on the shared runtime
+/// or as single actor group on a dedicated thread - and reports the number of processed messages.
+///
+/// Usage: cargo run --example actor_groups [grouped]
+pub async fn run_actor_groups_example(grouped: bool) {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.001);
+        let mut previous: Option<MovingAverage> = None;
+        for _ in 0..CHAIN_LENGTH {
+            let mut moving_average = MovingAverage::from_prop_and_state(
+                context,
+                MovingAverageProp {
+                    alpha: 0.3,
+                    timeout: f64::MAX,
+                },
+                MovingAverageState {
+                    moving_average: 0.0,
+                },
+            );
+            match previous.as_mut() {
+                Some(previous) => previous
+                    .outbound
+                    .average
+                    .connect(context, &mut moving_average.inbound.value),
+                None => timer
+                    .outbound
+                    .time_stamp
+                    .connect(context, &mut moving_average.inbound.value),
+            }
+            if grouped {
+                context.assign_actor_group(&moving_average.actor_name, "chain");
+            }
+            previous = Some(moving_average);
+        }
+    });
+
+    let duration = std::time::Duration::from_secs(5);
+    let pipeline = pipeline.run_with_deadline(duration).await.unwrap();
+    let num_messages: u64 = pipeline
+        .batch_stats()
+        .iter()
+        .map(|(_, stats)| stats.num_messages)
+        .sum();
+    println!(
+        "{}: processed {} messages in {:?}",
+        if grouped { "grouped" } else { "ungrouped" },
+        num_messages,
+        duration
+    );
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+    let grouped = std::env::args().any(|arg| arg == "grouped");
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_actor_groups_example(grouped).await;
+        })
+}
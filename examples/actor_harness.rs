@@ -0,0 +1,144 @@
+use hollywood::actors::Periodic;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+use hollywood::testing::ActorHarness;
+use std::time::Duration;
+
+/// Outbound hub of the lookup actor.
+#[actor_outputs]
+pub struct LookupOutbound {
+    /// Looked up values.
+    pub value: OutboundChannel<f64>,
+}
+
+/// Inbound message of the lookup actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    LookupInbound,
+    {
+        NullProp,
+        NullState,
+        LookupOutbound,
+        LookupOutRequest,
+        NullInRequestMessage
+    })]
+pub enum LookupMessage {
+    /// Key to look up.
+    Key(u32),
+    /// Reply of the table.
+    TableReply(ReplyMessage<f64>),
+}
+
+impl HasOnMessage for LookupMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        request: &Self::OutRequestHub,
+    ) {
+        match self {
+            LookupMessage::Key(key) => {
+                request.table.send_request(key);
+            }
+            LookupMessage::TableReply(reply) => {
+                outbound.value.send(reply.reply);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<u32> for LookupMessage {
+    fn new(_inbound_name: String, msg: u32) -> Self {
+        LookupMessage::Key(msg)
+    }
+}
+
+impl IsInboundMessageNew<ReplyMessage<f64>> for LookupMessage {
+    fn new(_inbound_name: String, msg: ReplyMessage<f64>) -> Self {
+        LookupMessage::TableReply(msg)
+    }
+}
+
+/// Out-request hub of the lookup actor.
+#[actor_out_requests]
+pub struct LookupOutRequest {
+    /// Request to the table.
+    pub table: OutRequestChannel<u32, f64, LookupMessage>,
+}
+
+/// Actor which looks up each key in a table, and sends the value.
+#[actor(LookupMessage, NullInRequestMessage)]
+type Lookup =
+    Actor<NullProp, LookupInbound, NullInRequests, NullState, LookupOutbound, LookupOutRequest>;
+
+/// Tests the moving average actor by injecting values and collecting the averages.
+async fn test_moving_average() {
+    let mut harness = ActorHarness::new();
+    let mut actor = MovingAverage::from_prop_and_state(
+        harness.context(),
+        MovingAverageProp {
+            alpha: 0.5,
+            timeout: 10.0,
+        },
+        MovingAverageState::default(),
+    );
+    let value = actor.inbound.value.injector();
+    let average = harness.collect(&mut actor.outbound.average);
+    harness.start().await;
+
+    harness.send(&value, 2.0).await;
+    harness.send(&value, 4.0).await;
+    assert_eq!(average.take(), vec![1.0, 2.5]);
+    harness.stop().await.unwrap();
+    println!("moving average: ok");
+}
+
+/// Tests the periodic actor by advancing the virtual clock.
+async fn test_periodic() {
+    let mut harness = ActorHarness::new();
+    let mut timer = Periodic::new_with_period(harness.context(), 1.0);
+    let time_stamps = harness.collect(&mut timer.outbound.time_stamp);
+    harness.start().await;
+
+    harness.advance(Duration::from_secs_f64(0.5)).await;
+    let num_ticks = time_stamps.len();
+    harness.advance(Duration::from_secs(3)).await;
+    assert_eq!(time_stamps.len(), num_ticks + 3);
+    harness.stop().await.unwrap();
+    println!("periodic: ok, {:?}", time_stamps.take());
+}
+
+/// Tests the lookup actor against a scripted table.
+async fn test_lookup() {
+    let mut harness = ActorHarness::new();
+    let mut actor =
+        Lookup::from_prop_and_state(harness.context(), NullProp::default(), NullState::default());
+    let key = actor.inbound.key.injector();
+    let values = harness.collect(&mut actor.outbound.value);
+    let requests = harness.script_replies(&mut actor.out_requests.table, |key| key as f64 * 0.5);
+    harness.start().await;
+
+    harness.send(&key, 3).await;
+    harness.send(&key, 8).await;
+    assert_eq!(requests.take(), vec![3, 8]);
+    assert_eq!(values.take(), vec![1.5, 4.0]);
+    harness.stop().await.unwrap();
+    println!("lookup: ok");
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            test_moving_average().await;
+            test_periodic().await;
+            test_lookup().await;
+        })
+}
@@ -0,0 +1,63 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+
+/// Runs the moving average on a dedicated thread, and the printer on a separate, named runtime.
+pub async fn run_actor_placement_example(io_runtime: tokio::runtime::Handle) {
+    let pipeline = Hollywood::configure(&mut |context| {
+        context.register_runtime("io", io_runtime.clone());
+
+        let mut timer = Periodic::new_with_period(context, 0.5);
+        let mut moving_average = MovingAverage::from_prop_and_state_with_placement(
+            context,
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: 5.0,
+            },
+            MovingAverageState {
+                moving_average: 0.0,
+            },
+            ActorPlacement::DedicatedThread,
+        );
+        let mut average_printer = Printer::<f64>::from_prop_and_state_with_placement(
+            context,
+            PrinterProp {
+                topic: "average".to_string(),
+            },
+            NullState {},
+            ActorPlacement::Runtime("io".to_owned()),
+        );
+        pipeline! {
+            timer.time_stamp -> moving_average.value;
+            moving_average.average -> average_printer.printable;
+        }
+
+        context.register_cancel_requester(&mut moving_average.outbound.cancel_request);
+    });
+
+    pipeline.run().await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    let io_runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("io")
+        .enable_all()
+        .build()
+        .unwrap();
+    let io_handle = io_runtime.handle().clone();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_actor_placement_example(io_handle).await;
+        })
+}
@@ -0,0 +1,54 @@
+use hollywood::actors::Periodic;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+
+/// Run the example which reads the final state of the moving average actor
+pub async fn run_actor_state_example() {
+    let mut average = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 1.0);
+        let mut moving_average = MovingAverage::from_prop_and_state(
+            context,
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: 5.0,
+            },
+            MovingAverageState {
+                moving_average: 0.0,
+            },
+        );
+        average = Some(moving_average.state_handle());
+        pipeline! {
+            timer.time_stamp -> moving_average.value;
+        }
+
+        context.register_cancel_requester(&mut moving_average.outbound.cancel_request);
+    });
+    let average = average.unwrap();
+
+    println!(
+        "initial state of {}: {:?}",
+        average.actor_name(),
+        pipeline.actor_state(&average).unwrap()
+    );
+    let pipeline = pipeline.run().await.unwrap();
+    println!(
+        "final state of {}: {:?}",
+        average.actor_name(),
+        pipeline.actor_state(&average).unwrap()
+    );
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_actor_state_example().await;
+        })
+}
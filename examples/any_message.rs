@@ -0,0 +1,78 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use tracing::warn;
+
+/// Inbound message for the plugin actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    PluginInbound,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum PluginMessage {
+    /// Type-erased message from the host.
+    Any(AnyMessage),
+}
+
+impl HasOnMessage for PluginMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            PluginMessage::Any(msg) => match msg.downcast_ref::<f64>() {
+                Ok(time) => println!("plugin received time: {}", time),
+                Err(err) => warn!("plugin ignores message: {}", err),
+            },
+        }
+    }
+}
+
+impl IsInboundMessageNew<AnyMessage> for PluginMessage {
+    fn new(_inbound_name: String, msg: AnyMessage) -> Self {
+        PluginMessage::Any(msg)
+    }
+}
+
+/// Plugin actor, which does not know the message types of the host at compile time.
+#[actor(PluginMessage, NullInRequestMessage)]
+type Plugin =
+    Actor<NullProp, PluginInbound, NullInRequests, NullState, NullOutbound, NullOutRequests>;
+
+/// Run the type-erased message example
+pub async fn run_any_message_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.5);
+        let mut plugin = Plugin::from_prop_and_state(context, NullProp {}, NullState {});
+        timer.outbound.time_stamp.connect_with_adapter(
+            context,
+            AnyMessage::new,
+            &mut plugin.inbound.any,
+        );
+    });
+
+    pipeline.print_flow_graph();
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(2))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_any_message_example().await;
+        })
+}
@@ -0,0 +1,73 @@
+use hollywood::actors::approx_zip::ApproxZip2State;
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::ApproxZip2;
+use hollywood::actors::ApproxZipPair;
+use hollywood::actors::ApproxZipProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+
+/// Run the example which fuses two sensor streams with slightly offset timestamps
+pub async fn run_approx_zip_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut camera_trigger = Periodic::new_with_period(context, 0.1);
+        let mut imu_trigger = Periodic::new_with_period(context, 0.05);
+
+        let mut sync = ApproxZip2::<f64, String, f64>::from_prop_and_state(
+            context,
+            ApproxZipProp {
+                tolerance: 0.01,
+                ..Default::default()
+            },
+            ApproxZip2State::default(),
+        );
+        let mut printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "fused".to_string(),
+            },
+            NullState::default(),
+        );
+
+        // The camera exposure is timestamped 5ms after the trigger.
+        camera_trigger.outbound.time_stamp.connect_with_adapter(
+            context,
+            |t| ApproxZipPair::new(t + 0.005, format!("image@{:.3}", t + 0.005)),
+            &mut sync.inbound.item0,
+        );
+        imu_trigger.outbound.time_stamp.connect_with_adapter(
+            context,
+            |t| ApproxZipPair::new(t, t),
+            &mut sync.inbound.item1,
+        );
+
+        sync.outbound.zipped.connect_with_adapter(
+            context,
+            |zipped| {
+                format!(
+                    "key {:.3}: {} with imu@{:.3}",
+                    zipped.key, zipped.item0, zipped.item1
+                )
+            },
+            &mut printer.inbound.printable,
+        );
+    });
+
+    pipeline.print_flow_graph();
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_approx_zip_example().await;
+        })
+}
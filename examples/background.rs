@@ -0,0 +1,42 @@
+use hollywood::actors::BackgroundActor;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the background actor example
+pub async fn run_background_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        // A health reporter, which would typically write to an external monitoring system.
+        BackgroundActor::from_named_task(context, "HealthReporter", |mut kill| async move {
+            let mut num_reports = 0;
+            loop {
+                tokio::select! {
+                    _ = kill.changed() => break,
+                    _ = tokio::time::sleep(Duration::from_millis(250)) => {
+                        num_reports += 1;
+                        println!("health report #{}: all good", num_reports);
+                    }
+                }
+            }
+            println!("health reporter stopped after {} reports", num_reports);
+        });
+    });
+
+    pipeline.print_flow_graph();
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_background_example().await;
+        })
+}
@@ -0,0 +1,114 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Properties of the slow consumer actor.
+#[derive(Clone, Debug, Default)]
+pub struct SlowConsumerProp {
+    /// Number of processed ticks.
+    pub num_processed: Arc<AtomicU64>,
+}
+
+/// Inbound message of the slow consumer actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    SlowConsumerInbound,
+    {
+        SlowConsumerProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum SlowConsumerMessage {
+    /// Tick to be processed.
+    Tick(Stamped<()>),
+}
+
+impl HasOnMessage for SlowConsumerMessage {
+    fn on_message(
+        self,
+        prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            SlowConsumerMessage::Tick(_) => {
+                // Simulates an expensive computation, which is ten times slower than the source.
+                std::thread::sleep(Duration::from_millis(10));
+                prop.num_processed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Stamped<()>> for SlowConsumerMessage {
+    fn new(_inbound_name: String, msg: Stamped<()>) -> Self {
+        SlowConsumerMessage::Tick(msg)
+    }
+}
+
+/// Consumer which cannot keep up with its source.
+#[actor(SlowConsumerMessage, NullInRequestMessage)]
+type SlowConsumer = Actor<
+    SlowConsumerProp,
+    SlowConsumerInbound,
+    NullInRequests,
+    NullState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+async fn run_with_policy(policy: BackpressurePolicy) {
+    let prop = SlowConsumerProp::default();
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.001);
+        let mut consumer =
+            SlowConsumer::from_prop_and_state(context, prop.clone(), NullState::default());
+        timer
+            .outbound
+            .tick
+            .connect_with_capacity(context, &mut consumer.inbound.tick, 4, policy);
+    });
+    let pipeline = pipeline
+        .run_with_deadline(Duration::from_secs(1))
+        .await
+        .unwrap();
+    for (actor_name, inbound_name, stats) in pipeline.backpressure_stats() {
+        println!(
+            "{:?}: {}.{} processed {}, {:?}",
+            policy,
+            actor_name,
+            inbound_name,
+            prop.num_processed.load(Ordering::Relaxed),
+            stats
+        );
+    }
+}
+
+/// Run the example which bounds the queue of a slow consumer with each backpressure policy
+pub async fn run_backpressure_example() {
+    for policy in [
+        BackpressurePolicy::DropNewest,
+        BackpressurePolicy::DropOldest,
+        BackpressurePolicy::Block,
+    ] {
+        run_with_policy(policy).await;
+    }
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_backpressure_example().await;
+        })
+}
@@ -0,0 +1,65 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Batcher;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+
+/// Run the example which batches ticks by count and by time window
+pub async fn run_batcher_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut by_size = Batcher::<f64>::new_with_size(context, 4);
+        let mut by_window = Batcher::<f64>::new_with_window(context, 0.55);
+        let mut size_printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "batch of 4".to_string(),
+            },
+            NullState::default(),
+        );
+        let mut window_printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "batch of 0.55s".to_string(),
+            },
+            NullState::default(),
+        );
+
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut by_size.inbound.value);
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut by_window.inbound.value);
+        by_size.outbound.batch.connect_with_adapter(
+            context,
+            |batch| format!("{:.1?}", batch),
+            &mut size_printer.inbound.printable,
+        );
+        by_window.outbound.batch.connect_with_adapter(
+            context,
+            |batch| format!("{:.1?}", batch),
+            &mut window_printer.inbound.printable,
+        );
+    });
+
+    pipeline.print_flow_graph();
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(2))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_batcher_example().await;
+        })
+}
@@ -0,0 +1,57 @@
+use hollywood::actors::Periodic;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+
+/// Run the example which inspects the moving average through a tap, without a printer actor
+pub async fn run_channel_tap_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut moving_average = MovingAverage::from_prop_and_state(
+            context,
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: 1.0,
+            },
+            MovingAverageState {
+                moving_average: 0.0,
+            },
+        );
+        pipeline! {
+            timer.time_stamp -> moving_average.value;
+        }
+        moving_average.outbound.average.tap(context);
+        context.register_cancel_requester(&mut moving_average.outbound.cancel_request);
+    });
+
+    let running = pipeline.spawn();
+    let mut average = running
+        .tap::<f64>("MovingAverage_0", "average", 16)
+        .expect("average is tappable");
+
+    // The tap stays open until the pipeline is dropped, hence a fixed number of messages is read.
+    let mut previous = 0.0;
+    for _ in 0..5 {
+        let value = average.recv().await.unwrap();
+        println!("average: {:.3}", value);
+        assert!(
+            value >= previous,
+            "the average of increasing times increases"
+        );
+        previous = value;
+    }
+    running.join().await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_channel_tap_example().await;
+        })
+}
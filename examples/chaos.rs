@@ -0,0 +1,62 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the chaos command example
+pub async fn run_chaos_example() {
+    let mut printer_name = String::new();
+    let pipeline = Hollywood::configure(&mut |context| {
+        context.enable_chaos_commands();
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "time".to_string(),
+            },
+            NullState::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut printer.inbound.printable);
+        printer_name = printer.actor_name.clone();
+    });
+
+    let running = pipeline.spawn();
+    let chaos = running.chaos_controller();
+
+    // Resilience drill: stall the printer, then slow it down and finally kill it.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    println!("--- stall for 1s");
+    chaos
+        .inject(&printer_name, ChaosFault::Stall(Duration::from_secs(1)))
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    println!("--- delay by 300ms");
+    chaos
+        .inject(&printer_name, ChaosFault::Delay(Duration::from_millis(300)))
+        .unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    println!("--- kill");
+    chaos.inject(&printer_name, ChaosFault::Kill).unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    running.cancel();
+    if let Err(err) = running.join().await {
+        println!("drill finished: {}", err);
+    }
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_chaos_example().await;
+        })
+}
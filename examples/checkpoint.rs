@@ -0,0 +1,117 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// State of the accumulator actor, which is stored in the pipeline snapshots.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct AccumulatorState {
+    /// Number of values received so far.
+    pub count: u64,
+    /// Sum of the values received so far.
+    pub sum: f64,
+}
+
+/// Inbound message of the accumulator actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    AccumulatorInbound,
+    {
+        NullProp,
+        AccumulatorState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum AccumulatorMessage {
+    /// Value to be accumulated.
+    Value(f64),
+}
+
+impl HasOnMessage for AccumulatorMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            AccumulatorMessage::Value(value) => {
+                state.count += 1;
+                state.sum += value;
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for AccumulatorMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        AccumulatorMessage::Value(msg)
+    }
+}
+
+/// Actor which accumulates its inbound values.
+#[actor(AccumulatorMessage, NullInRequestMessage)]
+type Accumulator = Actor<
+    NullProp,
+    AccumulatorInbound,
+    NullInRequests,
+    AccumulatorState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+fn configure(context: &mut Hollywood) {
+    let mut timer = Periodic::new_with_period(context, 0.1);
+    let mut accumulator = Accumulator::from_prop_and_state_checkpointed(
+        context,
+        NullProp {},
+        AccumulatorState::default(),
+    );
+    timer
+        .outbound
+        .time_stamp
+        .connect(context, &mut accumulator.inbound.value);
+}
+
+/// Run the example which persists the state of a pipeline and resumes it in a second run
+pub async fn run_checkpoint_example() {
+    let path = std::env::temp_dir().join("hollywood_checkpoint.json");
+
+    let pipeline = Hollywood::configure(&mut |context| configure(context));
+    let running = pipeline.spawn();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let pipeline = running
+        .shutdown_gracefully(Duration::from_secs(1))
+        .await
+        .unwrap();
+    pipeline.snapshot().save(&path).unwrap();
+    println!(
+        "snapshot after first run:\n{}",
+        pipeline.snapshot().to_json()
+    );
+
+    // E.g. after a restart of the process, the accumulator continues where it left off.
+    let snapshot = PipelineSnapshot::load(&path).unwrap();
+    let pipeline = Hollywood::configure_from_snapshot(snapshot, &mut |context| configure(context));
+    let pipeline = pipeline
+        .run_with_deadline(Duration::from_secs(1))
+        .await
+        .unwrap();
+    println!(
+        "snapshot after second run:\n{}",
+        pipeline.snapshot().to_json()
+    );
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_checkpoint_example().await;
+        })
+}
@@ -0,0 +1,59 @@
+use hollywood::actors::counter::CounterProp;
+use hollywood::actors::Counter;
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+
+/// Run the counter example
+pub async fn run_counter_example() {
+    let mut counts_client = None;
+    let mut counts_subscriber = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut counter = Counter::from_prop_and_state(
+            context,
+            CounterProp { publish_every: 5 },
+            Default::default(),
+        );
+        timer.outbound.time_stamp.connect_with_adapter(
+            context,
+            |t| {
+                if (t * 10.0).round() as i64 % 3 == 0 {
+                    "fizz".to_owned()
+                } else {
+                    "other".to_owned()
+                }
+            },
+            &mut counter.inbound.event,
+        );
+        counts_client = Some(counter.in_requests.counts.client());
+        counts_subscriber = Some(counter.outbound.counts.subscriber(context));
+    });
+
+    pipeline.print_flow_graph();
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+
+    let mut counts_subscriber = counts_subscriber.unwrap();
+    for _ in 0..3 {
+        println!("published: {:?}", counts_subscriber.recv().await.unwrap());
+    }
+    println!(
+        "requested: {:?}",
+        counts_client.unwrap().request(()).await.unwrap()
+    );
+
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_counter_example().await;
+        })
+}
@@ -0,0 +1,50 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Decimate;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+
+/// Run the example which decimates a 100 Hz source, by count and by time window
+pub async fn run_decimate_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut sensor = Periodic::new_with_period(context, 0.01);
+        let mut every_nth = Decimate::<f64>::new_every_nth(context, 50);
+        let mut per_window = Decimate::<f64>::new_with_window(context, 0.25);
+        let mut every_nth_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "every 50th".to_string(),
+            },
+            NullState::default(),
+        );
+        let mut per_window_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "once per 0.25s".to_string(),
+            },
+            NullState::default(),
+        );
+        pipeline! {
+            sensor.time_stamp -> every_nth.value, per_window.value;
+            every_nth.value -> every_nth_printer.printable;
+            per_window.value -> per_window_printer.printable;
+        }
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(2))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_decimate_example().await;
+        })
+}
@@ -0,0 +1,153 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+
+/// Properties of the next-tick actor.
+#[derive(Clone, Debug)]
+pub struct NextTickProp {
+    /// Maximal number of pending requests. Further requests are abandoned.
+    pub max_pending: usize,
+}
+
+/// State of the next-tick actor.
+#[derive(Clone, Debug, Default)]
+pub struct NextTickState {
+    /// Requests waiting for the next tick.
+    pub pending: Vec<DeferredReply<f64>>,
+}
+
+/// Inbound message for the next-tick actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    NextTickInbound,
+    {
+        NextTickProp,
+        NextTickState,
+        NullOutbound,
+        NullOutRequests,
+        NextTickInRequestMessage
+    })]
+pub enum NextTickMessage {
+    /// Time stamp of the tick.
+    TimeStamp(f64),
+}
+
+impl HasOnMessage for NextTickMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            NextTickMessage::TimeStamp(time) => {
+                for pending in state.pending.drain(..) {
+                    pending.reply(time);
+                }
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for NextTickMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        NextTickMessage::TimeStamp(msg)
+    }
+}
+
+/// In-request message for the next-tick actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    NextTickInRequest,
+    {
+        NextTickProp,
+        NextTickState,
+        NullOutbound,
+        NullOutRequests,
+        NextTickMessage
+    }
+)]
+pub enum NextTickInRequestMessage {
+    /// Request for the time stamp of the next tick.
+    NextTick(RequestWithReplyChannel<(), f64>),
+}
+
+impl HasOnRequestMessage for NextTickInRequestMessage {
+    fn on_request(
+        self,
+        prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            NextTickInRequestMessage::NextTick(request) => {
+                let (_, reply) = request.defer();
+                if state.pending.len() >= prop.max_pending {
+                    reply.abandon();
+                } else {
+                    state.pending.push(reply);
+                }
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<(), f64>> for NextTickInRequestMessage {
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<(), f64>) -> Self {
+        NextTickInRequestMessage::NextTick(request)
+    }
+}
+
+/// Actor which replies to requests with the time stamp of the next tick.
+#[actor(NextTickMessage, NextTickInRequestMessage)]
+type NextTick = Actor<
+    NextTickProp,
+    NextTickInbound,
+    NextTickInRequest,
+    NextTickState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+/// Run the deferred reply example
+pub async fn run_deferred_reply_example() {
+    let mut client = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.5);
+        let mut next_tick = NextTick::from_prop_and_state(
+            context,
+            NextTickProp { max_pending: 2 },
+            NextTickState::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut next_tick.inbound.time_stamp);
+        client = Some(next_tick.in_requests.next_tick.client());
+    });
+
+    pipeline.print_flow_graph();
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+
+    // Three concurrent requests, the third one exceeds the pending limit and is abandoned.
+    let client = client.unwrap();
+    let replies = tokio::join!(client.request(()), client.request(()), client.request(()));
+    println!("replies: {:?}", replies);
+
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_deferred_reply_example().await;
+        })
+}
@@ -0,0 +1,48 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Delay;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+
+/// Run the example which delays the ticks of a periodic actor by a simulated transport latency
+pub async fn run_delay_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.5);
+        let mut delay = Delay::<f64>::new_with_latency(context, 0.2);
+        let mut time_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "time".to_string(),
+            },
+            NullState::default(),
+        );
+        let mut delayed_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "delayed by 0.2s".to_string(),
+            },
+            NullState::default(),
+        );
+        pipeline! {
+            timer.time_stamp -> time_printer.printable, delay.value;
+            delay.value -> delayed_printer.printable;
+        }
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(3))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_delay_example().await;
+        })
+}
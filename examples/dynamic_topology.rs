@@ -0,0 +1,76 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the example which adds and removes a sensor subgraph while the pipeline is running
+pub async fn run_dynamic_topology_example() {
+    let mut clock_port = None;
+    let mut sensor_injector = None;
+
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let fusion = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "fusion".to_string(),
+            },
+            NullState::default(),
+        );
+        // Handles to wire subgraphs to the pipeline at runtime.
+        clock_port = Some(timer.outbound.time_stamp.dynamic_port(context));
+        sensor_injector = Some(fusion.inbound.printable.injector());
+    });
+    let clock_port = clock_port.unwrap();
+    let sensor_injector = sensor_injector.unwrap();
+
+    let running = pipeline.spawn();
+    let topology = running.dynamic_topology();
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    // A sensor comes online: it receives the clock of the pipeline and sends its readings to the
+    // fusion actor.
+    let mut clock_injector = None;
+    topology
+        .spawn_subgraph("sensor", &mut |context| {
+            let mut sensor = Periodic::new_with_period(context, 0.15);
+            let clock = Printer::<f64>::from_prop_and_state(
+                context,
+                PrinterProp {
+                    topic: "sensor clock".to_string(),
+                },
+                NullState::default(),
+            );
+            sensor
+                .outbound
+                .time_stamp
+                .connect_injector(context, &sensor_injector);
+            clock_injector = Some(clock.inbound.printable.injector());
+        })
+        .unwrap();
+    let clock_connection = clock_port.connect(&clock_injector.unwrap());
+    println!("subgraphs: {:?}", topology.subgraph_names());
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    // The sensor goes offline again.
+    clock_port.disconnect(clock_connection);
+    topology.remove_subgraph("sensor").await.unwrap();
+    println!("subgraphs: {:?}", topology.subgraph_names());
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    running.cancel();
+    running.join().await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_dynamic_topology_example().await;
+        })
+}
@@ -2,6 +2,7 @@ use eframe::egui;
 use hollywood::actors::egui::EguiActor;
 use hollywood::actors::egui::EguiAppFromBuilder;
 use hollywood::actors::egui::GenericEguiBuilder;
+use hollywood::actors::egui::ScrollingPlot;
 use hollywood::actors::egui::Stream;
 use hollywood::prelude::*;
 use std::sync::Arc;
@@ -72,13 +73,9 @@ impl HasOnMessage for ContentGeneratorInboundMessage {
 
                 let x = *new_value + state.offset;
 
-                let s = Stream {
-                    msg: PlotMessage::SinPlot((x, x.sin())),
-                };
+                let s = Stream::with_topic("sin", PlotMessage::SinPlot((x, x.sin())));
                 outbound.plot_message.send(s);
-                let c = Stream {
-                    msg: PlotMessage::SinPlot((x, x.cos())),
-                };
+                let c = Stream::with_topic("cos", PlotMessage::CosPlot((x, x.cos())));
                 outbound.plot_message.send(c);
 
                 if x > 2.0 && x < 2.1 {
@@ -209,9 +206,7 @@ pub struct EguiAppExample {
     pub cancel_request_sender: tokio::sync::mpsc::UnboundedSender<CancelRequest>,
     pub on_exit_recv: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<()>>>,
 
-    pub x: f64,
-    pub sin_value: f64,
-    pub cos_value: f64,
+    pub plot: ScrollingPlot,
 }
 
 impl EguiAppFromBuilder<EguiAppExampleBuilder> for EguiAppExample {
@@ -223,9 +218,7 @@ impl EguiAppFromBuilder<EguiAppExampleBuilder> for EguiAppExample {
             out_request_sender: builder.out_request_to_actor_sender,
             cancel_request_sender: builder.cancel_request_sender.unwrap(),
             on_exit_recv: builder.on_exit_recv,
-            x: 0.0,
-            sin_value: 0.0,
-            cos_value: 0.0,
+            plot: ScrollingPlot::new(200),
         })
     }
 
@@ -243,14 +236,8 @@ impl eframe::App for EguiAppExample {
         // Handle incoming messages
         while let Ok(value) = self.message_recv.try_recv() {
             match value.msg {
-                PlotMessage::SinPlot((x, y)) => {
-                    self.x = x;
-                    self.sin_value = y;
-                }
-                PlotMessage::CosPlot((x, y)) => {
-                    self.x = x;
-                    self.cos_value = y;
-                }
+                PlotMessage::SinPlot(point) => self.plot.push("sin(x)", point),
+                PlotMessage::CosPlot(point) => self.plot.push("cos(x)", point),
             }
         }
         // Handle incoming requests
@@ -264,9 +251,7 @@ impl eframe::App for EguiAppExample {
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Hello, egui!");
-            ui.label(format!("x: {}", self.x));
-            ui.label(format!("sin(x): {}", self.sin_value));
-            ui.label(format!("cos(y): {}", self.cos_value));
+            self.plot.ui(ui, 300.0);
 
             if ui.button("Reset").clicked() {
                 // send a reset request to the content generator
@@ -339,7 +324,7 @@ pub async fn run_viewer_example() {
     // 2. Run the viewer on the main thread. This is a blocking call.
     run_egui_app_on_man_thread::<EguiAppExampleBuilder, EguiAppExample>(builder);
     // 3. Wait for the pipeline to finish.
-    pipeline_handle.await.unwrap();
+    pipeline_handle.await.unwrap().unwrap();
 }
 
 // Run the egui app on the main thread.
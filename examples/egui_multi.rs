@@ -0,0 +1,140 @@
+use eframe::egui;
+use hollywood::actors::egui::EguiAppFromBuilder;
+use hollywood::actors::egui::EguiMultiActor;
+use hollywood::actors::egui::EguiMultiBuilder;
+use hollywood::actors::egui::ScrollingPlot;
+use hollywood::actors::Map;
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+struct EguiMultiExampleConfig {
+    max_log_lines: usize,
+}
+
+type EguiMultiExampleBuilder = EguiMultiBuilder<EguiMultiExampleConfig>;
+
+pub struct EguiMultiExample {
+    pub plot_recv: tokio::sync::mpsc::UnboundedReceiver<(f64, f64)>,
+    pub log_recv: tokio::sync::mpsc::UnboundedReceiver<String>,
+    pub cancel_request_sender: tokio::sync::mpsc::UnboundedSender<CancelRequest>,
+    pub on_exit_recv: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<()>>>,
+
+    pub plot: ScrollingPlot,
+    pub log: VecDeque<String>,
+    pub max_log_lines: usize,
+}
+
+impl EguiAppFromBuilder<EguiMultiExampleBuilder> for EguiMultiExample {
+    fn new(mut builder: EguiMultiExampleBuilder, _state: ()) -> Box<EguiMultiExample> {
+        Box::new(EguiMultiExample {
+            plot_recv: builder.take_stream("plot").unwrap(),
+            log_recv: builder.take_stream("log").unwrap(),
+            cancel_request_sender: builder.cancel_request_sender.unwrap(),
+            on_exit_recv: builder.on_exit_recv,
+            plot: ScrollingPlot::new(200),
+            log: VecDeque::new(),
+            max_log_lines: builder.config.max_log_lines,
+        })
+    }
+
+    type Out = EguiMultiExample;
+
+    type State = ();
+}
+
+impl eframe::App for EguiMultiExample {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Close the window if the on exit signal from the pipeline is received
+        if self.on_exit_recv.lock().unwrap().try_recv().is_ok() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+        // Each stream is received on its own typed channel.
+        while let Ok(point) = self.plot_recv.try_recv() {
+            self.plot.push("sin(x)", point);
+        }
+        while let Ok(line) = self.log_recv.try_recv() {
+            self.log.push_back(line);
+            if self.log.len() > self.max_log_lines {
+                self.log.pop_front();
+            }
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.plot.ui(ui, 300.0);
+            for line in self.log.iter() {
+                ui.label(line);
+            }
+        });
+
+        ctx.request_repaint_after(Duration::from_secs_f64(0.1));
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.cancel_request_sender.send(CancelRequest).unwrap();
+    }
+}
+
+pub async fn run_egui_multi_example() {
+    let mut builder =
+        EguiMultiExampleBuilder::from_config(EguiMultiExampleConfig { max_log_lines: 10 });
+    builder.add_stream::<(f64, f64)>("plot");
+    builder.add_stream::<String>("log");
+
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut sine = Map::<f64, (f64, f64)>::from_fn(context, |x| (x, x.sin()));
+        let mut log = Map::<f64, String>::from_fn(context, |x| format!("tick at {:.1}s", x));
+        let egui_actor = EguiMultiActor::from_builder(context, &builder);
+
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut sine.inbound.value);
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut log.inbound.value);
+        sine.outbound
+            .value
+            .connect(context, &mut egui_actor.inbound.stream("plot"));
+        log.outbound
+            .value
+            .connect(context, &mut egui_actor.inbound.stream("log"));
+    });
+
+    builder
+        .cancel_request_sender
+        .clone_from(&pipeline.cancel_request_sender_template);
+
+    let pipeline_handle = tokio::spawn(pipeline.run());
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([640.0, 480.0]),
+        renderer: eframe::Renderer::Wgpu,
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Egui multi actor",
+        options,
+        Box::new(|_cc| EguiMultiExample::new(builder, ())),
+    )
+    .unwrap();
+
+    pipeline_handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_egui_multi_example().await;
+        })
+}
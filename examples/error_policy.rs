@@ -0,0 +1,161 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+
+/// State of the calibrator actor.
+#[derive(Clone, Debug, Default)]
+pub struct CalibratorState {
+    /// Number of samples since the last (re-)start.
+    pub num_samples: u64,
+}
+
+/// Inbound message of the calibrator actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    CalibratorInbound,
+    {
+        NullProp,
+        CalibratorState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum CalibratorMessage {
+    /// Sample time in seconds.
+    Sample(f64),
+}
+
+impl HasOnMessage for CalibratorMessage {
+    fn try_on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) -> Result<(), ActorError> {
+        match self {
+            CalibratorMessage::Sample(time) => {
+                state.num_samples += 1;
+                println!("calibrator: sample {} at {:.1}s", state.num_samples, time);
+                if state.num_samples == 3 {
+                    return Err(format!("calibration drifted at {:.1}s", time).into());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for CalibratorMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        CalibratorMessage::Sample(msg)
+    }
+}
+
+/// Calibrator which restarts from its initial state on failure.
+#[actor(CalibratorMessage, NullInRequestMessage)]
+type Calibrator = Actor<
+    NullProp,
+    CalibratorInbound,
+    NullInRequests,
+    CalibratorState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+/// Inbound message of the watchdog actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    WatchdogInbound,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum WatchdogMessage {
+    /// Sample time in seconds.
+    Sample(f64),
+}
+
+impl HasOnMessage for WatchdogMessage {
+    fn try_on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) -> Result<(), ActorError> {
+        match self {
+            WatchdogMessage::Sample(time) if time > 2.0 => {
+                Err(format!("no fix after {:.1}s", time).into())
+            }
+            WatchdogMessage::Sample(_) => Ok(()),
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for WatchdogMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        WatchdogMessage::Sample(msg)
+    }
+}
+
+/// Watchdog which cancels the pipeline on failure.
+#[actor(WatchdogMessage, NullInRequestMessage)]
+type Watchdog =
+    Actor<NullProp, WatchdogInbound, NullInRequests, NullState, NullOutbound, NullOutRequests>;
+
+/// Run the example which handles errors of message handlers by restarting and escalating
+pub async fn run_error_policy_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.25);
+        let mut calibrator = Calibrator::from_prop_and_state_with_error_policy(
+            context,
+            NullProp::default(),
+            CalibratorState::default(),
+            ErrorPolicy::Restart,
+        );
+        let mut watchdog = Watchdog::from_prop_and_state_with_error_policy(
+            context,
+            NullProp::default(),
+            NullState::default(),
+            ErrorPolicy::Escalate,
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut calibrator.inbound.sample);
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut watchdog.inbound.sample);
+    });
+
+    let mut errors = pipeline.error_stream();
+    let printer = tokio::spawn(async move {
+        while let Ok(event) = errors.recv().await {
+            println!("error stream: {}", event);
+        }
+    });
+    let result = pipeline
+        .run_with_deadline(std::time::Duration::from_secs(10))
+        .await;
+    match result {
+        Ok(_) => println!("pipeline finished"),
+        Err(err) => println!("pipeline finished with failures: {}", err),
+    }
+    printer.abort();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_error_policy_example().await;
+        })
+}
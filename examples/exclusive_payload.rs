@@ -0,0 +1,161 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Large image, which is deliberately not [Clone].
+#[derive(Debug)]
+pub struct Image {
+    /// Frame index.
+    pub index: u64,
+    /// Pixel data.
+    pub pixels: Vec<u8>,
+}
+
+/// Outbound hub of the camera actor.
+#[actor_outputs]
+pub struct CameraOutbound {
+    /// Captured images, moved to the single receiver.
+    pub image: OutboundChannel<Image>,
+}
+
+/// State of the camera actor.
+#[derive(Clone, Debug, Default)]
+pub struct CameraState {
+    /// Number of captured images.
+    pub num_images: u64,
+}
+
+/// Inbound message of the camera actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    CameraInbound,
+    {
+        NullProp,
+        CameraState,
+        CameraOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum CameraMessage {
+    /// Trigger to capture an image.
+    Trigger(f64),
+}
+
+impl HasOnMessage for CameraMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            CameraMessage::Trigger(_) => {
+                outbound.image.send_exclusive(Image {
+                    index: state.num_images,
+                    pixels: vec![0; 1920 * 1080 * 3],
+                });
+                state.num_images += 1;
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for CameraMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        CameraMessage::Trigger(msg)
+    }
+}
+
+/// Camera actor, which captures an image on each trigger.
+#[actor(CameraMessage, NullInRequestMessage)]
+type Camera =
+    Actor<NullProp, CameraInbound, NullInRequests, CameraState, CameraOutbound, NullOutRequests>;
+
+/// State of the image consumer actor.
+#[derive(Clone, Debug, Default)]
+pub struct ConsumerState {
+    /// Number of received bytes.
+    pub num_bytes: usize,
+}
+
+/// Inbound message of the image consumer actor, which is not [Clone] either.
+#[derive(Debug)]
+#[actor_inputs(
+    ConsumerInbound,
+    {
+        NullProp,
+        ConsumerState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum ConsumerMessage {
+    /// Image, owned by the consumer.
+    Image(Image),
+}
+
+impl HasOnMessage for ConsumerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ConsumerMessage::Image(image) => {
+                state.num_bytes += image.pixels.len();
+                println!("image {} received", image.index);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Image> for ConsumerMessage {
+    fn new(_inbound_name: String, msg: Image) -> Self {
+        ConsumerMessage::Image(msg)
+    }
+}
+
+/// Actor which consumes images.
+#[actor(ConsumerMessage, NullInRequestMessage)]
+type Consumer =
+    Actor<NullProp, ConsumerInbound, NullInRequests, ConsumerState, NullOutbound, NullOutRequests>;
+
+/// Run the example which moves large, non-clonable images to a single consumer
+pub async fn run_exclusive_payload_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut camera = Camera::from_prop_and_state(context, NullProp {}, CameraState::default());
+        let mut recorder =
+            Consumer::from_prop_and_state(context, NullProp {}, ConsumerState::default());
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut camera.inbound.trigger);
+        camera
+            .outbound
+            .image
+            .connect_exclusive(context, &mut recorder.inbound.image);
+    });
+    // Cancel the pipeline after one second.
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let _ = cancel_requester.send(CancelRequest);
+    });
+    pipeline.run().await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_exclusive_payload_example().await;
+        })
+}
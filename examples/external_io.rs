@@ -0,0 +1,53 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use hollywood::RuntimeFlavor;
+
+/// Run the example which exchanges messages with code outside the pipeline
+pub fn run_external_io_example() {
+    let mut injector = None;
+    let mut subscriber = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 1.0);
+        let printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "injected".to_string(),
+            },
+            NullState::default(),
+        );
+        injector = Some(printer.inbound.printable.injector());
+        subscriber = Some(timer.outbound.time_stamp.subscriber(context));
+    });
+
+    // E.g. a hardware callback thread which is not part of the pipeline.
+    let injector = injector.unwrap();
+    std::thread::spawn(move || {
+        for i in 0.. {
+            if injector.send(format!("hello #{}", i)).is_err() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+    });
+
+    // E.g. a third-party library which runs its own event loop.
+    let mut subscriber = subscriber.unwrap();
+    std::thread::spawn(move || {
+        while let Some(time) = subscriber.blocking_recv() {
+            println!("subscribed: {}", time);
+        }
+    });
+
+    pipeline.print_flow_graph();
+    pipeline
+        .run_blocking_with(RuntimeFlavor::MultiThread)
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    run_external_io_example();
+}
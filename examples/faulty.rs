@@ -0,0 +1,58 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::FaultyActor;
+use hollywood::actors::FaultyProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+
+/// Run the failure-injection example
+pub async fn run_faulty_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.5);
+        let mut faulty = FaultyActor::<f64>::from_prop_and_state(
+            context,
+            FaultyProp {
+                drop_every_nth: Some(2),
+                panic_after: Some(7),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let mut printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "survived".to_string(),
+            },
+            NullState::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut faulty.inbound.input);
+        faulty
+            .outbound
+            .output
+            .connect(context, &mut printer.inbound.printable);
+    });
+
+    pipeline.print_flow_graph();
+    match pipeline
+        .run_with_deadline(std::time::Duration::from_secs(5))
+        .await
+    {
+        Ok(_) => println!("pipeline finished without failures"),
+        Err(err) => println!("pipeline finished with failures: {}", err),
+    }
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_faulty_example().await;
+        })
+}
@@ -0,0 +1,149 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Number of bytes of a camera frame.
+const FRAME_SIZE: usize = 1920 * 1080 * 3;
+
+/// Outbound hub of the camera actor.
+#[actor_outputs]
+pub struct CameraOutbound {
+    /// Captured frames, which are recycled through the pool of the channel.
+    pub frame: PooledOutboundChannel<Vec<u8>>,
+}
+
+/// State of the camera actor.
+#[derive(Clone, Debug, Default)]
+pub struct CameraState {
+    /// Number of captured frames.
+    pub num_frames: u64,
+    /// Number of allocated frame buffers.
+    pub num_allocations: u64,
+}
+
+/// Inbound message of the camera actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    CameraInbound,
+    {
+        NullProp,
+        CameraState,
+        CameraOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum CameraMessage {
+    /// Trigger to capture a frame.
+    Trigger(f64),
+}
+
+impl HasOnMessage for CameraMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            CameraMessage::Trigger(_) => {
+                let mut frame = outbound.frame.acquire_or(|| {
+                    state.num_allocations += 1;
+                    println!(
+                        "frame {}: buffer {} allocated",
+                        state.num_frames, state.num_allocations
+                    );
+                    vec![0; FRAME_SIZE]
+                });
+                frame.get_mut().fill(state.num_frames as u8);
+                outbound.frame.send(frame);
+                state.num_frames += 1;
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for CameraMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        CameraMessage::Trigger(msg)
+    }
+}
+
+/// Camera actor, which captures a frame on each trigger.
+#[actor(CameraMessage, NullInRequestMessage)]
+type Camera =
+    Actor<NullProp, CameraInbound, NullInRequests, CameraState, CameraOutbound, NullOutRequests>;
+
+/// Inbound message of the frame consumer actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    ConsumerInbound,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum ConsumerMessage {
+    /// Pooled frame, which is returned to the pool once dropped by all consumers.
+    Frame(Shared<PooledFrame<Vec<u8>>>),
+}
+
+impl HasOnMessage for ConsumerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ConsumerMessage::Frame(frame) => {
+                let checksum: u64 = frame.get().iter().step_by(4096).map(|v| *v as u64).sum();
+                println!("frame checksum: {}", checksum);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Shared<PooledFrame<Vec<u8>>>> for ConsumerMessage {
+    fn new(_inbound_name: String, msg: Shared<PooledFrame<Vec<u8>>>) -> Self {
+        ConsumerMessage::Frame(msg)
+    }
+}
+
+/// Actor which consumes frames.
+#[actor(ConsumerMessage, NullInRequestMessage)]
+type Consumer =
+    Actor<NullProp, ConsumerInbound, NullInRequests, NullState, NullOutbound, NullOutRequests>;
+
+/// Run the example which recycles camera frames through a pool
+pub async fn run_frame_pool_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.05);
+        let mut camera = Camera::from_prop_and_state(context, NullProp {}, CameraState::default());
+        let mut viewer = Consumer::from_prop_and_state(context, NullProp {}, NullState {});
+        let mut recorder = Consumer::from_prop_and_state(context, NullProp {}, NullState {});
+        pipeline! {
+            timer.time_stamp -> camera.trigger;
+            camera.frame -> viewer.frame, recorder.frame;
+        }
+    });
+    pipeline
+        .run_with_deadline(Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_frame_pool_example().await;
+        })
+}
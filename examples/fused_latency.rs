@@ -0,0 +1,202 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Probe which travels through the chain of actors.
+#[derive(Clone, Debug)]
+pub struct Probe {
+    /// Time the probe entered the chain.
+    pub sent: Instant,
+}
+
+/// Outbound hub of the stage actor.
+#[actor_outputs]
+pub struct StageOutbound {
+    /// Forwarded probe.
+    pub probe: OutboundChannel<Probe>,
+}
+
+/// Inbound message of the stage actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    StageInbound,
+    {
+        NullProp,
+        NullState,
+        StageOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum StageMessage {
+    /// Tick of the source, upon which a new probe enters the chain.
+    Tick(Stamped<()>),
+    /// Probe to be forwarded.
+    Probe(Probe),
+}
+
+impl HasOnMessage for StageMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            StageMessage::Tick(_) => outbound.probe.send(Probe {
+                sent: Instant::now(),
+            }),
+            StageMessage::Probe(probe) => outbound.probe.send(probe),
+        }
+    }
+}
+
+impl IsInboundMessageNew<Stamped<()>> for StageMessage {
+    fn new(_inbound_name: String, msg: Stamped<()>) -> Self {
+        StageMessage::Tick(msg)
+    }
+}
+
+impl IsInboundMessageNew<Probe> for StageMessage {
+    fn new(_inbound_name: String, msg: Probe) -> Self {
+        StageMessage::Probe(msg)
+    }
+}
+
+/// Stage of the chain, e.g. sensor driver, filter or controller.
+#[actor(StageMessage, NullInRequestMessage)]
+type Stage =
+    Actor<NullProp, StageInbound, NullInRequests, NullState, StageOutbound, NullOutRequests>;
+
+/// Latencies measured at the end of the chain.
+#[derive(Clone, Debug, Default)]
+pub struct LatencySinkProp {
+    /// Measured latencies.
+    pub latencies: Arc<Mutex<Vec<Duration>>>,
+}
+
+/// Inbound message of the latency sink actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    LatencySinkInbound,
+    {
+        LatencySinkProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum LatencySinkMessage {
+    /// Probe at the end of the chain.
+    Probe(Probe),
+}
+
+impl HasOnMessage for LatencySinkMessage {
+    fn on_message(
+        self,
+        prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            LatencySinkMessage::Probe(probe) => {
+                prop.latencies.lock().unwrap().push(probe.sent.elapsed());
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Probe> for LatencySinkMessage {
+    fn new(_inbound_name: String, msg: Probe) -> Self {
+        LatencySinkMessage::Probe(msg)
+    }
+}
+
+/// End of the chain, which records the latencies.
+#[actor(LatencySinkMessage, NullInRequestMessage)]
+type LatencySink = Actor<
+    LatencySinkProp,
+    LatencySinkInbound,
+    NullInRequests,
+    NullState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+/// Runs a chain of stages, either with regular or fused connections, and returns the latencies.
+async fn run_chain(fused: bool) -> Vec<Duration> {
+    const NUM_STAGES: usize = 3;
+    let prop = LatencySinkProp::default();
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.002);
+        let mut stages: Vec<_> = (0..NUM_STAGES)
+            .map(|_| Stage::from_prop_and_state(context, NullProp {}, NullState {}))
+            .collect();
+        let mut sink = LatencySink::from_prop_and_state(context, prop.clone(), NullState {});
+        timer
+            .outbound
+            .tick
+            .connect(context, &mut stages[0].inbound.tick);
+        for i in 1..NUM_STAGES {
+            let (upstream, downstream) = stages.split_at_mut(i);
+            let from = &mut upstream[i - 1].outbound.probe;
+            let to = &mut downstream[0].inbound.probe;
+            if fused {
+                context.enable_fused_dispatch(&downstream[0].actor_name);
+                from.connect_fused(context, to);
+            } else {
+                from.connect(context, to);
+            }
+        }
+        let last = &mut stages[NUM_STAGES - 1].outbound.probe;
+        if fused {
+            context.enable_fused_dispatch(&sink.actor_name);
+            last.connect_fused(context, &mut sink.inbound.probe);
+        } else {
+            last.connect(context, &mut sink.inbound.probe);
+        }
+    });
+    pipeline
+        .run_with_deadline(Duration::from_secs(2))
+        .await
+        .unwrap();
+    let mut latencies = prop.latencies.lock().unwrap().clone();
+    latencies.sort();
+    latencies
+}
+
+fn report(label: &str, latencies: &[Duration]) {
+    let percentile = |p: f64| latencies[((latencies.len() - 1) as f64 * p) as usize];
+    println!(
+        "{:>8}: {} probes, median {:?}, p99 {:?}, max {:?}",
+        label,
+        latencies.len(),
+        percentile(0.5),
+        percentile(0.99),
+        latencies[latencies.len() - 1]
+    );
+}
+
+/// Run the benchmark which compares the latency of regular and fused connections
+pub async fn run_fused_latency_example() {
+    let regular = run_chain(false).await;
+    let fused = run_chain(true).await;
+    report("regular", &regular);
+    report("fused", &fused);
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_fused_latency_example().await;
+        })
+}
@@ -0,0 +1,69 @@
+use hollywood::example_actors::one_dim_robot::filter::FilterInboundMessage;
+use hollywood::example_actors::one_dim_robot::filter::FilterState;
+use hollywood::example_actors::one_dim_robot::model::WorldModel;
+use hollywood::example_actors::one_dim_robot::Stamped;
+use hollywood::prelude::*;
+use hollywood::testing::collect;
+use hollywood::testing::fuzz_actor;
+use proptest::prelude::*;
+
+/// Random sequence of filter inputs, with increasing time stamps.
+///
+/// The ranges are those of a robot within the world, i.e. west of [WorldModel::BOUNDARY_EAST],
+/// since the range model is not defined beyond.
+fn filter_inputs() -> impl Strategy<Value = Vec<FilterInboundMessage>> {
+    let step = prop_oneof![
+        (0.0..1.0).prop_map(|velocity| (false, velocity)),
+        (20.0..WorldModel::BOUNDARY_EAST).prop_map(|range| (true, range)),
+    ];
+    proptest::collection::vec((0.01..0.5, step), 1..100).prop_map(|steps| {
+        let mut time = 0.0;
+        steps
+            .into_iter()
+            .enumerate()
+            .map(|(seq, (dt, (is_range, value)))| {
+                time += dt;
+                let msg = Stamped::new(Timestamp::monotonic(time), seq as u64, value);
+                if is_range {
+                    FilterInboundMessage::NoisyRange(msg)
+                } else {
+                    FilterInboundMessage::NoisyVelocity(msg)
+                }
+            })
+            .collect()
+    })
+}
+
+/// Fuzzes the Kalman filter of the one dimensional robot example
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    fuzz_actor::<FilterInboundMessage, _>(
+        NullProp {},
+        FilterState::default(),
+        filter_inputs(),
+        |context, outbound| {
+            (
+                collect(context, &mut outbound.predicted_state),
+                collect(context, &mut outbound.updated_state),
+            )
+        },
+        |state, (predicted, updated)| {
+            let covariance = &state.pos_vel_acc.covariance;
+            for i in 0..3 {
+                if !(covariance[(i, i)] > 0.0 && covariance[(i, i)].is_finite()) {
+                    return Err(format!("covariance is not positive: {}", covariance));
+                }
+            }
+            if !state.pos_vel_acc.mean.iter().all(|v| v.is_finite()) {
+                return Err(format!("mean is not finite: {}", state.pos_vel_acc.mean));
+            }
+            let num_published = predicted.take().len() + updated.take().len();
+            if num_published != 1 {
+                return Err(format!("{} states published instead of one", num_published));
+            }
+            Ok(())
+        },
+    );
+    println!("filter invariants hold");
+}
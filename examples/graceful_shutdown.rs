@@ -0,0 +1,119 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Properties of the log writer actor.
+#[derive(Clone, Debug, Default)]
+pub struct LogWriterProp {
+    /// Number of written ticks.
+    pub num_written: Arc<AtomicU64>,
+}
+
+/// Inbound message of the log writer actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    LogWriterInbound,
+    {
+        LogWriterProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum LogWriterMessage {
+    /// Tick to be written.
+    Tick(Stamped<()>),
+}
+
+impl HasOnMessage for LogWriterMessage {
+    fn on_message(
+        self,
+        prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            LogWriterMessage::Tick(_) => {
+                // Simulates writing to a slow disk, which is five times slower than the source.
+                std::thread::sleep(Duration::from_millis(5));
+                prop.num_written.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Stamped<()>> for LogWriterMessage {
+    fn new(_inbound_name: String, msg: Stamped<()>) -> Self {
+        LogWriterMessage::Tick(msg)
+    }
+}
+
+/// Log writer which cannot keep up with its source.
+#[actor(LogWriterMessage, NullInRequestMessage)]
+type LogWriter = Actor<
+    LogWriterProp,
+    LogWriterInbound,
+    NullInRequests,
+    NullState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+async fn run_with_shutdown(graceful: bool) {
+    let prop = LogWriterProp::default();
+    let mut ticks = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.001);
+        let mut writer =
+            LogWriter::from_prop_and_state(context, prop.clone(), NullState::default());
+        timer
+            .outbound
+            .tick
+            .connect(context, &mut writer.inbound.tick);
+        ticks = Some(timer.outbound.tick.subscriber(context));
+    });
+    let running = pipeline.spawn();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    if graceful {
+        running
+            .shutdown_gracefully(Duration::from_secs(10))
+            .await
+            .unwrap();
+    } else {
+        running.cancel();
+        running.join().await.unwrap();
+    }
+    let mut ticks = ticks.unwrap();
+    let mut num_sent = 0;
+    while ticks.try_recv().is_some() {
+        num_sent += 1;
+    }
+    println!(
+        "{}: {} ticks sent, {} written",
+        if graceful { "graceful" } else { "immediate" },
+        num_sent,
+        prop.num_written.load(Ordering::Relaxed)
+    );
+}
+
+/// Run the example which compares an immediate and a graceful shutdown of a slow log writer
+pub async fn run_graceful_shutdown_example() {
+    run_with_shutdown(false).await;
+    run_with_shutdown(true).await;
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_graceful_shutdown_example().await;
+        })
+}
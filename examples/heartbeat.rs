@@ -0,0 +1,42 @@
+use hollywood::actors::BackgroundActor;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the heartbeat example
+pub async fn run_heartbeat_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        // Notify the systemd watchdog if enabled (e.g. `WatchdogSec=2` in the service unit),
+        // otherwise print the heartbeat.
+        context.set_heartbeat(Heartbeat::systemd_watchdog_from_env().unwrap_or_else(|| {
+            Heartbeat::from_callback(Duration::from_millis(200), || println!("heartbeat"))
+        }));
+
+        // An actor which fails after one second, upon which the heartbeat stops.
+        BackgroundActor::from_named_task(context, "Flaky", |mut kill| async move {
+            tokio::select! {
+                _ = kill.changed() => {}
+                _ = tokio::time::sleep(Duration::from_secs(1)) => panic!("flaky actor failed"),
+            }
+        });
+    });
+
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    cancel_requester.send(CancelRequest).unwrap();
+    if let Err(err) = handle.await.unwrap() {
+        println!("{}", err);
+    }
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_heartbeat_example().await;
+        })
+}
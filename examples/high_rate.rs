@@ -0,0 +1,47 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+
+/// Run the high-rate example
+pub async fn run_high_rate_example() {
+    let mut ticks = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        // 2 kHz, which is below the millisecond resolution of the default interval timing.
+        let mut timer = Periodic::new_high_resolution(context, 0.0005, 0.0002);
+        ticks = Some(timer.outbound.tick.subscriber(context));
+    });
+
+    pipeline.print_flow_graph();
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+
+    let mut ticks = ticks.unwrap();
+    let first = ticks.recv().await.unwrap();
+    let start = std::time::Instant::now();
+    let mut last = first.clone();
+    for _ in 0..2000 {
+        last = ticks.recv().await.unwrap();
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    println!(
+        "{} ticks in {:.3}s, i.e. {:.0} Hz; tick time advanced by {:.3}s",
+        last.seq - first.seq,
+        elapsed,
+        (last.seq - first.seq) as f64 / elapsed,
+        last.time.seconds_since(&first.time).unwrap(),
+    );
+
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_high_rate_example().await;
+        })
+}
@@ -0,0 +1,65 @@
+use hollywood::actors::FaultyActor;
+use hollywood::actors::FaultyProp;
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the load shedding example
+///
+/// A timer produces messages faster than the slow consumer can process them. The load shedding
+/// policy keeps the backlog (and hence the latency) of the consumer bounded.
+pub async fn run_load_shedding_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.001);
+        let mut slow = FaultyActor::<f64>::from_prop_and_state_named(
+            context,
+            "slow_consumer",
+            FaultyProp {
+                max_delay: Some(Duration::from_millis(10)),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        // The consumer blocks its thread, hence it is run on a dedicated thread.
+        context.assign_actor_group(&slow.actor_name, "slow");
+        context.set_load_shedding(
+            &slow.actor_name,
+            LoadSheddingPolicy {
+                upstream_decimation: 2,
+                ..LoadSheddingPolicy::drop_oldest(20)
+            },
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut slow.inbound.input);
+    });
+
+    pipeline.print_flow_graph();
+    let pipeline = pipeline
+        .run_with_deadline(Duration::from_secs(3))
+        .await
+        .unwrap();
+    for (actor_name, stats) in pipeline.load_shedding_stats() {
+        println!(
+            "{}: {} overload events, {} dropped, {} decimated, peak backlog: {}",
+            actor_name,
+            stats.num_events,
+            stats.num_dropped,
+            stats.num_decimated,
+            stats.peak_backlog
+        );
+    }
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_load_shedding_example().await;
+        })
+}
@@ -0,0 +1,62 @@
+use hollywood::actors::Periodic;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+
+/// Number of moving average actors in the pipeline.
+const NUM_ACTORS: usize = 500;
+
+/// Run a pipeline with hundreds of actors and make sure all of them receive the kill signal.
+pub async fn run_many_actors_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        context.set_yield_interval(16);
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        for _ in 0..NUM_ACTORS {
+            let mut moving_average = MovingAverage::from_prop_and_state(
+                context,
+                MovingAverageProp {
+                    alpha: 0.3,
+                    timeout: 2.0,
+                },
+                MovingAverageState {
+                    moving_average: 0.0,
+                },
+            );
+            timer
+                .outbound
+                .time_stamp
+                .connect(context, &mut moving_average.inbound.value);
+            context.register_cancel_requester(&mut moving_average.outbound.cancel_request);
+        }
+    });
+
+    let start = std::time::Instant::now();
+    let pipeline = pipeline.run().await.unwrap();
+    // Each actor is handed back to the pipeline once its task completed.
+    assert_eq!(pipeline.manifest().actors.len(), NUM_ACTORS + 1);
+    println!(
+        "All {} actors shut down after {:.2}s",
+        NUM_ACTORS + 1,
+        start.elapsed().as_secs_f64()
+    );
+    let max_batch_size = pipeline
+        .batch_stats()
+        .iter()
+        .map(|(_, stats)| stats.max_batch_size)
+        .max()
+        .unwrap_or_default();
+    println!("Largest message batch: {}", max_batch_size);
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_many_actors_example().await;
+        })
+}
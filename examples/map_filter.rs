@@ -0,0 +1,46 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Filter;
+use hollywood::actors::Map;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+
+/// Run the example which filters and formats timestamps without defining a custom actor
+pub async fn run_map_filter_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        // Keeps every other tick only.
+        let mut every_other =
+            Filter::<f64>::from_predicate(context, |t| (t * 10.0).round() as i64 % 2 == 0);
+        let mut format = Map::<f64, String>::from_fn(context, |t| format!("{:.1}s", t));
+        let mut printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "tick".to_string(),
+            },
+            NullState {},
+        );
+        pipeline! {
+            timer.time_stamp -> every_other.value;
+            every_other.value -> format.value;
+            format.value -> printer.printable;
+        }
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_map_filter_example().await;
+        })
+}
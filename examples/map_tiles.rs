@@ -0,0 +1,230 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::collections::HashMap;
+
+/// Map tile at the given grid position.
+#[derive(Clone, Debug, Default)]
+pub struct Tile {
+    /// Grid position of the tile.
+    pub position: (i64, i64),
+}
+
+/// Inbound message of the tile server actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    TileServerInbound,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        TileServerInRequestMessage
+    })]
+pub enum TileServerMessage {
+    /// Tick time in seconds, which is ignored.
+    Tick(f64),
+}
+
+impl HasOnMessage for TileServerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+    }
+}
+
+impl IsInboundMessageNew<f64> for TileServerMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        TileServerMessage::Tick(msg)
+    }
+}
+
+/// In-request message of the tile server actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    TileServerInRequest,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        TileServerMessage
+    }
+)]
+pub enum TileServerInRequestMessage {
+    /// Query of the tiles around the given grid position, replied with a stream of tiles.
+    TilesAround(RequestWithReplyStream<(i64, i64), Tile>),
+}
+
+impl HasOnRequestMessage for TileServerInRequestMessage {
+    fn on_request(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            TileServerInRequestMessage::TilesAround(request) => {
+                let (x, y) = request.request;
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        if !request.send(Tile {
+                            position: (x + dx, y + dy),
+                        }) {
+                            // The requester is gone.
+                            return;
+                        }
+                    }
+                }
+                request.finish();
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyStream<(i64, i64), Tile>>
+    for TileServerInRequestMessage
+{
+    fn new(_inbound_name: String, request: RequestWithReplyStream<(i64, i64), Tile>) -> Self {
+        TileServerInRequestMessage::TilesAround(request)
+    }
+}
+
+/// Tile server actor.
+#[actor(TileServerMessage, TileServerInRequestMessage)]
+type TileServer = Actor<
+    NullProp,
+    TileServerInbound,
+    TileServerInRequest,
+    NullState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+/// State of the navigator actor.
+#[derive(Clone, Debug, Default)]
+pub struct NavigatorState {
+    /// Current grid position.
+    pub position: (i64, i64),
+    /// Tiles received so far, by request id.
+    pub tiles: HashMap<u64, Vec<Tile>>,
+}
+
+/// Inbound message of the navigator actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    NavigatorInbound,
+    {
+        NullProp,
+        NavigatorState,
+        NullOutbound,
+        NavigatorOutRequest,
+        NullInRequestMessage
+    })]
+pub enum NavigatorMessage {
+    /// Tick time in seconds.
+    Tick(f64),
+    /// Tiles streamed by the tile server.
+    Tiles(ReplyStreamMessage<Tile>),
+}
+
+impl HasOnMessage for NavigatorMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        request: &Self::OutRequestHub,
+    ) {
+        match self {
+            NavigatorMessage::Tick(_) => {
+                state.position.0 += 1;
+                let request_id = request.tiles_around.send_request(state.position);
+                state.tiles.insert(request_id, vec![]);
+            }
+            NavigatorMessage::Tiles(ReplyStreamMessage::Item { request_id, item }) => {
+                state.tiles.get_mut(&request_id).unwrap().push(item);
+            }
+            NavigatorMessage::Tiles(ReplyStreamMessage::Complete { request_id }) => {
+                let tiles = state.tiles.remove(&request_id).unwrap();
+                println!(
+                    "request #{}: {} tiles from {:?} to {:?}",
+                    request_id,
+                    tiles.len(),
+                    tiles.first().map(|tile| tile.position),
+                    tiles.last().map(|tile| tile.position)
+                );
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for NavigatorMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        NavigatorMessage::Tick(msg)
+    }
+}
+
+impl IsInboundMessageNew<ReplyStreamMessage<Tile>> for NavigatorMessage {
+    fn new(_inbound_name: String, msg: ReplyStreamMessage<Tile>) -> Self {
+        NavigatorMessage::Tiles(msg)
+    }
+}
+
+/// Out-request hub of the navigator actor.
+#[actor_out_requests]
+pub struct NavigatorOutRequest {
+    /// Queries of the tiles around a grid position.
+    pub tiles_around: OutRequestStreamChannel<(i64, i64), Tile, NavigatorMessage>,
+}
+
+/// Navigator actor, which moves one tile per tick and queries the tiles around it.
+#[actor(NavigatorMessage, NullInRequestMessage)]
+type Navigator = Actor<
+    NullProp,
+    NavigatorInbound,
+    NullInRequests,
+    NavigatorState,
+    NullOutbound,
+    NavigatorOutRequest,
+>;
+
+/// Run the example which streams map tiles in reply to requests
+pub async fn run_map_tiles_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut navigator =
+            Navigator::from_prop_and_state(context, NullProp::default(), NavigatorState::default());
+        let mut tile_server =
+            TileServer::from_prop_and_state(context, NullProp::default(), NullState::default());
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut navigator.inbound.tick);
+        navigator
+            .out_requests
+            .tiles_around
+            .connect(context, &mut tile_server.in_requests.tiles_around);
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_map_tiles_example().await;
+        })
+}
@@ -0,0 +1,111 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::ChannelSchema;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::actors::Recorder;
+use hollywood::actors::Replay;
+use hollywood::prelude::*;
+use std::time::Duration;
+use tokio_util::bytes::BufMut;
+use tokio_util::bytes::BytesMut;
+
+/// Codec of timestamps, as JSON objects such as `{"seconds":0.1}`.
+#[derive(Clone, Debug, Default)]
+pub struct JsonSecondsCodec;
+
+impl JsonSecondsCodec {
+    /// JSON schema of the encoded timestamps.
+    pub fn schema() -> ChannelSchema {
+        ChannelSchema {
+            name: "Seconds".to_owned(),
+            encoding: "jsonschema".to_owned(),
+            data: br#"{"type":"object","properties":{"seconds":{"type":"number"}}}"#.to_vec(),
+            message_encoding: "json".to_owned(),
+        }
+    }
+}
+
+impl tokio_util::codec::Encoder<f64> for JsonSecondsCodec {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, item: f64, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.put_slice(format!("{{\"seconds\":{}}}", item).as_bytes());
+        Ok(())
+    }
+}
+
+impl tokio_util::codec::Decoder for JsonSecondsCodec {
+    type Item = f64;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let json = String::from_utf8_lossy(&src.split()).to_string();
+        json.trim_start_matches("{\"seconds\":")
+            .trim_end_matches('}')
+            .parse()
+            .map(Some)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Run the example which records the timestamps of a periodic actor to an MCAP log, which can be
+/// opened in Foxglove, and replays it
+pub async fn run_mcap_log_example() {
+    let path = std::env::temp_dir().join("hollywood_mcap_log.mcap");
+
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut recorder = Recorder::create_mcap(context, &path).unwrap();
+        recorder.tap_with_schema(
+            context,
+            &mut timer.outbound.time_stamp,
+            "/timer/seconds",
+            JsonSecondsCodec,
+            JsonSecondsCodec::schema(),
+        );
+    });
+    pipeline
+        .run_with_deadline(Duration::from_millis(550))
+        .await
+        .unwrap();
+    println!("recorded {}", path.display());
+
+    let pipeline = Hollywood::configure(&mut |context| {
+        let printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "replay".to_string(),
+            },
+            NullState::default(),
+        );
+        let mut replay = Replay::open(&path).unwrap().cancel_when_done();
+        println!(
+            "replaying {} messages of {:?} with schema {:?}",
+            replay.num_messages(),
+            replay.channel_names(),
+            replay
+                .channel_schema("/timer/seconds")
+                .map(|schema| &schema.name)
+        );
+        replay.connect(
+            "/timer/seconds",
+            &printer.inbound.printable,
+            JsonSecondsCodec,
+        );
+        replay.spawn(context);
+    });
+    pipeline.run().await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_mcap_log_example().await;
+        })
+}
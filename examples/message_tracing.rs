@@ -0,0 +1,105 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use tracing::info;
+
+/// Properties of the stage actor.
+#[derive(Clone, Debug, Default)]
+pub struct StageProp {
+    /// Factor the values are scaled with.
+    pub factor: f64,
+}
+
+/// Inbound message of the stage actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    StageInbound,
+    {
+        StageProp,
+        NullState,
+        StageOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum StageMessage {
+    /// Value to be scaled.
+    Value(f64),
+}
+
+impl HasOnMessage for StageMessage {
+    fn on_message(
+        self,
+        prop: &Self::Prop,
+        _state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            StageMessage::Value(value) => {
+                // Logged within the actor and message spans, which carry the correlation id.
+                info!("scaling {:.2}", value);
+                outbound.value.send(value * prop.factor);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for StageMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        StageMessage::Value(msg)
+    }
+}
+
+/// Outbound hub of the stage actor.
+#[actor_outputs]
+pub struct StageOutbound {
+    /// Scaled values.
+    pub value: OutboundChannel<f64>,
+}
+
+/// Actor which scales its inbound values.
+#[actor(StageMessage, NullInRequestMessage)]
+type Stage =
+    Actor<StageProp, StageInbound, NullInRequests, NullState, StageOutbound, NullOutRequests>;
+
+/// Run the example which follows each tick through a chain of actors
+pub async fn run_message_tracing_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        // Must be called before the actors are created.
+        context.enable_message_tracing();
+
+        let mut timer = Periodic::new_with_period(context, 0.25);
+        let mut first =
+            Stage::from_prop_and_state(context, StageProp { factor: 10.0 }, NullState {});
+        let mut second =
+            Stage::from_prop_and_state(context, StageProp { factor: 0.5 }, NullState {});
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut first.inbound.value);
+        first
+            .outbound
+            .value
+            .connect(context, &mut second.inbound.value);
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    // Each log line of the stages is prefixed with its spans, e.g.
+    // `actor{name="Stage_1"}:message{channel="Value" correlation_id=3}: scaling 7.50`.
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_message_tracing_example().await;
+        })
+}
@@ -0,0 +1,64 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the example which counts the messages of a small pipeline and dumps the metrics
+pub async fn run_metrics_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        // Must be called before the actors are connected.
+        context.set_metrics_dump(MetricsDump::console(Duration::from_millis(500)));
+
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut moving_average = MovingAverage::from_prop_and_state(
+            context,
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: 5.0,
+            },
+            MovingAverageState {
+                moving_average: 0.0,
+            },
+        );
+        let mut average_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "average".to_string(),
+            },
+            NullState {},
+        );
+        pipeline! {
+            timer.time_stamp -> moving_average.value;
+            moving_average.average -> average_printer.printable;
+        }
+    });
+
+    let pipeline = pipeline
+        .run_with_deadline(Duration::from_secs(2))
+        .await
+        .unwrap();
+
+    let metrics = pipeline.metrics();
+    println!(
+        "{} averages sent, {} printed",
+        metrics.num_sent("MovingAverage_0", "average"),
+        metrics.num_received("Printer(average)_0", "Printable")
+    );
+    println!("{}", metrics.to_prometheus());
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_metrics_example().await;
+        })
+}
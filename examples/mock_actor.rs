@@ -0,0 +1,36 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+
+/// Run the mock actor example
+pub fn run_mock_actor_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        // Run with HOLLYWOOD_MOCK=timer to substitute the timer by a faster one.
+        let mut timer = context.actor_or_mock(
+            "timer",
+            |context| Periodic::new_with_period(context, 1.0),
+            |context| Periodic::new_with_period(context, 0.1),
+        );
+        let mut time_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "time".to_string(),
+            },
+            NullState::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut time_printer.inbound.printable);
+    });
+
+    pipeline.print_flow_graph();
+    pipeline.run_blocking().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    run_mock_actor_example();
+}
@@ -34,14 +34,13 @@ pub async fn run_moving_average_example() {
             },
             NullState {},
         );
-        timer
-            .outbound
-            .time_stamp
-            .connect(context, &mut moving_average.inbound.value);
-        timer
-            .outbound
-            .time_stamp
-            .connect(context, &mut time_printer.inbound.printable);
+        timer.outbound.time_stamp.tee(
+            context,
+            &mut [
+                &mut moving_average.inbound.value,
+                &mut time_printer.inbound.printable,
+            ],
+        );
 
         moving_average
             .outbound
@@ -52,7 +51,7 @@ pub async fn run_moving_average_example() {
     });
 
     pipeline.print_flow_graph();
-    pipeline.run().await;
+    pipeline.run().await.unwrap();
 }
 
 fn main() {
@@ -17,10 +17,17 @@ pub async fn run_tick_print_example() {
             .outbound
             .nudge
             .connect(context, &mut nudge_printer.inbound.printable);
+
+        // Messages sent during configuration are buffered and delivered in order once the nudge
+        // actor starts.
+        nudge
+            .outbound
+            .nudge
+            .send("sent during configuration".to_owned());
     });
 
     pipeline.print_flow_graph();
-    pipeline.run().await;
+    pipeline.run().await.unwrap();
 }
 
 fn main() {
@@ -4,6 +4,8 @@ use hollywood::actors::zip::ZipPair;
 use hollywood::actors::Periodic;
 use hollywood::actors::Printer;
 use hollywood::actors::Zip3;
+use hollywood::core::invariant::strictly_increasing;
+use hollywood::core::invariant::within_range;
 use hollywood::example_actors::one_dim_robot::draw::DrawState;
 use hollywood::example_actors::one_dim_robot::filter::FilterState;
 use hollywood::example_actors::one_dim_robot::DrawActor;
@@ -11,16 +13,21 @@ use hollywood::example_actors::one_dim_robot::Filter;
 use hollywood::example_actors::one_dim_robot::NamedFilterState;
 use hollywood::example_actors::one_dim_robot::Robot;
 use hollywood::example_actors::one_dim_robot::Sim;
+use hollywood::example_actors::one_dim_robot::SimProp;
 use hollywood::example_actors::one_dim_robot::SimState;
 use hollywood::example_actors::one_dim_robot::Stamped;
 use hollywood::prelude::*;
 
 async fn run_robot_example() {
+    let mut filter_client = None;
     let pipeline = Hollywood::configure(&mut |context| {
         let mut timer = Periodic::new_with_period(context, 0.1);
         let mut sim = Sim::from_prop_and_state(
             context,
-            NullProp {},
+            SimProp {
+                seed: Some(42),
+                ..Default::default()
+            },
             SimState {
                 shutdown_time: 15.0,
                 time: 0.0,
@@ -29,9 +36,11 @@ async fn run_robot_example() {
                     position: -2.0,
                     velocity: 0.4,
                 },
+                noise: None,
             },
         );
         let mut filter = Filter::from_prop_and_state(context, NullProp {}, FilterState::default());
+        filter_client = Some(filter.in_requests.client());
         let mut filter_state_printer = Printer::<NamedFilterState>::from_prop_and_state(
             context,
             PrinterProp {
@@ -62,20 +71,30 @@ async fn run_robot_example() {
         sim.outbound
             .noisy_range
             .connect(context, &mut filter.inbound.noisy_range);
+        // Data contracts of the simulated measurements, checked in debug builds.
+        sim.outbound.noisy_range.add_invariant(
+            context,
+            "time strictly increasing",
+            strictly_increasing(|r: &Stamped<f64>| r.time.seconds),
+        );
+        sim.outbound.noisy_velocity.add_invariant(
+            context,
+            "velocity within limits",
+            within_range(|v: &Stamped<f64>| v.value, -10.0, 10.0),
+        );
+        // The prediction must precede the update for the same time step.
+        context.set_channel_ordering(
+            &filter.actor_name,
+            ChannelOrdering::new("NoisyVelocity", "NoisyRange"),
+        );
         sim.outbound.true_robot.connect_with_adapter(
             context,
-            |x| ZipPair {
-                key: x.seq,
-                value: x,
-            },
+            ZipPair::from,
             &mut zip.inbound.item0,
         );
         sim.outbound.true_range.connect_with_adapter(
             context,
-            |x| ZipPair {
-                key: x.seq,
-                value: x,
-            },
+            ZipPair::from,
             &mut zip.inbound.item1,
         );
         sim.outbound
@@ -85,6 +104,8 @@ async fn run_robot_example() {
         sim.out_requests
             .ping_pong
             .connect(context, &mut filter.in_requests.ping_pong_request);
+        // At most one ping in flight, further pings wait for the pong.
+        sim.out_requests.ping_pong.set_max_in_flight(1);
         context.register_cancel_requester(&mut sim.outbound.cancel_request);
 
         filter
@@ -107,7 +128,18 @@ async fn run_robot_example() {
 
     pipeline.print_flow_graph();
 
-    let _pipeline = pipeline.run().await;
+    // Query the filter from outside the pipeline, e.g. as a server would do.
+    let filter_client = filter_client.unwrap();
+    tokio::spawn(async move {
+        let mut ping = 0.0;
+        while let Ok(reply) = filter_client.ping_pong_request(ping).await {
+            println!("external ping-pong: {:?}", reply);
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            ping += 1.0;
+        }
+    });
+
+    let _pipeline = pipeline.run().await.unwrap();
 }
 
 fn main() {
@@ -0,0 +1,59 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::example_actors::moving_average::MovingAverage;
+use hollywood::example_actors::moving_average::MovingAverageProp;
+use hollywood::example_actors::moving_average::MovingAverageState;
+use hollywood::prelude::*;
+
+/// Run the moving average example, with its channels wired by the pipeline! macro
+pub async fn run_pipeline_macro_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 1.0);
+        let mut moving_average = MovingAverage::from_prop_and_state(
+            context,
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: 5.0,
+            },
+            MovingAverageState {
+                moving_average: 0.0,
+            },
+        );
+        let mut time_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "time".to_string(),
+            },
+            NullState {},
+        );
+        let mut average_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "average".to_string(),
+            },
+            NullState {},
+        );
+        pipeline! {
+            timer.time_stamp -> moving_average.value, time_printer.printable;
+            moving_average.average -> average_printer.printable;
+        }
+
+        context.register_cancel_requester(&mut moving_average.outbound.cancel_request);
+    });
+
+    pipeline.print_flow_graph();
+    pipeline.run().await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_pipeline_macro_example().await;
+        })
+}
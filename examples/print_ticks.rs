@@ -2,9 +2,10 @@ use hollywood::actors::printer::PrinterProp;
 use hollywood::actors::Periodic;
 use hollywood::actors::Printer;
 use hollywood::prelude::*;
+use hollywood::RuntimeFlavor;
 
 /// Run the tick print example
-pub async fn run_tick_print_example() {
+pub fn run_tick_print_example() {
     let pipeline = Hollywood::configure(&mut |context| {
         let mut timer = Periodic::new_with_period(context, 1.0);
         let mut time_printer = Printer::<f64>::from_prop_and_state(
@@ -22,17 +23,13 @@ pub async fn run_tick_print_example() {
     });
 
     pipeline.print_flow_graph();
-    pipeline.run().await;
+    pipeline
+        .run_blocking_with(RuntimeFlavor::MultiThread)
+        .unwrap();
 }
 
 fn main() {
     tracing_subscriber::fmt::init();
 
-    tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(async {
-            run_tick_print_example().await;
-        })
+    run_tick_print_example();
 }
@@ -0,0 +1,101 @@
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// State of the motor actor.
+#[derive(Clone, Debug, Default)]
+pub struct MotorState {
+    /// Number of bulk samples processed so far.
+    pub num_samples: u64,
+    /// Whether the motor was stopped.
+    pub stopped: bool,
+}
+
+/// Inbound message of the motor actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    MotorInbound,
+    {
+        NullProp,
+        MotorState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum MotorMessage {
+    /// Bulk sensor sample.
+    Sample(Vec<f64>),
+    /// Emergency stop command, which must not wait behind the bulk samples.
+    #[priority]
+    Stop(()),
+}
+
+impl HasOnMessage for MotorMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            MotorMessage::Sample(_) => {
+                state.num_samples += 1;
+            }
+            MotorMessage::Stop(()) => {
+                println!("stop received after {} samples", state.num_samples);
+                state.stopped = true;
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Vec<f64>> for MotorMessage {
+    fn new(_inbound_name: String, msg: Vec<f64>) -> Self {
+        MotorMessage::Sample(msg)
+    }
+}
+
+impl IsInboundMessageNew<()> for MotorMessage {
+    fn new(_inbound_name: String, msg: ()) -> Self {
+        MotorMessage::Stop(msg)
+    }
+}
+
+/// Actor which processes bulk samples and stop commands.
+#[actor(MotorMessage, NullInRequestMessage)]
+type Motor =
+    Actor<NullProp, MotorInbound, NullInRequests, MotorState, NullOutbound, NullOutRequests>;
+
+/// Run the example which sends a stop command behind a large backlog of samples
+pub async fn run_priority_lane_example() {
+    let mut injectors = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let motor = Motor::from_prop_and_state(context, NullProp {}, MotorState::default());
+        injectors = Some((
+            motor.inbound.sample.injector(),
+            motor.inbound.stop.injector(),
+        ));
+    });
+    let (sample, stop) = injectors.unwrap();
+    // The messages are queued until the pipeline starts.
+    for _ in 0..10000 {
+        sample.send(vec![0.0; 64]).unwrap();
+    }
+    stop.send(()).unwrap();
+    pipeline
+        .run_with_deadline(Duration::from_millis(500))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_priority_lane_example().await;
+        })
+}
@@ -0,0 +1,122 @@
+use hollywood::actors::Periodic;
+use hollywood::actors::ProgressBar;
+use hollywood::prelude::*;
+
+/// Outbound hub of the batch job actor.
+#[actor_outputs]
+pub struct BatchJobOutbound {
+    /// Progress of the job.
+    pub progress: OutboundChannel<Progress>,
+    /// Cancel request, sent once the job is finished.
+    pub cancel_request: OutboundChannel<CancelRequest>,
+}
+
+/// State of the batch job actor.
+#[derive(Clone, Debug, Default)]
+pub struct BatchJobState {
+    /// Progress of the job.
+    pub tracker: ProgressTracker,
+}
+
+/// Inbound message of the batch job actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    BatchJobInbound,
+    {
+        NullProp,
+        BatchJobState,
+        BatchJobOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum BatchJobMessage {
+    /// Time stamp, upon which the next record is processed.
+    TimeStamp(f64),
+}
+
+impl HasOnMessage for BatchJobMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            BatchJobMessage::TimeStamp(_) => {
+                let progress = state.tracker.advance(1);
+                let finished = progress.is_finished();
+                outbound.progress.send(progress);
+                if finished {
+                    outbound.cancel_request.send(CancelRequest);
+                }
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for BatchJobMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        BatchJobMessage::TimeStamp(msg)
+    }
+}
+
+/// Batch job which processes one record per time stamp, standing in for a log replay.
+#[actor(BatchJobMessage, NullInRequestMessage)]
+type BatchJob = Actor<
+    NullProp,
+    BatchJobInbound,
+    NullInRequests,
+    BatchJobState,
+    BatchJobOutbound,
+    NullOutRequests,
+>;
+
+/// Run the progress example
+pub async fn run_progress_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.02);
+        let mut job = BatchJob::from_prop_and_state(
+            context,
+            NullProp {},
+            BatchJobState {
+                tracker: ProgressTracker::new(Some(200)),
+            },
+        );
+        let mut progress_bar =
+            ProgressBar::from_prop_and_state(context, Default::default(), Default::default());
+        progress_bar.subscribe(context);
+
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut job.inbound.time_stamp);
+        context.register_progress_reporter(&mut job.outbound.progress);
+        context.register_cancel_requester(&mut job.outbound.cancel_request);
+    });
+
+    pipeline.print_flow_graph();
+    let mut progress = pipeline.progress_watch();
+    tokio::spawn(async move {
+        while progress.changed().await.is_ok() {
+            let progress = progress.borrow_and_update().clone().unwrap();
+            if progress.done.is_multiple_of(50) {
+                println!("host application sees: {}", progress);
+            }
+        }
+    });
+    let pipeline = pipeline.run().await.unwrap();
+    println!("final progress: {}", pipeline.progress().unwrap());
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_progress_example().await;
+        })
+}
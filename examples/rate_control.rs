@@ -0,0 +1,127 @@
+use hollywood::actors::Periodic;
+use hollywood::actors::RateController;
+use hollywood::actors::RateControllerProp;
+use hollywood::prelude::*;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Outbound hub of the worker actor.
+#[actor_outputs]
+pub struct WorkerOutbound {
+    /// Lag behind the source in seconds.
+    pub latency: OutboundChannel<f64>,
+}
+
+/// State of the worker actor.
+#[derive(Clone, Debug, Default)]
+pub struct WorkerState {
+    /// Time until which the simulated worker is busy with the ticks received so far.
+    pub busy_until: Option<Instant>,
+}
+
+/// Inbound message of the worker actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    WorkerInbound,
+    {
+        NullProp,
+        WorkerState,
+        WorkerOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum WorkerMessage {
+    /// Tick of the source, upon which some expensive work is simulated.
+    Tick(Stamped<()>),
+}
+
+impl HasOnMessage for WorkerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            WorkerMessage::Tick(tick) => {
+                // Each tick takes 20ms of work, which is queued behind the previous ticks.
+                let now = Instant::now();
+                let start = state
+                    .busy_until
+                    .map_or(now, |busy_until| busy_until.max(now));
+                let busy_until = start + Duration::from_millis(20);
+                state.busy_until = Some(busy_until);
+                let latency = (busy_until - now).as_secs_f64();
+                if tick.seq % 20 == 0 {
+                    println!("tick #{}: latency {:.3}s", tick.seq, latency);
+                }
+                outbound.latency.send(latency);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Stamped<()>> for WorkerMessage {
+    fn new(_inbound_name: String, msg: Stamped<()>) -> Self {
+        WorkerMessage::Tick(msg)
+    }
+}
+
+/// Simulated worker, which takes 20ms per tick.
+#[actor(WorkerMessage, NullInRequestMessage)]
+type Worker =
+    Actor<NullProp, WorkerInbound, NullInRequests, WorkerState, WorkerOutbound, NullOutRequests>;
+
+/// Run the rate control example
+pub async fn run_rate_control_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        // The source is four times faster than the worker can keep up with.
+        let mut timer = Periodic::new_with_period(context, 0.005);
+        let mut worker = Worker::from_prop_and_state(context, NullProp {}, WorkerState::default());
+        let mut controller = RateController::from_prop_and_state(
+            context,
+            RateControllerProp {
+                initial_period: 0.005,
+                target_latency: 0.05,
+                settle_time: 0.2,
+                ..Default::default()
+            },
+            Default::default(),
+        );
+
+        timer
+            .outbound
+            .tick
+            .connect(context, &mut worker.inbound.tick);
+        worker
+            .outbound
+            .latency
+            .connect(context, &mut controller.inbound.latency);
+        controller
+            .out_requests
+            .set_period
+            .connect(context, &mut timer.in_requests.set_period);
+    });
+
+    pipeline.print_flow_graph();
+    // Render with e.g. `dot -Tsvg rate_control.dot -o rate_control.svg`.
+    println!("{}", pipeline.to_dot());
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+    tokio::time::sleep(Duration::from_secs(8)).await;
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_rate_control_example().await;
+        })
+}
@@ -0,0 +1,113 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::actors::Recorder;
+use hollywood::actors::Replay;
+use hollywood::actors::ReplayCommand;
+use hollywood::prelude::*;
+use std::time::Duration;
+use tokio_util::bytes::Buf;
+use tokio_util::bytes::BufMut;
+use tokio_util::bytes::BytesMut;
+
+/// Codec of timestamps, as little-endian f64.
+#[derive(Clone, Debug, Default)]
+pub struct F64Codec;
+
+impl tokio_util::codec::Encoder<f64> for F64Codec {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, item: f64, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.put_f64_le(item);
+        Ok(())
+    }
+}
+
+impl tokio_util::codec::Decoder for F64Codec {
+    type Item = f64;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < 8 {
+            return Ok(None);
+        }
+        Ok(Some(src.get_f64_le()))
+    }
+}
+
+/// Run the example which records the timestamps of a periodic actor, steps through the first
+/// ones and replays the rest twice as fast
+pub async fn run_record_replay_example() {
+    let path = std::env::temp_dir().join("hollywood_record_replay.hwrec");
+
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "live".to_string(),
+            },
+            NullState::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut printer.inbound.printable);
+        let mut recorder = Recorder::create(context, &path).unwrap();
+        recorder.tap(context, &mut timer.outbound.time_stamp, F64Codec);
+    });
+    pipeline
+        .run_with_deadline(Duration::from_millis(550))
+        .await
+        .unwrap();
+
+    let mut control = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "replay".to_string(),
+            },
+            NullState::default(),
+        );
+        let mut replay = Replay::open(&path)
+            .unwrap()
+            .start_paused()
+            .cancel_when_done();
+        println!(
+            "replaying {} messages of {:?}",
+            replay.num_messages(),
+            replay.channel_names()
+        );
+        replay.connect(
+            "Periodic_0.time_stamp",
+            &printer.inbound.printable,
+            F64Codec,
+        );
+        control = Some(replay.spawn(context).control.client());
+    });
+    let control = control.unwrap();
+    let running = pipeline.spawn();
+
+    // Step through the first two messages, then replay the rest twice as fast as recorded.
+    let status = control.request(ReplayCommand::Step(2)).await.unwrap();
+    println!("stepped: {:?}", status);
+    control.request(ReplayCommand::SetSpeed(2.0)).await.unwrap();
+    let status = control.request(ReplayCommand::Resume).await.unwrap();
+    println!("resumed: {:?}", status);
+
+    running.join().await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_record_replay_example().await;
+        })
+}
@@ -0,0 +1,58 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::actors::RemoteInbound;
+use hollywood::actors::RemoteOutbound;
+use hollywood::actors::RemoteSecurity;
+use hollywood::actors::RemoteTransport;
+use hollywood::prelude::*;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// Run the example which connects two pipelines through a bridge over the loopback interface,
+/// as if they were running in different processes. Both ends share a token, so that other
+/// senders are rejected.
+pub async fn run_remote_bridge_example(transport: RemoteTransport, addr: SocketAddr) {
+    let security = RemoteSecurity::with_token("not-so-secret");
+
+    let receiver = Hollywood::configure(&mut |context| {
+        let printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: format!("{:?}", transport),
+            },
+            NullState::default(),
+        );
+        let mut remote = RemoteInbound::bind(transport, addr).with_security(security.clone());
+        remote.connect("time_stamp", &printer.inbound.printable);
+        remote.spawn(context);
+    });
+
+    let sender = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut remote = RemoteOutbound::create_secure(context, transport, addr, security.clone());
+        remote.connect(context, &mut timer.outbound.time_stamp, "time_stamp");
+    });
+
+    let (received, sent) = tokio::join!(
+        receiver.run_with_deadline(Duration::from_millis(1200)),
+        sender.run_with_deadline(Duration::from_millis(1000)),
+    );
+    received.unwrap();
+    sent.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_remote_bridge_example(RemoteTransport::Tcp, "127.0.0.1:47301".parse().unwrap())
+                .await;
+            run_remote_bridge_example(RemoteTransport::Udp, "127.0.0.1:47302".parse().unwrap())
+                .await;
+        })
+}
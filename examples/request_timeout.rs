@@ -0,0 +1,226 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// State of the flaky echo actor.
+#[derive(Clone, Debug, Default)]
+pub struct FlakyEchoState {
+    /// Number of requests received.
+    pub num_requests: u64,
+    /// Requests which are stuck, and only replied to on the next tick.
+    pub stuck: Vec<(f64, DeferredReply<f64>)>,
+}
+
+/// Inbound message of the flaky echo actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    FlakyEchoInbound,
+    {
+        NullProp,
+        FlakyEchoState,
+        NullOutbound,
+        NullOutRequests,
+        FlakyEchoInRequestMessage
+    })]
+pub enum FlakyEchoMessage {
+    /// Tick time in seconds.
+    Tick(f64),
+}
+
+impl HasOnMessage for FlakyEchoMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            FlakyEchoMessage::Tick(_) => {
+                // Replies to stuck requests come too late.
+                for (value, reply) in state.stuck.drain(..) {
+                    reply.reply(value);
+                }
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for FlakyEchoMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        FlakyEchoMessage::Tick(msg)
+    }
+}
+
+/// In-request message of the flaky echo actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    FlakyEchoInRequest,
+    {
+        NullProp,
+        FlakyEchoState,
+        NullOutbound,
+        NullOutRequests,
+        FlakyEchoMessage
+    }
+)]
+pub enum FlakyEchoInRequestMessage {
+    /// Request to echo the given value.
+    Echo(RequestWithReplyChannel<f64, f64>),
+}
+
+impl HasOnRequestMessage for FlakyEchoInRequestMessage {
+    fn on_request(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            FlakyEchoInRequestMessage::Echo(request) => {
+                state.num_requests += 1;
+                if state.num_requests.is_multiple_of(3) {
+                    let (value, reply) = request.defer();
+                    state.stuck.push((value, reply));
+                } else {
+                    request.reply_from_request(|value| value);
+                }
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<f64, f64>> for FlakyEchoInRequestMessage {
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<f64, f64>) -> Self {
+        FlakyEchoInRequestMessage::Echo(request)
+    }
+}
+
+/// Echo actor which does not reply to every third request in time.
+#[actor(FlakyEchoMessage, FlakyEchoInRequestMessage)]
+type FlakyEcho = Actor<
+    NullProp,
+    FlakyEchoInbound,
+    FlakyEchoInRequest,
+    FlakyEchoState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+/// Inbound message of the requester actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    RequesterInbound,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        RequesterOutRequest,
+        NullInRequestMessage
+    })]
+pub enum RequesterMessage {
+    /// Tick time in seconds.
+    Tick(f64),
+    /// Reply of the echo actor.
+    EchoReply(ReplyMessage<f64>),
+    /// The echo actor did not reply in time.
+    EchoTimeout(RequestTimeout<f64>),
+}
+
+impl HasOnMessage for RequesterMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        request: &Self::OutRequestHub,
+    ) {
+        match self {
+            RequesterMessage::Tick(time) => {
+                request
+                    .echo
+                    .send_request_with_timeout(time, Duration::from_millis(200));
+            }
+            RequesterMessage::EchoReply(reply) => {
+                println!("reply: {:.1}", reply.reply);
+            }
+            RequesterMessage::EchoTimeout(timeout) => {
+                println!(
+                    "timeout: {:.1} not replied to within {:?}",
+                    timeout.request, timeout.timeout
+                );
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for RequesterMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        RequesterMessage::Tick(msg)
+    }
+}
+
+impl IsInboundMessageNew<ReplyMessage<f64>> for RequesterMessage {
+    fn new(_inbound_name: String, msg: ReplyMessage<f64>) -> Self {
+        RequesterMessage::EchoReply(msg)
+    }
+}
+
+impl IsInboundMessageNew<RequestTimeout<f64>> for RequesterMessage {
+    fn new(_inbound_name: String, msg: RequestTimeout<f64>) -> Self {
+        RequesterMessage::EchoTimeout(msg)
+    }
+}
+
+/// Out-request hub of the requester actor.
+#[actor_out_requests]
+pub struct RequesterOutRequest {
+    /// Echo request.
+    pub echo: OutRequestChannel<f64, f64, RequesterMessage>,
+}
+
+/// Actor which sends an echo request per tick.
+#[actor(RequesterMessage, NullInRequestMessage)]
+type Requester =
+    Actor<NullProp, RequesterInbound, NullInRequests, NullState, NullOutbound, RequesterOutRequest>;
+
+/// Run the example which handles requests which are not replied to in time
+pub async fn run_request_timeout_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.5);
+        let mut requester =
+            Requester::from_prop_and_state(context, NullProp::default(), NullState::default());
+        let mut echo =
+            FlakyEcho::from_prop_and_state(context, NullProp::default(), FlakyEchoState::default());
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut requester.inbound.tick);
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut echo.inbound.tick);
+        requester
+            .out_requests
+            .echo
+            .connect(context, &mut echo.in_requests.echo);
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(3))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_request_timeout_example().await;
+        })
+}
@@ -0,0 +1,100 @@
+use hollywood::prelude::*;
+use std::collections::BTreeMap;
+
+/// In-memory key-value store, standing in for a database connection.
+pub struct KeyValueStore {
+    name: String,
+    entries: BTreeMap<String, String>,
+}
+
+impl IsResource for KeyValueStore {
+    type Config = String;
+
+    fn open(config: &String) -> Self {
+        println!("opening store {}", config);
+        Self {
+            name: config.clone(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    fn close(&mut self) {
+        println!(
+            "closing store {} with {} entries",
+            self.name,
+            self.entries.len()
+        );
+    }
+}
+
+/// Operations of the key-value store.
+#[resource_actor(Store, KeyValueStore)]
+pub trait KeyValueOps {
+    /// Inserts the value for the key, and returns the previous value.
+    fn put(&mut self, entry: (String, String)) -> Option<String>;
+    /// Returns the value for the key.
+    fn get(&mut self, key: String) -> Option<String>;
+    /// Removes the value for the key, and returns it.
+    fn remove(&mut self, key: String) -> Option<String>;
+}
+
+impl KeyValueOps for KeyValueStore {
+    fn put(&mut self, (key, value): (String, String)) -> Option<String> {
+        self.entries.insert(key, value)
+    }
+
+    fn get(&mut self, key: String) -> Option<String> {
+        self.entries.get(&key).cloned()
+    }
+
+    fn remove(&mut self, key: String) -> Option<String> {
+        self.entries.remove(&key)
+    }
+}
+
+/// Run the resource actor example
+pub async fn run_resource_actor_example() {
+    let mut clients = None;
+    let mut close = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let store = Store::from_prop_and_state(context, "store.db".to_owned(), Default::default());
+        clients = Some((
+            store.in_requests.put.client(),
+            store.in_requests.get.client(),
+            store.in_requests.remove.client(),
+        ));
+        close = Some(store.inbound.close.injector());
+    });
+
+    pipeline.print_flow_graph();
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+
+    let (put, get, remove) = clients.unwrap();
+    println!(
+        "put: {:?}",
+        put.request(("answer".to_owned(), "42".to_owned())).await
+    );
+    println!("get: {:?}", get.request("answer".to_owned()).await);
+    println!("remove: {:?}", remove.request("answer".to_owned()).await);
+    println!("get: {:?}", get.request("answer".to_owned()).await);
+
+    // The store is closed, and reopened by the next request.
+    close.unwrap().send(()).unwrap();
+    println!("get: {:?}", get.request("answer".to_owned()).await);
+
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_resource_actor_example().await;
+        })
+}
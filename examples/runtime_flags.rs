@@ -0,0 +1,125 @@
+use hollywood::actors::Flags;
+use hollywood::actors::Periodic;
+use hollywood::actors::RuntimeFlags;
+use hollywood::prelude::*;
+
+/// State of the worker actor.
+#[derive(Clone, Debug, Default)]
+pub struct WorkerState {
+    /// Latest runtime flags.
+    pub flags: Flags,
+    /// Number of processed ticks.
+    pub num_ticks: u64,
+}
+
+/// Inbound message of the worker actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    WorkerInbound,
+    {
+        NullProp,
+        WorkerState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum WorkerMessage {
+    /// Runtime flags, which toggle the debug output.
+    Flags(Flags),
+    /// Time stamp, upon which some work is done.
+    TimeStamp(f64),
+}
+
+impl HasOnMessage for WorkerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            WorkerMessage::Flags(flags) => {
+                state.flags = flags;
+            }
+            WorkerMessage::TimeStamp(time) => {
+                state.num_ticks += 1;
+                if state.flags.is_enabled("verbose") {
+                    let scale = state.flags.number_or("scale", 1.0);
+                    println!(
+                        "worker: tick #{} at {:.1}s, scaled {:.1}",
+                        state.num_ticks,
+                        time,
+                        scale * time
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Flags> for WorkerMessage {
+    fn new(_inbound_name: String, msg: Flags) -> Self {
+        WorkerMessage::Flags(msg)
+    }
+}
+
+impl IsInboundMessageNew<f64> for WorkerMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        WorkerMessage::TimeStamp(msg)
+    }
+}
+
+/// Worker, which prints debug output only if the "verbose" flag is enabled.
+#[actor(WorkerMessage, NullInRequestMessage)]
+type Worker =
+    Actor<NullProp, WorkerInbound, NullInRequests, WorkerState, NullOutbound, NullOutRequests>;
+
+/// Run the runtime flags example
+pub async fn run_runtime_flags_example() {
+    let mut client = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut flags = RuntimeFlags::new(context, Flags::default().with("verbose", true));
+        let mut worker = Worker::from_prop_and_state(context, NullProp {}, WorkerState::default());
+
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut worker.inbound.time_stamp);
+        flags
+            .outbound
+            .flags
+            .connect(context, &mut worker.inbound.flags);
+        client = Some(flags.in_requests.client());
+    });
+
+    pipeline.print_flow_graph();
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+
+    let client = client.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(350)).await;
+    let previous = client.update(("scale".to_owned(), 10.0.into())).await;
+    println!("set scale to 10, previous: {:?}", previous);
+    tokio::time::sleep(std::time::Duration::from_millis(350)).await;
+    let previous = client.update(("verbose".to_owned(), false.into())).await;
+    println!("disabled verbose output, previous: {:?}", previous);
+    tokio::time::sleep(std::time::Duration::from_millis(350)).await;
+    println!("flags: {:?}", client.list(()).await);
+
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_runtime_flags_example().await;
+        })
+}
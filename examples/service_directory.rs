@@ -0,0 +1,56 @@
+use hollywood::actors::FaultyActor;
+use hollywood::actors::ServiceDirectory;
+use hollywood::actors::ServiceEntry;
+use hollywood::prelude::*;
+
+/// Run the service directory example
+pub async fn run_service_directory_example() {
+    let mut resolver = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        let directory =
+            ServiceDirectory::from_prop_and_state(context, NullProp {}, Default::default());
+        let echo = FaultyActor::<f64>::from_prop_and_state(
+            context,
+            Default::default(),
+            Default::default(),
+        );
+        // The echo service is registered by name, the client is not connected to it.
+        directory
+            .inbound
+            .register
+            .injector()
+            .send(ServiceEntry::new("echo", echo.in_requests.echo.address()))
+            .unwrap();
+        resolver = Some(directory.in_requests.resolve.client());
+    });
+
+    pipeline.print_flow_graph();
+    let cancel_requester = pipeline.get_cancel_request_sender();
+    let handle = tokio::spawn(pipeline.run());
+
+    let resolver = resolver.unwrap();
+    for name in ["echo", "unknown"] {
+        match resolver.request(name.to_owned()).await.unwrap() {
+            Some(entry) => {
+                let echo = entry.address::<f64, f64>().unwrap().client();
+                println!("{}: {:?}", name, echo.request(42.0).await);
+            }
+            None => println!("{}: no such service", name),
+        }
+    }
+
+    cancel_requester.send(CancelRequest).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_service_directory_example().await;
+        })
+}
@@ -0,0 +1,160 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Large image, which is deliberately not [Clone].
+#[derive(Debug)]
+pub struct Image {
+    /// Frame index.
+    pub index: u64,
+    /// Pixel data.
+    pub pixels: Vec<u8>,
+}
+
+/// Outbound hub of the camera actor.
+#[actor_outputs]
+pub struct CameraOutbound {
+    /// Captured images, shared by all receivers.
+    pub image: OutboundChannel<Shared<Image>>,
+}
+
+/// State of the camera actor.
+#[derive(Clone, Debug, Default)]
+pub struct CameraState {
+    /// Number of captured images.
+    pub num_images: u64,
+}
+
+/// Inbound message of the camera actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    CameraInbound,
+    {
+        NullProp,
+        CameraState,
+        CameraOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum CameraMessage {
+    /// Trigger to capture an image.
+    Trigger(f64),
+}
+
+impl HasOnMessage for CameraMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            CameraMessage::Trigger(_) => {
+                outbound.image.send_shared(Image {
+                    index: state.num_images,
+                    pixels: vec![0; 1920 * 1080 * 3],
+                });
+                state.num_images += 1;
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for CameraMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        CameraMessage::Trigger(msg)
+    }
+}
+
+/// Camera actor, which captures an image on each trigger.
+#[actor(CameraMessage, NullInRequestMessage)]
+type Camera =
+    Actor<NullProp, CameraInbound, NullInRequests, CameraState, CameraOutbound, NullOutRequests>;
+
+/// State of the image consumer actor.
+#[derive(Clone, Debug, Default)]
+pub struct ConsumerState {
+    /// Number of received bytes.
+    pub num_bytes: usize,
+}
+
+/// Inbound message of the image consumer actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    ConsumerInbound,
+    {
+        NullProp,
+        ConsumerState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum ConsumerMessage {
+    /// Shared image.
+    Image(Shared<Image>),
+}
+
+impl HasOnMessage for ConsumerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ConsumerMessage::Image(image) => {
+                state.num_bytes += image.pixels.len();
+                println!(
+                    "image {} received, shared by {} handles",
+                    image.index,
+                    std::sync::Arc::strong_count(&image)
+                );
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<Shared<Image>> for ConsumerMessage {
+    fn new(_inbound_name: String, msg: Shared<Image>) -> Self {
+        ConsumerMessage::Image(msg)
+    }
+}
+
+/// Actor which consumes images.
+#[actor(ConsumerMessage, NullInRequestMessage)]
+type Consumer =
+    Actor<NullProp, ConsumerInbound, NullInRequests, ConsumerState, NullOutbound, NullOutRequests>;
+
+/// Run the example which broadcasts large, non-clonable images to several consumers
+pub async fn run_shared_payload_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut camera = Camera::from_prop_and_state(context, NullProp {}, CameraState::default());
+        let mut viewer =
+            Consumer::from_prop_and_state(context, NullProp {}, ConsumerState::default());
+        let mut recorder =
+            Consumer::from_prop_and_state(context, NullProp {}, ConsumerState::default());
+        pipeline! {
+            timer.time_stamp -> camera.trigger;
+            camera.image -> viewer.image, recorder.image;
+        }
+    });
+    pipeline
+        .run_with_deadline(Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_shared_payload_example().await;
+        })
+}
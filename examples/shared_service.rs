@@ -0,0 +1,233 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::collections::HashMap;
+
+/// Inbound message of the service actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    ServiceInbound,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        ServiceInRequestMessage
+    })]
+pub enum ServiceMessage {
+    /// Tick time in seconds, which is ignored.
+    Tick(f64),
+}
+
+impl HasOnMessage for ServiceMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+    }
+}
+
+impl IsInboundMessageNew<f64> for ServiceMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        ServiceMessage::Tick(msg)
+    }
+}
+
+/// In-request message of the service actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    ServiceInRequest,
+    {
+        NullProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        ServiceMessage
+    }
+)]
+pub enum ServiceInRequestMessage {
+    /// Request to square the given number.
+    Square(RequestWithReplyChannel<u64, u64>),
+}
+
+impl HasOnRequestMessage for ServiceInRequestMessage {
+    fn on_request(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ServiceInRequestMessage::Square(request) => {
+                println!(
+                    "service: request #{} from {}",
+                    request.request_id(),
+                    request.requester()
+                );
+                if request.request % 5 == 4 {
+                    // Shed load by dropping the request, see
+                    // OutRequestChannel::set_dropped_request_policy().
+                    return;
+                }
+                request.reply_from_request(|x| x * x);
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<u64, u64>> for ServiceInRequestMessage {
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<u64, u64>) -> Self {
+        ServiceInRequestMessage::Square(request)
+    }
+}
+
+/// Service actor, which is shared by several clients.
+#[actor(ServiceMessage, ServiceInRequestMessage)]
+type Service =
+    Actor<NullProp, ServiceInbound, ServiceInRequest, NullState, NullOutbound, NullOutRequests>;
+
+/// Properties of the client actor.
+#[derive(Clone, Debug, Default)]
+pub struct ClientProp {
+    /// First number the client requests the square of.
+    pub offset: u64,
+}
+
+/// State of the client actor.
+#[derive(Clone, Debug, Default)]
+pub struct ClientState {
+    /// Number of the next request.
+    pub next: u64,
+    /// Numbers of the requests in flight, by request id.
+    pub pending: HashMap<u64, u64>,
+}
+
+/// Inbound message of the client actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    ClientInbound,
+    {
+        ClientProp,
+        ClientState,
+        NullOutbound,
+        ClientOutRequest,
+        NullInRequestMessage
+    })]
+pub enum ClientMessage {
+    /// Tick time in seconds.
+    Tick(f64),
+    /// Reply of the service.
+    SquareReply(ReplyMessage<u64>),
+    /// Request which the service dropped.
+    SquareDropped(ReplyError),
+}
+
+impl HasOnMessage for ClientMessage {
+    fn on_message(
+        self,
+        prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ClientMessage::Tick(_) => {
+                // Several requests are in flight at once, and their replies may interleave.
+                for _ in 0..2 {
+                    let x = prop.offset + state.next;
+                    let request_id = request.square.send_request(x);
+                    state.pending.insert(request_id, x);
+                    state.next += 1;
+                }
+            }
+            ClientMessage::SquareReply(reply) => {
+                let x = state.pending.remove(&reply.request_id).unwrap();
+                assert_eq!(reply.reply, x * x);
+                println!("client {}: {}^2 = {}", prop.offset, x, reply.reply);
+            }
+            ClientMessage::SquareDropped(ReplyError::Dropped { request_id }) => {
+                let x = state.pending.remove(&request_id).unwrap();
+                println!("client {}: request for {} dropped", prop.offset, x);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for ClientMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        ClientMessage::Tick(msg)
+    }
+}
+
+impl IsInboundMessageNew<ReplyMessage<u64>> for ClientMessage {
+    fn new(_inbound_name: String, msg: ReplyMessage<u64>) -> Self {
+        ClientMessage::SquareReply(msg)
+    }
+}
+
+impl IsInboundMessageNew<ReplyError> for ClientMessage {
+    fn new(_inbound_name: String, msg: ReplyError) -> Self {
+        ClientMessage::SquareDropped(msg)
+    }
+}
+
+/// Out-request hub of the client actor.
+#[actor_out_requests]
+pub struct ClientOutRequest {
+    /// Requests to the service.
+    pub square: OutRequestChannel<u64, u64, ClientMessage>,
+}
+
+/// Client actor, which sends requests to the shared service.
+#[actor(ClientMessage, NullInRequestMessage)]
+type Client =
+    Actor<ClientProp, ClientInbound, NullInRequests, ClientState, NullOutbound, ClientOutRequest>;
+
+/// Run the example where several clients share a single service
+pub async fn run_shared_service_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut service =
+            Service::from_prop_and_state(context, NullProp::default(), NullState::default());
+        for offset in [0, 100] {
+            let mut client =
+                Client::from_prop_and_state(context, ClientProp { offset }, ClientState::default());
+            timer
+                .outbound
+                .time_stamp
+                .connect(context, &mut client.inbound.tick);
+            // Both clients send their requests into the same in-request channel, and each reply
+            // is routed back to its requester.
+            client
+                .out_requests
+                .square
+                .connect(context, &mut service.in_requests.square);
+            client
+                .out_requests
+                .square
+                .set_dropped_request_policy(DroppedRequestPolicy::notify());
+        }
+    });
+
+    let running = pipeline.spawn();
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    running
+        .shutdown_gracefully(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_shared_service_example().await;
+        })
+}
@@ -0,0 +1,55 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Run the example which steps a pipeline through one hour of virtual time
+pub async fn run_sim_clock_example() {
+    let clock = Arc::new(SimClock::new());
+    let pipeline = Hollywood::configure(&mut |context| {
+        context.set_clock(clock.clone());
+        let mut timer = Periodic::new_with_period(context, 600.0);
+        let mut printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "virtual time".to_string(),
+            },
+            NullState::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut printer.inbound.printable);
+    });
+
+    let start = std::time::Instant::now();
+    let running = pipeline.spawn();
+    while clock.now() < Duration::from_secs(3600) {
+        // Let the actors process the messages of the current time step, before the next one.
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        clock.step();
+    }
+    running.cancel();
+    running.join().await.unwrap();
+    println!(
+        "{:?} of virtual time in {:?} of real time",
+        clock.now(),
+        start.elapsed()
+    );
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_sim_clock_example().await;
+        })
+}
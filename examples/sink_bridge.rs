@@ -0,0 +1,59 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Encodes time stamps as text lines.
+struct TimeStampEncoder;
+
+impl tokio_util::codec::Encoder<f64> for TimeStampEncoder {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, time: f64, dst: &mut tokio_util::bytes::BytesMut) -> std::io::Result<()> {
+        dst.extend_from_slice(format!("written: {:.1}\n", time).as_bytes());
+        Ok(())
+    }
+}
+
+/// Run the example which forwards outbound channels into a sink and a writer
+pub async fn run_sink_bridge_example() {
+    // E.g. the input of an existing protocol stack.
+    let (sender, mut receiver) = tokio::sync::mpsc::channel::<f64>(16);
+    let consumer = tokio::spawn(async move {
+        while let Some(time) = receiver.recv().await {
+            println!("sunk: {:.1}", time);
+        }
+    });
+
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.25);
+        timer
+            .outbound
+            .time_stamp
+            .forward_to_sink(context, tokio_util::sync::PollSender::new(sender.clone()));
+        timer
+            .outbound
+            .time_stamp
+            .forward_to_writer(context, tokio::io::stdout(), TimeStampEncoder);
+    });
+    drop(sender);
+
+    pipeline.print_flow_graph();
+    let running = pipeline.spawn();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    running.cancel();
+    // Dropping the pipeline closes the sink, upon which the consumer finishes.
+    drop(running.join().await.unwrap());
+    consumer.await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_sink_bridge_example().await;
+        })
+}
@@ -0,0 +1,48 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::actors::Stateful;
+use hollywood::prelude::*;
+
+/// Run the example which counts ticks with an actor defined by a closure
+pub async fn run_stateful_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        // Sends the number of ticks so far on every fifth tick.
+        let mut tick_counter =
+            Stateful::<f64, String, u64>::from_fn(context, 0, |t, count, outbound| {
+                *count += 1;
+                if *count % 5 == 0 {
+                    outbound.send(format!("{} ticks at {:.1}s", count, t));
+                }
+            });
+        let mut printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "count".to_string(),
+            },
+            NullState {},
+        );
+        pipeline! {
+            timer.time_stamp -> tick_counter.value;
+            tick_counter.value -> printer.printable;
+        }
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(2))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_stateful_example().await;
+        })
+}
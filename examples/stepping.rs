@@ -0,0 +1,65 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Map;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Run the example which embeds a pipeline into the main loop of an external simulation
+pub async fn run_stepping_example() {
+    let clock = Arc::new(SimClock::new());
+    let mut position_injector = None;
+    let mut command_subscriber = None;
+    let pipeline = Hollywood::configure(&mut |context| {
+        context.enable_stepping(clock.clone());
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "tick".to_string(),
+            },
+            NullState::default(),
+        );
+        // Proportional controller which drives the simulated position towards one.
+        let mut controller = Map::<f64, f64>::from_fn(context, |position| 2.0 * (1.0 - position));
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut printer.inbound.printable);
+        position_injector = Some(controller.inbound.value.injector());
+        command_subscriber = Some(controller.outbound.value.subscriber(context));
+    });
+    let position_injector = position_injector.unwrap();
+    let mut command_subscriber = command_subscriber.unwrap();
+
+    let running = pipeline.spawn();
+    let mut position = 0.0;
+    while let Some(now) = running.step().await {
+        if now > Duration::from_secs(2) {
+            break;
+        }
+        // The simulation feeds its measurement in, and reads the command back once the
+        // pipeline processed it.
+        position_injector.send(position).unwrap();
+        running.quiescent().await;
+        while let Some(command) = command_subscriber.try_recv() {
+            position += 0.1 * command;
+        }
+        println!("{:?}: position {:.3}", now, position);
+    }
+    running.cancel();
+    running.join().await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_stepping_example().await;
+        })
+}
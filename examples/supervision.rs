@@ -0,0 +1,100 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+
+/// State of the odometer actor.
+#[derive(Clone, Debug, Default)]
+pub struct OdometerState {
+    /// Number of ticks counted.
+    pub num_ticks: u64,
+}
+
+/// Inbound message of the odometer actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    OdometerInbound,
+    {
+        NullProp,
+        OdometerState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum OdometerMessage {
+    /// Tick time in seconds.
+    Tick(f64),
+}
+
+impl HasOnMessage for OdometerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            OdometerMessage::Tick(time) => {
+                state.num_ticks += 1;
+                // Transient glitch every 0.7 seconds.
+                if ((time * 10.0).round() as u64).is_multiple_of(7) {
+                    panic!("encoder glitch at {:.1}s", time);
+                }
+                println!("odometer: {} ticks at {:.1}s", state.num_ticks, time);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for OdometerMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        OdometerMessage::Tick(msg)
+    }
+}
+
+/// Odometer which panics from time to time.
+#[actor(OdometerMessage, NullInRequestMessage)]
+type Odometer =
+    Actor<NullProp, OdometerInbound, NullInRequests, OdometerState, NullOutbound, NullOutRequests>;
+
+/// Run the example which restarts a panicking actor from its last checkpoint
+pub async fn run_supervision_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut odometer = Odometer::from_prop_and_state_supervised(
+            context,
+            NullProp::default(),
+            OdometerState::default(),
+            SupervisionStrategy::OneForOne {
+                max_restarts: 3,
+                checkpoint_interval: Some(3),
+            },
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut odometer.inbound.tick);
+    });
+
+    match pipeline
+        .run_with_deadline(std::time::Duration::from_secs(2))
+        .await
+    {
+        Ok(pipeline) => println!(
+            "pipeline finished, restarts: {:?}",
+            pipeline.restart_report()
+        ),
+        Err(err) => println!("pipeline finished with failures: {}", err),
+    }
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_supervision_example().await;
+        })
+}
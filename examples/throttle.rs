@@ -0,0 +1,72 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::actors::Throttle;
+use hollywood::actors::ThrottlePolicy;
+use hollywood::prelude::*;
+
+/// Run the example which throttles a 100 Hz source down to a few messages per second
+pub async fn run_throttle_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut sensor = Periodic::new_with_period(context, 0.01);
+
+        // Latest data only, e.g. for a visualizer.
+        let mut dropping = Throttle::<f64>::new_with_max_rate(context, 5.0, ThrottlePolicy::Drop);
+        let mut dropping_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "drop at 5 Hz".to_string(),
+            },
+            NullState::default(),
+        );
+
+        // Bursts are smoothed out, but the queue only keeps the three most recent messages.
+        let mut queueing = Throttle::<f64>::new_with_min_interval(
+            context,
+            0.25,
+            ThrottlePolicy::Queue { capacity: 3 },
+        );
+        let mut queueing_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "queue every 0.25s".to_string(),
+            },
+            NullState::default(),
+        );
+
+        sensor
+            .outbound
+            .time_stamp
+            .connect(context, &mut dropping.inbound.value);
+        sensor
+            .outbound
+            .time_stamp
+            .connect(context, &mut queueing.inbound.value);
+        dropping
+            .outbound
+            .value
+            .connect(context, &mut dropping_printer.inbound.printable);
+        queueing
+            .outbound
+            .value
+            .connect(context, &mut queueing_printer.inbound.printable);
+    });
+
+    pipeline.print_flow_graph();
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(2))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_throttle_example().await;
+        })
+}
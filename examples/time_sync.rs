@@ -0,0 +1,63 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Map;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use hollywood::time::TimeSync;
+use hollywood::time::TimeSyncMode;
+
+/// Offset of the clock of the simulated device, in seconds.
+const DEVICE_CLOCK_OFFSET: f64 = 1234.5;
+
+/// Run the example which aligns the timestamps of a device with a skewed clock
+pub async fn run_time_sync_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        // The device stamps each reading with its own clock, and the reading is its device time.
+        let mut device = Map::<f64, Stamped<f64>>::from_fn(context, |t| {
+            let device_time = t + DEVICE_CLOCK_OFFSET;
+            Stamped::new(
+                Timestamp::monotonic(device_time).with_domain("device"),
+                (t * 10.0).round() as u64,
+                device_time,
+            )
+        });
+        let mut sync = TimeSync::<f64>::new_with_mode(context, TimeSyncMode::Align);
+        let mut format = Map::<Stamped<f64>, String>::from_fn(context, |msg| {
+            format!(
+                "#{}: device time {:.3}s -> pipeline time {:.3}s",
+                msg.seq, msg.value, msg.time.seconds
+            )
+        });
+        let mut printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "synced".to_string(),
+            },
+            NullState {},
+        );
+        pipeline! {
+            timer.time_stamp -> device.value;
+            device.value -> sync.value;
+            sync.value -> format.value;
+            format.value -> printer.printable;
+        }
+    });
+
+    pipeline
+        .run_with_deadline(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_time_sync_example().await;
+        })
+}
@@ -0,0 +1,58 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the example which subscribes actors to channels of an existing tokio application
+pub async fn run_tokio_bridge_example() {
+    // Channels owned by the existing application.
+    let (event_sender, _) = tokio::sync::broadcast::channel::<String>(16);
+    let (config_sender, config_receiver) = tokio::sync::watch::channel(1.0);
+
+    let pipeline = Hollywood::configure(&mut |context| {
+        let event_printer = Printer::<String>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "event".to_string(),
+            },
+            NullState::default(),
+        );
+        let config_printer = Printer::<f64>::from_prop_and_state(
+            context,
+            PrinterProp {
+                topic: "config".to_string(),
+            },
+            NullState::default(),
+        );
+        event_printer
+            .inbound
+            .printable
+            .subscribe_broadcast(context, &event_sender);
+        config_printer
+            .inbound
+            .printable
+            .subscribe_watch(context, config_receiver.clone());
+    });
+
+    let running = pipeline.spawn();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    for i in 0..3 {
+        event_sender.send(format!("event #{}", i)).unwrap();
+        config_sender.send_replace(2.0 + i as f64);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    running.cancel();
+    running.join().await.unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_tokio_bridge_example().await;
+        })
+}
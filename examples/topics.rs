@@ -0,0 +1,44 @@
+use hollywood::actors::printer::PrinterProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Printer;
+use hollywood::prelude::*;
+use hollywood::RuntimeFlavor;
+
+/// Run the topic-based publish/subscribe example
+pub fn run_topics_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut first_printer = Printer::<f64>::from_prop_and_state_named(
+            context,
+            "first_printer",
+            PrinterProp {
+                topic: "first".to_string(),
+            },
+            NullState::default(),
+        );
+        let mut second_printer = Printer::<f64>::from_prop_and_state_named(
+            context,
+            "second_printer",
+            PrinterProp {
+                topic: "second".to_string(),
+            },
+            NullState::default(),
+        );
+        let mut timer = Periodic::new_with_period(context, 1.0);
+
+        // Subscribers can be registered before or after the publisher.
+        context.subscribe("time", &mut first_printer.inbound.printable);
+        context.publish("time", &mut timer.outbound.time_stamp);
+        context.subscribe("time", &mut second_printer.inbound.printable);
+    });
+
+    pipeline.print_flow_graph();
+    pipeline
+        .run_blocking_with(RuntimeFlavor::MultiThread)
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    run_topics_example();
+}
@@ -0,0 +1,49 @@
+use hollywood::actors::FaultyActor;
+use hollywood::actors::FaultyProp;
+use hollywood::actors::Periodic;
+use hollywood::actors::Watchdog;
+use hollywood::prelude::*;
+use std::time::Duration;
+
+/// Run the watchdog example, which cancels the pipeline once a stalling actor went silent
+pub async fn run_watchdog_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        // An actor which stalls for up to one second per message.
+        let mut stalling = FaultyActor::<f64>::from_prop_and_state(
+            context,
+            FaultyProp {
+                max_delay: Some(Duration::from_secs(1)),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut stalling.inbound.input);
+
+        let mut watchdog = Watchdog::new_with_timeout(context, 0.5, true);
+        watchdog.monitor(context, &mut timer.outbound.time_stamp);
+        watchdog.monitor(context, &mut stalling.outbound.output);
+    });
+
+    let start = std::time::Instant::now();
+    pipeline
+        .run_with_deadline(Duration::from_secs(10))
+        .await
+        .unwrap();
+    println!("pipeline cancelled by watchdog after {:?}", start.elapsed());
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_watchdog_example().await;
+        })
+}
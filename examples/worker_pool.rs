@@ -0,0 +1,211 @@
+use hollywood::actors::Periodic;
+use hollywood::prelude::*;
+
+/// Properties of the worker actor.
+#[derive(Clone, Debug, Default)]
+pub struct WorkerProp {
+    /// Id of the worker.
+    pub id: usize,
+}
+
+/// Inbound message of the worker actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    WorkerInbound,
+    {
+        WorkerProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        WorkerInRequestMessage
+    })]
+pub enum WorkerMessage {
+    /// Tick time in seconds, which is ignored.
+    Tick(f64),
+}
+
+impl HasOnMessage for WorkerMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+    }
+}
+
+impl IsInboundMessageNew<f64> for WorkerMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        WorkerMessage::Tick(msg)
+    }
+}
+
+/// In-request message of the worker actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    WorkerInRequest,
+    {
+        WorkerProp,
+        NullState,
+        NullOutbound,
+        NullOutRequests,
+        WorkerMessage
+    }
+)]
+pub enum WorkerInRequestMessage {
+    /// Job to square the given number, replied with the result and the id of the worker.
+    Square(RequestWithReplyChannel<u64, (u64, usize)>),
+}
+
+impl HasOnRequestMessage for WorkerInRequestMessage {
+    fn on_request(
+        self,
+        prop: &Self::Prop,
+        _state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            WorkerInRequestMessage::Square(request) => {
+                request.reply_from_request(|x| (x * x, prop.id));
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<u64, (u64, usize)>> for WorkerInRequestMessage {
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<u64, (u64, usize)>) -> Self {
+        WorkerInRequestMessage::Square(request)
+    }
+}
+
+/// Worker actor of the pool.
+#[actor(WorkerMessage, WorkerInRequestMessage)]
+type Worker =
+    Actor<WorkerProp, WorkerInbound, WorkerInRequest, NullState, NullOutbound, NullOutRequests>;
+
+/// State of the dispatcher actor.
+#[derive(Clone, Debug, Default)]
+pub struct DispatcherState {
+    /// Number of the next job.
+    pub next_job: u64,
+}
+
+/// Inbound message of the dispatcher actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    DispatcherInbound,
+    {
+        NullProp,
+        DispatcherState,
+        NullOutbound,
+        DispatcherOutRequest,
+        NullInRequestMessage
+    })]
+pub enum DispatcherMessage {
+    /// Tick time in seconds.
+    Tick(f64),
+    /// Reply of the worker pool.
+    SquareReply(ReplyMessage<(u64, usize)>),
+}
+
+impl HasOnMessage for DispatcherMessage {
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        request: &Self::OutRequestHub,
+    ) {
+        match self {
+            DispatcherMessage::Tick(_) => {
+                request.square.send_request(state.next_job);
+                state.next_job += 1;
+            }
+            DispatcherMessage::SquareReply(reply) => {
+                let (square, worker) = reply.reply;
+                println!("worker {}: {}", worker, square);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for DispatcherMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        DispatcherMessage::Tick(msg)
+    }
+}
+
+impl IsInboundMessageNew<ReplyMessage<(u64, usize)>> for DispatcherMessage {
+    fn new(_inbound_name: String, msg: ReplyMessage<(u64, usize)>) -> Self {
+        DispatcherMessage::SquareReply(msg)
+    }
+}
+
+/// Out-request hub of the dispatcher actor.
+#[actor_out_requests]
+pub struct DispatcherOutRequest {
+    /// Job requests to the worker pool.
+    pub square: OutRequestChannel<u64, (u64, usize), DispatcherMessage>,
+}
+
+/// Actor which dispatches a job per tick to the worker pool.
+#[actor(DispatcherMessage, NullInRequestMessage)]
+type Dispatcher = Actor<
+    NullProp,
+    DispatcherInbound,
+    NullInRequests,
+    DispatcherState,
+    NullOutbound,
+    DispatcherOutRequest,
+>;
+
+/// Run the example which fans out requests to a pool of workers
+pub async fn run_worker_pool_example() {
+    let pipeline = Hollywood::configure(&mut |context| {
+        let mut timer = Periodic::new_with_period(context, 0.1);
+        let mut dispatcher = Dispatcher::from_prop_and_state(
+            context,
+            NullProp::default(),
+            DispatcherState::default(),
+        );
+        timer
+            .outbound
+            .time_stamp
+            .connect(context, &mut dispatcher.inbound.tick);
+        // Alternatively, RequestRouting::hash() sends equal jobs to the same worker, and
+        // RequestRouting::FirstReply sends each job to all workers.
+        dispatcher
+            .out_requests
+            .square
+            .set_routing(RequestRouting::RoundRobin);
+        for id in 0..3 {
+            let mut worker = Worker::from_prop_and_state(context, WorkerProp { id }, NullState {});
+            dispatcher
+                .out_requests
+                .square
+                .connect(context, &mut worker.in_requests.square);
+        }
+    });
+
+    let running = pipeline.spawn();
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    // Pending jobs are still processed, since the workers are stopped after the dispatcher.
+    running
+        .shutdown_gracefully(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+}
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            run_worker_pool_example().await;
+        })
+}
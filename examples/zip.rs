@@ -26,18 +26,12 @@ pub async fn run_tick_print_example() {
 
         periodic.outbound.time_stamp.connect_with_adapter(
             context,
-            |t| ZipPair {
-                key: t as u64,
-                value: "hello".to_string(),
-            },
+            |t| ZipPair::new(t as u64, "hello".to_string()),
             &mut zip.inbound.item0,
         );
         periodic.outbound.time_stamp.connect_with_adapter(
             context,
-            |t| ZipPair {
-                key: 2 * t as u64,
-                value: "world".to_string(),
-            },
+            |t| ZipPair::new(2 * t as u64, "world".to_string()),
             &mut zip.inbound.item1,
         );
 
@@ -47,7 +41,7 @@ pub async fn run_tick_print_example() {
     });
 
     pipeline.print_flow_graph();
-    pipeline.run().await;
+    pipeline.run().await.unwrap();
 }
 
 fn main() {
@@ -1 +1,3 @@
+pub mod approx_zip;
+pub mod resource;
 pub mod zip;
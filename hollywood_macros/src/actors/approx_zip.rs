@@ -0,0 +1,433 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse::Parse, parse::ParseStream, parse2, LitInt, Result};
+struct ApproxZipInput {
+    num_fields: usize,
+}
+
+impl Parse for ApproxZipInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let num_fields: LitInt = input.parse()?;
+        Ok(ApproxZipInput {
+            num_fields: num_fields.base10_parse()?,
+        })
+    }
+}
+
+pub(crate) fn approx_zip_state_n_impl(input: TokenStream) -> TokenStream {
+    let ApproxZipInput { num_fields } = match parse2(input) {
+        Ok(input) => input,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let state_struct = format_ident!("ApproxZip{}State", num_fields);
+
+    let queue_item_seq = (0..num_fields).map(|i| {
+        let queue_item = format_ident!("item{}_queue", i);
+        let item = format_ident!("Item{}", i);
+        let pair = quote! { ApproxZipPair<#i, Key, #item> };
+        quote! {
+            /// Queue for the Nth inbound channel, ordered by arrival.
+            pub #queue_item: std::collections::VecDeque<#pair>
+        }
+    });
+
+    let item_seq = (0..num_fields).map(|i| format_ident!("Item{}", i));
+
+    let expanded = quote! {
+        /// State of the approximate zip actor with N inbound channels.
+        #[derive(Clone, std::fmt::Debug, Default)]
+        pub struct #state_struct<
+            Key: IsApproxKey,
+            #( #item_seq: Default + Clone + std::fmt::Debug + Sync + Send + 'static ),*
+        >
+        {
+            #( #queue_item_seq ),*
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+pub(crate) fn approx_zip_inbound_message_n_impl(input: TokenStream) -> TokenStream {
+    let ApproxZipInput { num_fields } = match parse2(input) {
+        Ok(input) => input,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let inbound_message_enum = format_ident!("ApproxZip{}InboundMessage", num_fields);
+    let state_struct = format_ident!("ApproxZip{}State", num_fields);
+    let outbound_struct = format_ident!("Zip{}Outbound", num_fields);
+
+    let type_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("Item{}", i))
+        .collect();
+    let type_with_bounds_seq: Vec<_> = (0..num_fields)
+        .map(|i| {
+            let ident = format_ident!("Item{}", i);
+            quote! { #ident: Default + Clone + std::fmt::Debug + Sync + Send
+            + 'static}
+        })
+        .collect();
+    let i_seq: Vec<_> = (0..num_fields)
+        .map(|i| {
+            quote! { #i }
+        })
+        .collect();
+
+    let msg_new_impl_seq = (0..num_fields).map(|i| {
+        let item = format_ident!("Item{}", i);
+        let type_seq = (0..num_fields).map(|i| format_ident!("Item{}", i));
+
+        quote! {
+            impl<
+                Key: IsApproxKey,
+                #( #type_with_bounds_seq ),*
+            >
+                IsInboundMessageNew<ApproxZipPair<#i, Key, #item>>
+                for #inbound_message_enum<Key, #( #type_seq ),*>
+            {
+                fn new(_inbound_name: String, msg: ApproxZipPair<#i, Key, #item>) -> Self {
+                    #inbound_message_enum::#item(msg)
+                }
+            }
+        }
+    });
+
+    let expand = quote! {
+
+        /// Inbound message for the approximate zip actor.
+        #[derive(Clone,std::fmt::Debug)]
+        pub enum #inbound_message_enum<
+            Key: IsApproxKey,
+            #( #type_with_bounds_seq ),*
+        > {
+            #(
+                /// Inbound message for the Nth inbound channel.
+                #type_seq(ApproxZipPair<#i_seq, Key, #type_seq>)
+            ),*
+        }
+
+       #(#msg_new_impl_seq)*
+
+       impl<
+            Key: IsApproxKey,
+            #( #type_with_bounds_seq ),*
+        >
+            IsInboundMessage for  #inbound_message_enum<Key, #(#type_seq),*>
+        {
+            type Prop = ApproxZipProp;
+            type State = #state_struct<Key, #(#type_seq),*>;
+            type OutboundHub = #outbound_struct<Key, #(#type_seq),*>;
+            type OutRequestHub = NullOutRequests;
+
+            fn inbound_channel(&self) -> String {
+                match self {
+                    #( #inbound_message_enum::#type_seq(_) => stringify!(#type_seq).to_owned(), )*
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expand)
+}
+
+pub(crate) fn approx_zip_n_impl(input: TokenStream) -> TokenStream {
+    let ApproxZipInput { num_fields } = match parse2(input) {
+        Ok(input) => input,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let zip_struct = format_ident!("ApproxZip{}", num_fields);
+    let state_struct = format_ident!("ApproxZip{}State", num_fields);
+    let inbound_struct = format_ident!("ApproxZip{}Inbound", num_fields);
+    let outbound_struct = format_ident!("Zip{}Outbound", num_fields);
+    let inbound_message_enum = format_ident!("ApproxZip{}InboundMessage", num_fields);
+
+    let type_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("Item{}", i))
+        .collect();
+
+    let type_with_bounds_seq: Vec<_> = (0..num_fields)
+        .map(|i| {
+            let item_type = format_ident!("Item{}", i);
+            quote! { #item_type: Default + Clone + std::fmt::Debug + Sync + Send
+            + 'static}
+        })
+        .collect();
+
+    let expanded = quote! {
+
+        /// ApproxZipN actor, which zips N inbound channels into one outbound channel, by the
+        /// nearest keys within a tolerance.
+        pub type #zip_struct<Key, #( #type_seq), *> =
+            Actor<
+                ApproxZipProp,
+                #inbound_struct<Key, #( #type_seq), *>,
+                NullInRequests,
+                #state_struct<Key, #( #type_seq), *>,
+                #outbound_struct<Key, #( #type_seq), *>,
+                NullOutRequests,
+            >;
+
+        impl<
+            Key: IsApproxKey,
+            #( #type_with_bounds_seq ),*
+        >
+            HasFromPropState<
+                ApproxZipProp,
+                #inbound_struct<Key, #( #type_seq ), *>,
+                NullInRequests,
+                #state_struct<Key, #( #type_seq ), *>,
+                #outbound_struct<Key, #( #type_seq ), *>,
+                #inbound_message_enum<Key, #( #type_seq ), *>,
+                NullInRequestMessage,
+                NullOutRequests,
+                DefaultRunner<
+                    ApproxZipProp,
+                    #inbound_struct<Key, #( #type_seq ), *>,
+                    NullInRequests,
+                    #state_struct<Key, #( #type_seq ), *>,
+                    #outbound_struct<Key, #( #type_seq ), *>,
+                    NullOutRequests,
+                >,
+            > for #zip_struct<Key, #( #type_seq ), *>
+        {
+            fn name_hint(_prop: &ApproxZipProp) -> String {
+                stringify!(#zip_struct).to_owned()
+            }
+        }
+
+    };
+
+    TokenStream::from(expanded)
+}
+
+pub(crate) fn approx_zip_inbound_n_impl(input: TokenStream) -> TokenStream {
+    let ApproxZipInput { num_fields } = match parse2(input) {
+        Ok(input) => input,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let inbound_struct = format_ident!("ApproxZip{}Inbound", num_fields);
+    let state_struct = format_ident!("ApproxZip{}State", num_fields);
+    let outbound_struct = format_ident!("Zip{}Outbound", num_fields);
+    let inbound_message_enum = format_ident!("ApproxZip{}InboundMessage", num_fields);
+
+    let type_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("Item{}", i))
+        .collect();
+
+    let item_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("item{}", i))
+        .collect();
+
+    let type_with_bounds_seq: Vec<_> = (0..num_fields)
+        .map(|i| {
+            let ident = format_ident!("Item{}", i);
+            quote! { #ident: Default + Clone + std::fmt::Debug + Sync + Send
+            + 'static}
+        })
+        .collect();
+
+    let channel: Vec<_> = (0..num_fields)
+        .map(|i| {
+            let item_type = format_ident!("Item{}", i);
+            let type_seq = (0..num_fields).map(|i| format_ident!("Item{}", i));
+
+            quote! {
+                InboundChannel<ApproxZipPair<#i, Key, #item_type>,
+                              #inbound_message_enum<Key, #( #type_seq),*>>
+            }
+        })
+        .collect();
+
+    let expanded = quote! {
+
+        /// Inbound hub for the approximate zip actor.
+        #[derive(Clone, std::fmt::Debug)]
+        pub struct #inbound_struct<
+            Key: IsApproxKey,
+            #( #type_with_bounds_seq ),*
+        > {
+            #(
+                /// Inbound channel for the Nth inbound channel.
+                pub #item_seq: #channel
+            ),*
+        }
+
+        impl<
+                Key: IsApproxKey,
+                #( #type_with_bounds_seq ),*
+            >
+            IsInboundHub<
+                ApproxZipProp,
+                #state_struct<Key, #( #type_seq ),*>,
+                #outbound_struct<Key, #( #type_seq ),*>,
+                NullOutRequests,
+                #inbound_message_enum<Key, #( #type_seq ),*>,
+                NullInRequestMessage,
+            > for #inbound_struct<Key, #( #type_seq ),*>
+        {
+            fn from_builder(
+                builder: &mut ActorBuilder<
+                    ApproxZipProp,
+                    #state_struct<Key, #( #type_seq ),*>,
+                    #outbound_struct<Key, #( #type_seq ),*>,
+                    NullOutRequests,
+                    #inbound_message_enum<Key, #( #type_seq ),*>,
+                    NullInRequestMessage,
+                >,
+                actor_name: &str,
+            ) -> Self {
+                #(
+                let #item_seq = InboundChannel::new(
+                    builder.context,
+                    actor_name,
+                    &builder.sender,
+                    stringify!(#type_seq).to_owned(),
+                );
+                builder
+                    .forward
+                    .insert(#item_seq.name.clone(), Box::new(#item_seq.clone()));
+                )*
+
+                Self { #( #item_seq ),* }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+pub(crate) fn approx_zip_onmessage_n_impl(input: TokenStream) -> TokenStream {
+    let ApproxZipInput { num_fields } = match parse2(input) {
+        Ok(input) => input,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let tuple_struct = format_ident!("Tuple{}", num_fields);
+    let inbound_message_enum = format_ident!("ApproxZip{}InboundMessage", num_fields);
+
+    let type_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("Item{}", i))
+        .collect();
+
+    let type_with_bounds_seq: Vec<_> = (0..num_fields)
+        .map(|i| {
+            let ident = format_ident!("Item{}", i);
+            quote! {
+                #ident: Default + Clone + std::fmt::Debug + Sync + Send + 'static
+            }
+        })
+        .collect();
+
+    let i_seq: Vec<_> = (0..num_fields).collect();
+
+    let front_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("front{}", i))
+        .collect();
+
+    let index_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("index{}", i))
+        .collect();
+
+    let item_seq: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("item{}", i))
+        .collect();
+
+    let item_queue: Vec<_> = (0..num_fields)
+        .map(|i| format_ident!("item{}_queue", i))
+        .collect();
+
+    let case: Vec<_> = (0..num_fields)
+        .map(|i| {
+            let item_type = format_ident!("Item{}", i);
+            let item_queue = format_ident!("item{}_queue", i);
+
+            quote! {
+                #inbound_message_enum::#item_type(msg) => {
+                    state.#item_queue.push_back(msg);
+                    if state.#item_queue.len() > prop.max_queue_len {
+                        state.#item_queue.pop_front();
+                    }
+                }
+            }
+        })
+        .collect();
+
+    let expanded = quote! {
+
+        impl<
+            Key: IsApproxKey,
+            #( #type_with_bounds_seq ),*
+        >
+            HasOnMessage for #inbound_message_enum<Key, #(#type_seq), *>
+        {
+            fn on_message(
+                self,
+                prop: &Self::Prop,
+                state: &mut Self::State,
+                outbound: &Self::OutboundHub,
+                _request: &Self::OutRequestHub)
+            {
+                match self {
+                    #( #case )*
+                }
+
+                loop {
+                    // The latest of the oldest messages is the pivot, since all earlier
+                    // messages are either zipped with it or too old to be zipped at all.
+                    let (pivot_index, pivot) = {
+                        #(
+                            let Some(#front_seq) = state.#item_queue.front() else {
+                                break;
+                            };
+                        )*
+                        let keys = [#( &#front_seq.key ),*];
+                        let index = approx_latest(&keys);
+                        (index, keys[index].clone())
+                    };
+                    #(
+                        while state.#item_queue.front().is_some_and(|pair| {
+                            approx_is_stale(&pair.key, &pivot, prop.tolerance)
+                        }) {
+                            state.#item_queue.pop_front();
+                        }
+                    )*
+                    let results = [
+                        #(
+                            approx_match(
+                                state.#item_queue.iter().map(|pair| &pair.key),
+                                &pivot,
+                                prop.tolerance,
+                            )
+                        ),*
+                    ];
+                    if results.iter().all(|r| matches!(r, ApproxMatch::Found(_))) {
+                        #(
+                            let ApproxMatch::Found(#index_seq) = results[#i_seq] else {
+                                unreachable!()
+                            };
+                            let #item_seq = state.#item_queue.drain(..=#index_seq).last().unwrap();
+                        )*
+                        outbound.zipped.send(#tuple_struct {
+                            key: pivot,
+                            #( #item_seq: #item_seq.value ),*
+                        });
+                    } else if results.contains(&ApproxMatch::None) {
+                        // There is no match for the pivot.
+                        match pivot_index {
+                            #( #i_seq => { state.#item_queue.pop_front(); } )*
+                            _ => unreachable!(),
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -0,0 +1,293 @@
+use convert_case::Case;
+use convert_case::Casing;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse2;
+use syn::spanned::Spanned;
+use syn::Error;
+use syn::FnArg;
+use syn::Ident;
+use syn::ItemTrait;
+use syn::Result;
+use syn::ReturnType;
+use syn::Token;
+use syn::TraitItem;
+use syn::Type;
+
+struct ResourceActorArgs {
+    actor_name: Ident,
+    resource_type: Type,
+}
+
+impl Parse for ResourceActorArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let actor_name: Ident = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let resource_type: Type = input.parse()?;
+        Ok(ResourceActorArgs {
+            actor_name,
+            resource_type,
+        })
+    }
+}
+
+struct Operation {
+    method_name: Ident,
+    variant_name: Ident,
+    docs: Vec<syn::Attribute>,
+    request_type: Type,
+    reply_type: Type,
+}
+
+fn parse_operation(item: &TraitItem) -> Result<Operation> {
+    let TraitItem::Fn(method) = item else {
+        return Err(Error::new(
+            item.span(),
+            "resource_actor traits may only contain methods",
+        ));
+    };
+    let sig = &method.sig;
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+        _ => {
+            return Err(Error::new(
+                sig.span(),
+                "resource operations must take `&mut self`",
+            ))
+        }
+    }
+    let request_type = match (inputs.next(), inputs.next()) {
+        (Some(FnArg::Typed(arg)), None) => (*arg.ty).clone(),
+        _ => {
+            return Err(Error::new(
+                sig.span(),
+                "resource operations must take exactly one argument besides `&mut self`",
+            ))
+        }
+    };
+    let reply_type = match &sig.output {
+        ReturnType::Default => syn::parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    let method_name = sig.ident.clone();
+    let variant_name = Ident::new(
+        &method_name.to_string().to_case(Case::UpperCamel),
+        method_name.span(),
+    );
+    let docs = method
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .cloned()
+        .collect();
+    Ok(Operation {
+        method_name,
+        variant_name,
+        docs,
+        request_type,
+        reply_type,
+    })
+}
+
+pub(crate) fn resource_actor_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ResourceActorArgs {
+        actor_name,
+        resource_type,
+    } = match parse2::<ResourceActorArgs>(attr) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    let ast = match parse2::<ItemTrait>(item) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error(),
+    };
+    let operations = match ast
+        .items
+        .iter()
+        .map(parse_operation)
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(operations) => operations,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let trait_name = &ast.ident;
+    let inbound_message = format_ident!("{}InboundMessage", actor_name);
+    let inbound = format_ident!("{}Inbound", actor_name);
+    let in_request_message = format_ident!("{}InRequestMessage", actor_name);
+    let in_request = format_ident!("{}InRequest", actor_name);
+    let prop = quote!(<#resource_type as IsResource>::Config);
+    let state = quote!(ResourceState<#resource_type>);
+
+    let variants = operations.iter().map(|op| {
+        let Operation {
+            variant_name,
+            docs,
+            request_type,
+            reply_type,
+            ..
+        } = op;
+        let docs = if docs.is_empty() {
+            let msg = format!("`{}` operation of the resource.", op.method_name);
+            quote!(#[doc = #msg])
+        } else {
+            quote!(#(#docs)*)
+        };
+        quote! {
+            #docs
+            #variant_name(RequestWithReplyChannel<#request_type, #reply_type>)
+        }
+    });
+
+    let request_arms = operations.iter().map(|op| {
+        let Operation {
+            method_name,
+            variant_name,
+            ..
+        } = op;
+        quote! {
+            #in_request_message::#variant_name(request) => {
+                let resource = state.resource(prop);
+                request.reply_from_request(|request| {
+                    <#resource_type as #trait_name>::#method_name(resource, request)
+                });
+            }
+        }
+    });
+
+    // Operations with the same request and reply types share one IsInRequestMessageNew
+    // implementation, which dispatches on the in-request channel name.
+    let mut groups: Vec<(String, Vec<&Operation>)> = vec![];
+    for op in &operations {
+        let request_type = &op.request_type;
+        let reply_type = &op.reply_type;
+        let key = quote!(#request_type, #reply_type).to_string();
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, ops)) => ops.push(op),
+            None => groups.push((key, vec![op])),
+        }
+    }
+    let new_impls = groups.iter().map(|(_, ops)| {
+        let (last, others) = ops.split_last().unwrap();
+        let request_type = &last.request_type;
+        let reply_type = &last.reply_type;
+        let arms = others.iter().map(|op| {
+            let variant_name = &op.variant_name;
+            quote! {
+                stringify!(#variant_name) => #in_request_message::#variant_name(request),
+            }
+        });
+        let last_variant = &last.variant_name;
+        quote! {
+            impl IsInRequestMessageNew<RequestWithReplyChannel<#request_type, #reply_type>>
+                for #in_request_message
+            {
+                fn new(
+                    inbound_channel: String,
+                    request: RequestWithReplyChannel<#request_type, #reply_type>,
+                ) -> Self {
+                    match inbound_channel.as_str() {
+                        #(#arms)*
+                        _ => #in_request_message::#last_variant(request),
+                    }
+                }
+            }
+        }
+    });
+
+    let inbound_doc = format!("Inbound message for the {} resource actor.", actor_name);
+    let in_request_doc = format!("In-request message for the {} resource actor.", actor_name);
+    let actor_doc = format!(
+        "Resource actor which owns a [{}] and exposes the [{}] operations as in-requests.",
+        quote!(#resource_type),
+        trait_name
+    );
+
+    quote! {
+        #ast
+
+        #[doc = #inbound_doc]
+        #[derive(Clone, Debug)]
+        #[actor_inputs(
+            #inbound,
+            {
+                #prop,
+                #state,
+                NullOutbound,
+                NullOutRequests,
+                #in_request_message
+            })]
+        pub enum #inbound_message {
+            /// Closes the resource. It is reopened on the next operation.
+            Close(()),
+        }
+
+        impl HasOnMessage for #inbound_message {
+            fn on_message(
+                self,
+                _prop: &Self::Prop,
+                state: &mut Self::State,
+                _outbound: &Self::OutboundHub,
+                _request: &Self::OutRequestHub,
+            ) {
+                match self {
+                    #inbound_message::Close(()) => state.close(),
+                }
+            }
+        }
+
+        impl IsInboundMessageNew<()> for #inbound_message {
+            fn new(_inbound_channel: String, _msg: ()) -> Self {
+                #inbound_message::Close(())
+            }
+        }
+
+        #[doc = #in_request_doc]
+        #[derive(Debug)]
+        #[actor_in_requests(
+            #in_request,
+            {
+                #prop,
+                #state,
+                NullOutbound,
+                NullOutRequests,
+                #inbound_message
+            }
+        )]
+        pub enum #in_request_message {
+            #(#variants),*
+        }
+
+        impl HasOnRequestMessage for #in_request_message {
+            fn on_request(
+                self,
+                prop: &Self::Prop,
+                state: &mut Self::State,
+                _outbound: &Self::OutboundHub,
+                _request: &Self::OutRequestHub,
+            ) {
+                match self {
+                    #(#request_arms)*
+                }
+            }
+        }
+
+        #(#new_impls)*
+
+        #[doc = #actor_doc]
+        #[actor(#inbound_message, #in_request_message)]
+        type #actor_name = Actor<
+            #prop,
+            #inbound,
+            #in_request,
+            #state,
+            NullOutbound,
+            NullOutRequests,
+        >;
+    }
+}
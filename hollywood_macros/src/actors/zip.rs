@@ -40,6 +40,7 @@ pub(crate) fn tuple_n_impl(input: TokenStream) -> TokenStream {
 
     let expanded = quote! {
         #[derive(Default, Clone, std::fmt::Debug)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         /// A tuple struct with N fields.
         /// Used to send merged items from N inbound channels to one outbound channel.
         pub struct #tuple_struct<Key, #( #type_seq ),*>
@@ -54,6 +55,54 @@ pub(crate) fn tuple_n_impl(input: TokenStream) -> TokenStream {
             ),*
         }
 
+        impl<Key, #( #type_seq ),*> #tuple_struct<Key, #( #type_seq ),*>
+        where
+            Key: Default + Clone + std::fmt::Debug,
+        {
+            /// Number of zipped items (excluding the key).
+            pub const NUM_ITEMS: usize = #num_fields;
+
+            /// Create a new tuple from the key and the zipped items.
+            #[allow(clippy::too_many_arguments)]
+            pub fn new(key: Key, #( #field_seq: #type_seq ),*) -> Self {
+                Self { key, #( #field_seq ),* }
+            }
+
+            /// Splits the tuple into the key and the zipped items.
+            pub fn into_parts(self) -> (Key, #( #type_seq ),*) {
+                (self.key, #( self.#field_seq ),*)
+            }
+        }
+
+        impl<Key, #( #type_seq ),*> #tuple_struct<Key, #( #type_seq ),*>
+        where
+            Key: Default + Clone + std::fmt::Debug,
+            #( #type_seq: std::fmt::Debug ),*
+        {
+            /// Returns the zipped items (excluding the key) for uniform iteration.
+            pub fn items(&self) -> [&dyn std::fmt::Debug; #num_fields] {
+                [#( &self.#field_seq ),*]
+            }
+        }
+
+        impl<Key, #( #type_seq ),*> From<(Key, #( #type_seq ),*)> for #tuple_struct<Key, #( #type_seq ),*>
+        where
+            Key: Default + Clone + std::fmt::Debug,
+        {
+            fn from((key, #( #field_seq ),*): (Key, #( #type_seq ),*)) -> Self {
+                Self { key, #( #field_seq ),* }
+            }
+        }
+
+        impl<Key, #( #type_seq ),*> From<#tuple_struct<Key, #( #type_seq ),*>> for (Key, #( #type_seq ),*)
+        where
+            Key: Default + Clone + std::fmt::Debug,
+        {
+            fn from(tuple: #tuple_struct<Key, #( #type_seq ),*>) -> Self {
+                tuple.into_parts()
+            }
+        }
+
         impl<Key, #( #type_seq ),*> std::fmt::Display for #tuple_struct<Key, #( #type_seq ),*>
         where
             Key: Default + Clone + std::fmt::Debug + std::fmt::Display + PartialEq + Eq
@@ -5,6 +5,8 @@ use quote::quote;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::parse2;
+use syn::spanned::Spanned;
+use syn::Attribute;
 use syn::Error;
 use syn::Fields;
 use syn::Generics;
@@ -19,6 +21,33 @@ use syn::Token;
 use syn::Type;
 use syn::TypePath;
 
+/// Returns the `cfg` attributes of a variant or field, which are forwarded to all code generated
+/// for it, so that conditionally compiled channels are supported.
+fn cfg_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect()
+}
+
+/// Returns the attributes of a variant which are forwarded to the generated channel field: doc
+/// comments, `cfg`, `cfg_attr` and `allow`.
+///
+/// If there are no doc comments, the given fallback doc is added instead.
+fn field_attrs(attrs: &[Attribute], fallback_doc: &str) -> TokenStream {
+    let forwarded = attrs.iter().filter(|attr| {
+        ["doc", "cfg", "cfg_attr", "allow"]
+            .iter()
+            .any(|name| attr.path().is_ident(name))
+    });
+    let fallback = (!attrs.iter().any(|attr| attr.path().is_ident("doc")))
+        .then(|| quote!(#[doc = #fallback_doc]));
+    quote! {
+        #fallback
+        #(#forwarded)*
+    }
+}
+
 pub(crate) fn actor_outputs_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let ast = match parse2::<ItemStruct>(item) {
         Ok(ast) => ast,
@@ -35,31 +64,37 @@ pub(crate) fn actor_outputs_impl(_attr: TokenStream, item: TokenStream) -> Token
 
     let output_assignments = fields.iter().map(|field| {
         let field_name = &field.ident;
-        if let Some(inner_ty) = is_output_type(&field.ty) {
+        let cfgs = cfg_attrs(&field.attrs);
+        if let Some((channel, inner_ty)) = is_output_type(&field.ty) {
             quote! {
-                #field_name: OutboundChannel::<#inner_ty>::new(
+                #(#cfgs)*
+                #field_name: #channel::<#inner_ty>::new(
                     context,
                     stringify!(#field_name).to_owned(),
                     actor_name,
                 )
             }
         } else {
-            panic!("field type must be OutboundChannel<T>.");
+            panic!("field type must be OutboundChannel<T> or PooledOutboundChannel<T>.");
         }
     });
 
     let output_extract = fields.iter().map(|field| {
         let field_name = &field.ident;
+        let cfgs = cfg_attrs(&field.attrs);
 
         quote! {
+            #(#cfgs)*
             #field_name: self.#field_name.extract()
         }
     });
 
     let output_act = fields.iter().map(|field| {
         let field_name = &field.ident;
+        let cfgs = cfg_attrs(&field.attrs);
 
         quote! {
+            #(#cfgs)*
             self.#field_name.activate();
         }
     });
@@ -90,18 +125,22 @@ pub(crate) fn actor_outputs_impl(_attr: TokenStream, item: TokenStream) -> Token
     gen.into()
 }
 
-// This function checks if the field's type is OutboundChannel<T> and return T if it is
-fn is_output_type(ty: &Type) -> Option<&Type> {
+// This function checks if the field's type is OutboundChannel<T> or PooledOutboundChannel<T> and
+// returns the channel ident and T if it is
+fn is_output_type(ty: &Type) -> Option<(&Ident, &Type)> {
     if let Type::Path(TypePath {
         path: Path { segments, .. },
         ..
     }) = ty
     {
-        if segments.len() == 1 && segments[0].ident == "OutboundChannel" {
+        if segments.len() == 1
+            && (segments[0].ident == "OutboundChannel"
+                || segments[0].ident == "PooledOutboundChannel")
+        {
             if let PathArguments::AngleBracketed(args) = &segments[0].arguments {
                 if args.args.len() == 1 {
                     if let syn::GenericArgument::Type(inner_ty) = args.args.first().unwrap() {
-                        return Some(inner_ty);
+                        return Some((&segments[0].ident, inner_ty));
                     }
                 }
             }
@@ -124,10 +163,15 @@ pub(crate) fn actor_out_requests_impl(_attr: TokenStream, item: TokenStream) ->
         _ => panic!("`generate_outputs_trait` can only be used with structs with named fields"),
     };
 
+    // Fields are either OutRequestChannel or OutRequestStreamChannel, which share the signature
+    // of their constructors.
     let request_assignments = fields.iter().map(|field| {
         let field_name = &field.ident;
+        let field_type = &field.ty;
+        let cfgs = cfg_attrs(&field.attrs);
         quote! {
-            #field_name: OutRequestChannel::new(
+            #(#cfgs)*
+            #field_name: <#field_type>::new(
                 stringify!(#field_name).to_owned(),
                 actor_name,
                 sender,
@@ -137,20 +181,34 @@ pub(crate) fn actor_out_requests_impl(_attr: TokenStream, item: TokenStream) ->
 
     let request_extract = fields.iter().map(|field| {
         let field_name = &field.ident;
+        let cfgs = cfg_attrs(&field.attrs);
 
         quote! {
+            #(#cfgs)*
             #field_name: self.#field_name.extract()
         }
     });
 
     let output_act = fields.iter().map(|field| {
         let field_name = &field.ident;
+        let cfgs = cfg_attrs(&field.attrs);
 
         quote! {
+            #(#cfgs)*
             self.#field_name.activate();
         }
     });
 
+    let request_deact = fields.iter().map(|field| {
+        let field_name = &field.ident;
+        let cfgs = cfg_attrs(&field.attrs);
+
+        quote! {
+            #(#cfgs)*
+            self.#field_name.deactivate();
+        }
+    });
+
     let field0 = fields
         .first()
         .expect("Request struct must have at least one field");
@@ -177,6 +235,10 @@ pub(crate) fn actor_out_requests_impl(_attr: TokenStream, item: TokenStream) ->
             fn activate(&mut self) {
                 #(#output_act)*
             }
+
+            fn deactivate(&mut self) {
+                #(#request_deact)*
+            }
         }
 
         #ast
@@ -185,14 +247,18 @@ pub(crate) fn actor_out_requests_impl(_attr: TokenStream, item: TokenStream) ->
     gen.into()
 }
 
-// This function checks if the field's type is OutRequestChannel<Request, Reply, M>
+// This function checks if the field's type is OutRequestChannel<Request, Reply, M> or
+// OutRequestStreamChannel<Request, Item, M>
 fn is_out_request_type(ty: &Type) -> Option<[&Type; 3]> {
     if let Type::Path(TypePath {
         path: Path { segments, .. },
         ..
     }) = ty
     {
-        if segments.len() == 1 && segments[0].ident == "OutRequestChannel" {
+        if segments.len() == 1
+            && (segments[0].ident == "OutRequestChannel"
+                || segments[0].ident == "OutRequestStreamChannel")
+        {
             if let PathArguments::AngleBracketed(args) = &segments[0].arguments {
                 if args.args.len() == 3 {
                     let mut pop_iter = args.args.iter();
@@ -222,10 +288,27 @@ pub fn actor_inputs_impl(args: TokenStream, inbound: TokenStream) -> TokenStream
         Ok(args) => args,
         Err(err) => return err.to_compile_error(),
     };
-    let ast = match parse2::<ItemEnum>(inbound) {
+    let mut ast = match parse2::<ItemEnum>(inbound) {
         Ok(ast) => ast,
         Err(err) => return err.to_compile_error(),
     };
+    // The `#[priority]` attribute marks high-priority channels, and is not part of the enum.
+    let priority_variants: Vec<Ident> = ast
+        .variants
+        .iter()
+        .filter(|variant| {
+            variant
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("priority"))
+        })
+        .map(|variant| variant.ident.clone())
+        .collect();
+    for variant in ast.variants.iter_mut() {
+        variant
+            .attrs
+            .retain(|attr| !attr.path().is_ident("priority"));
+    }
 
     let name = &ast.ident;
     let generics = &ast.generics;
@@ -246,15 +329,18 @@ pub fn actor_inputs_impl(args: TokenStream, inbound: TokenStream) -> TokenStream
             "`{}` channel field - autogenerated by the [actor_inputs] macro.",
             variant_name
         );
+        let attrs = field_attrs(&variant.attrs, &msg);
         quote! {
-            #[doc = #msg]
+            #attrs
             pub #snake_case_variant_name: InboundChannel<#field_type, #name #ty_generics>
         }
     });
 
     let match_arm = fields.iter().map(|variant| {
         let variant_name = &variant.ident;
+        let cfgs = cfg_attrs(&variant.attrs);
         quote! {
+            #(#cfgs)*
             #name::#variant_name(_) => {
                 stringify!(#variant_name).to_string()
             }
@@ -272,13 +358,29 @@ pub fn actor_inputs_impl(args: TokenStream, inbound: TokenStream) -> TokenStream
             generics.params.len()
         );
 
+        let cfgs = cfg_attrs(&variant.attrs);
+        let channel = if priority_variants.contains(variant_name) {
+            quote! {
+                InboundChannel::new_priority(
+                    builder,
+                    actor_name.clone(),
+                    stringify!(#variant_name).to_owned(),
+                )
+            }
+        } else {
+            quote! {
+                InboundChannel::new(
+                    &mut builder.context,
+                    actor_name.clone(),
+                    &builder.sender,
+                    stringify!(#variant_name).to_owned(),
+                )
+            }
+        };
         quote! {
-            let #snake_case_variant_name = InboundChannel::new(
-                &mut builder.context,
-                actor_name.clone(),
-                &builder.sender,
-                stringify!(#variant_name).to_owned(),
-            );
+            #(#cfgs)*
+            let #snake_case_variant_name = #channel;
+            #(#cfgs)*
             builder.forward.insert(
                 #snake_case_variant_name.name.clone(),
                 Box::new(#snake_case_variant_name.clone())
@@ -291,15 +393,23 @@ pub fn actor_inputs_impl(args: TokenStream, inbound: TokenStream) -> TokenStream
         let snake_case_variant_name_str = variant_name.to_string().to_case(Case::Snake);
         let snake_case_variant_name = Ident::new(&snake_case_variant_name_str, variant_name.span());
 
+        let cfgs = cfg_attrs(&variant.attrs);
+
         quote! {
+            #(#cfgs)*
             #snake_case_variant_name,
         }
     });
 
+    let hub_doc = format!(
+        "Inbound hub with one channel per variant of [{}] - autogenerated by the \
+         [actor_inputs] macro.",
+        name
+    );
     let gen = quote! {
         #ast
 
-        /// Auto-generated inbound hub for actor.
+        #[doc = #hub_doc]
         pub struct #struct_name #impl_generics #where_clause {
             #(#inbound),*
         }
@@ -417,18 +527,21 @@ pub fn actor_in_requests_impl(args: TokenStream, inbound: TokenStream) -> TokenS
         };
 
         let msg = format!(
-            "`{}` channel field - autogenerated by the [actor_inputs] macro.",
+            "`{}` channel field - autogenerated by the [actor_in_requests] macro.",
             variant_name
         );
+        let attrs = field_attrs(&variant.attrs, &msg);
         quote! {
-            #[doc = #msg]
+            #attrs
             pub #snake_case_variant_name: InRequestChannel<#field_type, #name #ty_generics>
         }
     });
 
     let match_arm = fields.iter().map(|variant| {
         let variant_name = &variant.ident;
+        let cfgs = cfg_attrs(&variant.attrs);
         quote! {
+            #(#cfgs)*
             #name::#variant_name(_) => {
                 stringify!(#variant_name).to_string()
             }
@@ -452,14 +565,16 @@ pub fn actor_in_requests_impl(args: TokenStream, inbound: TokenStream) -> TokenS
             panic!("Enum variants must be tuples");
         };
 
+        let cfgs = cfg_attrs(&variant.attrs);
         quote! {
-
-            let #snake_case_variant_name = InRequestChannel::<#field_type, #name>::new(
+            #(#cfgs)*
+            let #snake_case_variant_name = InRequestChannel::<#field_type, #name #ty_generics>::new(
                 &mut builder.context,
                 actor_name.clone(),
                 &builder.request_sender,
                 stringify!(#variant_name).to_owned(),
             );
+            #(#cfgs)*
             builder.forward_request.insert(
                 #snake_case_variant_name.name.clone(),
                 Box::new(#snake_case_variant_name.clone())
@@ -472,15 +587,23 @@ pub fn actor_in_requests_impl(args: TokenStream, inbound: TokenStream) -> TokenS
         let snake_case_variant_name_str = variant_name.to_string().to_case(Case::Snake);
         let snake_case_variant_name = Ident::new(&snake_case_variant_name_str, variant_name.span());
 
+        let cfgs = cfg_attrs(&variant.attrs);
+
         quote! {
+            #(#cfgs)*
             #snake_case_variant_name,
         }
     });
 
+    let hub_doc = format!(
+        "In-request hub with one channel per variant of [{}] - autogenerated by the \
+         [actor_in_requests] macro.",
+        name
+    );
     let gen = quote! {
         #ast
 
-        /// Auto-generated inbound hub for actor.
+        #[doc = #hub_doc]
         pub struct #struct_name #impl_generics #where_clause {
             #(#in_requests),*
         }
@@ -693,3 +816,331 @@ pub fn actor_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     gen.into()
 }
+
+pub(crate) fn pipeline_impl(input: TokenStream) -> TokenStream {
+    let PipelineWiring {
+        context,
+        connections,
+    } = match parse2::<PipelineWiring>(input) {
+        Ok(wiring) => wiring,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let connects = connections.iter().flat_map(|connection| {
+        let source = &connection.source;
+        let outbound = &connection.outbound;
+        let context = &context;
+        connection.sinks.iter().map(move |(sink, inbound)| {
+            quote! {
+                #source.outbound.#outbound.connect(#context, &mut #sink.inbound.#inbound);
+            }
+        })
+    });
+
+    quote! {
+        #( #connects )*
+    }
+}
+
+// Wiring of the pipeline macro:
+//
+// ``` text
+// context: CONTEXT;                  (optional, defaults to `context`)
+// SOURCE.OUTBOUND -> SINK.INBOUND;
+// SOURCE.OUTBOUND -> SINK0.INBOUND0, SINK1.INBOUND1;
+// ```
+struct PipelineWiring {
+    context: syn::Expr,
+    connections: Vec<PipelineConnection>,
+}
+
+struct PipelineConnection {
+    source: Ident,
+    outbound: Ident,
+    sinks: Vec<(Ident, Ident)>,
+}
+
+fn parse_channel(input: ParseStream) -> Result<(Ident, Ident)> {
+    let actor: Ident = input.parse()?;
+    let _: Token![.] = input.parse()?;
+    let channel: Ident = input.parse()?;
+    Ok((actor, channel))
+}
+
+impl Parse for PipelineWiring {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut context: syn::Expr = syn::parse_quote!(context);
+        if input.peek(Ident) && input.peek2(Token![:]) {
+            let keyword: Ident = input.parse()?;
+            if keyword != "context" {
+                return Err(Error::new(keyword.span(), "expected `context: <expr>;`"));
+            }
+            let _: Token![:] = input.parse()?;
+            context = input.parse()?;
+            let _: Token![;] = input.parse()?;
+        }
+
+        let mut connections = vec![];
+        while !input.is_empty() {
+            let (source, outbound) = parse_channel(input)?;
+            let _: Token![->] = input.parse()?;
+            let mut sinks = vec![parse_channel(input)?];
+            while input.peek(Token![,]) {
+                let _: Token![,] = input.parse()?;
+                sinks.push(parse_channel(input)?);
+            }
+            let _: Token![;] = input.parse()?;
+            connections.push(PipelineConnection {
+                source,
+                outbound,
+                sinks,
+            });
+        }
+        Ok(PipelineWiring {
+            context,
+            connections,
+        })
+    }
+}
+
+pub(crate) fn hollywood_test_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match syn::parse::Parser::parse2(
+        syn::punctuated::Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated,
+        attr,
+    ) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    let ast = match parse2::<ItemEnum>(item.clone()) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error(),
+    };
+    if !ast.generics.params.is_empty() {
+        return Error::new(
+            ast.generics.span(),
+            "hollywood_test does not support generic inbound messages",
+        )
+        .to_compile_error();
+    }
+
+    let mut prop: syn::Expr = syn::parse_quote!(Default::default());
+    let mut state: syn::Expr = syn::parse_quote!(Default::default());
+    for arg in args {
+        if arg.path.is_ident("prop") {
+            prop = arg.value;
+        } else if arg.path.is_ident("state") {
+            state = arg.value;
+        } else {
+            return Error::new(
+                arg.path.span(),
+                "expected `prop = <expr>` or `state = <expr>`",
+            )
+            .to_compile_error();
+        }
+    }
+
+    let name = &ast.ident;
+    let messages = ast.variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        match &variant.fields {
+            Fields::Unnamed(fields) => {
+                let defaults = fields.unnamed.iter().map(|_| quote!(Default::default()));
+                quote!(#name::#variant_name(#(#defaults),*))
+            }
+            Fields::Named(fields) => {
+                let defaults = fields.named.iter().map(|field| {
+                    let field_name = &field.ident;
+                    quote!(#field_name: Default::default())
+                });
+                quote!(#name::#variant_name{#(#defaults),*})
+            }
+            Fields::Unit => quote!(#name::#variant_name),
+        }
+    });
+    let mod_name = Ident::new(
+        &format!("{}_hollywood_test", name.to_string().to_case(Case::Snake)),
+        name.span(),
+    );
+
+    quote! {
+        #item
+
+        #[cfg(test)]
+        mod #mod_name {
+            use super::*;
+
+            #[test]
+            fn on_message_does_not_panic() {
+                on_message_smoke_test::<#name>(
+                    #prop,
+                    #state,
+                    vec![#(#messages),*],
+                );
+            }
+        }
+    }
+}
+
+pub(crate) fn actor_client_impl(input: TokenStream) -> TokenStream {
+    let ast = match parse2::<ItemEnum>(input) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error(),
+    };
+    if !ast.generics.params.is_empty() {
+        return Error::new(
+            ast.generics.span(),
+            "ActorClient does not support generic in-request messages",
+        )
+        .to_compile_error();
+    }
+
+    let name = &ast.ident;
+    let name_str = name.to_string();
+    let client_name = Ident::new(
+        &format!(
+            "{}Client",
+            name_str
+                .strip_suffix("InRequestMessage")
+                .filter(|prefix| !prefix.is_empty())
+                .unwrap_or(&name_str)
+        ),
+        name.span(),
+    );
+
+    let mut channels = vec![];
+    for variant in &ast.variants {
+        let field_type = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Error::new(variant.span(), "Enum variants must be tuples")
+                    .to_compile_error()
+            }
+        };
+        let Some([request_type, reply_type]) = request_and_reply_types(field_type) else {
+            return Error::new(
+                field_type.span(),
+                "expected `RequestWithReplyChannel<Request, Reply>`",
+            )
+            .to_compile_error();
+        };
+        let variant_name = &variant.ident;
+        let channel_name = Ident::new(
+            &variant_name.to_string().to_case(Case::Snake),
+            variant_name.span(),
+        );
+        channels.push((
+            variant_name,
+            channel_name,
+            request_type,
+            reply_type,
+            &variant.attrs,
+        ));
+    }
+
+    let fields = channels.iter().map(|(_, channel, request, reply, attrs)| {
+        let cfgs = cfg_attrs(attrs);
+        quote! {
+            #(#cfgs)*
+            #channel: RequestClient<#request, #reply>
+        }
+    });
+    let new_args = channels.iter().map(|(_, channel, request, reply, attrs)| {
+        let cfgs = cfg_attrs(attrs);
+        quote! {
+            #(#cfgs)*
+            #channel: RequestClient<#request, #reply>
+        }
+    });
+    let new_init = channels.iter().map(|(_, channel, _, _, attrs)| {
+        let cfgs = cfg_attrs(attrs);
+        quote!(#(#cfgs)* #channel)
+    });
+    let methods = channels
+        .iter()
+        .map(|(variant, channel, request, reply, attrs)| {
+            let doc = format!(
+                "Sends a request to the `{}` channel and waits for the reply.",
+                variant
+            );
+            let attrs = field_attrs(attrs, &doc);
+            quote! {
+                #attrs
+                pub async fn #channel(&self, request: #request) -> Result<#reply, RequestClientError> {
+                    self.#channel.request(request).await
+                }
+            }
+        });
+
+    // If the in-request hub is generated by the actor_in_requests macro, it can create the
+    // client directly.
+    let hub_impl = ast
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("actor_in_requests"))
+        .and_then(|attr| attr.parse_args::<ActorInRequests>().ok())
+        .map(|args| {
+            let hub_name = args.struct_name;
+            let clients = channels.iter().map(|(_, channel, _, _, attrs)| {
+                let cfgs = cfg_attrs(attrs);
+                quote!(#(#cfgs)* #channel: self.#channel.client())
+            });
+            let doc = format!(
+                "Returns a [{}] to send requests to the actor from outside the pipeline.",
+                client_name
+            );
+            quote! {
+                impl #hub_name {
+                    #[doc = #doc]
+                    pub fn client(&self) -> #client_name {
+                        #client_name {
+                            #(#clients),*
+                        }
+                    }
+                }
+            }
+        });
+
+    let doc = format!(
+        "Async client for the in-request channels of [{}] - autogenerated by the ActorClient \
+         derive macro.",
+        name
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone)]
+        pub struct #client_name {
+            #(#fields),*
+        }
+
+        impl #client_name {
+            /// Creates the client from the request clients of the in-request channels.
+            pub fn new(#(#new_args),*) -> Self {
+                Self {
+                    #(#new_init),*
+                }
+            }
+
+            #(#methods)*
+        }
+
+        #hub_impl
+    }
+}
+
+fn request_and_reply_types(ty: &Type) -> Option<[&Type; 2]> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "RequestWithReplyChannel" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    Some([types.next()?, types.next()?])
+}
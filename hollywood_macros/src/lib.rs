@@ -98,3 +98,66 @@ pub fn zip_n(input: TokenStream) -> TokenStream {
 
     TokenStream::from(combined_output)
 }
+
+// See above.
+#[allow(missing_docs)]
+#[proc_macro]
+pub fn approx_zip_n(input: TokenStream) -> TokenStream {
+    let parsed = proc_macro2::TokenStream::from(input.clone());
+    let parsed2 = parsed.clone();
+    let parsed3 = parsed.clone();
+    let parsed4 = parsed.clone();
+    let parsed5 = parsed.clone();
+
+    let output_state_n = actors::approx_zip::approx_zip_state_n_impl(parsed);
+    let output_inbound_message_n = actors::approx_zip::approx_zip_inbound_message_n_impl(parsed2);
+    let output_approx_zip_n = actors::approx_zip::approx_zip_n_impl(parsed3);
+    let output_inbound_n = actors::approx_zip::approx_zip_inbound_n_impl(parsed4);
+    let output_onmessage_n = actors::approx_zip::approx_zip_onmessage_n_impl(parsed5);
+
+    let combined_output = quote! {
+        #output_state_n
+        #output_inbound_message_n
+        #output_approx_zip_n
+        #output_inbound_n
+        #output_onmessage_n
+    };
+
+    TokenStream::from(combined_output)
+}
+
+// See above.
+#[allow(missing_docs)]
+#[proc_macro]
+pub fn pipeline(input: TokenStream) -> TokenStream {
+    core::pipeline_impl(proc_macro2::TokenStream::from(input)).into()
+}
+
+// See above.
+#[allow(missing_docs)]
+#[proc_macro_attribute]
+pub fn hollywood_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    core::hollywood_test_impl(
+        proc_macro2::TokenStream::from(attr),
+        proc_macro2::TokenStream::from(item),
+    )
+    .into()
+}
+
+// See above.
+#[allow(missing_docs)]
+#[proc_macro_attribute]
+pub fn resource_actor(attr: TokenStream, item: TokenStream) -> TokenStream {
+    actors::resource::resource_actor_impl(
+        proc_macro2::TokenStream::from(attr),
+        proc_macro2::TokenStream::from(item),
+    )
+    .into()
+}
+
+// See above.
+#[allow(missing_docs)]
+#[proc_macro_derive(ActorClient)]
+pub fn actor_client(input: TokenStream) -> TokenStream {
+    core::actor_client_impl(proc_macro2::TokenStream::from(input)).into()
+}
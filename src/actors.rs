@@ -1,12 +1,31 @@
 /// The custom periodic actor.
 pub mod periodic;
 pub use periodic::Periodic;
+pub use periodic::PeriodicTiming;
+
+/// Background actor, without inbound and outbound channels.
+pub mod background;
+pub use background::BackgroundActor;
 
 /// Generic printer actor.
 pub mod printer;
 pub use printer::Printer;
 pub use printer::PrinterProp;
 
+/// Bridge which exposes in-request channels as gRPC methods.
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "grpc")]
+pub use grpc::GrpcRequestBridge;
+#[cfg(feature = "grpc")]
+pub use grpc::IsGrpcRequestBridge;
+
+/// Map and filter actors, for stateless transformations between channels.
+pub mod map;
+pub use map::Filter;
+pub use map::Map;
+pub use map::MapProp;
+
 /// Nudge actor.
 pub mod nudge;
 pub use nudge::Nudge;
@@ -25,6 +44,143 @@ pub use zip::Zip7;
 pub use zip::Zip8;
 pub use zip::Zip9;
 
+/// Approximate zip actor, which zips by the nearest keys within a tolerance.
+pub mod approx_zip;
+pub use approx_zip::ApproxZip10;
+pub use approx_zip::ApproxZip11;
+pub use approx_zip::ApproxZip12;
+pub use approx_zip::ApproxZip2;
+pub use approx_zip::ApproxZip3;
+pub use approx_zip::ApproxZip4;
+pub use approx_zip::ApproxZip5;
+pub use approx_zip::ApproxZip6;
+pub use approx_zip::ApproxZip7;
+pub use approx_zip::ApproxZip8;
+pub use approx_zip::ApproxZip9;
+pub use approx_zip::ApproxZipPair;
+pub use approx_zip::ApproxZipProp;
+pub use approx_zip::IsApproxKey;
+
+/// Batcher actor, which collects messages into batches.
+pub mod batcher;
+pub use batcher::Batcher;
+pub use batcher::BatcherProp;
+
+/// Decimate actor, which forwards only every Nth message or one message per time window.
+pub mod decimate;
+pub use decimate::Decimate;
+pub use decimate::DecimateProp;
+
+/// Delay actor, which forwards messages after a latency.
+pub mod delay;
+pub use delay::Delay;
+pub use delay::DelayProp;
+
+/// Failure-injection actor.
+#[cfg(feature = "sim")]
+pub mod faulty;
+#[cfg(feature = "sim")]
+pub use faulty::FaultyActor;
+#[cfg(feature = "sim")]
+pub use faulty::FaultyProp;
+
+/// Recorder of outbound channels to a log file.
+pub mod recorder;
+pub use recorder::ChannelSchema;
+pub use recorder::Recorder;
+
+/// Replay of recorded logs.
+pub mod replay;
+pub use replay::Replay;
+pub use replay::ReplayCommand;
+pub use replay::ReplayStatus;
+pub use replay::ReplayTransport;
+
+/// Event counter actor.
+pub mod counter;
+pub use counter::Counter;
+
+/// Service directory actor.
+pub mod service_directory;
+pub use service_directory::ServiceDirectory;
+pub use service_directory::ServiceEntry;
+
+/// Progress bar actor.
+pub mod progress_bar;
+pub use progress_bar::ProgressBar;
+pub use progress_bar::ProgressBarProp;
+
+/// Resource actors, which own an external resource.
+pub mod resource;
+pub use resource::IsResource;
+pub use resource::ResourceState;
+
+/// Stateful actor, defined by a closure.
+pub mod stateful;
+pub use stateful::Stateful;
+pub use stateful::StatefulProp;
+
+/// Rate controller actor.
+pub mod rate_controller;
+pub use rate_controller::RateController;
+pub use rate_controller::RateControllerProp;
+
+/// Runtime flags actor.
+pub mod runtime_flags;
+pub use runtime_flags::FlagValue;
+pub use runtime_flags::Flags;
+pub use runtime_flags::RuntimeFlags;
+pub use runtime_flags::RuntimeFlagsClient;
+
+/// Bridge actors, which connect pipelines in different processes over TCP or UDP, and the
+/// security of their connections.
+pub mod remote;
+#[cfg(feature = "serde")]
+pub use remote::bridge::RemoteInbound;
+#[cfg(feature = "serde")]
+pub use remote::bridge::RemoteOutbound;
+#[cfg(feature = "serde")]
+pub use remote::bridge::RemoteTransport;
+pub use remote::RemoteSecurity;
+#[cfg(feature = "tls")]
+pub use remote::RemoteTls;
+
+/// Bridge between hollywood channels and ROS2 topics.
+#[cfg(feature = "ros2")]
+pub mod ros2;
+#[cfg(feature = "ros2")]
+pub use ros2::Ros2Bridge;
+
+/// Bridge between hollywood channels and the topics of a message broker.
+#[cfg(feature = "serde")]
+pub mod transport;
+#[cfg(feature = "serde")]
+pub use transport::IsMessageTransport;
+#[cfg(feature = "mqtt")]
+pub use transport::MqttProp;
+#[cfg(feature = "mqtt")]
+pub use transport::MqttTransport;
+#[cfg(feature = "serde")]
+pub use transport::TransportBridge;
+
+/// Throttle actor, which limits the rate of messages.
+pub mod throttle;
+pub use throttle::Throttle;
+pub use throttle::ThrottlePolicy;
+pub use throttle::ThrottleProp;
+
+/// Time sync actor, which maps the timestamps of devices with skewed clocks to the pipeline clock.
+pub mod time_sync;
+pub use time_sync::TimeSync;
+pub use time_sync::TimeSyncMode;
+pub use time_sync::TimeSyncProp;
+
+/// Watchdog actor, which raises an alert if monitored channels go silent.
+pub mod watchdog;
+pub use watchdog::Watchdog;
+pub use watchdog::WatchdogAlert;
+pub use watchdog::WatchdogProp;
+
 /// Egui actor.
 #[cfg(feature = "egui")]
 pub mod egui;
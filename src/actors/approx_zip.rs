@@ -0,0 +1,278 @@
+use crate::actors::zip::Tuple10;
+use crate::actors::zip::Tuple11;
+use crate::actors::zip::Tuple12;
+use crate::actors::zip::Tuple2;
+use crate::actors::zip::Tuple3;
+use crate::actors::zip::Tuple4;
+use crate::actors::zip::Tuple5;
+use crate::actors::zip::Tuple6;
+use crate::actors::zip::Tuple7;
+use crate::actors::zip::Tuple8;
+use crate::actors::zip::Tuple9;
+use crate::actors::zip::Zip10Outbound;
+use crate::actors::zip::Zip11Outbound;
+use crate::actors::zip::Zip12Outbound;
+use crate::actors::zip::Zip2Outbound;
+use crate::actors::zip::Zip3Outbound;
+use crate::actors::zip::Zip4Outbound;
+use crate::actors::zip::Zip5Outbound;
+use crate::actors::zip::Zip6Outbound;
+use crate::actors::zip::Zip7Outbound;
+use crate::actors::zip::Zip8Outbound;
+use crate::actors::zip::Zip9Outbound;
+use crate::prelude::*;
+
+/// Key which can be matched approximately, e.g. a timestamp.
+///
+/// Keys of each inbound channel of an approximate zip actor are expected to be increasing.
+pub trait IsApproxKey:
+    Default + Clone + std::fmt::Debug + PartialOrd + Sync + Send + 'static
+{
+    /// Distance between two keys, which is infinite if the keys are not comparable.
+    fn distance(&self, other: &Self) -> f64;
+}
+
+impl IsApproxKey for f64 {
+    fn distance(&self, other: &Self) -> f64 {
+        (self - other).abs()
+    }
+}
+
+impl IsApproxKey for f32 {
+    fn distance(&self, other: &Self) -> f64 {
+        (self - other).abs() as f64
+    }
+}
+
+impl IsApproxKey for u64 {
+    fn distance(&self, other: &Self) -> f64 {
+        self.abs_diff(*other) as f64
+    }
+}
+
+impl IsApproxKey for i64 {
+    fn distance(&self, other: &Self) -> f64 {
+        self.abs_diff(*other) as f64
+    }
+}
+
+impl IsApproxKey for Timestamp {
+    /// Distance in seconds, which is infinite for timestamps of different clock domains.
+    fn distance(&self, other: &Self) -> f64 {
+        self.seconds_since(other)
+            .map_or(f64::INFINITY, |seconds| seconds.abs())
+    }
+}
+
+/// Type of the Nth inbound channel for the approximate zip actor.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproxZipPair<const N: usize, Key: IsApproxKey, Value> {
+    /// Key to associate messages from different inbound channels with.
+    pub key: Key,
+    /// The value to be zipped.
+    pub value: Value,
+}
+
+impl<const N: usize, Key: IsApproxKey, Value> ApproxZipPair<N, Key, Value> {
+    /// Create a new pair from key and value.
+    pub fn new(key: Key, value: Value) -> Self {
+        Self { key, value }
+    }
+
+    /// Splits the pair into key and value.
+    pub fn into_parts(self) -> (Key, Value) {
+        (self.key, self.value)
+    }
+}
+
+impl<const N: usize, Key: IsApproxKey, Value> From<(Key, Value)> for ApproxZipPair<N, Key, Value> {
+    fn from((key, value): (Key, Value)) -> Self {
+        Self { key, value }
+    }
+}
+
+impl<const N: usize, T> From<Stamped<T>> for ApproxZipPair<N, Timestamp, Stamped<T>> {
+    /// Zips stamped values by their timestamp.
+    fn from(stamped: Stamped<T>) -> Self {
+        Self {
+            key: stamped.time.clone(),
+            value: stamped,
+        }
+    }
+}
+
+/// Configuration properties for the approximate zip actor.
+#[derive(Clone, Debug)]
+pub struct ApproxZipProp {
+    /// Maximal distance of the keys of the zipped messages to the key of the zipped tuple, e.g.
+    /// in seconds for timestamps.
+    pub tolerance: f64,
+    /// Maximal number of messages buffered per inbound channel. If exceeded, the oldest message
+    /// is dropped, e.g. if one of the inbound channels stopped sending.
+    pub max_queue_len: usize,
+}
+
+impl Default for ApproxZipProp {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.0,
+            max_queue_len: 100,
+        }
+    }
+}
+
+/// Result of matching the messages of one inbound channel against a key, see [approx_match()].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApproxMatch {
+    /// The message at the given queue index is the nearest one within the tolerance.
+    Found(usize),
+    /// A nearer message might still arrive.
+    Pending,
+    /// There is no message within the tolerance.
+    None,
+}
+
+/// Index of the latest of the given keys.
+pub fn approx_latest<Key: IsApproxKey>(keys: &[&Key]) -> usize {
+    let mut latest = 0;
+    for (i, key) in keys.iter().enumerate() {
+        if *key > keys[latest] {
+            latest = i;
+        }
+    }
+    latest
+}
+
+/// Whether the given key is too old to be zipped with the pivot key, or any later key.
+pub fn approx_is_stale<Key: IsApproxKey>(key: &Key, pivot: &Key, tolerance: f64) -> bool {
+    key < pivot && key.distance(pivot) > tolerance
+}
+
+/// Matches the given increasing keys of one inbound channel against the pivot key.
+pub fn approx_match<'a, Key: IsApproxKey>(
+    keys: impl Iterator<Item = &'a Key>,
+    pivot: &Key,
+    tolerance: f64,
+) -> ApproxMatch {
+    let mut nearest: Option<(usize, f64)> = None;
+    for (i, key) in keys.enumerate() {
+        let distance = key.distance(pivot);
+        if nearest.is_none_or(|(_, nearest)| distance < nearest) {
+            nearest = Some((i, distance));
+        }
+        // Later keys are further away, and an exact match cannot be beaten.
+        if key >= pivot || distance == 0.0 {
+            return match nearest {
+                Some((i, distance)) if distance <= tolerance => ApproxMatch::Found(i),
+                _ => ApproxMatch::None,
+            };
+        }
+    }
+    ApproxMatch::Pending
+}
+
+approx_zip_n!(2);
+approx_zip_n!(3);
+approx_zip_n!(4);
+approx_zip_n!(5);
+approx_zip_n!(6);
+approx_zip_n!(7);
+approx_zip_n!(8);
+approx_zip_n!(9);
+approx_zip_n!(10);
+approx_zip_n!(11);
+approx_zip_n!(12);
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::approx_zip::approx_is_stale;
+    use crate::actors::approx_zip::approx_latest;
+    use crate::actors::approx_zip::approx_match;
+    use crate::actors::approx_zip::ApproxMatch;
+    use crate::actors::approx_zip::IsApproxKey;
+    use crate::prelude::*;
+
+    fn approx_match_f64(keys: &[f64], pivot: f64, tolerance: f64) -> ApproxMatch {
+        approx_match(keys.iter(), &pivot, tolerance)
+    }
+
+    #[test]
+    fn found() {
+        assert_eq!(
+            approx_match_f64(&[0.0, 1.0, 2.0], 1.0, 0.0),
+            ApproxMatch::Found(1)
+        );
+        // The nearest key is found, whether it is before or after the pivot.
+        assert_eq!(
+            approx_match_f64(&[0.8, 1.3], 1.0, 0.5),
+            ApproxMatch::Found(0)
+        );
+        assert_eq!(
+            approx_match_f64(&[0.6, 1.1, 1.2], 1.0, 0.5),
+            ApproxMatch::Found(1)
+        );
+    }
+
+    #[test]
+    fn pending() {
+        assert_eq!(approx_match_f64(&[], 1.0, 0.5), ApproxMatch::Pending);
+        // A key nearer to the pivot might still arrive.
+        assert_eq!(
+            approx_match_f64(&[0.8, 0.9], 1.0, 0.5),
+            ApproxMatch::Pending
+        );
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(approx_match_f64(&[0.2, 2.0], 1.0, 0.5), ApproxMatch::None);
+        assert_eq!(approx_match_f64(&[1.6], 1.0, 0.5), ApproxMatch::None);
+    }
+
+    #[test]
+    fn tolerance_is_inclusive() {
+        assert_eq!(approx_match([3_u64].iter(), &1, 2.0), ApproxMatch::Found(0));
+        assert_eq!(approx_match([3_u64].iter(), &1, 1.9), ApproxMatch::None);
+        assert_eq!(
+            approx_match([-1_i64, 4].iter(), &1, 2.0),
+            ApproxMatch::Found(0)
+        );
+
+        assert!(!approx_is_stale(&0.5, &1.0, 0.5));
+        assert!(approx_is_stale(&0.4, &1.0, 0.5));
+        // Keys after the pivot are never stale.
+        assert!(!approx_is_stale(&1.6, &1.0, 0.5));
+    }
+
+    #[test]
+    fn latest() {
+        assert_eq!(approx_latest(&[&1.0, &3.0, &2.0]), 1);
+        assert_eq!(approx_latest(&[&2_u64, &2]), 0);
+    }
+
+    #[test]
+    fn timestamps_of_different_clock_domains() {
+        let pivot = Timestamp::monotonic(1.0);
+        let other = Timestamp::monotonic(1.0).with_domain("camera");
+        assert_eq!(other.distance(&pivot), f64::INFINITY);
+        assert_eq!(pivot.distance(&Timestamp::monotonic(1.25)), 0.25);
+
+        // A key of another domain is neither matched nor stale, and does not block a later key
+        // of the same domain from being matched.
+        assert!(!approx_is_stale(&other, &pivot, 0.5));
+        assert_eq!(
+            approx_match([other.clone()].iter(), &pivot, 0.5),
+            ApproxMatch::Pending
+        );
+        assert_eq!(
+            approx_match(
+                [other.clone(), Timestamp::monotonic(1.25)].iter(),
+                &pivot,
+                0.5
+            ),
+            ApproxMatch::Found(1)
+        );
+        assert_eq!(approx_latest(&[&pivot, &other]), 0);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::prelude::*;
+use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
+
+type BackgroundTask = Box<
+    dyn FnOnce(tokio::sync::watch::Receiver<bool>) -> Pin<Box<dyn Future<Output = ()> + Send>>
+        + Send,
+>;
+
+/// Background actor, i.e. an actor with neither inbound nor outbound channels.
+///
+/// It runs an async task as part of the pipeline lifecycle, e.g. a health reporter writing to an
+/// external system:
+///
+/// ``` ignore
+/// BackgroundActor::from_task(context, |mut kill| async move {
+///     loop {
+///         tokio::select! {
+///             _ = kill.changed() => break,
+///             _ = tokio::time::sleep(std::time::Duration::from_secs(1)) => report_health(),
+///         }
+///     }
+///     // on-exit logic goes here
+/// });
+/// ```
+///
+/// The task is started when the pipeline starts. It shall return as soon as the `kill` watch
+/// channel is set to true (or its sender is dropped), since the pipeline waits for all actors to
+/// finish. Any on-exit logic goes after the kill signal within the task. The task runs once, hence
+/// it may take ownership of its captured state.
+pub struct BackgroundActor {
+    /// Unique name of the actor.
+    pub actor_name: String,
+}
+
+impl BackgroundActor {
+    /// Creates a background actor which runs the given task.
+    pub fn from_task<Task, Fut>(context: &mut Hollywood, task: Task) -> Self
+    where
+        Task: FnOnce(tokio::sync::watch::Receiver<bool>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self::from_named_task(context, "Background", task)
+    }
+
+    /// Creates a background actor which runs the given task, with a name hint for the actor name.
+    pub fn from_named_task<Task, Fut>(context: &mut Hollywood, name_hint: &str, task: Task) -> Self
+    where
+        Task: FnOnce(tokio::sync::watch::Receiver<bool>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let actor_name = context.add_new_unique_name(name_hint.to_owned());
+        context.actors.push(Box::new(BackgroundActorNode {
+            name: actor_name.clone(),
+            task: Some(Box::new(move |kill| Box::pin(task(kill)))),
+        }));
+        Self { actor_name }
+    }
+}
+
+/// The active background actor.
+struct BackgroundActorNode {
+    name: String,
+    task: Option<BackgroundTask>,
+}
+
+#[async_trait]
+impl IsActorNode for BackgroundActorNode {
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    async fn run(&mut self, kill: tokio::sync::watch::Receiver<bool>) {
+        if let Some(task) = self.task.take() {
+            task(kill).await;
+        }
+    }
+
+    fn on_exit(&mut self) {
+        // on-exit logic is part of the task
+    }
+}
@@ -0,0 +1,295 @@
+use crate::prelude::*;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Configuration properties for the batcher actor.
+///
+/// A batch is emitted as soon as it is full or its time window elapsed, whichever comes first.
+/// At least one of the two limits should be set, otherwise no batch is ever emitted.
+#[derive(Clone, Debug, Default)]
+pub struct BatcherProp {
+    /// Maximal number of messages per batch. Zero means no limit.
+    pub max_size: usize,
+    /// Time window in seconds, starting with the first message of a batch. None means no limit.
+    pub window: Option<f64>,
+}
+
+impl BatcherProp {
+    fn window(&self) -> Option<Duration> {
+        self.window
+            .map(|window| Duration::try_from_secs_f64(window).unwrap_or_default())
+    }
+}
+
+/// State of the batcher actor.
+#[derive(Clone, Debug)]
+pub struct BatcherState<T> {
+    batch: Vec<T>,
+    /// End of the time window of the current batch, if any.
+    window_end: Option<Duration>,
+    /// Current time of the pipeline clock, see [HasClockTimer::set_now()].
+    now: Duration,
+    num_batches: u64,
+}
+
+impl<T> Default for BatcherState<T> {
+    fn default() -> Self {
+        Self {
+            batch: vec![],
+            window_end: None,
+            now: Duration::ZERO,
+            num_batches: 0,
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> BatcherState<T> {
+    /// Number of emitted batches.
+    pub fn num_batches(&self) -> u64 {
+        self.num_batches
+    }
+
+    /// Number of messages in the current batch.
+    pub fn batch_len(&self) -> usize {
+        self.batch.len()
+    }
+
+    fn emit(&mut self, outbound: &BatcherOutbound<T>) {
+        if self.batch.is_empty() {
+            return;
+        }
+        outbound.batch.send(std::mem::take(&mut self.batch));
+        self.window_end = None;
+        self.num_batches += 1;
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasClockTimer<BatcherProp, BatcherOutbound<T>> for BatcherState<T>
+{
+    fn set_now(&mut self, now: Duration) {
+        self.now = now;
+    }
+
+    fn next_deadline(&self, _prop: &BatcherProp) -> Option<Duration> {
+        self.window_end
+    }
+
+    /// Emits the current batch at the end of its time window.
+    fn on_deadline(&mut self, _prop: &BatcherProp, outbound: &BatcherOutbound<T>) {
+        self.emit(outbound);
+    }
+}
+
+/// Inbound message for the batcher actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    BatcherInbound<T>,
+    {
+        BatcherProp,
+        BatcherState<T>,
+        BatcherOutbound<T>,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum BatcherInboundMessage<T: Default + Debug + Clone + Sync + Send + 'static> {
+    /// Message to be added to the current batch.
+    Value(T),
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> HasOnMessage for BatcherInboundMessage<T> {
+    fn on_message(
+        self,
+        prop: &BatcherProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            BatcherInboundMessage::Value(value) => {
+                if state.batch.is_empty() {
+                    state.window_end = prop.window().map(|window| state.now + window);
+                }
+                state.batch.push(value);
+                if prop.max_size > 0 && state.batch.len() >= prop.max_size {
+                    state.emit(outbound);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> IsInboundMessageNew<T>
+    for BatcherInboundMessage<T>
+{
+    fn new(_inbound_name: String, msg: T) -> Self {
+        BatcherInboundMessage::Value(msg)
+    }
+}
+
+/// Outbound hub of the batcher actor.
+#[actor_outputs]
+pub struct BatcherOutbound<T: 'static + Default + Clone + Send + Sync + std::fmt::Debug> {
+    /// Batches of messages, in the order they were received.
+    pub batch: OutboundChannel<Vec<T>>,
+}
+
+/// Batcher actor, which collects messages into batches by count or by time window:
+///
+/// ``` ignore
+/// let mut batcher = Batcher::<Detection>::new_with_window(context, 0.5);
+/// detector.outbound.detection.connect(context, &mut batcher.inbound.value);
+/// batcher.outbound.batch.connect(context, &mut tracker.inbound.detections);
+/// ```
+///
+/// The incomplete batch, if any, is discarded when the pipeline stops.
+pub type Batcher<T> = GenericActor<
+    BatcherProp,
+    BatcherInbound<T>,
+    NullInRequests,
+    BatcherState<T>,
+    BatcherOutbound<T>,
+    NullOutRequests,
+    BatcherRunner<T>,
+>;
+
+/// The runner of the batcher actor.
+pub type BatcherRunner<T> = ClockRunner<
+    BatcherProp,
+    BatcherInbound<T>,
+    NullInRequests,
+    BatcherState<T>,
+    BatcherOutbound<T>,
+    NullOutRequests,
+>;
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> Batcher<T> {
+    /// Create a new batcher actor, which emits batches of `max_size` messages.
+    pub fn new_with_size(context: &mut Hollywood, max_size: usize) -> Batcher<T> {
+        Batcher::from_prop_and_state(
+            context,
+            BatcherProp {
+                max_size,
+                window: None,
+            },
+            BatcherState::default(),
+        )
+    }
+
+    /// Create a new batcher actor, which emits the messages received within `window` seconds
+    /// after the first message of each batch.
+    pub fn new_with_window(context: &mut Hollywood, window: f64) -> Batcher<T> {
+        Batcher::from_prop_and_state(
+            context,
+            BatcherProp {
+                max_size: 0,
+                window: Some(window),
+            },
+            BatcherState::default(),
+        )
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasFromPropState<
+        BatcherProp,
+        BatcherInbound<T>,
+        NullInRequests,
+        BatcherState<T>,
+        BatcherOutbound<T>,
+        BatcherInboundMessage<T>,
+        NullInRequestMessage,
+        NullOutRequests,
+        BatcherRunner<T>,
+    > for Batcher<T>
+{
+    fn name_hint(_prop: &BatcherProp) -> String {
+        "Batcher".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::Batcher;
+    use crate::core::clock::advance_settled;
+    use crate::core::clock::settle;
+    use crate::prelude::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Batcher which runs on a virtual clock.
+    struct Fixture {
+        clock: Arc<SimClock>,
+        value: InboundInjector<i32>,
+        batches: OutboundSubscriber<Vec<i32>>,
+        running: RunningPipeline,
+    }
+
+    impl Fixture {
+        fn new(create: impl Fn(&mut Hollywood) -> Batcher<i32>) -> Self {
+            let clock = Arc::new(SimClock::new());
+            let mut handles = None;
+            let pipeline = Hollywood::configure(&mut |context| {
+                context.set_clock(clock.clone());
+                let mut batcher = create(context);
+                handles = Some((
+                    batcher.inbound.value.injector(),
+                    batcher.outbound.batch.subscriber(context),
+                ));
+            });
+            let (value, batches) = handles.unwrap();
+            Self {
+                clock,
+                value,
+                batches,
+                running: pipeline.spawn(),
+            }
+        }
+
+        async fn send(&self, value: i32) {
+            self.value.send(value).unwrap();
+            settle().await;
+        }
+
+        fn batches(&mut self) -> Vec<Vec<i32>> {
+            std::iter::from_fn(|| self.batches.try_recv()).collect()
+        }
+
+        async fn stop(self) {
+            self.running.cancel();
+            self.running.join().await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn batches_by_size() {
+        let mut batcher = Fixture::new(|context| Batcher::new_with_size(context, 2));
+        settle().await;
+
+        for i in 1..=5 {
+            batcher.send(i).await;
+        }
+        assert_eq!(batcher.batches(), vec![vec![1, 2], vec![3, 4]]);
+        batcher.stop().await;
+    }
+
+    #[tokio::test]
+    async fn batches_by_window() {
+        let mut batcher = Fixture::new(|context| Batcher::new_with_window(context, 1.0));
+        settle().await;
+
+        batcher.send(1).await;
+        advance_settled(&batcher.clock, Duration::from_secs_f64(0.5)).await;
+        batcher.send(2).await;
+        assert!(batcher.batches().is_empty());
+
+        // The window starts with the first message of the batch.
+        advance_settled(&batcher.clock, Duration::from_secs_f64(0.5)).await;
+        assert_eq!(batcher.batches(), vec![vec![1, 2]]);
+
+        batcher.send(3).await;
+        advance_settled(&batcher.clock, Duration::from_secs(5)).await;
+        assert_eq!(batcher.batches(), vec![vec![3]]);
+        batcher.stop().await;
+    }
+}
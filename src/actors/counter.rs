@@ -0,0 +1,135 @@
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+/// Current counts of the counter actor, by key.
+pub type Counts = BTreeMap<String, u64>;
+
+/// Configuration properties for the counter actor.
+#[derive(Clone, Debug, Default)]
+pub struct CounterProp {
+    /// The current counts are published after every Nth event. Zero disables publishing, in
+    /// which case the counts can only be requested.
+    pub publish_every: u64,
+}
+
+/// State of the counter actor.
+#[derive(Clone, Debug, Default)]
+pub struct CounterState {
+    /// Current counts by key. Counts saturate at u64::MAX.
+    pub counts: Counts,
+    /// Total number of events.
+    pub num_events: u64,
+}
+
+/// Outbound hub for the counter actor.
+#[actor_outputs]
+pub struct CounterOutbound {
+    /// Current counts, published after every Nth event, see [CounterProp::publish_every].
+    pub counts: OutboundChannel<Counts>,
+}
+
+/// Inbound message for the counter actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    CounterInbound,
+    {
+        CounterProp,
+        CounterState,
+        CounterOutbound,
+        NullOutRequests,
+        CounterInRequestMessage
+    })]
+pub enum CounterInboundMessage {
+    /// Key of the event to be counted.
+    Event(String),
+}
+
+impl HasOnMessage for CounterInboundMessage {
+    fn on_message(
+        self,
+        prop: &CounterProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            CounterInboundMessage::Event(key) => {
+                let count = state.counts.entry(key).or_default();
+                *count = count.saturating_add(1);
+                state.num_events = state.num_events.saturating_add(1);
+                if prop.publish_every > 0 && state.num_events.is_multiple_of(prop.publish_every) {
+                    outbound.counts.send(state.counts.clone());
+                }
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<String> for CounterInboundMessage {
+    fn new(_inbound_name: String, msg: String) -> Self {
+        CounterInboundMessage::Event(msg)
+    }
+}
+
+/// In-request message for the counter actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    CounterInRequest,
+    {
+        CounterProp,
+        CounterState,
+        CounterOutbound,
+        NullOutRequests,
+        CounterInboundMessage
+    })]
+pub enum CounterInRequestMessage {
+    /// Request for the current counts.
+    Counts(RequestWithReplyChannel<(), Counts>),
+}
+
+impl HasOnRequestMessage for CounterInRequestMessage {
+    fn on_request(
+        self,
+        _prop: &CounterProp,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            CounterInRequestMessage::Counts(request) => {
+                request.reply(state.counts.clone());
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<(), Counts>> for CounterInRequestMessage {
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<(), Counts>) -> Self {
+        CounterInRequestMessage::Counts(request)
+    }
+}
+
+/// Counter actor, a simple observability building block.
+///
+/// It counts inbound events by key. Events of any type are counted by connecting them with an
+/// adapter which extracts the key, e.g. to count messages by variant:
+///
+/// ``` ignore
+/// source.outbound.value.connect_with_adapter(
+///     context,
+///     |v| if v.is_valid() { "valid" } else { "invalid" }.to_owned(),
+///     &mut counter.inbound.event,
+/// );
+/// ```
+///
+/// The current counts are published periodically (see [CounterProp::publish_every]) and can be
+/// requested through the `counts` in-request channel.
+#[actor(CounterInboundMessage, CounterInRequestMessage)]
+type Counter = Actor<
+    CounterProp,
+    CounterInbound,
+    CounterInRequest,
+    CounterState,
+    CounterOutbound,
+    NullOutRequests,
+>;
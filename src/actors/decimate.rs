@@ -0,0 +1,277 @@
+use crate::prelude::*;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Configuration properties for the decimate actor.
+///
+/// If both limits are set, a message is forwarded only if it passes both.
+#[derive(Clone, Debug, Default)]
+pub struct DecimateProp {
+    /// Only every Nth message is forwarded, starting with the first one. Zero and one forward all
+    /// messages.
+    pub every_nth: u64,
+    /// Time window in seconds. At most one message, the first one, is forwarded per window. The
+    /// windows are aligned to the origin of the pipeline clock. None means no limit.
+    pub window: Option<f64>,
+}
+
+impl DecimateProp {
+    fn window(&self) -> Option<Duration> {
+        self.window
+            .map(|window| Duration::try_from_secs_f64(window).unwrap_or_default())
+            .filter(|window| !window.is_zero())
+    }
+}
+
+/// State of the decimate actor.
+#[derive(Clone, Debug, Default)]
+pub struct DecimateState {
+    /// Index of the time window of the last forwarded message, if any.
+    last_window: Option<u128>,
+    /// Current time of the pipeline clock, see [HasClockTimer::set_now()].
+    now: Duration,
+    num_received: u64,
+    num_forwarded: u64,
+}
+
+impl DecimateState {
+    /// Number of received messages.
+    pub fn num_received(&self) -> u64 {
+        self.num_received
+    }
+
+    /// Number of forwarded messages.
+    pub fn num_forwarded(&self) -> u64 {
+        self.num_forwarded
+    }
+
+    /// Whether the next message passes both limits, updating the counters.
+    fn pass(&mut self, prop: &DecimateProp) -> bool {
+        let index = self.num_received;
+        self.num_received += 1;
+        if prop.every_nth > 1 && !index.is_multiple_of(prop.every_nth) {
+            return false;
+        }
+        if let Some(window) = prop.window() {
+            let current_window = self.now.as_nanos() / window.as_nanos();
+            if self.last_window == Some(current_window) {
+                return false;
+            }
+            self.last_window = Some(current_window);
+        }
+        self.num_forwarded += 1;
+        true
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasClockTimer<DecimateProp, DecimateOutbound<T>> for DecimateState
+{
+    fn set_now(&mut self, now: Duration) {
+        self.now = now;
+    }
+}
+
+/// Inbound message for the decimate actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    DecimateInbound<T>,
+    {
+        DecimateProp,
+        DecimateState,
+        DecimateOutbound<T>,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum DecimateInboundMessage<T: Default + Debug + Clone + Sync + Send + 'static> {
+    /// Message to be decimated.
+    Value(T),
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> HasOnMessage
+    for DecimateInboundMessage<T>
+{
+    fn on_message(
+        self,
+        prop: &DecimateProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            DecimateInboundMessage::Value(value) => {
+                if state.pass(prop) {
+                    outbound.value.send(value);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> IsInboundMessageNew<T>
+    for DecimateInboundMessage<T>
+{
+    fn new(_inbound_name: String, msg: T) -> Self {
+        DecimateInboundMessage::Value(msg)
+    }
+}
+
+/// Outbound hub of the decimate actor.
+#[actor_outputs]
+pub struct DecimateOutbound<T: 'static + Default + Clone + Send + Sync + std::fmt::Debug> {
+    /// Forwarded messages.
+    pub value: OutboundChannel<T>,
+}
+
+/// Decimate actor, which forwards only every Nth message, or at most one message per time window,
+/// e.g. to feed a visualizer from a high-rate sensor:
+///
+/// ``` ignore
+/// let mut decimate = Decimate::<Image>::new_every_nth(context, 10);
+/// camera.outbound.image.connect(context, &mut decimate.inbound.value);
+/// decimate.outbound.value.connect(context, &mut viewer.inbound.image);
+/// ```
+///
+/// As opposed to the [Throttle](crate::actors::Throttle) actor, the decision is made per message
+/// and never delays a message. The time windows are measured using the clock of the pipeline.
+pub type Decimate<T> = GenericActor<
+    DecimateProp,
+    DecimateInbound<T>,
+    NullInRequests,
+    DecimateState,
+    DecimateOutbound<T>,
+    NullOutRequests,
+    DecimateRunner<T>,
+>;
+
+/// The runner of the decimate actor.
+pub type DecimateRunner<T> = ClockRunner<
+    DecimateProp,
+    DecimateInbound<T>,
+    NullInRequests,
+    DecimateState,
+    DecimateOutbound<T>,
+    NullOutRequests,
+>;
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> Decimate<T> {
+    /// Create a new decimate actor, which forwards only every Nth message.
+    pub fn new_every_nth(context: &mut Hollywood, every_nth: u64) -> Decimate<T> {
+        Decimate::from_prop_and_state(
+            context,
+            DecimateProp {
+                every_nth,
+                window: None,
+            },
+            DecimateState::default(),
+        )
+    }
+
+    /// Create a new decimate actor, which forwards at most one message per time window in
+    /// seconds.
+    pub fn new_with_window(context: &mut Hollywood, window: f64) -> Decimate<T> {
+        Decimate::from_prop_and_state(
+            context,
+            DecimateProp {
+                every_nth: 0,
+                window: Some(window),
+            },
+            DecimateState::default(),
+        )
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasFromPropState<
+        DecimateProp,
+        DecimateInbound<T>,
+        NullInRequests,
+        DecimateState,
+        DecimateOutbound<T>,
+        DecimateInboundMessage<T>,
+        NullInRequestMessage,
+        NullOutRequests,
+        DecimateRunner<T>,
+    > for Decimate<T>
+{
+    fn name_hint(_prop: &DecimateProp) -> String {
+        "Decimate".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::decimate::DecimateProp;
+    use crate::actors::decimate::DecimateState;
+    use std::time::Duration;
+
+    /// Which of the messages received at the given times pass.
+    fn passes(prop: &DecimateProp, times: &[f64]) -> Vec<bool> {
+        let mut state = DecimateState::default();
+        let passes = times
+            .iter()
+            .map(|time| {
+                state.now = Duration::from_secs_f64(*time);
+                state.pass(prop)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(state.num_received(), times.len() as u64);
+        assert_eq!(
+            state.num_forwarded(),
+            passes.iter().filter(|pass| **pass).count() as u64
+        );
+        passes
+    }
+
+    #[test]
+    fn every_nth() {
+        let times = [0.0; 7];
+        let prop = DecimateProp {
+            every_nth: 3,
+            window: None,
+        };
+        assert_eq!(
+            passes(&prop, &times),
+            vec![true, false, false, true, false, false, true]
+        );
+        for every_nth in [0, 1] {
+            let prop = DecimateProp {
+                every_nth,
+                window: None,
+            };
+            assert!(passes(&prop, &times).into_iter().all(|pass| pass));
+        }
+    }
+
+    #[test]
+    fn window() {
+        let prop = DecimateProp {
+            every_nth: 0,
+            window: Some(1.0),
+        };
+        // The windows are aligned to the origin of the clock, not to the first message.
+        assert_eq!(
+            passes(&prop, &[0.5, 0.9, 1.0, 1.99, 3.5, 3.6]),
+            vec![true, false, true, false, true, false]
+        );
+        let prop = DecimateProp {
+            every_nth: 0,
+            window: Some(0.0),
+        };
+        assert!(passes(&prop, &[0.0, 0.0]).into_iter().all(|pass| pass));
+    }
+
+    #[test]
+    fn every_nth_and_window() {
+        let prop = DecimateProp {
+            every_nth: 2,
+            window: Some(1.0),
+        };
+        // Every other message is dropped first, and the remaining ones are limited to one per
+        // window.
+        assert_eq!(
+            passes(&prop, &[0.0, 0.2, 0.4, 1.0, 1.2, 2.5]),
+            vec![true, false, false, false, true, false]
+        );
+    }
+}
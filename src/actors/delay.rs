@@ -0,0 +1,350 @@
+use crate::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Configuration properties for the delay actor.
+#[derive(Clone, Debug)]
+pub struct DelayProp<T> {
+    /// Latency in seconds which is added to each message.
+    pub latency: f64,
+    /// Returns the time stamp of the message in seconds, if the latency shall be added to the
+    /// time stamp of the message instead of the time it was received, see [Delay::new_stamped()].
+    pub time_stamp: Option<fn(&T) -> f64>,
+}
+
+impl<T> DelayProp<T> {
+    fn latency_duration(&self) -> Duration {
+        Duration::try_from_secs_f64(self.latency).unwrap_or_default()
+    }
+
+    /// Time at which the message is due, given the time it was received.
+    fn due(&self, value: &T, received: Duration) -> Duration {
+        match self.time_stamp {
+            Some(time_stamp) => {
+                Duration::try_from_secs_f64(time_stamp(value)).unwrap_or_default()
+                    + self.latency_duration()
+            }
+            None => received + self.latency_duration(),
+        }
+    }
+}
+
+impl<T> Default for DelayProp<T> {
+    fn default() -> Self {
+        Self {
+            latency: 0.1,
+            time_stamp: None,
+        }
+    }
+}
+
+/// State of the delay actor.
+#[derive(Clone, Debug)]
+pub struct DelayState<T> {
+    /// Delayed messages, by time they are due and arrival order.
+    queue: BTreeMap<(Duration, u64), T>,
+    num_received: u64,
+    /// Current time of the pipeline clock, see [HasClockTimer::set_now()].
+    now: Duration,
+}
+
+impl<T> Default for DelayState<T> {
+    fn default() -> Self {
+        Self {
+            queue: BTreeMap::new(),
+            num_received: 0,
+            now: Duration::ZERO,
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> DelayState<T> {
+    /// Number of messages which are delayed, i.e. not yet forwarded.
+    pub fn num_pending(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasClockTimer<DelayProp<T>, DelayOutbound<T>> for DelayState<T>
+{
+    fn set_now(&mut self, now: Duration) {
+        self.now = now;
+    }
+
+    /// Deadline of the next due message, if any.
+    fn next_deadline(&self, _prop: &DelayProp<T>) -> Option<Duration> {
+        self.queue.keys().next().map(|(due, _)| *due)
+    }
+
+    /// Forwards all messages which are due.
+    fn on_deadline(&mut self, _prop: &DelayProp<T>, outbound: &DelayOutbound<T>) {
+        while let Some(entry) = self.queue.first_entry() {
+            if entry.key().0 > self.now {
+                return;
+            }
+            outbound.value.send(entry.remove());
+        }
+    }
+}
+
+/// Inbound message for the delay actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    DelayInbound<T>,
+    {
+        DelayProp<T>,
+        DelayState<T>,
+        DelayOutbound<T>,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum DelayInboundMessage<T: Default + Debug + Clone + Sync + Send + 'static> {
+    /// Message to be delayed.
+    Value(T),
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> HasOnMessage for DelayInboundMessage<T> {
+    fn on_message(
+        self,
+        prop: &DelayProp<T>,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            DelayInboundMessage::Value(value) => {
+                let due = prop.due(&value, state.now);
+                state.queue.insert((due, state.num_received), value);
+                state.num_received += 1;
+                // Messages which are already due are forwarded right away.
+                state.on_deadline(prop, outbound);
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> IsInboundMessageNew<T>
+    for DelayInboundMessage<T>
+{
+    fn new(_inbound_name: String, msg: T) -> Self {
+        DelayInboundMessage::Value(msg)
+    }
+}
+
+/// Outbound hub of the delay actor.
+#[actor_outputs]
+pub struct DelayOutbound<T: 'static + Default + Clone + Send + Sync + std::fmt::Debug> {
+    /// Delayed messages.
+    pub value: OutboundChannel<T>,
+}
+
+/// Delay actor, which forwards each message after a latency, e.g. to simulate transport latency
+/// or to de-synchronize a feedback loop:
+///
+/// ``` ignore
+/// let mut delay = Delay::<f64>::new_with_latency(context, 0.05);
+/// controller.outbound.command.connect(context, &mut delay.inbound.value);
+/// delay.outbound.value.connect(context, &mut plant.inbound.command);
+/// ```
+///
+/// By default, the latency is added to the time a message is received. For [Stamped] messages,
+/// the latency can be added to the time stamp of the message instead, see
+/// [Delay::new_stamped()]. Messages are forwarded in the order they are due, and in arrival order
+/// if they are due at the same time. Messages which are still delayed when the pipeline stops are
+/// discarded.
+pub type Delay<T> = GenericActor<
+    DelayProp<T>,
+    DelayInbound<T>,
+    NullInRequests,
+    DelayState<T>,
+    DelayOutbound<T>,
+    NullOutRequests,
+    DelayRunner<T>,
+>;
+
+/// The runner of the delay actor.
+pub type DelayRunner<T> = ClockRunner<
+    DelayProp<T>,
+    DelayInbound<T>,
+    NullInRequests,
+    DelayState<T>,
+    DelayOutbound<T>,
+    NullOutRequests,
+>;
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> Delay<T> {
+    /// Create a new delay actor, which forwards each message `latency` seconds after it was
+    /// received.
+    pub fn new_with_latency(context: &mut Hollywood, latency: f64) -> Delay<T> {
+        Delay::from_prop_and_state(
+            context,
+            DelayProp {
+                latency,
+                time_stamp: None,
+            },
+            DelayState::default(),
+        )
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> Delay<Stamped<T>> {
+    /// Create a new delay actor, which forwards each message at its time stamp plus `latency`
+    /// seconds.
+    ///
+    /// The time stamps are interpreted as times of the pipeline clock. Messages whose time stamp
+    /// plus latency already passed are forwarded right away.
+    pub fn new_stamped(context: &mut Hollywood, latency: f64) -> Delay<Stamped<T>> {
+        Delay::from_prop_and_state(
+            context,
+            DelayProp {
+                latency,
+                time_stamp: Some(|msg: &Stamped<T>| msg.time.seconds),
+            },
+            DelayState::default(),
+        )
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasFromPropState<
+        DelayProp<T>,
+        DelayInbound<T>,
+        NullInRequests,
+        DelayState<T>,
+        DelayOutbound<T>,
+        DelayInboundMessage<T>,
+        NullInRequestMessage,
+        NullOutRequests,
+        DelayRunner<T>,
+    > for Delay<T>
+{
+    fn name_hint(_prop: &DelayProp<T>) -> String {
+        "Delay".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::delay::DelayProp;
+    use crate::actors::Delay;
+    use crate::core::clock::advance_settled;
+    use crate::core::clock::settle;
+    use crate::prelude::*;
+    use std::fmt::Debug;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Delay which runs on a virtual clock.
+    struct Fixture<T: Default + Debug + Clone + Sync + Send + 'static> {
+        clock: Arc<SimClock>,
+        value: InboundInjector<T>,
+        delayed: OutboundSubscriber<T>,
+        running: RunningPipeline,
+    }
+
+    impl<T: Default + Debug + Clone + Sync + Send + 'static> Fixture<T> {
+        fn new(create: impl Fn(&mut Hollywood) -> Delay<T>) -> Self {
+            let clock = Arc::new(SimClock::new());
+            let mut handles = None;
+            let pipeline = Hollywood::configure(&mut |context| {
+                context.set_clock(clock.clone());
+                let mut delay = create(context);
+                handles = Some((
+                    delay.inbound.value.injector(),
+                    delay.outbound.value.subscriber(context),
+                ));
+            });
+            let (value, delayed) = handles.unwrap();
+            Self {
+                clock,
+                value,
+                delayed,
+                running: pipeline.spawn(),
+            }
+        }
+
+        async fn send(&self, value: T) {
+            self.value.send(value).unwrap();
+            settle().await;
+        }
+
+        async fn advance(&self, duration: Duration) {
+            advance_settled(&self.clock, duration).await;
+        }
+
+        fn delayed(&mut self) -> Vec<T> {
+            std::iter::from_fn(|| self.delayed.try_recv()).collect()
+        }
+
+        async fn stop(self) {
+            self.running.cancel();
+            self.running.join().await.unwrap();
+        }
+    }
+
+    fn stamped(seconds: f64, value: i32) -> Stamped<i32> {
+        Stamped::new(Timestamp::monotonic(seconds), 0, value)
+    }
+
+    #[test]
+    fn due_time() {
+        let received = Duration::from_secs(2);
+        let prop = DelayProp::<i32> {
+            latency: 0.5,
+            time_stamp: None,
+        };
+        assert_eq!(prop.due(&7, received), Duration::from_secs_f64(2.5));
+
+        let prop = DelayProp::<Stamped<i32>> {
+            latency: 0.5,
+            time_stamp: Some(|msg: &Stamped<i32>| msg.time.seconds),
+        };
+        assert_eq!(
+            prop.due(&stamped(1.0, 7), received),
+            Duration::from_secs_f64(1.5)
+        );
+    }
+
+    #[tokio::test]
+    async fn equal_due_times_keep_arrival_order() {
+        let mut delay = Fixture::new(|context| Delay::<i32>::new_with_latency(context, 1.0));
+        settle().await;
+
+        for i in 1..=3 {
+            delay.send(i).await;
+        }
+        delay.advance(Duration::from_secs_f64(0.5)).await;
+        delay.send(4).await;
+        delay.advance(Duration::from_secs_f64(0.4)).await;
+        assert!(delay.delayed().is_empty());
+
+        delay.advance(Duration::from_secs_f64(0.1)).await;
+        assert_eq!(delay.delayed(), vec![1, 2, 3]);
+        delay.advance(Duration::from_secs(1)).await;
+        assert_eq!(delay.delayed(), vec![4]);
+        delay.stop().await;
+    }
+
+    #[tokio::test]
+    async fn stamped_messages_are_due_at_time_stamp_plus_latency() {
+        let mut delay = Fixture::new(|context| Delay::<Stamped<i32>>::new_stamped(context, 0.5));
+        settle().await;
+        let values =
+            |delayed: Vec<Stamped<i32>>| delayed.iter().map(|m| m.value).collect::<Vec<_>>();
+
+        // The later message carries the earlier time stamp, and hence overtakes the first.
+        delay.send(stamped(2.0, 1)).await;
+        delay.send(stamped(1.0, 2)).await;
+        delay.advance(Duration::from_secs_f64(1.5)).await;
+        assert_eq!(values(delay.delayed()), vec![2]);
+        delay.advance(Duration::from_secs(1)).await;
+        assert_eq!(values(delay.delayed()), vec![1]);
+
+        // A message whose time stamp plus latency passed already is forwarded right away.
+        delay.send(stamped(0.5, 3)).await;
+        assert_eq!(values(delay.delayed()), vec![3]);
+        delay.stop().await;
+    }
+}
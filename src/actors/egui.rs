@@ -5,6 +5,20 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use tracing::debug;
 
+/// Image viewer widget.
+pub mod image_viewer;
+pub use image_viewer::ImageFrame;
+pub use image_viewer::ImageViewer;
+
+/// Egui actor with multiple independent streams.
+pub mod multi;
+pub use multi::EguiMultiActor;
+pub use multi::EguiMultiBuilder;
+
+/// Plotting widgets.
+pub mod plot;
+pub use plot::ScrollingPlot;
+
 /// The inbound message for the egui actor.
 #[derive(Debug)]
 pub struct EguiState<
@@ -15,6 +29,8 @@ pub struct EguiState<
 > {
     /// Forwards messages to the egui app.
     pub forward_message_to_egui_app: Option<tokio::sync::mpsc::UnboundedSender<Stream<T>>>,
+    /// Forwards messages to additional egui apps, filtered by topic.
+    pub forward_message_to_egui_viewers: Vec<StreamSubscription<T>>,
     /// Forwards an incoming request to the egui app.
     pub forward_in_request_to_egui_app: Option<tokio::sync::mpsc::UnboundedSender<InReqMsg>>,
     /// Forwards an outbound reply to the egui app.
@@ -36,6 +52,7 @@ impl<
     fn clone(&self) -> Self {
         Self {
             forward_message_to_egui_app: self.forward_message_to_egui_app.clone(),
+            forward_message_to_egui_viewers: self.forward_message_to_egui_viewers.clone(),
             forward_in_request_to_egui_app: self.forward_in_request_to_egui_app.clone(),
             forward_out_reply_to_egui_app: self.forward_out_reply_to_egui_app.clone(),
             forward_out_request_from_egui_app: self.forward_out_request_from_egui_app.clone(),
@@ -44,10 +61,66 @@ impl<
 }
 
 /// The inbound message stream.
+///
+/// Breaking change: Since the stream carries a topic, a struct literal such as `Stream { msg }`
+/// no longer compiles. Use [Stream::new()] for messages without topic instead, or
+/// [Stream::with_topic()]. The struct is non-exhaustive, hence fields may be added without
+/// breaking these constructors again.
 #[derive(Clone, Debug, Default)]
+#[non_exhaustive]
 pub struct Stream<T: Default + Debug + Clone + Send + Sync + 'static> {
     /// The message to be forwarded to the egui app.
     pub msg: T,
+    /// The topic of the message, used to route it to subscribed viewers.
+    ///
+    /// An empty topic is only received by viewers which subscribe to all topics.
+    pub topic: String,
+}
+
+impl<T: Default + Debug + Clone + Send + Sync + 'static> Stream<T> {
+    /// Create a new stream message without a topic, like the former `Stream { msg }` literal.
+    pub fn new(msg: T) -> Self {
+        Self {
+            msg,
+            topic: "".to_owned(),
+        }
+    }
+
+    /// Create a new stream message with the given topic.
+    pub fn with_topic(topic: &str, msg: T) -> Self {
+        Self {
+            msg,
+            topic: topic.to_owned(),
+        }
+    }
+}
+
+/// Subscription of an additional egui viewer to a subset of stream topics.
+#[derive(Debug)]
+pub struct StreamSubscription<T: Default + Debug + Clone + Send + Sync + 'static> {
+    /// Topics the viewer is subscribed to. If empty, the viewer receives all messages.
+    pub topics: Vec<String>,
+    /// To forward messages to the viewer.
+    pub sender: tokio::sync::mpsc::UnboundedSender<Stream<T>>,
+    /// To signal the viewer that the pipeline has finished.
+    pub on_exit_sender: tokio::sync::mpsc::UnboundedSender<()>,
+}
+
+impl<T: Default + Debug + Clone + Send + Sync + 'static> Clone for StreamSubscription<T> {
+    fn clone(&self) -> Self {
+        Self {
+            topics: self.topics.clone(),
+            sender: self.sender.clone(),
+            on_exit_sender: self.on_exit_sender.clone(),
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Send + Sync + 'static> StreamSubscription<T> {
+    /// Returns true if the viewer is subscribed to the given topic.
+    pub fn is_subscribed_to(&self, topic: &str) -> bool {
+        self.topics.is_empty() || self.topics.iter().any(|t| t == topic)
+    }
 }
 
 /// The inbound message for the egui actor.
@@ -328,6 +401,15 @@ impl<
                 if let Some(sender) = &state.forward_message_to_egui_app {
                     sender.send(new_value.clone()).unwrap();
                 }
+                for viewer in state.forward_message_to_egui_viewers.iter() {
+                    if viewer.is_subscribed_to(&new_value.topic)
+                        && viewer.sender.send(new_value.clone()).is_err()
+                    {
+                        debug!(
+                            "Failed to forward stream message. Likely viewer is already closed."
+                        );
+                    }
+                }
             }
             EguiInboundMessage::Dummy(_) => {}
             EguiInboundMessage::OutReply(reply) => {
@@ -411,6 +493,10 @@ impl<
     fn activate(&mut self) {
         self.request.activate()
     }
+
+    fn deactivate(&mut self) {
+        self.request.deactivate()
+    }
 }
 
 impl<
@@ -493,19 +579,32 @@ impl<
         builder: &Builder,
     ) -> Self {
         let sender = builder.on_exit_sender();
+        let viewers = builder.viewer_subscriptions();
+        let viewer_exit_senders: Vec<_> =
+            viewers.iter().map(|v| v.on_exit_sender.clone()).collect();
         Self::with_on_exit_fn(
             context,
             NullProp {},
             EguiState::<T, RequestWithReplyChannel<InRequest, InReply>, OutRequest, OutReply> {
                 forward_message_to_egui_app: Some(builder.message_to_egui_app_sender()),
+                forward_message_to_egui_viewers: viewers,
                 forward_in_request_to_egui_app: Some(builder.in_request_to_egui_app_sender()),
                 forward_out_reply_to_egui_app: Some(builder.out_reply_to_egui_app_sender()),
                 forward_out_request_from_egui_app: Some(builder.out_request_from_egui_app_recv()),
             },
-            Box::new(move || match sender.send(()) {
-                Ok(_) => {}
-                Err(_) => {
-                    debug!("Failed to send on exit message. Likely egui app is already closed.");
+            Box::new(move || {
+                match sender.send(()) {
+                    Ok(_) => {}
+                    Err(_) => {
+                        debug!(
+                            "Failed to send on exit message. Likely egui app is already closed."
+                        );
+                    }
+                }
+                for viewer_exit_sender in viewer_exit_senders {
+                    if viewer_exit_sender.send(()).is_err() {
+                        debug!("Failed to send on exit message. Likely viewer is already closed.");
+                    }
                 }
             }),
         )
@@ -522,6 +621,10 @@ pub trait EguiActorBuilder<
 {
     /// Returns message sender.
     fn message_to_egui_app_sender(&self) -> tokio::sync::mpsc::UnboundedSender<Stream<T>>;
+    /// Returns the topic subscriptions of additional viewers.
+    fn viewer_subscriptions(&self) -> Vec<StreamSubscription<T>> {
+        vec![]
+    }
     /// Returns in request sender.
     fn in_request_to_egui_app_sender(&self) -> tokio::sync::mpsc::UnboundedSender<InReqMsg>;
     /// Returns out reply sender.
@@ -571,6 +674,9 @@ pub struct GenericEguiBuilder<
     /// On exit receiver
     pub on_exit_recv: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<()>>>,
 
+    /// Subscriptions of additional viewers, see [GenericEguiBuilder::add_viewer].
+    pub viewer_subscriptions: Vec<StreamSubscription<T>>,
+
     /// Pipeline cancel request sender
     pub cancel_request_sender: Option<tokio::sync::mpsc::UnboundedSender<CancelRequest>>,
 
@@ -611,10 +717,48 @@ impl<
             config,
             on_exit_sender,
             on_exit_recv: Arc::new(Mutex::new(on_exit_recv)),
+            viewer_subscriptions: vec![],
+        }
+    }
+
+    /// Adds an additional viewer which receives all stream messages of the given topics.
+    ///
+    /// If `topics` is empty, the viewer receives all stream messages. The returned endpoint
+    /// shares the out-request channel with the main egui app, so that any viewer can send
+    /// requests to the pipeline. Replies to out-requests are only forwarded to the main app.
+    ///
+    /// Viewers must be added before the egui actor is created using
+    /// [EguiActor::from_builder].
+    pub fn add_viewer(&mut self, topics: &[&str]) -> EguiViewerEndpoint<T, OutRequest> {
+        let (sender, message_from_actor_recv) = tokio::sync::mpsc::unbounded_channel();
+        let (on_exit_sender, on_exit_recv) = tokio::sync::mpsc::unbounded_channel();
+        let topics: Vec<String> = topics.iter().map(|t| t.to_string()).collect();
+        self.viewer_subscriptions.push(StreamSubscription {
+            topics: topics.clone(),
+            sender,
+            on_exit_sender,
+        });
+        EguiViewerEndpoint {
+            topics,
+            message_from_actor_recv,
+            out_request_to_actor_sender: self.out_request_to_actor_sender.clone(),
+            on_exit_recv: Arc::new(Mutex::new(on_exit_recv)),
         }
     }
 }
 
+/// Endpoint of an additional egui viewer, created by [GenericEguiBuilder::add_viewer].
+pub struct EguiViewerEndpoint<T: Default + Debug + Clone + Send + Sync + 'static, OutRequest> {
+    /// Topics the viewer is subscribed to. If empty, the viewer receives all messages.
+    pub topics: Vec<String>,
+    /// To receive messages from the actor.
+    pub message_from_actor_recv: tokio::sync::mpsc::UnboundedReceiver<Stream<T>>,
+    /// To forward outgoing requests from the viewer to the actor.
+    pub out_request_to_actor_sender: tokio::sync::mpsc::UnboundedSender<OutRequest>,
+    /// On exit receiver
+    pub on_exit_recv: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<()>>>,
+}
+
 impl<
         T: Default + Debug + Clone + Send + Sync + 'static,
         InReqMsg: IsRequestWithReplyChannel,
@@ -628,6 +772,10 @@ impl<
         self.message_to_egui_app_sender.clone()
     }
 
+    fn viewer_subscriptions(&self) -> Vec<StreamSubscription<T>> {
+        self.viewer_subscriptions.clone()
+    }
+
     fn in_request_to_egui_app_sender(&self) -> tokio::sync::mpsc::UnboundedSender<InReqMsg> {
         self.in_request_to_egui_app_sender.clone()
     }
@@ -0,0 +1,201 @@
+use crate::actors::egui::Stream;
+use eframe::egui;
+use std::collections::VecDeque;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::warn;
+
+/// Pixel encoding of an [ImageFrame].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageEncoding {
+    /// 8-bit grayscale, one byte per pixel.
+    Gray8,
+    /// 8-bit RGB, three bytes per pixel.
+    #[default]
+    Rgb8,
+    /// 8-bit RGBA (not premultiplied), four bytes per pixel.
+    Rgba8,
+}
+
+impl ImageEncoding {
+    /// Number of bytes per pixel.
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            ImageEncoding::Gray8 => 1,
+            ImageEncoding::Rgb8 => 3,
+            ImageEncoding::Rgba8 => 4,
+        }
+    }
+}
+
+/// A single image frame, e.g. from a camera driver.
+///
+/// Use `Stream<ImageFrame>` to send images to the egui actor.
+#[derive(Clone, Debug, Default)]
+pub struct ImageFrame {
+    /// Width of the image in pixels.
+    pub width: usize,
+    /// Height of the image in pixels.
+    pub height: usize,
+    /// Pixel encoding of `data`.
+    pub encoding: ImageEncoding,
+    /// Tightly packed, row-major pixel data.
+    pub data: Vec<u8>,
+}
+
+impl ImageFrame {
+    /// Returns true if the size of `data` is consistent with width, height and encoding.
+    pub fn is_valid(&self) -> bool {
+        self.data.len() == self.width * self.height * self.encoding.bytes_per_pixel()
+    }
+
+    /// Converts the frame into an egui color image.
+    ///
+    /// Returns None if the frame is not valid.
+    pub fn to_color_image(&self) -> Option<egui::ColorImage> {
+        if !self.is_valid() {
+            return None;
+        }
+        let size = [self.width, self.height];
+        Some(match self.encoding {
+            ImageEncoding::Gray8 => egui::ColorImage::from_gray(size, &self.data),
+            ImageEncoding::Rgb8 => egui::ColorImage::from_rgb(size, &self.data),
+            ImageEncoding::Rgba8 => egui::ColorImage::from_rgba_unmultiplied(size, &self.data),
+        })
+    }
+}
+
+/// Frame rate counter over a sliding time window.
+#[derive(Debug)]
+pub struct FpsCounter {
+    window: Duration,
+    arrivals: VecDeque<Instant>,
+}
+
+impl Default for FpsCounter {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1))
+    }
+}
+
+impl FpsCounter {
+    /// Create a new counter averaging over the given time window.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            arrivals: VecDeque::new(),
+        }
+    }
+
+    /// Registers the arrival of a new frame.
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        self.arrivals.push_back(now);
+        self.drop_outdated(now);
+    }
+
+    /// Returns the current frame rate in frames per second.
+    pub fn fps(&mut self) -> f64 {
+        self.drop_outdated(Instant::now());
+        self.arrivals.len() as f64 / self.window.as_secs_f64()
+    }
+
+    fn drop_outdated(&mut self, now: Instant) {
+        while let Some(front) = self.arrivals.front() {
+            if now.duration_since(*front) > self.window {
+                self.arrivals.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Widget displaying the latest frame of an image stream together with its frame rate.
+///
+/// Example usage within `eframe::App::update`:
+///
+/// ``` ignore
+/// while let Ok(frame) = self.image_recv.try_recv() {
+///     self.viewer.set_frame(ctx, &frame);
+/// }
+/// egui::CentralPanel::default().show(ctx, |ui| self.viewer.ui(ui));
+/// ```
+pub struct ImageViewer {
+    name: String,
+    texture: Option<egui::TextureHandle>,
+    size: [usize; 2],
+    topic: String,
+    fps: FpsCounter,
+}
+
+impl ImageViewer {
+    /// Create a new image viewer. The name is used as texture name and must be unique.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            texture: None,
+            size: [0, 0],
+            topic: "".to_owned(),
+            fps: FpsCounter::default(),
+        }
+    }
+
+    /// Replaces the displayed image by the given frame.
+    ///
+    /// Invalid frames are dropped with a warning.
+    pub fn set_frame(&mut self, ctx: &egui::Context, frame: &Stream<ImageFrame>) {
+        let image = match frame.msg.to_color_image() {
+            Some(image) => image,
+            None => {
+                warn!(
+                    "Dropping invalid image frame of {}x{} with {} bytes",
+                    frame.msg.width,
+                    frame.msg.height,
+                    frame.msg.data.len()
+                );
+                return;
+            }
+        };
+        self.fps.tick();
+        self.size = [frame.msg.width, frame.msg.height];
+        self.topic.clone_from(&frame.topic);
+        match &mut self.texture {
+            Some(texture) => texture.set(image, egui::TextureOptions::default()),
+            None => {
+                self.texture =
+                    Some(ctx.load_texture(&self.name, image, egui::TextureOptions::default()))
+            }
+        }
+    }
+
+    /// Returns the current frame rate of the image stream.
+    pub fn fps(&mut self) -> f64 {
+        self.fps.fps()
+    }
+
+    /// Draws the latest frame, scaled to fit the available space, and the frame rate.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let fps = self.fps();
+        ui.label(format!(
+            "{} {}x{} @ {:.1} fps",
+            self.topic, self.size[0], self.size[1], fps
+        ));
+        match &self.texture {
+            Some(texture) => {
+                let available = ui.available_size();
+                let image_size = texture.size_vec2();
+                let scale = (available.x / image_size.x)
+                    .min(available.y / image_size.y)
+                    .max(0.0);
+                ui.image(egui::load::SizedTexture::new(
+                    texture.id(),
+                    image_size * scale,
+                ));
+            }
+            None => {
+                ui.label("no image received yet");
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::prelude::*;
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::debug;
+
+type StreamForwarder = Arc<dyn Fn(&AnyMessage) + Send + Sync>;
+
+/// Name and type of a stream of the [EguiMultiActor].
+#[derive(Clone, Debug)]
+struct StreamInfo {
+    name: String,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+struct StreamEntry {
+    info: StreamInfo,
+    forwarder: StreamForwarder,
+    /// The `UnboundedReceiver<T>` of the egui app, until it is taken.
+    receiver: Option<Box<dyn Any + Send>>,
+}
+
+/// Builder for the [EguiMultiActor] and the egui app, with one typed channel per stream.
+///
+/// As opposed to the [GenericEguiBuilder](crate::actors::egui::GenericEguiBuilder), the streams
+/// need not share a common message type:
+///
+/// ``` ignore
+/// let mut builder = EguiMultiBuilder::from_config(config);
+/// builder.add_stream::<(f64, f64)>("plot");
+/// builder.add_stream::<String>("log");
+///
+/// // In the pipeline configuration:
+/// let mut egui_actor = EguiMultiActor::from_builder(context, &builder);
+/// generator.outbound.point.connect(context, &mut egui_actor.inbound.stream("plot"));
+/// generator.outbound.text.connect(context, &mut egui_actor.inbound.stream("log"));
+///
+/// // In the egui app:
+/// let plot_recv = builder.take_stream::<(f64, f64)>("plot").unwrap();
+/// ```
+pub struct EguiMultiBuilder<Config> {
+    streams: Vec<StreamEntry>,
+
+    /// On exit sender
+    pub on_exit_sender: tokio::sync::mpsc::UnboundedSender<()>,
+    /// On exit receiver
+    pub on_exit_recv: Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<()>>>,
+
+    /// Pipeline cancel request sender
+    pub cancel_request_sender: Option<tokio::sync::mpsc::UnboundedSender<CancelRequest>>,
+
+    /// The config for the egui app.
+    pub config: Config,
+}
+
+impl<Config> EguiMultiBuilder<Config> {
+    /// Create a new builder without any streams.
+    pub fn from_config(config: Config) -> Self {
+        let (on_exit_sender, on_exit_recv) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            streams: vec![],
+            on_exit_sender,
+            on_exit_recv: Arc::new(Mutex::new(on_exit_recv)),
+            cancel_request_sender: None,
+            config,
+        }
+    }
+
+    /// Adds a stream of messages of type T, which are forwarded to the egui app.
+    ///
+    /// Streams must be added before the egui actor is created using
+    /// [EguiMultiActor::from_builder]. The stream name must be unique.
+    pub fn add_stream<T: Clone + Debug + Send + Sync + 'static>(&mut self, name: &str) {
+        assert!(
+            self.streams.iter().all(|entry| entry.info.name != name),
+            "Stream {} added twice",
+            name
+        );
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<T>();
+        let stream = name.to_owned();
+        self.streams.push(StreamEntry {
+            info: StreamInfo {
+                name: name.to_owned(),
+                type_id: TypeId::of::<T>(),
+                type_name: std::any::type_name::<T>(),
+            },
+            forwarder: Arc::new(move |msg: &AnyMessage| {
+                if let Ok(value) = msg.downcast::<T>() {
+                    if sender.send(value).is_err() {
+                        debug!(
+                            "Failed to forward {} message. Likely egui app is already closed.",
+                            stream
+                        );
+                    }
+                }
+            }),
+            receiver: Some(Box::new(receiver)),
+        });
+    }
+
+    /// Takes the receiver of the given stream, to be used by the egui app.
+    ///
+    /// Returns None if there is no such stream of type T, or if the receiver was taken already.
+    pub fn take_stream<T: 'static>(
+        &mut self,
+        name: &str,
+    ) -> Option<tokio::sync::mpsc::UnboundedReceiver<T>> {
+        let entry = self
+            .streams
+            .iter_mut()
+            .find(|entry| entry.info.name == name && entry.info.type_id == TypeId::of::<T>())?;
+        entry
+            .receiver
+            .take()?
+            .downcast::<tokio::sync::mpsc::UnboundedReceiver<T>>()
+            .ok()
+            .map(|receiver| *receiver)
+    }
+}
+
+/// Configuration properties of the [EguiMultiActor], i.e. its streams.
+#[derive(Clone, Debug)]
+pub struct EguiMultiProp {
+    streams: Vec<StreamInfo>,
+}
+
+/// State of the [EguiMultiActor].
+pub struct EguiMultiState {
+    forwarders: HashMap<String, StreamForwarder>,
+}
+
+impl Debug for EguiMultiState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EguiMultiState")
+            .field("streams", &self.forwarders.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Inbound message of the [EguiMultiActor], i.e. a message of one of its streams.
+#[derive(Clone, Debug)]
+pub struct EguiMultiMessage {
+    stream: String,
+    payload: AnyMessage,
+}
+
+impl IsInboundMessage for EguiMultiMessage {
+    type Prop = EguiMultiProp;
+    type State = EguiMultiState;
+    type OutboundHub = NullOutbound;
+    type OutRequestHub = NullOutRequests;
+
+    fn inbound_channel(&self) -> String {
+        self.stream.clone()
+    }
+}
+
+impl HasOnMessage for EguiMultiMessage {
+    /// Forward the message to the egui app.
+    fn on_message(
+        self,
+        _prop: &Self::Prop,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        if let Some(forward) = state.forwarders.get(&self.stream) {
+            forward(&self.payload);
+        }
+    }
+}
+
+impl<T: Any + Send + Sync> IsInboundMessageNew<T> for EguiMultiMessage {
+    fn new(inbound_name: String, value: T) -> Self {
+        Self {
+            stream: inbound_name,
+            payload: AnyMessage::new(value),
+        }
+    }
+}
+
+/// Inbound hub of the [EguiMultiActor], with one inbound channel per stream.
+pub struct EguiMultiInbound {
+    streams: HashMap<String, (StreamInfo, AnyInboundChannel<EguiMultiMessage>)>,
+}
+
+impl EguiMultiInbound {
+    /// Returns the inbound channel of the given stream, e.g. to connect it to an outbound channel.
+    ///
+    /// Panics if there is no such stream, or if it is not of type T.
+    pub fn stream<T: Debug + Send + Sync + 'static>(
+        &self,
+        name: &str,
+    ) -> InboundChannel<T, EguiMultiMessage> {
+        let (info, channel) = self
+            .streams
+            .get(name)
+            .unwrap_or_else(|| panic!("Egui actor has no stream {}", name));
+        assert_eq!(
+            info.type_id,
+            TypeId::of::<T>(),
+            "Stream {} is of type {}, not {}",
+            name,
+            info.type_name,
+            std::any::type_name::<T>()
+        );
+        InboundChannel {
+            name: channel.name.clone(),
+            actor_name: channel.actor_name.clone(),
+            sender: channel.sender.clone(),
+            stats: channel.stats.clone(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl
+    IsInboundHub<
+        EguiMultiProp,
+        EguiMultiState,
+        NullOutbound,
+        NullOutRequests,
+        EguiMultiMessage,
+        NullInRequestMessage,
+    > for EguiMultiInbound
+{
+    fn from_builder(
+        builder: &mut ActorBuilder<
+            EguiMultiProp,
+            EguiMultiState,
+            NullOutbound,
+            NullOutRequests,
+            EguiMultiMessage,
+            NullInRequestMessage,
+        >,
+        actor_name: &str,
+    ) -> Self {
+        let mut streams = HashMap::new();
+        for info in builder.prop().streams.clone() {
+            let channel = AnyInboundChannel::new(
+                builder.context,
+                actor_name,
+                &builder.sender,
+                info.name.clone(),
+            );
+            builder
+                .forward
+                .insert(channel.name.clone(), Box::new(channel.clone()));
+            streams.insert(info.name.clone(), (info, channel));
+        }
+        Self { streams }
+    }
+}
+
+/// The egui actor with multiple independent streams.
+///
+/// This is a generic proxy which receives messages of differently typed streams and forwards
+/// each stream on its own channel to the egui app, see [EguiMultiBuilder]. Unlike the
+/// [EguiActor](crate::actors::egui::EguiActor), it does not forward requests.
+pub type EguiMultiActor = Actor<
+    EguiMultiProp,
+    EguiMultiInbound,
+    NullInRequests,
+    EguiMultiState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+impl EguiMultiActor {
+    /// Create a new egui actor with the streams of the builder.
+    pub fn from_builder<Config>(
+        context: &mut Hollywood,
+        builder: &EguiMultiBuilder<Config>,
+    ) -> Self {
+        let sender = builder.on_exit_sender.clone();
+        Self::with_on_exit_fn(
+            context,
+            EguiMultiProp {
+                streams: builder
+                    .streams
+                    .iter()
+                    .map(|entry| entry.info.clone())
+                    .collect(),
+            },
+            EguiMultiState {
+                forwarders: builder
+                    .streams
+                    .iter()
+                    .map(|entry| (entry.info.name.clone(), entry.forwarder.clone()))
+                    .collect(),
+            },
+            Box::new(move || {
+                if sender.send(()).is_err() {
+                    debug!("Failed to send on exit message. Likely egui app is already closed.");
+                }
+            }),
+        )
+    }
+}
+
+impl
+    HasFromPropState<
+        EguiMultiProp,
+        EguiMultiInbound,
+        NullInRequests,
+        EguiMultiState,
+        NullOutbound,
+        EguiMultiMessage,
+        NullInRequestMessage,
+        NullOutRequests,
+        DefaultRunner<
+            EguiMultiProp,
+            EguiMultiInbound,
+            NullInRequests,
+            EguiMultiState,
+            NullOutbound,
+            NullOutRequests,
+        >,
+    > for EguiMultiActor
+{
+    fn name_hint(_prop: &EguiMultiProp) -> String {
+        "EguiMulti".to_owned()
+    }
+}
@@ -0,0 +1,138 @@
+use crate::actors::egui::Stream;
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
+/// Scrolling time-series plot with bounded history per series.
+///
+/// Each series is identified by its name and keeps at most `capacity` points. When the capacity
+/// is exceeded, the oldest points are dropped, so that the plot scrolls along the x-axis.
+///
+/// Example usage within `eframe::App::update`:
+///
+/// ``` ignore
+/// while let Ok(stream) = self.message_recv.try_recv() {
+///     self.plot.push_stream(&stream);
+/// }
+/// egui::CentralPanel::default().show(ctx, |ui| self.plot.ui(ui, 200.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScrollingPlot {
+    capacity: usize,
+    series: BTreeMap<String, VecDeque<(f64, f64)>>,
+}
+
+impl Default for ScrollingPlot {
+    fn default() -> Self {
+        Self::new(500)
+    }
+}
+
+impl ScrollingPlot {
+    const COLORS: [egui::Color32; 6] = [
+        egui::Color32::LIGHT_BLUE,
+        egui::Color32::LIGHT_RED,
+        egui::Color32::LIGHT_GREEN,
+        egui::Color32::GOLD,
+        egui::Color32::from_rgb(200, 120, 255),
+        egui::Color32::from_rgb(0, 200, 200),
+    ];
+
+    /// Create a new plot which keeps at most `capacity` points per series.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            series: BTreeMap::new(),
+        }
+    }
+
+    /// Appends a point to the given series, dropping the oldest point if the capacity is reached.
+    pub fn push(&mut self, series: &str, point: (f64, f64)) {
+        let history = self.series.entry(series.to_owned()).or_default();
+        while history.len() >= self.capacity {
+            history.pop_front();
+        }
+        history.push_back(point);
+    }
+
+    /// Appends the point of a stream message, using the topic as series name.
+    pub fn push_stream(&mut self, stream: &Stream<(f64, f64)>) {
+        self.push(&stream.topic, stream.msg);
+    }
+
+    /// Returns the history of the given series.
+    pub fn series(&self, series: &str) -> Option<&VecDeque<(f64, f64)>> {
+        self.series.get(series)
+    }
+
+    /// Removes all points of all series.
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+
+    /// Returns the bounding box `[min_x, max_x, min_y, max_y]` of all points.
+    pub fn bounds(&self) -> Option<[f64; 4]> {
+        let mut points = self.series.values().flat_map(|s| s.iter());
+        let first = points.next()?;
+        let mut bounds = [first.0, first.0, first.1, first.1];
+        for (x, y) in points {
+            bounds[0] = bounds[0].min(*x);
+            bounds[1] = bounds[1].max(*x);
+            bounds[2] = bounds[2].min(*y);
+            bounds[3] = bounds[3].max(*y);
+        }
+        Some(bounds)
+    }
+
+    /// Draws all series and a legend using the full available width and the given height.
+    pub fn ui(&self, ui: &mut egui::Ui, height: f32) {
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(ui.available_width(), height),
+            egui::Sense::hover(),
+        );
+        let rect = response.rect;
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+        let [min_x, max_x, min_y, max_y] = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let range_x = (max_x - min_x).max(f64::EPSILON);
+        let range_y = (max_y - min_y).max(f64::EPSILON);
+        let to_screen = |(x, y): (f64, f64)| {
+            egui::pos2(
+                rect.left() + ((x - min_x) / range_x) as f32 * rect.width(),
+                rect.bottom() - ((y - min_y) / range_y) as f32 * rect.height(),
+            )
+        };
+
+        let font = egui::FontId::monospace(12.0);
+        for (i, (name, history)) in self.series.iter().enumerate() {
+            let color = Self::COLORS[i % Self::COLORS.len()];
+            let points: Vec<egui::Pos2> = history.iter().map(|p| to_screen(*p)).collect();
+            painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+            painter.text(
+                rect.left_top() + egui::vec2(4.0, 4.0 + 14.0 * i as f32),
+                egui::Align2::LEFT_TOP,
+                name,
+                font.clone(),
+                color,
+            );
+        }
+        let text_color = ui.visuals().text_color();
+        painter.text(
+            rect.right_top() + egui::vec2(-4.0, 4.0),
+            egui::Align2::RIGHT_TOP,
+            format!("{:.3}", max_y),
+            font.clone(),
+            text_color,
+        );
+        painter.text(
+            rect.right_bottom() + egui::vec2(-4.0, -4.0),
+            egui::Align2::RIGHT_BOTTOM,
+            format!("{:.3}", min_y),
+            font,
+            text_color,
+        );
+    }
+}
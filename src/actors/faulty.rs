@@ -0,0 +1,195 @@
+use crate::prelude::*;
+use rand::Rng;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Configuration properties for the faulty actor.
+///
+/// All faults are disabled by default, in which case the actor forwards all inbound messages
+/// unchanged and echoes all requests.
+#[derive(Clone, Debug, Default)]
+pub struct FaultyProp {
+    /// Drops every Nth message (and request) if set.
+    pub drop_every_nth: Option<u64>,
+    /// Panics when the Kth message (or request) is received if set.
+    pub panic_after: Option<u64>,
+    /// Blocks for a random duration of up to the given value for each message if set.
+    pub max_delay: Option<Duration>,
+    /// Replies with the default value instead of echoing the request if true.
+    pub wrong_replies: bool,
+}
+
+/// State of the faulty actor.
+#[derive(Clone, Debug, Default)]
+pub struct FaultyState {
+    /// Number of received messages and requests.
+    pub num_received: u64,
+    /// Number of dropped messages and requests.
+    pub num_dropped: u64,
+}
+
+impl FaultyState {
+    /// Registers a new message and applies the configured faults.
+    ///
+    /// Returns false if the message shall be dropped.
+    fn on_receive(&mut self, prop: &FaultyProp, actor: &str) -> bool {
+        self.num_received += 1;
+        if let Some(k) = prop.panic_after {
+            if self.num_received >= k {
+                panic!("{}: injected panic after {} messages", actor, k);
+            }
+        }
+        if let Some(max_delay) = prop.max_delay {
+            let delay = rand::thread_rng().gen_range(Duration::ZERO..=max_delay);
+            std::thread::sleep(delay);
+        }
+        if let Some(n) = prop.drop_every_nth {
+            if n > 0 && self.num_received.is_multiple_of(n) {
+                self.num_dropped += 1;
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Outbound hub for the faulty actor.
+#[actor_outputs]
+pub struct FaultyOutbound<T: 'static + Default + Clone + Send + Sync + Debug> {
+    /// Forwarded inbound messages.
+    pub output: OutboundChannel<T>,
+}
+
+/// Inbound message for the faulty actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    FaultyInbound<T>,
+    {
+        FaultyProp,
+        FaultyState,
+        FaultyOutbound<T>,
+        NullOutRequests,
+        FaultyInRequestMessage<T>
+    })]
+pub enum FaultyInboundMessage<T: Default + Debug + Clone + Sync + Send + 'static> {
+    /// Message to be forwarded.
+    Input(T),
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> HasOnMessage for FaultyInboundMessage<T> {
+    fn on_message(
+        self,
+        prop: &FaultyProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            FaultyInboundMessage::Input(msg) => {
+                if state.on_receive(prop, "Faulty") {
+                    outbound.output.send(msg);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> IsInboundMessageNew<T>
+    for FaultyInboundMessage<T>
+{
+    fn new(_inbound_name: String, msg: T) -> Self {
+        FaultyInboundMessage::Input(msg)
+    }
+}
+
+/// In-request message for the faulty actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    FaultyInRequest<T>,
+    {
+        FaultyProp,
+        FaultyState,
+        FaultyOutbound<T>,
+        NullOutRequests,
+        FaultyInboundMessage<T>
+    })]
+pub enum FaultyInRequestMessage<T: Default + Debug + Clone + Sync + Send + 'static> {
+    /// Request to be echoed.
+    Echo(RequestWithReplyChannel<T, T>),
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> HasOnRequestMessage
+    for FaultyInRequestMessage<T>
+{
+    fn on_request(
+        self,
+        prop: &FaultyProp,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            FaultyInRequestMessage::Echo(request) => {
+                if !state.on_receive(prop, "Faulty") {
+                    // The request is leaked on purpose: the requester never receives a reply, and
+                    // dropping the request would panic.
+                    std::mem::forget(request);
+                    return;
+                }
+                if prop.wrong_replies {
+                    request.reply(T::default());
+                } else {
+                    request.reply_from_request(|r| r);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    IsInRequestMessageNew<RequestWithReplyChannel<T, T>> for FaultyInRequestMessage<T>
+{
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<T, T>) -> Self {
+        FaultyInRequestMessage::Echo(request)
+    }
+}
+
+/// Faulty actor for failure-injection tests.
+///
+/// It forwards inbound messages and echoes requests, but misbehaves as configured in
+/// [FaultyProp]: it drops every Nth message, panics after K messages, delays messages randomly
+/// and/or replies incorrectly to requests. Use it to verify supervision, watchdog and retry
+/// logic without writing bespoke misbehaving actors.
+pub type FaultyActor<T> = Actor<
+    FaultyProp,
+    FaultyInbound<T>,
+    FaultyInRequest<T>,
+    FaultyState,
+    FaultyOutbound<T>,
+    NullOutRequests,
+>;
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasFromPropState<
+        FaultyProp,
+        FaultyInbound<T>,
+        FaultyInRequest<T>,
+        FaultyState,
+        FaultyOutbound<T>,
+        FaultyInboundMessage<T>,
+        FaultyInRequestMessage<T>,
+        NullOutRequests,
+        DefaultRunner<
+            FaultyProp,
+            FaultyInbound<T>,
+            FaultyInRequest<T>,
+            FaultyState,
+            FaultyOutbound<T>,
+            NullOutRequests,
+        >,
+    > for FaultyActor<T>
+{
+    fn name_hint(_prop: &FaultyProp) -> String {
+        "Faulty".to_owned()
+    }
+}
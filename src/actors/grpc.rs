@@ -0,0 +1,183 @@
+use crate::actors::BackgroundActor;
+use crate::prelude::*;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use tonic::codegen::http;
+use tonic::codegen::BoxFuture;
+use tonic::codegen::StdError;
+use tracing::warn;
+
+/// Conversion hooks between an in-request channel and a unary gRPC method.
+///
+/// The request and reply of the in-request channel are converted from and to prost messages,
+/// i.e. the types generated by `prost-build` from the `.proto` definition of the service:
+///
+/// ``` ignore
+/// struct PoseQuery;
+///
+/// impl IsGrpcRequestBridge for PoseQuery {
+///     const SERVICE_NAME: &'static str = "robot.Localizer";
+///     const METHOD_NAME: &'static str = "GetPose";
+///     type Request = f64;
+///     type Reply = Pose;
+///     type ProtoRequest = proto::PoseRequest;
+///     type ProtoReply = proto::Pose;
+///
+///     fn from_proto(request: proto::PoseRequest) -> Result<f64, tonic::Status> {
+///         Ok(request.time)
+///     }
+///
+///     fn to_proto(reply: Pose) -> proto::Pose {
+///         proto::Pose { x: reply.x, y: reply.y, theta: reply.theta }
+///     }
+/// }
+/// ```
+pub trait IsGrpcRequestBridge: Send + Sync + 'static {
+    /// Fully qualified name of the gRPC service, e.g. "robot.Localizer".
+    const SERVICE_NAME: &'static str;
+    /// Name of the unary method, e.g. "GetPose".
+    const METHOD_NAME: &'static str;
+
+    /// Request type of the in-request channel.
+    type Request: Send + Sync + std::fmt::Debug + 'static;
+    /// Reply type of the in-request channel.
+    type Reply: Send + Sync + std::fmt::Debug + 'static;
+    /// Prost message of the gRPC request.
+    type ProtoRequest: prost::Message + Default + 'static;
+    /// Prost message of the gRPC reply.
+    type ProtoReply: prost::Message + Default + 'static;
+
+    /// Converts the gRPC request into the request of the in-request channel.
+    ///
+    /// Invalid requests are rejected with an error status, which is returned to the caller.
+    // tonic::Status is large, but it is what tonic services return, so it is not boxed here.
+    #[allow(clippy::result_large_err)]
+    fn from_proto(request: Self::ProtoRequest) -> Result<Self::Request, tonic::Status>;
+
+    /// Converts the reply of the in-request channel into the gRPC reply.
+    fn to_proto(reply: Self::Reply) -> Self::ProtoReply;
+}
+
+/// Unary gRPC service which forwards each call to an in-request channel.
+struct GrpcRequestService<B: IsGrpcRequestBridge> {
+    client: Arc<RequestClient<B::Request, B::Reply>>,
+    phantom: PhantomData<fn(B)>,
+}
+
+impl<B: IsGrpcRequestBridge> Clone for GrpcRequestService<B> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<B: IsGrpcRequestBridge> tonic::server::NamedService for GrpcRequestService<B> {
+    const NAME: &'static str = B::SERVICE_NAME;
+}
+
+impl<B: IsGrpcRequestBridge> tonic::server::UnaryService<B::ProtoRequest>
+    for GrpcRequestService<B>
+{
+    type Response = B::ProtoReply;
+    type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+
+    fn call(&mut self, request: tonic::Request<B::ProtoRequest>) -> Self::Future {
+        let client = self.client.clone();
+        Box::pin(async move {
+            let request = B::from_proto(request.into_inner())?;
+            match client.request(request).await {
+                Ok(reply) => Ok(tonic::Response::new(B::to_proto(reply))),
+                Err(err) => Err(tonic::Status::unavailable(err.to_string())),
+            }
+        })
+    }
+}
+
+impl<B, Body> tonic::codegen::Service<http::Request<Body>> for GrpcRequestService<B>
+where
+    B: IsGrpcRequestBridge,
+    Body: tonic::codegen::Body + Send + 'static,
+    Body::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::BoxBody>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        let path = format!("/{}/{}", B::SERVICE_NAME, B::METHOD_NAME);
+        if request.uri().path() != path {
+            return Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(200)
+                    .header("grpc-status", tonic::Code::Unimplemented as i32)
+                    .header(http::header::CONTENT_TYPE, "application/grpc")
+                    .body(tonic::codegen::empty_body())
+                    .unwrap())
+            });
+        }
+        let method = self.clone();
+        Box::pin(async move {
+            let codec = tonic::codec::ProstCodec::<B::ProtoReply, B::ProtoRequest>::default();
+            let mut grpc = tonic::server::Grpc::new(codec);
+            Ok(grpc.unary(method, request).await)
+        })
+    }
+}
+
+/// Bridge which exposes an in-request channel as a unary gRPC method, so that external clients
+/// can make requests into a running pipeline and receive the reply of the actor.
+///
+/// The conversion between the channel and the gRPC messages is defined by [IsGrpcRequestBridge]:
+///
+/// ``` ignore
+/// GrpcRequestBridge::<PoseQuery>::serve(
+///     context,
+///     "0.0.0.0:50051".parse()?,
+///     localizer.in_requests.pose.client(),
+/// );
+/// ```
+///
+/// The server is run by a [BackgroundActor] while the pipeline is running. Calls made while the
+/// actor is not running fail with the `UNAVAILABLE` status. This bridge is only available with the
+/// `grpc` feature.
+pub struct GrpcRequestBridge<B: IsGrpcRequestBridge> {
+    phantom: PhantomData<fn(B)>,
+}
+
+impl<B: IsGrpcRequestBridge> GrpcRequestBridge<B> {
+    /// Creates the background actor which serves the in-request channel at the given address.
+    pub fn serve(
+        context: &mut Hollywood,
+        addr: SocketAddr,
+        client: RequestClient<B::Request, B::Reply>,
+    ) -> BackgroundActor {
+        let service = GrpcRequestService::<B> {
+            client: Arc::new(client),
+            phantom: PhantomData,
+        };
+        BackgroundActor::from_named_task(context, "GrpcRequestBridge", move |mut kill| async move {
+            let shutdown_kill = kill.clone();
+            let result = tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_shutdown(addr, async move {
+                    let mut kill = shutdown_kill;
+                    let _ = kill.wait_for(|kill| *kill).await;
+                })
+                .await;
+            if let Err(err) = result {
+                warn!("GrpcRequestBridge: failed to serve {}: {}", addr, err);
+                // Keep running until the pipeline stops, like any other actor.
+                let _ = kill.wait_for(|kill| *kill).await;
+            }
+        })
+    }
+}
@@ -0,0 +1,265 @@
+use crate::prelude::*;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Configuration properties of the map actor, i.e. the function applied to each message.
+pub struct MapProp<I, O> {
+    name_hint: &'static str,
+    f: Arc<dyn Fn(I) -> Option<O> + Send + Sync>,
+}
+
+impl<I, O> Clone for MapProp<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            name_hint: self.name_hint,
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<I, O> Debug for MapProp<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapProp")
+            .field("name_hint", &self.name_hint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, O> MapProp<I, O> {
+    /// Function which maps each message to a new one.
+    pub fn from_fn(f: impl Fn(I) -> O + Send + Sync + 'static) -> Self {
+        Self {
+            name_hint: "Map",
+            f: Arc::new(move |value| Some(f(value))),
+        }
+    }
+
+    /// Function which maps each message to a new one, or drops it by returning None.
+    pub fn from_filter_map(f: impl Fn(I) -> Option<O> + Send + Sync + 'static) -> Self {
+        Self {
+            name_hint: "FilterMap",
+            f: Arc::new(f),
+        }
+    }
+}
+
+impl<T> MapProp<T, T> {
+    /// Predicate which keeps the messages it returns true for, and drops all others.
+    pub fn from_predicate(predicate: impl Fn(&T) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            name_hint: "Filter",
+            f: Arc::new(move |value| predicate(&value).then_some(value)),
+        }
+    }
+}
+
+/// Inbound message of the map actor.
+///
+/// As opposed to most actors, the inbound hub is not generated by the [actor_inputs] macro,
+/// since the message is generic over both the inbound and the outbound type.
+pub struct MapInboundMessage<I, O> {
+    value: I,
+    phantom: PhantomData<fn() -> O>,
+}
+
+impl<I: Clone, O> Clone for MapInboundMessage<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<I: Debug, O> Debug for MapInboundMessage<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Value").field(&self.value).finish()
+    }
+}
+
+impl<I, O> IsInboundMessage for MapInboundMessage<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+{
+    type Prop = MapProp<I, O>;
+    type State = NullState;
+    type OutboundHub = MapOutbound<O>;
+    type OutRequestHub = NullOutRequests;
+
+    fn inbound_channel(&self) -> String {
+        "Value".to_owned()
+    }
+}
+
+impl<I, O> HasOnMessage for MapInboundMessage<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+{
+    fn on_message(
+        self,
+        prop: &Self::Prop,
+        _state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        if let Some(value) = (prop.f)(self.value) {
+            outbound.value.send(value);
+        }
+    }
+}
+
+impl<I, O> IsInboundMessageNew<I> for MapInboundMessage<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+{
+    fn new(_inbound_name: String, value: I) -> Self {
+        Self {
+            value,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Inbound hub of the map actor.
+pub struct MapInbound<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+{
+    /// Messages to be mapped.
+    pub value: InboundChannel<I, MapInboundMessage<I, O>>,
+}
+
+impl<I, O>
+    IsInboundHub<
+        MapProp<I, O>,
+        NullState,
+        MapOutbound<O>,
+        NullOutRequests,
+        MapInboundMessage<I, O>,
+        NullInRequestMessage,
+    > for MapInbound<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+{
+    fn from_builder(
+        builder: &mut ActorBuilder<
+            MapProp<I, O>,
+            NullState,
+            MapOutbound<O>,
+            NullOutRequests,
+            MapInboundMessage<I, O>,
+            NullInRequestMessage,
+        >,
+        actor_name: &str,
+    ) -> Self {
+        let value = InboundChannel::new(
+            builder.context,
+            actor_name,
+            &builder.sender,
+            "Value".to_owned(),
+        );
+        builder
+            .forward
+            .insert(value.name.clone(), Box::new(value.clone()));
+        Self { value }
+    }
+}
+
+/// Outbound hub of the map actor.
+#[actor_outputs]
+pub struct MapOutbound<O: 'static + Clone + Send + Sync + std::fmt::Debug> {
+    /// Mapped messages.
+    pub value: OutboundChannel<O>,
+}
+
+/// Map actor, which applies a function to each message, e.g. to convert between the channel
+/// types of two actors without defining a dedicated actor:
+///
+/// ``` ignore
+/// let mut to_meters = Map::<f64, f64>::from_fn(context, |mm| mm / 1000.0);
+/// sensor.outbound.distance.connect(context, &mut to_meters.inbound.value);
+/// to_meters.outbound.value.connect(context, &mut controller.inbound.distance);
+/// ```
+///
+/// The function is stateless. Use [Filter] to drop messages instead.
+pub type Map<I, O> = Actor<
+    MapProp<I, O>,
+    MapInbound<I, O>,
+    NullInRequests,
+    NullState,
+    MapOutbound<O>,
+    NullOutRequests,
+>;
+
+/// Filter actor, which forwards only the messages for which a predicate returns true, see
+/// [Filter::from_predicate()].
+///
+/// It is a [Map] actor with the same inbound and outbound type.
+pub type Filter<T> = Map<T, T>;
+
+impl<I, O> Map<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+{
+    /// Creates a new map actor, which applies `f` to each message.
+    pub fn from_fn(context: &mut Hollywood, f: impl Fn(I) -> O + Send + Sync + 'static) -> Self {
+        Map::from_prop_and_state(context, MapProp::from_fn(f), NullState {})
+    }
+
+    /// Creates a new map actor, which applies `f` to each message and drops the message if `f`
+    /// returns None.
+    pub fn from_filter_map(
+        context: &mut Hollywood,
+        f: impl Fn(I) -> Option<O> + Send + Sync + 'static,
+    ) -> Self {
+        Map::from_prop_and_state(context, MapProp::from_filter_map(f), NullState {})
+    }
+}
+
+impl<T> Filter<T>
+where
+    T: Clone + Debug + Send + Sync + 'static,
+{
+    /// Creates a new filter actor, which drops the messages `predicate` returns false for.
+    pub fn from_predicate(
+        context: &mut Hollywood,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Filter::from_prop_and_state(context, MapProp::from_predicate(predicate), NullState {})
+    }
+}
+
+impl<I, O>
+    HasFromPropState<
+        MapProp<I, O>,
+        MapInbound<I, O>,
+        NullInRequests,
+        NullState,
+        MapOutbound<O>,
+        MapInboundMessage<I, O>,
+        NullInRequestMessage,
+        NullOutRequests,
+        DefaultRunner<
+            MapProp<I, O>,
+            MapInbound<I, O>,
+            NullInRequests,
+            NullState,
+            MapOutbound<O>,
+            NullOutRequests,
+        >,
+    > for Map<I, O>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+{
+    fn name_hint(prop: &MapProp<I, O>) -> String {
+        prop.name_hint.to_owned()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::error_policy::ActorErrorHandler;
 use crate::prelude::*;
 use async_trait::async_trait;
 use std::fmt::Debug;
@@ -90,6 +91,7 @@ impl<Item: Default + Sync + Send + Clone + Debug + 'static>
             >,
             tokio::sync::mpsc::UnboundedReceiver<NullMessage>,
             NudgeOutbound<Item>,
+            tokio::sync::mpsc::UnboundedReceiver<NullMessage>,
         ),
         _forward_receiver_request: (
             std::collections::HashMap<
@@ -109,6 +111,7 @@ impl<Item: Default + Sync + Send + Clone + Debug + 'static>
             NullOutRequests,
         ),
         _on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+        _errors: ActorErrorHandler<NullState>,
     ) -> Box<dyn IsActorNode + Send + Sync> {
         Box::new(NudgeActor::<Item> {
             name: name.clone(),
@@ -137,7 +140,7 @@ impl<Item: 'static + Default + Clone + Send + Sync + std::fmt::Debug> IsActorNod
         &self.name
     }
 
-    async fn run(&mut self, mut _kill: tokio::sync::broadcast::Receiver<()>) {
+    async fn run(&mut self, _kill: tokio::sync::watch::Receiver<bool>) {
         let mut outbound = self.outbound.take().unwrap();
         outbound.activate();
         self.state = Some(self.init_state.clone());
@@ -1,6 +1,8 @@
+use crate::core::error_policy::ActorErrorHandler;
 use crate::prelude::*;
 use async_trait::async_trait;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// A periodic actor.
 ///
@@ -8,7 +10,7 @@ use std::sync::Arc;
 pub type Periodic = GenericActor<
     PeriodicProp,
     NullInbound,
-    NullInRequests,
+    PeriodicInRequest,
     PeriodicState,
     PeriodicOutbound,
     NullOutRequests,
@@ -24,10 +26,25 @@ impl Periodic {
                 period,
                 ..Default::default()
             },
-            PeriodicState {
-                count: 0,
-                time_elapsed: 0.0,
+            PeriodicState::default(),
+        )
+    }
+
+    /// Create a new periodic actor with high-resolution timing, see [PeriodicTiming].
+    ///
+    /// The period of `period` seconds may be below one millisecond. The last `spin_wait` seconds
+    /// before each tick are busy-waited.
+    pub fn new_high_resolution(context: &mut Hollywood, period: f64, spin_wait: f64) -> Periodic {
+        Periodic::from_prop_and_state(
+            context,
+            PeriodicProp {
+                period,
+                timing: PeriodicTiming::HighResolution {
+                    spin_wait: Duration::from_secs_f64(spin_wait),
+                },
+                ..Default::default()
             },
+            PeriodicState::default(),
         )
     }
 }
@@ -36,11 +53,11 @@ impl
     HasFromPropState<
         PeriodicProp,
         NullInbound,
-        NullInRequests,
+        PeriodicInRequest,
         PeriodicState,
         PeriodicOutbound,
         NullMessage,
-        NullInRequestMessage,
+        PeriodicInRequestMessage,
         NullOutRequests,
         PeriodicRunner,
     > for Periodic
@@ -50,11 +67,30 @@ impl
     }
 }
 
+/// Timing mode of the periodic actor.
+///
+/// It only applies to real time. If the pipeline has a clock, see [Hollywood::set_clock()], the
+/// periodic actor ticks using the deadlines of that clock instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PeriodicTiming {
+    /// Ticks using a tokio interval, which has millisecond resolution.
+    #[default]
+    Interval,
+    /// Ticks using `sleep_until` on absolute deadlines, followed by a busy-wait for the last
+    /// `spin_wait` of each period. This supports periods below one millisecond, e.g. for
+    /// high-rate control loops, at the cost of keeping a worker thread busy while spinning.
+    HighResolution {
+        /// Duration before each deadline which is busy-waited instead of slept.
+        spin_wait: Duration,
+    },
+}
+
 /// Configuration properties for the periodic actor.
 #[derive(Clone, Debug)]
 pub struct PeriodicProp {
     period: f64,
     stop_time: f64,
+    timing: PeriodicTiming,
 }
 
 impl Default for PeriodicProp {
@@ -62,6 +98,7 @@ impl Default for PeriodicProp {
         Self {
             period: 1.0,
             stop_time: 24.0 * 60.0 * 60.0,
+            timing: PeriodicTiming::default(),
         }
     }
 }
@@ -71,6 +108,7 @@ impl Default for PeriodicProp {
 pub struct PeriodicState {
     count: u32,
     time_elapsed: f64,
+    period: Option<f64>,
 }
 
 impl Default for PeriodicState {
@@ -78,26 +116,88 @@ impl Default for PeriodicState {
         Self {
             count: 0,
             time_elapsed: 0.0,
+            period: None,
+        }
+    }
+}
+
+impl PeriodicState {
+    /// Current period in seconds, which is the configured period unless changed through the
+    /// `set_period` in-request channel.
+    pub fn period(&self, prop: &PeriodicProp) -> f64 {
+        self.period.unwrap_or(prop.period)
+    }
+}
+
+/// In-request message of the periodic actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    PeriodicInRequest,
+    {
+        PeriodicProp,
+        PeriodicState,
+        PeriodicOutbound,
+        NullOutRequests,
+        NullMessage
+    })]
+pub enum PeriodicInRequestMessage {
+    /// Sets the period in seconds, and replies with the previous period.
+    ///
+    /// Non-positive or non-finite periods are ignored.
+    SetPeriod(RequestWithReplyChannel<f64, f64>),
+}
+
+impl HasOnRequestMessage for PeriodicInRequestMessage {
+    fn on_request(
+        self,
+        prop: &PeriodicProp,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            PeriodicInRequestMessage::SetPeriod(request) => {
+                request.reply_from_request(|period| {
+                    let previous = state.period(prop);
+                    if period.is_finite() && period > 0.0 {
+                        state.period = Some(period);
+                    } else {
+                        tracing::warn!("Ignoring invalid period: {}", period);
+                    }
+                    previous
+                });
+            }
         }
     }
 }
 
+impl IsInRequestMessageNew<RequestWithReplyChannel<f64, f64>> for PeriodicInRequestMessage {
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<f64, f64>) -> Self {
+        PeriodicInRequestMessage::SetPeriod(request)
+    }
+}
+
 /// Outbound hub of periodic actor, which consists of a single outbound channel.
 pub struct PeriodicOutbound {
     /// Time stamp outbound channel, which sends a messages every `period`
     /// seconds with the current time stamp.
     pub time_stamp: OutboundChannel<f64>,
+    /// Tick outbound channel, which sends the same time stamp as `time_stamp`, but tagged with
+    /// the pipeline clock domain and the tick count as sequence number.
+    pub tick: OutboundChannel<Stamped<()>>,
 }
 
 impl HasActivate for PeriodicOutbound {
     fn extract(&mut self) -> Self {
         Self {
             time_stamp: self.time_stamp.extract(),
+            tick: self.tick.extract(),
         }
     }
 
     fn activate(&mut self) {
         self.time_stamp.activate();
+        self.tick.activate();
     }
 }
 
@@ -105,6 +205,7 @@ impl IsOutboundHub for PeriodicOutbound {
     fn from_context_and_parent(context: &mut Hollywood, actor_name: &str) -> Self {
         Self {
             time_stamp: OutboundChannel::<f64>::new(context, "time_stamp".to_owned(), actor_name),
+            tick: OutboundChannel::<Stamped<()>>::new(context, "tick".to_owned(), actor_name),
         }
     }
 }
@@ -116,12 +217,12 @@ impl
     IsRunner<
         PeriodicProp,
         NullInbound,
-        NullInRequests,
+        PeriodicInRequest,
         PeriodicState,
         PeriodicOutbound,
         NullOutRequests,
         NullMessage,
-        NullInRequestMessage,
+        PeriodicInRequestMessage,
     > for PeriodicRunner
 {
     /// Create a new actor node.
@@ -145,8 +246,9 @@ impl
             >,
             tokio::sync::mpsc::UnboundedReceiver<NullMessage>,
             PeriodicOutbound,
+            tokio::sync::mpsc::UnboundedReceiver<NullMessage>,
         ),
-        _forward_receiver_request: (
+        forward_receiver_request: (
             std::collections::HashMap<
                 String,
                 Box<
@@ -155,15 +257,16 @@ impl
                             PeriodicState,
                             PeriodicOutbound,
                             NullOutRequests,
-                            NullInRequestMessage,
+                            PeriodicInRequestMessage,
                         > + Send
                         + Sync,
                 >,
             >,
-            tokio::sync::mpsc::UnboundedReceiver<NullInRequestMessage>,
+            tokio::sync::mpsc::UnboundedReceiver<PeriodicInRequestMessage>,
             NullOutRequests,
         ),
         _on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+        _errors: ActorErrorHandler<PeriodicState>,
     ) -> Box<dyn IsActorNode + Send + Sync> {
         Box::new(PeriodicActor {
             name: name.clone(),
@@ -171,10 +274,115 @@ impl
             init_state: state.clone(),
             state: None,
             outbound: Some(forward_receiver_outbound.2),
+            forward_request: forward_receiver_request.0,
+            request_receiver: Some(forward_receiver_request.1),
+            out_request: forward_receiver_request.2,
+            clock: None,
         })
     }
 }
 
+/// Source of the ticks of the periodic actor, according to its [PeriodicTiming].
+enum Ticker {
+    Interval {
+        interval: tokio::time::Interval,
+        last_tick: Option<tokio::time::Instant>,
+    },
+    HighResolution {
+        period: Duration,
+        spin_wait: Duration,
+        deadline: tokio::time::Instant,
+    },
+    /// Ticks using the clock of the pipeline, see [Hollywood::set_clock()].
+    Clock {
+        clock: Arc<dyn IsClock>,
+        period: Duration,
+        deadline: Duration,
+    },
+}
+
+impl Ticker {
+    fn new(period: Duration, timing: PeriodicTiming, clock: Option<Arc<dyn IsClock>>) -> Self {
+        if let Some(clock) = clock {
+            return Ticker::Clock {
+                deadline: clock.now(),
+                clock,
+                period,
+            };
+        }
+        match timing {
+            PeriodicTiming::Interval => Ticker::Interval {
+                interval: tokio::time::interval(period),
+                last_tick: None,
+            },
+            PeriodicTiming::HighResolution { spin_wait } => Ticker::HighResolution {
+                period,
+                spin_wait,
+                deadline: tokio::time::Instant::now(),
+            },
+        }
+    }
+
+    async fn tick(&mut self) {
+        match self {
+            Ticker::Interval {
+                interval,
+                last_tick,
+            } => {
+                *last_tick = Some(interval.tick().await);
+            }
+            Ticker::HighResolution {
+                period,
+                spin_wait,
+                deadline,
+            } => {
+                if let Some(sleep_until) = deadline.checked_sub(*spin_wait) {
+                    tokio::time::sleep_until(sleep_until).await;
+                }
+                while tokio::time::Instant::now() < *deadline {
+                    std::hint::spin_loop();
+                }
+                // Deadlines are absolute, hence the timing error does not accumulate. As for
+                // the interval, missed ticks are caught up in a burst.
+                *deadline += *period;
+            }
+            Ticker::Clock {
+                clock,
+                period,
+                deadline,
+            } => {
+                clock.sleep_until(*deadline).await;
+                *deadline += *period;
+            }
+        }
+    }
+
+    /// Changes the period, with the next tick being one new period after the last tick.
+    fn set_period(&mut self, new_period: Duration) {
+        match self {
+            Ticker::Interval {
+                interval,
+                last_tick,
+            } => {
+                let start = last_tick.map_or_else(tokio::time::Instant::now, |t| t + new_period);
+                *interval = tokio::time::interval_at(start, new_period);
+            }
+            Ticker::HighResolution {
+                period, deadline, ..
+            } => {
+                *deadline = deadline.checked_sub(*period).unwrap_or(*deadline) + new_period;
+                *period = new_period;
+            }
+            Ticker::Clock {
+                period, deadline, ..
+            } => {
+                *deadline = deadline.checked_sub(*period).unwrap_or(*deadline) + new_period;
+                *period = new_period;
+            }
+        }
+    }
+}
+
 /// The active periodic actor.
 pub struct PeriodicActor {
     name: String,
@@ -182,6 +390,16 @@ pub struct PeriodicActor {
     init_state: PeriodicState,
     state: Option<PeriodicState>,
     outbound: Option<PeriodicOutbound>,
+    forward_request: ForwardRequestTable<
+        PeriodicProp,
+        PeriodicState,
+        PeriodicOutbound,
+        NullOutRequests,
+        PeriodicInRequestMessage,
+    >,
+    request_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<PeriodicInRequestMessage>>,
+    out_request: NullOutRequests,
+    clock: Option<Arc<dyn IsClock>>,
 }
 
 #[async_trait]
@@ -190,22 +408,47 @@ impl IsActorNode for PeriodicActor {
         &self.name
     }
 
-    async fn run(&mut self, mut kill: tokio::sync::broadcast::Receiver<()>) {
+    async fn run(&mut self, mut kill: tokio::sync::watch::Receiver<bool>) {
         let mut outbound = self.outbound.take().unwrap();
         outbound.activate();
         self.state = Some(self.init_state.clone());
 
         let state = self.state.as_mut().unwrap();
 
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(
-            (1000.0 * self.prop.period) as u64,
-        ));
+        let mut period = state.period(&self.prop);
+        let mut ticker = Ticker::new(
+            Duration::from_secs_f64(period),
+            self.prop.timing,
+            self.clock.clone(),
+        );
+        let mut request_receiver = self.request_receiver.take().unwrap();
+        let mut requests_open = true;
 
         let conns = Arc::new(outbound);
 
         loop {
-            interval.tick().await;
-            if kill.try_recv().is_ok() {
+            tokio::select! {
+                // A virtual clock might not advance anymore, hence the kill signal cannot wait
+                // for the next tick.
+                _ = kill.changed() => break,
+                _ = ticker.tick() => {}
+                r = request_receiver.recv(), if requests_open => {
+                    let Some(r) = r else {
+                        requests_open = false;
+                        continue;
+                    };
+                    let channel = r.in_request_channel();
+                    if let Some(handler) = self.forward_request.get(&channel) {
+                        handler.forward_message(&self.prop, state, &conns, &self.out_request, r);
+                    }
+                    if state.period(&self.prop) != period {
+                        period = state.period(&self.prop);
+                        ticker.set_period(Duration::from_secs_f64(period));
+                    }
+                    continue;
+                }
+            }
+            if *kill.borrow() {
                 break;
             }
             state.count += 1;
@@ -213,7 +456,7 @@ impl IsActorNode for PeriodicActor {
             if state.time_elapsed > self.prop.stop_time {
                 break;
             }
-            state.time_elapsed += interval.period().as_secs_f64();
+            state.time_elapsed += period;
 
             let conns = conns.clone();
 
@@ -227,10 +470,29 @@ impl IsActorNode for PeriodicActor {
                     }
                 }
             }
+            match &conns.tick.connection_register {
+                ConnectionEnum::Config(_) => {
+                    panic!("Cannot extract connection config")
+                }
+                ConnectionEnum::Active(active) => {
+                    let tick = Stamped::new(
+                        Timestamp::monotonic(state.time_elapsed),
+                        state.count as u64,
+                        (),
+                    );
+                    for i in active.maybe_registers.as_ref().unwrap().iter() {
+                        i.send_impl(tick.clone());
+                    }
+                }
+            }
         }
     }
 
     fn on_exit(&mut self) {
         // do nothing
     }
+
+    fn set_run_options(&mut self, options: ActorRunOptions) {
+        self.clock = options.clock;
+    }
 }
@@ -0,0 +1,100 @@
+use crate::prelude::*;
+
+/// Configuration properties for the progress bar actor.
+#[derive(Clone, Debug)]
+pub struct ProgressBarProp {
+    /// Topic to print. It will be printed before the progress bar.
+    pub topic: String,
+    /// Number of characters of the bar.
+    pub width: usize,
+}
+
+impl Default for ProgressBarProp {
+    fn default() -> Self {
+        ProgressBarProp {
+            topic: "progress".to_owned(),
+            width: 40,
+        }
+    }
+}
+
+/// State of the progress bar actor.
+#[derive(Clone, Debug, Default)]
+pub struct ProgressBarState {
+    /// Percentage printed last. The bar is only printed if the percentage changes.
+    pub last_percent: Option<u32>,
+}
+
+/// Inbound message for the progress bar actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    ProgressBarInbound,
+    {
+        ProgressBarProp,
+        ProgressBarState,
+        NullOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum ProgressBarInboundMessage {
+    /// Progress to print.
+    Progress(Progress),
+}
+
+impl HasOnMessage for ProgressBarInboundMessage {
+    fn on_message(
+        self,
+        prop: &ProgressBarProp,
+        state: &mut Self::State,
+        _outputs: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ProgressBarInboundMessage::Progress(progress) => match progress.fraction() {
+                Some(fraction) => {
+                    let percent = (100.0 * fraction) as u32;
+                    if state.last_percent == Some(percent) {
+                        return;
+                    }
+                    state.last_percent = Some(percent);
+                    let filled = (fraction * prop.width as f64).round() as usize;
+                    println!(
+                        "{}: [{}{}] {}",
+                        prop.topic,
+                        "#".repeat(filled),
+                        ".".repeat(prop.width.saturating_sub(filled)),
+                        progress
+                    );
+                }
+                None => println!("{}: {}", prop.topic, progress),
+            },
+        }
+    }
+}
+
+impl IsInboundMessageNew<Progress> for ProgressBarInboundMessage {
+    fn new(_inbound_name: String, msg: Progress) -> Self {
+        ProgressBarInboundMessage::Progress(msg)
+    }
+}
+
+/// Progress bar actor, which prints the progress of a batch or offline job.
+///
+/// Use [ProgressBar::subscribe()] to print the progress reported through
+/// [Hollywood::register_progress_reporter()].
+#[actor(ProgressBarInboundMessage, NullInRequestMessage)]
+type ProgressBar = Actor<
+    ProgressBarProp,
+    ProgressBarInbound,
+    NullInRequests,
+    ProgressBarState,
+    NullOutbound,
+    NullOutRequests,
+>;
+
+impl ProgressBar {
+    /// Subscribes the progress bar to the progress of the pipeline, see [Progress::TOPIC].
+    pub fn subscribe(&mut self, context: &mut Hollywood) {
+        context.subscribe(Progress::TOPIC, &mut self.inbound.progress);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::prelude::*;
+use std::time::Instant;
+
+/// Configuration properties for the rate controller actor.
+#[derive(Clone, Debug)]
+pub struct RateControllerProp {
+    /// Period of the source in seconds when the pipeline starts.
+    pub initial_period: f64,
+    /// Minimal period in seconds, i.e. the maximal rate of the source.
+    pub min_period: f64,
+    /// Maximal period in seconds, i.e. the minimal rate of the source.
+    pub max_period: f64,
+    /// Latency in seconds above which the source is slowed down, as long as the latency is still
+    /// increasing. Once the latency is below half of the target, the source is sped up again.
+    pub target_latency: f64,
+    /// Queue depth above which the source is slowed down, as long as the queue is still growing.
+    /// Once the queue is empty, the source is sped up again.
+    pub max_queue_depth: usize,
+    /// Factor by which the period is multiplied (or divided) per adjustment.
+    pub gain: f64,
+    /// Minimal time in seconds between two adjustments, so that the effect of an adjustment can
+    /// be observed before the next one.
+    pub settle_time: f64,
+}
+
+impl Default for RateControllerProp {
+    fn default() -> Self {
+        RateControllerProp {
+            initial_period: 0.1,
+            min_period: 0.001,
+            max_period: 10.0,
+            target_latency: 0.1,
+            max_queue_depth: 10,
+            gain: 1.5,
+            settle_time: 0.5,
+        }
+    }
+}
+
+/// State of the rate controller actor.
+#[derive(Clone, Debug, Default)]
+pub struct RateControllerState {
+    /// Period last requested from the source, if any.
+    pub period: Option<f64>,
+    /// Whether a set-period request has not been replied to yet.
+    pub pending: bool,
+    /// Time of the last adjustment.
+    pub last_adjustment: Option<Instant>,
+    /// Last reported latency.
+    pub last_latency: Option<f64>,
+    /// Last reported queue depth.
+    pub last_queue_depth: Option<usize>,
+}
+
+impl RateControllerState {
+    fn adjust(
+        &mut self,
+        prop: &RateControllerProp,
+        overloaded: bool,
+        underloaded: bool,
+        request: &RateControllerOutRequest,
+    ) {
+        if self.pending || !(overloaded || underloaded) {
+            return;
+        }
+        if self
+            .last_adjustment
+            .is_some_and(|t| t.elapsed().as_secs_f64() < prop.settle_time)
+        {
+            return;
+        }
+        let period = self.period.unwrap_or(prop.initial_period);
+        let new_period = if overloaded {
+            period * prop.gain
+        } else {
+            period / prop.gain
+        }
+        .clamp(prop.min_period, prop.max_period);
+        if new_period == period {
+            return;
+        }
+        self.period = Some(new_period);
+        self.pending = true;
+        self.last_adjustment = Some(Instant::now());
+        request.set_period.send_request(new_period);
+    }
+}
+
+/// Inbound message for the rate controller actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    RateControllerInbound,
+    {
+        RateControllerProp,
+        RateControllerState,
+        NullOutbound,
+        RateControllerOutRequest,
+        NullInRequestMessage
+    })]
+pub enum RateControllerInboundMessage {
+    /// Latency of a downstream actor in seconds.
+    Latency(f64),
+    /// Queue depth of a downstream actor.
+    QueueDepth(usize),
+    /// Reply of the source, with the previous period.
+    SetPeriodReply(ReplyMessage<f64>),
+}
+
+impl HasOnMessage for RateControllerInboundMessage {
+    fn on_message(
+        self,
+        prop: &RateControllerProp,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        request: &Self::OutRequestHub,
+    ) {
+        match self {
+            RateControllerInboundMessage::Latency(latency) => {
+                // Once the source is slower than the downstream actors, the latency decreases
+                // while the backlog is processed, hence the source is not slowed down further.
+                let increasing = state.last_latency.is_none_or(|last| latency >= last);
+                state.last_latency = Some(latency);
+                let overloaded = latency > prop.target_latency && increasing;
+                let underloaded = latency < 0.5 * prop.target_latency;
+                state.adjust(prop, overloaded, underloaded, request);
+            }
+            RateControllerInboundMessage::QueueDepth(depth) => {
+                let growing = state.last_queue_depth.is_none_or(|last| depth >= last);
+                state.last_queue_depth = Some(depth);
+                let overloaded = depth > prop.max_queue_depth && growing;
+                state.adjust(prop, overloaded, depth == 0, request);
+            }
+            RateControllerInboundMessage::SetPeriodReply(_) => {
+                state.pending = false;
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<f64> for RateControllerInboundMessage {
+    fn new(_inbound_name: String, msg: f64) -> Self {
+        RateControllerInboundMessage::Latency(msg)
+    }
+}
+
+impl IsInboundMessageNew<usize> for RateControllerInboundMessage {
+    fn new(_inbound_name: String, msg: usize) -> Self {
+        RateControllerInboundMessage::QueueDepth(msg)
+    }
+}
+
+impl IsInboundMessageNew<ReplyMessage<f64>> for RateControllerInboundMessage {
+    fn new(_inbound_name: String, msg: ReplyMessage<f64>) -> Self {
+        RateControllerInboundMessage::SetPeriodReply(msg)
+    }
+}
+
+/// Out-request hub of the rate controller actor.
+#[actor_out_requests]
+pub struct RateControllerOutRequest {
+    /// Sets the period of the source in seconds, e.g. connected to the `set_period` in-request
+    /// channel of a [Periodic](crate::actors::Periodic).
+    pub set_period: OutRequestChannel<f64, f64, RateControllerInboundMessage>,
+}
+
+/// Rate controller actor, which tunes the rate of an upstream source based on downstream load.
+///
+/// Downstream actors report their latency or queue depth to the `latency` or `queue_depth`
+/// inbound channel. If the load is too high, the controller slows down the source by requesting a
+/// longer period through the `set_period` out-request channel; once the load is low, it speeds
+/// the source up again, within [RateControllerProp::min_period] and
+/// [RateControllerProp::max_period].
+#[actor(RateControllerInboundMessage, NullInRequestMessage)]
+type RateController = Actor<
+    RateControllerProp,
+    RateControllerInbound,
+    NullInRequests,
+    RateControllerState,
+    NullOutbound,
+    RateControllerOutRequest,
+>;
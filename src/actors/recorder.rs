@@ -0,0 +1,377 @@
+use crate::actors::BackgroundActor;
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+use tokio_util::bytes::BytesMut;
+use tokio_util::codec::Encoder;
+use tracing::warn;
+
+/// MCAP log format.
+#[cfg(feature = "mcap")]
+pub mod mcap;
+
+/// Magic bytes at the start of a recorded log.
+pub(crate) const RECORD_MAGIC: &[u8] = b"HWREC1\n";
+
+const CHANNEL_ENTRY: u8 = 0;
+const MESSAGE_ENTRY: u8 = 1;
+
+/// Schema of a recorded channel, e.g. a protobuf descriptor or a JSON schema, which lets tools such
+/// as Foxglove decode the messages of MCAP logs, see `Recorder::create_mcap()`.
+///
+/// The schema is only stored in MCAP logs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChannelSchema {
+    /// Name of the schema, e.g. "foxglove.CompressedImage".
+    pub name: String,
+    /// Encoding of the schema, e.g. "protobuf" or "jsonschema".
+    pub encoding: String,
+    /// The schema itself.
+    pub data: Vec<u8>,
+    /// Encoding of the messages, e.g. "protobuf" or "json".
+    pub message_encoding: String,
+}
+
+/// Entry of a recorded log.
+///
+/// The log starts with [RECORD_MAGIC], followed by the entries in little-endian encoding:
+///
+///   * channel: kind (u8, 0), channel id (u32), name length (u32), name (utf8)
+///   * message: kind (u8, 1), channel id (u32), seconds (f64), payload length (u32), payload
+#[derive(Clone, Debug)]
+pub(crate) enum RecordEntry {
+    /// Declares the name of a channel, i.e. "actor.outbound", and its schema, if any.
+    Channel {
+        id: u32,
+        name: String,
+        schema: Option<ChannelSchema>,
+    },
+    /// Message of a channel, with the seconds elapsed since the recorder was created.
+    Message {
+        id: u32,
+        seconds: f64,
+        payload: Vec<u8>,
+    },
+}
+
+impl RecordEntry {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            RecordEntry::Channel { id, name, .. } => {
+                buf.push(CHANNEL_ENTRY);
+                buf.extend_from_slice(&id.to_le_bytes());
+                buf.extend_from_slice(&(name.len() as u32).to_le_bytes());
+                buf.extend_from_slice(name.as_bytes());
+            }
+            RecordEntry::Message {
+                id,
+                seconds,
+                payload,
+            } => {
+                buf.push(MESSAGE_ENTRY);
+                buf.extend_from_slice(&id.to_le_bytes());
+                buf.extend_from_slice(&seconds.to_le_bytes());
+                buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+                buf.extend_from_slice(payload);
+            }
+        }
+    }
+
+    /// Parses all entries of a recorded log, which is either in the format of the [Recorder], or an
+    /// MCAP log if the `mcap` feature is enabled.
+    pub(crate) fn parse_log(bytes: &[u8]) -> std::io::Result<Vec<RecordEntry>> {
+        let invalid =
+            |what: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, what.to_owned());
+        if bytes.starts_with(b"\x89MCAP") {
+            #[cfg(feature = "mcap")]
+            return mcap::McapLog::parse(bytes).map(|log| log.into_entries());
+            #[cfg(not(feature = "mcap"))]
+            return Err(invalid("reading MCAP logs requires the mcap feature"));
+        }
+        let mut rest = bytes
+            .strip_prefix(RECORD_MAGIC)
+            .ok_or_else(|| invalid("not a hollywood record log"))?;
+        let mut take = |len: usize| -> std::io::Result<&[u8]> {
+            if rest.len() < len {
+                return Err(invalid("truncated record log"));
+            }
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            Ok(head)
+        };
+        let mut entries = vec![];
+        while let Ok(kind) = take(1) {
+            let kind = kind[0];
+            let id = u32::from_le_bytes(take(4)?.try_into().unwrap());
+            match kind {
+                CHANNEL_ENTRY => {
+                    let len = u32::from_le_bytes(take(4)?.try_into().unwrap()) as usize;
+                    let name = String::from_utf8(take(len)?.to_vec())
+                        .map_err(|_| invalid("invalid channel name"))?;
+                    entries.push(RecordEntry::Channel {
+                        id,
+                        name,
+                        schema: None,
+                    });
+                }
+                MESSAGE_ENTRY => {
+                    let seconds = f64::from_le_bytes(take(8)?.try_into().unwrap());
+                    let len = u32::from_le_bytes(take(4)?.try_into().unwrap()) as usize;
+                    entries.push(RecordEntry::Message {
+                        id,
+                        seconds,
+                        payload: take(len)?.to_vec(),
+                    });
+                }
+                _ => return Err(invalid("unknown record entry")),
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// Encodes the entries of a recorded log in its file format.
+enum LogWriter {
+    Hollywood,
+    #[cfg(feature = "mcap")]
+    Mcap(Box<mcap::McapRecordWriter>),
+}
+
+impl LogWriter {
+    fn start(&mut self, buf: &mut Vec<u8>) {
+        match self {
+            LogWriter::Hollywood => buf.extend_from_slice(RECORD_MAGIC),
+            #[cfg(feature = "mcap")]
+            LogWriter::Mcap(writer) => buf.extend(writer.take_bytes()),
+        }
+    }
+
+    fn write_entry(&mut self, entry: &RecordEntry, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        match self {
+            LogWriter::Hollywood => entry.write_to(buf),
+            #[cfg(feature = "mcap")]
+            LogWriter::Mcap(writer) => writer.write_entry(entry, buf)?,
+        }
+        Ok(())
+    }
+
+    /// Returns the trailer of the log, which is written once the recording stopped.
+    fn finish(self) -> std::io::Result<Vec<u8>> {
+        match self {
+            LogWriter::Hollywood => Ok(vec![]),
+            #[cfg(feature = "mcap")]
+            LogWriter::Mcap(writer) => writer.finish(),
+        }
+    }
+}
+
+struct TapConnection<T, E> {
+    id: u32,
+    start: std::time::Instant,
+    encoder: Mutex<E>,
+    sender: tokio::sync::mpsc::UnboundedSender<RecordEntry>,
+    phantom: std::marker::PhantomData<fn(T)>,
+}
+
+impl<T: Send + Sync, E: Encoder<T> + Send> IsGenericConnection<T> for TapConnection<T, E>
+where
+    E::Error: std::fmt::Display,
+{
+    fn send_impl(&self, msg: T) {
+        let seconds = self.start.elapsed().as_secs_f64();
+        let mut payload = BytesMut::new();
+        if let Err(err) = self.encoder.lock().unwrap().encode(msg, &mut payload) {
+            warn!("Recorder: failed to encode message: {}", err);
+            return;
+        }
+        // The recorder might have failed, which is already logged.
+        let _ = self.sender.send(RecordEntry::Message {
+            id: self.id,
+            seconds,
+            payload: payload.to_vec(),
+        });
+    }
+}
+
+/// Records the message streams of a set of outbound channels to a log file, e.g. to debug a
+/// robotics pipeline offline using [Replay](crate::actors::Replay).
+///
+/// Each message is encoded using the codec given to [Recorder::tap()], and stored together with
+/// the time it was sent and the identity of its channel, i.e. "actor.outbound":
+///
+/// ``` ignore
+/// let mut recorder = Recorder::create(context, "run.hwrec")?;
+/// recorder.tap(context, &mut camera.outbound.image, ImageCodec::default());
+/// recorder.tap(context, &mut imu.outbound.reading, ImuCodec::default());
+/// ```
+///
+/// The log is written by a [BackgroundActor] while the pipeline is running, and flushed once the
+/// pipeline stops. If writing fails, the error is logged and the recording stops. With the `mcap`
+/// feature, the log can be written in the MCAP format instead, see `Recorder::create_mcap()`.
+pub struct Recorder {
+    /// The background actor which writes the log.
+    pub actor: BackgroundActor,
+    start: std::time::Instant,
+    next_id: u32,
+    sender: tokio::sync::mpsc::UnboundedSender<RecordEntry>,
+}
+
+impl Recorder {
+    /// Creates the log file at the given path, and the background actor which writes it.
+    pub fn create(context: &mut Hollywood, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::create_with_writer(
+            context,
+            path,
+            LogWriter::Hollywood,
+            std::time::Instant::now(),
+        )
+    }
+
+    /// Creates the log file at the given path in the MCAP format, and the background actor which
+    /// writes it.
+    ///
+    /// MCAP logs can be opened by other robotics tooling such as Foxglove, given the schemas of
+    /// the channels, see [Recorder::tap_with_schema()]. Channels without a schema are written as
+    /// schemaless JSON channels, whose messages are JSON arrays of the encoded bytes. The log
+    /// times of the messages are the wall-clock times at which they were sent.
+    #[cfg(feature = "mcap")]
+    pub fn create_mcap(context: &mut Hollywood, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let start = std::time::Instant::now();
+        let writer = LogWriter::Mcap(Box::new(mcap::McapRecordWriter::new(
+            std::time::SystemTime::now(),
+        )?));
+        Self::create_with_writer(context, path, writer, start)
+    }
+
+    fn create_with_writer(
+        context: &mut Hollywood,
+        path: impl AsRef<Path>,
+        mut log_writer: LogWriter,
+        start: std::time::Instant,
+    ) -> std::io::Result<Self> {
+        let mut file = std::fs::File::create(path.as_ref())?;
+        let mut buf = vec![];
+        log_writer.start(&mut buf);
+        std::io::Write::write_all(&mut file, &buf)?;
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<RecordEntry>();
+        let path = path.as_ref().display().to_string();
+        let actor =
+            BackgroundActor::from_named_task(context, "Recorder", move |mut kill| async move {
+                let mut writer = tokio::io::BufWriter::new(tokio::fs::File::from_std(file));
+                let mut buf = vec![];
+                let mut result = Ok(());
+                while result.is_ok() {
+                    tokio::select! {
+                        _ = kill.changed() => break,
+                        entry = receiver.recv() => match entry {
+                            Some(entry) => {
+                                buf.clear();
+                                result = log_writer.write_entry(&entry, &mut buf);
+                                if result.is_ok() {
+                                    result = writer.write_all(&buf).await;
+                                }
+                            }
+                            None => break,
+                        },
+                    }
+                }
+                while let (true, Ok(entry)) = (result.is_ok(), receiver.try_recv()) {
+                    buf.clear();
+                    result = log_writer.write_entry(&entry, &mut buf);
+                    if result.is_ok() {
+                        result = writer.write_all(&buf).await;
+                    }
+                }
+                if result.is_ok() {
+                    result = match log_writer.finish() {
+                        Ok(trailer) => writer.write_all(&trailer).await,
+                        Err(err) => Err(err),
+                    };
+                }
+                if result.is_ok() {
+                    result = writer.flush().await;
+                }
+                if let Err(err) = result {
+                    warn!("Recorder: failed to write {}: {}", path, err);
+                    let _ = kill.wait_for(|kill| *kill).await;
+                }
+            });
+        Ok(Self {
+            actor,
+            start,
+            next_id: 0,
+            sender,
+        })
+    }
+
+    /// Records all messages of the outbound channel, encoded with the given codec.
+    pub fn tap<T, E>(
+        &mut self,
+        context: &mut Hollywood,
+        outbound: &mut OutboundChannel<T>,
+        encoder: E,
+    ) where
+        T: Clone + Send + Sync + std::fmt::Debug + 'static,
+        E: Encoder<T> + Send + 'static,
+        E::Error: std::fmt::Display,
+    {
+        let name = format!("{}.{}", outbound.actor_name, outbound.name);
+        self.tap_impl(context, outbound, name, encoder, None);
+    }
+
+    /// Records all messages of the outbound channel under the given channel name, e.g. a topic
+    /// name such as "/camera/image", with the schema of the encoded messages.
+    ///
+    /// The schema is only stored in MCAP logs, see `Recorder::create_mcap()`.
+    pub fn tap_with_schema<T, E>(
+        &mut self,
+        context: &mut Hollywood,
+        outbound: &mut OutboundChannel<T>,
+        name: &str,
+        encoder: E,
+        schema: ChannelSchema,
+    ) where
+        T: Clone + Send + Sync + std::fmt::Debug + 'static,
+        E: Encoder<T> + Send + 'static,
+        E::Error: std::fmt::Display,
+    {
+        self.tap_impl(context, outbound, name.to_owned(), encoder, Some(schema));
+    }
+
+    fn tap_impl<T, E>(
+        &mut self,
+        context: &mut Hollywood,
+        outbound: &mut OutboundChannel<T>,
+        name: String,
+        encoder: E,
+        schema: Option<ChannelSchema>,
+    ) where
+        T: Clone + Send + Sync + std::fmt::Debug + 'static,
+        E: Encoder<T> + Send + 'static,
+        E::Error: std::fmt::Display,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        let _ = self.sender.send(RecordEntry::Channel { id, name, schema });
+        let connection = context.decorate_connection(
+            Connection {
+                from_actor: outbound.actor_name.clone(),
+                from: outbound.name.clone(),
+                to_actor: self.actor.actor_name.clone(),
+                to: "record".to_owned(),
+                type_name: std::any::type_name::<T>(),
+            },
+            Arc::new(TapConnection {
+                id,
+                start: self.start,
+                encoder: Mutex::new(encoder),
+                sender: self.sender.clone(),
+                phantom: std::marker::PhantomData,
+            }),
+        );
+        outbound.connection_register.push(connection);
+    }
+}
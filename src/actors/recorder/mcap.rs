@@ -0,0 +1,421 @@
+use crate::actors::recorder::ChannelSchema;
+use crate::actors::recorder::RecordEntry;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::warn;
+
+/// Message encoding of channels without a schema, see [McapLogWriter::add_channel()].
+pub const SCHEMALESS_MESSAGE_ENCODING: &str = "json";
+
+fn to_io_error(err: ::mcap::McapError) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+}
+
+/// Output of the MCAP writer, which is drained by [McapLogWriter::take_bytes()].
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes messages to an MCAP log, see <https://mcap.dev/spec>, which can be opened by robotics
+/// tooling such as Foxglove.
+///
+/// The log is written as a stream: the encoded records are buffered by the writer, and drained
+/// using [McapLogWriter::take_bytes()], e.g. to write them to an asynchronous file.
+pub struct McapLogWriter {
+    writer: ::mcap::Writer<::mcap::write::NoSeek<SharedBuffer>>,
+    output: SharedBuffer,
+    start_nanos: u64,
+    sequences: HashMap<u16, u32>,
+}
+
+impl McapLogWriter {
+    /// Creates the writer, where the log times of the messages are relative to the given start.
+    pub fn new(start: std::time::SystemTime) -> std::io::Result<Self> {
+        let output = SharedBuffer::default();
+        let writer = ::mcap::WriteOptions::new()
+            .library("hollywood")
+            .disable_seeking(true)
+            .create(::mcap::write::NoSeek::new(output.clone()))
+            .map_err(to_io_error)?;
+        Ok(Self {
+            writer,
+            output,
+            start_nanos: start
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_nanos() as u64),
+            sequences: HashMap::new(),
+        })
+    }
+
+    /// Adds a channel with the given topic name, e.g. "/camera/image", and returns its id.
+    ///
+    /// Channels without a schema are schemaless JSON channels, i.e. their messages must be encoded
+    /// as JSON, see [SCHEMALESS_MESSAGE_ENCODING].
+    pub fn add_channel(
+        &mut self,
+        topic: &str,
+        schema: Option<&ChannelSchema>,
+    ) -> std::io::Result<u16> {
+        let (schema_id, message_encoding) = match schema {
+            Some(schema) => (
+                self.writer
+                    .add_schema(&schema.name, &schema.encoding, &schema.data)
+                    .map_err(to_io_error)?,
+                schema.message_encoding.as_str(),
+            ),
+            None => (0, SCHEMALESS_MESSAGE_ENCODING),
+        };
+        self.writer
+            .add_channel(schema_id, topic, message_encoding, &BTreeMap::new())
+            .map_err(to_io_error)
+    }
+
+    /// Writes the encoded message of the channel, sent the given seconds after the start.
+    pub fn write_message(
+        &mut self,
+        channel_id: u16,
+        seconds: f64,
+        payload: &[u8],
+    ) -> std::io::Result<()> {
+        let sequence = self.sequences.entry(channel_id).or_insert(0);
+        *sequence += 1;
+        let log_time = self.start_nanos + (seconds.max(0.0) * 1e9) as u64;
+        self.writer
+            .write_to_known_channel(
+                &::mcap::records::MessageHeader {
+                    channel_id,
+                    sequence: *sequence,
+                    log_time,
+                    publish_time: log_time,
+                },
+                payload,
+            )
+            .map_err(to_io_error)
+    }
+
+    /// Takes the bytes which were written so far.
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        std::mem::take(&mut *self.output.0.lock().unwrap())
+    }
+
+    /// Finishes the log, i.e. writes the summary section, and returns the remaining bytes.
+    pub fn finish(mut self) -> std::io::Result<Vec<u8>> {
+        self.writer.finish().map_err(to_io_error)?;
+        Ok(self.take_bytes())
+    }
+}
+
+/// Writes the entries of a recorded log as an MCAP log, see `Recorder::create_mcap()`.
+///
+/// The encoded messages of channels without a schema are stored as JSON arrays of their bytes,
+/// see [bytes_to_json()], since these are schemaless JSON channels.
+pub(crate) struct McapRecordWriter {
+    writer: McapLogWriter,
+    /// MCAP channel id of each recorded channel, and whether it is schemaless.
+    channels: HashMap<u32, (u16, bool)>,
+}
+
+impl McapRecordWriter {
+    pub(crate) fn new(start: std::time::SystemTime) -> std::io::Result<Self> {
+        Ok(Self {
+            writer: McapLogWriter::new(start)?,
+            channels: HashMap::new(),
+        })
+    }
+
+    pub(crate) fn take_bytes(&mut self) -> Vec<u8> {
+        self.writer.take_bytes()
+    }
+
+    pub(crate) fn write_entry(
+        &mut self,
+        entry: &RecordEntry,
+        buf: &mut Vec<u8>,
+    ) -> std::io::Result<()> {
+        match entry {
+            RecordEntry::Channel { id, name, schema } => {
+                let channel_id = self.writer.add_channel(name, schema.as_ref())?;
+                self.channels.insert(*id, (channel_id, schema.is_none()));
+            }
+            RecordEntry::Message {
+                id,
+                seconds,
+                payload,
+            } => {
+                let Some((channel_id, schemaless)) = self.channels.get(id) else {
+                    warn!("Recorder: message of unknown channel {}", id);
+                    return Ok(());
+                };
+                if *schemaless {
+                    self.writer
+                        .write_message(*channel_id, *seconds, &bytes_to_json(payload))?;
+                } else {
+                    self.writer.write_message(*channel_id, *seconds, payload)?;
+                }
+            }
+        }
+        buf.extend(self.writer.take_bytes());
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> std::io::Result<Vec<u8>> {
+        self.writer.finish()
+    }
+}
+
+/// Encodes the bytes as a JSON array of numbers, e.g. `[1,2,3]`.
+pub(crate) fn bytes_to_json(bytes: &[u8]) -> Vec<u8> {
+    let numbers: Vec<String> = bytes.iter().map(|byte| byte.to_string()).collect();
+    format!("[{}]", numbers.join(",")).into_bytes()
+}
+
+/// Decodes a JSON array of numbers as written by [bytes_to_json()], or returns None if the JSON
+/// is not such an array.
+pub(crate) fn json_to_bytes(json: &[u8]) -> Option<Vec<u8>> {
+    let json = std::str::from_utf8(json).ok()?.trim();
+    let numbers = json.strip_prefix('[')?.strip_suffix(']')?.trim();
+    if numbers.is_empty() {
+        return Some(vec![]);
+    }
+    numbers
+        .split(',')
+        .map(|number| number.trim().parse().ok())
+        .collect()
+}
+
+/// Channel of an MCAP log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct McapChannel {
+    /// Id of the channel within the log.
+    pub id: u16,
+    /// Topic name of the channel.
+    pub topic: String,
+    /// Encoding of the messages, e.g. "json".
+    pub message_encoding: String,
+    /// Schema of the messages, if any.
+    pub schema: Option<ChannelSchema>,
+}
+
+/// Message of an MCAP log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct McapMessage {
+    /// Id of the channel of the message.
+    pub channel_id: u16,
+    /// Log time of the message, in seconds since the first message of the log.
+    pub seconds: f64,
+    /// The encoded message.
+    pub payload: Vec<u8>,
+}
+
+/// Channels and messages of an MCAP log, as read by [McapLog::parse()].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct McapLog {
+    /// Channels of the log, in the order of their first message.
+    pub channels: Vec<McapChannel>,
+    /// Messages of the log, ordered by log time.
+    pub messages: Vec<McapMessage>,
+}
+
+impl McapLog {
+    /// Parses the MCAP log, e.g. as written by [McapLogWriter] or by other robotics tooling.
+    pub fn parse(bytes: &[u8]) -> std::io::Result<Self> {
+        let mut log = McapLog::default();
+        let mut log_times = vec![];
+        for message in ::mcap::MessageStream::new(bytes).map_err(to_io_error)? {
+            let message = message.map_err(to_io_error)?;
+            let channel = &message.channel;
+            if !log.channels.iter().any(|known| known.id == channel.id) {
+                log.channels.push(McapChannel {
+                    id: channel.id,
+                    topic: channel.topic.clone(),
+                    message_encoding: channel.message_encoding.clone(),
+                    schema: channel.schema.as_ref().map(|schema| ChannelSchema {
+                        name: schema.name.clone(),
+                        encoding: schema.encoding.clone(),
+                        data: schema.data.to_vec(),
+                        message_encoding: channel.message_encoding.clone(),
+                    }),
+                });
+            }
+            log_times.push(message.log_time);
+            log.messages.push(McapMessage {
+                channel_id: channel.id,
+                seconds: 0.0,
+                payload: message.data.to_vec(),
+            });
+        }
+        let start = log_times.iter().copied().min().unwrap_or(0);
+        for (message, log_time) in log.messages.iter_mut().zip(log_times) {
+            message.seconds = (log_time - start) as f64 * 1e-9;
+        }
+        log.messages.sort_by(|a, b| a.seconds.total_cmp(&b.seconds));
+        Ok(log)
+    }
+
+    /// Converts the log into the entries of a recorded log, where the channel names are the topic
+    /// names.
+    ///
+    /// The messages of schemaless JSON channels, which were written by the [Recorder](
+    /// crate::actors::Recorder), are decoded from their JSON arrays. Other JSON messages, e.g.
+    /// written by other tooling, are kept as is.
+    pub(crate) fn into_entries(self) -> Vec<RecordEntry> {
+        let mut schemaless = vec![];
+        let mut entries = vec![];
+        for channel in self.channels {
+            if channel.schema.is_none() && channel.message_encoding == SCHEMALESS_MESSAGE_ENCODING {
+                schemaless.push(channel.id);
+            }
+            entries.push(RecordEntry::Channel {
+                id: channel.id as u32,
+                name: channel.topic,
+                schema: channel.schema,
+            });
+        }
+        for message in self.messages {
+            let payload = if schemaless.contains(&message.channel_id) {
+                json_to_bytes(&message.payload).unwrap_or(message.payload)
+            } else {
+                message.payload
+            };
+            entries.push(RecordEntry::Message {
+                id: message.channel_id as u32,
+                seconds: message.seconds,
+                payload,
+            });
+        }
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seconds_schema() -> ChannelSchema {
+        ChannelSchema {
+            name: "Seconds".to_owned(),
+            encoding: "jsonschema".to_owned(),
+            data: br#"{"type":"object","properties":{"seconds":{"type":"number"}}}"#.to_vec(),
+            message_encoding: "json".to_owned(),
+        }
+    }
+
+    #[test]
+    fn written_log_is_read_by_mcap_crate() {
+        let mut writer = McapLogWriter::new(std::time::SystemTime::now()).unwrap();
+        let seconds = writer
+            .add_channel("/timer/seconds", Some(&seconds_schema()))
+            .unwrap();
+        let raw = writer.add_channel("/raw", None).unwrap();
+        writer
+            .write_message(seconds, 0.0, br#"{"seconds":0.0}"#)
+            .unwrap();
+        writer.write_message(raw, 0.5, b"42").unwrap();
+        let mut bytes = writer.take_bytes();
+        bytes.extend(writer.finish().unwrap());
+
+        let messages: Vec<_> = ::mcap::MessageStream::new(&bytes)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].channel.topic, "/timer/seconds");
+        assert_eq!(messages[0].channel.message_encoding, "json");
+        let schema = messages[0].channel.schema.as_ref().unwrap();
+        assert_eq!(schema.name, "Seconds");
+        assert_eq!(schema.encoding, "jsonschema");
+        assert_eq!(&messages[0].data[..], br#"{"seconds":0.0}"#);
+        assert_eq!(messages[1].channel.topic, "/raw");
+        assert_eq!(messages[1].channel.message_encoding, "json");
+        assert!(messages[1].channel.schema.is_none());
+        assert_eq!(messages[1].log_time - messages[0].log_time, 500_000_000);
+    }
+
+    #[test]
+    fn log_of_mcap_crate_is_parsed() {
+        let mut bytes = std::io::Cursor::new(vec![]);
+        {
+            let mut writer = ::mcap::Writer::new(&mut bytes).unwrap();
+            let schema = seconds_schema();
+            let schema_id = writer
+                .add_schema(&schema.name, &schema.encoding, &schema.data)
+                .unwrap();
+            let channel_id = writer
+                .add_channel(schema_id, "/timer/seconds", "json", &BTreeMap::new())
+                .unwrap();
+            for (sequence, log_time) in [(1, 2_000_000_000), (2, 2_250_000_000)] {
+                writer
+                    .write_to_known_channel(
+                        &::mcap::records::MessageHeader {
+                            channel_id,
+                            sequence,
+                            log_time,
+                            publish_time: log_time,
+                        },
+                        b"{}",
+                    )
+                    .unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let log = McapLog::parse(bytes.get_ref()).unwrap();
+        assert_eq!(log.channels.len(), 1);
+        assert_eq!(log.channels[0].topic, "/timer/seconds");
+        assert_eq!(log.channels[0].schema, Some(seconds_schema()));
+        let seconds: Vec<f64> = log.messages.iter().map(|msg| msg.seconds).collect();
+        assert_eq!(seconds, vec![0.0, 0.25]);
+
+        assert!(McapLog::parse(b"not an mcap log").is_err());
+    }
+
+    #[test]
+    fn schemaless_messages_round_trip_as_json() {
+        assert_eq!(bytes_to_json(&[0, 7, 255]), b"[0,7,255]");
+        assert_eq!(json_to_bytes(b"[0, 7, 255]"), Some(vec![0, 7, 255]));
+        assert_eq!(json_to_bytes(b"[]"), Some(vec![]));
+        assert_eq!(json_to_bytes(br#"{"seconds":0.5}"#), None);
+        assert_eq!(json_to_bytes(b"[256]"), None);
+
+        let mut writer = McapRecordWriter::new(std::time::SystemTime::now()).unwrap();
+        let mut bytes = writer.take_bytes();
+        for entry in [
+            RecordEntry::Channel {
+                id: 3,
+                name: "Periodic_0.time_stamp".to_owned(),
+                schema: None,
+            },
+            RecordEntry::Message {
+                id: 3,
+                seconds: 0.1,
+                payload: 0.1f64.to_le_bytes().to_vec(),
+            },
+        ] {
+            writer.write_entry(&entry, &mut bytes).unwrap();
+        }
+        bytes.extend(writer.finish().unwrap());
+
+        let log = McapLog::parse(&bytes).unwrap();
+        assert_eq!(log.channels[0].message_encoding, "json");
+        assert!(std::str::from_utf8(&log.messages[0].payload)
+            .unwrap()
+            .starts_with('['));
+        let entries = log.into_entries();
+        assert!(matches!(
+            &entries[1],
+            RecordEntry::Message { payload, .. } if payload == &0.1f64.to_le_bytes()
+        ));
+    }
+}
@@ -0,0 +1,377 @@
+use std::net::SocketAddr;
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+
+/// Bridge actors, which ship the messages of channels between pipelines over TCP or UDP.
+#[cfg(feature = "serde")]
+pub mod bridge;
+
+/// Time a connecting peer has to complete the handshake, see [RemoteSecurity::accept()].
+const HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Reply to a valid token.
+const HANDSHAKE_ACCEPTED: u8 = 1;
+
+/// Security settings of a connection between pipelines in different processes, e.g. between a
+/// robot and an operator laptop.
+///
+/// Both ends of a connection must use the same settings:
+///
+/// ``` ignore
+/// let mut security = RemoteSecurity::with_token(&std::env::var("BRIDGE_TOKEN")?);
+/// security.tls = Some(RemoteTls::client_from_pem("ca.pem", "robot.local")?);
+/// let stream = security.connect(addr).await?;
+/// ```
+///
+/// By default, connections are neither encrypted nor authenticated.
+#[derive(Clone, Debug, Default)]
+pub struct RemoteSecurity {
+    /// Shared token, which the connecting peer presents and the accepting peer checks.
+    ///
+    /// Over TCP, the token is presented once per connection, and connections with a wrong token
+    /// are closed. Over UDP, each datagram carries the token, and datagrams with a wrong token are
+    /// dropped. The token is sent in the clear unless TLS is used, hence UDP connections are only
+    /// protected against senders which do not know the token.
+    pub token: Option<String>,
+    /// TLS settings, which are only supported over TCP.
+    #[cfg(feature = "tls")]
+    pub tls: Option<RemoteTls>,
+}
+
+impl RemoteSecurity {
+    /// Security settings which require the given shared token, without TLS.
+    pub fn with_token(token: &str) -> Self {
+        Self {
+            token: Some(token.to_owned()),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// Connects to the given address over TCP, establishes TLS if configured, and presents the
+    /// token, if any.
+    pub async fn connect(&self, addr: SocketAddr) -> std::io::Result<Box<dyn RemoteStream>> {
+        let tcp = tokio::net::TcpStream::connect(addr).await?;
+        let _ = tcp.set_nodelay(true);
+        #[allow(unused_mut)]
+        let mut stream: Box<dyn RemoteStream> = Box::new(tcp);
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let RemoteTls::Client {
+                config,
+                server_name,
+            } = tls
+            else {
+                return Err(invalid_tls(
+                    "connecting requires a TLS client configuration",
+                ));
+            };
+            let server_name =
+                tokio_rustls::rustls::pki_types::ServerName::try_from(server_name.clone())
+                    .map_err(invalid_tls)?;
+            let connector = tokio_rustls::TlsConnector::from(config.clone());
+            stream = Box::new(connector.connect(server_name, stream).await?);
+        }
+        if let Some(token) = &self.token {
+            write_frame(&mut stream, token.as_bytes()).await?;
+            let mut reply = [0u8; 1];
+            let accepted =
+                stream.read_exact(&mut reply).await.is_ok() && reply[0] == HANDSHAKE_ACCEPTED;
+            if !accepted {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "token rejected",
+                ));
+            }
+        }
+        Ok(stream)
+    }
+
+    /// Accepts the TCP connection of a peer, serves TLS if configured, and checks its token, if
+    /// any.
+    ///
+    /// Peers which do not complete the handshake within a few seconds are rejected.
+    pub async fn accept(
+        &self,
+        tcp: tokio::net::TcpStream,
+    ) -> std::io::Result<Box<dyn RemoteStream>> {
+        tokio::time::timeout(HANDSHAKE_TIMEOUT, self.accept_impl(tcp))
+            .await
+            .unwrap_or_else(|_| Err(std::io::ErrorKind::TimedOut.into()))
+    }
+
+    async fn accept_impl(
+        &self,
+        tcp: tokio::net::TcpStream,
+    ) -> std::io::Result<Box<dyn RemoteStream>> {
+        #[allow(unused_mut)]
+        let mut stream: Box<dyn RemoteStream> = Box::new(tcp);
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let RemoteTls::Server(config) = tls else {
+                return Err(invalid_tls("accepting requires a TLS server configuration"));
+            };
+            let acceptor = tokio_rustls::TlsAcceptor::from(config.clone());
+            stream = Box::new(acceptor.accept(stream).await?);
+        }
+        if let Some(token) = &self.token {
+            // The frame is read before the peer is authenticated, hence it is not larger than the
+            // expected token.
+            let presented = read_frame(&mut stream, token.len()).await?;
+            if !token_matches(token.as_bytes(), &presented) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "invalid token",
+                ));
+            }
+            stream.write_all(&[HANDSHAKE_ACCEPTED]).await?;
+            stream.flush().await?;
+        }
+        Ok(stream)
+    }
+
+    /// Prefixes the datagram with the token, if any.
+    pub fn seal_datagram(&self, frame: &[u8]) -> Vec<u8> {
+        let mut datagram = vec![];
+        if let Some(token) = &self.token {
+            datagram.extend_from_slice(&(token.len() as u32).to_le_bytes());
+            datagram.extend_from_slice(token.as_bytes());
+        }
+        datagram.extend_from_slice(frame);
+        datagram
+    }
+
+    /// Checks and strips the token prefix of a datagram, if a token is required. Returns `None`
+    /// if the token is missing or wrong.
+    pub fn open_datagram<'a>(&self, datagram: &'a [u8]) -> Option<&'a [u8]> {
+        let Some(token) = &self.token else {
+            return Some(datagram);
+        };
+        let len = u32::from_le_bytes(datagram.get(..4)?.try_into().unwrap()) as usize;
+        let presented = datagram.get(4..4 + len)?;
+        token_matches(token.as_bytes(), presented).then(|| &datagram[4 + len..])
+    }
+}
+
+/// TLS settings of a remote connection, see [RemoteSecurity::tls].
+#[cfg(feature = "tls")]
+#[derive(Clone, Debug)]
+pub enum RemoteTls {
+    /// TLS client, i.e. the connecting peer, which verifies the certificate of the accepting peer
+    /// against the given server name.
+    Client {
+        /// Configuration of the client, e.g. its root certificates.
+        config: Arc<tokio_rustls::rustls::ClientConfig>,
+        /// Name of the server, which its certificate must be valid for.
+        server_name: String,
+    },
+    /// TLS server, i.e. the accepting peer.
+    Server(Arc<tokio_rustls::rustls::ServerConfig>),
+}
+
+#[cfg(feature = "tls")]
+impl RemoteTls {
+    /// Creates a TLS client which trusts the CA certificates in the given PEM file, and expects
+    /// the server to present a certificate for the given server name.
+    pub fn client_from_pem(
+        ca_path: impl AsRef<std::path::Path>,
+        server_name: &str,
+    ) -> std::io::Result<Self> {
+        let mut roots = tokio_rustls::rustls::RootCertStore::empty();
+        for cert in read_pem_certs(ca_path.as_ref())? {
+            roots.add(cert).map_err(invalid_tls)?;
+        }
+        let config = tokio_rustls::rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(RemoteTls::Client {
+            config: Arc::new(config),
+            server_name: server_name.to_owned(),
+        })
+    }
+
+    /// Creates a TLS server with the certificate chain and private key in the given PEM files.
+    pub fn server_from_pem(
+        cert_path: impl AsRef<std::path::Path>,
+        key_path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<Self> {
+        let certs = read_pem_certs(cert_path.as_ref())?;
+        let key = rustls_pemfile::private_key(&mut std::io::BufReader::new(std::fs::File::open(
+            key_path.as_ref(),
+        )?))?
+        .ok_or_else(|| invalid_tls("no private key found"))?;
+        let config = tokio_rustls::rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(invalid_tls)?;
+        Ok(RemoteTls::Server(Arc::new(config)))
+    }
+}
+
+#[cfg(feature = "tls")]
+fn read_pem_certs(
+    path: &std::path::Path,
+) -> std::io::Result<Vec<tokio_rustls::rustls::pki_types::CertificateDer<'static>>> {
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(std::fs::File::open(path)?))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    if certs.is_empty() {
+        return Err(invalid_tls(format!(
+            "no certificate found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+#[cfg(feature = "tls")]
+fn invalid_tls(err: impl std::fmt::Display) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, err.to_string())
+}
+
+/// Byte stream of a remote connection, i.e. a TCP connection, possibly wrapped by TLS.
+pub trait RemoteStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<S: AsyncRead + AsyncWrite + Unpin + Send> RemoteStream for S {}
+
+/// Compares the tokens in constant time, so that the token cannot be guessed byte by byte.
+fn token_matches(expected: &[u8], presented: &[u8]) -> bool {
+    expected.len() == presented.len()
+        && expected
+            .iter()
+            .zip(presented)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Writes the frame, prefixed by its length (u32, little-endian).
+async fn write_frame(stream: &mut (impl AsyncWrite + Unpin), frame: &[u8]) -> std::io::Result<()> {
+    let mut buf = Vec::with_capacity(4 + frame.len());
+    buf.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+    buf.extend_from_slice(frame);
+    stream.write_all(&buf).await?;
+    // TLS streams buffer the written data.
+    stream.flush().await
+}
+
+/// Reads a frame of at most `max_len` bytes.
+async fn read_frame(
+    stream: &mut (impl AsyncRead + Unpin),
+    max_len: usize,
+) -> std::io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len).await?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > max_len {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("frame of {} bytes, expected at most {}", len, max_len),
+        ));
+    }
+    let mut frame = vec![0u8; len];
+    stream.read_exact(&mut frame).await?;
+    Ok(frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_matches_only_equal_tokens() {
+        assert!(token_matches(b"secret", b"secret"));
+        assert!(token_matches(b"", b""));
+        assert!(!token_matches(b"secret", b"secreT"));
+        assert!(!token_matches(b"secret", b"secret!"));
+        assert!(!token_matches(b"secret", b"secre"));
+        assert!(!token_matches(b"secret", b""));
+    }
+
+    #[test]
+    fn datagrams_carry_the_token() {
+        let security = RemoteSecurity::with_token("secret");
+        let datagram = security.seal_datagram(b"frame");
+        assert_eq!(security.open_datagram(&datagram), Some(&b"frame"[..]));
+        assert_eq!(
+            RemoteSecurity::with_token("guess!").open_datagram(&datagram),
+            None
+        );
+        assert_eq!(security.open_datagram(b"frame"), None);
+        assert_eq!(
+            RemoteSecurity::default().open_datagram(b"frame"),
+            Some(&b"frame"[..])
+        );
+    }
+
+    /// Connects a client to a server with the given security settings, and returns the results
+    /// of both sides of the handshake.
+    async fn handshake(
+        server: RemoteSecurity,
+        client: RemoteSecurity,
+    ) -> (std::io::Result<()>, std::io::Result<()>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            server.accept(tcp).await.map(|_| ())
+        });
+        let connected = client.connect(addr).await.map(|_| ());
+        (accepted.await.unwrap(), connected)
+    }
+
+    #[tokio::test]
+    async fn handshake_accepts_valid_token() {
+        let (accepted, connected) = handshake(
+            RemoteSecurity::with_token("secret"),
+            RemoteSecurity::with_token("secret"),
+        )
+        .await;
+        accepted.unwrap();
+        connected.unwrap();
+    }
+
+    #[tokio::test]
+    async fn handshake_rejects_invalid_token() {
+        let (accepted, connected) = handshake(
+            RemoteSecurity::with_token("secret"),
+            RemoteSecurity::with_token("guess!"),
+        )
+        .await;
+        assert_eq!(
+            accepted.err().unwrap().kind(),
+            std::io::ErrorKind::PermissionDenied
+        );
+        assert_eq!(
+            connected.err().unwrap().kind(),
+            std::io::ErrorKind::PermissionDenied
+        );
+    }
+
+    #[tokio::test]
+    async fn handshake_rejects_oversized_token_frame() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            RemoteSecurity::with_token("secret")
+                .accept(tcp)
+                .await
+                .map(|_| ())
+        });
+        // The length prefix announces a frame far larger than the token, which is rejected
+        // without waiting for its payload.
+        let mut tcp = tokio::net::TcpStream::connect(addr).await.unwrap();
+        tcp.write_all(&u32::MAX.to_le_bytes()).await.unwrap();
+        let accepted = tokio::time::timeout(HANDSHAKE_TIMEOUT / 2, accepted)
+            .await
+            .expect("oh no, the handshake waited for the payload")
+            .unwrap();
+        assert_eq!(
+            accepted.err().unwrap().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use crate::actors::remote::read_frame;
+use crate::actors::remote::write_frame;
+use crate::actors::remote::RemoteSecurity;
+use crate::actors::remote::RemoteStream;
+use crate::actors::BackgroundActor;
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::info;
+use tracing::warn;
+
+/// Largest payload of a single UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// Largest frame accepted over TCP, to guard against corrupt length prefixes.
+const MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// Transport of the remote bridge actors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoteTransport {
+    /// Reliable, ordered stream. Each frame is prefixed by its length (u32, little-endian).
+    Tcp,
+    /// Unreliable datagrams, one frame per datagram. Frames larger than a datagram are dropped.
+    Udp,
+}
+
+impl RemoteSecurity {
+    fn assert_supported(&self, transport: RemoteTransport) {
+        #[cfg(feature = "tls")]
+        assert!(
+            self.tls.is_none() || transport == RemoteTransport::Tcp,
+            "oh no, TLS is only supported over TCP"
+        );
+        #[cfg(not(feature = "tls"))]
+        let _ = transport;
+    }
+}
+
+/// Frame sent over the wire: the channel name and the JSON encoded message.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct RemoteFrame {
+    channel: String,
+    payload: serde_json::Value,
+}
+
+impl RemoteFrame {
+    fn encode<T: Serialize>(channel: &str, msg: &T) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(&RemoteFrame {
+            channel: channel.to_owned(),
+            payload: serde_json::to_value(msg)?,
+        })
+    }
+}
+
+struct RemoteConnection<T> {
+    channel: String,
+    sender: tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
+    phantom: std::marker::PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + Send + Sync> IsGenericConnection<T> for RemoteConnection<T> {
+    fn send_impl(&self, msg: T) {
+        match RemoteFrame::encode(&self.channel, &msg) {
+            // The bridge might have failed, which is already logged.
+            Ok(frame) => {
+                let _ = self.sender.send(frame);
+            }
+            Err(err) => warn!("RemoteOutbound: failed to encode {}: {}", self.channel, err),
+        }
+    }
+}
+
+/// Bridge which ships the messages of outbound channels to a [RemoteInbound] in another process,
+/// possibly on another machine.
+///
+/// Messages are serialized as JSON, and tagged with the name of the remote channel:
+///
+/// ``` ignore
+/// let mut remote = RemoteOutbound::create(context, RemoteTransport::Tcp, "10.0.0.2:7000".parse()?);
+/// remote.connect(context, &mut localizer.outbound.pose, "pose");
+/// ```
+///
+/// The sending is done by a [BackgroundActor] while the pipeline is running. Over TCP, the
+/// bridge connects to the remote address when the pipeline starts, and reconnects if the
+/// connection is lost. Messages sent while there is no connection are dropped.
+///
+/// By default, the bridge neither encrypts nor authenticates its connection. Use
+/// [RemoteOutbound::create_secure()] for bridges over untrusted networks.
+pub struct RemoteOutbound {
+    /// The background actor which sends the messages.
+    pub actor: BackgroundActor,
+    sender: tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
+}
+
+impl RemoteOutbound {
+    /// Creates the background actor which sends the messages to the remote address.
+    pub fn create(context: &mut Hollywood, transport: RemoteTransport, addr: SocketAddr) -> Self {
+        Self::create_secure(context, transport, addr, RemoteSecurity::default())
+    }
+
+    /// Creates the background actor which sends the messages to the remote address, using the
+    /// given token and TLS settings, see [RemoteSecurity].
+    ///
+    /// Panics if TLS is configured for UDP.
+    pub fn create_secure(
+        context: &mut Hollywood,
+        transport: RemoteTransport,
+        addr: SocketAddr,
+        security: RemoteSecurity,
+    ) -> Self {
+        security.assert_supported(transport);
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
+        let actor =
+            BackgroundActor::from_named_task(context, "RemoteOutbound", move |kill| async move {
+                match transport {
+                    RemoteTransport::Tcp => send_tcp(addr, &security, &mut receiver, kill).await,
+                    RemoteTransport::Udp => send_udp(addr, &security, &mut receiver, kill).await,
+                }
+            });
+        Self { actor, sender }
+    }
+
+    /// Sends all messages of the outbound channel to the remote channel with the given name.
+    pub fn connect<T>(
+        &mut self,
+        context: &mut Hollywood,
+        outbound: &mut OutboundChannel<T>,
+        channel: &str,
+    ) where
+        T: Serialize + Clone + Send + Sync + std::fmt::Debug + 'static,
+    {
+        let connection = context.decorate_connection(
+            Connection {
+                from_actor: outbound.actor_name.clone(),
+                from: outbound.name.clone(),
+                to_actor: self.actor.actor_name.clone(),
+                to: channel.to_owned(),
+                type_name: std::any::type_name::<T>(),
+            },
+            Arc::new(RemoteConnection {
+                channel: channel.to_owned(),
+                sender: self.sender.clone(),
+                phantom: std::marker::PhantomData,
+            }),
+        );
+        outbound.connection_register.push(connection);
+    }
+}
+
+async fn send_tcp(
+    addr: SocketAddr,
+    security: &RemoteSecurity,
+    receiver: &mut tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+    mut kill: tokio::sync::watch::Receiver<bool>,
+) {
+    let retry = std::time::Duration::from_millis(500);
+    let mut stream: Option<Box<dyn RemoteStream>> = None;
+    let mut next_attempt = tokio::time::Instant::now();
+    loop {
+        if stream.is_none() && tokio::time::Instant::now() >= next_attempt {
+            let connected = tokio::select! {
+                _ = kill.changed() => return,
+                connected = security.connect(addr) => connected,
+            };
+            match connected {
+                Ok(connected) => {
+                    info!("RemoteOutbound: connected to {}", addr);
+                    stream = Some(connected);
+                }
+                Err(err) => {
+                    warn!("RemoteOutbound: failed to connect to {}: {}", addr, err);
+                    next_attempt = tokio::time::Instant::now() + retry;
+                }
+            }
+        }
+        let frame = tokio::select! {
+            _ = kill.changed() => return,
+            _ = tokio::time::sleep_until(next_attempt), if stream.is_none() => continue,
+            frame = receiver.recv() => match frame {
+                Some(frame) => frame,
+                None => break,
+            },
+        };
+        let Some(connected) = stream.as_mut() else {
+            continue;
+        };
+        if let Err(err) = write_frame(connected, &frame).await {
+            warn!("RemoteOutbound: lost connection to {}: {}", addr, err);
+            stream = None;
+            next_attempt = tokio::time::Instant::now() + retry;
+        }
+    }
+    // Keep running until the pipeline stops, like any other actor.
+    let _ = kill.wait_for(|kill| *kill).await;
+}
+
+async fn send_udp(
+    addr: SocketAddr,
+    security: &RemoteSecurity,
+    receiver: &mut tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+    mut kill: tokio::sync::watch::Receiver<bool>,
+) {
+    let local: SocketAddr = if addr.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0u16; 8], 0).into()
+    };
+    match tokio::net::UdpSocket::bind(local).await {
+        Ok(socket) => loop {
+            tokio::select! {
+                _ = kill.changed() => return,
+                frame = receiver.recv() => match frame {
+                    Some(frame) => {
+                        // Each datagram carries the token, if any.
+                        let datagram = security.seal_datagram(&frame);
+                        if datagram.len() > MAX_DATAGRAM_SIZE {
+                            warn!(
+                                "RemoteOutbound: dropped frame of {} bytes, larger than a datagram",
+                                frame.len()
+                            );
+                            continue;
+                        }
+                        if let Err(err) = socket.send_to(&datagram, addr).await {
+                            warn!("RemoteOutbound: failed to send to {}: {}", addr, err);
+                        }
+                    }
+                    None => break,
+                },
+            }
+        },
+        Err(err) => warn!("RemoteOutbound: failed to bind udp socket: {}", err),
+    }
+    // Keep running until the pipeline stops, like any other actor.
+    let _ = kill.wait_for(|kill| *kill).await;
+}
+
+type RemoteTarget = Box<dyn FnMut(serde_json::Value) + Send>;
+type RemoteTargets = HashMap<String, RemoteTarget>;
+
+/// Bridge which feeds the messages sent by [RemoteOutbound]s in other processes into inbound
+/// channels.
+///
+/// Remote channels are connected by name to inbound channels, and deserialized from JSON:
+///
+/// ``` ignore
+/// let mut remote = RemoteInbound::bind(RemoteTransport::Tcp, "0.0.0.0:7000".parse()?);
+/// remote.connect("pose", &planner.inbound.pose);
+/// remote.spawn(context);
+/// ```
+///
+/// The address is bound when the pipeline starts. Over TCP, any number of remote outbound bridges
+/// can connect at the same time. Frames of channels which are not connected are skipped.
+///
+/// By default, the bridge accepts any connection. Use [RemoteInbound::with_security()] for
+/// bridges over untrusted networks.
+pub struct RemoteInbound {
+    transport: RemoteTransport,
+    addr: SocketAddr,
+    security: RemoteSecurity,
+    targets: RemoteTargets,
+}
+
+impl RemoteInbound {
+    /// Creates a bridge which listens on the given address.
+    pub fn bind(transport: RemoteTransport, addr: SocketAddr) -> Self {
+        Self {
+            transport,
+            addr,
+            security: RemoteSecurity::default(),
+            targets: HashMap::new(),
+        }
+    }
+
+    /// Requires connecting [RemoteOutbound]s to present the given token, and serves TLS, if
+    /// configured, see [RemoteSecurity].
+    ///
+    /// Panics if TLS is configured for UDP.
+    pub fn with_security(mut self, security: RemoteSecurity) -> Self {
+        security.assert_supported(self.transport);
+        self.security = security;
+        self
+    }
+
+    /// Feeds the remote channel with the given name into the inbound channel.
+    ///
+    /// Panics if the remote channel is already connected.
+    pub fn connect<T, M>(&mut self, channel: &str, inbound: &InboundChannel<T, M>)
+    where
+        T: DeserializeOwned + Send + Sync + std::fmt::Debug + 'static,
+        M: IsInboundMessageNew<T>,
+    {
+        let sender = inbound.sender.clone();
+        let name = inbound.name.clone();
+        let remote = channel.to_owned();
+        let previous = self.targets.insert(
+            channel.to_owned(),
+            Box::new(move |payload| match serde_json::from_value::<T>(payload) {
+                Ok(msg) => {
+                    // The actor might have stopped, which is not an error.
+                    let _ = sender.send(M::new(name.clone(), msg));
+                }
+                Err(err) => warn!("RemoteInbound: failed to decode {}: {}", remote, err),
+            }),
+        );
+        assert!(
+            previous.is_none(),
+            "oh no, remote channel {} is already connected",
+            channel
+        );
+    }
+
+    /// Creates the background actor which receives the messages while the pipeline is running.
+    pub fn spawn(self, context: &mut Hollywood) -> BackgroundActor {
+        let RemoteInbound {
+            transport,
+            addr,
+            security,
+            targets,
+        } = self;
+        BackgroundActor::from_named_task(context, "RemoteInbound", move |kill| async move {
+            // The targets are shared with the tasks of the TCP connections.
+            let targets = Arc::new(tokio::sync::Mutex::new(targets));
+            match transport {
+                RemoteTransport::Tcp => receive_tcp(addr, Arc::new(security), targets, kill).await,
+                RemoteTransport::Udp => receive_udp(addr, &security, targets, kill).await,
+            }
+        })
+    }
+}
+
+async fn dispatch(targets: &tokio::sync::Mutex<RemoteTargets>, bytes: &[u8]) {
+    match serde_json::from_slice::<RemoteFrame>(bytes) {
+        Ok(frame) => {
+            if let Some(target) = targets.lock().await.get_mut(&frame.channel) {
+                target(frame.payload);
+            }
+        }
+        Err(err) => warn!("RemoteInbound: invalid frame: {}", err),
+    }
+}
+
+async fn receive_tcp(
+    addr: SocketAddr,
+    security: Arc<RemoteSecurity>,
+    targets: Arc<tokio::sync::Mutex<RemoteTargets>>,
+    mut kill: tokio::sync::watch::Receiver<bool>,
+) {
+    let listener = match tokio::net::TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            warn!("RemoteInbound: failed to bind {}: {}", addr, err);
+            let _ = kill.wait_for(|kill| *kill).await;
+            return;
+        }
+    };
+    let mut peers = tokio::task::JoinSet::new();
+    loop {
+        tokio::select! {
+            _ = kill.changed() => break,
+            Some(joined) = peers.join_next(), if !peers.is_empty() => {
+                // Reap the tasks of disconnected peers.
+                if let Err(err) = joined {
+                    warn!("RemoteInbound: connection task failed: {}", err);
+                }
+            }
+            accepted = listener.accept() => match accepted {
+                Ok((tcp, peer)) => {
+                    let targets = targets.clone();
+                    let security = security.clone();
+                    peers.spawn(async move {
+                        let mut stream = match security.accept(tcp).await {
+                            Ok(stream) => stream,
+                            Err(err) => {
+                                warn!("RemoteInbound: rejected {}: {}", peer, err);
+                                return;
+                            }
+                        };
+                        info!("RemoteInbound: accepted {}", peer);
+                        loop {
+                            match read_frame(&mut stream, MAX_FRAME_SIZE).await {
+                                Ok(frame) => dispatch(&targets, &frame).await,
+                                Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+                                    warn!("RemoteInbound: {} from {}", err, peer);
+                                    break;
+                                }
+                                Err(_) => break,
+                            }
+                        }
+                        info!("RemoteInbound: {} disconnected", peer);
+                    });
+                }
+                Err(err) => warn!("RemoteInbound: failed to accept: {}", err),
+            },
+        }
+    }
+    peers.shutdown().await;
+}
+
+async fn receive_udp(
+    addr: SocketAddr,
+    security: &RemoteSecurity,
+    targets: Arc<tokio::sync::Mutex<RemoteTargets>>,
+    mut kill: tokio::sync::watch::Receiver<bool>,
+) {
+    let socket = match tokio::net::UdpSocket::bind(addr).await {
+        Ok(socket) => socket,
+        Err(err) => {
+            warn!("RemoteInbound: failed to bind {}: {}", addr, err);
+            let _ = kill.wait_for(|kill| *kill).await;
+            return;
+        }
+    };
+    let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+    loop {
+        tokio::select! {
+            _ = kill.changed() => return,
+            received = socket.recv_from(&mut buf) => match received {
+                Ok((len, peer)) => match security.open_datagram(&buf[..len]) {
+                    Some(frame) => dispatch(&targets, frame).await,
+                    None => warn!("RemoteInbound: dropped datagram with invalid token from {}", peer),
+                },
+                Err(err) => warn!("RemoteInbound: failed to receive: {}", err),
+            },
+        }
+    }
+}
@@ -0,0 +1,548 @@
+use crate::actors::recorder::ChannelSchema;
+use crate::actors::recorder::RecordEntry;
+use crate::actors::BackgroundActor;
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::time::Instant;
+use tokio_util::bytes::BytesMut;
+use tokio_util::codec::Decoder;
+use tracing::info;
+use tracing::warn;
+
+type ReplayTarget = Box<dyn FnMut(&[u8]) + Send>;
+
+/// Source which feeds a log recorded by a [Recorder](crate::actors::Recorder) back into a
+/// pipeline, at the original or at a scaled speed.
+///
+/// Recorded channels are connected by name, i.e. "actor.outbound", to inbound channels, and
+/// decoded using the given codec:
+///
+/// ``` ignore
+/// let mut replay = Replay::open("run.hwrec")?.with_speed(2.0).cancel_when_done();
+/// replay.connect("Camera_0.image", &detector.inbound.image, ImageCodec::default());
+/// let replay = replay.spawn(context);
+/// ```
+///
+/// Channels which are not connected are skipped. The replay can be driven like a media player
+/// through the `control` in-request channel of the [ReplayActor], see [ReplayCommand].
+pub struct Replay {
+    channels: HashMap<String, u32>,
+    schemas: HashMap<String, ChannelSchema>,
+    messages: Vec<(u32, f64, Vec<u8>)>,
+    targets: HashMap<u32, ReplayTarget>,
+    speed: f64,
+    paused: bool,
+    cancel_when_done: bool,
+}
+
+impl Replay {
+    /// Reads the log at the given path.
+    ///
+    /// With the `mcap` feature, MCAP logs can be read as well, e.g. as recorded by other robotics
+    /// tooling. The channel names are the topic names then, and the times of the messages are
+    /// relative to the first message.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut channels = HashMap::new();
+        let mut schemas = HashMap::new();
+        let mut messages = vec![];
+        for entry in RecordEntry::parse_log(&std::fs::read(path)?)? {
+            match entry {
+                RecordEntry::Channel { id, name, schema } => {
+                    if let Some(schema) = schema {
+                        schemas.insert(name.clone(), schema);
+                    }
+                    channels.insert(name, id);
+                }
+                RecordEntry::Message {
+                    id,
+                    seconds,
+                    payload,
+                } => messages.push((id, seconds, payload)),
+            }
+        }
+        Ok(Self {
+            channels,
+            schemas,
+            messages,
+            targets: HashMap::new(),
+            speed: 1.0,
+            paused: false,
+            cancel_when_done: false,
+        })
+    }
+
+    /// Names of the recorded channels, i.e. "actor.outbound".
+    pub fn channel_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.channels.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Schema of the recorded channel with the given name, which is only stored in MCAP logs.
+    pub fn channel_schema(&self, channel: &str) -> Option<&ChannelSchema> {
+        self.schemas.get(channel)
+    }
+
+    /// Number of recorded messages.
+    pub fn num_messages(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Sets the speed factor of the replay, e.g. 2.0 replays the log twice as fast as recorded.
+    /// An infinite speed replays the log as fast as possible.
+    ///
+    /// Panics if the speed is not positive.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        assert!(speed > 0.0, "replay speed must be positive, got {}", speed);
+        self.speed = speed;
+        self
+    }
+
+    /// Starts the replay paused, e.g. to step through the log using [ReplayCommand::Step].
+    pub fn start_paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+
+    /// Cancels the pipeline once the log was replayed.
+    pub fn cancel_when_done(mut self) -> Self {
+        self.cancel_when_done = true;
+        self
+    }
+
+    /// Feeds the recorded channel with the given name into the inbound channel, decoded with the
+    /// given codec.
+    ///
+    /// Panics if there is no recorded channel with the given name.
+    pub fn connect<T, M, D>(
+        &mut self,
+        channel: &str,
+        inbound: &InboundChannel<T, M>,
+        mut decoder: D,
+    ) where
+        T: Send + Sync + std::fmt::Debug + 'static,
+        M: IsInboundMessageNew<T>,
+        D: Decoder<Item = T> + Send + 'static,
+        D::Error: std::fmt::Display,
+    {
+        let id = *self.channels.get(channel).unwrap_or_else(|| {
+            panic!(
+                "oh no, there is no recorded channel {}, available: {:?}",
+                channel,
+                self.channel_names()
+            )
+        });
+        let sender = inbound.sender.clone();
+        let name = inbound.name.clone();
+        let channel = channel.to_owned();
+        self.targets.insert(
+            id,
+            Box::new(
+                move |payload| match decoder.decode_eof(&mut BytesMut::from(payload)) {
+                    Ok(Some(msg)) => {
+                        // The actor might have stopped, which is not an error.
+                        let _ = sender.send(M::new(name.clone(), msg));
+                    }
+                    Ok(None) => warn!("Replay: incomplete message of {}", channel),
+                    Err(err) => warn!("Replay: failed to decode message of {}: {}", channel, err),
+                },
+            ),
+        );
+    }
+
+    /// Creates the background actor which replays the log while the pipeline is running.
+    pub fn spawn(self, context: &mut Hollywood) -> ReplayActor {
+        let Replay {
+            messages,
+            targets,
+            speed,
+            paused,
+            cancel_when_done,
+            ..
+        } = self;
+        // Messages of channels which are not connected are skipped, also when stepping.
+        let messages: Vec<_> = messages
+            .into_iter()
+            .filter(|(id, _, _)| targets.contains_key(id))
+            .collect();
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let player = ReplayPlayer {
+            transport: ReplayTransport::new(
+                messages.iter().map(|(_, seconds, _)| *seconds).collect(),
+                speed,
+                paused,
+            ),
+            messages: messages
+                .into_iter()
+                .map(|(id, _, payload)| (id, payload))
+                .collect(),
+            targets,
+        };
+        let cancel_requester = context.get_cancel_request_sender();
+        let actor = BackgroundActor::from_named_task(context, "Replay", move |kill| {
+            player.run(kill, receiver, cancel_when_done.then_some(cancel_requester))
+        });
+        let control =
+            InRequestChannel::new(context, &actor.actor_name, &sender, "control".to_owned());
+        ReplayActor { actor, control }
+    }
+}
+
+/// Transport command of a replay, which lets a UI or a test harness drive the replay of a log
+/// like a media player. Each command is replied to with the resulting [ReplayStatus].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayCommand {
+    /// Pauses the replay.
+    Pause,
+    /// Resumes the replay at the current position.
+    Resume,
+    /// Seeks to the given time of the log, in seconds since the recording started. The replay
+    /// continues with the first message at or after that time.
+    Seek(f64),
+    /// Pauses the replay, and replays the next N messages at once.
+    Step(usize),
+    /// Sets the speed factor, e.g. 2.0 replays the log twice as fast as recorded. Non-positive
+    /// speeds are ignored.
+    SetSpeed(f64),
+    /// Does not change the replay, but replies with its status.
+    Status,
+}
+
+/// Status of a replay, as replied to each [ReplayCommand].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayStatus {
+    /// Current time of the log, in seconds since the recording started.
+    pub position: f64,
+    /// Number of messages replayed so far, i.e. the index of the next message.
+    pub index: usize,
+    /// Total number of messages to replay.
+    pub num_messages: usize,
+    /// Whether the replay is paused.
+    pub paused: bool,
+    /// Speed factor of the replay.
+    pub speed: f64,
+}
+
+/// In-request message of a replay actor, which receives the transport commands through its
+/// `control` in-request channel.
+#[derive(Debug)]
+pub enum ReplayInRequestMessage {
+    /// Transport command, see [ReplayCommand].
+    Control(RequestWithReplyChannel<ReplayCommand, ReplayStatus>),
+}
+
+impl IsInRequestMessage for ReplayInRequestMessage {
+    type Prop = NullProp;
+    type State = NullState;
+    type OutboundHub = NullOutbound;
+    type OutRequestHub = NullOutRequests;
+
+    fn in_request_channel(&self) -> String {
+        "control".to_owned()
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<ReplayCommand, ReplayStatus>>
+    for ReplayInRequestMessage
+{
+    fn new(
+        _inbound_name: String,
+        request: RequestWithReplyChannel<ReplayCommand, ReplayStatus>,
+    ) -> Self {
+        ReplayInRequestMessage::Control(request)
+    }
+}
+
+/// The replay actor, as returned by [Replay::spawn()].
+pub struct ReplayActor {
+    /// The background actor which replays the log.
+    pub actor: BackgroundActor,
+    /// In-request channel to control the replay, e.g. by a UI actor, or from outside the pipeline
+    /// using [InRequestChannel::client()]:
+    ///
+    /// ``` ignore
+    /// let control = replay.control.client();
+    /// control.request(ReplayCommand::Pause).await?;
+    /// control.request(ReplayCommand::Step(1)).await?;
+    /// ```
+    pub control: InRequestChannel<
+        RequestWithReplyChannel<ReplayCommand, ReplayStatus>,
+        ReplayInRequestMessage,
+    >,
+}
+
+/// Replays the messages, while processing the transport commands.
+struct ReplayPlayer {
+    messages: Vec<(u32, Vec<u8>)>,
+    targets: HashMap<u32, ReplayTarget>,
+    transport: ReplayTransport,
+}
+
+impl ReplayPlayer {
+    async fn run(
+        mut self,
+        mut kill: tokio::sync::watch::Receiver<bool>,
+        mut receiver: tokio::sync::mpsc::UnboundedReceiver<ReplayInRequestMessage>,
+        cancel_when_done: Option<tokio::sync::mpsc::UnboundedSender<CancelRequest>>,
+    ) {
+        self.transport.restart();
+        let mut requests_open = true;
+        let mut finished = false;
+        while !*kill.borrow() {
+            let deadline = self.transport.next_deadline();
+            tokio::select! {
+                _ = kill.changed() => break,
+                request = receiver.recv(), if requests_open => match request {
+                    Some(ReplayInRequestMessage::Control(request)) => {
+                        request.reply_from_request(|command| self.on_command(command));
+                    }
+                    None => requests_open = false,
+                },
+                _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)),
+                    if deadline.is_some() => {
+                    let index = self.transport.index();
+                    self.replay(index);
+                    self.transport.advance();
+                }
+            }
+            if !finished && self.transport.is_finished() {
+                finished = true;
+                info!("Replay: {} messages replayed", self.messages.len());
+                if let Some(cancel_requester) = &cancel_when_done {
+                    let _ = cancel_requester.send(CancelRequest);
+                }
+            }
+        }
+        // Commands which are still queued are answered with the final status.
+        while let Ok(ReplayInRequestMessage::Control(request)) = receiver.try_recv() {
+            request.reply_from_request(|_| self.transport.status());
+        }
+    }
+
+    fn replay(&mut self, index: usize) {
+        let (id, payload) = &self.messages[index];
+        if let Some(target) = self.targets.get_mut(id) {
+            target(payload);
+        }
+    }
+
+    fn on_command(&mut self, command: ReplayCommand) -> ReplayStatus {
+        let messages = &self.messages;
+        let targets = &mut self.targets;
+        self.transport.on_command(command, |index| {
+            let (id, payload) = &messages[index];
+            if let Some(target) = targets.get_mut(id) {
+                target(payload);
+            }
+        })
+    }
+}
+
+/// Transport of a replay, i.e. the mapping from the times of the recorded messages to the
+/// instants at which they are due, which is changed by [ReplayCommand]s.
+///
+/// A replay source waits until [ReplayTransport::next_deadline()], replays the message at
+/// [ReplayTransport::index()] and calls [ReplayTransport::advance()]:
+///
+/// ``` ignore
+/// while let Some(deadline) = transport.next_deadline() {
+///     tokio::time::sleep_until(deadline).await;
+///     replay(transport.index());
+///     transport.advance();
+/// }
+/// ```
+pub struct ReplayTransport {
+    times: Vec<f64>,
+    index: usize,
+    speed: f64,
+    paused: bool,
+    /// Time of the log at the anchor instant.
+    position: f64,
+    /// Instant at which the replay was last paused, resumed, sought or changed its speed.
+    anchor: Instant,
+}
+
+impl ReplayTransport {
+    /// Creates the transport for messages recorded at the given times, in seconds and in
+    /// ascending order, which starts at the first message.
+    ///
+    /// An infinite speed replays the messages as fast as possible. Panics if the speed is not
+    /// positive.
+    pub fn new(times: Vec<f64>, speed: f64, paused: bool) -> Self {
+        assert!(speed > 0.0, "replay speed must be positive, got {}", speed);
+        Self {
+            position: times.first().copied().unwrap_or(0.0),
+            times,
+            index: 0,
+            speed,
+            paused,
+            anchor: Instant::now(),
+        }
+    }
+
+    /// Index of the next message to replay.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Whether all messages were replayed.
+    pub fn is_finished(&self) -> bool {
+        self.index == self.times.len()
+    }
+
+    /// Restarts the clock of the replay at the current position, e.g. once the pipeline runs.
+    pub fn restart(&mut self) {
+        self.anchor = Instant::now();
+    }
+
+    /// Instant at which the next message is due, if any, and if the replay is not paused.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        if self.paused {
+            return None;
+        }
+        let seconds = self.times.get(self.index)?;
+        let offset = (seconds - self.position) / self.speed;
+        if offset.is_finite() && offset > 0.0 {
+            Some(self.anchor + std::time::Duration::from_secs_f64(offset))
+        } else {
+            Some(self.anchor)
+        }
+    }
+
+    /// Current time of the log.
+    pub fn current_position(&self) -> f64 {
+        if self.paused {
+            return self.position;
+        }
+        let advanced = self.position + self.anchor.elapsed().as_secs_f64() * self.speed;
+        match self.times.get(self.index) {
+            // The log time does not run ahead of the next message, e.g. for infinite speeds.
+            Some(next) if advanced.is_nan() || advanced >= *next => next.max(self.position),
+            _ if advanced.is_finite() => advanced,
+            _ => self
+                .times
+                .last()
+                .map_or(self.position, |last| last.max(self.position)),
+        }
+    }
+
+    /// Marks the message at [ReplayTransport::index()] as replayed.
+    pub fn advance(&mut self) {
+        let Some(seconds) = self.times.get(self.index) else {
+            return;
+        };
+        if self.paused {
+            self.position = *seconds;
+        }
+        self.index += 1;
+    }
+
+    /// Status of the replay.
+    pub fn status(&self) -> ReplayStatus {
+        ReplayStatus {
+            position: self.current_position(),
+            index: self.index,
+            num_messages: self.times.len(),
+            paused: self.paused,
+            speed: self.speed,
+        }
+    }
+
+    /// Applies the transport command, and returns the resulting status. Messages which are
+    /// stepped over are replayed by calling `replay` with their index.
+    pub fn on_command(
+        &mut self,
+        command: ReplayCommand,
+        mut replay: impl FnMut(usize),
+    ) -> ReplayStatus {
+        match command {
+            ReplayCommand::Pause => {
+                self.reanchor();
+                self.paused = true;
+            }
+            ReplayCommand::Resume => {
+                self.reanchor();
+                self.paused = false;
+            }
+            ReplayCommand::Seek(seconds) => {
+                self.index = self.times.partition_point(|recorded| *recorded < seconds);
+                self.position = seconds;
+                self.anchor = Instant::now();
+            }
+            ReplayCommand::Step(n) => {
+                self.reanchor();
+                self.paused = true;
+                for _ in 0..n.min(self.times.len() - self.index) {
+                    replay(self.index);
+                    self.advance();
+                }
+            }
+            ReplayCommand::SetSpeed(speed) => {
+                if speed > 0.0 {
+                    self.reanchor();
+                    self.speed = speed;
+                } else {
+                    warn!("Replay: ignoring invalid speed: {}", speed);
+                }
+            }
+            ReplayCommand::Status => {}
+        }
+        self.status()
+    }
+
+    /// Moves the anchor to now, keeping the current time of the log.
+    fn reanchor(&mut self) {
+        self.position = self.current_position();
+        self.anchor = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_and_seek() {
+        let mut transport = ReplayTransport::new(vec![1.0, 2.0, 3.0, 4.0], 1.0, true);
+        assert_eq!(transport.next_deadline(), None);
+
+        let mut replayed = vec![];
+        let status = transport.on_command(ReplayCommand::Step(2), |index| replayed.push(index));
+        assert_eq!(replayed, vec![0, 1]);
+        assert_eq!(status.index, 2);
+        assert_eq!(status.position, 2.0);
+        assert!(status.paused);
+
+        let status = transport.on_command(ReplayCommand::Seek(3.5), |_| unreachable!());
+        assert_eq!(status.index, 3);
+        assert_eq!(status.position, 3.5);
+
+        // Steps beyond the end of the log stop at the last message.
+        let status = transport.on_command(ReplayCommand::Step(5), |index| replayed.push(index));
+        assert_eq!(replayed, vec![0, 1, 3]);
+        assert_eq!(status.position, 4.0);
+        assert!(transport.is_finished());
+        assert_eq!(transport.next_deadline(), None);
+    }
+
+    #[test]
+    fn resume_and_speed() {
+        let mut transport = ReplayTransport::new(vec![0.0, 10.0], 1.0, true);
+        transport.on_command(ReplayCommand::Resume, |_| unreachable!());
+        let deadline = transport.next_deadline().unwrap();
+        assert!(deadline <= Instant::now());
+        transport.advance();
+
+        let status = transport.on_command(ReplayCommand::SetSpeed(-1.0), |_| unreachable!());
+        assert_eq!(status.speed, 1.0);
+        transport.on_command(ReplayCommand::SetSpeed(5.0), |_| unreachable!());
+        let deadline = transport.next_deadline().unwrap();
+        assert!(deadline <= Instant::now() + std::time::Duration::from_secs(2));
+        assert!(deadline > Instant::now() + std::time::Duration::from_secs(1));
+
+        transport.on_command(ReplayCommand::Pause, |_| unreachable!());
+        assert_eq!(transport.next_deadline(), None);
+        assert!(transport.current_position() < 10.0);
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt::Debug;
+
+/// External resource owned by a resource actor, such as a database connection or a serial port.
+///
+/// A resource actor is generated from a trait of resource operations using the
+/// [resource_actor](crate::macros::resource_actor) macro. The resource is opened once on the
+/// first operation and closed when the actor shuts down, or when a message is received on its
+/// `close` inbound channel. Since many actors can send requests to the same in-request channel,
+/// the resource is shared by all of them.
+pub trait IsResource: Send + Sync + 'static {
+    /// Configuration to open the resource, e.g. a path or an address. It is the prop type of the
+    /// resource actor.
+    type Config: Clone + Debug + Send + Sync + 'static;
+
+    /// Opens the resource.
+    fn open(config: &Self::Config) -> Self;
+
+    /// Closes the resource. The default implementation does nothing, i.e. the resource is simply
+    /// dropped.
+    fn close(&mut self) {}
+}
+
+/// State of a resource actor: the resource, if it is open.
+pub struct ResourceState<R: IsResource> {
+    resource: Option<R>,
+    num_opened: u64,
+}
+
+impl<R: IsResource> Default for ResourceState<R> {
+    fn default() -> Self {
+        Self {
+            resource: None,
+            num_opened: 0,
+        }
+    }
+}
+
+impl<R: IsResource> Debug for ResourceState<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceState")
+            .field("is_open", &self.is_open())
+            .field("num_opened", &self.num_opened)
+            .finish()
+    }
+}
+
+impl<R: IsResource> ResourceState<R> {
+    /// Returns the resource, opening it first if needed.
+    pub fn resource(&mut self, config: &R::Config) -> &mut R {
+        if self.resource.is_none() {
+            self.num_opened += 1;
+        }
+        self.resource.get_or_insert_with(|| R::open(config))
+    }
+
+    /// Whether the resource is currently open.
+    pub fn is_open(&self) -> bool {
+        self.resource.is_some()
+    }
+
+    /// Number of times the resource was opened.
+    pub fn num_opened(&self) -> u64 {
+        self.num_opened
+    }
+
+    /// Closes the resource, if it is open. It is reopened on the next operation.
+    pub fn close(&mut self) {
+        if let Some(mut resource) = self.resource.take() {
+            resource.close();
+        }
+    }
+}
+
+impl<R: IsResource> Drop for ResourceState<R> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
@@ -0,0 +1,177 @@
+use crate::actors::BackgroundActor;
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use futures_util::stream::SelectAll;
+use futures_util::Stream;
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::warn;
+
+type Ros2Subscriptions = SelectAll<Pin<Box<dyn Stream<Item = ()> + Send>>>;
+
+struct Ros2PublishConnection<T> {
+    topic: String,
+    publisher: r2r::PublisherUntyped,
+    phantom: std::marker::PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + Send + Sync> IsGenericConnection<T> for Ros2PublishConnection<T> {
+    fn send_impl(&self, msg: T) {
+        match serde_json::to_value(&msg) {
+            Ok(value) => {
+                if let Err(err) = self.publisher.publish(value) {
+                    warn!("Ros2Bridge: failed to publish to {}: {}", self.topic, err);
+                }
+            }
+            Err(err) => warn!(
+                "Ros2Bridge: failed to encode message of {}: {}",
+                self.topic, err
+            ),
+        }
+    }
+}
+
+/// Bridge between hollywood channels and ROS2 topics.
+///
+/// Outbound channels are published to ROS2 topics, and inbound channels are subscribed to ROS2
+/// topics. Messages are mapped to and from the ROS2 message type via serde, hence the field names
+/// of the hollywood message type must match the ROS2 message definition:
+///
+/// ``` ignore
+/// #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// pub struct Twist { pub linear: Vector3, pub angular: Vector3 }
+///
+/// let mut ros = Ros2Bridge::create(context, "hollywood", "/robot")?;
+/// ros.publish(context, &mut planner.outbound.cmd_vel, "cmd_vel", "geometry_msgs/msg/Twist")?;
+/// ros.subscribe(&planner.inbound.odom, "odom", "nav_msgs/msg/Odometry")?;
+/// ```
+///
+/// The ROS2 node is spun by a [BackgroundActor] while the pipeline is running. Messages which
+/// fail to map are logged and dropped. This bridge is only available with the `ros2` feature,
+/// which requires a sourced ROS2 installation at build time.
+pub struct Ros2Bridge {
+    /// The background actor which spins the ROS2 node.
+    pub actor: BackgroundActor,
+    node: Arc<Mutex<r2r::Node>>,
+    subscriptions: Arc<tokio::sync::Mutex<Ros2Subscriptions>>,
+}
+
+impl Ros2Bridge {
+    /// Creates a ROS2 node with the given name and namespace, and the background actor which
+    /// spins it.
+    pub fn create(
+        context: &mut Hollywood,
+        node_name: &str,
+        namespace: &str,
+    ) -> Result<Self, r2r::Error> {
+        let ros_context = r2r::Context::create()?;
+        let node = Arc::new(Mutex::new(r2r::Node::create(
+            ros_context,
+            node_name,
+            namespace,
+        )?));
+        let subscriptions = Arc::new(tokio::sync::Mutex::new(Ros2Subscriptions::new()));
+        let spin_interval = std::time::Duration::from_millis(10);
+        let actor = BackgroundActor::from_named_task(context, "Ros2Bridge", {
+            let node = node.clone();
+            let subscriptions = subscriptions.clone();
+            move |mut kill| async move {
+                let mut subscriptions = subscriptions.lock_owned().await;
+                let mut spin = tokio::time::interval(spin_interval);
+                loop {
+                    tokio::select! {
+                        _ = kill.changed() => return,
+                        _ = spin.tick() => {
+                            node.lock().unwrap().spin_once(std::time::Duration::ZERO);
+                        }
+                        _ = subscriptions.next(), if !subscriptions.is_empty() => {}
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            actor,
+            node,
+            subscriptions,
+        })
+    }
+
+    /// Publishes all messages of the outbound channel to the ROS2 topic of the given type, e.g.
+    /// "geometry_msgs/msg/Twist".
+    pub fn publish<T>(
+        &mut self,
+        context: &mut Hollywood,
+        outbound: &mut OutboundChannel<T>,
+        topic: &str,
+        topic_type: &str,
+    ) -> Result<(), r2r::Error>
+    where
+        T: Serialize + Clone + Send + Sync + std::fmt::Debug + 'static,
+    {
+        let publisher = self.node.lock().unwrap().create_publisher_untyped(
+            topic,
+            topic_type,
+            r2r::QosProfile::default(),
+        )?;
+        let connection = context.decorate_connection(
+            Connection {
+                from_actor: outbound.actor_name.clone(),
+                from: outbound.name.clone(),
+                to_actor: self.actor.actor_name.clone(),
+                to: topic.to_owned(),
+                type_name: std::any::type_name::<T>(),
+            },
+            Arc::new(Ros2PublishConnection {
+                topic: topic.to_owned(),
+                publisher,
+                phantom: std::marker::PhantomData,
+            }),
+        );
+        outbound.connection_register.push(connection);
+        Ok(())
+    }
+
+    /// Feeds the messages of the ROS2 topic of the given type, e.g. "nav_msgs/msg/Odometry", into
+    /// the inbound channel.
+    pub fn subscribe<T, M>(
+        &mut self,
+        inbound: &InboundChannel<T, M>,
+        topic: &str,
+        topic_type: &str,
+    ) -> Result<(), r2r::Error>
+    where
+        T: DeserializeOwned + Send + Sync + std::fmt::Debug + 'static,
+        M: IsInboundMessageNew<T>,
+    {
+        let stream = self.node.lock().unwrap().subscribe_untyped(
+            topic,
+            topic_type,
+            r2r::QosProfile::default(),
+        )?;
+        let sender = inbound.sender.clone();
+        let name = inbound.name.clone();
+        let topic = topic.to_owned();
+        let forward = stream.map(move |value| match value {
+            Ok(value) => match serde_json::from_value::<T>(value) {
+                Ok(msg) => {
+                    // The actor might have stopped, which is not an error.
+                    let _ = sender.send(M::new(name.clone(), msg));
+                }
+                Err(err) => warn!("Ros2Bridge: failed to decode message of {}: {}", topic, err),
+            },
+            Err(err) => warn!(
+                "Ros2Bridge: failed to receive message of {}: {}",
+                topic, err
+            ),
+        });
+        self.subscriptions
+            .try_lock()
+            .expect("subscriptions are not in use during configuration")
+            .push(Box::pin(forward));
+        Ok(())
+    }
+}
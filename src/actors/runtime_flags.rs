@@ -0,0 +1,246 @@
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+/// Value of a runtime flag.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FlagValue {
+    /// Boolean flag, e.g. to toggle debug outputs.
+    Bool(bool),
+    /// Numeric flag, e.g. a verbosity level or a tuning parameter.
+    Number(f64),
+}
+
+impl FlagValue {
+    /// Whether the flag is enabled: true for `Bool(true)` and non-zero numbers.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            FlagValue::Bool(b) => *b,
+            FlagValue::Number(n) => *n != 0.0,
+        }
+    }
+
+    /// Numeric value of the flag, with booleans mapped to 0.0 and 1.0.
+    pub fn as_number(&self) -> f64 {
+        match self {
+            FlagValue::Bool(b) => f64::from(u8::from(*b)),
+            FlagValue::Number(n) => *n,
+        }
+    }
+}
+
+impl From<bool> for FlagValue {
+    fn from(value: bool) -> Self {
+        FlagValue::Bool(value)
+    }
+}
+
+impl From<f64> for FlagValue {
+    fn from(value: f64) -> Self {
+        FlagValue::Number(value)
+    }
+}
+
+/// Set of named runtime flags.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Flags {
+    /// Flag values by name.
+    pub values: BTreeMap<String, FlagValue>,
+}
+
+impl Flags {
+    /// Adds the flag, builder-style.
+    pub fn with(mut self, name: &str, value: impl Into<FlagValue>) -> Self {
+        self.values.insert(name.to_owned(), value.into());
+        self
+    }
+
+    /// Returns the value of the flag, if set.
+    pub fn get(&self, name: &str) -> Option<FlagValue> {
+        self.values.get(name).copied()
+    }
+
+    /// Whether the flag is set and enabled, see [FlagValue::is_enabled()].
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|v| v.is_enabled())
+    }
+
+    /// Numeric value of the flag, or the given default if not set.
+    pub fn number_or(&self, name: &str, default: f64) -> f64 {
+        self.get(name).map_or(default, |v| v.as_number())
+    }
+
+    /// Sets the flag and returns the previous value.
+    pub fn set(&mut self, name: String, value: FlagValue) -> Option<FlagValue> {
+        self.values.insert(name, value)
+    }
+}
+
+/// State of the runtime flags actor.
+#[derive(Clone, Debug, Default)]
+pub struct RuntimeFlagsState {
+    /// Current flags.
+    pub flags: Flags,
+}
+
+impl RuntimeFlagsState {
+    fn set(
+        &mut self,
+        name: String,
+        value: FlagValue,
+        outbound: &RuntimeFlagsOutbound,
+    ) -> Option<FlagValue> {
+        let previous = self.flags.set(name, value);
+        if previous != Some(value) {
+            outbound.flags.send(self.flags.clone());
+        }
+        previous
+    }
+}
+
+/// Outbound hub for the runtime flags actor.
+#[actor_outputs]
+pub struct RuntimeFlagsOutbound {
+    /// All current flags, sent initially and whenever a flag changes.
+    pub flags: OutboundChannel<Flags>,
+}
+
+/// Inbound message for the runtime flags actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    RuntimeFlagsInbound,
+    {
+        NullProp,
+        RuntimeFlagsState,
+        RuntimeFlagsOutbound,
+        NullOutRequests,
+        RuntimeFlagsInRequestMessage
+    })]
+pub enum RuntimeFlagsInboundMessage {
+    /// Sets the flag of the given name.
+    Set((String, FlagValue)),
+}
+
+impl HasOnMessage for RuntimeFlagsInboundMessage {
+    fn on_message(
+        self,
+        _prop: &NullProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            RuntimeFlagsInboundMessage::Set((name, value)) => {
+                state.set(name, value, outbound);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<(String, FlagValue)> for RuntimeFlagsInboundMessage {
+    fn new(_inbound_name: String, msg: (String, FlagValue)) -> Self {
+        RuntimeFlagsInboundMessage::Set(msg)
+    }
+}
+
+/// In-request message for the runtime flags actor.
+#[derive(ActorClient, Debug)]
+#[actor_in_requests(
+    RuntimeFlagsInRequest,
+    {
+        NullProp,
+        RuntimeFlagsState,
+        RuntimeFlagsOutbound,
+        NullOutRequests,
+        RuntimeFlagsInboundMessage
+    })]
+pub enum RuntimeFlagsInRequestMessage {
+    /// Sets the flag of the given name, and replies with the previous value.
+    Update(RequestWithReplyChannel<(String, FlagValue), Option<FlagValue>>),
+    /// Replies with the value of the flag of the given name.
+    Get(RequestWithReplyChannel<String, Option<FlagValue>>),
+    /// Replies with all current flags.
+    List(RequestWithReplyChannel<(), Flags>),
+}
+
+impl HasOnRequestMessage for RuntimeFlagsInRequestMessage {
+    fn on_request(
+        self,
+        _prop: &NullProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            RuntimeFlagsInRequestMessage::Update(request) => {
+                request.reply_from_request(|(name, value)| state.set(name, value, outbound));
+            }
+            RuntimeFlagsInRequestMessage::Get(request) => {
+                request.reply_from_request(|name| state.flags.get(&name));
+            }
+            RuntimeFlagsInRequestMessage::List(request) => {
+                request.reply(state.flags.clone());
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<(String, FlagValue), Option<FlagValue>>>
+    for RuntimeFlagsInRequestMessage
+{
+    fn new(
+        _inbound_name: String,
+        request: RequestWithReplyChannel<(String, FlagValue), Option<FlagValue>>,
+    ) -> Self {
+        RuntimeFlagsInRequestMessage::Update(request)
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<String, Option<FlagValue>>>
+    for RuntimeFlagsInRequestMessage
+{
+    fn new(
+        _inbound_name: String,
+        request: RequestWithReplyChannel<String, Option<FlagValue>>,
+    ) -> Self {
+        RuntimeFlagsInRequestMessage::Get(request)
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<(), Flags>> for RuntimeFlagsInRequestMessage {
+    fn new(_inbound_name: String, request: RequestWithReplyChannel<(), Flags>) -> Self {
+        RuntimeFlagsInRequestMessage::List(request)
+    }
+}
+
+/// Runtime flags actor, which stores named boolean and numeric flags.
+///
+/// The flags are set through the `set` inbound channel or the `update` in-request channel, e.g.
+/// from external code using [RuntimeFlagsInRequest::client()]. Other actors consult the flags by
+/// connecting to the `flags` outbound channel: The flags are sent initially and whenever a flag
+/// changes, hence each connected actor always knows the current flags. This allows to toggle
+/// debug outputs, expensive visualizations or experimental code paths at runtime.
+#[actor(RuntimeFlagsInboundMessage, RuntimeFlagsInRequestMessage)]
+type RuntimeFlags = Actor<
+    NullProp,
+    RuntimeFlagsInbound,
+    RuntimeFlagsInRequest,
+    RuntimeFlagsState,
+    RuntimeFlagsOutbound,
+    NullOutRequests,
+>;
+
+impl RuntimeFlags {
+    /// Creates the runtime flags actor with the given initial flags.
+    pub fn new(context: &mut Hollywood, flags: Flags) -> Self {
+        let actor = RuntimeFlags::from_prop_and_state(
+            context,
+            NullProp {},
+            RuntimeFlagsState {
+                flags: flags.clone(),
+            },
+        );
+        // Sent before the pipeline starts, hence it is the first message of all subscribers.
+        actor.outbound.flags.send(flags);
+        actor
+    }
+}
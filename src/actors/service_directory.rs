@@ -0,0 +1,142 @@
+use crate::prelude::*;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Named service, i.e. the address of a request/reply API, registered at the service directory.
+///
+/// The address is stored type-erased, so that services with arbitrary request and reply types
+/// can be registered at the same directory.
+#[derive(Clone, Debug, Default)]
+pub struct ServiceEntry {
+    /// Name of the service.
+    pub name: String,
+    /// Type-erased [Address] of the service.
+    pub address: AnyMessage,
+}
+
+impl ServiceEntry {
+    /// Creates a new service entry from the given name and address.
+    pub fn new<Request: Send + Sync + 'static, Reply: Send + Sync + 'static>(
+        name: &str,
+        address: Address<Request, Reply>,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            address: AnyMessage::new(address),
+        }
+    }
+
+    /// Returns the typed address of the service, or an error if the service has a different
+    /// request or reply type.
+    pub fn address<Request: Send + Sync + 'static, Reply: Send + Sync + 'static>(
+        &self,
+    ) -> Result<Address<Request, Reply>, AnyMessageError> {
+        self.address.downcast::<Address<Request, Reply>>()
+    }
+}
+
+/// State of the service directory actor.
+#[derive(Clone, Debug, Default)]
+pub struct ServiceDirectoryState {
+    /// Registered services by name.
+    pub services: HashMap<String, ServiceEntry>,
+}
+
+/// Inbound message for the service directory actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    ServiceDirectoryInbound,
+    {
+        NullProp,
+        ServiceDirectoryState,
+        NullOutbound,
+        NullOutRequests,
+        ServiceDirectoryInRequestMessage
+    })]
+pub enum ServiceDirectoryInboundMessage {
+    /// Registers a service. An existing service of the same name is replaced.
+    Register(ServiceEntry),
+}
+
+impl HasOnMessage for ServiceDirectoryInboundMessage {
+    fn on_message(
+        self,
+        _prop: &NullProp,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ServiceDirectoryInboundMessage::Register(entry) => {
+                if let Some(previous) = state.services.insert(entry.name.clone(), entry) {
+                    warn!("ServiceDirectory: service {} was replaced", previous.name);
+                }
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<ServiceEntry> for ServiceDirectoryInboundMessage {
+    fn new(_inbound_name: String, msg: ServiceEntry) -> Self {
+        ServiceDirectoryInboundMessage::Register(msg)
+    }
+}
+
+/// In-request message for the service directory actor.
+#[derive(Debug)]
+#[actor_in_requests(
+    ServiceDirectoryInRequest,
+    {
+        NullProp,
+        ServiceDirectoryState,
+        NullOutbound,
+        NullOutRequests,
+        ServiceDirectoryInboundMessage
+    })]
+pub enum ServiceDirectoryInRequestMessage {
+    /// Resolves the service of the given name. The reply is None if there is no such service.
+    Resolve(RequestWithReplyChannel<String, Option<ServiceEntry>>),
+}
+
+impl HasOnRequestMessage for ServiceDirectoryInRequestMessage {
+    fn on_request(
+        self,
+        _prop: &NullProp,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ServiceDirectoryInRequestMessage::Resolve(request) => {
+                request.reply_from_request(|name| state.services.get(&name).cloned());
+            }
+        }
+    }
+}
+
+impl IsInRequestMessageNew<RequestWithReplyChannel<String, Option<ServiceEntry>>>
+    for ServiceDirectoryInRequestMessage
+{
+    fn new(
+        _inbound_name: String,
+        request: RequestWithReplyChannel<String, Option<ServiceEntry>>,
+    ) -> Self {
+        ServiceDirectoryInRequestMessage::Resolve(request)
+    }
+}
+
+/// Service directory actor, which resolves service names to request addresses.
+///
+/// Actors register named services through the `register` inbound channel, and clients resolve
+/// them through the `resolve` in-request channel. This enables late binding between producers
+/// and consumers of request/reply APIs within large pipelines: a client only needs to be
+/// connected to the directory, not to every service it uses.
+#[actor(ServiceDirectoryInboundMessage, ServiceDirectoryInRequestMessage)]
+type ServiceDirectory = Actor<
+    NullProp,
+    ServiceDirectoryInbound,
+    ServiceDirectoryInRequest,
+    ServiceDirectoryState,
+    NullOutbound,
+    NullOutRequests,
+>;
@@ -0,0 +1,237 @@
+use crate::prelude::*;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+type OnMessageFn<I, O, State> = dyn Fn(I, &mut State, &OutboundChannel<O>) + Send + Sync;
+
+/// Configuration properties of the stateful actor, i.e. its message handler.
+pub struct StatefulProp<I, O, State> {
+    on_message: Arc<OnMessageFn<I, O, State>>,
+}
+
+impl<I, O, State> Clone for StatefulProp<I, O, State> {
+    fn clone(&self) -> Self {
+        Self {
+            on_message: self.on_message.clone(),
+        }
+    }
+}
+
+impl<I, O, State> Debug for StatefulProp<I, O, State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatefulProp").finish_non_exhaustive()
+    }
+}
+
+impl<I, O, State> StatefulProp<I, O, State> {
+    /// Message handler, which is called with each inbound message, the state of the actor and
+    /// its outbound channel.
+    pub fn from_fn(
+        on_message: impl Fn(I, &mut State, &OutboundChannel<O>) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            on_message: Arc::new(on_message),
+        }
+    }
+}
+
+/// Inbound message of the stateful actor.
+///
+/// As for the [Map](crate::actors::Map) actor, the inbound hub is not generated by the
+/// [actor_inputs] macro, since the message is generic over more than one type.
+pub struct StatefulInboundMessage<I, O, State> {
+    value: I,
+    phantom: PhantomData<fn() -> (O, State)>,
+}
+
+impl<I: Clone, O, State> Clone for StatefulInboundMessage<I, O, State> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<I: Debug, O, State> Debug for StatefulInboundMessage<I, O, State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Value").field(&self.value).finish()
+    }
+}
+
+impl<I, O, State> IsInboundMessage for StatefulInboundMessage<I, O, State>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    type Prop = StatefulProp<I, O, State>;
+    type State = State;
+    type OutboundHub = StatefulOutbound<O>;
+    type OutRequestHub = NullOutRequests;
+
+    fn inbound_channel(&self) -> String {
+        "Value".to_owned()
+    }
+}
+
+impl<I, O, State> HasOnMessage for StatefulInboundMessage<I, O, State>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    fn on_message(
+        self,
+        prop: &Self::Prop,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        (prop.on_message)(self.value, state, &outbound.value);
+    }
+}
+
+impl<I, O, State> IsInboundMessageNew<I> for StatefulInboundMessage<I, O, State>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    fn new(_inbound_name: String, value: I) -> Self {
+        Self {
+            value,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Inbound hub of the stateful actor.
+pub struct StatefulInbound<I, O, State>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    /// Messages to be handled.
+    pub value: InboundChannel<I, StatefulInboundMessage<I, O, State>>,
+}
+
+impl<I, O, State>
+    IsInboundHub<
+        StatefulProp<I, O, State>,
+        State,
+        StatefulOutbound<O>,
+        NullOutRequests,
+        StatefulInboundMessage<I, O, State>,
+        NullInRequestMessage,
+    > for StatefulInbound<I, O, State>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    fn from_builder(
+        builder: &mut ActorBuilder<
+            StatefulProp<I, O, State>,
+            State,
+            StatefulOutbound<O>,
+            NullOutRequests,
+            StatefulInboundMessage<I, O, State>,
+            NullInRequestMessage,
+        >,
+        actor_name: &str,
+    ) -> Self {
+        let value = InboundChannel::new(
+            builder.context,
+            actor_name,
+            &builder.sender,
+            "Value".to_owned(),
+        );
+        builder
+            .forward
+            .insert(value.name.clone(), Box::new(value.clone()));
+        Self { value }
+    }
+}
+
+/// Outbound hub of the stateful actor.
+#[actor_outputs]
+pub struct StatefulOutbound<O: 'static + Clone + Send + Sync + std::fmt::Debug> {
+    /// Messages sent by the message handler.
+    pub value: OutboundChannel<O>,
+}
+
+/// Stateful actor, whose message handler is a closure, with a single inbound and outbound
+/// channel:
+///
+/// ``` ignore
+/// let mut running_sum = Stateful::<f64, f64, f64>::from_fn(
+///     context,
+///     0.0,
+///     |value, sum, outbound| {
+///         *sum += value;
+///         outbound.send(*sum);
+///     },
+/// );
+/// sensor.outbound.value.connect(context, &mut running_sum.inbound.value);
+/// ```
+///
+/// It is meant for prototyping and generic library code. Actors with several channels, requests
+/// or a custom runner are defined using the [actor], [actor_inputs] and [actor_outputs] macros
+/// instead. See [Map](crate::actors::Map) and [Filter](crate::actors::Filter) for stateless
+/// transformations.
+pub type Stateful<I, O, State> = Actor<
+    StatefulProp<I, O, State>,
+    StatefulInbound<I, O, State>,
+    NullInRequests,
+    State,
+    StatefulOutbound<O>,
+    NullOutRequests,
+>;
+
+impl<I, O, State> Stateful<I, O, State>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    /// Creates a new stateful actor, which calls `on_message` with each inbound message.
+    pub fn from_fn(
+        context: &mut Hollywood,
+        initial_state: State,
+        on_message: impl Fn(I, &mut State, &OutboundChannel<O>) + Send + Sync + 'static,
+    ) -> Self {
+        Stateful::from_prop_and_state(context, StatefulProp::from_fn(on_message), initial_state)
+    }
+}
+
+impl<I, O, State>
+    HasFromPropState<
+        StatefulProp<I, O, State>,
+        StatefulInbound<I, O, State>,
+        NullInRequests,
+        State,
+        StatefulOutbound<O>,
+        StatefulInboundMessage<I, O, State>,
+        NullInRequestMessage,
+        NullOutRequests,
+        DefaultRunner<
+            StatefulProp<I, O, State>,
+            StatefulInbound<I, O, State>,
+            NullInRequests,
+            State,
+            StatefulOutbound<O>,
+            NullOutRequests,
+        >,
+    > for Stateful<I, O, State>
+where
+    I: Clone + Debug + Send + Sync + 'static,
+    O: Clone + Debug + Send + Sync + 'static,
+    State: Send + Sync + 'static,
+{
+    fn name_hint(_prop: &StatefulProp<I, O, State>) -> String {
+        "Stateful".to_owned()
+    }
+}
@@ -0,0 +1,423 @@
+use crate::prelude::*;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// How the throttle actor handles messages which arrive faster than its rate limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThrottlePolicy {
+    /// Excess messages are dropped.
+    #[default]
+    Drop,
+    /// Excess messages are queued and forwarded as soon as the rate limit allows. If the queue is
+    /// full, the oldest queued message is dropped, so that the queue holds the most recent
+    /// messages.
+    Queue {
+        /// Maximal number of queued messages.
+        capacity: usize,
+    },
+}
+
+/// Configuration properties for the throttle actor.
+#[derive(Clone, Debug)]
+pub struct ThrottleProp {
+    /// Minimal time in seconds between two forwarded messages. It must be finite and not
+    /// negative, which is checked by the constructors of the [Throttle] actor.
+    pub min_interval: f64,
+    /// Policy for messages which arrive faster than the rate limit.
+    pub policy: ThrottlePolicy,
+}
+
+impl ThrottleProp {
+    /// Properties which limit the rate to `max_rate` messages per second.
+    ///
+    /// Panics if `max_rate` is not positive.
+    pub fn from_max_rate(max_rate: f64, policy: ThrottlePolicy) -> Self {
+        assert!(
+            max_rate > 0.0,
+            "oh no, the max rate of a throttle must be positive, got {}",
+            max_rate
+        );
+        Self {
+            min_interval: 1.0 / max_rate,
+            policy,
+        }
+    }
+
+    fn assert_valid(&self) {
+        assert!(
+            self.min_interval >= 0.0 && self.min_interval.is_finite(),
+            "oh no, the min interval of a throttle must be finite and not negative, got {}",
+            self.min_interval
+        );
+    }
+
+    fn min_interval_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.min_interval)
+    }
+}
+
+impl Default for ThrottleProp {
+    fn default() -> Self {
+        Self {
+            min_interval: 0.1,
+            policy: ThrottlePolicy::default(),
+        }
+    }
+}
+
+/// State of the throttle actor.
+#[derive(Clone, Debug)]
+pub struct ThrottleState<T> {
+    queue: VecDeque<T>,
+    /// Earliest time at which the next message may be forwarded.
+    next_release: Option<Duration>,
+    /// Current time of the pipeline clock, see [HasClockTimer::set_now()].
+    now: Duration,
+    num_forwarded: u64,
+    num_dropped: u64,
+}
+
+impl<T> Default for ThrottleState<T> {
+    fn default() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            next_release: None,
+            now: Duration::ZERO,
+            num_forwarded: 0,
+            num_dropped: 0,
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> ThrottleState<T> {
+    /// Number of forwarded messages.
+    pub fn num_forwarded(&self) -> u64 {
+        self.num_forwarded
+    }
+
+    /// Number of dropped messages.
+    pub fn num_dropped(&self) -> u64 {
+        self.num_dropped
+    }
+
+    /// Number of queued messages.
+    pub fn queue_len(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn forward(&mut self, prop: &ThrottleProp, outbound: &ThrottleOutbound<T>, value: T) {
+        outbound.value.send(value);
+        self.num_forwarded += 1;
+        self.next_release = Some(self.now + prop.min_interval_duration());
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasClockTimer<ThrottleProp, ThrottleOutbound<T>> for ThrottleState<T>
+{
+    fn set_now(&mut self, now: Duration) {
+        self.now = now;
+    }
+
+    /// Deadline of the next queued message, if any.
+    fn next_deadline(&self, _prop: &ThrottleProp) -> Option<Duration> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        Some(self.next_release.unwrap_or_default())
+    }
+
+    /// Forwards the oldest queued message, if the rate limit allows.
+    fn on_deadline(&mut self, prop: &ThrottleProp, outbound: &ThrottleOutbound<T>) {
+        if self.next_release.is_some_and(|t| self.now < t) {
+            return;
+        }
+        if let Some(value) = self.queue.pop_front() {
+            self.forward(prop, outbound, value);
+        }
+    }
+}
+
+/// Inbound message for the throttle actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    ThrottleInbound<T>,
+    {
+        ThrottleProp,
+        ThrottleState<T>,
+        ThrottleOutbound<T>,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum ThrottleInboundMessage<T: Default + Debug + Clone + Sync + Send + 'static> {
+    /// Message to be forwarded.
+    Value(T),
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> HasOnMessage
+    for ThrottleInboundMessage<T>
+{
+    fn on_message(
+        self,
+        prop: &ThrottleProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            ThrottleInboundMessage::Value(value) => {
+                // Queued messages go first, to preserve the order.
+                let ready = state.next_release.is_none_or(|t| state.now >= t);
+                if ready && state.queue.is_empty() {
+                    state.forward(prop, outbound, value);
+                    return;
+                }
+                match prop.policy {
+                    ThrottlePolicy::Drop => state.num_dropped += 1,
+                    ThrottlePolicy::Queue { capacity } => {
+                        if capacity == 0 {
+                            state.num_dropped += 1;
+                            return;
+                        }
+                        if state.queue.len() >= capacity {
+                            state.queue.pop_front();
+                            state.num_dropped += 1;
+                        }
+                        state.queue.push_back(value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> IsInboundMessageNew<T>
+    for ThrottleInboundMessage<T>
+{
+    fn new(_inbound_name: String, msg: T) -> Self {
+        ThrottleInboundMessage::Value(msg)
+    }
+}
+
+/// Outbound hub of the throttle actor.
+#[actor_outputs]
+pub struct ThrottleOutbound<T: 'static + Default + Clone + Send + Sync + std::fmt::Debug> {
+    /// Forwarded messages, at most one per [ThrottleProp::min_interval].
+    pub value: OutboundChannel<T>,
+}
+
+/// Throttle actor, which limits the rate of messages from a fast upstream actor to a slow
+/// downstream actor, e.g. from a sensor driver to a visualizer:
+///
+/// ``` ignore
+/// let mut throttle = Throttle::<Image>::new_with_max_rate(context, 10.0, ThrottlePolicy::Drop);
+/// camera.outbound.image.connect(context, &mut throttle.inbound.value);
+/// throttle.outbound.value.connect(context, &mut viewer.inbound.image);
+/// ```
+///
+/// Messages are forwarded immediately, as long as they are at least [ThrottleProp::min_interval]
+/// apart. Excess messages are handled according to the [ThrottlePolicy]. Queued messages are
+/// discarded when the pipeline stops.
+pub type Throttle<T> = GenericActor<
+    ThrottleProp,
+    ThrottleInbound<T>,
+    NullInRequests,
+    ThrottleState<T>,
+    ThrottleOutbound<T>,
+    NullOutRequests,
+    ThrottleRunner<T>,
+>;
+
+/// The runner of the throttle actor.
+pub type ThrottleRunner<T> = ClockRunner<
+    ThrottleProp,
+    ThrottleInbound<T>,
+    NullInRequests,
+    ThrottleState<T>,
+    ThrottleOutbound<T>,
+    NullOutRequests,
+>;
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> Throttle<T> {
+    /// Create a new throttle actor, which forwards at most `max_rate` messages per second.
+    pub fn new_with_max_rate(
+        context: &mut Hollywood,
+        max_rate: f64,
+        policy: ThrottlePolicy,
+    ) -> Throttle<T> {
+        Throttle::from_prop_and_state(
+            context,
+            ThrottleProp::from_max_rate(max_rate, policy),
+            ThrottleState::default(),
+        )
+    }
+
+    /// Create a new throttle actor, which forwards messages at least `min_interval` seconds
+    /// apart.
+    pub fn new_with_min_interval(
+        context: &mut Hollywood,
+        min_interval: f64,
+        policy: ThrottlePolicy,
+    ) -> Throttle<T> {
+        let prop = ThrottleProp {
+            min_interval,
+            policy,
+        };
+        prop.assert_valid();
+        Throttle::from_prop_and_state(context, prop, ThrottleState::default())
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasFromPropState<
+        ThrottleProp,
+        ThrottleInbound<T>,
+        NullInRequests,
+        ThrottleState<T>,
+        ThrottleOutbound<T>,
+        ThrottleInboundMessage<T>,
+        NullInRequestMessage,
+        NullOutRequests,
+        ThrottleRunner<T>,
+    > for Throttle<T>
+{
+    fn name_hint(_prop: &ThrottleProp) -> String {
+        "Throttle".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::throttle::ThrottleProp;
+    use crate::actors::Throttle;
+    use crate::actors::ThrottlePolicy;
+    use crate::core::clock::advance_settled;
+    use crate::core::clock::settle;
+    use crate::prelude::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Throttle with a min interval of one second, which runs on a virtual clock.
+    struct Fixture {
+        clock: Arc<SimClock>,
+        value: InboundInjector<i32>,
+        forwarded: OutboundSubscriber<i32>,
+        running: RunningPipeline,
+    }
+
+    impl Fixture {
+        fn new(policy: ThrottlePolicy) -> Self {
+            let clock = Arc::new(SimClock::new());
+            let mut handles = None;
+            let pipeline = Hollywood::configure(&mut |context| {
+                context.set_clock(clock.clone());
+                let mut throttle = Throttle::<i32>::new_with_min_interval(context, 1.0, policy);
+                handles = Some((
+                    throttle.inbound.value.injector(),
+                    throttle.outbound.value.subscriber(context),
+                ));
+            });
+            let (value, forwarded) = handles.unwrap();
+            Self {
+                clock,
+                value,
+                forwarded,
+                running: pipeline.spawn(),
+            }
+        }
+
+        async fn send(&self, value: i32) {
+            self.value.send(value).unwrap();
+            settle().await;
+        }
+
+        async fn advance(&self, duration: Duration) {
+            advance_settled(&self.clock, duration).await;
+        }
+
+        fn forwarded(&mut self) -> Vec<i32> {
+            std::iter::from_fn(|| self.forwarded.try_recv()).collect()
+        }
+
+        async fn stop(self) {
+            self.running.cancel();
+            self.running.join().await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_policy_drops_excess_messages() {
+        let mut throttle = Fixture::new(ThrottlePolicy::Drop);
+        settle().await;
+
+        throttle.send(1).await;
+        throttle.send(2).await;
+        throttle.advance(Duration::from_secs_f64(0.5)).await;
+        throttle.send(3).await;
+        assert_eq!(throttle.forwarded(), vec![1]);
+
+        throttle.advance(Duration::from_secs_f64(0.5)).await;
+        throttle.send(4).await;
+        throttle.advance(Duration::from_secs(5)).await;
+        assert_eq!(throttle.forwarded(), vec![4]);
+        throttle.stop().await;
+    }
+
+    #[tokio::test]
+    async fn queue_policy_releases_in_order() {
+        let mut throttle = Fixture::new(ThrottlePolicy::Queue { capacity: 3 });
+        settle().await;
+
+        for i in 1..=3 {
+            throttle.send(i).await;
+        }
+        assert_eq!(throttle.forwarded(), vec![1]);
+
+        throttle.advance(Duration::from_secs(1)).await;
+        assert_eq!(throttle.forwarded(), vec![2]);
+        // A message which arrives while messages are queued goes after them, even if the rate
+        // limit would allow forwarding it right away.
+        throttle.advance(Duration::from_secs_f64(1.5)).await;
+        throttle.send(4).await;
+        assert_eq!(throttle.forwarded(), vec![3]);
+
+        throttle.advance(Duration::from_secs(5)).await;
+        assert_eq!(throttle.forwarded(), vec![4]);
+        throttle.stop().await;
+    }
+
+    #[tokio::test]
+    async fn queue_policy_drops_oldest_on_overflow() {
+        let mut throttle = Fixture::new(ThrottlePolicy::Queue { capacity: 2 });
+        settle().await;
+
+        for i in 1..=5 {
+            throttle.send(i).await;
+        }
+        throttle.advance(Duration::from_secs(5)).await;
+        // 1 is forwarded right away, and 2 and 3 are pushed out of the queue by 4 and 5.
+        assert_eq!(throttle.forwarded(), vec![1, 4, 5]);
+        throttle.stop().await;
+    }
+    #[test]
+    #[should_panic(expected = "max rate of a throttle must be positive")]
+    fn non_positive_max_rate_is_rejected() {
+        ThrottleProp::from_max_rate(0.0, ThrottlePolicy::Drop);
+    }
+
+    #[test]
+    fn invalid_min_interval_is_rejected() {
+        for min_interval in [-1.0, f64::NAN, f64::INFINITY] {
+            let created = std::panic::catch_unwind(|| {
+                let mut context = Hollywood::new();
+                Throttle::<i32>::new_with_min_interval(
+                    &mut context,
+                    min_interval,
+                    ThrottlePolicy::Drop,
+                );
+            });
+            assert!(created.is_err(), "min interval {} accepted", min_interval);
+        }
+    }
+}
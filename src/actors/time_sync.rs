@@ -0,0 +1,256 @@
+use crate::prelude::*;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// How the time sync actor maps the timestamps of a device to the pipeline clock.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeSyncMode {
+    /// The timestamp is replaced by the time the message was received. This is robust, but
+    /// includes the transport latency and its jitter.
+    Restamp,
+    /// The offset between the device clock and the pipeline clock is estimated, and added to the
+    /// timestamp of the device. Hence, the timestamps keep the precision of the device clock.
+    #[default]
+    Align,
+}
+
+/// Configuration properties for the time sync actor.
+#[derive(Clone, Debug)]
+pub struct TimeSyncProp {
+    /// How timestamps are mapped to the pipeline clock.
+    pub mode: TimeSyncMode,
+    /// Number of recent messages the clock offset is estimated from, see [TimeSyncMode::Align].
+    /// A smaller window follows drifting clocks faster, a larger window is less affected by
+    /// latency spikes.
+    pub window: usize,
+}
+
+impl Default for TimeSyncProp {
+    fn default() -> Self {
+        Self {
+            mode: TimeSyncMode::Align,
+            window: 100,
+        }
+    }
+}
+
+/// State of the time sync actor.
+#[derive(Clone, Debug, Default)]
+pub struct TimeSyncState {
+    /// Differences between the receive time and the device timestamp of recent messages.
+    differences: VecDeque<f64>,
+    /// Current time of the pipeline clock, see [HasClockTimer::set_now()].
+    now: Duration,
+    num_received: u64,
+}
+
+impl TimeSyncState {
+    /// Number of received messages.
+    pub fn num_received(&self) -> u64 {
+        self.num_received
+    }
+
+    /// Estimated offset in seconds to be added to the device timestamps, if any message was
+    /// received.
+    ///
+    /// Since the transport latency only delays messages, the smallest difference between the
+    /// receive time and the device timestamp within the window is the best estimate.
+    pub fn offset(&self) -> Option<f64> {
+        self.differences.iter().copied().reduce(f64::min)
+    }
+
+    /// Maps the device timestamp to the pipeline clock.
+    fn sync(&mut self, prop: &TimeSyncProp, time: &Timestamp) -> Timestamp {
+        self.num_received += 1;
+        let now = self.now.as_secs_f64();
+        match prop.mode {
+            TimeSyncMode::Restamp => Timestamp::monotonic(now),
+            TimeSyncMode::Align => {
+                self.differences.push_back(now - time.seconds);
+                while self.differences.len() > prop.window.max(1) {
+                    self.differences.pop_front();
+                }
+                Timestamp::monotonic(time.seconds + self.offset().unwrap_or_default())
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasClockTimer<TimeSyncProp, TimeSyncOutbound<T>> for TimeSyncState
+{
+    fn set_now(&mut self, now: Duration) {
+        self.now = now;
+    }
+}
+
+/// Inbound message for the time sync actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    TimeSyncInbound<T>,
+    {
+        TimeSyncProp,
+        TimeSyncState,
+        TimeSyncOutbound<T>,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum TimeSyncInboundMessage<T: Default + Debug + Clone + Sync + Send + 'static> {
+    /// Message stamped by the device clock.
+    Value(Stamped<T>),
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> HasOnMessage
+    for TimeSyncInboundMessage<T>
+{
+    fn on_message(
+        self,
+        prop: &TimeSyncProp,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            TimeSyncInboundMessage::Value(mut value) => {
+                value.time = state.sync(prop, &value.time);
+                outbound.value.send(value);
+            }
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> IsInboundMessageNew<Stamped<T>>
+    for TimeSyncInboundMessage<T>
+{
+    fn new(_inbound_name: String, msg: Stamped<T>) -> Self {
+        TimeSyncInboundMessage::Value(msg)
+    }
+}
+
+/// Outbound hub of the time sync actor.
+#[actor_outputs]
+pub struct TimeSyncOutbound<T: 'static + Default + Clone + Send + Sync + std::fmt::Debug> {
+    /// Messages stamped by the pipeline clock.
+    pub value: OutboundChannel<Stamped<T>>,
+}
+
+/// Time sync actor, which maps the timestamps of a device with its own, skewed clock to the
+/// clock of the pipeline, e.g. so that the messages of several devices can be fused:
+///
+/// ``` ignore
+/// let mut lidar_sync = TimeSync::<Scan>::new_with_mode(context, TimeSyncMode::Align);
+/// lidar.outbound.scan.connect(context, &mut lidar_sync.inbound.value);
+/// lidar_sync.outbound.value.connect(context, &mut fusion.inbound.scan);
+/// ```
+///
+/// The output timestamps are monotonic timestamps in the [Timestamp::PIPELINE_DOMAIN], i.e.
+/// comparable to the timestamps of the pipeline clock, see [Hollywood::set_clock()]. The value
+/// and sequence number of each message are kept.
+pub type TimeSync<T> = GenericActor<
+    TimeSyncProp,
+    TimeSyncInbound<T>,
+    NullInRequests,
+    TimeSyncState,
+    TimeSyncOutbound<T>,
+    NullOutRequests,
+    TimeSyncRunner<T>,
+>;
+
+/// The runner of the time sync actor.
+pub type TimeSyncRunner<T> = ClockRunner<
+    TimeSyncProp,
+    TimeSyncInbound<T>,
+    NullInRequests,
+    TimeSyncState,
+    TimeSyncOutbound<T>,
+    NullOutRequests,
+>;
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static> TimeSync<T> {
+    /// Create a new time sync actor with the given mode, and the default window.
+    pub fn new_with_mode(context: &mut Hollywood, mode: TimeSyncMode) -> TimeSync<T> {
+        TimeSync::from_prop_and_state(
+            context,
+            TimeSyncProp {
+                mode,
+                ..Default::default()
+            },
+            TimeSyncState::default(),
+        )
+    }
+}
+
+impl<T: Default + Debug + Clone + Sync + Send + 'static>
+    HasFromPropState<
+        TimeSyncProp,
+        TimeSyncInbound<T>,
+        NullInRequests,
+        TimeSyncState,
+        TimeSyncOutbound<T>,
+        TimeSyncInboundMessage<T>,
+        NullInRequestMessage,
+        NullOutRequests,
+        TimeSyncRunner<T>,
+    > for TimeSync<T>
+{
+    fn name_hint(_prop: &TimeSyncProp) -> String {
+        "TimeSync".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::time_sync::TimeSyncProp;
+    use crate::actors::time_sync::TimeSyncState;
+    use crate::actors::TimeSyncMode;
+    use crate::prelude::*;
+    use std::time::Duration;
+
+    /// Maps the device timestamps, received at the given pipeline times, to the pipeline clock.
+    fn sync(state: &mut TimeSyncState, prop: &TimeSyncProp, messages: &[(f64, f64)]) -> Vec<f64> {
+        messages
+            .iter()
+            .map(|(received, device_time)| {
+                state.now = Duration::from_secs_f64(*received);
+                state
+                    .sync(prop, &Timestamp::monotonic(*device_time))
+                    .seconds
+            })
+            .collect()
+    }
+
+    #[test]
+    fn restamp_uses_receive_time() {
+        let prop = TimeSyncProp {
+            mode: TimeSyncMode::Restamp,
+            window: 10,
+        };
+        let mut state = TimeSyncState::default();
+        assert_eq!(
+            sync(&mut state, &prop, &[(1.0, 100.0), (2.5, 101.0)]),
+            vec![1.0, 2.5]
+        );
+        assert_eq!(state.num_received(), 2);
+        assert_eq!(state.offset(), None);
+    }
+
+    #[test]
+    fn align_keeps_device_precision_using_min_offset_within_window() {
+        let prop = TimeSyncProp {
+            mode: TimeSyncMode::Align,
+            window: 2,
+        };
+        let mut state = TimeSyncState::default();
+        // The device clock is 100s ahead, and the second message has a latency spike of 0.5s.
+        assert_eq!(
+            sync(&mut state, &prop, &[(1.0, 101.0), (2.5, 102.0)]),
+            vec![1.0, 2.0]
+        );
+        assert_eq!(state.offset(), Some(-100.0));
+
+        // Once the least delayed message left the window, the offset follows the later ones.
+        assert_eq!(sync(&mut state, &prop, &[(3.5, 103.0)]), vec![3.5]);
+        assert_eq!(state.offset(), Some(-99.5));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::actors::BackgroundActor;
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use async_trait::async_trait;
+use futures_util::stream::SelectAll;
+use futures_util::Stream;
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::warn;
+
+/// MQTT transport.
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "mqtt")]
+pub use mqtt::MqttProp;
+#[cfg(feature = "mqtt")]
+pub use mqtt::MqttTransport;
+
+/// Message transport of a broker, such as MQTT or Zenoh, with topic-based publish and subscribe.
+///
+/// The transport only deals with raw payloads. The serialization of the messages is done by the
+/// [TransportBridge].
+#[async_trait]
+pub trait IsMessageTransport: Send + Sync + 'static {
+    /// Publishes the payload to the topic.
+    ///
+    /// This must not block. If the transport is not connected, the payload may be dropped.
+    fn publish(&self, topic: &str, payload: Vec<u8>);
+
+    /// Subscribes to the topic. The payloads received on the topic are sent to the returned
+    /// receiver while the transport is running.
+    fn subscribe(&self, topic: &str) -> tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>;
+
+    /// Drives the transport, i.e. its network event loop, while the pipeline is running.
+    ///
+    /// Shall return as soon as the `kill` watch channel is set to true.
+    async fn run(&self, kill: tokio::sync::watch::Receiver<bool>);
+}
+
+type TransportSubscriptions = SelectAll<Pin<Box<dyn Stream<Item = ()> + Send>>>;
+
+struct TransportConnection<T, Tr> {
+    topic: String,
+    transport: Arc<Tr>,
+    phantom: std::marker::PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + Send + Sync, Tr: IsMessageTransport> IsGenericConnection<T>
+    for TransportConnection<T, Tr>
+{
+    fn send_impl(&self, msg: T) {
+        match serde_json::to_vec(&msg) {
+            Ok(payload) => self.transport.publish(&self.topic, payload),
+            Err(err) => warn!(
+                "TransportBridge: failed to encode message of {}: {}",
+                self.topic, err
+            ),
+        }
+    }
+}
+
+/// Bridge which mirrors channels to the topics of a broker, through a [IsMessageTransport].
+///
+/// Messages are serialized as JSON. Outbound channels are mirrored to broker topics, and inbound
+/// channels are fed from broker topics:
+///
+/// ``` ignore
+/// let transport = MqttTransport::new(MqttProp {
+///     host: "broker.local".to_owned(),
+///     ..Default::default()
+/// });
+/// let mut bridge = TransportBridge::create(context, transport);
+/// bridge.mirror(context, &mut localizer.outbound.pose, "robot/pose");
+/// bridge.feed(&planner.inbound.goal, "robot/goal");
+/// ```
+///
+/// The transport is driven by a [BackgroundActor] while the pipeline is running. Messages which
+/// fail to decode are logged and dropped.
+pub struct TransportBridge<Tr: IsMessageTransport> {
+    /// The background actor which drives the transport.
+    pub actor: BackgroundActor,
+    transport: Arc<Tr>,
+    subscriptions: Arc<tokio::sync::Mutex<TransportSubscriptions>>,
+}
+
+impl<Tr: IsMessageTransport> TransportBridge<Tr> {
+    /// Creates the background actor which drives the given transport.
+    pub fn create(context: &mut Hollywood, transport: Tr) -> Self {
+        let transport = Arc::new(transport);
+        let subscriptions = Arc::new(tokio::sync::Mutex::new(TransportSubscriptions::new()));
+        let actor = BackgroundActor::from_named_task(context, "TransportBridge", {
+            let transport = transport.clone();
+            let subscriptions = subscriptions.clone();
+            move |mut kill| async move {
+                let mut subscriptions = subscriptions.lock_owned().await;
+                let run = transport.run(kill.clone());
+                tokio::pin!(run);
+                loop {
+                    tokio::select! {
+                        _ = &mut run => break,
+                        _ = subscriptions.next(), if !subscriptions.is_empty() => {}
+                    }
+                }
+                // Keep running until the pipeline stops, like any other actor.
+                let _ = kill.wait_for(|kill| *kill).await;
+            }
+        });
+        Self {
+            actor,
+            transport,
+            subscriptions,
+        }
+    }
+
+    /// The transport of the bridge.
+    pub fn transport(&self) -> &Tr {
+        &self.transport
+    }
+
+    /// Mirrors all messages of the outbound channel to the broker topic.
+    pub fn mirror<T>(
+        &mut self,
+        context: &mut Hollywood,
+        outbound: &mut OutboundChannel<T>,
+        topic: &str,
+    ) where
+        T: Serialize + Clone + Send + Sync + std::fmt::Debug + 'static,
+    {
+        let connection = context.decorate_connection(
+            Connection {
+                from_actor: outbound.actor_name.clone(),
+                from: outbound.name.clone(),
+                to_actor: self.actor.actor_name.clone(),
+                to: topic.to_owned(),
+                type_name: std::any::type_name::<T>(),
+            },
+            Arc::new(TransportConnection {
+                topic: topic.to_owned(),
+                transport: self.transport.clone(),
+                phantom: std::marker::PhantomData,
+            }),
+        );
+        outbound.connection_register.push(connection);
+    }
+
+    /// Feeds the messages of the broker topic into the inbound channel.
+    pub fn feed<T, M>(&mut self, inbound: &InboundChannel<T, M>, topic: &str)
+    where
+        T: DeserializeOwned + Send + Sync + std::fmt::Debug + 'static,
+        M: IsInboundMessageNew<T>,
+    {
+        let receiver = self.transport.subscribe(topic);
+        let sender = inbound.sender.clone();
+        let name = inbound.name.clone();
+        let topic = topic.to_owned();
+        let forward = UnboundedReceiverStream::new(receiver).map(move |payload| {
+            match serde_json::from_slice::<T>(&payload) {
+                Ok(msg) => {
+                    // The actor might have stopped, which is not an error.
+                    let _ = sender.send(M::new(name.clone(), msg));
+                }
+                Err(err) => warn!(
+                    "TransportBridge: failed to decode message of {}: {}",
+                    topic, err
+                ),
+            }
+        });
+        self.subscriptions
+            .try_lock()
+            .expect("subscriptions are not in use during configuration")
+            .push(Box::pin(forward));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::actors::transport::IsMessageTransport;
+use async_trait::async_trait;
+use rumqttc::AsyncClient;
+use rumqttc::Event;
+use rumqttc::EventLoop;
+use rumqttc::MqttOptions;
+use rumqttc::Packet;
+use rumqttc::QoS;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::info;
+use tracing::warn;
+
+/// Connection properties of the [MqttTransport].
+#[derive(Clone, Debug)]
+pub struct MqttProp {
+    /// Host name of the broker.
+    pub host: String,
+    /// Port of the broker.
+    pub port: u16,
+    /// Client id, which must be unique per broker.
+    pub client_id: String,
+    /// Keep-alive interval of the connection.
+    pub keep_alive: std::time::Duration,
+    /// Quality of service of published messages and subscriptions.
+    pub qos: QoS,
+    /// Delay before reconnecting after the connection failed.
+    pub reconnect_delay: std::time::Duration,
+}
+
+impl Default for MqttProp {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_owned(),
+            port: 1883,
+            client_id: "hollywood".to_owned(),
+            keep_alive: std::time::Duration::from_secs(5),
+            qos: QoS::AtMostOnce,
+            reconnect_delay: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+/// Transport over an MQTT broker.
+///
+/// The connection to the broker is established when the pipeline starts, and re-established if
+/// it is lost. Subscriptions are renewed on each connection. Topics are matched exactly, i.e.
+/// wildcard subscriptions are not supported.
+pub struct MqttTransport {
+    prop: MqttProp,
+    client: AsyncClient,
+    event_loop: tokio::sync::Mutex<EventLoop>,
+    subscribers: Mutex<HashMap<String, Vec<tokio::sync::mpsc::UnboundedSender<Vec<u8>>>>>,
+}
+
+impl MqttTransport {
+    /// Creates the transport from the given connection properties.
+    pub fn new(prop: MqttProp) -> Self {
+        let mut options = MqttOptions::new(prop.client_id.clone(), prop.host.clone(), prop.port);
+        options.set_keep_alive(prop.keep_alive);
+        let (client, event_loop) = AsyncClient::new(options, 64);
+        Self {
+            prop,
+            client,
+            event_loop: tokio::sync::Mutex::new(event_loop),
+            subscribers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Connection properties of the transport.
+    pub fn prop(&self) -> &MqttProp {
+        &self.prop
+    }
+}
+
+#[async_trait]
+impl IsMessageTransport for MqttTransport {
+    fn publish(&self, topic: &str, payload: Vec<u8>) {
+        if let Err(err) = self
+            .client
+            .try_publish(topic, self.prop.qos, false, payload)
+        {
+            warn!("MqttTransport: failed to publish to {}: {}", topic, err);
+        }
+    }
+
+    fn subscribe(&self, topic: &str) -> tokio::sync::mpsc::UnboundedReceiver<Vec<u8>> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        self.subscribers
+            .lock()
+            .unwrap()
+            .entry(topic.to_owned())
+            .or_default()
+            .push(sender);
+        receiver
+    }
+
+    async fn run(&self, mut kill: tokio::sync::watch::Receiver<bool>) {
+        let mut event_loop = self.event_loop.lock().await;
+        loop {
+            let event = tokio::select! {
+                _ = kill.changed() => return,
+                event = event_loop.poll() => event,
+            };
+            match event {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    info!("MqttTransport: connected to {}", self.prop.host);
+                    let topics: Vec<String> =
+                        self.subscribers.lock().unwrap().keys().cloned().collect();
+                    for topic in topics {
+                        if let Err(err) = self.client.try_subscribe(&topic, self.prop.qos) {
+                            warn!("MqttTransport: failed to subscribe to {}: {}", topic, err);
+                        }
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    if let Some(senders) = self.subscribers.lock().unwrap().get_mut(&publish.topic)
+                    {
+                        senders.retain(|sender| sender.send(publish.payload.to_vec()).is_ok());
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    warn!(
+                        "MqttTransport: connection to {} failed: {}",
+                        self.prop.host, err
+                    );
+                    tokio::select! {
+                        _ = kill.changed() => return,
+                        _ = tokio::time::sleep(self.prop.reconnect_delay) => {}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use crate::core::correlation::InboundSender;
+use crate::prelude::*;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+use tracing::warn;
+
+/// Configuration properties for the watchdog actor.
+#[derive(Clone, Debug)]
+pub struct WatchdogProp {
+    /// Maximal time in seconds a monitored channel may be silent.
+    pub timeout: f64,
+    /// If true, the pipeline is cancelled once a monitored channel went silent.
+    pub cancel_on_timeout: bool,
+}
+
+impl WatchdogProp {
+    fn timeout_duration(&self) -> Duration {
+        Duration::try_from_secs_f64(self.timeout).unwrap_or_default()
+    }
+}
+
+impl Default for WatchdogProp {
+    fn default() -> Self {
+        Self {
+            timeout: 1.0,
+            cancel_on_timeout: false,
+        }
+    }
+}
+
+/// Alert of the watchdog actor, which is sent once a monitored channel went silent.
+#[derive(Clone, Debug, Default)]
+pub struct WatchdogAlert {
+    /// Label of the silent channel, see [Watchdog::monitor()].
+    pub channel: String,
+    /// Time in seconds since the last message of the channel.
+    pub silence: f64,
+}
+
+/// State of the watchdog actor.
+#[derive(Clone, Debug, Default)]
+pub struct WatchdogState {
+    /// Time of the last message, by label of the monitored channel.
+    last_seen: BTreeMap<String, Duration>,
+    /// Labels of the channels which are currently silent.
+    silent: BTreeSet<String>,
+    /// Current time of the pipeline clock, see [HasClockTimer::set_now()].
+    now: Duration,
+    num_alerts: u64,
+}
+
+impl WatchdogState {
+    /// Number of alerts sent so far.
+    pub fn num_alerts(&self) -> u64 {
+        self.num_alerts
+    }
+
+    /// Labels of the channels which are currently silent.
+    pub fn silent_channels(&self) -> &BTreeSet<String> {
+        &self.silent
+    }
+}
+
+impl HasClockTimer<WatchdogProp, WatchdogOutbound> for WatchdogState {
+    fn set_now(&mut self, now: Duration) {
+        self.now = now;
+    }
+
+    /// Deadline of the channel which goes silent next, if any.
+    fn next_deadline(&self, prop: &WatchdogProp) -> Option<Duration> {
+        self.last_seen
+            .iter()
+            .filter(|(label, _)| !self.silent.contains(*label))
+            .map(|(_, last_seen)| *last_seen + prop.timeout_duration())
+            .min()
+    }
+
+    /// Sends an alert for each channel which went silent.
+    fn on_deadline(&mut self, prop: &WatchdogProp, outbound: &WatchdogOutbound) {
+        let mut any_silent = false;
+        for (label, last_seen) in self.last_seen.iter() {
+            if self.silent.contains(label) || self.now < *last_seen + prop.timeout_duration() {
+                continue;
+            }
+            let silence = (self.now - *last_seen).as_secs_f64();
+            warn!("Watchdog: {} is silent for {:.3}s", label, silence);
+            self.silent.insert(label.clone());
+            self.num_alerts += 1;
+            outbound.alert.send(WatchdogAlert {
+                channel: label.clone(),
+                silence,
+            });
+            any_silent = true;
+        }
+        if any_silent && prop.cancel_on_timeout {
+            outbound.cancel_request.send(CancelRequest);
+        }
+    }
+}
+
+/// Outbound hub of the watchdog actor.
+#[actor_outputs]
+pub struct WatchdogOutbound {
+    /// Alert, sent once a monitored channel went silent.
+    pub alert: OutboundChannel<WatchdogAlert>,
+    /// Cancel request, sent once a monitored channel went silent, if
+    /// [WatchdogProp::cancel_on_timeout] is set.
+    pub cancel_request: OutboundChannel<CancelRequest>,
+}
+
+/// Inbound message for the watchdog actor.
+#[derive(Clone, Debug)]
+#[actor_inputs(
+    WatchdogInbound,
+    {
+        WatchdogProp,
+        WatchdogState,
+        WatchdogOutbound,
+        NullOutRequests,
+        NullInRequestMessage
+    })]
+pub enum WatchdogInboundMessage {
+    /// Sign of life of the monitored channel with the given label.
+    Beat(String),
+}
+
+impl HasOnMessage for WatchdogInboundMessage {
+    fn on_message(
+        self,
+        _prop: &WatchdogProp,
+        state: &mut Self::State,
+        _outbound: &Self::OutboundHub,
+        _request: &Self::OutRequestHub,
+    ) {
+        match self {
+            WatchdogInboundMessage::Beat(label) => {
+                if state.silent.remove(&label) {
+                    info!("Watchdog: {} is alive again", label);
+                }
+                state.last_seen.insert(label, state.now);
+            }
+        }
+    }
+}
+
+impl IsInboundMessageNew<String> for WatchdogInboundMessage {
+    fn new(_inbound_name: String, msg: String) -> Self {
+        WatchdogInboundMessage::Beat(msg)
+    }
+}
+
+/// Connection which turns each message of a monitored channel into a beat.
+struct WatchdogConnection {
+    sender: InboundSender<WatchdogInboundMessage>,
+    label: String,
+}
+
+impl<T> IsGenericConnection<T> for WatchdogConnection {
+    fn send_impl(&self, _msg: T) {
+        // The watchdog might have stopped, which is not an error.
+        let _ = self
+            .sender
+            .send(WatchdogInboundMessage::Beat(self.label.clone()));
+    }
+}
+
+/// Watchdog actor, which monitors critical channels and raises an alert if any of them goes
+/// silent, e.g. a sensor driver which stopped publishing:
+///
+/// ``` ignore
+/// let mut watchdog = Watchdog::new_with_timeout(context, 0.5, true);
+/// watchdog.monitor(context, &mut lidar.outbound.scan);
+/// watchdog.monitor(context, &mut imu.outbound.reading);
+/// ```
+///
+/// A monitored channel is silent if it did not send any message for longer than
+/// [WatchdogProp::timeout]. The watchdog is armed when the pipeline starts, i.e. a channel which
+/// never sends a message is detected as well. For each silent channel, a [WatchdogAlert] is sent
+/// once, until the channel is alive again. If [WatchdogProp::cancel_on_timeout] is set, the
+/// pipeline is cancelled as well.
+pub type Watchdog = GenericActor<
+    WatchdogProp,
+    WatchdogInbound,
+    NullInRequests,
+    WatchdogState,
+    WatchdogOutbound,
+    NullOutRequests,
+    WatchdogRunner,
+>;
+
+/// The runner of the watchdog actor.
+pub type WatchdogRunner = ClockRunner<
+    WatchdogProp,
+    WatchdogInbound,
+    NullInRequests,
+    WatchdogState,
+    WatchdogOutbound,
+    NullOutRequests,
+>;
+
+impl Watchdog {
+    /// Create a new watchdog actor with the given timeout in seconds.
+    ///
+    /// If `cancel_on_timeout` is true, the cancel request channel is registered with the
+    /// pipeline, so that the pipeline is cancelled once a monitored channel went silent.
+    pub fn new_with_timeout(
+        context: &mut Hollywood,
+        timeout: f64,
+        cancel_on_timeout: bool,
+    ) -> Watchdog {
+        let mut watchdog = Watchdog::from_prop_and_state(
+            context,
+            WatchdogProp {
+                timeout,
+                cancel_on_timeout,
+            },
+            WatchdogState::default(),
+        );
+        if cancel_on_timeout {
+            context.register_cancel_requester(&mut watchdog.outbound.cancel_request);
+        }
+        watchdog
+    }
+
+    /// Monitors the outbound channel, labeled as "ACTOR.CHANNEL".
+    ///
+    /// Every message of the channel counts as sign of life. The messages are neither cloned nor
+    /// inspected.
+    pub fn monitor<T: Send + Sync + std::fmt::Debug + 'static>(
+        &mut self,
+        context: &mut Hollywood,
+        outbound: &mut OutboundChannel<T>,
+    ) {
+        let label = format!("{}.{}", outbound.actor_name, outbound.name);
+        // Arms the watchdog for this channel once the pipeline starts.
+        let _ = self
+            .inbound
+            .beat
+            .sender
+            .send(WatchdogInboundMessage::Beat(label.clone()));
+        let sender = self.inbound.beat.sender.clone();
+        context.connect_impl(
+            outbound,
+            &mut self.inbound.beat,
+            Arc::new(WatchdogConnection { sender, label }),
+        );
+    }
+}
+
+impl
+    HasFromPropState<
+        WatchdogProp,
+        WatchdogInbound,
+        NullInRequests,
+        WatchdogState,
+        WatchdogOutbound,
+        WatchdogInboundMessage,
+        NullInRequestMessage,
+        NullOutRequests,
+        WatchdogRunner,
+    > for Watchdog
+{
+    fn name_hint(_prop: &WatchdogProp) -> String {
+        "Watchdog".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::Map;
+    use crate::actors::Watchdog;
+    use crate::actors::WatchdogAlert;
+    use crate::core::clock::advance_settled;
+    use crate::core::clock::settle;
+    use crate::prelude::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Watchdog with a timeout of one second, which monitors the output of a map actor on a
+    /// virtual clock.
+    struct Fixture {
+        clock: Arc<SimClock>,
+        value: InboundInjector<i32>,
+        alerts: OutboundSubscriber<WatchdogAlert>,
+        cancel_requests: OutboundSubscriber<CancelRequest>,
+        running: RunningPipeline,
+    }
+
+    impl Fixture {
+        fn new(cancel_on_timeout: bool) -> Self {
+            let clock = Arc::new(SimClock::new());
+            let mut handles = None;
+            let pipeline = Hollywood::configure(&mut |context| {
+                context.set_clock(clock.clone());
+                let mut sensor = Map::<i32, i32>::from_fn(context, |x| x);
+                let mut watchdog = Watchdog::new_with_timeout(context, 1.0, cancel_on_timeout);
+                watchdog.monitor(context, &mut sensor.outbound.value);
+                handles = Some((
+                    sensor.inbound.value.injector(),
+                    watchdog.outbound.alert.subscriber(context),
+                    watchdog.outbound.cancel_request.subscriber(context),
+                ));
+            });
+            let (value, alerts, cancel_requests) = handles.unwrap();
+            Self {
+                clock,
+                value,
+                alerts,
+                cancel_requests,
+                running: pipeline.spawn(),
+            }
+        }
+
+        async fn send(&self, value: i32) {
+            self.value.send(value).unwrap();
+            settle().await;
+        }
+
+        async fn advance(&self, duration: Duration) {
+            advance_settled(&self.clock, duration).await;
+        }
+
+        fn alerts(&mut self) -> Vec<WatchdogAlert> {
+            std::iter::from_fn(|| self.alerts.try_recv()).collect()
+        }
+
+        fn num_cancel_requests(&mut self) -> usize {
+            std::iter::from_fn(|| self.cancel_requests.try_recv()).count()
+        }
+
+        async fn stop(self) {
+            self.running.cancel();
+            self.running.join().await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn armed_at_start() {
+        let mut watchdog = Fixture::new(false);
+        settle().await;
+
+        // The channel never sends a message, and is silent once the timeout passed after start.
+        watchdog.advance(Duration::from_secs_f64(0.9)).await;
+        assert!(watchdog.alerts().is_empty());
+        watchdog.advance(Duration::from_secs_f64(0.2)).await;
+        let alerts = watchdog.alerts();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].channel, "Map_0.value");
+        assert_eq!(watchdog.num_cancel_requests(), 0);
+        watchdog.stop().await;
+    }
+
+    #[tokio::test]
+    async fn alerts_once_until_alive_again() {
+        let mut watchdog = Fixture::new(false);
+        settle().await;
+
+        watchdog.advance(Duration::from_secs_f64(0.5)).await;
+        watchdog.send(1).await;
+        watchdog.advance(Duration::from_secs_f64(0.9)).await;
+        assert!(watchdog.alerts().is_empty());
+
+        watchdog.advance(Duration::from_secs(5)).await;
+        assert_eq!(watchdog.alerts().len(), 1);
+
+        // Once alive again, the channel is monitored anew.
+        watchdog.send(2).await;
+        watchdog.advance(Duration::from_secs_f64(0.9)).await;
+        assert!(watchdog.alerts().is_empty());
+        watchdog.advance(Duration::from_secs(5)).await;
+        assert_eq!(watchdog.alerts().len(), 1);
+        watchdog.stop().await;
+    }
+
+    #[tokio::test]
+    async fn cancel_on_timeout() {
+        let mut watchdog = Fixture::new(true);
+        settle().await;
+
+        watchdog.advance(Duration::from_secs_f64(1.5)).await;
+        assert_eq!(watchdog.alerts().len(), 1);
+        assert_eq!(watchdog.num_cancel_requests(), 1);
+        watchdog.stop().await;
+    }
+}
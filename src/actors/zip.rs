@@ -2,6 +2,7 @@ use crate::prelude::*;
 
 /// Type of the Nth inbound channel for the zip actor.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ZipPair<const N: usize, Key: PartialEq + Eq + PartialOrd + Ord, Value> {
     /// Key to associate message from different inbound channels with.
     pub key: Key,
@@ -9,6 +10,36 @@ pub struct ZipPair<const N: usize, Key: PartialEq + Eq + PartialOrd + Ord, Value
     pub value: Value,
 }
 
+impl<const N: usize, Key: PartialEq + Eq + PartialOrd + Ord, Value> ZipPair<N, Key, Value> {
+    /// Create a new pair from key and value.
+    pub fn new(key: Key, value: Value) -> Self {
+        Self { key, value }
+    }
+
+    /// Splits the pair into key and value.
+    pub fn into_parts(self) -> (Key, Value) {
+        (self.key, self.value)
+    }
+}
+
+impl<const N: usize, Key: PartialEq + Eq + PartialOrd + Ord, Value> From<(Key, Value)>
+    for ZipPair<N, Key, Value>
+{
+    fn from((key, value): (Key, Value)) -> Self {
+        Self { key, value }
+    }
+}
+
+impl<const N: usize, T> From<Stamped<T>> for ZipPair<N, u64, Stamped<T>> {
+    /// Zips stamped values by their sequence number.
+    fn from(stamped: Stamped<T>) -> Self {
+        Self {
+            key: stamped.seq,
+            value: stamped,
+        }
+    }
+}
+
 impl<const N: usize, Key: PartialEq + Eq + PartialOrd + Ord, T> PartialEq for ZipPair<N, Key, T> {
     fn eq(&self, other: &Self) -> bool {
         self.key == other.key
@@ -1,8 +1,29 @@
 /// The compute context.
 pub mod context;
 
+/// Dynamic graph mutation while the pipeline is running.
+pub mod dynamic;
+
+/// Heartbeat of the pipeline for external supervisors.
+pub mod heartbeat;
+
+/// Versions of the actors and message types of a pipeline.
+pub mod manifest;
+
 /// The compute graph of actors.
 pub mod pipeline;
 
+/// Progress reporting of batch and offline jobs.
+pub mod progress;
+
+/// Step-wise execution of pipelines, e.g. within an external simulation loop.
+pub mod stepping;
+
+/// Supervision and automatic restart of actors which panic.
+pub mod supervisor;
+
+/// Topic-based publish/subscribe wiring.
+pub mod topic;
+
 /// The graph topology.
 pub mod topology;
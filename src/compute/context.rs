@@ -1,9 +1,28 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
+use crate::compute::manifest::ManifestVersions;
+use crate::compute::progress::ProgressConnection;
+use crate::compute::stepping::Stepping;
+use crate::compute::topic::TopicRegistry;
+use crate::compute::topology::Connection;
+use crate::compute::topology::ExclusiveOutbound;
 use crate::compute::topology::Topology;
+use crate::core::backpressure::BoundedInbound;
+use crate::core::channel_tap::TapRegistry;
+#[cfg(feature = "serde")]
+use crate::core::checkpoint::Checkpoints;
+use crate::core::connection::middleware::MiddlewareConnection;
+use crate::core::correlation::CorrelationQueue;
+use crate::core::invariant::InvariantCounter;
+use crate::core::load_shedding::DecimatingConnection;
+use crate::core::load_shedding::LoadShedder;
 use crate::core::outbound::OutboundConnection;
+use crate::introspect::metrics::Metrics;
 use crate::prelude::*;
+use tracing::info;
 
 /// The context of the compute graph which is used to configure the network topology.
 ///
@@ -13,9 +32,42 @@ pub struct Hollywood {
     pub(crate) topology: Topology,
     pub(crate) cancel_request_sender_template: tokio::sync::mpsc::UnboundedSender<CancelRequest>,
     pub(crate) cancel_request_receiver: tokio::sync::mpsc::UnboundedReceiver<CancelRequest>,
+    pub(crate) run_options: ActorRunOptions,
+    pub(crate) topics: TopicRegistry,
+    pub(crate) versions: ManifestVersions,
+    pub(crate) connection_middlewares: Vec<Arc<dyn IsConnectionMiddleware>>,
+    pub(crate) actor_placements: HashMap<String, ActorPlacement>,
+    pub(crate) runtimes: HashMap<String, tokio::runtime::Handle>,
+    pub(crate) load_shedders: HashMap<String, Arc<LoadShedder>>,
+    pub(crate) bounded_inbounds: HashMap<String, Vec<Arc<BoundedInbound>>>,
+    pub(crate) pre_start_send_policy: PreStartSendPolicy,
+    pub(crate) channel_orderings: HashMap<String, Vec<ChannelOrdering>>,
+    pub(crate) invariants: Vec<InvariantCounter>,
+    pub(crate) mocked_actors: Vec<String>,
+    pub(crate) progress: Arc<tokio::sync::watch::Sender<Option<Progress>>>,
+    pub(crate) heartbeat: Option<Heartbeat>,
+    pub(crate) graceful_shutdown: Option<std::time::Duration>,
+    pub(crate) ctrl_c_handling: CtrlCHandling,
+    pub(crate) clock: Option<Arc<dyn IsClock>>,
+    pub(crate) stepping: Option<Stepping>,
+    pub(crate) chaos_commands: bool,
+    pub(crate) fused_actors: HashSet<String>,
+    pub(crate) exclusive_outbounds: Vec<ExclusiveOutbound>,
+    pub(crate) metrics: Option<Metrics>,
+    pub(crate) metrics_dump: Option<MetricsDump>,
+    pub(crate) message_tracing: bool,
+    pub(crate) correlation_queues: HashMap<String, HashMap<String, Arc<CorrelationQueue>>>,
+    pub(crate) taps: TapRegistry,
+    #[cfg(feature = "serde")]
+    pub(crate) checkpoints: Checkpoints,
 }
 
 impl Hollywood {
+    /// Environment variable which lists the actors to be mocked, see [Hollywood::actor_or_mock()].
+    ///
+    /// It is a comma-separated list of names, or `*` to mock all actors.
+    pub const MOCK_ENV_VAR: &'static str = "HOLLYWOOD_MOCK";
+
     /// Create a new Hollywood context.
     ///
     /// This is the main entry point to configure the compute graph. The network topology is defined
@@ -43,12 +95,54 @@ impl Hollywood {
     ///         .connect(context, &mut nudge_printer.inbound.printable);
     /// });
     /// ```
+    ///
+    /// Panics if the topology is misconfigured, see [Hollywood::try_configure()].
     pub fn configure(callback: &mut dyn FnMut(&mut Hollywood)) -> Pipeline {
         let mut context = Hollywood::new();
         callback(&mut context);
         Pipeline::from_context(context)
     }
 
+    /// Configures the pipeline like [Hollywood::configure()], but returns an error instead of
+    /// panicking if the topology is misconfigured.
+    ///
+    /// The error lists all misconfigured connections by actor and channel name, such as
+    /// self-connections, duplicate connections and cycles, see [TopologyIssue].
+    pub fn try_configure(
+        callback: &mut dyn FnMut(&mut Hollywood),
+    ) -> Result<Pipeline, TopologyError> {
+        let mut context = Hollywood::new();
+        callback(&mut context);
+        Pipeline::try_from_context(context)
+    }
+
+    /// Checks the topology configured so far for misconfigurations, see [TopologyIssue].
+    ///
+    /// The topology is validated when the pipeline is configured, see
+    /// [Hollywood::try_configure()]. This method allows to check it earlier, e.g. after each part
+    /// of a larger configuration.
+    pub fn validate(&self) -> Result<(), TopologyError> {
+        self.topology.validate(&self.exclusive_outbounds)
+    }
+
+    /// Configures the pipeline like [Hollywood::configure()], with the actor states restored from
+    /// the given snapshot, e.g. to resume a long-running pipeline after a restart.
+    ///
+    /// Only the states of checkpointed actors are restored, see
+    /// [ActorBuilder::enable_checkpoint()]. The states are matched by actor name, hence the
+    /// callback must create the actors in the same order as the pipeline the snapshot was taken
+    /// from. Checkpointed actors which are not part of the snapshot keep their initial state.
+    #[cfg(feature = "serde")]
+    pub fn configure_from_snapshot(
+        snapshot: PipelineSnapshot,
+        callback: &mut dyn FnMut(&mut Hollywood),
+    ) -> Pipeline {
+        let mut context = Hollywood::new();
+        context.checkpoints.restore_from = Some(snapshot);
+        callback(&mut context);
+        Pipeline::from_context(context)
+    }
+
     /// Returns a sender to send cancel requests to the pipeline.
     pub fn get_cancel_request_sender(
         &mut self,
@@ -63,13 +157,404 @@ impl Hollywood {
         outbound
             .connection_register
             .push(Arc::new(OutboundConnection {
-                sender: self.cancel_request_sender_template.clone(),
+                sender: self.cancel_request_sender_template.clone().into(),
                 inbound_channel: CancelRequest::CANCEL_REQUEST_INBOUND_CHANNEL.to_owned(),
                 phantom: PhantomData {},
             }));
     }
 
-    fn new() -> Self {
+    /// Registers an outbound channel which reports the progress of a batch or offline job.
+    ///
+    /// The progress is published under the [Progress::TOPIC] topic, and the latest progress is
+    /// accessible through [Pipeline::progress_watch()].
+    pub fn register_progress_reporter(&mut self, outbound: &mut OutboundChannel<Progress>) {
+        outbound
+            .connection_register
+            .push(Arc::new(ProgressConnection {
+                sender: self.progress.clone(),
+            }));
+        self.publish(Progress::TOPIC, outbound);
+    }
+
+    /// Publishes the messages of the outbound channel under the given topic name.
+    ///
+    /// All inbound channels which subscribe to the same topic (see [Hollywood::subscribe()])
+    /// receive the messages, regardless of whether they subscribe before or after this call. This
+    /// topic-based wiring is an alternative to [OutboundChannel::connect()] for systems which are
+    /// composed dynamically at runtime.
+    ///
+    /// Connection middlewares (see [Hollywood::add_connection_middleware()]) see each message on
+    /// its way from the outbound channel to the topic, and from the topic to each subscriber.
+    ///
+    /// Panics if the topic was already registered with a different message type.
+    pub fn publish<T: Clone + Send + Sync + std::fmt::Debug + 'static>(
+        &mut self,
+        topic: &str,
+        outbound: &mut OutboundChannel<T>,
+    ) {
+        // Rejected connections are reported when the pipeline is configured, see validate().
+        if !outbound.connection_register.admit_connection() {
+            return;
+        }
+        let (connection, subscribers) =
+            self.topics
+                .publish::<T>(topic, &outbound.actor_name, &outbound.name);
+        for (to_actor, to) in subscribers {
+            self.topology.connect_by_name(
+                &outbound.actor_name,
+                &outbound.name,
+                &to_actor,
+                &to,
+                std::any::type_name::<T>(),
+            );
+        }
+        // For middlewares, the topic takes the place of the subscribing actor and inbound channel.
+        let connection = self.decorate_connection(
+            Connection {
+                from_actor: outbound.actor_name.clone(),
+                from: outbound.name.clone(),
+                to_actor: "topic".to_owned(),
+                to: topic.to_owned(),
+                type_name: std::any::type_name::<T>(),
+            },
+            Arc::new(connection),
+        );
+        outbound.connection_register.push_admitted(connection);
+    }
+
+    /// Subscribes the inbound channel to the given topic name.
+    ///
+    /// See [Hollywood::publish()] for details.
+    ///
+    /// Panics if the topic was already registered with a different message type.
+    pub fn subscribe<
+        T: Clone + Send + Sync + std::fmt::Debug + 'static,
+        M: IsInboundMessageNew<T>,
+    >(
+        &mut self,
+        topic: &str,
+        inbound: &mut InboundChannel<T, M>,
+    ) {
+        // For middlewares, the topic takes the place of the publishing actor and outbound channel.
+        let connection = self.decorate_connection(
+            Connection {
+                from_actor: "topic".to_owned(),
+                from: topic.to_owned(),
+                to_actor: inbound.actor_name.clone(),
+                to: inbound.name.clone(),
+                type_name: std::any::type_name::<T>(),
+            },
+            Arc::new(OutboundConnection::<T, M> {
+                sender: inbound.sender.clone(),
+                inbound_channel: inbound.name.clone(),
+                phantom: PhantomData,
+            }),
+        );
+        let publishers =
+            self.topics
+                .subscribe::<T>(topic, &inbound.actor_name, &inbound.name, connection);
+        for (from_actor, from) in publishers {
+            self.topology.connect_by_name(
+                &from_actor,
+                &from,
+                &inbound.actor_name,
+                &inbound.name,
+                std::any::type_name::<T>(),
+            );
+        }
+    }
+
+    /// Registers a middleware which decorates every connection created afterwards.
+    ///
+    /// Middlewares are applied in the order of registration. Connections which were created before
+    /// this call are not affected. See [IsConnectionMiddleware] for details.
+    pub fn add_connection_middleware<Middleware: IsConnectionMiddleware>(
+        &mut self,
+        middleware: Middleware,
+    ) {
+        self.connection_middlewares.push(Arc::new(middleware));
+    }
+
+    /// Assigns the actor with the given name to an actor group.
+    ///
+    /// All actors of a group are executed cooperatively on a single dedicated OS thread. Grouping
+    /// small, chatty actors improves cache locality and reduces cross-thread wakeups. Actors
+    /// without a group are executed on the runtime of the pipeline.
+    ///
+    /// Panics if there is no actor with the given name.
+    pub fn assign_actor_group(&mut self, actor_name: &str, group: &str) {
+        self.set_actor_placement(actor_name, ActorPlacement::Group(group.to_owned()));
+    }
+
+    /// Sets where the actor with the given name is executed, see [ActorPlacement].
+    ///
+    /// Panics if there is no actor with the given name, or if the placement refers to a runtime
+    /// which is not registered, see [Hollywood::register_runtime()].
+    pub fn set_actor_placement(&mut self, actor_name: &str, placement: ActorPlacement) {
+        assert!(
+            self.topology
+                .unique_idx_name_pairs
+                .get_node_idx(actor_name)
+                .is_some(),
+            "oh no, there is no actor named {}",
+            actor_name
+        );
+        if let ActorPlacement::Runtime(runtime) = &placement {
+            assert!(
+                self.runtimes.contains_key(runtime),
+                "oh no, there is no runtime named {}",
+                runtime
+            );
+        }
+        self.actor_placements
+            .insert(actor_name.to_owned(), placement);
+    }
+
+    /// Registers a tokio runtime under the given name, so that actors can be placed on it using
+    /// [ActorPlacement::Runtime].
+    ///
+    /// The runtime must outlive the execution of the pipeline. Registering a runtime under an
+    /// existing name replaces it.
+    pub fn register_runtime(&mut self, name: &str, handle: tokio::runtime::Handle) {
+        self.runtimes.insert(name.to_owned(), handle);
+    }
+
+    /// Registers a load shedding policy for the actor with the given name.
+    ///
+    /// Upstream decimation only applies to connections into the actor which are created after
+    /// this call. See [LoadSheddingPolicy] for details.
+    ///
+    /// Panics if there is no actor with the given name.
+    pub fn set_load_shedding(&mut self, actor_name: &str, policy: LoadSheddingPolicy) {
+        assert!(
+            self.topology
+                .unique_idx_name_pairs
+                .get_node_idx(actor_name)
+                .is_some(),
+            "oh no, there is no actor named {}",
+            actor_name
+        );
+        self.load_shedders.insert(
+            actor_name.to_owned(),
+            Arc::new(LoadShedder::new(actor_name, policy)),
+        );
+    }
+
+    /// Sets the names of the actors to be mocked, overriding [Hollywood::MOCK_ENV_VAR].
+    ///
+    /// See [Hollywood::actor_or_mock()] for details.
+    pub fn set_mocked_actors(&mut self, names: &[&str]) {
+        self.mocked_actors = names.iter().map(|name| name.to_string()).collect();
+    }
+
+    /// Returns true if the actor with the given name shall be substituted by a mock.
+    pub fn is_mocked(&self, name: &str) -> bool {
+        self.mocked_actors
+            .iter()
+            .any(|mocked| mocked == "*" || mocked == name)
+    }
+
+    /// Creates either the actual actor or its mock replacement, depending on whether the given
+    /// name is mocked (see [Hollywood::set_mocked_actors()] and [Hollywood::MOCK_ENV_VAR]).
+    ///
+    /// Both constructors must return the same type, i.e. the mock has the same channel signature
+    /// as the actual actor, so the rest of the pipeline definition is unchanged. This makes it
+    /// possible to use the same pipeline for simulation and hardware runs, e.g. by swapping a
+    /// hardware driver for a simulated one:
+    ///
+    /// ``` ignore
+    /// let mut timer = context.actor_or_mock(
+    ///     "timer",
+    ///     |context| Periodic::new_with_period(context, 1.0),
+    ///     |context| Periodic::new_with_period(context, 0.01),
+    /// );
+    /// ```
+    pub fn actor_or_mock<A>(
+        &mut self,
+        name: &str,
+        actor: impl FnOnce(&mut Hollywood) -> A,
+        mock: impl FnOnce(&mut Hollywood) -> A,
+    ) -> A {
+        if self.is_mocked(name) {
+            info!("{}: substituted by mock", name);
+            return mock(self);
+        }
+        actor(self)
+    }
+
+    /// Adds a cross-channel ordering constraint between two inbound channels of the actor with
+    /// the given name. See [ChannelOrdering] for details.
+    ///
+    /// Panics if there is no actor with the given name.
+    pub fn set_channel_ordering(&mut self, actor_name: &str, ordering: ChannelOrdering) {
+        assert!(
+            self.topology
+                .unique_idx_name_pairs
+                .get_node_idx(actor_name)
+                .is_some(),
+            "oh no, there is no actor named {}",
+            actor_name
+        );
+        self.channel_orderings
+            .entry(actor_name.to_owned())
+            .or_default()
+            .push(ordering);
+    }
+
+    /// Sets the behavior of outbound channels if messages are sent before the pipeline is
+    /// started, e.g. an initial message sent during configuration.
+    ///
+    /// By default, such messages are buffered (bounded) and sent in order once the sending actor
+    /// starts. The policy applies to all outbound channels created after this call, and to all
+    /// out-request channels connected after this call. See [PreStartSendPolicy] for details.
+    pub fn set_pre_start_send_policy(&mut self, policy: PreStartSendPolicy) {
+        self.pre_start_send_policy = policy;
+    }
+
+    /// Sets the maximal number of messages an actor processes in a row before yielding back to
+    /// the tokio scheduler. Zero disables yielding.
+    ///
+    /// See [ActorRunOptions::yield_interval] for details.
+    pub fn set_yield_interval(&mut self, yield_interval: usize) {
+        self.run_options.yield_interval = yield_interval;
+    }
+
+    /// Sets the version of the message schema of the payload type `T`, as listed for each
+    /// connection of that type in the [PipelineManifest]. It should be bumped whenever the
+    /// message type changes, see [SemVer].
+    ///
+    /// By default, the version is 0.0.0.
+    pub fn set_message_version<T: 'static>(&mut self, version: SemVer) {
+        self.versions
+            .messages
+            .insert(std::any::type_name::<T>(), version);
+    }
+
+    /// Sets the heartbeat which notifies an external supervisor, such as the systemd watchdog,
+    /// while all actors are healthy. See [Heartbeat] for details.
+    pub fn set_heartbeat(&mut self, heartbeat: Heartbeat) {
+        self.heartbeat = Some(heartbeat);
+    }
+
+    /// Shuts the pipeline down gracefully upon cancel requests, e.g. on Ctrl-C.
+    ///
+    /// Instead of stopping all actors at once and discarding their pending messages, source
+    /// actors are stopped first. Each downstream actor is stopped once all its upstream actors
+    /// stopped, and processes its pending messages and requests before exiting, so that e.g. a
+    /// log writer does not lose in-flight data. Actors which did not stop within the given
+    /// timeout are stopped right away. See also [RunningPipeline::shutdown_gracefully()].
+    pub fn set_graceful_shutdown(&mut self, timeout: std::time::Duration) {
+        self.graceful_shutdown = Some(timeout);
+    }
+
+    /// Sets how the pipeline reacts to Ctrl-C.
+    ///
+    /// By default, a process-wide Ctrl-C handler is installed which cancels the pipeline, see
+    /// [CtrlCHandling::Cancel]. Host applications which handle signals themselves, or which run
+    /// several pipelines at once, shall use [CtrlCHandling::Disabled] or a
+    /// [CtrlCHandling::Custom] callback instead.
+    pub fn set_ctrl_c_handling(&mut self, handling: CtrlCHandling) {
+        self.ctrl_c_handling = handling;
+    }
+
+    /// Sets the clock which drives the periodic and other time-based actors, e.g. a [SimClock]
+    /// to step the pipeline through virtual time deterministically.
+    ///
+    /// By default, time-based actors run in real time. See [ClockRunner] and
+    /// [ActorRunOptions::clock()] for how custom actors access the clock.
+    pub fn set_clock(&mut self, clock: Arc<dyn IsClock>) {
+        self.clock = Some(clock);
+    }
+
+    /// Enables the step-wise execution of the pipeline, e.g. to embed it within the main loop
+    /// of an external simulation instead of running it freely:
+    ///
+    /// ``` ignore
+    /// let clock = Arc::new(SimClock::new());
+    /// let pipeline = Hollywood::configure(&mut |context| {
+    ///     context.enable_stepping(clock.clone());
+    ///     // ...
+    /// });
+    /// let running = pipeline.spawn();
+    /// while let Some(now) = running.step().await {
+    ///     // The actors processed all messages of the tick, e.g. read back their outputs here.
+    /// }
+    /// ```
+    ///
+    /// The given virtual clock drives the time-based actors, see [Hollywood::set_clock()], and
+    /// is advanced by [RunningPipeline::step()] only.
+    pub fn enable_stepping(&mut self, clock: Arc<SimClock>) {
+        self.clock = Some(clock.clone());
+        self.stepping = Some(Stepping::new(clock));
+    }
+
+    /// Enables chaos commands, which deliberately kill, stall or delay actors of the running
+    /// pipeline for resilience drills. See [ChaosController] for details.
+    ///
+    /// This is meant for integration environments and shall not be enabled in production.
+    pub fn enable_chaos_commands(&mut self) {
+        self.chaos_commands = true;
+    }
+
+    /// Lets the actor with the given name opt in to fused dispatch, i.e. upstream actors may use
+    /// [OutboundChannel::connect_fused()] to invoke its message handlers inline.
+    ///
+    /// A fused actor processes the messages of its other connections and its requests
+    /// sequentially, without batching, load shedding, channel orderings or chaos commands.
+    ///
+    /// Panics if there is no actor with the given name.
+    pub fn enable_fused_dispatch(&mut self, actor_name: &str) {
+        assert!(
+            self.topology
+                .unique_idx_name_pairs
+                .get_node_idx(actor_name)
+                .is_some(),
+            "oh no, there is no actor named {}",
+            actor_name
+        );
+        self.fused_actors.insert(actor_name.to_owned());
+    }
+
+    /// Enables the metrics of the pipeline, i.e. the number of messages sent per connection, the
+    /// number of messages handled per inbound and in-request channel, and the time spent in the
+    /// message handlers of each actor. See [Pipeline::metrics()] for the snapshot API.
+    ///
+    /// Only connections which are created after this call are counted, hence it is best called
+    /// first. The received messages and the handler latency are recorded by the default runner
+    /// only, i.e. neither for actors with a custom runner such as [Periodic](crate::actors::Periodic),
+    /// nor for actors in fused mode (see [Hollywood::enable_fused_dispatch()]).
+    pub fn enable_metrics(&mut self) {
+        if self.metrics.is_none() {
+            self.metrics = Some(Metrics::default());
+        }
+    }
+
+    /// Enables the metrics (see [Hollywood::enable_metrics()]) and dumps them periodically
+    /// while the pipeline is running, e.g. to a file scraped by Prometheus.
+    pub fn set_metrics_dump(&mut self, dump: MetricsDump) {
+        self.enable_metrics();
+        self.metrics_dump = Some(dump);
+    }
+
+    /// Enables correlation ids, which follow each message through the pipeline: a message sent
+    /// by a source actor starts a new correlation id, and the messages sent while handling it
+    /// carry its id on to the downstream actors. See [current_correlation_id()].
+    ///
+    /// Independent of this setting, the execution of each actor is wrapped in an `actor` span
+    /// with the actor name, and the handling of each message in a `message` span with the
+    /// inbound channel name, which also records the correlation id if enabled. Hence, the path of
+    /// a message through the pipeline can be followed with e.g. `tracing-subscriber` or an
+    /// OpenTelemetry exporter.
+    ///
+    /// Only the inbound channels which are created after this call are traced, hence it is best
+    /// called first. Correlation ids are recorded by the default runner only, i.e. messages sent
+    /// by actors with a custom runner such as [Periodic](crate::actors::Periodic) always start a
+    /// new correlation id.
+    pub fn enable_message_tracing(&mut self) {
+        self.message_tracing = true;
+    }
+
+    pub(crate) fn new() -> Self {
         let (cancel_request_sender_template, cancel_request_receiver) =
             tokio::sync::mpsc::unbounded_channel();
         Self {
@@ -77,13 +562,71 @@ impl Hollywood {
             topology: Topology::new(),
             cancel_request_sender_template,
             cancel_request_receiver,
+            run_options: ActorRunOptions::default(),
+            topics: TopicRegistry::new(),
+            versions: ManifestVersions::default(),
+            connection_middlewares: vec![],
+            actor_placements: HashMap::new(),
+            runtimes: HashMap::new(),
+            load_shedders: HashMap::new(),
+            bounded_inbounds: HashMap::new(),
+            pre_start_send_policy: PreStartSendPolicy::default(),
+            channel_orderings: HashMap::new(),
+            invariants: vec![],
+            mocked_actors: std::env::var(Self::MOCK_ENV_VAR)
+                .map(|names| Self::parse_mocked_actors(&names))
+                .unwrap_or_default(),
+            progress: Arc::new(tokio::sync::watch::channel(None).0),
+            heartbeat: None,
+            graceful_shutdown: None,
+            ctrl_c_handling: CtrlCHandling::default(),
+            clock: None,
+            stepping: None,
+            chaos_commands: false,
+            fused_actors: HashSet::new(),
+            exclusive_outbounds: vec![],
+            metrics: None,
+            metrics_dump: None,
+            message_tracing: false,
+            correlation_queues: HashMap::new(),
+            taps: TapRegistry::new(),
+            #[cfg(feature = "serde")]
+            checkpoints: Checkpoints::default(),
+        }
+    }
+
+    fn parse_mocked_actors(names: &str) -> Vec<String> {
+        names
+            .split(',')
+            .map(|name| name.trim().to_owned())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    pub(crate) fn add_correlation_queue(
+        &mut self,
+        actor_name: &str,
+        inbound_name: &str,
+    ) -> Option<Arc<CorrelationQueue>> {
+        if !self.message_tracing {
+            return None;
         }
+        let queue = Arc::new(CorrelationQueue::default());
+        self.correlation_queues
+            .entry(actor_name.to_owned())
+            .or_default()
+            .insert(inbound_name.to_owned(), queue.clone());
+        Some(queue)
     }
 
     pub(crate) fn add_new_unique_name(&mut self, name_hint: String) -> String {
         self.topology.add_new_unique_name(name_hint)
     }
 
+    pub(crate) fn add_unique_label(&mut self, label: &str) -> String {
+        self.topology.add_unique_label(label)
+    }
+
     pub(crate) fn assert_unique_inbound_name(
         &mut self,
         unique_inbound_name: String,
@@ -103,14 +646,71 @@ impl Hollywood {
     }
 
     pub(crate) fn connect_impl<
-        T0: Clone + std::fmt::Debug + Sync + Send + 'static,
-        T1: Clone + std::fmt::Debug + Sync + Send + 'static,
+        T0: std::fmt::Debug + Sync + Send + 'static,
+        T1: std::fmt::Debug + Sync + Send + 'static,
         M: IsInboundMessage,
     >(
         &mut self,
         outbound: &mut OutboundChannel<T0>,
         inbound: &mut InboundChannel<T1, M>,
+        connection: Arc<dyn IsGenericConnection<T0> + Send + Sync>,
     ) {
-        self.topology.connect(outbound, inbound);
+        // Rejected connections are reported when the pipeline is configured, see validate().
+        if !outbound.connection_register.admit_connection() {
+            return;
+        }
+        if let Some(connection_info) = self.topology.connect(outbound, inbound) {
+            outbound
+                .connection_register
+                .push_admitted(self.decorate_connection(connection_info, connection));
+        }
+    }
+
+    /// Makes the outbound channel exclusive, i.e. it admits a single connection only, see
+    /// [ConnectionEnum::set_exclusive()].
+    pub(crate) fn set_exclusive<T: Send + Sync + 'static>(
+        &mut self,
+        outbound: &mut OutboundChannel<T>,
+        fused: bool,
+    ) {
+        let fan_out = outbound.connection_register.set_exclusive();
+        if self
+            .exclusive_outbounds
+            .iter()
+            .any(|e| e.actor == outbound.actor_name && e.outbound == outbound.name)
+        {
+            return;
+        }
+        self.exclusive_outbounds.push(ExclusiveOutbound {
+            actor: outbound.actor_name.clone(),
+            outbound: outbound.name.clone(),
+            fused,
+            fan_out,
+        });
+    }
+
+    pub(crate) fn decorate_connection<T: std::fmt::Debug + Send + Sync + 'static>(
+        &self,
+        connection_info: Connection,
+        connection: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    ) -> Arc<dyn IsGenericConnection<T> + Send + Sync> {
+        let shedder = self.load_shedders.get(&connection_info.to_actor);
+        let connection = if self.connection_middlewares.is_empty() {
+            DecimatingConnection::decorate(&connection_info, connection, shedder)
+        } else {
+            DecimatingConnection::decorate(
+                &connection_info,
+                Arc::new(MiddlewareConnection {
+                    inner: connection,
+                    connection: connection_info.clone(),
+                    middlewares: self.connection_middlewares.clone(),
+                }),
+                shedder,
+            )
+        };
+        match &self.metrics {
+            Some(metrics) => metrics.decorate(&connection_info, connection),
+            None => connection,
+        }
     }
 }
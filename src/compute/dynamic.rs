@@ -0,0 +1,366 @@
+use crate::compute::pipeline::ActorFailure;
+use crate::compute::pipeline::PipelineError;
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+use tracing::info;
+use tracing::warn;
+
+/// Error returned by the methods of [DynamicTopology].
+#[derive(Debug)]
+pub enum DynamicTopologyError {
+    /// The pipeline is not running, hence the topology cannot be changed.
+    NotRunning,
+    /// There is already a subgraph with the given name.
+    DuplicateSubgraph(String),
+    /// There is no subgraph with the given name.
+    UnknownSubgraph(String),
+    /// One or more actors of the removed subgraph failed.
+    SubgraphFailed(Box<PipelineError>),
+    /// The subgraph does not match the expected manifest, see
+    /// [DynamicTopology::spawn_subgraph_checked()].
+    ManifestMismatch(ManifestError),
+}
+
+impl std::fmt::Display for DynamicTopologyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DynamicTopologyError::NotRunning => write!(f, "pipeline is not running"),
+            DynamicTopologyError::DuplicateSubgraph(name) => {
+                write!(f, "there is already a subgraph named {}", name)
+            }
+            DynamicTopologyError::UnknownSubgraph(name) => {
+                write!(f, "there is no subgraph named {}", name)
+            }
+            DynamicTopologyError::SubgraphFailed(err) => write!(f, "subgraph failed: {}", err),
+            DynamicTopologyError::ManifestMismatch(err) => {
+                write!(f, "subgraph does not match the manifest: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DynamicTopologyError {}
+
+#[derive(Default)]
+struct DynamicState {
+    running: bool,
+    subgraphs: HashMap<String, RunningPipeline>,
+}
+
+/// Handle to add and remove actors while the pipeline is running, e.g. for long-lived robotics
+/// processes where sensors come and go.
+///
+/// Actors are added in groups, called subgraphs. A subgraph is configured like a pipeline, using
+/// a fresh [Hollywood] context, and runs alongside the actors of the pipeline until it is removed
+/// or the pipeline stops. The handle is obtained through [Pipeline::dynamic_topology()] or
+/// [RunningPipeline::dynamic_topology()]:
+///
+/// ``` ignore
+/// let running = pipeline.spawn();
+/// let topology = running.dynamic_topology();
+/// topology.spawn_subgraph("lidar", &mut |context| {
+///     let lidar = Lidar::from_prop_and_state(context, prop, state);
+///     lidar.outbound.scan.connect_injector(context, &fusion_injector);
+/// })?;
+/// ```
+///
+/// Subgraphs are wired to the actors of the pipeline through handles which are created during
+/// configuration of the pipeline:
+///
+///   * [OutboundChannel::connect_injector()] connects an outbound channel of the subgraph to an
+///     inbound channel of the pipeline, using its [InboundInjector].
+///   * A [DynamicPort] of an outbound channel of the pipeline can be connected to and
+///     disconnected from the inbound channels of subgraphs at any time.
+///
+/// Subgraphs are not part of the topology of the pipeline, e.g. as shown by
+/// [Pipeline::print_flow_graph()]. If one of their actors fails, the failure is reported once the
+/// subgraph is removed, or by the pipeline once it stops.
+#[derive(Clone, Default)]
+pub struct DynamicTopology {
+    state: Arc<Mutex<DynamicState>>,
+}
+
+impl DynamicTopology {
+    /// Configures the subgraph with the given name using the callback, and starts it right away.
+    ///
+    /// Must be called from within the tokio runtime of the pipeline.
+    pub fn spawn_subgraph(
+        &self,
+        name: &str,
+        callback: &mut dyn FnMut(&mut Hollywood),
+    ) -> Result<(), DynamicTopologyError> {
+        self.spawn_subgraph_impl(name, None, callback)
+    }
+
+    /// Like [DynamicTopology::spawn_subgraph()], but the subgraph is only started if it matches
+    /// the expected manifest, see [PipelineManifest::check()].
+    ///
+    /// This guards against instantiating incompatible actor implementations, e.g. if the
+    /// subgraph is requested by a remote command which was issued against a different version of
+    /// the binary.
+    pub fn spawn_subgraph_checked(
+        &self,
+        name: &str,
+        expected: &PipelineManifest,
+        callback: &mut dyn FnMut(&mut Hollywood),
+    ) -> Result<(), DynamicTopologyError> {
+        self.spawn_subgraph_impl(name, Some(expected), callback)
+    }
+
+    fn spawn_subgraph_impl(
+        &self,
+        name: &str,
+        expected: Option<&PipelineManifest>,
+        callback: &mut dyn FnMut(&mut Hollywood),
+    ) -> Result<(), DynamicTopologyError> {
+        self.check_name(name)?;
+        // The lock is not held during configuration, so that the callback may use this handle.
+        let mut subgraph = Hollywood::configure(callback);
+        if let Some(expected) = expected {
+            subgraph
+                .manifest()
+                .check(expected)
+                .map_err(DynamicTopologyError::ManifestMismatch)?;
+        }
+        // Ctrl-C cancels the pipeline, which in turn stops all subgraphs.
+        subgraph.ctrl_c_handling = CtrlCHandling::Disabled;
+        let mut state = self.state.lock().unwrap();
+        Self::check_name_impl(&state, name)?;
+        info!("{}: subgraph started", name);
+        state.subgraphs.insert(name.to_owned(), subgraph.spawn());
+        Ok(())
+    }
+
+    /// Stops the subgraph with the given name and returns it once all its actors completed their
+    /// execution.
+    pub async fn remove_subgraph(
+        &self,
+        name: &str,
+    ) -> Result<Pipeline<Finished>, DynamicTopologyError> {
+        let subgraph = self
+            .state
+            .lock()
+            .unwrap()
+            .subgraphs
+            .remove(name)
+            .ok_or_else(|| DynamicTopologyError::UnknownSubgraph(name.to_owned()))?;
+        subgraph.cancel();
+        let result = subgraph.join().await;
+        info!("{}: subgraph removed", name);
+        result.map_err(|err| DynamicTopologyError::SubgraphFailed(Box::new(err)))
+    }
+
+    /// Names of the subgraphs which are currently running.
+    pub fn subgraph_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .state
+            .lock()
+            .unwrap()
+            .subgraphs
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn check_name(&self, name: &str) -> Result<(), DynamicTopologyError> {
+        Self::check_name_impl(&self.state.lock().unwrap(), name)
+    }
+
+    fn check_name_impl(state: &DynamicState, name: &str) -> Result<(), DynamicTopologyError> {
+        if !state.running {
+            return Err(DynamicTopologyError::NotRunning);
+        }
+        if state.subgraphs.contains_key(name) {
+            return Err(DynamicTopologyError::DuplicateSubgraph(name.to_owned()));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn start(&self) {
+        self.state.lock().unwrap().running = true;
+    }
+
+    /// Stops all subgraphs and returns the actors which failed.
+    pub(crate) async fn stop(&self) -> Vec<ActorFailure> {
+        let subgraphs = {
+            let mut state = self.state.lock().unwrap();
+            state.running = false;
+            std::mem::take(&mut state.subgraphs)
+        };
+        let mut failed_actors = vec![];
+        for (name, subgraph) in subgraphs {
+            subgraph.cancel();
+            if let Err(err) = subgraph.join().await {
+                warn!("{}: subgraph failed: {}", name, err);
+                failed_actors.extend(err.failed_actors);
+            }
+        }
+        failed_actors
+    }
+}
+
+/// Identifier of a connection of a [DynamicPort].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DynamicConnectionId(u64);
+
+type PortTargets<T> = Arc<RwLock<Vec<(DynamicConnectionId, InboundInjector<T>)>>>;
+
+/// Handle to connect an outbound channel to inbound channels while the pipeline is running, see
+/// [DynamicTopology].
+///
+/// A port is obtained during configuration using [OutboundChannel::dynamic_port()]. It can be
+/// cloned, and each message sent through the outbound channel is forwarded to all inbound
+/// channels which are currently connected to the port. Connections to actors which stopped, e.g.
+/// since their subgraph was removed, are dropped automatically.
+pub struct DynamicPort<T> {
+    actor_name: String,
+    outbound_name: String,
+    next_id: Arc<AtomicU64>,
+    targets: PortTargets<T>,
+}
+
+impl<T> Clone for DynamicPort<T> {
+    fn clone(&self) -> Self {
+        Self {
+            actor_name: self.actor_name.clone(),
+            outbound_name: self.outbound_name.clone(),
+            next_id: self.next_id.clone(),
+            targets: self.targets.clone(),
+        }
+    }
+}
+
+impl<T> DynamicPort<T> {
+    /// Name of the actor the messages are sent from.
+    pub fn actor_name(&self) -> &str {
+        &self.actor_name
+    }
+
+    /// Name of the outbound channel the messages are sent from.
+    pub fn outbound_name(&self) -> &str {
+        &self.outbound_name
+    }
+
+    /// Connects the port to the inbound channel of the given injector.
+    pub fn connect(&self, injector: &InboundInjector<T>) -> DynamicConnectionId {
+        let id = DynamicConnectionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        info!(
+            "{}: {} connected to {}.{}",
+            self.actor_name,
+            self.outbound_name,
+            injector.actor_name(),
+            injector.inbound_name()
+        );
+        self.targets.write().unwrap().push((id, injector.clone()));
+        id
+    }
+
+    /// Removes the connection with the given id. Returns false if there is no such connection.
+    pub fn disconnect(&self, id: DynamicConnectionId) -> bool {
+        let mut targets = self.targets.write().unwrap();
+        let len = targets.len();
+        targets.retain(|(target_id, _)| *target_id != id);
+        targets.len() != len
+    }
+
+    /// Number of inbound channels which are currently connected.
+    pub fn num_connections(&self) -> usize {
+        self.targets.read().unwrap().len()
+    }
+}
+
+struct DynamicPortConnection<T> {
+    targets: PortTargets<T>,
+}
+
+impl<T: Clone + Send + Sync> IsGenericConnection<T> for DynamicPortConnection<T> {
+    fn send_impl(&self, msg: T) {
+        let mut disconnected = vec![];
+        {
+            let targets = self.targets.read().unwrap();
+            if let Some(((last_id, last), others)) = targets.split_last() {
+                for (id, target) in others.iter() {
+                    if target.send(msg.clone()).is_err() {
+                        disconnected.push(*id);
+                    }
+                }
+                if last.send(msg).is_err() {
+                    disconnected.push(*last_id);
+                }
+            }
+        }
+        if !disconnected.is_empty() {
+            self.targets
+                .write()
+                .unwrap()
+                .retain(|(id, _)| !disconnected.contains(id));
+        }
+    }
+}
+
+struct InjectorConnection<T> {
+    injector: InboundInjector<T>,
+}
+
+impl<T: Send + Sync> IsGenericConnection<T> for InjectorConnection<T> {
+    fn send_impl(&self, msg: T) {
+        // The receiving actor might have stopped, which is not an error.
+        let _ = self.injector.send(msg);
+    }
+}
+
+impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
+    /// Returns a port to connect this channel to inbound channels while the pipeline is running,
+    /// see [DynamicTopology].
+    pub fn dynamic_port(&mut self, ctx: &mut Hollywood) -> DynamicPort<OutT> {
+        let targets = PortTargets::<OutT>::default();
+        let connection = ctx.decorate_connection(
+            Connection {
+                from_actor: self.actor_name.clone(),
+                from: self.name.clone(),
+                to_actor: "dynamic".to_owned(),
+                to: "port".to_owned(),
+                type_name: std::any::type_name::<OutT>(),
+            },
+            Arc::new(DynamicPortConnection {
+                targets: targets.clone(),
+            }),
+        );
+        self.connection_register.push(connection);
+        DynamicPort {
+            actor_name: self.actor_name.clone(),
+            outbound_name: self.name.clone(),
+            next_id: Arc::new(AtomicU64::new(0)),
+            targets,
+        }
+    }
+
+    /// Connects this channel to the inbound channel of the given injector, e.g. of an actor of
+    /// another pipeline.
+    ///
+    /// This is how a subgraph sends messages to the pipeline it is part of, see
+    /// [DynamicTopology].
+    pub fn connect_injector(&mut self, ctx: &mut Hollywood, injector: &InboundInjector<OutT>) {
+        let connection = ctx.decorate_connection(
+            Connection {
+                from_actor: self.actor_name.clone(),
+                from: self.name.clone(),
+                to_actor: injector.actor_name().to_owned(),
+                to: injector.inbound_name().to_owned(),
+                type_name: std::any::type_name::<OutT>(),
+            },
+            Arc::new(InjectorConnection {
+                injector: injector.clone(),
+            }),
+        );
+        self.connection_register.push(connection);
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+/// Target which is notified by the pipeline heartbeat, see [Heartbeat].
+#[derive(Clone)]
+pub enum HeartbeatTarget {
+    /// The systemd service manager, notified through the `sd_notify` protocol, i.e. by sending
+    /// `READY=1` once all actors are started and `WATCHDOG=1` on each heartbeat to the socket in
+    /// the `NOTIFY_SOCKET` environment variable.
+    SystemdWatchdog,
+    /// A user callback, e.g. to feed a hardware watchdog.
+    Callback(Arc<dyn Fn() + Send + Sync>),
+}
+
+impl std::fmt::Debug for HeartbeatTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeartbeatTarget::SystemdWatchdog => write!(f, "SystemdWatchdog"),
+            HeartbeatTarget::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
+
+/// Heartbeat of the pipeline for external supervisors, see [Hollywood::set_heartbeat()].
+///
+/// While the pipeline is running, the target is notified at the given interval as long as all
+/// actors are healthy, i.e. none of the actors panicked or finished prematurely. Once an actor
+/// failed, the heartbeat stops, so that the supervisor (e.g. systemd with `WatchdogSec=` set)
+/// restarts the process.
+///
+/// [Hollywood::set_heartbeat()]: crate::Hollywood::set_heartbeat()
+#[derive(Clone, Debug)]
+pub struct Heartbeat {
+    /// Interval between two notifications.
+    pub interval: Duration,
+    /// Target to be notified.
+    pub target: HeartbeatTarget,
+}
+
+impl Heartbeat {
+    /// Environment variable with the watchdog timeout in microseconds, set by systemd.
+    pub const WATCHDOG_USEC_ENV_VAR: &'static str = "WATCHDOG_USEC";
+    /// Environment variable with the path of the notification socket, set by systemd.
+    pub const NOTIFY_SOCKET_ENV_VAR: &'static str = "NOTIFY_SOCKET";
+
+    /// Creates a heartbeat which notifies the systemd watchdog at the given interval.
+    pub fn systemd_watchdog(interval: Duration) -> Self {
+        Self {
+            interval,
+            target: HeartbeatTarget::SystemdWatchdog,
+        }
+    }
+
+    /// Creates a heartbeat which notifies the systemd watchdog at half of the watchdog timeout
+    /// configured by systemd, or None if the watchdog is not enabled for this process.
+    pub fn systemd_watchdog_from_env() -> Option<Self> {
+        let usec: u64 = std::env::var(Self::WATCHDOG_USEC_ENV_VAR)
+            .ok()?
+            .parse()
+            .ok()?;
+        Some(Self::systemd_watchdog(Duration::from_micros(usec / 2)))
+    }
+
+    /// Creates a heartbeat which calls the given callback at the given interval.
+    pub fn from_callback(interval: Duration, callback: impl Fn() + Send + Sync + 'static) -> Self {
+        Self {
+            interval,
+            target: HeartbeatTarget::Callback(Arc::new(callback)),
+        }
+    }
+
+    pub(crate) fn notify_ready(&self) {
+        if let HeartbeatTarget::SystemdWatchdog = self.target {
+            sd_notify("READY=1");
+        }
+    }
+
+    pub(crate) fn notify(&self) {
+        match &self.target {
+            HeartbeatTarget::SystemdWatchdog => sd_notify("WATCHDOG=1"),
+            HeartbeatTarget::Callback(callback) => callback(),
+        }
+    }
+
+    /// Notifies the target at the configured interval while all actors are alive.
+    pub(crate) async fn run(self, liveness: ActorLiveness) {
+        self.notify_ready();
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if !liveness.all_alive() {
+                warn!("Heartbeat stopped, since not all actors are alive");
+                return;
+            }
+            self.notify();
+        }
+    }
+}
+
+/// Counts the actors which are still running, see [Heartbeat].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ActorLiveness {
+    num_started: Arc<AtomicUsize>,
+    num_alive: Arc<AtomicUsize>,
+}
+
+impl ActorLiveness {
+    /// Registers a started actor. The actor is considered alive until the guard is dropped, which
+    /// is also the case if the actor panics.
+    pub(crate) fn guard(&self) -> AliveGuard {
+        self.num_started.fetch_add(1, Ordering::SeqCst);
+        self.num_alive.fetch_add(1, Ordering::SeqCst);
+        AliveGuard {
+            num_alive: self.num_alive.clone(),
+        }
+    }
+
+    pub(crate) fn all_alive(&self) -> bool {
+        self.num_alive.load(Ordering::SeqCst) == self.num_started.load(Ordering::SeqCst)
+    }
+}
+
+/// Marks an actor as alive while it exists, see [ActorLiveness::guard()].
+pub(crate) struct AliveGuard {
+    num_alive: Arc<AtomicUsize>,
+}
+
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        self.num_alive.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(unix)]
+fn sd_notify(state: &str) {
+    use std::os::unix::net::UnixDatagram;
+
+    let Some(path) = std::env::var_os(Heartbeat::NOTIFY_SOCKET_ENV_VAR) else {
+        return;
+    };
+    let socket = match UnixDatagram::unbound() {
+        Ok(socket) => socket,
+        Err(err) => {
+            warn!("sd_notify: cannot create socket: {}", err);
+            return;
+        }
+    };
+    let path = path.to_string_lossy();
+    let result = match path.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(abstract_name) => {
+            use std::os::linux::net::SocketAddrExt;
+            std::os::unix::net::SocketAddr::from_abstract_name(abstract_name)
+                .and_then(|addr| socket.send_to_addr(state.as_bytes(), &addr))
+        }
+        _ => socket.send_to(state.as_bytes(), path.as_ref()),
+    };
+    if let Err(err) = result {
+        warn!("sd_notify: cannot notify {}: {}", path, err);
+    }
+}
+
+#[cfg(not(unix))]
+fn sd_notify(_state: &str) {
+    warn!("sd_notify: the systemd watchdog is only supported on unix");
+}
@@ -0,0 +1,439 @@
+use crate::compute::topology::Topology;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+/// Semantic version of an actor type or a message schema, see [PipelineManifest].
+///
+/// The major version is bumped for incompatible changes, and the minor version for compatible
+/// ones, e.g. a new optional field. The patch version is informational only.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SemVer {
+    /// major version, bumped for incompatible changes
+    pub major: u32,
+    /// minor version, bumped for backward compatible changes
+    pub minor: u32,
+    /// patch version, bumped for changes which do not affect compatibility
+    pub patch: u32,
+}
+
+impl SemVer {
+    /// Creates a new version.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Whether this version can be used where the expected version is required, i.e. whether
+    /// both have the same major version and this minor version is not older than the expected
+    /// one.
+    pub fn is_compatible_with(&self, expected: &SemVer) -> bool {
+        self.major == expected.major && self.minor >= expected.minor
+    }
+}
+
+impl std::fmt::Display for SemVer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Versions of the actor types and message schemas of a pipeline, collected by the context.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ManifestVersions {
+    /// version of the actor type, by actor name
+    pub(crate) actors: HashMap<String, SemVer>,
+    /// version of the message schema, by type name of the message
+    pub(crate) messages: HashMap<&'static str, SemVer>,
+}
+
+/// Versions of the actors and message types of a pipeline, to detect incompatible actor
+/// implementations across binaries, e.g. within a fleet of robots.
+///
+/// The manifest lists each actor by name, along with the [SemVer] of its actor type, and each
+/// connection along with the [SemVer] of its message schema. A manifest taken from the pipeline
+/// of one binary (see [Pipeline::manifest()](crate::Pipeline::manifest)) is compared with the
+/// expectations of another using [PipelineManifest::check()]:
+///
+/// ``` ignore
+/// // The expected manifest is e.g. shipped along with the fleet configuration.
+/// pipeline.manifest().check(&expected)?;
+/// ```
+///
+/// The version of an actor type is given by
+/// [HasFromPropState::version()](crate::HasFromPropState::version), and the version of a message
+/// schema is set using [Hollywood::set_message_version()](crate::Hollywood::set_message_version).
+/// Both default to 0.0.0. Type names are listed for information only, but not compared, since
+/// they are not stable across compiler versions. Actors which are not created through
+/// [HasFromPropState](crate::HasFromPropState), e.g.
+/// [BackgroundActor](crate::actors::BackgroundActor), are listed without version.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineManifest {
+    /// The actors, sorted by name.
+    pub actors: Vec<ActorManifest>,
+    /// The connections, sorted by their outbound and inbound channels.
+    pub connections: Vec<ConnectionManifest>,
+}
+
+/// Entry of an actor in the [PipelineManifest].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActorManifest {
+    /// unique name of the actor
+    pub name: String,
+    /// version of the actor type, if known
+    pub version: Option<SemVer>,
+}
+
+/// Entry of a connection in the [PipelineManifest], which includes request connections.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionManifest {
+    /// name of the actor that owns the outbound channel
+    pub from_actor: String,
+    /// name of the outbound channel
+    pub from: String,
+    /// name of the actor that owns the inbound channel
+    pub to_actor: String,
+    /// name of the inbound channel
+    pub to: String,
+    /// Rust type name of the channel payload, as given by [std::any::type_name()], for
+    /// information only
+    pub type_name: String,
+    /// version of the message schema of the channel payload
+    pub version: SemVer,
+}
+
+impl ConnectionManifest {
+    fn key(&self) -> (&str, &str, &str, &str) {
+        (&self.from_actor, &self.from, &self.to_actor, &self.to)
+    }
+}
+
+impl std::fmt::Display for ConnectionManifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{} -> {}.{}",
+            self.from_actor, self.from, self.to_actor, self.to
+        )
+    }
+}
+
+/// Incompatibility between two pipelines, see [ManifestError].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ManifestIssue {
+    /// An expected actor is missing.
+    MissingActor(String),
+    /// An actor is not expected.
+    UnexpectedActor(String),
+    /// The version of an actor type is not compatible, see [SemVer::is_compatible_with()].
+    ActorVersion {
+        /// name of the actor
+        actor: String,
+        /// the expected version
+        expected: SemVer,
+        /// the version found
+        found: SemVer,
+    },
+    /// An expected connection is missing.
+    MissingConnection(ConnectionManifest),
+    /// A connection is not expected.
+    UnexpectedConnection(ConnectionManifest),
+    /// The version of the message schema of a connection is not compatible, see
+    /// [SemVer::is_compatible_with()].
+    MessageVersion {
+        /// the expected connection
+        expected: ConnectionManifest,
+        /// version of the message schema found
+        found: SemVer,
+    },
+}
+
+impl std::fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestIssue::MissingActor(actor) => write!(f, "actor {} is missing", actor),
+            ManifestIssue::UnexpectedActor(actor) => write!(f, "actor {} is unexpected", actor),
+            ManifestIssue::ActorVersion {
+                actor,
+                expected,
+                found,
+            } => write!(
+                f,
+                "actor {}: expected version {} but found {}",
+                actor, expected, found
+            ),
+            ManifestIssue::MissingConnection(connection) => {
+                write!(f, "connection {} is missing", connection)
+            }
+            ManifestIssue::UnexpectedConnection(connection) => {
+                write!(f, "connection {} is unexpected", connection)
+            }
+            ManifestIssue::MessageVersion { expected, found } => write!(
+                f,
+                "connection {}: expected {} version {} but found {}",
+                expected, expected.type_name, expected.version, found
+            ),
+        }
+    }
+}
+
+/// Error returned if a pipeline does not match the expected [PipelineManifest].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestError {
+    /// The issues found.
+    pub issues: Vec<ManifestIssue>,
+}
+
+impl std::fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} manifest issue(s):", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl PipelineManifest {
+    pub(crate) fn new<'a>(
+        actor_names: impl Iterator<Item = &'a String>,
+        versions: &ManifestVersions,
+        topology: &Topology,
+    ) -> Self {
+        let mut actors: Vec<ActorManifest> = actor_names
+            .map(|name| ActorManifest {
+                name: name.clone(),
+                version: versions.actors.get(name).copied(),
+            })
+            .collect();
+        actors.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut connections: Vec<ConnectionManifest> = topology
+            .graph
+            .edge_weights()
+            .chain(topology.request_connections.iter())
+            .map(|connection| ConnectionManifest {
+                from_actor: connection.from_actor.clone(),
+                from: connection.from.clone(),
+                to_actor: connection.to_actor.clone(),
+                to: connection.to.clone(),
+                type_name: connection.type_name.to_owned(),
+                version: versions
+                    .messages
+                    .get(connection.type_name)
+                    .copied()
+                    .unwrap_or_default(),
+            })
+            .collect();
+        connections.sort_by(|a, b| a.key().cmp(&b.key()));
+        connections.dedup();
+        Self {
+            actors,
+            connections,
+        }
+    }
+
+    /// Checks whether this manifest matches the expected one, i.e. whether both list the same
+    /// actors and connections, and whether the versions found are compatible with the expected
+    /// ones, see [SemVer::is_compatible_with()].
+    ///
+    /// The version of an actor is only compared if it is known in both manifests.
+    pub fn check(&self, expected: &PipelineManifest) -> Result<(), ManifestError> {
+        let mut issues = vec![];
+
+        let found_actors: BTreeMap<&str, &ActorManifest> = self
+            .actors
+            .iter()
+            .map(|actor| (actor.name.as_str(), actor))
+            .collect();
+        for expected_actor in &expected.actors {
+            match found_actors.get(expected_actor.name.as_str()) {
+                None => issues.push(ManifestIssue::MissingActor(expected_actor.name.clone())),
+                Some(found_actor) => {
+                    if let (Some(expected_version), Some(found_version)) =
+                        (expected_actor.version, found_actor.version)
+                    {
+                        if !found_version.is_compatible_with(&expected_version) {
+                            issues.push(ManifestIssue::ActorVersion {
+                                actor: expected_actor.name.clone(),
+                                expected: expected_version,
+                                found: found_version,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        for actor in &self.actors {
+            if !expected.actors.iter().any(|a| a.name == actor.name) {
+                issues.push(ManifestIssue::UnexpectedActor(actor.name.clone()));
+            }
+        }
+
+        let found_connections: BTreeMap<_, &ConnectionManifest> = self
+            .connections
+            .iter()
+            .map(|connection| (connection.key(), connection))
+            .collect();
+        for expected_connection in &expected.connections {
+            match found_connections.get(&expected_connection.key()) {
+                None => issues.push(ManifestIssue::MissingConnection(
+                    expected_connection.clone(),
+                )),
+                Some(found)
+                    if !found
+                        .version
+                        .is_compatible_with(&expected_connection.version) =>
+                {
+                    issues.push(ManifestIssue::MessageVersion {
+                        expected: expected_connection.clone(),
+                        found: found.version,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for connection in &self.connections {
+            if !expected
+                .connections
+                .iter()
+                .any(|c| c.key() == connection.key())
+            {
+                issues.push(ManifestIssue::UnexpectedConnection(connection.clone()));
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ManifestError { issues })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::printer::PrinterProp;
+    use crate::actors::Nudge;
+    use crate::actors::Printer;
+    use crate::prelude::*;
+
+    fn manifest<T>(message_version: SemVer) -> PipelineManifest
+    where
+        T: Clone + Default + std::fmt::Debug + std::fmt::Display + Send + Sync + 'static,
+    {
+        Hollywood::configure(&mut |context| {
+            context.set_message_version::<T>(message_version);
+            let mut nudge = Nudge::<T>::new(context, T::default());
+            let mut printer = Printer::<T>::from_prop_and_state(
+                context,
+                PrinterProp {
+                    topic: "value".to_owned(),
+                },
+                NullState::default(),
+            );
+            nudge
+                .outbound
+                .nudge
+                .connect(context, &mut printer.inbound.printable);
+        })
+        .manifest()
+    }
+
+    #[test]
+    fn compatible_versions() {
+        let expected = SemVer::new(1, 2, 3);
+        assert!(SemVer::new(1, 2, 0).is_compatible_with(&expected));
+        assert!(SemVer::new(1, 3, 0).is_compatible_with(&expected));
+        assert!(!SemVer::new(1, 1, 9).is_compatible_with(&expected));
+        assert!(!SemVer::new(2, 2, 3).is_compatible_with(&expected));
+        assert!(!SemVer::new(0, 2, 3).is_compatible_with(&expected));
+    }
+
+    #[test]
+    fn same_pipeline_matches() {
+        let expected = manifest::<f64>(SemVer::new(1, 2, 0));
+        assert_eq!(expected.actors.len(), 2);
+        assert_eq!(expected.connections.len(), 1);
+        assert!(expected
+            .actors
+            .iter()
+            .all(|actor| actor.version == Some(SemVer::default())));
+        assert_eq!(expected.connections[0].version, SemVer::new(1, 2, 0));
+        manifest::<f64>(SemVer::new(1, 2, 0))
+            .check(&expected)
+            .unwrap();
+        // A newer minor version is compatible.
+        manifest::<f64>(SemVer::new(1, 3, 0))
+            .check(&expected)
+            .unwrap();
+    }
+
+    #[test]
+    fn type_names_are_not_compared() {
+        manifest::<f32>(SemVer::new(1, 2, 0))
+            .check(&manifest::<f64>(SemVer::new(1, 2, 0)))
+            .unwrap();
+    }
+
+    #[test]
+    fn message_version_mismatch() {
+        let expected = manifest::<f64>(SemVer::new(1, 2, 0));
+        for found in [SemVer::new(1, 1, 0), SemVer::new(2, 2, 0)] {
+            let err = manifest::<f64>(found).check(&expected).err().unwrap();
+            assert!(
+                matches!(
+                    err.issues.as_slice(),
+                    [ManifestIssue::MessageVersion { found: f, .. }] if *f == found
+                ),
+                "{}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn actor_version_mismatch() {
+        let expected = manifest::<f64>(SemVer::default());
+        let mut found = expected.clone();
+        found.actors[0].version = Some(SemVer::new(1, 0, 0));
+        let err = found.check(&expected).err().unwrap();
+        assert!(
+            matches!(err.issues.as_slice(), [ManifestIssue::ActorVersion { actor, .. }] if actor == "Nudge_0"),
+            "{}",
+            err
+        );
+        // Actors without version are not compared.
+        found.actors[0].version = None;
+        found.check(&expected).unwrap();
+    }
+
+    #[test]
+    fn missing_and_unexpected_actors() {
+        let expected = manifest::<f64>(SemVer::default());
+        let found = Hollywood::configure(&mut |context| {
+            let _ = Nudge::<f64>::new(context, 0.0);
+        })
+        .manifest();
+        let printer = expected.actors[1].name.clone();
+        let err = found.check(&expected).err().unwrap();
+        assert!(err
+            .issues
+            .contains(&ManifestIssue::MissingActor(printer.clone())));
+        assert!(matches!(
+            err.issues.last(),
+            Some(ManifestIssue::MissingConnection(_))
+        ));
+        let err = expected.check(&found).err().unwrap();
+        assert!(err
+            .issues
+            .contains(&ManifestIssue::UnexpectedActor(printer)));
+    }
+}
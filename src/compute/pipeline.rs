@@ -1,7 +1,35 @@
+use crate::compute::heartbeat::ActorLiveness;
+use crate::compute::heartbeat::AliveGuard;
+use crate::compute::manifest::ManifestVersions;
+use crate::compute::stepping::spawn_local_tracked;
+use crate::compute::stepping::spawn_tracked;
+use crate::compute::stepping::spawn_tracked_on;
+use crate::compute::stepping::Activity;
+use crate::compute::stepping::Stepping;
+use crate::compute::supervisor::panic_message;
 use crate::compute::topology::Topology;
+use crate::core::backpressure::BoundedInbound;
+use crate::core::backpressure::BoundedInbounds;
+use crate::core::channel_tap::find_tap;
+use crate::core::channel_tap::TapRegistry;
+#[cfg(feature = "serde")]
+use crate::core::checkpoint::Checkpoints;
+use crate::core::correlation::Correlations;
+use crate::core::error_policy::ErrorSink;
+use crate::core::invariant::InvariantCounter;
+use crate::core::load_shedding::LoadShedder;
+use crate::introspect::metrics::Metrics;
 use crate::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::marker::PhantomData;
 use std::mem::swap;
-use tracing::{info, warn};
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Once;
+use tracing::{info, info_span, warn, Instrument};
 
 /// A message to cancel the pipeline.
 #[derive(Clone, Debug)]
@@ -28,72 +56,814 @@ impl IsInboundMessageNew<CancelRequest> for CancelRequest {
     }
 }
 
+/// Selection of the actors to execute, see [Pipeline::run_partial()].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActorFilter {
+    /// Only the actors with the given names are executed.
+    Only(Vec<String>),
+    /// All actors but the ones with the given names are executed.
+    Except(Vec<String>),
+}
+
+impl ActorFilter {
+    /// Returns true if the actor with the given name is selected.
+    pub fn includes(&self, actor_name: &str) -> bool {
+        match self {
+            ActorFilter::Only(names) => names.iter().any(|name| name == actor_name),
+            ActorFilter::Except(names) => !names.iter().any(|name| name == actor_name),
+        }
+    }
+}
+
+/// Reason for an actor to fail during pipeline execution.
+#[derive(Debug, Clone)]
+pub enum ActorFailureReason {
+    /// The actor task panicked. Contains the panic message, if it is a string.
+    Panicked(Option<String>),
+    /// The actor task was cancelled by the runtime.
+    Cancelled,
+}
+
+/// An actor which failed during pipeline execution.
+#[derive(Debug, Clone)]
+pub struct ActorFailure {
+    /// Name of the failed actor.
+    pub actor_name: String,
+    /// Reason for the failure.
+    pub reason: ActorFailureReason,
+}
+
+impl ActorFailure {
+    fn from_join_error(actor_name: String, err: tokio::task::JoinError) -> Self {
+        let reason = if err.is_panic() {
+            ActorFailureReason::Panicked(panic_message(err.into_panic().as_ref()))
+        } else {
+            ActorFailureReason::Cancelled
+        };
+        Self { actor_name, reason }
+    }
+}
+
+impl std::fmt::Display for ActorFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.reason {
+            ActorFailureReason::Panicked(Some(msg)) => {
+                write!(f, "actor {} panicked: {}", self.actor_name, msg)
+            }
+            ActorFailureReason::Panicked(None) => write!(f, "actor {} panicked", self.actor_name),
+            ActorFailureReason::Cancelled => write!(f, "actor {} was cancelled", self.actor_name),
+        }
+    }
+}
+
+/// Error returned by [Pipeline::run()] if one or more actors failed.
+///
+/// The pipeline is returned as part of the error and contains all actors which completed
+/// successfully, so that their final state remains accessible.
+pub struct PipelineError {
+    /// Actors which failed during execution.
+    pub failed_actors: Vec<ActorFailure>,
+    /// The pipeline with all remaining actors.
+    pub pipeline: Box<Pipeline<Finished>>,
+}
+
+impl std::fmt::Debug for PipelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PipelineError")
+            .field("failed_actors", &self.failed_actors)
+            .finish()
+    }
+}
+
+impl std::fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} actor(s) failed:", self.failed_actors.len())?;
+        for failure in &self.failed_actors {
+            write!(f, " [{}]", failure)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+type ActorNode = Box<dyn IsActorNode + Send>;
+
+type GroupedActor = (
+    ActorNode,
+    tokio::sync::watch::Receiver<bool>,
+    tokio::sync::oneshot::Sender<ActorNode>,
+    tokio::sync::oneshot::Sender<Result<(), tokio::task::JoinError>>,
+    AliveGuard,
+);
+
+/// Handle to an actor which is executed by the pipeline.
+enum ActorHandle {
+    /// The actor runs as a task on the pipeline's runtime.
+    Task(tokio::task::JoinHandle<()>),
+    /// The actor runs as part of an actor group on a dedicated thread.
+    Grouped(tokio::sync::oneshot::Receiver<Result<(), tokio::task::JoinError>>),
+}
+
+impl ActorHandle {
+    /// Waits for the actor to complete. Must not be called again once it returned.
+    async fn join(&mut self, actor_name: String) -> Result<(), ActorFailure> {
+        let result = match self {
+            ActorHandle::Task(h) => h.await,
+            ActorHandle::Grouped(rx) => match rx.await {
+                Ok(result) => result,
+                Err(_) => {
+                    return Err(ActorFailure {
+                        actor_name,
+                        reason: ActorFailureReason::Cancelled,
+                    })
+                }
+            },
+        };
+        result.map_err(|err| ActorFailure::from_join_error(actor_name, err))
+    }
+}
+
+/// Runs all actors of a group on a single dedicated thread with its own current-thread runtime.
+///
+/// If `local` is true, the actors are spawned onto a [tokio::task::LocalSet] on that thread.
+fn spawn_actor_group(
+    group: String,
+    actors: Vec<GroupedActor>,
+    activity: Option<Arc<Activity>>,
+    local: bool,
+) {
+    info!("Actor group {} with {} actors started", group, actors.len());
+    std::thread::Builder::new()
+        .name(format!("hollywood-{}", group))
+        .spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Error building tokio runtime for actor group");
+            let run_group = async move {
+                let mut handles = vec![];
+                for (mut actor, kill_receiver, tx, join_tx, alive) in actors {
+                    let span = info_span!("actor", name = actor.name().as_str());
+                    let task = async move {
+                        let _alive = alive;
+                        actor.run(kill_receiver).await;
+                        if tx.send(actor).is_err() {}
+                    }
+                    .instrument(span);
+                    let h = if local {
+                        spawn_local_tracked(activity.clone(), task)
+                    } else {
+                        spawn_tracked(activity.clone(), task)
+                    };
+                    handles.push((h, join_tx));
+                }
+                for (h, join_tx) in handles {
+                    let _ = join_tx.send(h.await);
+                }
+            };
+            if local {
+                tokio::task::LocalSet::new().block_on(&runtime, run_group);
+            } else {
+                runtime.block_on(run_group);
+            }
+        })
+        .expect("Error spawning actor group thread");
+}
+
+/// Stops the actors wave by wave, i.e. each wave once the previous one stopped, see
+/// [Hollywood::set_graceful_shutdown()]. The actors which stopped are removed from the handles,
+/// and the ones which failed are returned.
+async fn stop_gracefully(
+    waves: Vec<Vec<String>>,
+    handles: &mut Vec<(String, ActorHandle, tokio::sync::watch::Sender<bool>)>,
+    timeout: std::time::Duration,
+) -> Vec<ActorFailure> {
+    info!("Pipeline shutting down gracefully ...");
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut failed_actors = vec![];
+    for wave in waves {
+        for (_, _, kill_sender) in handles.iter().filter(|(name, _, _)| wave.contains(name)) {
+            kill_sender.send_replace(true);
+        }
+        let mut i = 0;
+        while i < handles.len() {
+            if !wave.contains(&handles[i].0) {
+                i += 1;
+                continue;
+            }
+            let actor_name = handles[i].0.clone();
+            let joined = tokio::time::timeout_at(deadline, handles[i].1.join(actor_name)).await;
+            match joined {
+                Ok(result) => {
+                    if let Err(failure) = result {
+                        warn!("{}", failure);
+                        failed_actors.push(failure);
+                    }
+                    handles.remove(i);
+                }
+                Err(_) => {
+                    warn!(
+                        "Graceful shutdown timed out after {:?}, stopping remaining actors",
+                        timeout
+                    );
+                    return failed_actors;
+                }
+            }
+        }
+    }
+    failed_actors
+}
+
+/// Callback which is invoked on Ctrl-C, see [CtrlCHandling::Custom].
+pub type CtrlCCallback =
+    Arc<dyn Fn(&tokio::sync::mpsc::UnboundedSender<CancelRequest>) + Send + Sync>;
+
+/// How a pipeline reacts to Ctrl-C, see [Hollywood::set_ctrl_c_handling()].
+#[derive(Clone, Default)]
+pub enum CtrlCHandling {
+    /// A process-wide Ctrl-C handler is installed, which cancels the pipeline.
+    #[default]
+    Cancel,
+    /// A process-wide Ctrl-C handler is installed, which calls the given callback with the cancel
+    /// request sender of the pipeline instead, e.g. to ask for confirmation or to shut down a
+    /// host application first.
+    Custom(CtrlCCallback),
+    /// No Ctrl-C handler is installed, e.g. since the host application handles signals itself.
+    /// The pipeline is cancelled using [RunningPipeline::cancel()] or a sender obtained from
+    /// [Pipeline::get_cancel_request_sender()] instead.
+    Disabled,
+}
+
+impl std::fmt::Debug for CtrlCHandling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CtrlCHandling::Cancel => write!(f, "Cancel"),
+            CtrlCHandling::Custom(_) => write!(f, "Custom"),
+            CtrlCHandling::Disabled => write!(f, "Disabled"),
+        }
+    }
+}
+
+/// Target of the process-wide Ctrl-C handler, see [on_ctrl_c()].
+#[derive(Clone)]
+struct CtrlCTarget {
+    cancel_requester: tokio::sync::mpsc::UnboundedSender<CancelRequest>,
+    callback: Option<CtrlCCallback>,
+}
+
+/// Pipeline which was started last with a Ctrl-C handler, see [on_ctrl_c()].
+static CTRL_C_TARGET: Mutex<Option<CtrlCTarget>> = Mutex::new(None);
+static CTRL_C_HANDLER: Once = Once::new();
+
+/// Sets up the Ctrl-C handling of the pipeline with the given sender.
+///
+/// The process-wide handler can only be set once, hence it is shared by all pipelines which
+/// are run (in sequence) within the same process, and only the pipeline which was started last
+/// is notified. Pipelines with [CtrlCHandling::Disabled] neither install the handler nor become
+/// its target.
+fn on_ctrl_c(
+    handling: &CtrlCHandling,
+    cancel_requester: tokio::sync::mpsc::UnboundedSender<CancelRequest>,
+) {
+    let callback = match handling {
+        CtrlCHandling::Cancel => None,
+        CtrlCHandling::Custom(callback) => Some(callback.clone()),
+        CtrlCHandling::Disabled => return,
+    };
+    *CTRL_C_TARGET.lock().unwrap() = Some(CtrlCTarget {
+        cancel_requester,
+        callback,
+    });
+    CTRL_C_HANDLER.call_once(|| {
+        ctrlc::set_handler(|| {
+            // The lock is released before the callback is invoked.
+            let target = CTRL_C_TARGET.lock().unwrap().clone();
+            match target {
+                Some(CtrlCTarget {
+                    cancel_requester,
+                    callback: Some(callback),
+                }) => callback(&cancel_requester),
+                Some(CtrlCTarget {
+                    cancel_requester,
+                    callback: None,
+                }) => {
+                    // The pipeline might have finished already, which is fine.
+                    let _ = cancel_requester.send(CancelRequest);
+                }
+                None => {}
+            }
+        })
+        .expect("Error setting Ctrl-C handler");
+    });
+}
+
+/// Where the pipeline executes an actor, see [ActorBuilder::set_placement()].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ActorPlacement {
+    /// The actor runs as a task on the runtime the pipeline is run on.
+    #[default]
+    Ambient,
+    /// The actor runs on its own dedicated OS thread, with a current-thread runtime. This isolates
+    /// actors which block or hog the CPU from the rest of the pipeline.
+    DedicatedThread,
+    /// The actor runs on its own dedicated OS thread, within a [tokio::task::LocalSet]. Hence, its
+    /// handlers can spawn `!Send` futures using [tokio::task::spawn_local()], e.g. to drive
+    /// thread-bound resources such as GPU contexts.
+    LocalSet,
+    /// The actor runs together with the other actors of the group on a shared dedicated thread,
+    /// see [Hollywood::assign_actor_group()].
+    Group(String),
+    /// The actor runs as a task on the runtime registered with the given name, see
+    /// [Hollywood::register_runtime()].
+    Runtime(String),
+}
+
+/// Flavor of the tokio runtime created by [Pipeline::run_blocking_with()].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RuntimeFlavor {
+    /// All actors are executed on the current thread.
+    #[default]
+    CurrentThread,
+    /// Actors are executed on a multi-threaded work-stealing runtime.
+    MultiThread,
+}
+
+/// Phase of a pipeline which is configured and ready to run, see [Pipeline].
+#[derive(Debug)]
+pub struct Configured;
+
+/// Phase of a pipeline which finished its execution, see [Pipeline].
+#[derive(Debug)]
+pub struct Finished;
+
 /// Compute pipeline, strictly speaking a DAG (directed acyclic graph) of actors. It is created by
 /// the [Hollywood::configure()] method.
-pub struct Pipeline {
+///
+/// The phase of the pipeline is part of its type, so that operations which are invalid in a
+/// phase are rejected at compile time:
+///
+///   - A `Pipeline<Configured>` is returned by [Hollywood::configure()]. The topology is fixed
+///     at this point, since the [Hollywood] context is only available during configuration.
+///     Actors can still be added while the pipeline is running, see [DynamicTopology].
+///   - [Pipeline::run()] and [Pipeline::spawn()] consume the configured pipeline. While it is
+///     running, it is only accessible through a [RunningPipeline] handle, and cannot be run a
+///     second time.
+///   - A `Pipeline<Finished>` is returned once the execution completed. It gives access to the
+///     statistics of the run and to the final states of the actors, but cannot be run again:
+///
+/// ```compile_fail
+/// # use hollywood::actors::Periodic;
+/// # use hollywood::prelude::*;
+/// # async fn run_twice() {
+/// let pipeline = Hollywood::configure(&mut |context| {
+///     let _timer = Periodic::new_with_period(context, 1.0);
+/// });
+/// let pipeline = pipeline.run().await.unwrap();
+/// pipeline.run().await.unwrap();
+/// # }
+/// ```
+pub struct Pipeline<Phase = Configured> {
     actors: Vec<Box<dyn IsActorNode + Send>>,
     topology: Topology,
     /// We have this here to keep receiver alive
     pub cancel_request_sender_template: Option<tokio::sync::mpsc::UnboundedSender<CancelRequest>>,
     cancel_request_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<CancelRequest>>,
+    run_options: ActorRunOptions,
+    actor_placements: HashMap<String, ActorPlacement>,
+    runtimes: HashMap<String, tokio::runtime::Handle>,
+    load_shedders: HashMap<String, Arc<LoadShedder>>,
+    bounded_inbounds: HashMap<String, Vec<Arc<BoundedInbound>>>,
+    channel_orderings: HashMap<String, Vec<ChannelOrdering>>,
+    correlations: HashMap<String, Correlations>,
+    invariants: Vec<InvariantCounter>,
+    actor_filter: Option<ActorFilter>,
+    progress: Arc<tokio::sync::watch::Sender<Option<Progress>>>,
+    errors: tokio::sync::broadcast::Sender<ActorErrorEvent>,
+    heartbeat: Option<Heartbeat>,
+    chaos: ChaosController,
+    fused_actors: HashSet<String>,
+    dynamic: DynamicTopology,
+    graceful_shutdown: Option<std::time::Duration>,
+    clock: Option<Arc<dyn IsClock>>,
+    stepping: Option<Stepping>,
+    metrics: Option<Metrics>,
+    metrics_dump: Option<MetricsDump>,
+    taps: Arc<TapRegistry>,
+    #[cfg(feature = "serde")]
+    checkpoints: Checkpoints,
+    /// Drain timeout requested by [RunningPipeline::shutdown_gracefully()] for the current run.
+    requested_drain: Arc<Mutex<Option<std::time::Duration>>>,
+    /// Whether the actors process their pending messages once they are stopped.
+    drain: Arc<AtomicBool>,
+    /// How Ctrl-C is handled, which is disabled for subgraphs.
+    pub(crate) ctrl_c_handling: CtrlCHandling,
+    versions: ManifestVersions,
+    phase: PhantomData<Phase>,
 }
 
-impl Pipeline {
+impl<Phase> Pipeline<Phase> {
+    /// Returns a sender to send cancel requests to the pipeline.
+    pub fn get_cancel_request_sender(&self) -> tokio::sync::mpsc::UnboundedSender<CancelRequest> {
+        self.cancel_request_sender_template
+            .as_ref()
+            .unwrap()
+            .clone()
+    }
+
+    /// Returns the controller to inject faults into the actors of the running pipeline, see
+    /// [ChaosController].
+    ///
+    /// The controller is inert unless [Hollywood::enable_chaos_commands()] was called.
+    pub fn chaos_controller(&self) -> ChaosController {
+        self.chaos.clone()
+    }
+
+    /// Returns the handle to add and remove actors while the pipeline is running, see
+    /// [DynamicTopology].
+    pub fn dynamic_topology(&self) -> DynamicTopology {
+        self.dynamic.clone()
+    }
+
+    /// Returns a receiver to watch the progress of a batch or offline job while the pipeline is
+    /// running, see [Hollywood::register_progress_reporter()].
+    ///
+    /// The value is None until the first progress is reported.
+    pub fn progress_watch(&self) -> tokio::sync::watch::Receiver<Option<Progress>> {
+        self.progress.subscribe()
+    }
+
+    /// Returns the latest progress reported, see [Hollywood::register_progress_reporter()].
+    pub fn progress(&self) -> Option<Progress> {
+        self.progress.borrow().clone()
+    }
+
+    /// Returns a receiver of the errors of the message handlers of all actors, see
+    /// [HasOnMessage::try_on_message()].
+    ///
+    /// Only errors which occur after this call are received. If the receiver falls behind by
+    /// more than [Pipeline::ERROR_STREAM_CAPACITY] errors, the oldest ones are skipped.
+    pub fn error_stream(&self) -> tokio::sync::broadcast::Receiver<ActorErrorEvent> {
+        self.errors.subscribe()
+    }
+
+    /// Returns the number of inbound channels the given outbound channel is connected to.
+    ///
+    /// A fan-out of one means that messages are moved to the downstream actor without cloning.
+    pub fn fan_out(&self, actor_name: &str, outbound_name: &str) -> usize {
+        self.topology.fan_out(actor_name, outbound_name)
+    }
+
+    /// Returns the message batch statistics of all actors of the pipeline.
+    ///
+    /// The statistics are only meaningful after the pipeline was run, see [Pipeline::run()].
+    pub fn batch_stats(&self) -> Vec<(String, BatchStats)> {
+        self.actors
+            .iter()
+            .filter_map(|actor| {
+                actor
+                    .batch_stats()
+                    .map(|stats| (actor.name().clone(), stats))
+            })
+            .collect()
+    }
+
+    /// Returns the resource accounting of all actors, sorted by cost (most expensive first).
+    ///
+    /// The cost of an actor is the cumulative time spent in its message and request handlers. The
+    /// report also contains the number of processed messages and the peak queue length, see
+    /// [BatchStats]. It is only meaningful after the pipeline was run, see [Pipeline::run()].
+    pub fn actor_report(&self) -> Vec<(String, BatchStats)> {
+        let mut report = self.batch_stats();
+        report.sort_by(|a, b| b.1.busy_time.cmp(&a.1.busy_time).then(a.0.cmp(&b.0)));
+        report
+    }
+
+    /// Returns the number of restarts of all actors which were restarted while the pipeline was
+    /// running, see [SupervisionStrategy].
+    pub fn restart_report(&self) -> Vec<(String, usize)> {
+        self.actors
+            .iter()
+            .map(|actor| (actor.name().clone(), actor.num_restarts()))
+            .filter(|(_, num_restarts)| *num_restarts > 0)
+            .collect()
+    }
+
+    /// Returns the reports of all invariants registered using
+    /// [OutboundChannel::add_invariant()].
+    pub fn invariant_reports(&self) -> Vec<InvariantReport> {
+        self.invariants.iter().map(|i| i.report()).collect()
+    }
+
+    fn log_invariant_reports(&self) {
+        for report in self.invariant_reports() {
+            if report.num_violations > 0 {
+                warn!(
+                    "{}.{}: invariant {} violated by {} of {} messages",
+                    report.actor_name,
+                    report.outbound_name,
+                    report.invariant_name,
+                    report.num_violations,
+                    report.num_checked
+                );
+            }
+        }
+    }
+
+    fn log_actor_report(&self) {
+        const MAX_REPORTED_ACTORS: usize = 10;
+        let report = self.actor_report();
+        let total: std::time::Duration = report.iter().map(|(_, stats)| stats.busy_time).sum();
+        info!("Actor report, total busy time {:?}, sorted by cost:", total);
+        for (actor_name, stats) in report.iter().take(MAX_REPORTED_ACTORS) {
+            info!(
+                "  {}: {:?} busy, {} messages ({:?} per message), peak queue length {}",
+                actor_name,
+                stats.busy_time,
+                stats.num_messages,
+                stats.mean_busy_time(),
+                stats.peak_queue_len
+            );
+        }
+        if report.len() > MAX_REPORTED_ACTORS {
+            info!(
+                "  ... and {} more actors, see Pipeline::actor_report()",
+                report.len() - MAX_REPORTED_ACTORS
+            );
+        }
+    }
+
+    /// Returns the load shedding statistics of all actors with a load shedding policy.
+    ///
+    /// See [Hollywood::set_load_shedding()] for details.
+    pub fn load_shedding_stats(&self) -> Vec<(String, LoadSheddingStats)> {
+        let mut stats: Vec<_> = self
+            .load_shedders
+            .iter()
+            .map(|(name, shedder)| (name.clone(), shedder.stats()))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    /// Returns the statistics of all bounded inbound channels as (actor name, inbound name,
+    /// statistics).
+    ///
+    /// See [OutboundChannel::connect_with_capacity()] for details.
+    pub fn backpressure_stats(&self) -> Vec<(String, String, BackpressureStats)> {
+        let mut stats: Vec<_> = self
+            .bounded_inbounds
+            .iter()
+            .flat_map(|(actor_name, inbounds)| {
+                inbounds.iter().map(|inbound| {
+                    (
+                        actor_name.clone(),
+                        inbound.inbound_name.clone(),
+                        inbound.stats(),
+                    )
+                })
+            })
+            .collect();
+        stats.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        stats
+    }
+
+    /// Printers the flow graph of the compute graph.
+    ///
+    /// The flow graph is drawn with the `flow_graph` feature only, otherwise the connections are
+    /// listed.
+    pub fn print_flow_graph(&self) {
+        self.topology.print_flow_graph();
+    }
+
+    /// Returns the topology in the DOT language, e.g. to render large graphs with Graphviz:
+    ///
+    /// ``` ignore
+    /// std::fs::write("pipeline.dot", pipeline.to_dot())?;
+    /// // dot -Tsvg pipeline.dot -o pipeline.svg
+    /// ```
+    ///
+    /// Data connections are solid edges between the channels of the actors, and request-reply
+    /// connections are dashed edges.
+    pub fn to_dot(&self) -> String {
+        self.topology.to_dot()
+    }
+
+    /// Returns the topology as a Mermaid flowchart, e.g. to embed it in markdown documentation.
+    ///
+    /// See [Pipeline::to_dot()] for details.
+    pub fn to_mermaid(&self) -> String {
+        self.topology.to_mermaid()
+    }
+
+    /// Returns a snapshot of the metrics, i.e. the messages sent and received per channel and
+    /// the handler latency per actor, accumulated while the pipeline was running.
+    ///
+    /// The snapshot is empty unless [Hollywood::enable_metrics()] was called.
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics
+            .as_ref()
+            .map(|metrics| metrics.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Returns a copy of the state of the actor, see [ActorStateHandle].
+    ///
+    /// Returns None if the actor is not part of this pipeline, or if its state is not accessible,
+    /// e.g. since it was aborted mid-run or since its runner does not expose its state, see
+    /// [IsActorNode::inspect_state()].
+    pub fn actor_state<State: Clone + 'static>(
+        &self,
+        handle: &ActorStateHandle<State>,
+    ) -> Option<State> {
+        let actor = self
+            .actors
+            .iter()
+            .find(|actor| actor.name() == handle.actor_name())?;
+        let mut state = None;
+        actor.inspect_state(&mut |any| {
+            state = any.downcast_ref::<State>().cloned();
+        });
+        state
+    }
+
+    /// Attaches a receiver to the tap of the outbound channel of the given actor, which buffers up
+    /// to `capacity` messages, see [ChannelTap].
+    ///
+    /// Returns None if the channel was not made tappable using [OutboundChannel::tap()], or if
+    /// its payload type is not `T`.
+    pub fn tap<T: Send + 'static>(
+        &self,
+        actor_name: &str,
+        outbound_name: &str,
+        capacity: usize,
+    ) -> Option<tokio::sync::mpsc::Receiver<T>> {
+        find_tap::<T>(&self.taps, actor_name, outbound_name).map(|tap| tap.receiver(capacity))
+    }
+
+    /// Returns a snapshot of the states of the checkpointed actors, see
+    /// [ActorBuilder::enable_checkpoint()], to be restored using
+    /// [Hollywood::configure_from_snapshot()].
+    ///
+    /// Since the states are not accessible while the pipeline is running, it is taken from the
+    /// configured or finished pipeline, e.g. after [RunningPipeline::shutdown_gracefully()].
+    /// Messages which are still queued are not part of the snapshot. Actors which were aborted
+    /// mid-run are skipped.
+    #[cfg(feature = "serde")]
+    pub fn snapshot(&self) -> PipelineSnapshot {
+        self.checkpoints.snapshot(&self.actors)
+    }
+
+    /// Returns the manifest of the pipeline, i.e. its actors and connections along with the
+    /// versions of the actor types and message schemas, see [PipelineManifest].
+    ///
+    /// Subgraphs which are added while the pipeline is running are not included.
+    pub fn manifest(&self) -> PipelineManifest {
+        PipelineManifest::new(
+            self.actors.iter().map(|actor| actor.name()),
+            &self.versions,
+            &self.topology,
+        )
+    }
+
+    fn into_phase<NextPhase>(self) -> Pipeline<NextPhase> {
+        Pipeline {
+            actors: self.actors,
+            topology: self.topology,
+            cancel_request_sender_template: self.cancel_request_sender_template,
+            cancel_request_receiver: self.cancel_request_receiver,
+            run_options: self.run_options,
+            actor_placements: self.actor_placements,
+            runtimes: self.runtimes,
+            load_shedders: self.load_shedders,
+            bounded_inbounds: self.bounded_inbounds,
+            channel_orderings: self.channel_orderings,
+            correlations: self.correlations,
+            invariants: self.invariants,
+            actor_filter: self.actor_filter,
+            progress: self.progress,
+            errors: self.errors,
+            heartbeat: self.heartbeat,
+            chaos: self.chaos,
+            fused_actors: self.fused_actors,
+            dynamic: self.dynamic,
+            graceful_shutdown: self.graceful_shutdown,
+            clock: self.clock,
+            stepping: self.stepping,
+            metrics: self.metrics,
+            metrics_dump: self.metrics_dump,
+            taps: self.taps,
+            #[cfg(feature = "serde")]
+            checkpoints: self.checkpoints,
+            requested_drain: self.requested_drain,
+            drain: self.drain,
+            ctrl_c_handling: self.ctrl_c_handling,
+            versions: self.versions,
+            phase: PhantomData,
+        }
+    }
+}
+
+impl Pipeline<Configured> {
+    /// Number of errors buffered by the error stream, see [Pipeline::error_stream()].
+    pub const ERROR_STREAM_CAPACITY: usize = 256;
+
     pub(crate) fn from_context(context: Hollywood) -> Self {
+        Self::try_from_context(context)
+            .unwrap_or_else(|err| panic!("oh no, pipeline is misconfigured: {}", err))
+    }
+
+    pub(crate) fn try_from_context(context: Hollywood) -> Result<Self, TopologyError> {
+        context.validate()?;
         let mut active = vec![];
+        let mut correlations = HashMap::new();
+        let mut correlation_queues = context.correlation_queues;
         for actor in context.actors.into_iter() {
+            // Correlation ids are only recorded for actors which consume them, see
+            // IsActorNode::traces_messages().
+            if let Some(queues) = correlation_queues.remove(actor.name()) {
+                if actor.traces_messages() {
+                    queues.values().for_each(|queue| queue.activate());
+                    correlations.insert(actor.name().clone(), Correlations::new(queues));
+                }
+            }
             active.push(actor);
         }
+        let chaos = if context.chaos_commands {
+            ChaosController::new(active.iter().map(|actor| actor.name()))
+        } else {
+            ChaosController::default()
+        };
         let compute_graph = Pipeline {
             actors: active,
             topology: context.topology,
             cancel_request_sender_template: Some(context.cancel_request_sender_template),
             cancel_request_receiver: Some(context.cancel_request_receiver),
+            run_options: context.run_options,
+            actor_placements: context.actor_placements,
+            runtimes: context.runtimes,
+            load_shedders: context.load_shedders,
+            bounded_inbounds: context.bounded_inbounds,
+            channel_orderings: context.channel_orderings,
+            correlations,
+            invariants: context.invariants,
+            actor_filter: None,
+            progress: context.progress,
+            errors: tokio::sync::broadcast::channel(Self::ERROR_STREAM_CAPACITY).0,
+            heartbeat: context.heartbeat,
+            chaos,
+            fused_actors: context.fused_actors,
+            dynamic: DynamicTopology::default(),
+            graceful_shutdown: context.graceful_shutdown,
+            clock: context.clock,
+            stepping: context.stepping,
+            metrics: context.metrics,
+            metrics_dump: context.metrics_dump,
+            taps: Arc::new(context.taps),
+            #[cfg(feature = "serde")]
+            checkpoints: context.checkpoints,
+            requested_drain: Arc::new(Mutex::new(None)),
+            drain: Arc::new(AtomicBool::new(false)),
+            ctrl_c_handling: context.ctrl_c_handling,
+            versions: context.versions,
+            phase: PhantomData,
         };
-        compute_graph.topology.analyze_graph_topology();
-        compute_graph
-    }
-
-    /// Returns a sender to send cancel requests to the pipeline.
-    pub fn get_cancel_request_sender(&self) -> tokio::sync::mpsc::UnboundedSender<CancelRequest> {
-        self.cancel_request_sender_template
-            .as_ref()
-            .unwrap()
-            .clone()
+        Ok(compute_graph)
     }
 
     /// Executes the compute graph.
     ///
     /// It consumes the self, starts  execution of the pipeline and returns a future (since it is
-    /// an async function) that resolves to the pipeline itself. The future is completed when all
-    /// actors have completed their execution.
+    /// an async function) that resolves to the finished pipeline. The future is completed when all
+    /// actors have completed their execution. See [Pipeline::spawn()] to run the pipeline in the
+    /// background instead.
+    ///
+    /// If one or more actors failed (e.g. panicked) during execution, a [PipelineError] is
+    /// returned instead, which lists the failed actors and contains the pipeline with all
+    /// remaining actors.
     ///
     /// In particular, [IsActorNode::run()] is called for each actor in the pipeline in a dedicated
-    /// tokio task. Hence, the actors run concurrently.
-    ///
-    /// TODO:
-    ///   Document state of actors before during and after completion, and validate that this is
-    ///   indeed the case.
-    ///    - All actors are set to its initial state right when this method is called and before
-    ///      the actual execution starts.
-    ///    - All actors remain their current state when the execution is completed.
-    ///    - Repeatable execution of the pipeline shall lead to comparable results.
-    ///      
-    pub async fn run(mut self) -> Self {
+    /// tokio task. Hence, the actors run concurrently. If a [Heartbeat] is set (see
+    /// [Hollywood::set_heartbeat()]), it notifies its target while all actors are running.
+    ///
+    /// The states of the actors evolve as follows:
+    ///    - Each actor starts from the state it was created with, see
+    ///      [HasFromPropState::from_prop_and_state()], or from the state restored from a pipeline
+    ///      snapshot.
+    ///    - While the pipeline is running, the state is owned by the task of the actor. An actor
+    ///      which is restarted continues from its initial or checkpointed state, see
+    ///      [SupervisionStrategy].
+    ///    - Once the execution is completed, each actor which did not fail is handed back to the
+    ///      finished pipeline along with its final state, see [Pipeline::actor_state()]. Failed
+    ///      actors are dropped along with their states.
+    ///
+    /// Since the actors are not reset to their initial states, a finished pipeline cannot be run
+    /// again.
+    pub async fn run(mut self) -> Result<Pipeline<Finished>, PipelineError> {
         info!("Pipeline started ...");
 
-        // Set up Ctrl-C handler to cancel the pipeline
-        //
-        // TODO: Make this configurable, but keep it enabled by default.
-        let cancel_requester = self.get_cancel_request_sender();
-        ctrlc::set_handler(move || {
-            cancel_requester
-                .send(CancelRequest)
-                .expect("Error sending Ctrl-C triggered cancel request");
-        })
-        .expect("Error setting Ctrl-C handler");
-
-        let (kill_sender, _) = tokio::sync::broadcast::channel(10);
+        // Set up Ctrl-C handler to cancel the pipeline, see Hollywood::set_ctrl_c_handling().
+        on_ctrl_c(&self.ctrl_c_handling, self.get_cancel_request_sender());
 
         let mut handles = vec![];
         let mut actors = vec![];
@@ -114,27 +884,153 @@ impl Pipeline {
             }
         });
 
+        let liveness = ActorLiveness::default();
         swap(&mut actors, &mut self.actors);
+        let mut groups: HashMap<String, Vec<GroupedActor>> = HashMap::new();
         for mut actor in actors {
+            if let Some(filter) = &self.actor_filter {
+                if !filter.includes(actor.name()) {
+                    info!("{}: excluded from this run", actor.name());
+                    self.actors.push(actor);
+                    continue;
+                }
+            }
             let (tx, rx) = tokio::sync::oneshot::channel();
-            let kill_receiver = kill_sender.subscribe();
-            let h = tokio::spawn(async move {
-                actor.run(kill_receiver).await;
-                if tx.send(actor).is_err() {}
+            // Each actor has its own kill signal, so that actors can be stopped one after
+            // another during a graceful shutdown. A watch channel is used so that the kill signal
+            // cannot be missed.
+            let (kill_sender, kill_receiver) = tokio::sync::watch::channel(false);
+            let actor_name = actor.name().clone();
+            actor.set_run_options(ActorRunOptions {
+                load_shedder: self.load_shedders.get(&actor_name).cloned(),
+                bounded: BoundedInbounds::new(
+                    self.bounded_inbounds
+                        .get(&actor_name)
+                        .cloned()
+                        .unwrap_or_default(),
+                ),
+                channel_orderings: self
+                    .channel_orderings
+                    .get(&actor_name)
+                    .cloned()
+                    .unwrap_or_default(),
+                correlations: self
+                    .correlations
+                    .get(&actor_name)
+                    .cloned()
+                    .unwrap_or_default(),
+                chaos: self.chaos.subscribe(&actor_name),
+                fused: self.fused_actors.contains(&actor_name),
+                drain: self.drain.clone(),
+                clock: self.clock.clone(),
+                metrics: self
+                    .metrics
+                    .as_ref()
+                    .map(|metrics| metrics.actor(&actor_name)),
+                error_sink: Some(ErrorSink {
+                    events: self.errors.clone(),
+                    cancel_request_sender: self.get_cancel_request_sender(),
+                }),
+                ..self.run_options.clone()
             });
             rxs.push(rx);
 
-            handles.push(h);
+            let placement = self
+                .actor_placements
+                .get(&actor_name)
+                .cloned()
+                .unwrap_or_default();
+            match placement {
+                ActorPlacement::Ambient | ActorPlacement::Runtime(_) => {}
+                ActorPlacement::Group(group) => {
+                    let (join_tx, join_rx) = tokio::sync::oneshot::channel();
+                    groups.entry(group).or_default().push((
+                        actor,
+                        kill_receiver,
+                        tx,
+                        join_tx,
+                        liveness.guard(),
+                    ));
+                    handles.push((actor_name, ActorHandle::Grouped(join_rx), kill_sender));
+                    continue;
+                }
+                ActorPlacement::DedicatedThread | ActorPlacement::LocalSet => {
+                    let (join_tx, join_rx) = tokio::sync::oneshot::channel();
+                    spawn_actor_group(
+                        actor_name.clone(),
+                        vec![(actor, kill_receiver, tx, join_tx, liveness.guard())],
+                        self.activity(),
+                        placement == ActorPlacement::LocalSet,
+                    );
+                    handles.push((actor_name, ActorHandle::Grouped(join_rx), kill_sender));
+                    continue;
+                }
+            }
+
+            let alive = liveness.guard();
+            let span = info_span!("actor", name = actor_name.as_str());
+            let task = async move {
+                let _alive = alive;
+                actor.run(kill_receiver).await;
+                if tx.send(actor).is_err() {}
+            }
+            .instrument(span);
+            let h = match &placement {
+                ActorPlacement::Runtime(runtime) => {
+                    spawn_tracked_on(&self.runtimes[runtime], self.activity(), task)
+                }
+                _ => spawn_tracked(self.activity(), task),
+            };
+            handles.push((actor_name, ActorHandle::Task(h), kill_sender));
+        }
+        for (group, group_actors) in groups {
+            spawn_actor_group(group, group_actors, self.activity(), false);
         }
+        let h_heartbeat = self
+            .heartbeat
+            .clone()
+            .map(|heartbeat| tokio::spawn(heartbeat.run(liveness)));
+        let h_metrics_dump = match (&self.metrics, &self.metrics_dump) {
+            (Some(metrics), Some(dump)) => Some(tokio::spawn(dump.clone().run(metrics.clone()))),
+            _ => None,
+        };
+        self.dynamic.start();
         match h_exit.await {
             Ok(_) => {}
             Err(err) => {
                 warn!("Error in cancel request handler: {}", err);
             }
         }
-        kill_sender.send(()).unwrap();
-        for h in handles {
-            h.await.unwrap();
+        if let Some(h) = h_heartbeat {
+            h.abort();
+        }
+        if let Some(h) = h_metrics_dump {
+            h.abort();
+        }
+        let mut failed_actors = self.dynamic.stop().await;
+        let drain_timeout = self
+            .requested_drain
+            .lock()
+            .unwrap()
+            .take()
+            .or(self.graceful_shutdown);
+        if let Some(timeout) = drain_timeout {
+            self.drain.store(true, Ordering::Release);
+            let waves = self.topology.shutdown_waves();
+            failed_actors.extend(stop_gracefully(waves, &mut handles, timeout).await);
+        }
+        for (_, _, kill_sender) in handles.iter() {
+            kill_sender.send_replace(true);
+        }
+        for (actor_name, mut h, _) in handles {
+            if let Err(failure) = h.join(actor_name).await {
+                warn!("{}", failure);
+                failed_actors.push(failure);
+            }
+        }
+        self.drain.store(false, Ordering::Release);
+        if let Some(dump) = &self.metrics_dump {
+            dump.dump(&self.metrics());
         }
 
         let mut r = exit_rx.await.unwrap();
@@ -144,22 +1040,390 @@ impl Pipeline {
         self.cancel_request_receiver = Some(r);
 
         for rx in rxs {
-            match rx.await {
-                Ok(a) => {
-                    self.actors.push(a);
-                }
-                Err(err) => {
-                    panic!("oh no, actor died: {}", err);
-                }
+            // The sender is dropped without sending if the actor failed, which is already
+            // recorded above.
+            if let Ok(a) = rx.await {
+                self.actors.push(a);
             }
         }
 
+        if !failed_actors.is_empty() {
+            warn!("Pipeline execution finished with failed actors");
+            self.log_actor_report();
+            self.log_invariant_reports();
+            return Err(PipelineError {
+                failed_actors,
+                pipeline: Box::new(self.into_phase()),
+            });
+        }
+
         info!("Pipeline execution finished");
-        self
+        self.log_actor_report();
+        self.log_invariant_reports();
+        Ok(self.into_phase())
     }
 
-    /// Printers the flow graph of the compute graph.
-    pub fn print_flow_graph(&self) {
-        self.topology.print_flow_graph();
+    /// Executes only the subset of actors selected by the given filter.
+    ///
+    /// The other actors are not spawned and their channels become inert: messages sent to them
+    /// are queued but never processed, and they do not send any messages. This makes it possible
+    /// to iterate on one branch of a large graph without bringing up e.g. hardware-bound source
+    /// actors. See [Pipeline::run()] for details.
+    pub async fn run_partial(
+        mut self,
+        filter: ActorFilter,
+    ) -> Result<Pipeline<Finished>, PipelineError> {
+        self.actor_filter = Some(filter);
+        let mut result = self.run().await;
+        match &mut result {
+            Ok(pipeline) => pipeline.actor_filter = None,
+            Err(err) => err.pipeline.actor_filter = None,
+        }
+        result
+    }
+
+    /// Executes the compute graph, but cancels it automatically once the given deadline elapsed.
+    ///
+    /// This is useful for CI jobs and batch experiments which must never hang indefinitely. The
+    /// pipeline may still stop earlier due to a regular cancel request. See [Pipeline::run()] for
+    /// details.
+    pub async fn run_with_deadline(
+        self,
+        deadline: std::time::Duration,
+    ) -> Result<Pipeline<Finished>, PipelineError> {
+        let cancel_requester = self.get_cancel_request_sender();
+        let h_deadline = tokio::spawn(async move {
+            tokio::time::sleep(deadline).await;
+            info!("Pipeline deadline of {:?} elapsed", deadline);
+            let _ = cancel_requester.send(CancelRequest);
+        });
+        let result = self.run().await;
+        h_deadline.abort();
+        result
+    }
+
+    /// Executes the compute graph on a newly created current-thread tokio runtime and blocks until
+    /// completion.
+    ///
+    /// This is a convenience wrapper around [Pipeline::run()] for binaries which do not set up
+    /// a tokio runtime themselves. Panics if called from within an async context.
+    pub fn run_blocking(self) -> Result<Pipeline<Finished>, PipelineError> {
+        self.run_blocking_with(RuntimeFlavor::CurrentThread)
+    }
+
+    /// Executes the compute graph on a newly created tokio runtime of the given flavor and blocks
+    /// until completion.
+    ///
+    /// See [Pipeline::run_blocking()] for details.
+    pub fn run_blocking_with(
+        self,
+        flavor: RuntimeFlavor,
+    ) -> Result<Pipeline<Finished>, PipelineError> {
+        let mut builder = match flavor {
+            RuntimeFlavor::CurrentThread => tokio::runtime::Builder::new_current_thread(),
+            RuntimeFlavor::MultiThread => tokio::runtime::Builder::new_multi_thread(),
+        };
+        builder
+            .enable_all()
+            .build()
+            .expect("Error building tokio runtime")
+            .block_on(self.run())
+    }
+
+    /// Starts the execution of the compute graph on the current tokio runtime and returns a
+    /// handle to the running pipeline.
+    ///
+    /// See [Pipeline::run()] for details.
+    pub fn spawn(self) -> RunningPipeline {
+        RunningPipeline {
+            cancel_request_sender: self.get_cancel_request_sender(),
+            chaos: self.chaos.clone(),
+            dynamic: self.dynamic.clone(),
+            requested_drain: self.requested_drain.clone(),
+            progress: self.progress.clone(),
+            errors: self.errors.clone(),
+            metrics: self.metrics.clone(),
+            taps: self.taps.clone(),
+            stepping: self.stepping.clone(),
+            // The pipeline is busy until all actors are spawned.
+            handle: spawn_tracked(self.activity(), self.run()),
+        }
+    }
+
+    fn activity(&self) -> Option<Arc<Activity>> {
+        self.stepping
+            .as_ref()
+            .map(|stepping| stepping.activity.clone())
+    }
+}
+
+/// Handle to a running pipeline, see [Pipeline::spawn()].
+pub struct RunningPipeline {
+    cancel_request_sender: tokio::sync::mpsc::UnboundedSender<CancelRequest>,
+    chaos: ChaosController,
+    dynamic: DynamicTopology,
+    requested_drain: Arc<Mutex<Option<std::time::Duration>>>,
+    progress: Arc<tokio::sync::watch::Sender<Option<Progress>>>,
+    errors: tokio::sync::broadcast::Sender<ActorErrorEvent>,
+    metrics: Option<Metrics>,
+    taps: Arc<TapRegistry>,
+    stepping: Option<Stepping>,
+    handle: tokio::task::JoinHandle<Result<Pipeline<Finished>, PipelineError>>,
+}
+
+impl RunningPipeline {
+    /// Returns a sender to send cancel requests to the pipeline.
+    pub fn get_cancel_request_sender(&self) -> tokio::sync::mpsc::UnboundedSender<CancelRequest> {
+        self.cancel_request_sender.clone()
+    }
+
+    /// Requests the pipeline to stop. Use [RunningPipeline::join()] to wait for completion.
+    pub fn cancel(&self) {
+        // The pipeline holds the receiver until it is finished, after which cancelling is moot.
+        let _ = self.cancel_request_sender.send(CancelRequest);
+    }
+
+    /// Shuts the pipeline down gracefully, and waits until all actors completed their execution.
+    ///
+    /// Source actors are stopped first, and downstream actors process their pending messages
+    /// before they exit. Actors which did not stop within the given timeout are stopped right
+    /// away. See [Hollywood::set_graceful_shutdown()] for details.
+    pub async fn shutdown_gracefully(
+        self,
+        timeout: std::time::Duration,
+    ) -> Result<Pipeline<Finished>, PipelineError> {
+        *self.requested_drain.lock().unwrap() = Some(timeout);
+        self.cancel();
+        self.join().await
+    }
+
+    /// Returns the controller to inject faults into the actors, see [ChaosController].
+    pub fn chaos_controller(&self) -> ChaosController {
+        self.chaos.clone()
+    }
+
+    /// Returns the handle to add and remove actors, see [DynamicTopology].
+    pub fn dynamic_topology(&self) -> DynamicTopology {
+        self.dynamic.clone()
+    }
+
+    /// Returns a receiver to watch the progress of a batch or offline job, see
+    /// [Pipeline::progress_watch()].
+    pub fn progress_watch(&self) -> tokio::sync::watch::Receiver<Option<Progress>> {
+        self.progress.subscribe()
+    }
+
+    /// Returns the latest progress reported, see [Pipeline::progress()].
+    pub fn progress(&self) -> Option<Progress> {
+        self.progress.borrow().clone()
+    }
+
+    /// Returns a receiver of the errors of the message handlers of all actors, see
+    /// [Pipeline::error_stream()].
+    pub fn error_stream(&self) -> tokio::sync::broadcast::Receiver<ActorErrorEvent> {
+        self.errors.subscribe()
+    }
+
+    /// Returns a snapshot of the metrics, see [Pipeline::metrics()].
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics
+            .as_ref()
+            .map(|metrics| metrics.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Attaches a receiver to the tap of an outbound channel while the pipeline is running, see
+    /// [Pipeline::tap()].
+    pub fn tap<T: Send + 'static>(
+        &self,
+        actor_name: &str,
+        outbound_name: &str,
+        capacity: usize,
+    ) -> Option<tokio::sync::mpsc::Receiver<T>> {
+        find_tap::<T>(&self.taps, actor_name, outbound_name).map(|tap| tap.receiver(capacity))
+    }
+
+    /// Advances the pipeline by one step, i.e. advances its virtual clock to the next tick of
+    /// the time-based actors, and waits until the pipeline is quiescent again. Returns the time
+    /// of the clock, or None if no actor is waiting for a tick anymore, e.g. since the pipeline
+    /// finished.
+    ///
+    /// The pipeline is quiescent once all actors processed their pending messages and requests,
+    /// including the messages they sent to each other in turn. Tasks which are spawned by the
+    /// business logic, as well as actors which wait for I/O or real time, are not waited for.
+    ///
+    /// Panics if stepping is not enabled, see [Hollywood::enable_stepping()].
+    pub async fn step(&self) -> Option<std::time::Duration> {
+        self.stepping().step().await
+    }
+
+    /// Waits until the pipeline is quiescent, e.g. after messages were injected from outside,
+    /// see [RunningPipeline::step()].
+    ///
+    /// Panics if stepping is not enabled, see [Hollywood::enable_stepping()].
+    pub async fn quiescent(&self) {
+        self.stepping().activity.quiescent().await;
+    }
+
+    fn stepping(&self) -> &Stepping {
+        self.stepping
+            .as_ref()
+            .expect("Stepping not enabled, see Hollywood::enable_stepping()")
+    }
+
+    /// Whether the pipeline finished its execution.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Waits until all actors completed their execution, and returns the finished pipeline.
+    ///
+    /// See [Pipeline::run()] for details.
+    pub async fn join(self) -> Result<Pipeline<Finished>, PipelineError> {
+        match self.handle.await {
+            Ok(result) => result,
+            Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+            Err(err) => panic!("pipeline task failed: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::Periodic;
+    use crate::example_actors::moving_average::MovingAverage;
+    use crate::example_actors::moving_average::MovingAverageProp;
+    use crate::example_actors::moving_average::MovingAverageState;
+    use crate::prelude::*;
+    use crate::RuntimeFlavor;
+
+    /// Moving average whose timeout is never reached.
+    fn moving_average(context: &mut Hollywood) -> MovingAverage {
+        MovingAverage::from_prop_and_state(
+            context,
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: f64::MAX,
+            },
+            MovingAverageState::default(),
+        )
+    }
+
+    #[test]
+    fn many_actors_stop_on_cancel() {
+        const NUM_ACTORS: usize = 500;
+        const STOP_BOUND: std::time::Duration = std::time::Duration::from_secs(5);
+
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(async {
+                let pipeline = Hollywood::configure(&mut |context| {
+                    let mut timer = Periodic::new_with_period(context, 0.01);
+                    for _ in 0..NUM_ACTORS {
+                        // The timeout is never reached, hence only the cancel request stops
+                        // the actors.
+                        let mut moving_average = moving_average(context);
+                        timer
+                            .outbound
+                            .time_stamp
+                            .connect(context, &mut moving_average.inbound.value);
+                    }
+                });
+                let cancel_requester = pipeline.get_cancel_request_sender();
+                let running = tokio::spawn(pipeline.run());
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                assert!(!running.is_finished());
+
+                let start = std::time::Instant::now();
+                cancel_requester.send(CancelRequest).unwrap();
+                let finished = tokio::time::timeout(STOP_BOUND, running)
+                    .await
+                    .expect("oh no, not all actors stopped in time")
+                    .unwrap()
+                    .unwrap();
+                assert!(start.elapsed() < STOP_BOUND);
+                // Each actor is handed back to the pipeline once its task completed.
+                assert_eq!(finished.actors.len(), NUM_ACTORS + 1);
+            });
+    }
+
+    #[test]
+    fn run_blocking_completes_pipeline() {
+        for flavor in [RuntimeFlavor::CurrentThread, RuntimeFlavor::MultiThread] {
+            let pipeline = Hollywood::configure(&mut |context| {
+                let mut timer = Periodic::new_with_period(context, 0.01);
+                // The moving average requests to cancel the pipeline once the timeout is reached.
+                let mut moving_average = MovingAverage::from_prop_and_state(
+                    context,
+                    MovingAverageProp {
+                        alpha: 0.3,
+                        timeout: 0.05,
+                    },
+                    MovingAverageState::default(),
+                );
+                timer
+                    .outbound
+                    .time_stamp
+                    .connect(context, &mut moving_average.inbound.value);
+                context.register_cancel_requester(&mut moving_average.outbound.cancel_request);
+            });
+            let finished = pipeline.run_blocking_with(flavor).unwrap();
+            assert_eq!(finished.manifest().actors.len(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn never_ending_pipeline_stops_at_deadline() {
+        const DEADLINE: std::time::Duration = std::time::Duration::from_millis(200);
+
+        let pipeline = Hollywood::configure(&mut |context| {
+            let mut timer = Periodic::new_with_period(context, 0.01);
+            // The timeout is never reached, hence the pipeline runs until the deadline.
+            let mut moving_average = moving_average(context);
+            timer
+                .outbound
+                .time_stamp
+                .connect(context, &mut moving_average.inbound.value);
+        });
+        let start = std::time::Instant::now();
+        tokio::time::timeout(10 * DEADLINE, pipeline.run_with_deadline(DEADLINE))
+            .await
+            .expect("oh no, the pipeline did not stop at the deadline")
+            .unwrap();
+        assert!(start.elapsed() >= DEADLINE);
+    }
+
+    #[tokio::test]
+    async fn fused_dispatch_is_counted() {
+        let pipeline = Hollywood::configure(&mut |context| {
+            let mut timer = Periodic::new_with_period(context, 0.01);
+            let mut source = moving_average(context);
+            let mut fused = moving_average(context);
+            context.enable_fused_dispatch(&fused.actor_name);
+            timer
+                .outbound
+                .time_stamp
+                .connect(context, &mut source.inbound.value);
+            source
+                .outbound
+                .average
+                .connect_fused(context, &mut fused.inbound.value);
+        });
+        let finished = pipeline
+            .run_with_deadline(std::time::Duration::from_millis(200))
+            .await
+            .unwrap();
+        let stats = finished.batch_stats();
+        let (_, fused_stats) = stats
+            .iter()
+            .find(|(name, _)| name == "MovingAverage_1")
+            .unwrap();
+        // The fused actor has no other inbound connections, hence all its messages were
+        // dispatched inline.
+        assert!(fused_stats.num_messages > 0, "{:?}", fused_stats);
+        assert_eq!(fused_stats.num_batches, fused_stats.num_messages);
+        assert!(fused_stats.busy_time > std::time::Duration::ZERO);
     }
 }
@@ -0,0 +1,124 @@
+use crate::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Progress of a batch or offline job, such as replaying a log file.
+///
+/// The source actor of the job reports its progress on an outbound channel registered with
+/// [Hollywood::register_progress_reporter()]. The progress is then published under the
+/// [Progress::TOPIC] topic, e.g. to be printed by a [ProgressBar](crate::actors::ProgressBar), and
+/// is accessible to the host application through [Pipeline::progress_watch()].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+    /// Number of consumed units, e.g. log messages or bytes.
+    pub done: u64,
+    /// Total number of units, if known.
+    pub total: Option<u64>,
+    /// Time elapsed since the job started.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Topic under which the progress is published.
+    pub const TOPIC: &'static str = "progress";
+
+    /// Fraction of consumed units in [0, 1], or None if the total is unknown.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(1.0),
+            Some(total) => Some((self.done as f64 / total as f64).min(1.0)),
+            None => None,
+        }
+    }
+
+    /// Estimated time until the job is finished, extrapolated from the average rate so far.
+    ///
+    /// Returns None if the total is unknown or no unit was consumed yet.
+    pub fn eta(&self) -> Option<Duration> {
+        let total = self.total?;
+        if self.done == 0 {
+            return None;
+        }
+        let remaining = total.saturating_sub(self.done);
+        Some(self.elapsed.mul_f64(remaining as f64 / self.done as f64))
+    }
+
+    /// Whether all units were consumed.
+    pub fn is_finished(&self) -> bool {
+        self.total.is_some_and(|total| self.done >= total)
+    }
+}
+
+impl std::fmt::Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.total, self.fraction()) {
+            (Some(total), Some(fraction)) => {
+                write!(f, "{:.1}% ({}/{})", 100.0 * fraction, self.done, total)?;
+                if let Some(eta) = self.eta() {
+                    write!(f, ", ETA {:.1}s", eta.as_secs_f64())?;
+                }
+                Ok(())
+            }
+            _ => write!(
+                f,
+                "{} done in {:.1}s",
+                self.done,
+                self.elapsed.as_secs_f64()
+            ),
+        }
+    }
+}
+
+/// Helper for source actors to keep track of their progress.
+///
+/// The clock starts with the first call to [ProgressTracker::advance()].
+#[derive(Clone, Debug, Default)]
+pub struct ProgressTracker {
+    start: Option<Instant>,
+    done: u64,
+    total: Option<u64>,
+}
+
+impl ProgressTracker {
+    /// Creates a tracker for a job with the given total number of units, if known.
+    pub fn new(total: Option<u64>) -> Self {
+        Self {
+            start: None,
+            done: 0,
+            total,
+        }
+    }
+
+    /// Sets the total number of units, e.g. once the size of the log is known.
+    pub fn set_total(&mut self, total: Option<u64>) {
+        self.total = total;
+    }
+
+    /// Marks the given number of units as consumed and returns the current progress.
+    pub fn advance(&mut self, num_units: u64) -> Progress {
+        self.start.get_or_insert_with(Instant::now);
+        self.done = self.done.saturating_add(num_units);
+        self.progress()
+    }
+
+    /// Returns the current progress.
+    pub fn progress(&self) -> Progress {
+        Progress {
+            done: self.done,
+            total: self.total,
+            elapsed: self.start.map(|s| s.elapsed()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Connection which stores the latest progress in the watch channel of the pipeline.
+pub(crate) struct ProgressConnection {
+    pub(crate) sender: Arc<tokio::sync::watch::Sender<Option<Progress>>>,
+}
+
+impl IsGenericConnection<Progress> for ProgressConnection {
+    fn send_impl(&self, msg: Progress) {
+        self.sender.send_replace(Some(msg));
+    }
+}
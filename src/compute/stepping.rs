@@ -0,0 +1,242 @@
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::task::Waker;
+use std::time::Duration;
+
+thread_local! {
+    static CURRENT_ACTIVITY: RefCell<Option<Arc<Activity>>> = const { RefCell::new(None) };
+}
+
+/// Step-wise execution of a pipeline, see [Hollywood::enable_stepping()].
+#[derive(Clone)]
+pub(crate) struct Stepping {
+    pub(crate) clock: Arc<SimClock>,
+    pub(crate) activity: Arc<Activity>,
+}
+
+impl Stepping {
+    pub(crate) fn new(clock: Arc<SimClock>) -> Self {
+        Self {
+            clock,
+            activity: Arc::new(Activity::default()),
+        }
+    }
+
+    /// Advances the clock to the next deadline once the pipeline is quiescent, and waits until
+    /// it is quiescent again.
+    pub(crate) async fn step(&self) -> Option<Duration> {
+        self.activity.quiescent().await;
+        let now = self.clock.step()?;
+        self.activity.quiescent().await;
+        Some(now)
+    }
+}
+
+/// Number of busy tasks of a pipeline, i.e. tasks which are scheduled to run or running.
+#[derive(Debug, Default)]
+pub(crate) struct Activity {
+    num_busy: AtomicUsize,
+    idle: tokio::sync::Notify,
+}
+
+impl Activity {
+    fn busy(&self) {
+        self.num_busy.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn idle(&self) {
+        if self.num_busy.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+
+    /// Waits until no task is busy.
+    pub(crate) async fn quiescent(&self) {
+        loop {
+            let idle = self.idle.notified();
+            tokio::pin!(idle);
+            idle.as_mut().enable();
+            if self.num_busy.load(Ordering::Acquire) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+/// The task is waiting to be woken up.
+const IDLE: u8 = 0;
+/// The task is being polled.
+const POLLING: u8 = 1;
+/// The task was woken up and is scheduled to be polled.
+const NOTIFIED: u8 = 2;
+/// The task completed or was dropped.
+const DONE: u8 = 3;
+
+/// Busy state of a tracked task.
+struct TaskActivity {
+    activity: Arc<Activity>,
+    state: AtomicU8,
+}
+
+impl TaskActivity {
+    fn notify(&self) {
+        let previous = self
+            .state
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |state| {
+                (state != DONE).then_some(NOTIFIED)
+            });
+        if previous == Ok(IDLE) {
+            self.activity.busy();
+        }
+    }
+}
+
+/// Waker which marks the task as busy before waking it up, i.e. synchronously within e.g. the
+/// send of a message to an idle actor. Hence, the sender is still busy at that point, and there
+/// is no gap in which the pipeline seems quiescent while the message is pending.
+struct TrackingWaker {
+    task: Arc<TaskActivity>,
+    waker: Waker,
+}
+
+impl Wake for TrackingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.task.notify();
+        self.waker.wake_by_ref();
+    }
+}
+
+/// Future whose task is tracked by the [Activity] of the pipeline.
+struct Tracked<F> {
+    future: Pin<Box<F>>,
+    task: Arc<TaskActivity>,
+}
+
+impl<F: Future> Tracked<F> {
+    fn new(activity: Arc<Activity>, future: F) -> Self {
+        // The task is busy until it is polled for the first time.
+        activity.busy();
+        Self {
+            future: Box::pin(future),
+            task: Arc::new(TaskActivity {
+                activity,
+                state: AtomicU8::new(NOTIFIED),
+            }),
+        }
+    }
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let task = self.task.clone();
+        if task.state.swap(POLLING, Ordering::AcqRel) == IDLE {
+            task.activity.busy();
+        }
+        let waker = Waker::from(Arc::new(TrackingWaker {
+            task: task.clone(),
+            waker: cx.waker().clone(),
+        }));
+        let previous =
+            CURRENT_ACTIVITY.with(|current| current.replace(Some(task.activity.clone())));
+        let poll = self.future.as_mut().poll(&mut Context::from_waker(&waker));
+        CURRENT_ACTIVITY.with(|current| *current.borrow_mut() = previous);
+        match poll {
+            Poll::Ready(output) => {
+                if task.state.swap(DONE, Ordering::AcqRel) != DONE {
+                    task.activity.idle();
+                }
+                Poll::Ready(output)
+            }
+            Poll::Pending => {
+                // Unless the task was woken up in the meantime, it is idle now.
+                if task
+                    .state
+                    .compare_exchange(POLLING, IDLE, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+                {
+                    task.activity.idle();
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<F> Drop for Tracked<F> {
+    fn drop(&mut self) {
+        match self.task.state.swap(DONE, Ordering::AcqRel) {
+            IDLE | DONE => {}
+            _ => self.task.activity.idle(),
+        }
+    }
+}
+
+/// Spawns a task, which is tracked by the activity of the given or the current pipeline, if it
+/// is executed step-wise. The current pipeline is the one of the tracked task which is being
+/// polled on this thread, e.g. an actor which sends a request.
+pub(crate) fn spawn_tracked<F>(
+    activity: Option<Arc<Activity>>,
+    future: F,
+) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match activity.or_else(current_activity) {
+        Some(activity) => tokio::spawn(Tracked::new(activity, future)),
+        None => tokio::spawn(future),
+    }
+}
+
+/// Spawns a task on the given runtime, which is tracked like in [spawn_tracked()].
+pub(crate) fn spawn_tracked_on<F>(
+    handle: &tokio::runtime::Handle,
+    activity: Option<Arc<Activity>>,
+    future: F,
+) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match activity.or_else(current_activity) {
+        Some(activity) => handle.spawn(Tracked::new(activity, future)),
+        None => handle.spawn(future),
+    }
+}
+
+/// Spawns a task onto the current [tokio::task::LocalSet], which is tracked like in
+/// [spawn_tracked()].
+pub(crate) fn spawn_local_tracked<F>(
+    activity: Option<Arc<Activity>>,
+    future: F,
+) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    match activity.or_else(current_activity) {
+        Some(activity) => tokio::task::spawn_local(Tracked::new(activity, future)),
+        None => tokio::task::spawn_local(future),
+    }
+}
+
+/// Activity of the pipeline whose tracked task is being polled on this thread, if any.
+pub(crate) fn current_activity() -> Option<Arc<Activity>> {
+    CURRENT_ACTIVITY.with(|current| current.borrow().clone())
+}
@@ -0,0 +1,156 @@
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+use tracing::warn;
+
+/// How the pipeline reacts if an actor panics, see [ActorBuilder::set_supervision()].
+///
+/// Supervision relies on unwinding, hence it has no effect if panics abort the process, e.g. if
+/// `panic = 'abort'` is set in the build profile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SupervisionStrategy {
+    /// The panic fails the actor, which is reported once the pipeline stopped, see
+    /// [PipelineError].
+    #[default]
+    Escalate,
+    /// Only the panicked actor is restarted, and the message or request which caused the panic is
+    /// dropped. The actor continues from its last checkpoint, or from its initial state if there
+    /// is none.
+    OneForOne {
+        /// Maximal number of restarts per run, after which the panic is escalated.
+        max_restarts: usize,
+        /// If set, the state is checkpointed after every n-th message or request which was
+        /// processed successfully.
+        checkpoint_interval: Option<u64>,
+    },
+}
+
+/// Returns the message of a panic, if it is a string.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> Option<String> {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        Some(s.to_string())
+    } else {
+        payload.downcast_ref::<String>().cloned()
+    }
+}
+
+/// Restarts an actor from its initial or checkpointed state if its handlers panic, according to
+/// its [SupervisionStrategy].
+pub(crate) struct Supervisor<State> {
+    strategy: SupervisionStrategy,
+    clone_state: Option<fn(&State) -> State>,
+    initial_state: Option<State>,
+    checkpoint: Option<State>,
+    num_since_checkpoint: u64,
+    /// Number of restarts during the current run.
+    pub(crate) num_restarts: usize,
+}
+
+impl<State> Supervisor<State> {
+    pub(crate) fn new() -> Self {
+        Self {
+            strategy: SupervisionStrategy::default(),
+            clone_state: None,
+            initial_state: None,
+            checkpoint: None,
+            num_since_checkpoint: 0,
+            num_restarts: 0,
+        }
+    }
+
+    /// Keeps the given state to restart from.
+    pub(crate) fn keep_initial_state(&mut self, initial_state: State)
+    where
+        State: Clone,
+    {
+        self.clone_state = Some(State::clone);
+        self.initial_state = Some(initial_state);
+    }
+
+    pub(crate) fn set_strategy(&mut self, strategy: SupervisionStrategy, initial_state: State)
+    where
+        State: Clone,
+    {
+        self.strategy = strategy;
+        self.keep_initial_state(initial_state);
+    }
+
+    /// Returns a copy of the initial state, if it was kept.
+    pub(crate) fn initial_state(&self) -> Option<State> {
+        Some((self.clone_state?)(self.initial_state.as_ref()?))
+    }
+
+    /// Called when the actor starts a new run.
+    pub(crate) fn start_run(&mut self) {
+        self.checkpoint = None;
+        self.num_since_checkpoint = 0;
+        self.num_restarts = 0;
+    }
+
+    /// Calls the given handler. If it panics, the actor is restarted and None is returned, or
+    /// the panic is resumed if the actor is not to be restarted.
+    pub(crate) fn supervise<Ret>(
+        &mut self,
+        actor_name: &str,
+        state: &mut State,
+        handler: impl FnOnce(&mut State) -> Ret,
+    ) -> Option<Ret> {
+        let SupervisionStrategy::OneForOne {
+            checkpoint_interval,
+            ..
+        } = self.strategy
+        else {
+            return Some(handler(state));
+        };
+        match std::panic::catch_unwind(AssertUnwindSafe(|| handler(state))) {
+            Ok(ret) => {
+                if let (Some(interval), Some(clone_state)) = (checkpoint_interval, self.clone_state)
+                {
+                    self.num_since_checkpoint += 1;
+                    if self.num_since_checkpoint >= interval {
+                        self.checkpoint = Some(clone_state(state));
+                        self.num_since_checkpoint = 0;
+                    }
+                }
+                Some(ret)
+            }
+            Err(payload) => {
+                let reason = panic_message(payload.as_ref()).unwrap_or_default();
+                if !self.restart(actor_name, state, &reason) {
+                    std::panic::resume_unwind(payload);
+                }
+                None
+            }
+        }
+    }
+
+    /// Restarts the actor after it crashed for the given reason. Returns false if the actor is
+    /// not to be restarted, i.e. the crash is to be escalated.
+    pub(crate) fn restart(&mut self, actor_name: &str, state: &mut State, reason: &str) -> bool {
+        let SupervisionStrategy::OneForOne { max_restarts, .. } = self.strategy else {
+            return false;
+        };
+        let Some(clone_state) = self.clone_state else {
+            return false;
+        };
+        if self.num_restarts >= max_restarts {
+            warn!(
+                "{}: crashed after {} restarts, escalating: {}",
+                actor_name, self.num_restarts, reason
+            );
+            return false;
+        }
+        let (restored, from) = match (&self.checkpoint, &self.initial_state) {
+            (Some(checkpoint), _) => (checkpoint, "checkpoint"),
+            (None, Some(initial_state)) => (initial_state, "initial state"),
+            (None, None) => return false,
+        };
+        *state = clone_state(restored);
+        self.num_since_checkpoint = 0;
+        self.num_restarts += 1;
+        warn!(
+            "{}: crashed, restarted from {} ({} of {}): {}",
+            actor_name, from, self.num_restarts, max_restarts, reason
+        );
+        true
+    }
+}
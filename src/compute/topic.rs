@@ -0,0 +1,202 @@
+use crate::prelude::*;
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+type Subscribers<T> = Arc<RwLock<Vec<Arc<dyn IsGenericConnection<T> + Send + Sync>>>>;
+
+/// Connection from a publishing outbound channel to all subscribers of a topic.
+///
+/// The list of subscribers is shared between all publishers of the topic, hence publishers and
+/// subscribers can be registered in any order during configuration.
+pub(crate) struct TopicConnection<T> {
+    subscribers: Subscribers<T>,
+}
+
+impl<T: Clone + Send + Sync + 'static> IsGenericConnection<T> for TopicConnection<T> {
+    fn send_impl(&self, msg: T) {
+        let subscribers = self.subscribers.read().unwrap();
+        if let Some((last, others)) = subscribers.split_last() {
+            for subscriber in others.iter() {
+                subscriber.send_impl(msg.clone());
+            }
+            last.send_impl(msg);
+        }
+    }
+}
+
+struct TopicEntry {
+    type_id: TypeId,
+    type_name: &'static str,
+    subscribers: Box<dyn Any + Send + Sync>,
+    publishers: Vec<(String, String)>,
+    subscriber_names: Vec<(String, String)>,
+}
+
+/// Registry of all topics of the pipeline.
+pub(crate) struct TopicRegistry {
+    topics: HashMap<String, TopicEntry>,
+}
+
+impl TopicRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            topics: HashMap::new(),
+        }
+    }
+
+    fn entry<T: Send + Sync + 'static>(&mut self, topic: &str) -> &mut TopicEntry {
+        let entry = self
+            .topics
+            .entry(topic.to_owned())
+            .or_insert_with(|| TopicEntry {
+                type_id: TypeId::of::<T>(),
+                type_name: std::any::type_name::<T>(),
+                subscribers: Box::new(Subscribers::<T>::default()),
+                publishers: vec![],
+                subscriber_names: vec![],
+            });
+        assert_eq!(
+            entry.type_id,
+            TypeId::of::<T>(),
+            "oh no, topic {} has type {}, but {} was requested",
+            topic,
+            entry.type_name,
+            std::any::type_name::<T>()
+        );
+        entry
+    }
+
+    fn subscribers<T: Send + Sync + 'static>(entry: &TopicEntry) -> Subscribers<T> {
+        entry
+            .subscribers
+            .downcast_ref::<Subscribers<T>>()
+            .unwrap()
+            .clone()
+    }
+
+    /// Registers a publisher and returns the connection to all subscribers as well as the
+    /// (actor, inbound) names of the subscribers registered so far.
+    pub(crate) fn publish<T: Send + Sync + 'static>(
+        &mut self,
+        topic: &str,
+        actor_name: &str,
+        outbound_name: &str,
+    ) -> (TopicConnection<T>, Vec<(String, String)>) {
+        let entry = self.entry::<T>(topic);
+        entry
+            .publishers
+            .push((actor_name.to_owned(), outbound_name.to_owned()));
+        (
+            TopicConnection {
+                subscribers: Self::subscribers::<T>(entry),
+            },
+            entry.subscriber_names.clone(),
+        )
+    }
+
+    /// Registers a subscriber and returns the (actor, outbound) names of the publishers registered
+    /// so far.
+    pub(crate) fn subscribe<T: Send + Sync + 'static>(
+        &mut self,
+        topic: &str,
+        actor_name: &str,
+        inbound_name: &str,
+        connection: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    ) -> Vec<(String, String)> {
+        let entry = self.entry::<T>(topic);
+        Self::subscribers::<T>(entry)
+            .write()
+            .unwrap()
+            .push(connection);
+        entry
+            .subscriber_names
+            .push((actor_name.to_owned(), inbound_name.to_owned()));
+        entry.publishers.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compute::topology::Connection;
+    use crate::example_actors::moving_average::MovingAverage;
+    use crate::example_actors::moving_average::MovingAverageProp;
+    use crate::example_actors::moving_average::MovingAverageState;
+    use crate::prelude::*;
+    use crate::testing::ActorHarness;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    /// Counts the messages per connection, keyed by "actor.channel -> actor.channel".
+    #[derive(Clone, Default)]
+    struct CountingMiddleware {
+        counts: Arc<Mutex<HashMap<String, usize>>>,
+    }
+
+    impl CountingMiddleware {
+        fn count(&self, from: &str, to: &str) -> usize {
+            let key = format!("{} -> {}", from, to);
+            self.counts.lock().unwrap().get(&key).copied().unwrap_or(0)
+        }
+    }
+
+    impl IsConnectionMiddleware for CountingMiddleware {
+        fn on_message(&self, connection: &Connection, _msg: &dyn std::fmt::Debug) -> bool {
+            let key = format!(
+                "{}.{} -> {}.{}",
+                connection.from_actor, connection.from, connection.to_actor, connection.to
+            );
+            *self.counts.lock().unwrap().entry(key).or_default() += 1;
+            true
+        }
+    }
+
+    fn moving_average(context: &mut Hollywood) -> MovingAverage {
+        MovingAverage::from_prop_and_state(
+            context,
+            MovingAverageProp {
+                alpha: 0.5,
+                timeout: 10.0,
+            },
+            MovingAverageState::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn middleware_sees_topic_messages() {
+        let middleware = CountingMiddleware::default();
+        let mut harness = ActorHarness::new();
+        harness
+            .context()
+            .add_connection_middleware(middleware.clone());
+        let mut publisher = moving_average(harness.context());
+        let mut first = moving_average(harness.context());
+        let mut second = moving_average(harness.context());
+        let context = harness.context();
+        context.subscribe("average", &mut first.inbound.value);
+        context.publish("average", &mut publisher.outbound.average);
+        context.subscribe("average", &mut second.inbound.value);
+        let value = publisher.inbound.value.injector();
+        harness.start().await;
+
+        for i in 0..3 {
+            harness.send(&value, i as f64).await;
+        }
+        let published = format!(
+            "{}.{}",
+            publisher.actor_name, publisher.outbound.average.name
+        );
+        assert_eq!(middleware.count(&published, "topic.average"), 3);
+        for subscriber in [&first, &second] {
+            let subscribed = format!(
+                "{}.{}",
+                subscriber.actor_name, subscriber.inbound.value.name
+            );
+            assert_eq!(middleware.count("topic.average", &subscribed), 3);
+        }
+        harness.stop().await.unwrap();
+    }
+}
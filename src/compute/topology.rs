@@ -1,7 +1,22 @@
+#[cfg(feature = "flow_graph")]
 use crate::introspect::flow_graph::FlowGraph;
+use crate::introspect::graph_export;
 use crate::prelude::*;
 use petgraph::stable_graph::StableDiGraph;
 use std::collections::BTreeSet;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// Outbound channel which admits a single connection, see [OutboundChannel::connect_exclusive()]
+/// and [OutboundChannel::connect_fused()].
+pub(crate) struct ExclusiveOutbound {
+    pub actor: String,
+    pub outbound: String,
+    pub fused: bool,
+    /// number of connections made or attempted
+    pub fan_out: Arc<AtomicUsize>,
+}
 
 // A node in a compute graph.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -22,8 +37,152 @@ pub struct Connection {
     pub to: String,
     /// name of the inbound channel
     pub to_actor: String,
+    /// Rust type name of the channel payload, as given by [std::any::type_name()]
+    pub type_name: &'static str,
+}
+
+impl Connection {
+    /// Type name of the channel payload without module paths, e.g. `Vec<f64>` instead of
+    /// `alloc::vec::Vec<f64>`.
+    pub fn short_type_name(&self) -> String {
+        short_type_name(self.type_name)
+    }
+}
+
+/// Strips the module paths of all types within the given type name.
+pub(crate) fn short_type_name(type_name: &str) -> String {
+    let mut short = String::with_capacity(type_name.len());
+    let mut segment = String::new();
+    for c in type_name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+    short
+}
+
+/// Misconfiguration of the pipeline topology, see [TopologyError].
+#[derive(Clone, Debug)]
+pub enum TopologyIssue {
+    /// An outbound channel is connected to an inbound channel of the same actor.
+    SelfConnection(Connection),
+    /// An outbound channel is connected to the same inbound channel more than once, hence each
+    /// message would be received several times.
+    DuplicateConnection(Connection),
+    /// A connection refers to an actor which is not part of the context, e.g. since the actor was
+    /// created by a different context.
+    UnknownActor {
+        /// name of the unknown actor
+        actor: String,
+        /// the connection
+        connection: Connection,
+    },
+    /// A fused outbound channel is not connected to exactly one inbound channel, see
+    /// [OutboundChannel::connect_fused()].
+    FusedFanOut {
+        /// name of the actor that owns the outbound channel
+        actor: String,
+        /// name of the outbound channel
+        outbound: String,
+        /// number of connections of the outbound channel
+        fan_out: usize,
+    },
+    /// An exclusive outbound channel is not connected to exactly one inbound channel, see
+    /// [OutboundChannel::connect_exclusive()].
+    ExclusiveFanOut {
+        /// name of the actor that owns the outbound channel
+        actor: String,
+        /// name of the outbound channel
+        outbound: String,
+        /// number of connections of the outbound channel
+        fan_out: usize,
+    },
+    /// The graph is cyclic, and the given actor is part of a cycle.
+    Cycle {
+        /// name of the actor
+        actor: String,
+    },
+}
+
+impl std::fmt::Display for TopologyIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let connection = |f: &mut std::fmt::Formatter<'_>, c: &Connection| {
+            write!(
+                f,
+                "{}.{} -> {}.{} ({})",
+                c.from_actor,
+                c.from,
+                c.to_actor,
+                c.to,
+                c.short_type_name()
+            )
+        };
+        match self {
+            TopologyIssue::SelfConnection(c) => {
+                write!(f, "actor {} is connected to itself: ", c.from_actor)?;
+                connection(f, c)
+            }
+            TopologyIssue::DuplicateConnection(c) => {
+                write!(f, "channels are connected more than once: ")?;
+                connection(f, c)
+            }
+            TopologyIssue::UnknownActor {
+                actor,
+                connection: c,
+            } => {
+                write!(f, "actor {} is not part of the context: ", actor)?;
+                connection(f, c)
+            }
+            TopologyIssue::FusedFanOut {
+                actor,
+                outbound,
+                fan_out,
+            } => write!(
+                f,
+                "fused outbound {}.{} must be connected to exactly one inbound channel, but has {}",
+                actor, outbound, fan_out
+            ),
+            TopologyIssue::ExclusiveFanOut {
+                actor,
+                outbound,
+                fan_out,
+            } => write!(
+                f,
+                "exclusive outbound {}.{} must be connected to exactly one inbound channel, but \
+                 has {}",
+                actor, outbound, fan_out
+            ),
+            TopologyIssue::Cycle { actor } => write!(f, "actor {} is part of a cycle", actor),
+        }
+    }
+}
+
+/// Error returned if the pipeline topology is misconfigured, see [Hollywood::try_configure()].
+///
+/// It lists all issues found, rather than only the first one.
+#[derive(Clone, Debug)]
+pub struct TopologyError {
+    /// The issues found.
+    pub issues: Vec<TopologyIssue>,
+}
+
+impl std::fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} topology issue(s):", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue)?;
+        }
+        Ok(())
+    }
 }
 
+impl std::error::Error for TopologyError {}
+
 pub(crate) type HollywoodNodeIndex = petgraph::stable_graph::NodeIndex<u32>;
 
 #[derive(Clone, Debug)]
@@ -75,6 +234,11 @@ impl UniqueNodeIdxNamePairs {
 pub(crate) struct Topology {
     pub(crate) graph: StableDiGraph<ActorNode, Connection, u32>,
     pub(crate) unique_idx_name_pairs: UniqueNodeIdxNamePairs,
+    /// Request-reply connections, from out-request to in-request channels. They are not part of
+    /// the graph, since a request and its reply form a cycle.
+    pub(crate) request_connections: Vec<Connection>,
+    /// Issues found while connecting channels, see [Topology::validate()].
+    pub(crate) issues: Vec<TopologyIssue>,
 }
 
 impl Topology {
@@ -82,6 +246,8 @@ impl Topology {
         Topology {
             graph: StableDiGraph::new(),
             unique_idx_name_pairs: UniqueNodeIdxNamePairs::new(),
+            request_connections: vec![],
+            issues: vec![],
         }
     }
 
@@ -112,6 +278,21 @@ impl Topology {
         unique_name
     }
 
+    pub(crate) fn add_unique_label(&mut self, label: &str) -> String {
+        let node_idx = self.graph.add_node(ActorNode {
+            name: label.to_owned(),
+            inbound: std::collections::BTreeSet::new(),
+            outbound: std::collections::BTreeSet::new(),
+        });
+        if let Err(err) = self
+            .unique_idx_name_pairs
+            .try_insert(label.to_owned(), node_idx)
+        {
+            panic!("oh no, actor label is not unique: {}", err);
+        }
+        label.to_owned()
+    }
+
     pub(crate) fn assert_unique_inbound_name(
         &mut self,
         unique_inbound_name: String,
@@ -145,44 +326,92 @@ impl Topology {
     }
 
     pub(crate) fn connect<
-        T0: Clone + std::fmt::Debug + Sync + Send + 'static,
-        T1: Clone + std::fmt::Debug + Sync + Send + 'static,
+        T0: std::fmt::Debug + Sync + Send + 'static,
+        T1: std::fmt::Debug + Sync + Send + 'static,
         M: IsInboundMessage,
     >(
         &mut self,
         outbound: &mut OutboundChannel<T0>,
         inbound: &mut InboundChannel<T1, M>,
-    ) {
-        let output_parent_idx = self
-            .unique_idx_name_pairs
-            .get_node_idx(&outbound.actor_name)
-            .unwrap();
-        let inbound_parent_idx = self
-            .unique_idx_name_pairs
-            .get_node_idx(&inbound.actor_name)
-            .unwrap();
-        assert_ne!(
-            output_parent_idx, inbound_parent_idx,
-            "oh no, outbound and inbound have same parent {} {}",
-            &outbound.actor_name, &inbound.actor_name
-        );
-        self.graph.add_edge(
-            output_parent_idx,
-            inbound_parent_idx,
-            Connection {
-                from_actor: outbound.actor_name.clone(),
-                from: outbound.name.clone(),
-                to_actor: inbound.actor_name.clone(),
-                to: inbound.name.clone(),
-            },
-        );
+    ) -> Option<Connection> {
+        self.connect_by_name(
+            &outbound.actor_name,
+            &outbound.name,
+            &inbound.actor_name,
+            &inbound.name,
+            std::any::type_name::<T0>(),
+        )
+    }
+
+    pub(crate) fn connect_by_name(
+        &mut self,
+        from_actor: &str,
+        from: &str,
+        to_actor: &str,
+        to: &str,
+        type_name: &'static str,
+    ) -> Option<Connection> {
+        let connection = Connection {
+            from_actor: from_actor.to_owned(),
+            from: from.to_owned(),
+            to_actor: to_actor.to_owned(),
+            to: to.to_owned(),
+            type_name,
+        };
+        // Misconfigured connections are rejected, i.e. neither added to the graph nor to the
+        // outbound channel, and reported by validate().
+        let mut parent_idx = |actor: &str| {
+            let idx = self.unique_idx_name_pairs.get_node_idx(actor);
+            if idx.is_none() {
+                self.issues.push(TopologyIssue::UnknownActor {
+                    actor: actor.to_owned(),
+                    connection: connection.clone(),
+                });
+            }
+            idx
+        };
+        let (Some(output_parent_idx), Some(inbound_parent_idx)) =
+            (parent_idx(from_actor), parent_idx(to_actor))
+        else {
+            return None;
+        };
+        if output_parent_idx == inbound_parent_idx {
+            self.issues.push(TopologyIssue::SelfConnection(connection));
+            return None;
+        }
+        if self
+            .graph
+            .edges_connecting(output_parent_idx, inbound_parent_idx)
+            .any(|edge| edge.weight().from == from && edge.weight().to == to)
+        {
+            self.issues
+                .push(TopologyIssue::DuplicateConnection(connection));
+            return None;
+        }
+        self.graph
+            .add_edge(output_parent_idx, inbound_parent_idx, connection.clone());
+        Some(connection)
     }
 
+    pub(crate) fn connect_request(&mut self, connection: Connection) {
+        self.request_connections.push(connection);
+    }
+
+    /// Number of connections of the given outbound channel.
+    pub(crate) fn fan_out(&self, actor_name: &str, outbound_name: &str) -> usize {
+        self.graph
+            .edge_weights()
+            .filter(|c| c.from_actor == actor_name && c.from == outbound_name)
+            .count()
+    }
+
+    #[cfg(feature = "flow_graph")]
     pub(crate) fn start_nodes(&self) -> Vec<ActorNode> {
         let start_nodes = self.graph.externals(petgraph::Direction::Incoming);
         start_nodes.map(|n| self.graph[n].clone()).collect()
     }
 
+    #[cfg(feature = "flow_graph")]
     pub(crate) fn pop_start_nodes(&mut self) -> Vec<ActorNode> {
         let start_nodes = self.start_nodes();
         for node in &start_nodes {
@@ -192,13 +421,282 @@ impl Topology {
         start_nodes
     }
 
-    pub(crate) fn analyze_graph_topology(&self) {
-        let is_cyclic = petgraph::algo::is_cyclic_directed(&self.graph);
-        assert!(!is_cyclic, "oh no, graph is cyclic: {}", is_cyclic);
+    /// Groups the actors into waves to be stopped one after another during a graceful shutdown,
+    /// such that all upstream actors of an actor are part of earlier waves.
+    ///
+    /// Actors without any connections, e.g. bridges to external code, form the last wave.
+    pub(crate) fn shutdown_waves(&self) -> Vec<Vec<String>> {
+        let order = petgraph::algo::toposort(&self.graph, None)
+            .expect("oh no, graph is cyclic, which is checked during configuration");
+        let mut depths = std::collections::HashMap::new();
+        let mut waves: Vec<Vec<String>> = vec![];
+        let mut isolated = vec![];
+        for node_idx in order {
+            let node = &self.graph[node_idx];
+            let mut upstream = self
+                .graph
+                .neighbors_directed(node_idx, petgraph::Direction::Incoming)
+                .peekable();
+            if upstream.peek().is_none()
+                && self
+                    .graph
+                    .neighbors_directed(node_idx, petgraph::Direction::Outgoing)
+                    .next()
+                    .is_none()
+            {
+                isolated.push(node.name.clone());
+                continue;
+            }
+            let depth = upstream
+                .map(|upstream_idx| depths[&upstream_idx] + 1)
+                .max()
+                .unwrap_or(0);
+            depths.insert(node_idx, depth);
+            if waves.len() <= depth {
+                waves.resize(depth + 1, vec![]);
+            }
+            waves[depth].push(node.name.clone());
+        }
+        if !isolated.is_empty() {
+            waves.push(isolated);
+        }
+        waves
+    }
+
+    /// Checks the topology for misconfigurations, i.e. the issues found while connecting
+    /// channels, exclusive or fused outbound channels with a fan-out other than one, and cycles.
+    pub(crate) fn validate(
+        &self,
+        exclusive_outbounds: &[ExclusiveOutbound],
+    ) -> Result<(), TopologyError> {
+        let mut issues = self.issues.clone();
+        for exclusive in exclusive_outbounds {
+            let fan_out = exclusive.fan_out.load(Ordering::Relaxed);
+            if fan_out == 1 {
+                continue;
+            }
+            let actor = exclusive.actor.clone();
+            let outbound = exclusive.outbound.clone();
+            issues.push(if exclusive.fused {
+                TopologyIssue::FusedFanOut {
+                    actor,
+                    outbound,
+                    fan_out,
+                }
+            } else {
+                TopologyIssue::ExclusiveFanOut {
+                    actor,
+                    outbound,
+                    fan_out,
+                }
+            });
+        }
+        if let Err(cycle) = petgraph::algo::toposort(&self.graph, None) {
+            issues.push(TopologyIssue::Cycle {
+                actor: self.graph[cycle.node_id()].name.clone(),
+            });
+        }
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(TopologyError { issues })
+        }
     }
 
+    /// Prints the flow graph, followed by the list of connections with the types of the channels,
+    /// since the channel names within the flow graph might be truncated.
+    ///
+    /// Without the `flow_graph` feature, only the list of connections is printed.
     pub fn print_flow_graph(&self) {
-        let flow_graph = FlowGraph::new(self);
-        flow_graph.print();
+        #[cfg(feature = "flow_graph")]
+        println!("{}", FlowGraph::new(self).canvas().frame());
+        for connection in self.graph.edge_weights() {
+            println!(
+                "  {}.{} -> {}.{}: {}",
+                connection.from_actor,
+                connection.from,
+                connection.to_actor,
+                connection.to,
+                connection.short_type_name()
+            );
+        }
+    }
+
+    pub(crate) fn to_dot(&self) -> String {
+        graph_export::to_dot(self)
+    }
+
+    pub(crate) fn to_mermaid(&self) -> String {
+        graph_export::to_mermaid(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::Map;
+    use crate::prelude::*;
+
+    fn issues(callback: &mut dyn FnMut(&mut Hollywood)) -> Vec<TopologyIssue> {
+        match Hollywood::try_configure(callback) {
+            Ok(_) => vec![],
+            Err(err) => err.issues,
+        }
+    }
+
+    #[test]
+    fn valid_topology() {
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut b = Map::<f64, f64>::from_fn(context, |x| x);
+            a.outbound.value.connect(context, &mut b.inbound.value);
+        });
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn duplicate_connection() {
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut b = Map::<f64, f64>::from_fn(context, |x| x);
+            a.outbound.value.connect(context, &mut b.inbound.value);
+            a.outbound.value.connect(context, &mut b.inbound.value);
+        });
+        assert!(
+            matches!(
+                issues.as_slice(),
+                [TopologyIssue::DuplicateConnection(c)] if c.from_actor == "Map_0" && c.to_actor == "Map_1"
+            ),
+            "{:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn self_connection() {
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            a.outbound.value.connect(context, &mut a.inbound.value);
+        });
+        assert!(
+            matches!(issues.as_slice(), [TopologyIssue::SelfConnection(c)] if c.from == "value"),
+            "{:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn unknown_actor() {
+        // Names are unique per context only, hence the foreign actor is named Map_1 while the
+        // actor of the configured context is named Map_0.
+        let mut other = Hollywood::new();
+        let _ = Map::<f64, f64>::from_fn(&mut other, |x| x);
+        let mut foreign = Map::<f64, f64>::from_fn(&mut other, |x| x);
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            foreign
+                .outbound
+                .value
+                .connect(context, &mut a.inbound.value);
+        });
+        assert!(
+            matches!(
+                issues.as_slice(),
+                [TopologyIssue::UnknownActor { actor, .. }] if actor == "Map_1"
+            ),
+            "{:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn fused_fan_out() {
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut b = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut c = Map::<f64, f64>::from_fn(context, |x| x);
+            context.enable_fused_dispatch(&b.actor_name);
+            a.outbound
+                .value
+                .connect_fused(context, &mut b.inbound.value);
+            a.outbound.value.connect(context, &mut c.inbound.value);
+        });
+        assert!(
+            matches!(
+                issues.as_slice(),
+                [TopologyIssue::FusedFanOut { fan_out: 2, .. }]
+            ),
+            "{:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn exclusive_fan_out() {
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut b = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut c = Map::<f64, f64>::from_fn(context, |x| x);
+            a.outbound
+                .value
+                .connect_exclusive(context, &mut b.inbound.value);
+            a.outbound.value.connect(context, &mut c.inbound.value);
+        });
+        assert!(
+            matches!(
+                issues.as_slice(),
+                [TopologyIssue::ExclusiveFanOut { fan_out: 2, .. }]
+            ),
+            "{:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn exclusive_after_external_connection() {
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut b = Map::<f64, f64>::from_fn(context, |x| x);
+            let _subscriber = a.outbound.value.subscriber(context);
+            a.outbound
+                .value
+                .connect_exclusive(context, &mut b.inbound.value);
+        });
+        assert!(
+            matches!(
+                issues.as_slice(),
+                [TopologyIssue::ExclusiveFanOut { fan_out: 2, .. }]
+            ),
+            "{:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn cycle() {
+        let issues = issues(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut b = Map::<f64, f64>::from_fn(context, |x| x);
+            a.outbound.value.connect(context, &mut b.inbound.value);
+            b.outbound.value.connect(context, &mut a.inbound.value);
+        });
+        assert!(
+            matches!(issues.as_slice(), [TopologyIssue::Cycle { .. }]),
+            "{:?}",
+            issues
+        );
+    }
+
+    #[test]
+    fn all_issues_are_listed() {
+        let err = Hollywood::try_configure(&mut |context| {
+            let mut a = Map::<f64, f64>::from_fn(context, |x| x);
+            let mut b = Map::<f64, f64>::from_fn(context, |x| x);
+            a.outbound.value.connect(context, &mut a.inbound.value);
+            b.outbound.value.connect(context, &mut b.inbound.value);
+        })
+        .err()
+        .unwrap();
+        assert_eq!(err.issues.len(), 2);
+        assert!(err.to_string().starts_with("2 topology issue(s):"));
     }
 }
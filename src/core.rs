@@ -6,23 +6,93 @@ pub mod actor;
 /// Actor builder
 pub mod actor_builder;
 
+/// Address of an actor's in-request channel
+pub mod address;
+
+/// Type-erased messages
+pub mod any_message;
+
+/// Bounded connections with a backpressure policy
+pub mod backpressure;
+
+/// Fault injection into running actors for resilience drills
+pub mod chaos;
+
+/// Error handling policies of fallible message handlers
+pub mod error_policy;
+
+/// Pluggable clocks, to drive time-based actors in real or simulated time
+pub mod clock;
+
+/// Pool of large, reusable buffers which are recycled through the pipeline
+pub mod frame_pool;
+
+/// Fused connections which invoke the downstream message handler inline
+pub mod fused;
+
 /// Inbound
 pub mod inbound;
 
+/// Bridges from tokio broadcast and watch channels into inbound channels
+pub mod inbound_bridge;
+
+/// Injector to push messages into inbound channels from external (non-actor) code
+pub mod inbound_injector;
+
 /// Inbound requests
 pub mod in_request;
 
+/// Executable invariants of outbound messages
+pub mod invariant;
+
+/// Automatic load shedding under overload
+pub mod load_shedding;
+
 /// Outbound
 pub mod outbound;
 
+/// Bridges from outbound channels into sinks and writers
+pub mod outbound_sink;
+
+/// Subscriber to consume outbound channels from external (non-actor) code
+pub mod outbound_subscriber;
+
 /// Outbound requests
 pub mod out_request;
 
+/// Cross-channel ordering constraints
+pub mod channel_ordering;
+
+/// Liveness statistics of inbound channels
+pub mod channel_stats;
+
+/// Taps to inspect outbound channels at runtime
+pub mod channel_tap;
+
+/// Snapshots of actor states, to resume a pipeline after a restart
+#[cfg(feature = "serde")]
+pub mod checkpoint;
+
 /// Connection
 pub mod connection;
 
+/// Correlation ids which follow messages through the pipeline
+pub mod correlation;
+
+/// Request client for external (non-actor) code
+pub mod request_client;
+
+/// Requests which are replied to with a stream of items
+pub mod request_stream;
+
 /// Run
 pub mod runner;
 
+/// Timestamps tagged with their clock, and stamped values
+pub mod stamped;
+
+/// Schema of actor states, to detect stale states between pipeline versions
+pub mod state_schema;
+
 /// State
 pub mod value;
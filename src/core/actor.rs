@@ -1,7 +1,29 @@
+use crate::core::backpressure::BoundedInbounds;
+use crate::core::channel_ordering::OrderingGate;
+use crate::core::chaos::next_chaos_fault;
+use crate::core::clock::sleep_until_deadline;
+use crate::core::clock::ClockTimer;
+use crate::core::correlation::CorrelationScope;
+use crate::core::correlation::Correlations;
+use crate::core::error_policy::ActorErrorHandler;
+use crate::core::error_policy::ErrorSink;
+use crate::core::fused::run_fused;
+use crate::core::fused::ActorCore;
+use crate::core::fused::FusedSlot;
+use crate::core::fused::IsFusedDispatch;
+use crate::core::load_shedding::LoadShedder;
+use crate::core::out_request::discard_requests;
+use crate::introspect::metrics::ActorMetrics;
 use crate::prelude::*;
 use async_trait::async_trait;
+use std::any::Any;
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use tokio::select;
+use tracing::debug;
+use tracing::debug_span;
 
 /// A generic actor in the hollywood compute graph framework.
 ///
@@ -36,6 +58,66 @@ pub type Actor<Prop, Inbound, InRequest, State, Outbound, OutRequest> = GenericA
     DefaultRunner<Prop, Inbound, InRequest, State, Outbound, OutRequest>,
 >;
 
+impl<Prop, Inbound, InRequest, State, Outbound: IsOutboundHub, OutRequest, Run>
+    GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run>
+{
+    /// Returns a typed handle to read the state of this actor from the pipeline, see
+    /// [ActorStateHandle].
+    pub fn state_handle(&self) -> ActorStateHandle<State> {
+        ActorStateHandle {
+            actor_name: self.actor_name.clone(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Typed handle to the state of an actor, e.g. to compute results from the final state once the
+/// pipeline finished:
+///
+/// ``` ignore
+/// let mut average = None;
+/// let pipeline = Hollywood::configure(&mut |context| {
+///     let moving_average = MovingAverage::from_prop_and_state(context, prop, state);
+///     average = Some(moving_average.state_handle());
+///     // ...
+/// });
+/// let pipeline = pipeline.run().await?;
+/// let state = pipeline.actor_state(&average.unwrap()).unwrap();
+/// ```
+///
+/// The handle is obtained during configuration using [GenericActor::state_handle()], and read
+/// using [Pipeline::actor_state()]. The state is not accessible while the actor is running, i.e.
+/// it is read from the configured pipeline (the initial state) or the finished pipeline (the
+/// state at the end of the run).
+pub struct ActorStateHandle<State> {
+    actor_name: String,
+    phantom: std::marker::PhantomData<fn() -> State>,
+}
+
+impl<State> Clone for ActorStateHandle<State> {
+    fn clone(&self) -> Self {
+        Self {
+            actor_name: self.actor_name.clone(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<State> std::fmt::Debug for ActorStateHandle<State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActorStateHandle")
+            .field("actor_name", &self.actor_name)
+            .finish()
+    }
+}
+
+impl<State> ActorStateHandle<State> {
+    /// Name of the actor.
+    pub fn actor_name(&self) -> &str {
+        &self.actor_name
+    }
+}
+
 /// New actor from properties and state.
 pub trait HasFromPropState<
     Prop,
@@ -53,6 +135,15 @@ pub trait HasFromPropState<
     /// generate a unique name.
     fn name_hint(prop: &Prop) -> String;
 
+    /// Semantic version of the actor type, as listed in the [PipelineManifest] of the pipeline.
+    /// It should be bumped whenever the behavior of the actor changes, e.g. to detect binaries
+    /// with incompatible actor implementations, see [SemVer].
+    ///
+    /// By default, the version is 0.0.0.
+    fn version() -> SemVer {
+        SemVer::default()
+    }
+
     /// Produces a new actor with the given state.
     ///
     /// Also, a dormant actor node is created added to the context.
@@ -62,17 +153,129 @@ pub trait HasFromPropState<
         initial_state: State,
     ) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run> {
         let actor_name = context.add_new_unique_name(Self::name_hint(&prop).to_string());
-        let out = Outbound::from_context_and_parent(context, &actor_name);
-        let mut builder = ActorBuilder::<Prop, State, Outbound, OutRequest, M, R>::new(
+        build_actor(
             context,
-            &actor_name,
+            actor_name,
             prop,
             initial_state,
-        );
-        let out_request = OutRequest::from_parent_and_sender(&actor_name, &builder.sender);
-        let inbound = Inbound::from_builder(&mut builder, &actor_name);
-        let in_request = InRequest::from_builder(&mut builder, &actor_name);
-        builder.build::<Inbound, InRequest, Run>(inbound, in_request, out, out_request, None)
+            Self::version(),
+            None,
+            |_| {},
+        )
+    }
+
+    /// Produces a new actor with the given state and a user-defined label as actor name.
+    ///
+    /// As opposed to [HasFromPropState::from_prop_and_state()], the label is used as is (no
+    /// suffix is added), so that the actor name is stable across runs, e.g. for logs, metrics,
+    /// monitoring dashboards and the flow graph.
+    ///
+    /// Panics if the label is already taken by another actor.
+    fn from_prop_and_state_named(
+        context: &mut Hollywood,
+        label: &str,
+        prop: Prop,
+        initial_state: State,
+    ) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run> {
+        let actor_name = context.add_unique_label(label);
+        build_actor(
+            context,
+            actor_name,
+            prop,
+            initial_state,
+            Self::version(),
+            None,
+            |_| {},
+        )
+    }
+
+    /// Produces a new actor with the given state and error policy, see
+    /// [ActorBuilder::set_error_policy()].
+    fn from_prop_and_state_with_error_policy(
+        context: &mut Hollywood,
+        prop: Prop,
+        initial_state: State,
+        policy: ErrorPolicy,
+    ) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run>
+    where
+        State: Clone + Send + Sync + 'static,
+    {
+        let actor_name = context.add_new_unique_name(Self::name_hint(&prop).to_string());
+        build_actor(
+            context,
+            actor_name,
+            prop,
+            initial_state,
+            Self::version(),
+            None,
+            |builder| builder.set_error_policy(policy),
+        )
+    }
+
+    /// Produces a new actor with the given state and supervision strategy, see
+    /// [ActorBuilder::set_supervision()].
+    fn from_prop_and_state_supervised(
+        context: &mut Hollywood,
+        prop: Prop,
+        initial_state: State,
+        strategy: SupervisionStrategy,
+    ) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run>
+    where
+        State: Clone + Send + Sync + 'static,
+    {
+        let actor_name = context.add_new_unique_name(Self::name_hint(&prop).to_string());
+        build_actor(
+            context,
+            actor_name,
+            prop,
+            initial_state,
+            Self::version(),
+            None,
+            |builder| builder.set_supervision(strategy),
+        )
+    }
+
+    /// Produces a new actor with the given state, which is executed as given by the placement,
+    /// see [ActorBuilder::set_placement()].
+    fn from_prop_and_state_with_placement(
+        context: &mut Hollywood,
+        prop: Prop,
+        initial_state: State,
+        placement: ActorPlacement,
+    ) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run> {
+        let actor_name = context.add_new_unique_name(Self::name_hint(&prop).to_string());
+        build_actor(
+            context,
+            actor_name,
+            prop,
+            initial_state,
+            Self::version(),
+            None,
+            |builder| builder.set_placement(placement),
+        )
+    }
+
+    /// Produces a new actor with the given state, which is part of the pipeline snapshots, see
+    /// [ActorBuilder::enable_checkpoint()].
+    #[cfg(feature = "serde")]
+    fn from_prop_and_state_checkpointed(
+        context: &mut Hollywood,
+        prop: Prop,
+        initial_state: State,
+    ) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run>
+    where
+        State: serde::Serialize + serde::de::DeserializeOwned + 'static,
+    {
+        let actor_name = context.add_new_unique_name(Self::name_hint(&prop).to_string());
+        build_actor(
+            context,
+            actor_name,
+            prop,
+            initial_state,
+            Self::version(),
+            None,
+            |builder| builder.enable_checkpoint(StateSchema::of::<State>()),
+        )
     }
 
     /// Called by when the pipeline on shutdown.
@@ -83,26 +286,52 @@ pub trait HasFromPropState<
         on_exit_fn: Box<dyn FnOnce() + Send + Sync + 'static>,
     ) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run> {
         let actor_name = context.add_new_unique_name(Self::name_hint(&prop).to_string());
-        let out = Outbound::from_context_and_parent(context, &actor_name);
-        let mut builder = ActorBuilder::<Prop, State, Outbound, OutRequest, M, R>::new(
+        build_actor(
             context,
-            &actor_name,
+            actor_name,
             prop,
             initial_state,
-        );
-        let out_request = OutRequest::from_parent_and_sender(&actor_name, &builder.sender);
-        let inbound = Inbound::from_builder(&mut builder, &actor_name);
-        let in_request = InRequest::from_builder(&mut builder, &actor_name);
-        builder.build::<Inbound, InRequest, Run>(
-            inbound,
-            in_request,
-            out,
-            out_request,
+            Self::version(),
             Some(on_exit_fn),
+            |_| {},
         )
     }
 }
 
+fn build_actor<
+    Prop,
+    Inbound: IsInboundHub<Prop, State, Outbound, OutRequest, M, R>,
+    InRequest: IsInRequestHub<Prop, State, Outbound, OutRequest, M, R>,
+    State,
+    Outbound: IsOutboundHub,
+    M: IsInboundMessage,
+    R: IsInRequestMessage,
+    OutRequest: IsOutRequestHub<M>,
+    Run: IsRunner<Prop, Inbound, InRequest, State, Outbound, OutRequest, M, R>,
+>(
+    context: &mut Hollywood,
+    actor_name: String,
+    prop: Prop,
+    initial_state: State,
+    version: SemVer,
+    on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+    configure: impl FnOnce(&mut ActorBuilder<Prop, State, Outbound, OutRequest, M, R>),
+) -> GenericActor<Prop, Inbound, InRequest, State, Outbound, OutRequest, Run> {
+    let out = Outbound::from_context_and_parent(context, &actor_name);
+    let mut builder = ActorBuilder::<Prop, State, Outbound, OutRequest, M, R>::new(
+        context,
+        &actor_name,
+        prop,
+        initial_state,
+    );
+    builder.set_version(version);
+    configure(&mut builder);
+    let out_request = OutRequest::from_parent_and_sender(&actor_name, &builder.sender);
+    let inbound = Inbound::from_builder(&mut builder, &actor_name);
+    let in_request = InRequest::from_builder(&mut builder, &actor_name);
+    builder.build::<Inbound, InRequest, Run>(inbound, in_request, out, out_request, on_exit_fn)
+}
+
 /// Actor node of the pipeline. It is created by the [IsRunner::new_actor_node()] method.
 #[async_trait]
 pub trait IsActorNode {
@@ -120,12 +349,174 @@ pub trait IsActorNode {
     ///   * Outbound messages are produced by [HasOnMessage::on_message()] the method and sent to
     ///     the through the corresponding outbound channel to downstream actors.
     ///
+    /// The actor shall stop as soon as the `kill` watch channel is set to true (or its sender is
+    /// dropped). As opposed to a broadcast channel, a watch channel cannot lag, hence the kill
+    /// signal is never missed regardless of the number of actors in the pipeline.
+    ///
     /// Note: It is an async function which returns a future a completion handler. This method is
     /// not intended to be called directly but is called by the runtime of the pipeline.
-    async fn run(&mut self, kill: tokio::sync::broadcast::Receiver<()>);
+    async fn run(&mut self, kill: tokio::sync::watch::Receiver<bool>);
 
     /// on exit
     fn on_exit(&mut self);
+
+    /// Sets the run options of the actor. It is called by the pipeline right before
+    /// [IsActorNode::run()].
+    ///
+    /// The default implementation ignores the options.
+    fn set_run_options(&mut self, _options: ActorRunOptions) {}
+
+    /// Returns statistics about the message batches processed while the actor was running, if
+    /// available.
+    fn batch_stats(&self) -> Option<BatchStats> {
+        None
+    }
+
+    /// Returns the type-erased handle to invoke the message handlers of the actor inline, if
+    /// supported. See [OutboundChannel::connect_fused()] for details.
+    ///
+    /// The default implementation does not support fused dispatch.
+    fn fused_dispatcher(&self) -> Option<Box<dyn Any + Send>> {
+        None
+    }
+
+    /// Returns the number of times the actor was restarted while it was running, see
+    /// [SupervisionStrategy].
+    fn num_restarts(&self) -> usize {
+        0
+    }
+
+    /// Whether the actor keeps track of the correlation ids of its inbound messages, see
+    /// [Hollywood::enable_message_tracing()].
+    ///
+    /// The default implementation returns false, i.e. no correlation ids are recorded for the
+    /// messages sent to the actor.
+    fn traces_messages(&self) -> bool {
+        false
+    }
+
+    /// Calls `f` with the type-erased state of the actor, if it is accessible, e.g. to take a
+    /// snapshot of the pipeline. The state is not accessible while the actor is running.
+    ///
+    /// The default implementation does not call `f`.
+    fn inspect_state(&self, _f: &mut dyn FnMut(&dyn Any)) {}
+}
+
+/// Options which control how the pipeline executes an actor node.
+#[derive(Clone)]
+pub struct ActorRunOptions {
+    /// Maximal number of messages which are processed in a row before the actor yields back to
+    /// the tokio scheduler. This bounds the latency of other tasks on the same worker thread in
+    /// case of a large message backlog. Zero disables yielding.
+    pub yield_interval: usize,
+    pub(crate) load_shedder: Option<Arc<LoadShedder>>,
+    pub(crate) bounded: BoundedInbounds,
+    /// Whether pending messages are processed once the actor is stopped, see
+    /// [Hollywood::set_graceful_shutdown()].
+    pub(crate) drain: Arc<AtomicBool>,
+    pub(crate) error_sink: Option<ErrorSink>,
+    pub(crate) clock: Option<Arc<dyn IsClock>>,
+    pub(crate) channel_orderings: Vec<ChannelOrdering>,
+    pub(crate) chaos: Option<tokio::sync::watch::Receiver<ChaosFault>>,
+    pub(crate) fused: bool,
+    pub(crate) metrics: Option<Arc<ActorMetrics>>,
+    pub(crate) correlations: Correlations,
+}
+
+impl std::fmt::Debug for ActorRunOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActorRunOptions")
+            .field("yield_interval", &self.yield_interval)
+            .field("load_shedding", &self.load_shedder.is_some())
+            .field("channel_orderings", &self.channel_orderings)
+            .field("chaos", &self.chaos.is_some())
+            .field("drain", &self.drain.load(Ordering::Relaxed))
+            .field("clock", &self.clock.is_some())
+            .field("fused", &self.fused)
+            .field("metrics", &self.metrics.is_some())
+            .finish()
+    }
+}
+
+impl ActorRunOptions {
+    /// Default value of [ActorRunOptions::yield_interval].
+    pub const DEFAULT_YIELD_INTERVAL: usize = 64;
+
+    /// Returns the clock of the pipeline, see [Hollywood::set_clock()], or a real time clock
+    /// starting now if none was set.
+    pub fn clock(&self) -> Arc<dyn IsClock> {
+        self.clock
+            .clone()
+            .unwrap_or_else(|| Arc::new(RealClock::new()))
+    }
+}
+
+impl Default for ActorRunOptions {
+    fn default() -> Self {
+        Self {
+            yield_interval: Self::DEFAULT_YIELD_INTERVAL,
+            load_shedder: None,
+            bounded: BoundedInbounds::default(),
+            drain: Arc::new(AtomicBool::new(false)),
+            error_sink: None,
+            clock: None,
+            channel_orderings: vec![],
+            chaos: None,
+            fused: false,
+            metrics: None,
+            correlations: Correlations::default(),
+        }
+    }
+}
+
+/// Statistics about the message batches processed by an actor.
+///
+/// A batch is a sequence of messages processed in a row, without the actor waiting for new
+/// messages or yielding to the scheduler in between.
+///
+/// Besides the batch sizes, it contains the accounting of the resources used by the actor, see
+/// [Pipeline::actor_report()].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchStats {
+    /// Number of batches.
+    pub num_batches: u64,
+    /// Total number of processed messages.
+    pub num_messages: u64,
+    /// Size of the largest batch.
+    pub max_batch_size: usize,
+    /// Number of times the actor yielded because the yield interval was reached.
+    pub num_yields: u64,
+    /// Cumulative time spent in the message and request handlers.
+    pub busy_time: std::time::Duration,
+    /// Largest number of pending messages and requests observed.
+    pub peak_queue_len: usize,
+}
+
+impl BatchStats {
+    /// Average number of messages per batch.
+    pub fn mean_batch_size(&self) -> f64 {
+        if self.num_batches == 0 {
+            return 0.0;
+        }
+        self.num_messages as f64 / self.num_batches as f64
+    }
+
+    /// Average time spent in the handlers per processed message.
+    pub fn mean_busy_time(&self) -> std::time::Duration {
+        if self.num_messages == 0 {
+            return std::time::Duration::ZERO;
+        }
+        self.busy_time.div_f64(self.num_messages as f64)
+    }
+
+    fn finish_batch(&mut self, batch_size: usize) {
+        if batch_size == 0 {
+            return;
+        }
+        self.num_batches += 1;
+        self.num_messages += batch_size as u64;
+        self.max_batch_size = self.max_batch_size.max(batch_size);
+    }
 }
 
 /// A table to forward outbound messages to message handlers of downstream actors.
@@ -138,22 +529,70 @@ pub type ForwardRequestTable<Prop, State, OutRequestHub, Request, R> = HashMap<
     Box<dyn HasForwardRequestMessage<Prop, State, OutRequestHub, Request, R> + Send + Sync>,
 >;
 
+/// The forward table, the receivers of regular and high-priority inbound messages, and the
+/// outbound hub of an actor, which are passed to [IsRunner::new_actor_node()].
+pub type ForwardReceiverOutbound<Prop, State, OutboundHub, Request, M> = (
+    ForwardTable<Prop, State, OutboundHub, Request, M>,
+    tokio::sync::mpsc::UnboundedReceiver<M>,
+    OutboundHub,
+    tokio::sync::mpsc::UnboundedReceiver<M>,
+);
+
 pub(crate) struct ActorNodeImpl<Prop, State, OutboundHub, OutRequestHub, M, R> {
     pub(crate) name: String,
-    pub(crate) prop: Prop,
-    pub(crate) state: Option<State>,
-    pub(crate) forward: ForwardTable<Prop, State, OutboundHub, OutRequestHub, M>,
+    pub(crate) core: Arc<FusedSlot<Prop, State, OutboundHub, OutRequestHub, M>>,
     pub(crate) receiver: Option<tokio::sync::mpsc::UnboundedReceiver<M>>,
-    pub(crate) outbound: OutboundHub,
+    pub(crate) priority_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<M>>,
     pub(crate) forward_request: ForwardRequestTable<Prop, State, OutboundHub, OutRequestHub, R>,
     pub(crate) request_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<R>>,
-    pub(crate) out_request: OutRequestHub,
     pub(crate) on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+    pub(crate) run_options: ActorRunOptions,
+    pub(crate) batch_stats: BatchStats,
 }
 
-impl<Prop, State, Outbound: IsOutboundHub, Request, R: IsInRequestMessage, M: IsInboundMessage>
-    ActorNodeImpl<Prop, State, Outbound, Request, M, R>
+impl<
+        Prop: std::marker::Send + std::marker::Sync + 'static,
+        State: std::marker::Send + std::marker::Sync + 'static,
+        Outbound: IsOutboundHub,
+        Request: IsOutRequestHub<M>,
+        R: IsInRequestMessage,
+        M: IsInboundMessage,
+    > ActorNodeImpl<Prop, State, Outbound, Request, M, R>
 {
+    async fn run_fused(&mut self, kill: tokio::sync::watch::Receiver<bool>) {
+        let error_sink = self.run_options.error_sink.clone();
+        let clock = self.run_options.clock();
+        self.core.with_core(|core| {
+            core.errors.sink = error_sink;
+            if let Some(timer) = &mut core.timer {
+                timer.clock = clock;
+            }
+            core.errors.supervisor.start_run();
+            core.outbound.activate();
+            core.out_request.activate();
+        });
+        let (recv, priority_recv, batch_stats) = run_fused(
+            &self.core,
+            self.receiver.take().unwrap(),
+            self.priority_receiver.take().unwrap(),
+            self.request_receiver.take().unwrap(),
+            &self.forward_request,
+            &self.run_options.bounded,
+            &self.run_options.correlations,
+            &self.run_options.drain,
+            kill,
+        )
+        .await;
+        self.core.with_core(|core| core.out_request.deactivate());
+        self.receiver = Some(recv);
+        self.priority_receiver = Some(priority_recv);
+        debug!(
+            "{}: processed {} messages in fused mode in {:?}",
+            self.name, batch_stats.num_messages, batch_stats.busy_time
+        );
+        self.batch_stats = batch_stats;
+        self.on_exit();
+    }
 }
 
 #[async_trait]
@@ -170,27 +609,80 @@ impl<
         &self.name
     }
 
-    async fn run(&mut self, kill: tokio::sync::broadcast::Receiver<()>) {
-        self.outbound.activate();
-        self.out_request.activate();
+    async fn run(&mut self, kill: tokio::sync::watch::Receiver<bool>) {
+        let bounded = self.run_options.bounded.clone();
+        bounded.set_running(true);
+        if self.run_options.fused {
+            self.run_fused(kill).await;
+            bounded.set_running(false);
+            return;
+        }
+        let ActorCore {
+            prop,
+            state,
+            forward,
+            mut outbound,
+            mut out_request,
+            mut errors,
+            mut timer,
+        } = self.core.take_core();
+        errors.sink = self.run_options.error_sink.clone();
+        if let Some(timer) = &mut timer {
+            timer.clock = self.run_options.clock();
+        }
+        errors.supervisor.start_run();
+        outbound.activate();
+        out_request.activate();
 
-        let (state, recv) = on_message(
+        let (state, recv, priority_recv, batch_stats) = on_message(
             self.name.clone(),
-            &self.prop,
+            &prop,
             OnMessageMutValues {
-                state: self.state.take().unwrap(),
+                state,
                 receiver: self.receiver.take().unwrap(),
+                priority_receiver: self.priority_receiver.take().unwrap(),
                 request_receiver: self.request_receiver.take().unwrap(),
                 kill,
+                yield_interval: self.run_options.yield_interval,
+                load_shedder: self.run_options.load_shedder.clone(),
+                bounded: bounded.clone(),
+                drain: self.run_options.drain.clone(),
+                gate: OrderingGate::new(&self.run_options.channel_orderings),
+                chaos: self.run_options.chaos.take(),
+                metrics: self.run_options.metrics.clone(),
+                correlations: self.run_options.correlations.clone(),
             },
-            &self.forward,
+            &forward,
             &self.forward_request,
-            &self.outbound,
-            &self.out_request,
+            &outbound,
+            &out_request,
+            &mut errors,
+            timer.as_ref(),
         )
         .await;
-        self.state = Some(state);
+        bounded.set_running(false);
+        out_request.deactivate();
+        self.core.put_core(ActorCore {
+            prop,
+            state,
+            forward,
+            outbound,
+            out_request,
+            errors,
+            timer,
+        });
         self.receiver = Some(recv);
+        self.priority_receiver = Some(priority_recv);
+        debug!(
+            "{}: processed {} messages in {} batches (max: {}, yields: {}) in {:?}",
+            self.name,
+            batch_stats.num_messages,
+            batch_stats.num_batches,
+            batch_stats.max_batch_size,
+            batch_stats.num_yields,
+            batch_stats.busy_time
+        );
+        self.batch_stats = batch_stats;
         self.on_exit();
     }
 
@@ -199,15 +691,117 @@ impl<
             f();
         }
     }
+
+    fn set_run_options(&mut self, options: ActorRunOptions) {
+        self.run_options = options;
+    }
+
+    fn batch_stats(&self) -> Option<BatchStats> {
+        Some(self.batch_stats)
+    }
+
+    fn fused_dispatcher(&self) -> Option<Box<dyn Any + Send>> {
+        let dispatch: Arc<dyn IsFusedDispatch<M>> = self.core.clone();
+        Some(Box::new(dispatch))
+    }
+
+    fn num_restarts(&self) -> usize {
+        self.core
+            .with_core(|core| core.errors.supervisor.num_restarts)
+    }
+
+    fn traces_messages(&self) -> bool {
+        true
+    }
+
+    fn inspect_state(&self, f: &mut dyn FnMut(&dyn Any)) {
+        self.core.try_with_core(|core| f(&core.state));
+    }
 }
 
 pub(crate) struct OnMessageMutValues<State, M: IsInboundMessage, R: IsInRequestMessage> {
     state: State,
     receiver: tokio::sync::mpsc::UnboundedReceiver<M>,
+    priority_receiver: tokio::sync::mpsc::UnboundedReceiver<M>,
     request_receiver: tokio::sync::mpsc::UnboundedReceiver<R>,
-    kill: tokio::sync::broadcast::Receiver<()>,
+    kill: tokio::sync::watch::Receiver<bool>,
+    yield_interval: usize,
+    load_shedder: Option<Arc<LoadShedder>>,
+    bounded: BoundedInbounds,
+    drain: Arc<AtomicBool>,
+    gate: OrderingGate<M>,
+    chaos: Option<tokio::sync::watch::Receiver<ChaosFault>>,
+    metrics: Option<Arc<ActorMetrics>>,
+    correlations: Correlations,
 }
 
+/// Forwards the inbound message to its handler, unless it is held back by a cross-channel
+/// ordering constraint. Messages released by processing it are forwarded as well.
+#[allow(clippy::too_many_arguments)]
+fn dispatch_message<Prop, State, Outbound, OutRequest, M: IsInboundMessage>(
+    prop: &Prop,
+    state: &mut State,
+    forward: &ForwardTable<Prop, State, Outbound, OutRequest, M>,
+    outbound: &Outbound,
+    out_request: &OutRequest,
+    errors: &mut ActorErrorHandler<State>,
+    timer: Option<&ClockTimer<Prop, State, Outbound>>,
+    gate: &mut OrderingGate<M>,
+    stats: &mut BatchStats,
+    metrics: Option<&ActorMetrics>,
+    m: M,
+    correlation_id: Option<u64>,
+) {
+    let channel = m.inbound_channel();
+    let Some(handler) = forward.get(&channel) else {
+        return;
+    };
+    let timestamp = handler.timestamp(&m);
+    // The correlation id of a message which is held back is not carried on.
+    let Some(m) = gate.admit(&channel, timestamp, m) else {
+        return;
+    };
+    if let Some(timer) = timer {
+        timer.set_now(state);
+    }
+    let span = debug_span!(
+        "message",
+        channel = channel.as_str(),
+        correlation_id = tracing::field::Empty
+    );
+    if let Some(id) = correlation_id {
+        span.record("correlation_id", id);
+    }
+    let _span = span.enter();
+    let _scope = CorrelationScope::enter(correlation_id);
+    let start = std::time::Instant::now();
+    errors.dispatch(state, channel.clone(), |state| {
+        handler.try_forward_message(prop, state, outbound, out_request, m)
+    });
+    let latency = start.elapsed();
+    stats.busy_time += latency;
+    if let Some(metrics) = metrics {
+        metrics.record(&channel, latency);
+    }
+    for released in gate.on_processed(&channel, timestamp) {
+        dispatch_message(
+            prop,
+            state,
+            forward,
+            outbound,
+            out_request,
+            errors,
+            timer,
+            gate,
+            stats,
+            metrics,
+            released,
+            None,
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn on_message<
     Prop,
     State,
@@ -216,49 +810,260 @@ pub(crate) async fn on_message<
     M: IsInboundMessage,
     R: IsInRequestMessage,
 >(
-    _actor_name: String,
+    actor_name: String,
     prop: &Prop,
     mut values: OnMessageMutValues<State, M, R>,
     forward: &ForwardTable<Prop, State, Outbound, OutRequest, M>,
     forward_request: &ForwardRequestTable<Prop, State, Outbound, OutRequest, R>,
     outbound: &Outbound,
     out_request: &OutRequest,
-) -> (State, tokio::sync::mpsc::UnboundedReceiver<M>) {
+    errors: &mut ActorErrorHandler<State>,
+    timer: Option<&ClockTimer<Prop, State, Outbound>>,
+) -> (
+    State,
+    tokio::sync::mpsc::UnboundedReceiver<M>,
+    tokio::sync::mpsc::UnboundedReceiver<M>,
+    BatchStats,
+) {
     let mut requests_open = true;
+    let mut priority_open = true;
+    let mut stats = BatchStats::default();
+    let mut batch_size = 0;
+    let mut chaos_delay = None;
     loop {
-        select! {
-            _ = values.kill.recv() => {
-
-                while values.receiver.try_recv().is_ok(){}
-
-                return (values.state, values.receiver);
-            },
-            m = values.receiver.recv() => {
-                if m.is_none() {
-                    let _ = values.kill.try_recv();
-                    return (values.state, values.receiver);
+        if *values.kill.borrow() {
+            break;
+        }
+        if batch_size > 0 {
+            if values.receiver.is_empty()
+                && values.priority_receiver.is_empty()
+                && values.request_receiver.is_empty()
+            {
+                // No more pending messages, hence the actor is about to wait.
+                stats.finish_batch(batch_size);
+                batch_size = 0;
+            } else if values.yield_interval > 0 && batch_size >= values.yield_interval {
+                // Give other tasks on this worker a chance to run during heavy message bursts.
+                stats.finish_batch(batch_size);
+                stats.num_yields += 1;
+                batch_size = 0;
+                tokio::task::yield_now().await;
+            }
+        }
+        stats.peak_queue_len = stats.peak_queue_len.max(
+            values.receiver.len() + values.priority_receiver.len() + values.request_receiver.len(),
+        );
+        if let Some(shedder) = &values.load_shedder {
+            // Drop the oldest pending messages of the shed channels, and process the others in
+            // order, so that the backlog is bounded.
+            let mut num_dropped = 0;
+            for _ in 0..shedder.on_backlog(values.receiver.len()) {
+                let Ok(m) = values.receiver.try_recv() else {
+                    break;
+                };
+                let correlation_id = values.correlations.on_receive(&m);
+                let channel = m.inbound_channel();
+                if !values.bounded.on_dequeue(&m) {
+                    continue;
                 }
-                let m = m.unwrap();
-                let t = forward.get(&m.inbound_channel());
-                if t.is_none() {
+                if shedder.sheds(&channel) {
+                    num_dropped += 1;
                     continue;
                 }
-                t.unwrap().forward_message(prop, &mut values.state, outbound, out_request, m);
-            },
-            m = values.request_receiver.recv(), if requests_open => {
-                match m {
-                    Some(r) => {
-                        let t = forward_request.get(&r.in_request_channel());
-                        if let Some(handler) = t {
-                            handler.forward_message(
-                                prop, &mut values.state, outbound, out_request, r);
-                        }
-                    },
+                batch_size += 1;
+                dispatch_message(
+                    prop,
+                    &mut values.state,
+                    forward,
+                    outbound,
+                    out_request,
+                    errors,
+                    timer,
+                    &mut values.gate,
+                    &mut stats,
+                    values.metrics.as_deref(),
+                    m,
+                    correlation_id,
+                );
+            }
+            shedder.add_dropped(num_dropped);
+        }
+        let deadline = timer.and_then(|timer| {
+            let deadline = timer.next_deadline(prop, &values.state)?;
+            Some((timer.clock.as_ref(), deadline))
+        });
+        // Pending high-priority messages are processed before regular messages and requests.
+        let m = match values.priority_receiver.try_recv() {
+            Ok(m) => m,
+            Err(_) => select! {
+                _ = values.kill.changed() => break,
+                m = values.priority_receiver.recv(), if priority_open => match m {
+                    Some(m) => m,
                     None => {
-                        requests_open = false;
+                        // The actor has no priority lane.
+                        priority_open = false;
+                        continue;
+                    }
+                },
+                m = values.receiver.recv() => {
+                    // All senders are gone, hence the actor stops like on the kill signal, so
+                    // that queued requests are discarded as well.
+                    let Some(m) = m else {
+                        break;
+                    };
+                    m
+                },
+                m = values.request_receiver.recv(), if requests_open => {
+                    match m {
+                        Some(r) => {
+                            if let Some(delay) = chaos_delay {
+                                tokio::time::sleep(delay).await;
+                            }
+                            batch_size += 1;
+                            let channel = r.in_request_channel();
+                            if let Some(handler) = forward_request.get(&channel) {
+                                if let Some(timer) = timer {
+                                    timer.set_now(&mut values.state);
+                                }
+                                let _span =
+                                    debug_span!("request", channel = channel.as_str()).entered();
+                                let start = std::time::Instant::now();
+                                errors.supervisor.supervise(
+                                    &actor_name, &mut values.state, |state| {
+                                    handler.forward_message(prop, state, outbound, out_request, r)
+                                });
+                                let latency = start.elapsed();
+                                stats.busy_time += latency;
+                                if let Some(metrics) = &values.metrics {
+                                    metrics.record(&channel, latency);
+                                }
+                            }
+                        },
+                        None => {
+                            requests_open = false;
+                        }
+                    }
+                    continue;
+                }
+                _ = sleep_until_deadline(deadline) => {
+                    if let Some(timer) = timer {
+                        let start = std::time::Instant::now();
+                        errors.supervisor.supervise(&actor_name, &mut values.state, |state| {
+                            timer.on_deadline(prop, state, outbound)
+                        });
+                        stats.busy_time += start.elapsed();
+                    }
+                    continue;
+                }
+                fault = next_chaos_fault(&mut values.chaos) => {
+                    match fault {
+                        ChaosFault::Clear => chaos_delay = None,
+                        ChaosFault::Kill => {
+                            let reason = "killed by chaos command";
+                            if !errors.supervisor.restart(&actor_name, &mut values.state, reason) {
+                                panic!("{}: {}", actor_name, reason);
+                            }
+                        }
+                        ChaosFault::Stall(duration) => {
+                            // The kill signal is checked at the top of the loop.
+                            select! {
+                                _ = values.kill.changed() => {},
+                                _ = tokio::time::sleep(duration) => {},
+                            }
+                        }
+                        ChaosFault::Delay(delay) => chaos_delay = Some(delay),
                     }
+                    continue;
                 }
+            },
+        };
+        let correlation_id = values.correlations.on_receive(&m);
+        // Messages skipped due to a bounded inbound channel are dropped right away.
+        if values.bounded.on_dequeue(&m) {
+            if let Some(delay) = chaos_delay {
+                tokio::time::sleep(delay).await;
             }
+            batch_size += 1;
+            dispatch_message(
+                prop,
+                &mut values.state,
+                forward,
+                outbound,
+                out_request,
+                errors,
+                timer,
+                &mut values.gate,
+                &mut stats,
+                values.metrics.as_deref(),
+                m,
+                correlation_id,
+            );
+        }
+    }
+    if values.drain.load(Ordering::Acquire) {
+        // Graceful shutdown: the upstream actors stopped already, hence the pending messages and
+        // requests are the last ones.
+        while let Ok(m) = values
+            .priority_receiver
+            .try_recv()
+            .or_else(|_| values.receiver.try_recv())
+        {
+            let correlation_id = values.correlations.on_receive(&m);
+            if values.bounded.on_dequeue(&m) {
+                batch_size += 1;
+                dispatch_message(
+                    prop,
+                    &mut values.state,
+                    forward,
+                    outbound,
+                    out_request,
+                    errors,
+                    timer,
+                    &mut values.gate,
+                    &mut stats,
+                    values.metrics.as_deref(),
+                    m,
+                    correlation_id,
+                );
+            }
+        }
+        while let Ok(r) = values.request_receiver.try_recv() {
+            let channel = r.in_request_channel();
+            if let Some(handler) = forward_request.get(&channel) {
+                batch_size += 1;
+                if let Some(timer) = timer {
+                    timer.set_now(&mut values.state);
+                }
+                let _span = debug_span!("request", channel = channel.as_str()).entered();
+                let start = std::time::Instant::now();
+                errors
+                    .supervisor
+                    .supervise(&actor_name, &mut values.state, |state| {
+                        handler.forward_message(prop, state, outbound, out_request, r)
+                    });
+                let latency = start.elapsed();
+                stats.busy_time += latency;
+                if let Some(metrics) = &values.metrics {
+                    metrics.record(&channel, latency);
+                }
+            }
+        }
+    } else {
+        while let Ok(m) = values
+            .priority_receiver
+            .try_recv()
+            .or_else(|_| values.receiver.try_recv())
+        {
+            values.correlations.on_receive(&m);
         }
     }
+    stats.finish_batch(batch_size);
+    // Requests which are still queued are dropped without a reply.
+    discard_requests(values.request_receiver);
+    (
+        values.state,
+        values.receiver,
+        values.priority_receiver,
+        stats,
+    )
 }
@@ -1,3 +1,4 @@
+use crate::core::error_policy::ActorErrorHandler;
 use crate::prelude::*;
 
 /// Creates actor from its components.
@@ -21,6 +22,10 @@ pub struct ActorBuilder<
     /// a channel for sending messages to the actor
     pub sender: tokio::sync::mpsc::UnboundedSender<M>,
     pub(crate) receiver: tokio::sync::mpsc::UnboundedReceiver<M>,
+    /// a channel for sending high-priority messages to the actor, see
+    /// [InboundChannel::new_priority()]
+    pub priority_sender: tokio::sync::mpsc::UnboundedSender<M>,
+    pub(crate) priority_receiver: tokio::sync::mpsc::UnboundedReceiver<M>,
     /// a channel for sending requests to the actor
     pub request_sender: tokio::sync::mpsc::UnboundedSender<R>,
     pub(crate) request_receiver: tokio::sync::mpsc::UnboundedReceiver<R>,
@@ -28,6 +33,7 @@ pub struct ActorBuilder<
     pub forward: ForwardTable<Prop, State, IsOutboundHub, OutRequest, M>,
     /// a collection of inbound channels
     pub forward_request: ForwardRequestTable<Prop, State, IsOutboundHub, OutRequest, R>,
+    errors: ActorErrorHandler<State>,
 }
 
 impl<
@@ -47,6 +53,7 @@ impl<
         initial_state: State,
     ) -> Self {
         let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (priority_sender, priority_receiver) = tokio::sync::mpsc::unbounded_channel();
         let (request_sender, request_receiver) = tokio::sync::mpsc::unbounded_channel();
 
         Self {
@@ -56,13 +63,85 @@ impl<
             context,
             sender: sender.clone(),
             receiver,
+            priority_sender,
+            priority_receiver,
             request_sender,
             request_receiver,
             forward: ForwardTable::new(),
             forward_request: ForwardRequestTable::new(),
+            errors: ActorErrorHandler::new(actor_name),
         }
     }
 
+    /// Properties the actor is created with, e.g. to create inbound channels which depend on the
+    /// configuration of the actor.
+    pub fn prop(&self) -> &Prop {
+        &self.prop
+    }
+
+    /// Sets how the actor reacts if its message handler returns an error, see
+    /// [HasOnMessage::try_on_message()].
+    ///
+    /// For [ErrorPolicy::Restart], the state the actor is created with is kept as initial state.
+    /// The default policy is [ErrorPolicy::Skip].
+    pub fn set_error_policy(&mut self, policy: ErrorPolicy)
+    where
+        State: Clone + Send + Sync + 'static,
+    {
+        self.errors.set_policy(policy, self.state.clone());
+    }
+
+    /// Sets how the pipeline reacts if a message or request handler of the actor panics.
+    ///
+    /// For [SupervisionStrategy::OneForOne], the state the actor is created with is kept as
+    /// initial state to restart from. The default strategy is [SupervisionStrategy::Escalate].
+    pub fn set_supervision(&mut self, strategy: SupervisionStrategy)
+    where
+        State: Clone + Send + Sync + 'static,
+    {
+        self.errors
+            .supervisor
+            .set_strategy(strategy, self.state.clone());
+    }
+
+    /// Sets where the pipeline executes the actor, e.g. on a dedicated thread. See
+    /// [ActorPlacement] for details. By default, the actor runs on the runtime the pipeline is run
+    /// on.
+    ///
+    /// Panics if the placement refers to a runtime which is not registered, see
+    /// [Hollywood::register_runtime()].
+    pub fn set_placement(&mut self, placement: ActorPlacement) {
+        self.context
+            .set_actor_placement(&self.actor_name, placement);
+    }
+
+    /// Makes the state of the actor part of the pipeline snapshots, see [Pipeline::snapshot()].
+    ///
+    /// If the pipeline is configured from a snapshot (see [Hollywood::configure_from_snapshot()])
+    /// which contains a state of this actor, it replaces the initial state, given that the stored
+    /// schema matches the given one. Otherwise, a warning is logged and the initial state is kept.
+    /// Hence, it is best called before [ActorBuilder::set_error_policy()] or
+    /// [ActorBuilder::set_supervision()], so that the actor restarts from the restored state.
+    #[cfg(feature = "serde")]
+    pub fn enable_checkpoint(&mut self, schema: StateSchema)
+    where
+        State: serde::Serialize + serde::de::DeserializeOwned + 'static,
+    {
+        self.context
+            .checkpoints
+            .register(&self.actor_name, schema, &mut self.state);
+    }
+
+    /// Sets the version of the actor type, as listed in the [PipelineManifest] of the pipeline.
+    ///
+    /// It is set from [HasFromPropState::version()] when the actor is created.
+    pub fn set_version(&mut self, version: SemVer) {
+        self.context
+            .versions
+            .actors
+            .insert(self.actor_name.clone(), version);
+    }
+
     pub(crate) fn build<
         Inbound: IsInboundHub<Prop, State, Outbound, OutRequest, M, R>,
         InRequest: IsInRequestHub<Prop, State, Outbound, OutRequest, M, R>,
@@ -87,13 +166,19 @@ impl<
             self.actor_name,
             self.prop,
             self.state,
-            (self.forward, self.receiver, actor.outbound.extract()),
+            (
+                self.forward,
+                self.receiver,
+                actor.outbound.extract(),
+                self.priority_receiver,
+            ),
             (
                 self.forward_request,
                 self.request_receiver,
                 actor.out_requests.extract(),
             ),
             on_exit_fn,
+            self.errors,
         ));
         actor
     }
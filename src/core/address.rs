@@ -0,0 +1,88 @@
+use crate::core::connection::request_connection::GenericRequestConnection;
+use crate::core::connection::request_connection::RequestConnection;
+use crate::prelude::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Lightweight address of an actor's in-request channel.
+///
+/// As opposed to [OutRequestChannel::connect()], which fixes the receiver of requests at
+/// configuration time, an address is a value which can be cloned freely, stored in the props or
+/// state of other actors and passed around in messages. This enables dynamic request routing,
+/// e.g. a directory actor which hands out addresses of service actors.
+///
+/// An address is obtained using [InRequestChannel::address()], and requests are sent using
+/// [OutRequestChannel::send_request_to()]. The reply is routed back to the requesting actor
+/// through its out-request channel, just as for regular requests.
+pub struct Address<Request, Reply> {
+    actor_name: String,
+    in_request_name: String,
+    pub(crate) connection:
+        Arc<dyn GenericRequestConnection<RequestWithReplyChannel<Request, Reply>> + Send + Sync>,
+}
+
+impl<Request, Reply> Clone for Address<Request, Reply> {
+    fn clone(&self) -> Self {
+        Self {
+            actor_name: self.actor_name.clone(),
+            in_request_name: self.in_request_name.clone(),
+            connection: self.connection.clone(),
+        }
+    }
+}
+
+impl<Request, Reply> std::fmt::Debug for Address<Request, Reply> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Address({}.{})", self.actor_name, self.in_request_name)
+    }
+}
+
+impl<Request, Reply> Address<Request, Reply> {
+    /// Name of the addressed actor.
+    pub fn actor_name(&self) -> &str {
+        &self.actor_name
+    }
+
+    /// Name of the addressed in-request channel.
+    pub fn in_request_name(&self) -> &str {
+        &self.in_request_name
+    }
+}
+
+impl<Request: Send + Sync + 'static, Reply: Send + Sync + 'static> Address<Request, Reply> {
+    /// Returns a client to send requests to the addressed actor from outside the pipeline.
+    ///
+    /// Note that the client cannot detect whether the addressed actor is still running, hence a
+    /// request to a stopped actor fails with [RequestClientError::NoReply].
+    pub fn client(&self) -> RequestClient<Request, Reply> {
+        let connection = self.connection.clone();
+        RequestClient {
+            actor_name: self.actor_name.clone(),
+            in_request_name: self.in_request_name.clone(),
+            sender: Arc::new(move |request| {
+                connection.send_impl(request);
+                Ok(())
+            }),
+        }
+    }
+}
+
+impl<
+        Request: Send + Sync + std::fmt::Debug + 'static,
+        Reply: Send + Sync + std::fmt::Debug + 'static,
+        M: IsInRequestMessageNew<RequestWithReplyChannel<Request, Reply>>,
+    > InRequestChannel<RequestWithReplyChannel<Request, Reply>, M>
+{
+    /// Returns the address of this in-request channel.
+    pub fn address(&self) -> Address<Request, Reply> {
+        Address {
+            actor_name: self.actor_name.clone(),
+            in_request_name: self.name.clone(),
+            connection: Arc::new(RequestConnection {
+                sender: self.sender.as_ref().clone(),
+                inbound_channel: self.name.clone(),
+                phantom: PhantomData {},
+            }),
+        }
+    }
+}
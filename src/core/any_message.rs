@@ -0,0 +1,100 @@
+use crate::prelude::*;
+use std::any::Any;
+use std::sync::Arc;
+
+/// Error returned when an [AnyMessage] is downcast to the wrong type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnyMessageError {
+    /// The payload is not of the requested type.
+    TypeMismatch {
+        /// Type name of the requested type.
+        expected: &'static str,
+        /// Type name of the actual payload.
+        actual: &'static str,
+    },
+}
+
+impl std::fmt::Display for AnyMessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnyMessageError::TypeMismatch { expected, actual } => {
+                write!(f, "expected message of type {}, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnyMessageError {}
+
+/// Type-erased message payload.
+///
+/// It enables plugin-style actors to exchange messages whose types are not known to the host
+/// crate. The payload is reference counted, so that broadcasting a message to multiple inbound
+/// channels does not require the payload to be [Clone].
+///
+/// A typed outbound channel is connected to a type-erased inbound channel using an adapter:
+///
+/// ``` ignore
+/// source
+///     .outbound
+///     .value
+///     .connect_with_adapter(context, AnyMessage::new, &mut plugin.inbound.any);
+/// ```
+#[derive(Clone)]
+pub struct AnyMessage {
+    type_name: &'static str,
+    payload: Arc<dyn Any + Send + Sync>,
+}
+
+impl AnyMessage {
+    /// Wraps the given value into a type-erased message.
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self {
+            type_name: std::any::type_name::<T>(),
+            payload: Arc::new(value),
+        }
+    }
+
+    /// Type name of the payload.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns true if the payload is of type T.
+    pub fn is<T: Any>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    /// Returns a reference to the payload, or an error if it is not of type T.
+    pub fn downcast_ref<T: Any>(&self) -> Result<&T, AnyMessageError> {
+        self.payload
+            .downcast_ref::<T>()
+            .ok_or(AnyMessageError::TypeMismatch {
+                expected: std::any::type_name::<T>(),
+                actual: self.type_name,
+            })
+    }
+
+    /// Returns a copy of the payload, or an error if it is not of type T.
+    pub fn downcast<T: Any + Clone>(&self) -> Result<T, AnyMessageError> {
+        self.downcast_ref::<T>().cloned()
+    }
+}
+
+impl Default for AnyMessage {
+    fn default() -> Self {
+        Self::new(())
+    }
+}
+
+impl std::fmt::Debug for AnyMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AnyMessage<{}>", self.type_name)
+    }
+}
+
+/// Outbound channel of type-erased messages.
+pub type AnyOutboundChannel = OutboundChannel<AnyMessage>;
+
+/// Inbound channel of type-erased messages.
+pub type AnyInboundChannel<M> = InboundChannel<AnyMessage, M>;
@@ -0,0 +1,330 @@
+use crate::core::outbound::OutboundConnection;
+use crate::prelude::*;
+use std::marker::PhantomData;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::warn;
+
+/// Behavior of a bounded connection if the capacity of the inbound channel is reached, see
+/// [OutboundChannel::connect_with_capacity()].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// The sending actor blocks until the receiving actor caught up.
+    ///
+    /// Blocking requires the multi-threaded tokio runtime. On the current-thread runtime, the
+    /// sender would block the receiver as well, hence the capacity is exceeded with a warning
+    /// instead. This includes actors placed on a dedicated thread or a local set (see
+    /// [ActorPlacement]), which run on a current-thread runtime of their own.
+    ///
+    /// Note: Tokio does not allow to block within a [tokio::task::LocalSet], and there is no way
+    /// to detect a local set which runs on the multi-threaded runtime. Hence, sending on a
+    /// blocking connection from such a local task (e.g. from code outside of the pipeline)
+    /// panics once the capacity is reached. Use one of the dropping policies there instead.
+    Block,
+    /// The oldest pending message is dropped, i.e. the receiving actor skips it.
+    DropOldest,
+    /// The new message is dropped.
+    DropNewest,
+}
+
+/// Statistics about a bounded inbound channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BackpressureStats {
+    /// Number of messages dropped due to the capacity.
+    pub num_dropped: u64,
+    /// Number of sends which blocked due to the capacity.
+    pub num_blocked: u64,
+    /// Largest number of pending messages observed.
+    pub peak_len: usize,
+}
+
+#[derive(Default)]
+struct BoundedState {
+    /// Number of messages which are pending in the queue of the receiving actor.
+    len: usize,
+    /// Number of the oldest pending messages which are to be skipped, see
+    /// [BackpressurePolicy::DropOldest].
+    stale: usize,
+    /// Whether the receiving actor is running, i.e. whether blocking senders will be woken up.
+    running: bool,
+    stats: BackpressureStats,
+}
+
+/// Shared state between a bounded inbound channel and its upstream connections.
+pub(crate) struct BoundedInbound {
+    actor_name: String,
+    pub(crate) inbound_name: String,
+    capacity: usize,
+    policy: BackpressurePolicy,
+    state: Mutex<BoundedState>,
+    space: Condvar,
+    warned: AtomicBool,
+}
+
+impl BoundedInbound {
+    pub(crate) fn new(
+        actor_name: &str,
+        inbound_name: &str,
+        capacity: usize,
+        policy: BackpressurePolicy,
+    ) -> Self {
+        assert!(
+            capacity > 0,
+            "capacity of {} must be positive",
+            inbound_name
+        );
+        Self {
+            actor_name: actor_name.to_owned(),
+            inbound_name: inbound_name.to_owned(),
+            capacity,
+            policy,
+            state: Mutex::new(BoundedState::default()),
+            space: Condvar::new(),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> BackpressureStats {
+        self.state.lock().unwrap().stats
+    }
+
+    /// Called by upstream connections before a message is sent. Returns false if the message is
+    /// to be dropped.
+    fn admit(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match self.policy {
+            BackpressurePolicy::DropNewest if state.len >= self.capacity => {
+                state.stats.num_dropped += 1;
+                return false;
+            }
+            BackpressurePolicy::DropOldest if state.len - state.stale >= self.capacity => {
+                state.stale += 1;
+                state.stats.num_dropped += 1;
+            }
+            BackpressurePolicy::Block if state.running && state.len >= self.capacity => {
+                state.stats.num_blocked += 1;
+                state = self.wait_for_space(state);
+            }
+            _ => {}
+        }
+        state.len += 1;
+        state.stats.peak_len = state.stats.peak_len.max(state.len - state.stale);
+        true
+    }
+
+    fn wait_for_space<'a>(
+        &self,
+        state: std::sync::MutexGuard<'a, BoundedState>,
+    ) -> std::sync::MutexGuard<'a, BoundedState> {
+        let wait = |mut state: std::sync::MutexGuard<'a, BoundedState>| {
+            while state.running && state.len >= self.capacity {
+                // The timeout guards against missed wake-ups, e.g. if the receiver stops.
+                state = self
+                    .space
+                    .wait_timeout(state, Duration::from_millis(100))
+                    .unwrap()
+                    .0;
+            }
+            state
+        };
+        // The local sets of actor groups run on current-thread runtimes, see ActorPlacement.
+        match tokio::runtime::Handle::try_current().map(|handle| handle.runtime_flavor()) {
+            Ok(tokio::runtime::RuntimeFlavor::CurrentThread) => {
+                if !self.warned.swap(true, Ordering::Relaxed) {
+                    warn!(
+                        "{}: cannot block on {} on the current-thread runtime, capacity of {} \
+                         exceeded",
+                        self.actor_name, self.inbound_name, self.capacity
+                    );
+                }
+                state
+            }
+            Ok(_) => tokio::task::block_in_place(|| wait(state)),
+            Err(_) => wait(state),
+        }
+    }
+
+    /// Called by the receiving actor for each message of the channel taken from its queue.
+    /// Returns false if the message is to be skipped.
+    pub(crate) fn on_dequeue(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        state.len = state.len.saturating_sub(1);
+        let keep = if state.stale > 0 {
+            state.stale -= 1;
+            false
+        } else {
+            true
+        };
+        drop(state);
+        self.space.notify_one();
+        keep
+    }
+
+    /// Called by the receiving actor when it starts or stops. All pending messages are discarded
+    /// when the actor stops.
+    pub(crate) fn set_running(&self, running: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.running = running;
+        if !running {
+            state.len = 0;
+            state.stale = 0;
+        }
+        drop(state);
+        self.space.notify_all();
+    }
+}
+
+/// Bounded inbound channels of an actor, see [OutboundChannel::connect_with_capacity()].
+#[derive(Clone, Default)]
+pub(crate) struct BoundedInbounds {
+    inbounds: Vec<Arc<BoundedInbound>>,
+}
+
+impl BoundedInbounds {
+    pub(crate) fn new(inbounds: Vec<Arc<BoundedInbound>>) -> Self {
+        Self { inbounds }
+    }
+
+    /// Accounts for the message taken from the queue. Returns false if the message is to be
+    /// skipped.
+    pub(crate) fn on_dequeue<M: IsInboundMessage>(&self, m: &M) -> bool {
+        if self.inbounds.is_empty() {
+            return true;
+        }
+        let inbound_name = m.inbound_channel();
+        self.inbounds
+            .iter()
+            .find(|inbound| inbound.inbound_name == inbound_name)
+            .is_none_or(|inbound| inbound.on_dequeue())
+    }
+
+    pub(crate) fn set_running(&self, running: bool) {
+        for inbound in self.inbounds.iter() {
+            inbound.set_running(running);
+        }
+    }
+}
+
+struct BoundedConnection<T> {
+    inner: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    bounded: Arc<BoundedInbound>,
+}
+
+impl<T: Send + Sync> IsGenericConnection<T> for BoundedConnection<T> {
+    fn send_impl(&self, msg: T) {
+        if self.bounded.admit() {
+            self.inner.send_impl(msg);
+        }
+    }
+}
+
+impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
+    /// Connects the outbound channel to the inbound channel of another actor, such that the
+    /// number of pending messages of the inbound channel is bounded by the given capacity.
+    ///
+    /// Once the capacity is reached, new messages are handled according to the given policy,
+    /// e.g. to protect a slow downstream actor from unbounded memory growth:
+    ///
+    /// ``` ignore
+    /// camera
+    ///     .outbound
+    ///     .image
+    ///     .connect_with_capacity(context, &mut detector.inbound.image, 2, BackpressurePolicy::DropOldest);
+    /// ```
+    ///
+    /// The capacity applies to the inbound channel, i.e. it is shared by all bounded connections
+    /// into the channel. Messages which reach the channel through other means, e.g. regular
+    /// connections or an [InboundInjector], are not accounted for. Messages skipped due to
+    /// [BackpressurePolicy::DropOldest] are dropped once the receiving actor takes them from its
+    /// queue. Statistics are available through [Pipeline::backpressure_stats()].
+    ///
+    /// Panics if the inbound channel is already bounded with a different capacity or policy.
+    pub fn connect_with_capacity<M: IsInboundMessageNew<OutT>>(
+        &mut self,
+        ctx: &mut Hollywood,
+        inbound: &mut InboundChannel<OutT, M>,
+        capacity: usize,
+        policy: BackpressurePolicy,
+    ) {
+        let bounded = bounded_inbound(ctx, &inbound.actor_name, &inbound.name, capacity, policy);
+        ctx.connect_impl(
+            self,
+            inbound,
+            Arc::new(BoundedConnection {
+                inner: Arc::new(OutboundConnection::<OutT, M> {
+                    sender: inbound.sender.clone(),
+                    inbound_channel: inbound.name.clone(),
+                    phantom: PhantomData,
+                }),
+                bounded,
+            }),
+        );
+    }
+}
+
+/// Returns the bounded state of the inbound channel, which is created on first use.
+fn bounded_inbound(
+    ctx: &mut Hollywood,
+    actor_name: &str,
+    inbound_name: &str,
+    capacity: usize,
+    policy: BackpressurePolicy,
+) -> Arc<BoundedInbound> {
+    let inbounds = ctx
+        .bounded_inbounds
+        .entry(actor_name.to_owned())
+        .or_default();
+    if let Some(bounded) = inbounds
+        .iter()
+        .find(|bounded| bounded.inbound_name == inbound_name)
+    {
+        assert!(
+            bounded.capacity == capacity && bounded.policy == policy,
+            "Inbound {} of {} is already bounded with capacity {} and policy {:?}",
+            inbound_name,
+            actor_name,
+            bounded.capacity,
+            bounded.policy
+        );
+        return bounded.clone();
+    }
+    let bounded = Arc::new(BoundedInbound::new(
+        actor_name,
+        inbound_name,
+        capacity,
+        policy,
+    ));
+    inbounds.push(bounded.clone());
+    bounded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_falls_back_on_local_set() {
+        let inbound = BoundedInbound::new("Receiver", "value", 1, BackpressurePolicy::Block);
+        inbound.set_running(true);
+        // Like an actor with ActorPlacement::LocalSet, see spawn_actor_group().
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&runtime, async {
+            tokio::task::spawn_local(async move {
+                assert!(inbound.admit());
+                // The capacity is exceeded instead of blocking (or panicking).
+                assert!(inbound.admit());
+                assert_eq!(inbound.stats().num_blocked, 1);
+                assert_eq!(inbound.stats().peak_len, 2);
+            })
+            .await
+            .unwrap();
+        });
+    }
+}
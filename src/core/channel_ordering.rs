@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use tracing::warn;
+
+/// Cross-channel ordering constraint between two inbound channels of an actor.
+///
+/// A message of the `after` channel with timestamp `t` is held back until a message of the
+/// `before` channel with a timestamp of at least `t` was processed. Hence, all messages of the
+/// `before` channel with timestamp up to `t` are processed before it - e.g. predictions are
+/// processed before updates in a filter fed by two streams. Both streams are expected to have
+/// non-decreasing timestamps.
+///
+/// Timestamps are provided by [HasOnMessage::timestamp()](crate::HasOnMessage::timestamp);
+/// messages without a timestamp are not constrained. The constraint is registered using
+/// [Hollywood::set_channel_ordering()](crate::Hollywood::set_channel_ordering).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelOrdering {
+    /// Name of the inbound channel which is processed first.
+    pub before: String,
+    /// Name of the inbound channel which is held back.
+    pub after: String,
+    /// Maximal number of held back messages. If exceeded, the oldest message is processed
+    /// regardless of the constraint, e.g. if the `before` stream stalls.
+    pub max_buffered: usize,
+}
+
+impl ChannelOrdering {
+    /// Default value of [ChannelOrdering::max_buffered].
+    pub const DEFAULT_MAX_BUFFERED: usize = 1024;
+
+    /// Creates a new ordering constraint with the default buffer size.
+    pub fn new(before: &str, after: &str) -> Self {
+        Self {
+            before: before.to_owned(),
+            after: after.to_owned(),
+            max_buffered: Self::DEFAULT_MAX_BUFFERED,
+        }
+    }
+}
+
+struct OrderingRule<M> {
+    ordering: ChannelOrdering,
+    latest_before: Option<f64>,
+    buffered: VecDeque<(f64, M)>,
+}
+
+/// Buffers messages within the runner to enforce [ChannelOrdering] constraints.
+pub(crate) struct OrderingGate<M> {
+    rules: Vec<OrderingRule<M>>,
+}
+
+impl<M> OrderingGate<M> {
+    pub(crate) fn new(orderings: &[ChannelOrdering]) -> Self {
+        Self {
+            rules: orderings
+                .iter()
+                .map(|ordering| OrderingRule {
+                    ordering: ordering.clone(),
+                    latest_before: None,
+                    buffered: VecDeque::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the message if it can be processed right away, otherwise it is held back. If the
+    /// buffer overflows, the oldest held back message is returned instead.
+    pub(crate) fn admit(&mut self, channel: &str, timestamp: Option<f64>, msg: M) -> Option<M> {
+        let Some(timestamp) = timestamp else {
+            return Some(msg);
+        };
+        let Some(rule) = self.rules.iter_mut().find(|rule| {
+            rule.ordering.after == channel
+                && rule.latest_before.is_none_or(|latest| latest < timestamp)
+        }) else {
+            return Some(msg);
+        };
+        rule.buffered.push_back((timestamp, msg));
+        if rule.buffered.len() > rule.ordering.max_buffered {
+            warn!(
+                "Ordering of {} before {} violated, buffer of {} is full",
+                rule.ordering.before, rule.ordering.after, rule.ordering.max_buffered
+            );
+            return rule.buffered.pop_front().map(|(_, msg)| msg);
+        }
+        None
+    }
+
+    /// Registers that a message was processed, and returns the held back messages which are
+    /// released by it, in order.
+    pub(crate) fn on_processed(&mut self, channel: &str, timestamp: Option<f64>) -> Vec<M> {
+        let mut released = vec![];
+        let Some(timestamp) = timestamp else {
+            return released;
+        };
+        for rule in self.rules.iter_mut() {
+            if rule.ordering.before != channel {
+                continue;
+            }
+            rule.latest_before = Some(rule.latest_before.map_or(timestamp, |t| t.max(timestamp)));
+            while rule.buffered.front().is_some_and(|(t, _)| *t <= timestamp) {
+                released.push(rule.buffered.pop_front().unwrap().1);
+            }
+        }
+        released
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Liveness statistics of an inbound channel.
+///
+/// It is a lightweight handle: clones share the same counters. The statistics are updated by the
+/// actor right before the message is passed to [HasOnMessage::on_message()](crate::HasOnMessage),
+/// hence they can be used by the business logic to react to the liveness of its inputs, e.g. to
+/// fall back to prediction-only once measurements stop arriving.
+///
+/// The handle of an inbound channel is obtained through [InboundChannel::stats()]. Since the state
+/// of an actor is passed in before its inbound channels exist, a handle created up front (e.g. as
+/// part of the state) can be installed using [InboundChannel::share_stats()].
+///
+/// [InboundChannel::stats()]: crate::InboundChannel::stats()
+/// [InboundChannel::share_stats()]: crate::InboundChannel::share_stats()
+#[derive(Clone, Debug, Default)]
+pub struct ChannelStats {
+    inner: Arc<ChannelStatsInner>,
+}
+
+#[derive(Debug, Default)]
+struct ChannelStatsInner {
+    num_received: AtomicU64,
+    last_arrival: Mutex<Option<Instant>>,
+}
+
+impl ChannelStats {
+    /// Number of messages received so far.
+    pub fn num_received(&self) -> u64 {
+        self.inner.num_received.load(Ordering::Relaxed)
+    }
+
+    /// Time of the most recent message, or None if no message was received yet.
+    pub fn last_arrival(&self) -> Option<Instant> {
+        *self.inner.last_arrival.lock().unwrap()
+    }
+
+    /// Time elapsed since the most recent message, or None if no message was received yet.
+    pub fn since_last_arrival(&self) -> Option<Duration> {
+        self.last_arrival().map(|t| t.elapsed())
+    }
+
+    /// Whether no message was received within the given timeout, including the case that no
+    /// message was received at all.
+    pub fn is_stale(&self, timeout: Duration) -> bool {
+        self.since_last_arrival().is_none_or(|d| d > timeout)
+    }
+
+    pub(crate) fn record(&self) {
+        self.inner.num_received.fetch_add(1, Ordering::Relaxed);
+        *self.inner.last_arrival.lock().unwrap() = Some(Instant::now());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokio::sync::mpsc::error::TrySendError;
+
+/// Registry of the taps of a pipeline, by "ACTOR.CHANNEL" label.
+pub(crate) type TapRegistry = HashMap<String, Arc<dyn Any + Send + Sync>>;
+
+fn tap_label(actor_name: &str, outbound_name: &str) -> String {
+    format!("{}.{}", actor_name, outbound_name)
+}
+
+/// Looks up the tap of the given outbound channel, if it is tappable and of payload type `T`.
+pub(crate) fn find_tap<T: Send + 'static>(
+    registry: &TapRegistry,
+    actor_name: &str,
+    outbound_name: &str,
+) -> Option<ChannelTap<T>> {
+    registry
+        .get(&tap_label(actor_name, outbound_name))
+        .and_then(|tap| tap.downcast_ref::<ChannelTap<T>>())
+        .cloned()
+}
+
+/// Tap on an outbound channel, to inspect its messages at runtime without defining an actor.
+///
+/// A channel is made tappable during configuration using [OutboundChannel::tap()]. Receivers can
+/// then be attached at any time, also while the pipeline is running, using
+/// [ChannelTap::receiver()], [Pipeline::tap()] or [RunningPipeline::tap()]. Each receiver gets a
+/// copy of every message sent after it was attached:
+///
+/// ``` ignore
+/// let pipeline = Hollywood::configure(&mut |context| {
+///     // ...
+///     moving_average.outbound.average.tap(context);
+/// });
+/// let running = pipeline.spawn();
+/// let mut average = running.tap::<f64>("MovingAverage_0", "average", 16).unwrap();
+/// assert_eq!(average.recv().await, Some(1.5));
+/// ```
+///
+/// Taps never slow down the pipeline: if a receiver is full, the message is dropped for this
+/// receiver. Receivers which were dropped are detached. Without receivers, a tap costs a lock per
+/// message, and messages are not cloned.
+///
+/// The tap is a cheap handle, i.e. clones share the same receivers.
+pub struct ChannelTap<T> {
+    label: String,
+    senders: Arc<Mutex<Vec<tokio::sync::mpsc::Sender<T>>>>,
+}
+
+impl<T> Clone for ChannelTap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            senders: self.senders.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ChannelTap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelTap")
+            .field("label", &self.label)
+            .field("num_receivers", &self.num_receivers())
+            .finish()
+    }
+}
+
+impl<T> ChannelTap<T> {
+    /// Label of the tapped channel, as "ACTOR.CHANNEL".
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Attaches a new receiver, which buffers up to `capacity` messages.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn receiver(&self, capacity: usize) -> tokio::sync::mpsc::Receiver<T> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+        self.senders.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Number of attached receivers.
+    pub fn num_receivers(&self) -> usize {
+        self.senders
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|sender| !sender.is_closed())
+            .count()
+    }
+}
+
+struct TapConnection<T> {
+    senders: Arc<Mutex<Vec<tokio::sync::mpsc::Sender<T>>>>,
+}
+
+impl<T: Clone + Send + Sync> IsGenericConnection<T> for TapConnection<T> {
+    fn send_impl(&self, msg: T) {
+        let mut senders = self.senders.lock().unwrap();
+        if senders.is_empty() {
+            return;
+        }
+        senders.retain(|sender| match sender.try_send(msg.clone()) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Closed(_)) => false,
+        });
+    }
+}
+
+impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
+    /// Makes this channel tappable, see [ChannelTap], and returns its tap.
+    ///
+    /// Tapping the same channel twice returns the same tap.
+    pub fn tap(&mut self, ctx: &mut Hollywood) -> ChannelTap<OutT> {
+        let label = tap_label(&self.actor_name, &self.name);
+        if let Some(tap) = find_tap(&ctx.taps, &self.actor_name, &self.name) {
+            return tap;
+        }
+        let tap = ChannelTap {
+            label: label.clone(),
+            senders: Arc::new(Mutex::new(vec![])),
+        };
+        let connection = ctx.decorate_connection(
+            Connection {
+                from_actor: self.actor_name.clone(),
+                from: self.name.clone(),
+                to_actor: "external".to_owned(),
+                to: "tap".to_owned(),
+                type_name: std::any::type_name::<OutT>(),
+            },
+            Arc::new(TapConnection {
+                senders: tap.senders.clone(),
+            }),
+        );
+        self.connection_register.push(connection);
+        ctx.taps.insert(label, Arc::new(tap.clone()));
+        tap
+    }
+}
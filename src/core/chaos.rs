@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+/// Fault to be injected into a running actor, see [ChaosController].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ChaosFault {
+    /// No fault, i.e. clears a previously injected delay.
+    #[default]
+    Clear,
+    /// The actor panics, as if its message handler failed. The failure is reported in the
+    /// [PipelineError](crate::PipelineError) returned by [Pipeline::run()](crate::Pipeline::run()).
+    Kill,
+    /// The actor stops processing messages and requests for the given duration. Pending messages
+    /// queue up in the meantime.
+    Stall(Duration),
+    /// The actor waits for the given duration before processing each message and request, until
+    /// the delay is cleared.
+    Delay(Duration),
+}
+
+/// Error returned by [ChaosController::inject()].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChaosCommandError {
+    /// Chaos commands are not enabled, see [Hollywood::enable_chaos_commands()].
+    ///
+    /// [Hollywood::enable_chaos_commands()]: crate::Hollywood::enable_chaos_commands()
+    Disabled,
+    /// There is no actor with the given name.
+    UnknownActor(String),
+}
+
+impl std::fmt::Display for ChaosCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChaosCommandError::Disabled => write!(f, "chaos commands are not enabled"),
+            ChaosCommandError::UnknownActor(name) => write!(f, "there is no actor named {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ChaosCommandError {}
+
+/// Controller to deliberately kill, stall or delay named actors of a running pipeline.
+///
+/// This is meant for resilience drills in integration environments, e.g. to rehearse
+/// supervision, watchdogs and arbitration. Chaos commands must be enabled explicitly using
+/// [Hollywood::enable_chaos_commands()], and the controller is obtained through
+/// [Pipeline::chaos_controller()] or [RunningPipeline::chaos_controller()]:
+///
+/// ``` ignore
+/// let running = pipeline.spawn();
+/// let chaos = running.chaos_controller();
+/// chaos.inject("Filter", ChaosFault::Stall(Duration::from_secs(2)))?;
+/// ```
+///
+/// Faults only apply to actors which process messages, i.e. actors created by one of the actor
+/// macros, and not to e.g. a [BackgroundActor](crate::actors::BackgroundActor). Faults injected
+/// while the pipeline is not running have no effect.
+///
+/// [Hollywood::enable_chaos_commands()]: crate::Hollywood::enable_chaos_commands()
+/// [Pipeline::chaos_controller()]: crate::Pipeline::chaos_controller()
+/// [RunningPipeline::chaos_controller()]: crate::RunningPipeline::chaos_controller()
+#[derive(Clone, Debug, Default)]
+pub struct ChaosController {
+    senders: Option<Arc<HashMap<String, tokio::sync::watch::Sender<ChaosFault>>>>,
+}
+
+impl ChaosController {
+    pub(crate) fn new<'a>(actor_names: impl Iterator<Item = &'a String>) -> Self {
+        Self {
+            senders: Some(Arc::new(
+                actor_names
+                    .map(|name| {
+                        (
+                            name.clone(),
+                            tokio::sync::watch::channel(ChaosFault::Clear).0,
+                        )
+                    })
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Injects the fault into the actor with the given name.
+    pub fn inject(&self, actor_name: &str, fault: ChaosFault) -> Result<(), ChaosCommandError> {
+        let senders = self.senders.as_ref().ok_or(ChaosCommandError::Disabled)?;
+        let sender = senders
+            .get(actor_name)
+            .ok_or_else(|| ChaosCommandError::UnknownActor(actor_name.to_owned()))?;
+        warn!("{}: chaos command {:?}", actor_name, fault);
+        sender.send_replace(fault);
+        Ok(())
+    }
+
+    pub(crate) fn subscribe(
+        &self,
+        actor_name: &str,
+    ) -> Option<tokio::sync::watch::Receiver<ChaosFault>> {
+        Some(self.senders.as_ref()?.get(actor_name)?.subscribe())
+    }
+}
+
+/// Waits for the next fault to be injected. Never resolves if chaos commands are disabled.
+pub(crate) async fn next_chaos_fault(
+    chaos: &mut Option<tokio::sync::watch::Receiver<ChaosFault>>,
+) -> ChaosFault {
+    if let Some(receiver) = chaos {
+        if receiver.changed().await.is_ok() {
+            return receiver.borrow_and_update().clone();
+        }
+        // The controller was dropped, hence no more faults will be injected.
+        *chaos = None;
+    }
+    std::future::pending().await
+}
@@ -0,0 +1,189 @@
+use crate::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tracing::info;
+use tracing::warn;
+
+/// Stored state of a single actor, see [PipelineSnapshot].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ActorCheckpoint {
+    /// Schema of the state type at the time the state was stored.
+    pub schema: StateSchema,
+    /// The serialized state.
+    pub state: serde_json::Value,
+}
+
+impl ActorCheckpoint {
+    /// Stores the given state along with its schema.
+    pub fn new<State: Serialize>(
+        schema: StateSchema,
+        state: &State,
+    ) -> Result<Self, CheckpointError> {
+        Ok(Self {
+            schema,
+            state: serde_json::to_value(state)?,
+        })
+    }
+
+    /// Restores the state, if the stored schema matches the given schema of the current state
+    /// type.
+    pub fn restore<State: DeserializeOwned>(
+        &self,
+        schema: &StateSchema,
+    ) -> Result<State, CheckpointError> {
+        schema.check(&self.schema)?;
+        Ok(serde_json::from_value(self.state.clone())?)
+    }
+}
+
+/// Snapshot of the states of the checkpointed actors of a pipeline.
+///
+/// It is taken using [Pipeline::snapshot()] and restored using
+/// [Hollywood::configure_from_snapshot()]. The actor states are stored by actor name, hence the
+/// pipeline must be configured the same way, so that the actors get the same unique names.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PipelineSnapshot {
+    /// Stored states by actor name.
+    pub actors: BTreeMap<String, ActorCheckpoint>,
+}
+
+impl PipelineSnapshot {
+    /// Stored state of the given actor, if any.
+    pub fn actor(&self, actor_name: &str) -> Option<&ActorCheckpoint> {
+        self.actors.get(actor_name)
+    }
+
+    /// Returns the snapshot as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("snapshot is valid JSON")
+    }
+
+    /// Parses a snapshot from JSON, as returned by [PipelineSnapshot::to_json()].
+    pub fn from_json(json: &str) -> Result<Self, CheckpointError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Writes the snapshot as JSON to the given file, which is replaced atomically.
+    ///
+    /// Hence, the file always contains a complete snapshot, even if the process crashes while
+    /// writing.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let path = path.as_ref();
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, self.to_json())?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Reads a snapshot from the given file, as written by [PipelineSnapshot::save()].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// Error when storing or restoring a snapshot.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// The stored state was created by a different version of the state type.
+    SchemaMismatch(StateSchemaMismatch),
+    /// The state could not be serialized or deserialized.
+    Serde(serde_json::Error),
+    /// The snapshot file could not be read or written.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::SchemaMismatch(mismatch) => write!(f, "{}", mismatch),
+            CheckpointError::Serde(err) => write!(f, "invalid checkpoint: {}", err),
+            CheckpointError::Io(err) => write!(f, "checkpoint I/O failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<StateSchemaMismatch> for CheckpointError {
+    fn from(mismatch: StateSchemaMismatch) -> Self {
+        CheckpointError::SchemaMismatch(mismatch)
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(err: serde_json::Error) -> Self {
+        CheckpointError::Serde(err)
+    }
+}
+
+impl From<std::io::Error> for CheckpointError {
+    fn from(err: std::io::Error) -> Self {
+        CheckpointError::Io(err)
+    }
+}
+
+type StateCheckpointer = Box<dyn Fn(&dyn Any) -> Option<ActorCheckpoint> + Send + Sync>;
+
+/// The checkpointed actors of a pipeline, and the snapshot their states are restored from.
+#[derive(Default)]
+pub(crate) struct Checkpoints {
+    pub(crate) restore_from: Option<PipelineSnapshot>,
+    checkpointers: BTreeMap<String, StateCheckpointer>,
+}
+
+impl Checkpoints {
+    /// Registers the actor to be part of the snapshots, and replaces its initial state with the
+    /// stored one, if any.
+    ///
+    /// A stored state of a different schema is not restored, but the initial state is kept.
+    pub(crate) fn register<State: Serialize + DeserializeOwned + 'static>(
+        &mut self,
+        actor_name: &str,
+        schema: StateSchema,
+        state: &mut State,
+    ) {
+        if let Some(checkpoint) = self
+            .restore_from
+            .as_ref()
+            .and_then(|snapshot| snapshot.actor(actor_name))
+        {
+            match checkpoint.restore(&schema) {
+                Ok(restored) => {
+                    info!("{}: state restored from snapshot", actor_name);
+                    *state = restored;
+                }
+                Err(err) => warn!(
+                    "{}: state not restored from snapshot, keeping initial state: {}",
+                    actor_name, err
+                ),
+            }
+        }
+        let name = actor_name.to_owned();
+        self.checkpointers.insert(
+            actor_name.to_owned(),
+            Box::new(move |state| {
+                let state = state.downcast_ref::<State>()?;
+                ActorCheckpoint::new(schema.clone(), state)
+                    .map_err(|err| warn!("{}: state not stored in snapshot: {}", name, err))
+                    .ok()
+            }),
+        );
+    }
+
+    pub(crate) fn snapshot(&self, actors: &[Box<dyn IsActorNode + Send>]) -> PipelineSnapshot {
+        let mut snapshot = PipelineSnapshot::default();
+        for actor in actors.iter() {
+            if let Some(checkpointer) = self.checkpointers.get(actor.name()) {
+                actor.inspect_state(&mut |state| {
+                    if let Some(checkpoint) = checkpointer(state) {
+                        snapshot.actors.insert(actor.name().clone(), checkpoint);
+                    }
+                });
+            }
+        }
+        snapshot
+    }
+}
@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Source of time for periodic and other time-based actors, see [Hollywood::set_clock()].
+///
+/// Times are durations since the origin of the clock, e.g. the start of the pipeline.
+#[async_trait]
+pub trait IsClock: Send + Sync + 'static {
+    /// Current time of the clock.
+    fn now(&self) -> Duration;
+
+    /// Waits until the clock reached the given time.
+    async fn sleep_until(&self, deadline: Duration);
+}
+
+/// Real time clock, based on the tokio timer.
+#[derive(Clone, Copy, Debug)]
+pub struct RealClock {
+    origin: tokio::time::Instant,
+}
+
+impl RealClock {
+    /// Creates a real time clock with the current instant as origin.
+    pub fn new() -> Self {
+        Self {
+            origin: tokio::time::Instant::now(),
+        }
+    }
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl IsClock for RealClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        tokio::time::sleep_until(self.origin + deadline).await;
+    }
+}
+
+/// Virtual clock which only advances when told to, e.g. to run simulations faster than real time
+/// or to test time-based actors deterministically:
+///
+/// ``` ignore
+/// let clock = Arc::new(SimClock::new());
+/// let pipeline = Hollywood::configure(&mut |context| {
+///     context.set_clock(clock.clone());
+///     // ...
+/// });
+/// let running = pipeline.spawn();
+/// while clock.now() < Duration::from_secs(10) {
+///     clock.step();
+///     tokio::task::yield_now().await;
+/// }
+/// ```
+pub struct SimClock {
+    now: tokio::sync::watch::Sender<Duration>,
+    /// Number of sleepers per deadline.
+    deadlines: Mutex<BTreeMap<Duration, usize>>,
+}
+
+impl SimClock {
+    /// Creates a virtual clock at time zero.
+    pub fn new() -> Self {
+        Self {
+            now: tokio::sync::watch::channel(Duration::ZERO).0,
+            deadlines: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Advances the clock by the given duration, and wakes up all sleepers whose deadline was
+    /// reached.
+    pub fn advance(&self, duration: Duration) {
+        self.now.send_modify(|now| *now += duration);
+    }
+
+    /// Advances the clock to the earliest deadline of all sleepers. Returns the new time, or None
+    /// if nobody is sleeping.
+    pub fn step(&self) -> Option<Duration> {
+        let next = self.next_deadline()?;
+        self.now.send_modify(|now| *now = (*now).max(next));
+        Some(self.now())
+    }
+
+    /// Earliest deadline of all sleepers, if any.
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.deadlines.lock().unwrap().keys().next().copied()
+    }
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Registration of a sleeper of a [SimClock], which is removed when dropped, e.g. if the sleep
+/// is cancelled.
+struct SimSleeper<'a> {
+    clock: &'a SimClock,
+    deadline: Duration,
+}
+
+impl Drop for SimSleeper<'_> {
+    fn drop(&mut self) {
+        let mut deadlines = self.clock.deadlines.lock().unwrap();
+        if let Some(count) = deadlines.get_mut(&self.deadline) {
+            *count -= 1;
+            if *count == 0 {
+                deadlines.remove(&self.deadline);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl IsClock for SimClock {
+    fn now(&self) -> Duration {
+        *self.now.borrow()
+    }
+
+    async fn sleep_until(&self, deadline: Duration) {
+        *self.deadlines.lock().unwrap().entry(deadline).or_default() += 1;
+        let _sleeper = SimSleeper {
+            clock: self,
+            deadline,
+        };
+        let mut now = self.now.subscribe();
+        // The sender is owned by the clock, hence it outlives the receiver.
+        let _ = now.wait_for(|now| *now >= deadline).await;
+    }
+}
+
+/// State of a time-based actor, which is run by the [ClockRunner](crate::ClockRunner).
+///
+/// The runner keeps the state informed about the current time of the pipeline clock, and calls
+/// [HasClockTimer::on_deadline()] once the clock reached the deadline of the state.
+pub trait HasClockTimer<Prop, Outbound>: Send + Sync + 'static {
+    /// Sets the current time of the clock. It is called right before each message or request is
+    /// handled, and right before [HasClockTimer::on_deadline()].
+    fn set_now(&mut self, now: Duration);
+
+    /// Time at which [HasClockTimer::on_deadline()] shall be called next, if any.
+    ///
+    /// By default, there is no deadline.
+    fn next_deadline(&self, _prop: &Prop) -> Option<Duration> {
+        None
+    }
+
+    /// Called once the clock reached the time returned by [HasClockTimer::next_deadline()].
+    fn on_deadline(&mut self, _prop: &Prop, _outbound: &Outbound) {}
+}
+
+/// Type-erased [HasClockTimer] of the actor state, together with the clock of the pipeline.
+pub(crate) struct ClockTimer<Prop, State, Outbound> {
+    pub(crate) clock: Arc<dyn IsClock>,
+    set_now: fn(&mut State, Duration),
+    next_deadline: fn(&State, &Prop) -> Option<Duration>,
+    on_deadline: fn(&mut State, &Prop, &Outbound),
+}
+
+impl<Prop, State: HasClockTimer<Prop, Outbound>, Outbound> Default
+    for ClockTimer<Prop, State, Outbound>
+{
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(RealClock::new()),
+            set_now: State::set_now,
+            next_deadline: State::next_deadline,
+            on_deadline: State::on_deadline,
+        }
+    }
+}
+
+impl<Prop, State, Outbound> ClockTimer<Prop, State, Outbound> {
+    pub(crate) fn set_now(&self, state: &mut State) {
+        (self.set_now)(state, self.clock.now());
+    }
+
+    pub(crate) fn next_deadline(&self, prop: &Prop, state: &State) -> Option<Duration> {
+        (self.next_deadline)(state, prop)
+    }
+
+    pub(crate) fn on_deadline(&self, prop: &Prop, state: &mut State, outbound: &Outbound) {
+        self.set_now(state);
+        (self.on_deadline)(state, prop, outbound);
+    }
+}
+
+/// Waits until the clock reached the deadline. Never resolves if there is no deadline.
+pub(crate) async fn sleep_until_deadline(deadline: Option<(&dyn IsClock, Duration)>) {
+    if let Some((clock, deadline)) = deadline {
+        clock.sleep_until(deadline).await;
+        return;
+    }
+    std::future::pending().await
+}
+
+/// Lets the actors of a pipeline on a [SimClock] process their pending messages, e.g. in tests
+/// which run on a single-threaded tokio runtime.
+#[cfg(test)]
+pub(crate) async fn settle() {
+    for _ in 0..20 {
+        tokio::task::yield_now().await;
+    }
+}
+
+/// Advances the virtual clock by the given duration, one deadline at a time, and lets the actors
+/// settle after each step.
+#[cfg(test)]
+pub(crate) async fn advance_settled(clock: &SimClock, duration: Duration) {
+    let target = clock.now() + duration;
+    settle().await;
+    while let Some(deadline) = clock.next_deadline().filter(|t| *t <= target) {
+        clock.advance(deadline.saturating_sub(clock.now()));
+        settle().await;
+    }
+    clock.advance(target.saturating_sub(clock.now()));
+    settle().await;
+}
@@ -16,6 +16,9 @@ pub mod outbound_connection;
 /// Note that the implementation is a bit over-engineered and can likely be simplified.
 pub mod request_connection;
 
+/// Middleware to decorate connections, e.g. for logging, metrics or fault injection.
+pub mod middleware;
+
 type ConnectionRegister<T> = Vec<Arc<dyn IsGenericConnection<T> + Send + Sync>>;
 
 /// Connection
@@ -26,7 +29,7 @@ pub enum ConnectionEnum<T> {
     Active(ActiveConnection<T>),
 }
 
-type RequestConnectionRegister<T> = Option<Arc<dyn GenericRequestConnection<T> + Send + Sync>>;
+type RequestConnectionRegister<T> = Vec<Arc<dyn GenericRequestConnection<T> + Send + Sync>>;
 
 pub(crate) enum RequestConnectionEnum<T> {
     Config(RequestConnectionConfig<T>),
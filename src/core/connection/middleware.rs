@@ -0,0 +1,52 @@
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use std::fmt::Debug;
+use std::sync::Arc;
+use tracing::trace;
+
+/// Middleware which is applied to every message sent through a decorated connection.
+///
+/// Middlewares are registered using [Hollywood::add_connection_middleware()] and decorate all
+/// outbound-to-inbound connections created afterwards. They enable cross-cutting behavior such as
+/// logging, metrics or fault injection without touching every connect call.
+pub trait IsConnectionMiddleware: Send + Sync + 'static {
+    /// Called for each message before it is forwarded to the inbound channel.
+    ///
+    /// Return false to drop the message.
+    fn on_message(&self, connection: &Connection, msg: &dyn Debug) -> bool;
+}
+
+/// Middleware which logs every message at trace level.
+#[derive(Clone, Debug, Default)]
+pub struct TraceMiddleware;
+
+impl IsConnectionMiddleware for TraceMiddleware {
+    fn on_message(&self, connection: &Connection, msg: &dyn Debug) -> bool {
+        trace!(
+            "{}.{} -> {}.{}: {:?}",
+            connection.from_actor,
+            connection.from,
+            connection.to_actor,
+            connection.to,
+            msg
+        );
+        true
+    }
+}
+
+pub(crate) struct MiddlewareConnection<T> {
+    pub(crate) inner: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    pub(crate) connection: Connection,
+    pub(crate) middlewares: Vec<Arc<dyn IsConnectionMiddleware>>,
+}
+
+impl<T: Debug + Send + Sync> IsGenericConnection<T> for MiddlewareConnection<T> {
+    fn send_impl(&self, msg: T) {
+        for middleware in self.middlewares.iter() {
+            if !middleware.on_message(&self.connection, &msg) {
+                return;
+            }
+        }
+        self.inner.send_impl(msg);
+    }
+}
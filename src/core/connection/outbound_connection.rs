@@ -1,36 +1,94 @@
 use crate::core::connection::ConnectionRegister;
 use crate::prelude::*;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::warn;
 
-/// Connection configuration
-pub struct ConnectionConfig<T> {
-    /// List of connections
-    pub connection_register: ConnectionRegister<T>,
-    /// Launch pad for new connections
-    pub maybe_register_launch_pad: Option<tokio::sync::oneshot::Sender<ConnectionRegister<T>>>,
-    /// Landing pad for new connections
-    pub maybe_register_landing_pad: Option<tokio::sync::oneshot::Receiver<ConnectionRegister<T>>>,
+type PendingSend<T> = Box<dyn FnOnce(&ConnectionRegister<T>) + Send>;
+
+/// Messages sent before the connection is activated, shared between the configuration and the
+/// active connection.
+type PendingSends<T> = Arc<Mutex<Vec<PendingSend<T>>>>;
+
+/// List of connections, shared between the configuration and the active connection. Hence, the
+/// configuration may outlive the activation, e.g. if an actor is tested by an
+/// [ActorHarness](crate::testing::ActorHarness).
+type SharedRegister<T> = Arc<Mutex<ConnectionRegister<T>>>;
+
+/// Behavior of an outbound channel if a message is sent before the pipeline is started, i.e.
+/// during configuration. The same applies to requests sent through out-request channels.
+///
+/// The policy is set for all outbound channels using [Hollywood::set_pre_start_send_policy()].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreStartSendPolicy {
+    /// Messages are buffered and sent in order once the sending actor starts. If the buffer is
+    /// full, further messages are dropped with a warning.
+    Buffer {
+        /// Maximal number of buffered messages per outbound channel.
+        capacity: usize,
+    },
+    /// Sending before the pipeline is started is an error and panics.
+    Panic,
+}
+
+impl PreStartSendPolicy {
+    /// Default capacity of [PreStartSendPolicy::Buffer].
+    pub const DEFAULT_CAPACITY: usize = 1024;
 }
 
-impl<T> Drop for ConnectionConfig<T> {
-    fn drop(&mut self) {
-        if let Some(connection_launch_pad) = self.maybe_register_launch_pad.take() {
-            let connection_register = std::mem::take(&mut self.connection_register);
-            let _ = connection_launch_pad.send(connection_register);
-        } else {
-            panic!("ConnectionConfig dropped when launch pad is is empty");
+impl Default for PreStartSendPolicy {
+    fn default() -> Self {
+        Self::Buffer {
+            capacity: Self::DEFAULT_CAPACITY,
         }
     }
 }
 
+/// Connection configuration
+pub struct ConnectionConfig<T> {
+    /// List of connections
+    pub connection_register: SharedRegister<T>,
+    pub(crate) pre_start_policy: PreStartSendPolicy,
+    pub(crate) pending: PendingSends<T>,
+    /// Number of connections made or attempted, if the channel admits a single connection only,
+    /// see [ConnectionEnum::set_exclusive()].
+    pub(crate) exclusive_fan_out: Option<Arc<AtomicUsize>>,
+}
+
 impl<T> ConnectionConfig<T> {
     /// Create connection configuration
     pub fn new() -> Self {
-        let (connection_launch_pad, connection_landing_pad) = tokio::sync::oneshot::channel();
+        Self::with_pre_start_policy(PreStartSendPolicy::default())
+    }
+
+    /// Create connection configuration with the given behavior for sends before activation.
+    pub fn with_pre_start_policy(pre_start_policy: PreStartSendPolicy) -> Self {
         Self {
-            connection_register: vec![],
-            maybe_register_launch_pad: Some(connection_launch_pad),
-            maybe_register_landing_pad: Some(connection_landing_pad),
+            connection_register: Arc::new(Mutex::new(vec![])),
+            pre_start_policy,
+            pending: Arc::new(Mutex::new(vec![])),
+            exclusive_fan_out: None,
+        }
+    }
+
+    fn push_pending(&self, pending_send: PendingSend<T>) {
+        match self.pre_start_policy {
+            PreStartSendPolicy::Buffer { capacity } => {
+                let mut pending = self.pending.lock().unwrap();
+                if pending.len() >= capacity {
+                    warn!(
+                        "Message sent before pipeline start dropped, buffer of {} is full",
+                        capacity
+                    );
+                    return;
+                }
+                pending.push(pending_send);
+            }
+            PreStartSendPolicy::Panic => {
+                panic!("Cannot send to config connection, the pipeline has not started yet");
+            }
         }
     }
 }
@@ -45,27 +103,79 @@ impl<T> Default for ConnectionConfig<T> {
 pub struct ActiveConnection<T> {
     /// List of connections
     pub maybe_registers: Option<ConnectionRegister<T>>,
-    /// Landing pad for new connections
-    pub maybe_register_landing_pad: Option<tokio::sync::oneshot::Receiver<ConnectionRegister<T>>>,
+    pub(crate) connection_register: SharedRegister<T>,
+    pub(crate) pending: PendingSends<T>,
 }
 
-impl<T: Clone + Send + Sync + std::fmt::Debug + 'static> Default for ConnectionEnum<T> {
+impl<T: Send + Sync + 'static> Default for ConnectionEnum<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T: Clone + Send + Sync + std::fmt::Debug + 'static> ConnectionEnum<T> {
+impl<T: Send + Sync + 'static> ConnectionEnum<T> {
     /// new connection
     pub fn new() -> Self {
         Self::Config(ConnectionConfig::new())
     }
 
+    /// new connection with the given behavior for sends before activation
+    pub fn with_pre_start_policy(pre_start_policy: PreStartSendPolicy) -> Self {
+        Self::Config(ConnectionConfig::with_pre_start_policy(pre_start_policy))
+    }
+
     /// push connection
+    ///
+    /// If the channel is exclusive and already connected, the connection is rejected, see
+    /// [ConnectionEnum::set_exclusive()].
     pub fn push(&mut self, connection: Arc<dyn IsGenericConnection<T> + Send + Sync>) {
+        if self.admit_connection() {
+            self.push_admitted(connection);
+        }
+    }
+
+    /// Makes the channel exclusive, i.e. it admits a single connection only, and returns the
+    /// number of connections made or attempted so far. Further connections are rejected, and
+    /// reported once the pipeline is configured, see [TopologyIssue::ExclusiveFanOut].
+    pub(crate) fn set_exclusive(&mut self) -> Arc<AtomicUsize> {
+        match self {
+            Self::Config(config) => config
+                .exclusive_fan_out
+                .get_or_insert_with(|| {
+                    Arc::new(AtomicUsize::new(
+                        config.connection_register.lock().unwrap().len(),
+                    ))
+                })
+                .clone(),
+            Self::Active(_) => {
+                panic!("Cannot make active connection exclusive");
+            }
+        }
+    }
+
+    /// Returns false if the channel is exclusive and already connected. Each connection made or
+    /// attempted on an exclusive channel is counted.
+    pub(crate) fn admit_connection(&self) -> bool {
+        match self {
+            Self::Config(config) => match &config.exclusive_fan_out {
+                Some(fan_out) => {
+                    fan_out.fetch_add(1, Ordering::Relaxed);
+                    config.connection_register.lock().unwrap().is_empty()
+                }
+                None => true,
+            },
+            Self::Active(_) => true,
+        }
+    }
+
+    /// Pushes a connection which was admitted already, see [ConnectionEnum::admit_connection()].
+    pub(crate) fn push_admitted(
+        &mut self,
+        connection: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    ) {
         match self {
             Self::Config(config) => {
-                config.connection_register.push(connection);
+                config.connection_register.lock().unwrap().push(connection);
             }
             Self::Active(_) => {
                 panic!("Cannot push to active connection");
@@ -73,26 +183,82 @@ impl<T: Clone + Send + Sync + std::fmt::Debug + 'static> ConnectionEnum<T> {
         }
     }
 
-    pub(crate) fn send(&self, msg: T) {
+    /// Number of connections, i.e. the fan-out of the outbound channel.
+    pub fn len(&self) -> usize {
         match self {
-            Self::Config(_) => {
-                panic!("Cannot send to config connection");
+            Self::Config(config) => config.connection_register.lock().unwrap().len(),
+            Self::Active(active) => active.maybe_registers.as_ref().map_or(0, |r| r.len()),
+        }
+    }
+
+    /// Returns true if there are no connections.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sends the message to the single connection, if any, without cloning it.
+    ///
+    /// Panics if there is more than one connection, which is prevented for exclusive channels,
+    /// see [ConnectionEnum::set_exclusive()].
+    pub(crate) fn send_exclusive(&self, msg: T) {
+        match self {
+            Self::Config(config) => {
+                config.push_pending(Box::new(move |registers: &ConnectionRegister<T>| {
+                    if let Some(connection) = registers.first() {
+                        connection.send_impl(msg);
+                    }
+                }));
             }
             Self::Active(active) => {
-                for i in active.maybe_registers.as_ref().unwrap().iter() {
-                    i.send_impl(msg.clone());
+                let registers = active.maybe_registers.as_ref().unwrap();
+                assert!(
+                    registers.len() <= 1,
+                    "Cannot send to {} connections without cloning the message",
+                    registers.len()
+                );
+                if let Some(connection) = registers.first() {
+                    connection.send_impl(msg);
                 }
             }
         }
     }
 }
 
+impl<T: Clone + Send + Sync + 'static> ConnectionEnum<T> {
+    /// Sends the message to all connections.
+    ///
+    /// The message is cloned for all but the last connection, hence there is no clone for a
+    /// fan-out of one.
+    pub(crate) fn send(&self, msg: T) {
+        match self {
+            Self::Config(config) => {
+                config.push_pending(Box::new(move |registers: &ConnectionRegister<T>| {
+                    send_to_all(registers, msg)
+                }));
+            }
+            Self::Active(active) => {
+                send_to_all(active.maybe_registers.as_ref().unwrap(), msg);
+            }
+        }
+    }
+}
+
+fn send_to_all<T: Clone>(registers: &ConnectionRegister<T>, msg: T) {
+    if let Some((last, others)) = registers.split_last() {
+        for i in others.iter() {
+            i.send_impl(msg.clone());
+        }
+        last.send_impl(msg);
+    }
+}
+
 impl<T> HasActivate for ConnectionEnum<T> {
     fn extract(&mut self) -> Self {
         match self {
             Self::Config(config) => Self::Active(ActiveConnection {
                 maybe_registers: None,
-                maybe_register_landing_pad: Some(config.maybe_register_landing_pad.take().unwrap()),
+                connection_register: config.connection_register.clone(),
+                pending: config.pending.clone(),
             }),
             Self::Active(_) => {
                 panic!("Cannot extract active connection");
@@ -106,12 +272,11 @@ impl<T> HasActivate for ConnectionEnum<T> {
                 panic!("Cannot activate config connection");
             }
             Self::Active(active) => {
-                let connection_register = active
-                    .maybe_register_landing_pad
-                    .take()
-                    .unwrap()
-                    .try_recv()
-                    .unwrap();
+                let connection_register = active.connection_register.lock().unwrap().clone();
+                // Flush the messages which were sent during configuration, in order.
+                for pending_send in std::mem::take(&mut *active.pending.lock().unwrap()) {
+                    pending_send(&connection_register);
+                }
                 active.maybe_registers = Some(connection_register);
             }
         }
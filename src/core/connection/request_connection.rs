@@ -1,8 +1,11 @@
+use crate::compute::stepping::spawn_tracked;
 use crate::core::connection::RequestConnectionEnum;
 use crate::core::connection::RequestConnectionRegister;
+use crate::core::out_request::discard_requests;
 use crate::prelude::*;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::sync::Mutex;
 use tokio::sync::mpsc::error::SendError;
 use tracing::warn;
 
@@ -23,11 +26,13 @@ impl<T: Send + Sync, M: IsInRequestMessageNew<T>> GenericRequestConnection<T>
     fn send_impl(&self, msg: T) {
         let msg = M::new(self.inbound_channel.clone(), msg);
         let c = self.sender.clone();
-        let handler = tokio::spawn(async move {
+        let handler = spawn_tracked(None, async move {
             match c.send(msg) {
                 Ok(_) => {}
                 Err(SendError(e)) => {
+                    // The replying actor stopped, e.g. during shutdown.
                     warn!("Send request message error: {:?}", e);
+                    discard_requests(e);
                 }
             }
         });
@@ -35,40 +40,86 @@ impl<T: Send + Sync, M: IsInRequestMessageNew<T>> GenericRequestConnection<T>
     }
 }
 
-pub(crate) struct RequestConnectionConfig<T> {
-    pub connection_register: RequestConnectionRegister<T>,
-    pub maybe_register_launch_pad:
-        Option<tokio::sync::oneshot::Sender<RequestConnectionRegister<T>>>,
-    pub maybe_register_landing_pad:
-        Option<tokio::sync::oneshot::Receiver<RequestConnectionRegister<T>>>,
+/// Requests sent before the request channel is activated, which are dispatched in order once the
+/// sending actor starts, according to the [PreStartSendPolicy].
+///
+/// The buffer is shared between the configuration and the active request channel.
+pub(crate) struct PreStartRequests<P> {
+    policy: PreStartSendPolicy,
+    pending: Arc<Mutex<Vec<P>>>,
 }
 
-impl<T> Drop for RequestConnectionConfig<T> {
-    fn drop(&mut self) {
-        if let Some(connection_launch_pad) = self.maybe_register_launch_pad.take() {
-            let connection_register = std::mem::take(&mut self.connection_register);
-            let _ = connection_launch_pad.send(connection_register);
-        } else {
-            panic!("ConnectionConfig dropped when launch pad is is empty");
+impl<P> Clone for PreStartRequests<P> {
+    fn clone(&self) -> Self {
+        Self {
+            policy: self.policy,
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<P> Default for PreStartRequests<P> {
+    fn default() -> Self {
+        Self {
+            policy: PreStartSendPolicy::default(),
+            pending: Arc::new(Mutex::new(vec![])),
         }
     }
 }
 
+impl<P> PreStartRequests<P> {
+    pub(crate) fn set_policy(&mut self, policy: PreStartSendPolicy) {
+        self.policy = policy;
+    }
+
+    /// Buffers the request, or drops it with a warning if the buffer is full.
+    pub(crate) fn push(&self, channel_name: &str, pending_request: P) {
+        match self.policy {
+            PreStartSendPolicy::Buffer { capacity } => {
+                let mut pending = self.pending.lock().unwrap();
+                if pending.len() >= capacity {
+                    warn!(
+                        "{}: request sent before pipeline start dropped, buffer of {} is full",
+                        channel_name, capacity
+                    );
+                    return;
+                }
+                pending.push(pending_request);
+            }
+            PreStartSendPolicy::Panic => {
+                panic!(
+                    "Cannot send request to config connection {}, the pipeline has not started yet",
+                    channel_name
+                );
+            }
+        }
+    }
+
+    /// Takes the buffered requests, in the order they were sent.
+    pub(crate) fn take(&self) -> Vec<P> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+}
+
+/// List of connections, shared between the configuration and the active connection, see
+/// [ConnectionConfig].
+type SharedRequestRegister<T> = Arc<Mutex<RequestConnectionRegister<T>>>;
+
+pub(crate) struct RequestConnectionConfig<T> {
+    pub connection_register: SharedRequestRegister<T>,
+}
+
 impl<T> RequestConnectionConfig<T> {
     pub fn new() -> Self {
-        let (connection_launch_pad, connection_landing_pad) = tokio::sync::oneshot::channel();
         Self {
-            connection_register: None,
-            maybe_register_launch_pad: Some(connection_launch_pad),
-            maybe_register_landing_pad: Some(connection_landing_pad),
+            connection_register: Arc::new(Mutex::new(vec![])),
         }
     }
 }
 
 pub(crate) struct ActiveRequestConnection<T> {
     pub maybe_registers: Option<RequestConnectionRegister<T>>,
-    pub maybe_register_landing_pad:
-        Option<tokio::sync::oneshot::Receiver<RequestConnectionRegister<T>>>,
+    pub connection_register: SharedRequestRegister<T>,
 }
 
 impl<T: Send + Sync + std::fmt::Debug + 'static> RequestConnectionEnum<T> {
@@ -79,8 +130,7 @@ impl<T: Send + Sync + std::fmt::Debug + 'static> RequestConnectionEnum<T> {
     pub fn push(&mut self, connection: Arc<dyn GenericRequestConnection<T> + Send + Sync>) {
         match self {
             Self::Config(config) => {
-                assert!(config.connection_register.is_none());
-                config.connection_register = Some(connection);
+                config.connection_register.lock().unwrap().push(connection);
             }
             Self::Active(_) => {
                 panic!("Cannot push to active connection");
@@ -88,17 +138,19 @@ impl<T: Send + Sync + std::fmt::Debug + 'static> RequestConnectionEnum<T> {
         }
     }
 
-    pub(crate) fn send(&self, msg: T) {
+    /// Returns true if the channel is not activated yet, i.e. requests are subject to the
+    /// [PreStartSendPolicy], see [PreStartRequests].
+    pub(crate) fn is_config(&self) -> bool {
+        matches!(self, Self::Config(_))
+    }
+
+    /// Returns the connected in-request channels.
+    pub(crate) fn connections(&self) -> &RequestConnectionRegister<T> {
         match self {
             Self::Config(_) => {
                 panic!("Cannot send to config connection");
             }
-            Self::Active(active) => {
-                let maybe_connection = active.maybe_registers.as_ref().unwrap();
-                if maybe_connection.is_some() {
-                    maybe_connection.as_ref().unwrap().send_impl(msg);
-                }
-            }
+            Self::Active(active) => active.maybe_registers.as_ref().unwrap(),
         }
     }
 }
@@ -108,7 +160,7 @@ impl<T> HasActivate for RequestConnectionEnum<T> {
         match self {
             Self::Config(config) => Self::Active(ActiveRequestConnection {
                 maybe_registers: None,
-                maybe_register_landing_pad: Some(config.maybe_register_landing_pad.take().unwrap()),
+                connection_register: config.connection_register.clone(),
             }),
             Self::Active(_) => {
                 panic!("Cannot extract active connection");
@@ -122,13 +174,7 @@ impl<T> HasActivate for RequestConnectionEnum<T> {
                 panic!("Cannot activate config connection");
             }
             Self::Active(active) => {
-                let connection_register = active
-                    .maybe_register_landing_pad
-                    .take()
-                    .unwrap()
-                    .try_recv()
-                    .unwrap();
-                active.maybe_registers = Some(connection_register);
+                active.maybe_registers = Some(active.connection_register.lock().unwrap().clone());
             }
         }
     }
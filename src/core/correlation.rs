@@ -0,0 +1,150 @@
+use crate::prelude::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokio::sync::mpsc::error::SendError;
+
+static NEXT_CORRELATION_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static CURRENT_CORRELATION_ID: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Returns the correlation id of the message which is being handled on this thread, if any.
+///
+/// With message tracing enabled (see
+/// [Hollywood::enable_message_tracing()](crate::Hollywood::enable_message_tracing)), each
+/// message sent by a source actor starts a new correlation id, and all messages sent while
+/// handling a message carry its correlation id on to the downstream actors. It is also recorded
+/// in the `message` span of each handled message, so that the path of a message through the
+/// pipeline can be followed in the tracing output.
+pub fn current_correlation_id() -> Option<u64> {
+    CURRENT_CORRELATION_ID.with(|id| id.get())
+}
+
+/// Sets the current correlation id while the scope is alive, see [current_correlation_id()].
+pub(crate) struct CorrelationScope {
+    previous: Option<u64>,
+}
+
+impl CorrelationScope {
+    pub(crate) fn enter(id: Option<u64>) -> Self {
+        Self {
+            previous: CURRENT_CORRELATION_ID.with(|current| current.replace(id)),
+        }
+    }
+}
+
+impl Drop for CorrelationScope {
+    fn drop(&mut self) {
+        CURRENT_CORRELATION_ID.with(|current| current.set(self.previous));
+    }
+}
+
+/// Correlation ids of the messages queued for an inbound channel, in the order of the messages.
+///
+/// The ids are only recorded once the queue is activated, i.e. if message tracing is enabled and
+/// the receiving actor pops an id for each message it receives, see
+/// [IsActorNode::traces_messages()](crate::IsActorNode::traces_messages).
+#[derive(Debug, Default)]
+pub(crate) struct CorrelationQueue {
+    active: AtomicBool,
+    ids: Mutex<VecDeque<u64>>,
+}
+
+impl CorrelationQueue {
+    pub(crate) fn activate(&self) {
+        self.active.store(true, Ordering::Release);
+    }
+
+    fn pop(&self) -> Option<u64> {
+        self.ids.lock().unwrap().pop_front()
+    }
+}
+
+/// Sender of the messages of an inbound channel, which records the correlation id of each
+/// message alongside.
+#[derive(Debug)]
+pub(crate) struct InboundSender<M> {
+    sender: tokio::sync::mpsc::UnboundedSender<M>,
+    correlation: Option<Arc<CorrelationQueue>>,
+}
+
+impl<M> Clone for InboundSender<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            correlation: self.correlation.clone(),
+        }
+    }
+}
+
+impl<M> From<tokio::sync::mpsc::UnboundedSender<M>> for InboundSender<M> {
+    fn from(sender: tokio::sync::mpsc::UnboundedSender<M>) -> Self {
+        Self {
+            sender,
+            correlation: None,
+        }
+    }
+}
+
+impl<M> InboundSender<M> {
+    pub(crate) fn new(
+        sender: tokio::sync::mpsc::UnboundedSender<M>,
+        correlation: Option<Arc<CorrelationQueue>>,
+    ) -> Self {
+        Self {
+            sender,
+            correlation,
+        }
+    }
+
+    /// Sends the message, tagged with the current correlation id, or a new one if there is none.
+    pub(crate) fn send(&self, msg: M) -> Result<(), SendError<M>> {
+        match &self.correlation {
+            Some(queue) if queue.active.load(Ordering::Acquire) => {
+                let id = current_correlation_id()
+                    .unwrap_or_else(|| NEXT_CORRELATION_ID.fetch_add(1, Ordering::Relaxed));
+                // The id is queued while holding the lock, so that the ids are in the same order
+                // as the messages of concurrent senders.
+                let mut ids = queue.ids.lock().unwrap();
+                ids.push_back(id);
+                let result = self.sender.send(msg);
+                if result.is_err() {
+                    ids.pop_back();
+                }
+                result
+            }
+            _ => self.sender.send(msg),
+        }
+    }
+}
+
+/// Correlation queues of the inbound channels of an actor, by inbound channel name.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Correlations {
+    queues: HashMap<String, Arc<CorrelationQueue>>,
+}
+
+impl Correlations {
+    pub(crate) fn new(queues: HashMap<String, Arc<CorrelationQueue>>) -> Self {
+        Self { queues }
+    }
+
+    /// Returns the correlation id of the message which was just received.
+    ///
+    /// It must be called exactly once for each received message, including the dropped ones.
+    pub(crate) fn on_receive<M: IsInboundMessage>(&self, msg: &M) -> Option<u64> {
+        if self.queues.is_empty() {
+            return None;
+        }
+        self.queues
+            .get(&msg.inbound_channel())
+            .and_then(|queue| queue.pop())
+    }
+}
@@ -0,0 +1,131 @@
+use crate::compute::supervisor::Supervisor;
+use crate::prelude::*;
+use tracing::warn;
+
+/// Error returned by a fallible message handler, see [HasOnMessage::try_on_message()].
+///
+/// Any error type can be converted into it using the `?` operator, and a string using `into()`.
+pub type ActorError = Box<dyn std::error::Error + Send + Sync>;
+
+/// How an actor reacts if its message handler returns an error, see
+/// [ActorBuilder::set_error_policy()].
+///
+/// Regardless of the policy, each error is logged and published on the error stream of the
+/// pipeline, see [Pipeline::error_stream()].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// The message is skipped, and the actor continues with its current state.
+    #[default]
+    Skip,
+    /// The state of the actor is reset to its initial state, i.e. the state it was created with.
+    Restart,
+    /// The pipeline is cancelled.
+    Escalate,
+}
+
+/// Error of a message handler, as published on the error stream of the pipeline, see
+/// [Pipeline::error_stream()].
+#[derive(Clone, Debug)]
+pub struct ActorErrorEvent {
+    /// Name of the actor whose message handler failed.
+    pub actor_name: String,
+    /// Name of the inbound channel of the message.
+    pub inbound_name: String,
+    /// Error message.
+    pub message: String,
+    /// Policy which was applied in response.
+    pub policy: ErrorPolicy,
+}
+
+impl std::fmt::Display for ActorErrorEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: error on {} ({:?}): {}",
+            self.actor_name, self.inbound_name, self.policy, self.message
+        )
+    }
+}
+
+/// Targets of actor errors, which are provided by the pipeline.
+#[derive(Clone)]
+pub(crate) struct ErrorSink {
+    pub(crate) events: tokio::sync::broadcast::Sender<ActorErrorEvent>,
+    pub(crate) cancel_request_sender: tokio::sync::mpsc::UnboundedSender<CancelRequest>,
+}
+
+/// Applies the error policy and the supervision strategy of an actor.
+pub struct ActorErrorHandler<State> {
+    actor_name: String,
+    policy: ErrorPolicy,
+    pub(crate) supervisor: Supervisor<State>,
+    pub(crate) sink: Option<ErrorSink>,
+}
+
+impl<State> ActorErrorHandler<State> {
+    pub(crate) fn new(actor_name: &str) -> Self {
+        Self {
+            actor_name: actor_name.to_owned(),
+            policy: ErrorPolicy::default(),
+            supervisor: Supervisor::new(),
+            sink: None,
+        }
+    }
+
+    pub(crate) fn actor_name(&self) -> &str {
+        &self.actor_name
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: ErrorPolicy, initial_state: State)
+    where
+        State: Clone,
+    {
+        self.policy = policy;
+        if policy == ErrorPolicy::Restart {
+            self.supervisor.keep_initial_state(initial_state);
+        }
+    }
+
+    /// Calls the given message handler under supervision, and handles its error, if any.
+    pub(crate) fn dispatch(
+        &mut self,
+        state: &mut State,
+        inbound_name: String,
+        handler: impl FnOnce(&mut State) -> Result<(), ActorError>,
+    ) {
+        if let Some(Err(err)) = self.supervisor.supervise(&self.actor_name, state, handler) {
+            self.handle(state, inbound_name, err);
+        }
+    }
+
+    /// Handles the error of the message handler of the given inbound channel.
+    fn handle(&self, state: &mut State, inbound_name: String, err: ActorError) {
+        warn!(
+            "{}: error on {}, {:?}: {}",
+            self.actor_name, inbound_name, self.policy, err
+        );
+        match self.policy {
+            ErrorPolicy::Skip => {}
+            ErrorPolicy::Restart => {
+                if let Some(initial_state) = self.supervisor.initial_state() {
+                    *state = initial_state;
+                }
+            }
+            ErrorPolicy::Escalate => {
+                if let Some(sink) = &self.sink {
+                    // The pipeline might be cancelled already, which is fine.
+                    let _ = sink.cancel_request_sender.send(CancelRequest);
+                }
+            }
+        }
+        if let Some(sink) = &self.sink {
+            // There might be no subscribers, which is fine.
+            let _ = sink.events.send(ActorErrorEvent {
+                actor_name: self.actor_name.clone(),
+                inbound_name,
+                message: err.to_string(),
+                policy: self.policy,
+            });
+        }
+    }
+}
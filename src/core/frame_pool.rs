@@ -0,0 +1,214 @@
+use crate::prelude::*;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+
+struct FramePoolInner<T> {
+    free: Mutex<Vec<T>>,
+    capacity: usize,
+}
+
+/// Pool of large, reusable buffers such as camera frames.
+///
+/// Frames are acquired from the pool, filled and sent downstream, typically through a
+/// [PooledOutboundChannel]. Once the last consumer drops a frame, its buffer is returned to the
+/// pool, so that a pipeline in steady state does not allocate per frame. At most `capacity` free
+/// buffers are retained, further buffers are deallocated as usual.
+///
+/// The pool is a cheap handle, i.e. clones share the same buffers.
+pub struct FramePool<T> {
+    inner: Arc<FramePoolInner<T>>,
+}
+
+impl<T> Clone for FramePool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for FramePool<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FramePool")
+            .field("capacity", &self.inner.capacity)
+            .field("num_free", &self.num_free())
+            .finish()
+    }
+}
+
+impl<T> Default for FramePool<T> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<T> FramePool<T> {
+    /// Default number of free buffers retained by the pool.
+    pub const DEFAULT_CAPACITY: usize = 8;
+
+    /// Creates an empty pool which retains at most `capacity` free buffers.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(FramePoolInner {
+                free: Mutex::new(Vec::with_capacity(capacity)),
+                capacity,
+            }),
+        }
+    }
+
+    /// Acquires a recycled buffer from the pool, or allocates a new one using `allocate` if the
+    /// pool is empty.
+    ///
+    /// Note that recycled buffers hold the content of the previous frame.
+    pub fn acquire_or(&self, allocate: impl FnOnce() -> T) -> PooledFrame<T> {
+        let recycled = self.inner.free.lock().unwrap().pop();
+        PooledFrame {
+            value: Some(recycled.unwrap_or_else(allocate)),
+            pool: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Number of free buffers in the pool.
+    pub fn num_free(&self) -> usize {
+        self.inner.free.lock().unwrap().len()
+    }
+
+    /// Maximal number of free buffers retained by the pool.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+}
+
+/// Buffer acquired from a [FramePool], which is returned to the pool when dropped.
+pub struct PooledFrame<T> {
+    value: Option<T>,
+    pool: Weak<FramePoolInner<T>>,
+}
+
+impl<T> PooledFrame<T> {
+    /// Creates a frame which does not belong to any pool, i.e. it is deallocated when dropped.
+    pub fn detached(value: T) -> Self {
+        Self {
+            value: Some(value),
+            pool: Weak::new(),
+        }
+    }
+
+    /// The buffer of the frame.
+    pub fn get(&self) -> &T {
+        self.value.as_ref().unwrap()
+    }
+
+    /// The mutable buffer of the frame.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.as_mut().unwrap()
+    }
+
+    /// Takes the buffer out of the frame, i.e. it is not returned to the pool.
+    pub fn into_inner(mut self) -> T {
+        self.value.take().unwrap()
+    }
+}
+
+impl<T: Debug> Debug for PooledFrame<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PooledFrame").field(&self.value).finish()
+    }
+}
+
+impl<T> Drop for PooledFrame<T> {
+    fn drop(&mut self) {
+        let (Some(value), Some(pool)) = (self.value.take(), self.pool.upgrade()) else {
+            return;
+        };
+        let mut free = pool.free.lock().unwrap();
+        if free.len() < pool.capacity {
+            free.push(value);
+        }
+    }
+}
+
+/// Outbound channel of pooled frames, which are shared by all connected inbound channels and
+/// recycled once the last consumer dropped them.
+///
+/// It can be used as field of an [actor_outputs](crate::macros::actor_outputs) hub, and is
+/// connected to inbound channels of `Shared<PooledFrame<T>>`:
+///
+/// ``` ignore
+/// #[actor_outputs]
+/// pub struct CameraOutbound {
+///     pub image: PooledOutboundChannel<Vec<u8>>,
+/// }
+///
+/// let mut frame = outbound.image.acquire_or(|| vec![0; WIDTH * HEIGHT]);
+/// camera.capture_into(frame.get_mut());
+/// outbound.image.send(frame);
+/// ```
+pub struct PooledOutboundChannel<T> {
+    /// The underlying outbound channel of shared frames.
+    pub channel: OutboundChannel<Shared<PooledFrame<T>>>,
+    pool: FramePool<T>,
+}
+
+impl<T: Send + Sync + Debug + 'static> PooledOutboundChannel<T> {
+    /// Create a new pooled outbound for actor in provided context, with a pool of default
+    /// capacity.
+    pub fn new(context: &mut Hollywood, name: String, actor_name: &str) -> Self {
+        Self {
+            channel: OutboundChannel::new(context, name, actor_name),
+            pool: FramePool::default(),
+        }
+    }
+
+    /// Replaces the pool of the channel, e.g. to change its capacity or to share a pool between
+    /// several channels.
+    pub fn set_pool(&mut self, pool: FramePool<T>) {
+        self.pool = pool;
+    }
+
+    /// The pool of the channel.
+    pub fn pool(&self) -> &FramePool<T> {
+        &self.pool
+    }
+
+    /// Acquires a frame from the pool, see [FramePool::acquire_or()].
+    pub fn acquire_or(&self, allocate: impl FnOnce() -> T) -> PooledFrame<T> {
+        self.pool.acquire_or(allocate)
+    }
+
+    /// Connect the pooled outbound channel from this actor to the inbound channel of another
+    /// actor.
+    pub fn connect<M: IsInboundMessageNew<Shared<PooledFrame<T>>>>(
+        &mut self,
+        ctx: &mut Hollywood,
+        inbound: &mut InboundChannel<Shared<PooledFrame<T>>, M>,
+    ) {
+        self.channel.connect(ctx, inbound);
+    }
+
+    /// Number of inbound channels this outbound channel is connected to.
+    pub fn fan_out(&self) -> usize {
+        self.channel.fan_out()
+    }
+
+    /// Send the frame to the connected inbound channels, which share it without copying.
+    pub fn send(&self, frame: PooledFrame<T>) {
+        self.channel.send_shared(frame);
+    }
+}
+
+impl<T> HasActivate for PooledOutboundChannel<T> {
+    fn extract(&mut self) -> Self {
+        Self {
+            channel: self.channel.extract(),
+            pool: self.pool.clone(),
+        }
+    }
+
+    fn activate(&mut self) {
+        self.channel.activate();
+    }
+}
@@ -0,0 +1,442 @@
+use crate::core::backpressure::BoundedInbounds;
+use crate::core::clock::sleep_until_deadline;
+use crate::core::clock::ClockTimer;
+use crate::core::correlation::CorrelationScope;
+use crate::core::correlation::Correlations;
+use crate::core::error_policy::ActorErrorHandler;
+use crate::core::out_request::discard_requests;
+use crate::prelude::*;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokio::select;
+
+/// Properties, state, message handlers and outbound hubs of an actor.
+pub(crate) struct ActorCore<Prop, State, Outbound, OutRequest, M> {
+    pub(crate) prop: Prop,
+    pub(crate) state: State,
+    pub(crate) forward: ForwardTable<Prop, State, Outbound, OutRequest, M>,
+    pub(crate) outbound: Outbound,
+    pub(crate) out_request: OutRequest,
+    pub(crate) errors: ActorErrorHandler<State>,
+    /// Timer of time-based actors, see [ClockRunner].
+    pub(crate) timer: Option<ClockTimer<Prop, State, Outbound>>,
+}
+
+impl<Prop, State, Outbound, OutRequest, M: IsInboundMessage>
+    ActorCore<Prop, State, Outbound, OutRequest, M>
+{
+    fn dispatch(&mut self, m: M) {
+        let channel = m.inbound_channel();
+        if let Some(handler) = self.forward.get(&channel) {
+            if let Some(timer) = &self.timer {
+                timer.set_now(&mut self.state);
+            }
+            let (prop, outbound, out_request) = (&self.prop, &self.outbound, &self.out_request);
+            self.errors.dispatch(&mut self.state, channel, |state| {
+                handler.try_forward_message(prop, state, outbound, out_request, m)
+            });
+        }
+    }
+}
+
+type SharedCore<Prop, State, Outbound, OutRequest, M> =
+    Mutex<Option<ActorCore<Prop, State, Outbound, OutRequest, M>>>;
+
+/// Shared slot of the actor core, which allows fused connections to invoke the message handlers
+/// of the actor inline, see [OutboundChannel::connect_fused()].
+pub(crate) struct FusedSlot<Prop, State, Outbound, OutRequest, M> {
+    core: SharedCore<Prop, State, Outbound, OutRequest, M>,
+    /// Whether the actor runs in fused mode, i.e. inline dispatch is possible.
+    running: AtomicBool,
+    /// Number of messages of fused connections which are queued, since they could not be
+    /// dispatched inline.
+    pending: AtomicUsize,
+    /// Number of messages dispatched inline by the sending actors, see [run_fused].
+    inline_messages: AtomicU64,
+    /// Time spent in the message handlers of inline dispatches, in nanoseconds.
+    inline_busy_nanos: AtomicU64,
+    /// Notified on inline dispatches of time-based actors, since they may change the deadline.
+    inline_dispatched: tokio::sync::Notify,
+    fallback_sender: tokio::sync::mpsc::UnboundedSender<M>,
+    fallback_receiver: Mutex<Option<tokio::sync::mpsc::UnboundedReceiver<M>>>,
+}
+
+impl<Prop, State, Outbound, OutRequest, M: IsInboundMessage>
+    FusedSlot<Prop, State, Outbound, OutRequest, M>
+{
+    pub(crate) fn new(core: ActorCore<Prop, State, Outbound, OutRequest, M>) -> Self {
+        let (fallback_sender, fallback_receiver) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            core: Mutex::new(Some(core)),
+            running: AtomicBool::new(false),
+            pending: AtomicUsize::new(0),
+            inline_messages: AtomicU64::new(0),
+            inline_busy_nanos: AtomicU64::new(0),
+            inline_dispatched: tokio::sync::Notify::new(),
+            fallback_sender,
+            fallback_receiver: Mutex::new(Some(fallback_receiver)),
+        }
+    }
+
+    /// Takes the core out of the slot, so that the actor runs with exclusive access.
+    pub(crate) fn take_core(&self) -> ActorCore<Prop, State, Outbound, OutRequest, M> {
+        self.core
+            .lock()
+            .expect("actor core poisoned")
+            .take()
+            .expect("actor core taken twice")
+    }
+
+    /// Puts the core back into the slot.
+    pub(crate) fn put_core(&self, core: ActorCore<Prop, State, Outbound, OutRequest, M>) {
+        *self.core.lock().expect("actor core poisoned") = Some(core);
+    }
+
+    pub(crate) fn with_core<Ret>(
+        &self,
+        f: impl FnOnce(&mut ActorCore<Prop, State, Outbound, OutRequest, M>) -> Ret,
+    ) -> Ret {
+        let mut guard = self
+            .core
+            .lock()
+            .expect("actor core poisoned, since a fused upstream actor panicked");
+        f(guard.as_mut().expect("actor core taken"))
+    }
+
+    /// Like [FusedSlot::with_core()], but returns None if the core is taken, i.e. the actor is
+    /// running (in non-fused mode) or was aborted.
+    pub(crate) fn try_with_core<Ret>(
+        &self,
+        f: impl FnOnce(&mut ActorCore<Prop, State, Outbound, OutRequest, M>) -> Ret,
+    ) -> Option<Ret> {
+        let mut guard = self.core.lock().ok()?;
+        guard.as_mut().map(f)
+    }
+}
+
+/// Type-erased inline dispatch of messages of type M, see [FusedSlot].
+pub(crate) trait IsFusedDispatch<M>: Send + Sync {
+    fn dispatch(&self, m: M);
+}
+
+impl<
+        Prop: Send + Sync,
+        State: Send + Sync,
+        Outbound: Send + Sync,
+        OutRequest: Send + Sync,
+        M: IsInboundMessage,
+    > IsFusedDispatch<M> for FusedSlot<Prop, State, Outbound, OutRequest, M>
+{
+    fn dispatch(&self, m: M) {
+        // Messages are only dispatched inline if no earlier message is queued, so that the order
+        // of messages is preserved. If the core is locked, the actor is busy, e.g. because the
+        // message is sent from within its own message handler, hence the message is queued to
+        // avoid reentrancy.
+        if self.running.load(Ordering::Acquire) && self.pending.load(Ordering::Acquire) == 0 {
+            if let Ok(mut guard) = self.core.try_lock() {
+                if let Some(core) = guard.as_mut() {
+                    let start = std::time::Instant::now();
+                    core.dispatch(m);
+                    self.inline_busy_nanos
+                        .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+                    self.inline_messages.fetch_add(1, Ordering::Relaxed);
+                    if core.timer.is_some() {
+                        self.inline_dispatched.notify_one();
+                    }
+                    return;
+                }
+            }
+        }
+        self.pending.fetch_add(1, Ordering::AcqRel);
+        if self.fallback_sender.send(m).is_err() {
+            self.pending.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+enum FusedEvent<M, R> {
+    /// Message of a fused connection which could not be dispatched inline.
+    Fallback(M),
+    /// Message of a regular connection, with its correlation id.
+    Message(M, Option<u64>),
+    Request(R),
+    /// The deadline of a time-based actor was reached.
+    Deadline,
+}
+
+/// Runs the actor in fused mode: messages of fused connections are dispatched inline by the
+/// sending actor, while all other messages and requests are processed here.
+///
+/// The returned stats include the inline dispatches, each counted as a batch of one message.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_fused<
+    Prop: Send + Sync,
+    State: Send + Sync,
+    Outbound: Send + Sync,
+    OutRequest: Send + Sync,
+    M: IsInboundMessage,
+    R: IsInRequestMessage,
+>(
+    slot: &FusedSlot<Prop, State, Outbound, OutRequest, M>,
+    mut receiver: tokio::sync::mpsc::UnboundedReceiver<M>,
+    mut priority_receiver: tokio::sync::mpsc::UnboundedReceiver<M>,
+    mut request_receiver: tokio::sync::mpsc::UnboundedReceiver<R>,
+    forward_request: &ForwardRequestTable<Prop, State, Outbound, OutRequest, R>,
+    bounded: &BoundedInbounds,
+    correlations: &Correlations,
+    drain: &AtomicBool,
+    mut kill: tokio::sync::watch::Receiver<bool>,
+) -> (
+    tokio::sync::mpsc::UnboundedReceiver<M>,
+    tokio::sync::mpsc::UnboundedReceiver<M>,
+    BatchStats,
+) {
+    let mut fallback_receiver = slot
+        .fallback_receiver
+        .lock()
+        .expect("fallback receiver poisoned")
+        .take()
+        .expect("fallback receiver taken twice");
+    let mut stats = BatchStats::default();
+    let mut requests_open = true;
+    let mut priority_open = true;
+    slot.inline_messages.store(0, Ordering::Relaxed);
+    slot.inline_busy_nanos.store(0, Ordering::Relaxed);
+    let clock = slot.with_core(|core| core.timer.as_ref().map(|timer| timer.clock.clone()));
+    slot.running.store(true, Ordering::Release);
+    loop {
+        if *kill.borrow() {
+            break;
+        }
+        stats.peak_queue_len = stats.peak_queue_len.max(
+            receiver.len()
+                + priority_receiver.len()
+                + fallback_receiver.len()
+                + request_receiver.len(),
+        );
+        // Pending high-priority messages are processed before all other messages and requests.
+        if let Ok(m) = priority_receiver.try_recv() {
+            let correlation_id = correlations.on_receive(&m);
+            if bounded.on_dequeue(&m) {
+                let start = std::time::Instant::now();
+                let _scope = CorrelationScope::enter(correlation_id);
+                slot.with_core(|core| core.dispatch(m));
+                stats.busy_time += start.elapsed();
+                stats.num_messages += 1;
+                stats.num_batches += 1;
+                stats.max_batch_size = 1;
+            }
+            continue;
+        }
+        let deadline = slot.with_core(|core| {
+            core.timer
+                .as_ref()
+                .and_then(|timer| timer.next_deadline(&core.prop, &core.state))
+        });
+        let event = select! {
+            _ = kill.changed() => break,
+            m = priority_receiver.recv(), if priority_open => match m {
+                Some(m) => {
+                    let correlation_id = correlations.on_receive(&m);
+                    if !bounded.on_dequeue(&m) {
+                        continue;
+                    }
+                    FusedEvent::Message(m, correlation_id)
+                }
+                None => {
+                    // The actor has no priority lane.
+                    priority_open = false;
+                    continue;
+                }
+            },
+            m = fallback_receiver.recv() => match m {
+                Some(m) => FusedEvent::Fallback(m),
+                None => break,
+            },
+            m = receiver.recv() => match m {
+                Some(m) => {
+                    let correlation_id = correlations.on_receive(&m);
+                    if !bounded.on_dequeue(&m) {
+                        continue;
+                    }
+                    FusedEvent::Message(m, correlation_id)
+                }
+                None => break,
+            },
+            r = request_receiver.recv(), if requests_open => match r {
+                Some(r) => FusedEvent::Request(r),
+                None => {
+                    requests_open = false;
+                    continue;
+                }
+            },
+            _ = sleep_until_deadline(clock.as_deref().zip(deadline)) => FusedEvent::Deadline,
+            _ = slot.inline_dispatched.notified(), if clock.is_some() => continue,
+        };
+        let start = std::time::Instant::now();
+        match event {
+            FusedEvent::Fallback(m) => {
+                slot.with_core(|core| core.dispatch(m));
+                slot.pending.fetch_sub(1, Ordering::AcqRel);
+            }
+            FusedEvent::Message(m, correlation_id) => {
+                let _scope = CorrelationScope::enter(correlation_id);
+                slot.with_core(|core| core.dispatch(m))
+            }
+            FusedEvent::Request(r) => {
+                if let Some(handler) = forward_request.get(&r.in_request_channel()) {
+                    slot.with_core(|core| {
+                        let (prop, outbound, out_request) =
+                            (&core.prop, &core.outbound, &core.out_request);
+                        let actor_name = core.errors.actor_name().to_owned();
+                        if let Some(timer) = &core.timer {
+                            timer.set_now(&mut core.state);
+                        }
+                        core.errors
+                            .supervisor
+                            .supervise(&actor_name, &mut core.state, |state| {
+                                handler.forward_message(prop, state, outbound, out_request, r)
+                            });
+                    });
+                }
+            }
+            FusedEvent::Deadline => {
+                slot.with_core(|core| {
+                    let Some(timer) = &core.timer else {
+                        return;
+                    };
+                    let actor_name = core.errors.actor_name().to_owned();
+                    core.errors
+                        .supervisor
+                        .supervise(&actor_name, &mut core.state, |state| {
+                            timer.on_deadline(&core.prop, state, &core.outbound)
+                        });
+                });
+                stats.busy_time += start.elapsed();
+                continue;
+            }
+        }
+        stats.busy_time += start.elapsed();
+        stats.num_messages += 1;
+        stats.num_batches += 1;
+        stats.max_batch_size = 1;
+    }
+    slot.running.store(false, Ordering::Release);
+    // Inline dispatches hold the core lock, hence they all completed once the flag is cleared and
+    // the core was locked once more.
+    slot.with_core(|_| ());
+    let inline_messages = slot.inline_messages.swap(0, Ordering::Relaxed);
+    if inline_messages > 0 {
+        stats.num_messages += inline_messages;
+        stats.num_batches += inline_messages;
+        stats.max_batch_size = 1;
+        stats.busy_time +=
+            std::time::Duration::from_nanos(slot.inline_busy_nanos.swap(0, Ordering::Relaxed));
+    }
+    if drain.load(Ordering::Acquire) {
+        // Graceful shutdown: the upstream actors stopped already, hence the pending messages are
+        // the last ones.
+        while let Ok(m) = fallback_receiver.try_recv() {
+            slot.with_core(|core| core.dispatch(m));
+            stats.num_messages += 1;
+        }
+        while let Ok(m) = priority_receiver
+            .try_recv()
+            .or_else(|_| receiver.try_recv())
+        {
+            let correlation_id = correlations.on_receive(&m);
+            if bounded.on_dequeue(&m) {
+                let _scope = CorrelationScope::enter(correlation_id);
+                slot.with_core(|core| core.dispatch(m));
+                stats.num_messages += 1;
+            }
+        }
+    }
+    while fallback_receiver.try_recv().is_ok() {}
+    slot.pending.store(0, Ordering::Release);
+    while let Ok(m) = priority_receiver
+        .try_recv()
+        .or_else(|_| receiver.try_recv())
+    {
+        correlations.on_receive(&m);
+    }
+    *slot
+        .fallback_receiver
+        .lock()
+        .expect("fallback receiver poisoned") = Some(fallback_receiver);
+    // Requests which are still queued are dropped without a reply.
+    discard_requests(request_receiver);
+    (receiver, priority_receiver, stats)
+}
+
+struct FusedConnection<T, M> {
+    dispatch: Arc<dyn IsFusedDispatch<M>>,
+    inbound_channel: String,
+    phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Send + Sync, M: IsInboundMessageNew<T>> IsGenericConnection<T> for FusedConnection<T, M> {
+    fn send_impl(&self, msg: T) {
+        self.dispatch
+            .dispatch(M::new(self.inbound_channel.clone(), msg));
+    }
+}
+
+impl<OutT: Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
+    /// Connects the outbound channel exclusively to a single inbound channel, such that the
+    /// message handler of the downstream actor is invoked inline from the send path of this
+    /// actor, instead of via a channel hop.
+    ///
+    /// This minimal-latency mode trades isolation for latency, e.g. in tight
+    /// sensor-filter-control chains: the downstream handler runs on the task of this actor, and a
+    /// panic in the downstream handler fails this actor as well. The downstream actor must opt in
+    /// using [Hollywood::enable_fused_dispatch()]. Besides, the following safeguards apply:
+    ///
+    ///   * If the downstream actor is busy, e.g. processing a message of another channel or
+    ///     being reentered from its own message handler through a cycle, the message is queued
+    ///     instead of being dispatched inline.
+    ///   * Once a message is queued, subsequent messages are queued as well until the downstream
+    ///     actor caught up, so that the order of messages is preserved.
+    ///   * Messages sent before the pipeline is started are queued.
+    ///
+    /// Like [OutboundChannel::connect_exclusive()], the message type does not need to implement
+    /// [Clone], and the outbound channel admits this single connection only, see
+    /// [TopologyIssue::FusedFanOut]. Panics if the downstream actor did not opt in.
+    pub fn connect_fused<M: IsInboundMessageNew<OutT>>(
+        &mut self,
+        ctx: &mut Hollywood,
+        inbound: &mut InboundChannel<OutT, M>,
+    ) {
+        assert!(
+            ctx.fused_actors.contains(&inbound.actor_name),
+            "Actor {} did not opt in to fused dispatch, see Hollywood::enable_fused_dispatch()",
+            inbound.actor_name
+        );
+        let dispatch = ctx
+            .actors
+            .iter()
+            .find(|actor| actor.name() == &inbound.actor_name)
+            .and_then(|actor| actor.fused_dispatcher())
+            .and_then(|dispatch| dispatch.downcast::<Arc<dyn IsFusedDispatch<M>>>().ok())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Actor {} does not support fused dispatch",
+                    inbound.actor_name
+                )
+            });
+        ctx.set_exclusive(self, true);
+        ctx.connect_impl(
+            self,
+            inbound,
+            Arc::new(FusedConnection::<OutT, M> {
+                dispatch: *dispatch,
+                inbound_channel: inbound.name.clone(),
+                phantom: std::marker::PhantomData,
+            }),
+        );
+    }
+}
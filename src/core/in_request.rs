@@ -49,7 +49,10 @@ impl<
 
 /// InRequest channel to receive messages of a specific type `T`.
 ///
-/// InRequest channels can be connected to one or more outbound channels of upstream actors.
+/// InRequest channels can be connected to one or more out-request channels of other actors. The
+/// reply to each request is routed back to the actor which sent it, even if the requests of
+/// several actors interleave, see [RequestWithReplyChannel::requester()] and
+/// [RequestWithReplyChannel::request_id()].
 #[derive(Debug)]
 pub struct InRequestChannel<T, M: IsInRequestMessage> {
     /// Unique identifier of the in-request channel.
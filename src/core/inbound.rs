@@ -1,4 +1,7 @@
+use crate::core::correlation::InboundSender;
 use crate::prelude::*;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 /// The inbound hub is a collection of inbound channels.
 pub trait IsInboundHub<
@@ -49,17 +52,30 @@ impl<
 /// Inbound channel to receive messages of a specific type `T`.
 ///
 /// Inbound channels can be connected to one or more outbound channels of upstream actors.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct InboundChannel<T, M: IsInboundMessage> {
     /// Unique identifier of the inbound channel.
     pub name: String,
     /// Name of the actor that the inbound messages are for.
     pub actor_name: String,
-    pub(crate) sender: tokio::sync::mpsc::UnboundedSender<M>,
+    pub(crate) sender: InboundSender<M>,
+    pub(crate) stats: Arc<Mutex<ChannelStats>>,
     pub(crate) phantom: std::marker::PhantomData<T>,
 }
 
-impl<T: Clone + Send + Sync + std::fmt::Debug + 'static, M: IsInboundMessage> InboundChannel<T, M> {
+impl<T, M: IsInboundMessage> Clone for InboundChannel<T, M> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            actor_name: self.actor_name.clone(),
+            sender: self.sender.clone(),
+            stats: self.stats.clone(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Send + Sync + std::fmt::Debug + 'static, M: IsInboundMessage> InboundChannel<T, M> {
     /// Creates a new inbound channel.
     pub fn new(
         context: &mut Hollywood,
@@ -68,17 +84,56 @@ impl<T: Clone + Send + Sync + std::fmt::Debug + 'static, M: IsInboundMessage> In
         name: String,
     ) -> Self {
         context.assert_unique_inbound_name(name.clone(), actor_name);
+        let correlation = context.add_correlation_queue(actor_name, &name);
         Self {
             name,
             actor_name: actor_name.to_owned(),
-            sender: sender.clone(),
+            sender: InboundSender::new(sender.clone(), correlation),
+            stats: Arc::new(Mutex::new(ChannelStats::default())),
             phantom: std::marker::PhantomData {},
         }
     }
+
+    /// Creates a new high-priority inbound channel.
+    ///
+    /// Pending messages of high-priority channels are processed before any pending message of
+    /// regular channels and before pending requests, e.g. so that control commands are not stuck
+    /// behind bulk data. Messages of the same lane are processed in arrival order. Within the
+    /// [actor_inputs](crate::macros::actor_inputs) macro, a variant is marked as high-priority
+    /// channel with the `#[priority]` attribute.
+    ///
+    /// Priority lanes are supported by actors of the [DefaultRunner].
+    pub fn new_priority<Prop, State, Outbound, OutRequest, R>(
+        builder: &mut ActorBuilder<Prop, State, Outbound, OutRequest, M, R>,
+        actor_name: &str,
+        name: String,
+    ) -> Self
+    where
+        OutRequest: IsOutRequestHub<M>,
+        R: IsInRequestMessage,
+    {
+        let sender = builder.priority_sender.clone();
+        Self::new(builder.context, actor_name, &sender, name)
+    }
+}
+
+impl<T, M: IsInboundMessage> InboundChannel<T, M> {
+    /// Returns the liveness statistics of the inbound channel.
+    pub fn stats(&self) -> ChannelStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Records the statistics of the inbound channel in the given handle from now on.
+    ///
+    /// This allows the business logic to access the statistics through a handle which was
+    /// created before the actor, e.g. as part of its state.
+    pub fn share_stats(&self, stats: &ChannelStats) {
+        *self.stats.lock().unwrap() = stats.clone();
+    }
 }
 
 /// Inbound messages to be received by the actor.
-pub trait IsInboundMessage: Send + Sync + Clone + 'static {
+pub trait IsInboundMessage: Send + Sync + Sized + 'static {
     /// Prop type of the receiving actor.
     type Prop;
 
@@ -96,25 +151,101 @@ pub trait IsInboundMessage: Send + Sync + Clone + 'static {
 }
 
 /// Customization point for processing inbound messages.
+///
+/// Implementations provide either [HasOnMessage::on_message()], or its fallible variant
+/// [HasOnMessage::try_on_message()].
 pub trait HasOnMessage: IsInboundMessage {
     /// Process the inbound message - user code with main business logic goes here.
+    ///
+    /// The default implementation calls [HasOnMessage::try_on_message()] and logs the error, if
+    /// any.
     fn on_message(
         self,
         prop: &Self::Prop,
         state: &mut Self::State,
         outbound: &Self::OutboundHub,
         request: &Self::OutRequestHub,
-    );
+    ) {
+        let inbound_channel = self.inbound_channel();
+        if let Err(err) = self.try_on_message(prop, state, outbound, request) {
+            tracing::warn!("error on {}: {}", inbound_channel, err);
+        }
+    }
+
+    /// Process the inbound message, and return an error if processing failed.
+    ///
+    /// Within a pipeline, the error is handled according to the error policy of the actor, see
+    /// [ActorBuilder::set_error_policy()].
+    ///
+    /// The default implementation calls [HasOnMessage::on_message()], which cannot fail.
+    fn try_on_message(
+        self,
+        prop: &Self::Prop,
+        state: &mut Self::State,
+        outbound: &Self::OutboundHub,
+        request: &Self::OutRequestHub,
+    ) -> Result<(), ActorError> {
+        self.on_message(prop, state, outbound, request);
+        Ok(())
+    }
+
+    /// Timestamp of the message, used to enforce cross-channel ordering constraints, see
+    /// [ChannelOrdering].
+    ///
+    /// The default implementation returns None, i.e. the message is not constrained.
+    fn timestamp(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// Passes each message to [HasOnMessage::on_message()] of a detached actor with the given
+/// properties and initial state, and returns the final state.
+///
+/// The outbound and out-request hubs of the actor are created, but not connected, so that all
+/// outbound messages are discarded. It is the runtime part of the
+/// [hollywood_test](crate::macros::hollywood_test) smoke tests.
+pub fn on_message_smoke_test<M>(prop: M::Prop, mut state: M::State, messages: Vec<M>) -> M::State
+where
+    M: HasOnMessage,
+    M::OutboundHub: IsOutboundHub,
+    M::OutRequestHub: IsOutRequestHub<M>,
+{
+    let mut context = Hollywood::new();
+    let actor_name = context.add_unique_label("hollywood_test");
+    let outbound = M::OutboundHub::from_context_and_parent(&mut context, &actor_name);
+    let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel::<M>();
+    let request = M::OutRequestHub::from_parent_and_sender(&actor_name, &sender);
+    for msg in messages {
+        msg.on_message(&prop, &mut state, &outbound, &request);
+    }
+    state
 }
 
 /// Trait for creating inbound messages of compatible types `T`.
 pub trait IsInboundMessageNew<T>:
-    std::fmt::Debug + Send + Sync + Clone + 'static + IsInboundMessage
+    std::fmt::Debug + Send + Sync + 'static + IsInboundMessage
 {
     /// Create a new inbound message from the inbound channel name and the message value of type `T`.
     fn new(inbound_channel: String, value: T) -> Self;
 }
 
+/// Inbound channel of message type `T`, regardless of the receiving actor type.
+///
+/// It enables connecting an outbound channel to inbound channels of different actor types at
+/// once, see [OutboundChannel::tee()].
+pub trait IsInboundSink<T> {
+    /// Connects the given outbound channel to this inbound channel.
+    fn connect_from(&mut self, ctx: &mut Hollywood, outbound: &mut OutboundChannel<T>);
+}
+
+impl<T: Clone + Send + Sync + std::fmt::Debug + 'static, M: IsInboundMessageNew<T>> IsInboundSink<T>
+    for InboundChannel<T, M>
+{
+    fn connect_from(&mut self, ctx: &mut Hollywood, outbound: &mut OutboundChannel<T>) {
+        outbound.connect(ctx, self);
+    }
+}
+
 /// Message forwarder.
 pub trait HasForwardMessage<Prop, State, OutboundHub, OutRequestHub, M: IsInboundMessage> {
     /// Forward the message to the HasOnMessage customization point.
@@ -126,10 +257,31 @@ pub trait HasForwardMessage<Prop, State, OutboundHub, OutRequestHub, M: IsInboun
         request: &OutRequestHub,
         msg: M,
     );
+
+    /// Forward the message to the [HasOnMessage::try_on_message()] customization point.
+    ///
+    /// The default implementation calls [HasForwardMessage::forward_message()], which cannot
+    /// fail.
+    fn try_forward_message(
+        &self,
+        prop: &Prop,
+        state: &mut State,
+        outbound: &OutboundHub,
+        request: &OutRequestHub,
+        msg: M,
+    ) -> Result<(), ActorError> {
+        self.forward_message(prop, state, outbound, request, msg);
+        Ok(())
+    }
+
+    /// Returns the timestamp of the message, see [HasOnMessage::timestamp()].
+    fn timestamp(&self, _msg: &M) -> Option<f64> {
+        None
+    }
 }
 
 impl<
-        T: Clone + Send + Sync + std::fmt::Debug + 'static,
+        T: Send + Sync + std::fmt::Debug + 'static,
         Prop,
         State,
         OutboundHub,
@@ -150,8 +302,25 @@ impl<
         request: &OutRequestHub,
         msg: M,
     ) {
+        self.stats.lock().unwrap().record();
         msg.on_message(prop, state, outbound, request);
     }
+
+    fn try_forward_message(
+        &self,
+        prop: &Prop,
+        state: &mut State,
+        outbound: &OutboundHub,
+        request: &OutRequestHub,
+        msg: M,
+    ) -> Result<(), ActorError> {
+        self.stats.lock().unwrap().record();
+        msg.try_on_message(prop, state, outbound, request)
+    }
+
+    fn timestamp(&self, msg: &M) -> Option<f64> {
+        msg.timestamp()
+    }
 }
 
 /// Null message is a marker type for actors with no inbound channels.
@@ -0,0 +1,81 @@
+use crate::actors::BackgroundActor;
+use crate::prelude::*;
+use tracing::warn;
+
+impl<T: Send + Sync + std::fmt::Debug + 'static, M: IsInboundMessageNew<T>> InboundChannel<T, M> {
+    /// Subscribes this channel to a tokio broadcast channel owned by external code.
+    ///
+    /// Each item sent through the broadcast channel while the pipeline is running is converted
+    /// into an inbound message. The forwarding is done by a [BackgroundActor], which is returned.
+    /// If the subscription lags behind, the skipped items are logged and dropped. Once all
+    /// senders are dropped, no more items are forwarded.
+    pub fn subscribe_broadcast<U: Clone + Into<T> + Send + 'static>(
+        &self,
+        context: &mut Hollywood,
+        sender: &tokio::sync::broadcast::Sender<U>,
+    ) -> BackgroundActor {
+        let channel = self.sender.clone();
+        let name = self.name.clone();
+        let receiver = sender.subscribe();
+        BackgroundActor::from_named_task(context, "BroadcastBridge", move |mut kill| {
+            // Items sent before the pipeline started are skipped.
+            let mut receiver = receiver.resubscribe();
+            async move {
+                loop {
+                    tokio::select! {
+                        _ = kill.changed() => return,
+                        item = receiver.recv() => match item {
+                            Ok(item) => {
+                                if channel.send(M::new(name.clone(), item.into())).is_err() {
+                                    break;
+                                }
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                                warn!("{}: broadcast bridge skipped {} items", name, n);
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        },
+                    }
+                }
+                // Keep running until the pipeline stops, like any other actor.
+                let _ = kill.wait_for(|kill| *kill).await;
+            }
+        })
+    }
+
+    /// Subscribes this channel to a tokio watch channel owned by external code.
+    ///
+    /// The current value is forwarded when the pipeline starts, and each change while the
+    /// pipeline is running is converted into an inbound message. Intermediate values may be
+    /// skipped, as usual for watch channels. The forwarding is done by a [BackgroundActor], which
+    /// is returned.
+    pub fn subscribe_watch<U: Clone + Into<T> + Send + Sync + 'static>(
+        &self,
+        context: &mut Hollywood,
+        mut receiver: tokio::sync::watch::Receiver<U>,
+    ) -> BackgroundActor {
+        let channel = self.sender.clone();
+        let name = self.name.clone();
+        BackgroundActor::from_named_task(context, "WatchBridge", move |mut kill| {
+            receiver.mark_changed();
+            async move {
+                loop {
+                    tokio::select! {
+                        _ = kill.changed() => return,
+                        changed = receiver.changed() => {
+                            if changed.is_err() {
+                                break;
+                            }
+                            let item = receiver.borrow_and_update().clone();
+                            if channel.send(M::new(name.clone(), item.into())).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                // Keep running until the pipeline stops, like any other actor.
+                let _ = kill.wait_for(|kill| *kill).await;
+            }
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use crate::prelude::*;
+use std::sync::Arc;
+
+type InjectFn<T> = Arc<dyn Fn(T) -> Result<(), InboundInjectorError> + Send + Sync>;
+
+/// Error returned by [InboundInjector::send()].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InboundInjectorError {
+    /// The actor is not running anymore and cannot receive messages.
+    Disconnected,
+}
+
+impl std::fmt::Display for InboundInjectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InboundInjectorError::Disconnected => write!(f, "actor is not running"),
+        }
+    }
+}
+
+impl std::error::Error for InboundInjectorError {}
+
+/// Handle to push messages into an inbound channel from outside the pipeline.
+///
+/// An injector is obtained during configuration using [InboundChannel::injector()]. It can be
+/// cloned and moved to non-actor code, e.g. a hardware callback thread. Sending does not block and
+/// does not require a tokio runtime. Messages sent before the pipeline is started are queued and
+/// processed once the actor runs.
+pub struct InboundInjector<T> {
+    actor_name: String,
+    inbound_name: String,
+    inject: InjectFn<T>,
+}
+
+impl<T> Clone for InboundInjector<T> {
+    fn clone(&self) -> Self {
+        Self {
+            actor_name: self.actor_name.clone(),
+            inbound_name: self.inbound_name.clone(),
+            inject: self.inject.clone(),
+        }
+    }
+}
+
+impl<T> InboundInjector<T> {
+    /// Name of the actor the messages are sent to.
+    pub fn actor_name(&self) -> &str {
+        &self.actor_name
+    }
+
+    /// Name of the inbound channel the messages are sent to.
+    pub fn inbound_name(&self) -> &str {
+        &self.inbound_name
+    }
+
+    /// Pushes a message into the inbound channel.
+    pub fn send(&self, msg: T) -> Result<(), InboundInjectorError> {
+        (self.inject)(msg)
+    }
+}
+
+impl<T: Send + Sync + std::fmt::Debug + 'static, M: IsInboundMessageNew<T>> InboundChannel<T, M> {
+    /// Returns a handle to push messages into this channel from outside the pipeline.
+    pub fn injector(&self) -> InboundInjector<T> {
+        let sender = self.sender.clone();
+        let name = self.name.clone();
+        InboundInjector {
+            actor_name: self.actor_name.clone(),
+            inbound_name: self.name.clone(),
+            inject: Arc::new(move |msg| {
+                sender
+                    .send(M::new(name.clone(), msg))
+                    .map_err(|_| InboundInjectorError::Disconnected)
+            }),
+        }
+    }
+}
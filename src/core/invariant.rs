@@ -0,0 +1,129 @@
+use crate::prelude::*;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::warn;
+
+type Predicate<T> = Box<dyn FnMut(&T) -> bool + Send>;
+
+/// Report of an invariant registered on an outbound channel.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InvariantReport {
+    /// Name of the actor which sends the messages.
+    pub actor_name: String,
+    /// Name of the outbound channel.
+    pub outbound_name: String,
+    /// Name of the invariant.
+    pub invariant_name: String,
+    /// Number of checked messages.
+    pub num_checked: u64,
+    /// Number of messages which violated the invariant.
+    pub num_violations: u64,
+}
+
+pub(crate) struct Invariant<T> {
+    report: InvariantCounter,
+    predicate: Mutex<Predicate<T>>,
+}
+
+/// Type-erased counters of an invariant, shared with the pipeline for reporting.
+#[derive(Clone)]
+pub(crate) struct InvariantCounter {
+    actor_name: String,
+    outbound_name: String,
+    invariant_name: String,
+    num_checked: Arc<AtomicU64>,
+    num_violations: Arc<AtomicU64>,
+}
+
+impl InvariantCounter {
+    pub(crate) fn report(&self) -> InvariantReport {
+        InvariantReport {
+            actor_name: self.actor_name.clone(),
+            outbound_name: self.outbound_name.clone(),
+            invariant_name: self.invariant_name.clone(),
+            num_checked: self.num_checked.load(Ordering::Relaxed),
+            num_violations: self.num_violations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Invariants of an outbound channel, shared between the configuration and the active channel.
+pub(crate) type Invariants<T> = Arc<Mutex<Vec<Invariant<T>>>>;
+
+pub(crate) fn check_invariants<T: std::fmt::Debug>(invariants: &Invariants<T>, msg: &T) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    for invariant in invariants.lock().unwrap().iter() {
+        invariant.report.num_checked.fetch_add(1, Ordering::Relaxed);
+        if !(invariant.predicate.lock().unwrap())(msg) {
+            let num_violations = invariant
+                .report
+                .num_violations
+                .fetch_add(1, Ordering::Relaxed);
+            if num_violations == 0 {
+                warn!(
+                    "{}.{}: invariant {} violated by {:?}",
+                    invariant.report.actor_name,
+                    invariant.report.outbound_name,
+                    invariant.report.invariant_name,
+                    msg
+                );
+            }
+        }
+    }
+}
+
+impl<T: Send + Sync + std::fmt::Debug + 'static> OutboundChannel<T> {
+    /// Registers an invariant, i.e. a predicate which every message sent through this channel
+    /// must satisfy.
+    ///
+    /// Invariants are only evaluated in debug builds. Violations are counted, the first violation
+    /// of each invariant is logged, and all invariants are reported at the end of
+    /// [Pipeline::run()], see [Pipeline::invariant_reports()]. Predicates may carry state, e.g.
+    /// to check that timestamps are strictly increasing, see [strictly_increasing()].
+    pub fn add_invariant(
+        &mut self,
+        context: &mut Hollywood,
+        invariant_name: &str,
+        predicate: impl FnMut(&T) -> bool + Send + 'static,
+    ) {
+        let counter = InvariantCounter {
+            actor_name: self.actor_name.clone(),
+            outbound_name: self.name.clone(),
+            invariant_name: invariant_name.to_owned(),
+            num_checked: Arc::new(AtomicU64::new(0)),
+            num_violations: Arc::new(AtomicU64::new(0)),
+        };
+        context.invariants.push(counter.clone());
+        self.invariants.lock().unwrap().push(Invariant {
+            report: counter,
+            predicate: Mutex::new(Box::new(predicate)),
+        });
+    }
+}
+
+/// Predicate which checks that the key of consecutive messages is strictly increasing, e.g. a
+/// timestamp.
+pub fn strictly_increasing<T: 'static>(
+    key: fn(&T) -> f64,
+) -> impl FnMut(&T) -> bool + Send + 'static {
+    let mut previous = None;
+    move |msg| {
+        let value = key(msg);
+        let ok = previous.is_none_or(|previous| value > previous);
+        previous = Some(value);
+        ok
+    }
+}
+
+/// Predicate which checks that the key of each message is within the closed range [min, max].
+pub fn within_range<T: 'static>(
+    key: fn(&T) -> f64,
+    min: f64,
+    max: f64,
+) -> impl Fn(&T) -> bool + Send + 'static {
+    move |msg| (min..=max).contains(&key(msg))
+}
@@ -0,0 +1,183 @@
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tracing::info;
+use tracing::warn;
+
+/// Policy to automatically shed load of an actor under overload.
+///
+/// The policy is registered for an actor using [Hollywood::set_load_shedding()]. Whenever the
+/// backlog of the actor's inbound queue exceeds [LoadSheddingPolicy::max_backlog], the actor is
+/// considered overloaded and the configured shedding actions are triggered:
+///
+///   * if [LoadSheddingPolicy::drop_oldest] is set, the oldest pending messages of the shed
+///     channels are dropped until the backlog is back at the threshold,
+///   * if [LoadSheddingPolicy::upstream_decimation] is greater than one, the upstream connections
+///     into the shed channels only forward every n-th message.
+///
+/// The overload ends once the backlog dropped to half of the threshold. Start and end of each
+/// overload event are logged, and the accumulated numbers are available through
+/// [Pipeline::load_shedding_stats()].
+#[derive(Clone, Debug)]
+pub struct LoadSheddingPolicy {
+    /// Number of pending inbound messages above which the actor is considered overloaded.
+    pub max_backlog: usize,
+    /// Names of the inbound channels to shed. If empty, all inbound channels are shed.
+    pub channels: Vec<String>,
+    /// Whether the oldest pending messages are dropped during overload.
+    pub drop_oldest: bool,
+    /// Decimation factor applied to upstream connections during overload. Values of zero and one
+    /// disable decimation.
+    pub upstream_decimation: u64,
+}
+
+impl LoadSheddingPolicy {
+    /// Policy which drops the oldest messages of all inbound channels once the backlog exceeds
+    /// the given threshold.
+    pub fn drop_oldest(max_backlog: usize) -> Self {
+        Self {
+            max_backlog,
+            channels: vec![],
+            drop_oldest: true,
+            upstream_decimation: 1,
+        }
+    }
+
+    fn sheds(&self, channel: &str) -> bool {
+        self.channels.is_empty() || self.channels.iter().any(|c| c == channel)
+    }
+}
+
+/// Statistics about the load shedding of an actor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadSheddingStats {
+    /// Number of overload events, i.e. how often the backlog exceeded the threshold.
+    pub num_events: u64,
+    /// Number of pending messages dropped by the actor.
+    pub num_dropped: u64,
+    /// Number of messages not forwarded by upstream connections due to decimation.
+    pub num_decimated: u64,
+    /// Largest backlog observed.
+    pub peak_backlog: usize,
+}
+
+/// Shared state between an actor under a load shedding policy and its upstream connections.
+pub(crate) struct LoadShedder {
+    actor_name: String,
+    policy: LoadSheddingPolicy,
+    overloaded: AtomicBool,
+    num_events: AtomicU64,
+    num_dropped: AtomicU64,
+    num_decimated: AtomicU64,
+    peak_backlog: AtomicUsize,
+}
+
+impl LoadShedder {
+    pub(crate) fn new(actor_name: &str, policy: LoadSheddingPolicy) -> Self {
+        Self {
+            actor_name: actor_name.to_owned(),
+            policy,
+            overloaded: AtomicBool::new(false),
+            num_events: AtomicU64::new(0),
+            num_dropped: AtomicU64::new(0),
+            num_decimated: AtomicU64::new(0),
+            peak_backlog: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> LoadSheddingStats {
+        LoadSheddingStats {
+            num_events: self.num_events.load(Ordering::Relaxed),
+            num_dropped: self.num_dropped.load(Ordering::Relaxed),
+            num_decimated: self.num_decimated.load(Ordering::Relaxed),
+            peak_backlog: self.peak_backlog.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Updates the overload state given the current backlog and returns the number of oldest
+    /// messages to drop.
+    pub(crate) fn on_backlog(&self, backlog: usize) -> usize {
+        self.peak_backlog.fetch_max(backlog, Ordering::Relaxed);
+        let overloaded = self.overloaded.load(Ordering::Relaxed);
+        if backlog > self.policy.max_backlog {
+            if !overloaded {
+                self.overloaded.store(true, Ordering::Relaxed);
+                self.num_events.fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    "{}: overloaded, backlog of {} exceeds {}, shedding load",
+                    self.actor_name, backlog, self.policy.max_backlog
+                );
+            }
+            if self.policy.drop_oldest {
+                return backlog - self.policy.max_backlog;
+            }
+        } else if overloaded && backlog <= self.policy.max_backlog / 2 {
+            self.overloaded.store(false, Ordering::Relaxed);
+            let stats = self.stats();
+            info!(
+                "{}: overload resolved (dropped: {}, decimated: {})",
+                self.actor_name, stats.num_dropped, stats.num_decimated
+            );
+        }
+        0
+    }
+
+    /// Whether a pending message of the given inbound channel may be dropped.
+    pub(crate) fn sheds(&self, channel: &str) -> bool {
+        self.policy.sheds(channel)
+    }
+
+    pub(crate) fn add_dropped(&self, num_dropped: u64) {
+        self.num_dropped.fetch_add(num_dropped, Ordering::Relaxed);
+    }
+
+    pub(crate) fn decimates(&self, channel: &str) -> bool {
+        self.policy.upstream_decimation > 1 && self.policy.sheds(channel)
+    }
+}
+
+/// Connection which decimates the messages sent to an overloaded actor.
+pub(crate) struct DecimatingConnection<T> {
+    pub(crate) inner: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    pub(crate) shedder: Arc<LoadShedder>,
+    pub(crate) counter: AtomicU64,
+}
+
+impl<T> DecimatingConnection<T> {
+    pub(crate) fn decorate(
+        connection_info: &Connection,
+        connection: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+        shedder: Option<&Arc<LoadShedder>>,
+    ) -> Arc<dyn IsGenericConnection<T> + Send + Sync>
+    where
+        T: Send + Sync + 'static,
+    {
+        match shedder {
+            Some(shedder) if shedder.decimates(&connection_info.to) => {
+                Arc::new(DecimatingConnection {
+                    inner: connection,
+                    shedder: shedder.clone(),
+                    counter: AtomicU64::new(0),
+                })
+            }
+            _ => connection,
+        }
+    }
+}
+
+impl<T: Send + Sync> IsGenericConnection<T> for DecimatingConnection<T> {
+    fn send_impl(&self, msg: T) {
+        if self.shedder.overloaded.load(Ordering::Relaxed) {
+            let count = self.counter.fetch_add(1, Ordering::Relaxed);
+            if !count.is_multiple_of(self.shedder.policy.upstream_decimation) {
+                self.shedder.num_decimated.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+        self.inner.send_impl(msg);
+    }
+}
@@ -1,12 +1,45 @@
+use crate::compute::stepping::spawn_tracked;
+use crate::compute::topology::Connection;
+use crate::core::connection::request_connection::GenericRequestConnection;
+use crate::core::connection::request_connection::PreStartRequests;
 use crate::core::connection::request_connection::RequestConnection;
 use crate::core::connection::RequestConnectionEnum;
 use crate::prelude::*;
 use linear_type::Linear;
+use std::cell::Cell;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
 use tracing::warn;
 
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Returns a new, unique request id, see [RequestWithReplyChannel::request_id()].
+pub(crate) fn next_request_id() -> u64 {
+    NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+thread_local! {
+    static DISCARDING_REQUESTS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Drops the given value, e.g. the request receiver of an actor which stopped, whereby the
+/// requests it holds are discarded without a reply instead of panicking, see
+/// [RequestWithReplyChannel].
+pub(crate) fn discard_requests<T>(requests: T) {
+    let previous = DISCARDING_REQUESTS.with(|discarding| discarding.replace(true));
+    drop(requests);
+    DISCARDING_REQUESTS.with(|discarding| discarding.set(previous));
+}
+
 /// A request hub is used to send requests to other actors which will reply later.
 pub trait IsOutRequestHub<M: IsInboundMessage>: Send + Sync + 'static + HasActivate {
     /// Create a new request hub for an actor.
@@ -33,12 +66,117 @@ pub trait IsOutRequestHub<M: IsInboundMessage>: Send + Sync + 'static + HasActiv
 /// errors early where a reply is not sent by mistake. The stacktrace will show the location
 /// where the request struct was dropped (before a reply was sent).
 ///
-/// This behavior might change in the future.
+/// Requests which are discarded by the pipeline during shutdown, e.g. since they are still
+/// queued when the replying actor stops, are dropped without a panic.
+///
+/// The panic is too strict for some applications, e.g. responders which shed load by dropping
+/// requests. Hence, the requester can choose to receive a default reply or a [ReplyError]
+/// instead, see [OutRequestChannel::set_dropped_request_policy()]. To store a pending reply in the
+/// actor's state, use [RequestWithReplyChannel::defer] instead.
+///
+/// Each request carries its own reply channel, hence several actors can send requests to the same
+/// in-request channel, and each reply is routed back to its requester, even if the requests
+/// interleave. The requester and a unique request id are exposed for bookkeeping, see
+/// [RequestWithReplyChannel::requester()] and [RequestWithReplyChannel::request_id()].
 #[derive(Debug)]
 pub struct RequestWithReplyChannel<Request, Reply> {
     /// The request.
     pub request: Request,
-    pub(crate) reply_channel: Linear<tokio::sync::oneshot::Sender<ReplyMessage<Reply>>>,
+    pub(crate) requester: String,
+    pub(crate) reply_channel: ReplyChannel<Reply>,
+}
+
+impl<Request, Reply> RequestWithReplyChannel<Request, Reply> {
+    pub(crate) fn new(
+        request: Request,
+        request_id: u64,
+        requester: String,
+        reply_sender: tokio::sync::oneshot::Sender<ReplyMessage<Reply>>,
+        on_drop: OnDroppedReply<Reply>,
+    ) -> Self {
+        Self {
+            request,
+            requester,
+            reply_channel: ReplyChannel::new(reply_sender, request_id, on_drop),
+        }
+    }
+
+    /// Unique id of the request, which is passed on to the [ReplyMessage]. Hence, an actor with
+    /// several requests in flight can correlate the replies with its requests, see
+    /// [OutRequestChannel::send_request()].
+    pub fn request_id(&self) -> u64 {
+        self.reply_channel.request_id
+    }
+
+    /// Name of the requester, i.e. `actor.out_request` for requests sent through an
+    /// [OutRequestChannel], or `client` for requests sent by a [RequestClient].
+    pub fn requester(&self) -> &str {
+        &self.requester
+    }
+}
+
+/// What a reply channel does if it is dropped without a reply, see [DroppedRequestPolicy].
+#[derive(Debug)]
+pub(crate) enum OnDroppedReply<Reply> {
+    /// Panic, since dropping the linear sender panics.
+    Panic,
+    /// Send the reply created by the given function.
+    Reply(fn() -> Reply),
+    /// Close the reply channel, which the requester is notified of.
+    Close,
+}
+
+/// Reply channel of a request, which must be consumed by sending a reply.
+#[derive(Debug)]
+pub(crate) struct ReplyChannel<Reply> {
+    sender: Option<Linear<tokio::sync::oneshot::Sender<ReplyMessage<Reply>>>>,
+    request_id: u64,
+    on_drop: OnDroppedReply<Reply>,
+}
+
+impl<Reply> ReplyChannel<Reply> {
+    fn new(
+        sender: tokio::sync::oneshot::Sender<ReplyMessage<Reply>>,
+        request_id: u64,
+        on_drop: OnDroppedReply<Reply>,
+    ) -> Self {
+        Self {
+            sender: Some(Linear::new(sender)),
+            request_id,
+            on_drop,
+        }
+    }
+
+    fn into_inner(mut self) -> tokio::sync::oneshot::Sender<ReplyMessage<Reply>> {
+        self.sender.take().unwrap().into_inner()
+    }
+}
+
+impl<Reply> Drop for ReplyChannel<Reply> {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            if DISCARDING_REQUESTS.with(|discarding| discarding.get()) {
+                debug!("Request discarded during shutdown");
+                drop(sender.into_inner());
+                return;
+            }
+            match self.on_drop {
+                // Dropping the linear sender panics.
+                OnDroppedReply::Panic => drop(sender),
+                OnDroppedReply::Reply(reply) => {
+                    debug!("Request dropped without a reply, sending the default reply");
+                    let _ = sender.into_inner().send(ReplyMessage {
+                        reply: reply(),
+                        request_id: self.request_id,
+                    });
+                }
+                OnDroppedReply::Close => {
+                    debug!("Request dropped without a reply, notifying the requester");
+                    drop(sender.into_inner());
+                }
+            }
+        }
+    }
 }
 
 /// A trait for request messages.
@@ -64,17 +202,129 @@ impl<Request, Reply: Debug> RequestWithReplyChannel<Request, Reply> {
     where
         F: FnOnce(Request) -> Reply,
     {
-        let request = self.request;
-        let reply = func(request);
-
-        let reply_channel = self.reply_channel.into_inner();
-        reply_channel.send(ReplyMessage { reply }).unwrap();
+        let reply = func(self.request);
+        Self::send_reply(self.reply_channel, reply);
     }
 
     /// Reply to the request.
+    ///
+    /// If the requester is gone (e.g. an external [RequestClient] future was dropped), the reply
+    /// is discarded with a warning.
     pub fn reply(self, reply: Reply) {
-        let reply_channel = self.reply_channel.into_inner();
-        reply_channel.send(ReplyMessage { reply }).unwrap();
+        Self::send_reply(self.reply_channel, reply);
+    }
+
+    /// Splits off the request and returns a [DeferredReply] handle to reply later, e.g. when
+    /// processing a later inbound message.
+    pub fn defer(self) -> (Request, DeferredReply<Reply>) {
+        (
+            self.request,
+            DeferredReply {
+                inner: Arc::new(DeferredReplyInner {
+                    request_id: self.reply_channel.request_id,
+                    reply_channel: Mutex::new(Some(self.reply_channel)),
+                }),
+            },
+        )
+    }
+
+    fn send_reply(reply_channel: ReplyChannel<Reply>, reply: Reply) {
+        let request_id = reply_channel.request_id;
+        if let Err(reply) = reply_channel
+            .into_inner()
+            .send(ReplyMessage { reply, request_id })
+        {
+            warn!("Requester is gone, dropping reply: {:?}", reply);
+        }
+    }
+}
+
+/// Handle to reply to a request later, obtained by [RequestWithReplyChannel::defer].
+///
+/// As opposed to [RequestWithReplyChannel], the handle can be cloned and stored in the state of
+/// an actor. The reply is sent at most once: the first call to [DeferredReply::reply] or
+/// [DeferredReply::abandon] on any of the clones completes the request, subsequent calls have no
+/// effect. If the last clone is dropped while the request is still pending, the request is
+/// abandoned with a warning instead of a panic.
+pub struct DeferredReply<Reply> {
+    inner: Arc<DeferredReplyInner<Reply>>,
+}
+
+struct DeferredReplyInner<Reply> {
+    request_id: u64,
+    reply_channel: Mutex<Option<ReplyChannel<Reply>>>,
+}
+
+impl<Reply> DeferredReplyInner<Reply> {
+    fn take(&self) -> Option<ReplyChannel<Reply>> {
+        self.reply_channel.lock().unwrap().take()
+    }
+}
+
+impl<Reply> Drop for DeferredReplyInner<Reply> {
+    fn drop(&mut self) {
+        if let Some(reply_channel) = self.take() {
+            warn!("Deferred reply dropped while pending, request abandoned");
+            drop(reply_channel.into_inner());
+        }
+    }
+}
+
+impl<Reply> Clone for DeferredReply<Reply> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Reply> Debug for DeferredReply<Reply> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeferredReply")
+            .field("request_id", &self.request_id())
+            .field("pending", &self.is_pending())
+            .finish()
+    }
+}
+
+impl<Reply> DeferredReply<Reply> {
+    /// Unique id of the request, see [RequestWithReplyChannel::request_id()].
+    pub fn request_id(&self) -> u64 {
+        self.inner.request_id
+    }
+
+    /// Whether the request was neither replied to nor abandoned yet.
+    pub fn is_pending(&self) -> bool {
+        self.inner.reply_channel.lock().unwrap().is_some()
+    }
+
+    /// Abandons the request without a reply. The requester is notified that no reply will be
+    /// sent, e.g. [RequestClient::request()] returns [RequestClientError::NoReply].
+    ///
+    /// Returns false if the request was already completed.
+    pub fn abandon(&self) -> bool {
+        match self.inner.take() {
+            Some(reply_channel) => {
+                drop(reply_channel.into_inner());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<Reply: Debug> DeferredReply<Reply> {
+    /// Sends the reply.
+    ///
+    /// Returns false if the request was already completed, in which case the reply is discarded.
+    pub fn reply(&self, reply: Reply) -> bool {
+        match self.inner.take() {
+            Some(reply_channel) => {
+                RequestWithReplyChannel::<(), Reply>::send_reply(reply_channel, reply);
+                true
+            }
+            None => false,
+        }
     }
 }
 
@@ -83,6 +333,168 @@ impl<Request, Reply: Debug> RequestWithReplyChannel<Request, Reply> {
 pub struct ReplyMessage<Reply> {
     /// The reply value.
     pub reply: Reply,
+    /// Id of the request this is the reply to, see [RequestWithReplyChannel::request_id()].
+    pub request_id: u64,
+}
+
+/// Notification that a request was not replied to in time, see
+/// [OutRequestChannel::send_request_with_timeout()].
+///
+/// It is delivered to the inbound hub of the requesting actor instead of the reply.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTimeout<Request> {
+    /// The request which timed out.
+    pub request: Request,
+    /// The timeout which elapsed.
+    pub timeout: Duration,
+}
+
+/// Handle of a request sent with [OutRequestChannel::send_request_with_timeout()].
+#[derive(Debug, Clone)]
+pub struct RequestHandle {
+    request_id: u64,
+    cancel: CancellationToken,
+}
+
+impl RequestHandle {
+    /// Unique id of the request, see [RequestWithReplyChannel::request_id()].
+    pub fn request_id(&self) -> u64 {
+        self.request_id
+    }
+
+    /// Cancels the request. Neither its reply nor its timeout is delivered to the requesting
+    /// actor, and a queued request is not dispatched at all.
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    /// Whether the request was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+/// How an out-request channel which is connected to several in-request channels routes its
+/// requests, see [OutRequestChannel::set_routing()].
+#[derive(Default)]
+pub enum RequestRouting<Request> {
+    /// Each request is sent to the next in-request channel in turn, e.g. for a pool of workers.
+    #[default]
+    RoundRobin,
+    /// Each request is sent to all in-request channels, and only the first reply is delivered.
+    FirstReply,
+    /// Each request is sent to the in-request channel selected by the given key of the request,
+    /// modulo the number of channels. Hence, requests with the same key are handled by the same
+    /// actor, e.g. to keep per-key state in a sharded pool of workers.
+    KeyHash(fn(&Request) -> u64),
+}
+
+impl<Request: std::hash::Hash> RequestRouting<Request> {
+    /// Routing by the hash of the whole request.
+    pub fn hash() -> Self {
+        RequestRouting::KeyHash(|request| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            request.hash(&mut hasher);
+            std::hash::Hasher::finish(&hasher)
+        })
+    }
+}
+
+impl<Request> Clone for RequestRouting<Request> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Request> Copy for RequestRouting<Request> {}
+
+impl<Request> Debug for RequestRouting<Request> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestRouting::RoundRobin => write!(f, "RoundRobin"),
+            RequestRouting::FirstReply => write!(f, "FirstReply"),
+            RequestRouting::KeyHash(_) => write!(f, "KeyHash"),
+        }
+    }
+}
+
+/// Error delivered to the requesting actor instead of a reply, see
+/// [DroppedRequestPolicy::Notify].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplyError {
+    /// The responder dropped the request without a reply, e.g. to shed load.
+    Dropped {
+        /// Id of the request, see [RequestWithReplyChannel::request_id()].
+        request_id: u64,
+    },
+}
+
+impl std::fmt::Display for ReplyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplyError::Dropped { request_id } => {
+                write!(f, "request #{} was dropped without a reply", request_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplyError {}
+
+/// What happens if the responder drops a request of an out-request channel without a reply, see
+/// [OutRequestChannel::set_dropped_request_policy()].
+#[derive(Default)]
+pub enum DroppedRequestPolicy<Reply, M> {
+    /// The responder panics where the request is dropped, see [RequestWithReplyChannel].
+    #[default]
+    Panic,
+    /// The reply created by the given function is sent instead.
+    Reply(fn() -> Reply),
+    /// The requesting actor is notified with a [ReplyError::Dropped] message, which is created by
+    /// the given function from the name of the out-request channel and the error.
+    Notify(fn(String, ReplyError) -> M),
+}
+
+impl<Reply: Default, M> DroppedRequestPolicy<Reply, M> {
+    /// Policy which sends the default reply.
+    pub fn reply_with_default() -> Self {
+        DroppedRequestPolicy::Reply(Reply::default)
+    }
+}
+
+impl<Reply, M: IsInboundMessageNew<ReplyError>> DroppedRequestPolicy<Reply, M> {
+    /// Policy which notifies the requesting actor through its inbound hub.
+    pub fn notify() -> Self {
+        DroppedRequestPolicy::Notify(M::new)
+    }
+}
+
+impl<Reply, M> Clone for DroppedRequestPolicy<Reply, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Reply, M> Copy for DroppedRequestPolicy<Reply, M> {}
+
+impl<Reply, M> Debug for DroppedRequestPolicy<Reply, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DroppedRequestPolicy::Panic => write!(f, "Panic"),
+            DroppedRequestPolicy::Reply(_) => write!(f, "Reply"),
+            DroppedRequestPolicy::Notify(_) => write!(f, "Notify"),
+        }
+    }
+}
+
+impl<Reply, M> DroppedRequestPolicy<Reply, M> {
+    fn on_dropped_reply(&self) -> OnDroppedReply<Reply> {
+        match self {
+            DroppedRequestPolicy::Panic => OnDroppedReply::Panic,
+            DroppedRequestPolicy::Reply(reply) => OnDroppedReply::Reply(*reply),
+            DroppedRequestPolicy::Notify(_) => OnDroppedReply::Close,
+        }
+    }
 }
 
 /// OutRequestChannel is a connections for sending requests to other actors (and receiving replies
@@ -95,6 +507,51 @@ pub struct OutRequestChannel<Request, Reply, M: IsInboundMessage> {
 
     pub(crate) connection_register: RequestConnectionEnum<RequestWithReplyChannel<Request, Reply>>,
     pub(crate) sender: tokio::sync::mpsc::UnboundedSender<M>,
+    pub(crate) in_flight: Arc<Mutex<InFlightRequests<Request, Reply, M>>>,
+    /// Requests sent before the actor started, see [PreStartSendPolicy].
+    pub(crate) pre_start: PreStartRequests<PreStartRequest<Request, Reply, M>>,
+    pub(crate) routing: Arc<Mutex<RequestRouting<Request>>>,
+    pub(crate) dropped_request_policy: Arc<Mutex<DroppedRequestPolicy<Reply, M>>>,
+    pub(crate) next_target: Arc<AtomicUsize>,
+    /// Cancelled once the actor stopped, which aborts the requests in flight.
+    pub(crate) shutdown: CancellationToken,
+}
+
+type RequestTarget<Request, Reply> =
+    Arc<dyn GenericRequestConnection<RequestWithReplyChannel<Request, Reply>> + Send + Sync>;
+
+/// A request sent before the actor started, which is sent once the channel is activated.
+type PreStartRequest<Request, Reply, M> =
+    Box<dyn FnOnce(&OutRequestChannel<Request, Reply, M>) + Send>;
+
+/// Creates the message which notifies the requesting actor of a timeout.
+type TimeoutMessageFn<Request, M> = fn(String, RequestTimeout<Request>) -> M;
+
+/// A request which is dispatched, or queued to be dispatched, with its timeout and cancellation.
+struct PendingRequest<Request, Reply, M> {
+    request_id: u64,
+    /// In-request channels to send the request to, whose first reply is delivered.
+    targets: Vec<RequestTarget<Request, Reply>>,
+    request: Request,
+    timeout: Option<(Duration, TimeoutMessageFn<Request, M>)>,
+    cancel: Option<CancellationToken>,
+}
+
+/// Requests of an out-request channel which are in flight or queued.
+pub(crate) struct InFlightRequests<Request, Reply, M> {
+    max_in_flight: usize,
+    num_in_flight: usize,
+    queue: VecDeque<PendingRequest<Request, Reply, M>>,
+}
+
+impl<Request, Reply, M> Default for InFlightRequests<Request, Reply, M> {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 0,
+            num_in_flight: 0,
+            queue: VecDeque::new(),
+        }
+    }
 }
 
 impl<Request, Reply, M: IsInboundMessage> HasActivate for OutRequestChannel<Request, Reply, M> {
@@ -104,11 +561,26 @@ impl<Request, Reply, M: IsInboundMessage> HasActivate for OutRequestChannel<Requ
             actor_name: self.actor_name.clone(),
             connection_register: self.connection_register.extract(),
             sender: self.sender.clone(),
+            in_flight: self.in_flight.clone(),
+            pre_start: self.pre_start.clone(),
+            routing: self.routing.clone(),
+            dropped_request_policy: self.dropped_request_policy.clone(),
+            next_target: self.next_target.clone(),
+            shutdown: self.shutdown.clone(),
         }
     }
 
     fn activate(&mut self) {
         self.connection_register.activate();
+        self.shutdown = CancellationToken::new();
+        for pending in self.pre_start.take() {
+            pending(self);
+        }
+    }
+
+    fn deactivate(&mut self) {
+        self.shutdown.cancel();
+        self.in_flight.lock().unwrap().queue.clear();
     }
 }
 
@@ -129,15 +601,67 @@ impl<
             actor_name: actor_name.to_owned(),
             connection_register: RequestConnectionEnum::new(),
             sender: sender.clone(),
+            in_flight: Arc::new(Mutex::new(InFlightRequests::default())),
+            pre_start: PreStartRequests::default(),
+            routing: Arc::new(Mutex::new(RequestRouting::default())),
+            dropped_request_policy: Arc::new(Mutex::new(DroppedRequestPolicy::default())),
+            next_target: Arc::new(AtomicUsize::new(0)),
+            shutdown: CancellationToken::new(),
         }
     }
 
+    /// Limits the number of concurrent requests in flight, i.e. requests which were sent but not
+    /// replied to yet. Further requests are queued and dispatched in order as replies come back.
+    /// This protects slow responders from being flooded by eager requesters.
+    ///
+    /// Zero, the default, disables the limit.
+    pub fn set_max_in_flight(&mut self, max_in_flight: usize) {
+        self.in_flight.lock().unwrap().max_in_flight = max_in_flight;
+    }
+
+    /// Number of requests which were sent but not replied to yet.
+    pub fn num_in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().num_in_flight
+    }
+
+    /// Number of requests waiting to be dispatched, see [OutRequestChannel::set_max_in_flight()].
+    pub fn queue_len(&self) -> usize {
+        self.in_flight.lock().unwrap().queue.len()
+    }
+
+    /// Sets how requests are routed if the channel is connected to several in-request channels.
+    ///
+    /// The default is [RequestRouting::RoundRobin].
+    pub fn set_routing(&mut self, routing: RequestRouting<Request>) {
+        *self.routing.lock().unwrap() = routing;
+    }
+
+    /// Sets what happens if a responder drops a request without a reply.
+    ///
+    /// The default is [DroppedRequestPolicy::Panic]. For responders which drop requests on
+    /// purpose, e.g. to shed load, use [DroppedRequestPolicy::reply_with_default()] or
+    /// [DroppedRequestPolicy::notify()] instead.
+    pub fn set_dropped_request_policy(&mut self, policy: DroppedRequestPolicy<Reply, M>) {
+        *self.dropped_request_policy.lock().unwrap() = policy;
+    }
+
     /// Connects the out-request channel from this actor to the in-request channel of another actor.
+    ///
+    /// The channel can be connected to several in-request channels, see
+    /// [OutRequestChannel::set_routing()].
     pub fn connect<Me: IsInRequestMessageNew<RequestWithReplyChannel<Request, Reply>>>(
         &mut self,
-        _ctx: &mut Hollywood,
+        ctx: &mut Hollywood,
         inbound: &mut InRequestChannel<RequestWithReplyChannel<Request, Reply>, Me>,
     ) {
+        self.pre_start.set_policy(ctx.pre_start_send_policy);
+        ctx.topology.connect_request(Connection {
+            from_actor: self.actor_name.clone(),
+            from: self.name.clone(),
+            to_actor: inbound.actor_name.clone(),
+            to: inbound.name.clone(),
+            type_name: std::any::type_name::<Request>(),
+        });
         self.connection_register.push(Arc::new(RequestConnection {
             sender: inbound.sender.as_ref().clone(),
             inbound_channel: inbound.name.clone(),
@@ -146,31 +670,265 @@ impl<
     }
 
     /// Sends a request message to the connected in-request channel of other actors.
-    pub fn send_request(&self, msg: Request) {
-        let (reply_sender, reply_receiver) = tokio::sync::oneshot::channel();
-        let msg = RequestWithReplyChannel {
+    ///
+    /// If the limit of requests in flight is reached, the request is queued. The reply is awaited
+    /// until the actor stops, see [OutRequestChannel::send_request_with_timeout()] otherwise.
+    ///
+    /// A request sent before the actor started is dispatched once it starts, according to the
+    /// [PreStartSendPolicy] of the context at the time the channel was connected.
+    ///
+    /// Returns the id of the request, which is passed on to its [ReplyMessage], e.g. to correlate
+    /// replies with requests if several requests are in flight.
+    pub fn send_request(&self, msg: Request) -> u64 {
+        let request_id = next_request_id();
+        self.send_or_buffer(request_id, msg, None, None);
+        request_id
+    }
+
+    /// Sends a request message to the given address, regardless of the connection of this
+    /// channel. The reply is received through this channel.
+    ///
+    /// If the limit of requests in flight is reached, the request is queued. Returns the id of the
+    /// request, see [OutRequestChannel::send_request()].
+    pub fn send_request_to(&self, address: &Address<Request, Reply>, msg: Request) -> u64 {
+        let request_id = next_request_id();
+        let pending = PendingRequest {
+            request_id,
+            targets: vec![address.connection.clone()],
             request: msg,
-            reply_channel: Linear::new(reply_sender),
+            timeout: None,
+            cancel: None,
+        };
+        if self.connection_register.is_config() {
+            self.pre_start.push(
+                &self.name,
+                Box::new(move |channel: &Self| channel.dispatch(pending)),
+            );
+            return request_id;
+        }
+        self.dispatch(pending);
+        request_id
+    }
+
+    /// Sends a request message to the connected in-request channel of other actors, and
+    /// delivers a [RequestTimeout] to the inbound hub of this actor instead of the reply, if no
+    /// reply arrives within the given timeout. A late reply is discarded.
+    ///
+    /// The timeout starts once the request is dispatched, i.e. a request which is queued due to
+    /// the limit of requests in flight times out later. The returned handle cancels the request.
+    pub fn send_request_with_timeout(&self, msg: Request, timeout: Duration) -> RequestHandle
+    where
+        M: IsInboundMessageNew<RequestTimeout<Request>>,
+    {
+        let request_id = next_request_id();
+        let cancel = CancellationToken::new();
+        self.send_or_buffer(
+            request_id,
+            msg,
+            Some((timeout, M::new)),
+            Some(cancel.clone()),
+        );
+        RequestHandle { request_id, cancel }
+    }
+
+    /// Dispatches the request to the connected in-request channel, or buffers it according to the
+    /// [PreStartSendPolicy] if the actor has not started yet.
+    fn send_or_buffer(
+        &self,
+        request_id: u64,
+        msg: Request,
+        timeout: Option<(Duration, TimeoutMessageFn<Request, M>)>,
+        cancel: Option<CancellationToken>,
+    ) {
+        if self.connection_register.is_config() {
+            self.pre_start.push(
+                &self.name,
+                Box::new(move |channel: &Self| {
+                    channel.send_or_buffer(request_id, msg, timeout, cancel)
+                }),
+            );
+            return;
+        }
+        let targets = self.route(&msg);
+        if targets.is_empty() {
+            warn!(
+                "{}: request channel not connected, dropping request",
+                self.name
+            );
+        } else {
+            self.dispatch(PendingRequest {
+                request_id,
+                targets,
+                request: msg,
+                timeout,
+                cancel,
+            });
+        }
+    }
+
+    /// Selects the in-request channels for the request, according to the routing.
+    fn route(&self, request: &Request) -> Vec<RequestTarget<Request, Reply>> {
+        let connections = self.connection_register.connections();
+        if connections.is_empty() {
+            return vec![];
+        }
+        let index = match *self.routing.lock().unwrap() {
+            RequestRouting::RoundRobin => self.next_target.fetch_add(1, Ordering::Relaxed),
+            RequestRouting::FirstReply => return connections.clone(),
+            RequestRouting::KeyHash(key) => key(request) as usize,
         };
-        self.connection_register.send(msg);
+        vec![connections[index % connections.len()].clone()]
+    }
 
-        let sender = self.sender.clone();
-        let name = self.name.clone();
+    fn dispatch(&self, pending: PendingRequest<Request, Reply, M>) {
+        dispatch_request(
+            self.in_flight.clone(),
+            self.sender.clone(),
+            self.name.clone(),
+            format!("{}.{}", self.actor_name, self.name),
+            *self.dropped_request_policy.lock().unwrap(),
+            self.shutdown.clone(),
+            pending,
+        );
+    }
+}
+
+fn dispatch_request<
+    Request: Clone + Send + Sync + 'static,
+    Reply: Send + Sync + std::fmt::Debug + 'static,
+    M: IsInboundMessageNew<ReplyMessage<Reply>>,
+>(
+    in_flight: Arc<Mutex<InFlightRequests<Request, Reply, M>>>,
+    sender: tokio::sync::mpsc::UnboundedSender<M>,
+    name: String,
+    requester: String,
+    dropped_request_policy: DroppedRequestPolicy<Reply, M>,
+    shutdown: CancellationToken,
+    pending: PendingRequest<Request, Reply, M>,
+) {
+    if shutdown.is_cancelled() {
+        debug!("{}: actor stopped, dropping request", name);
+        return;
+    }
+    {
+        let mut in_flight = in_flight.lock().unwrap();
+        if in_flight.max_in_flight > 0 && in_flight.num_in_flight >= in_flight.max_in_flight {
+            in_flight.queue.push_back(pending);
+            return;
+        }
+        in_flight.num_in_flight += 1;
+    }
+
+    let PendingRequest {
+        request_id,
+        targets,
+        request,
+        timeout,
+        cancel,
+    } = pending;
+    let cancelled = cancel.as_ref().is_some_and(|cancel| cancel.is_cancelled());
+    // The request is kept for the timeout notification.
+    let timeout = timeout.map(|(duration, new_message)| (duration, new_message, request.clone()));
+    let mut reply_receivers = Vec::with_capacity(targets.len());
+    if !cancelled {
+        let mut request = Some(request);
+        for (i, target) in targets.iter().enumerate() {
+            let request = if i + 1 == targets.len() {
+                request.take().unwrap()
+            } else {
+                request.clone().unwrap()
+            };
+            let (reply_sender, reply_receiver) = tokio::sync::oneshot::channel();
+            target.send_impl(RequestWithReplyChannel::new(
+                request,
+                request_id,
+                requester.clone(),
+                reply_sender,
+                dropped_request_policy.on_dropped_reply(),
+            ));
+            reply_receivers.push(reply_receiver);
+        }
+    }
 
-        tokio::spawn(async move {
-            match reply_receiver.await {
-                Ok(r) => match sender.send(M::new(name, r)) {
-                    Ok(_) => {}
-                    Err(e) => {
-                        warn!("Error sending request: {:?}", e);
+    spawn_tracked(None, async move {
+        let timed_out = async {
+            match &timeout {
+                Some((duration, _, _)) => tokio::time::sleep(*duration).await,
+                None => std::future::pending().await,
+            }
+        };
+        let on_cancel = async {
+            match &cancel {
+                Some(cancel) => cancel.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+        if !cancelled {
+            tokio::select! {
+                r = futures_util::future::select_ok(reply_receivers) => match r {
+                    Ok((r, later_replies)) => {
+                        if !later_replies.is_empty() {
+                            // Later replies are discarded, without warnings about a requester
+                            // which is gone.
+                            spawn_tracked(None, futures_util::future::join_all(later_replies));
+                        }
+                        match sender.send(M::new(name.clone(), r)) {
+                            Ok(_) => {}
+                            Err(e) => {
+                                warn!("Error sending request: {:?}", e);
+                            }
+                        }
                     }
+                    Err(e) => match dropped_request_policy {
+                        DroppedRequestPolicy::Notify(new_message) => {
+                            let msg = new_message(name.clone(), ReplyError::Dropped { request_id });
+                            if let Err(e) = sender.send(msg) {
+                                warn!("Error sending reply error: {:?}", e);
+                            }
+                        }
+                        _ => {
+                            warn!("Reply receiver error: {:?}", e);
+                        }
+                    },
                 },
-                Err(e) => {
-                    warn!("Reply receiver error: {:?}", e);
+                _ = timed_out => {
+                    let (duration, new_message, request) = timeout.unwrap();
+                    warn!("{}: request timed out after {:?}", name, duration);
+                    let msg = new_message(
+                        name.clone(),
+                        RequestTimeout {
+                            request,
+                            timeout: duration,
+                        },
+                    );
+                    if let Err(e) = sender.send(msg) {
+                        warn!("Error sending request timeout: {:?}", e);
+                    }
                 }
-            };
-        });
-    }
+                _ = on_cancel => {}
+                _ = shutdown.cancelled() => {
+                    debug!("{}: actor stopped, request in flight aborted", name);
+                }
+            }
+        }
+
+        let next = {
+            let mut in_flight = in_flight.lock().unwrap();
+            in_flight.num_in_flight -= 1;
+            in_flight.queue.pop_front()
+        };
+        if let Some(next) = next {
+            dispatch_request(
+                in_flight,
+                sender,
+                name,
+                requester,
+                dropped_request_policy,
+                shutdown,
+                next,
+            );
+        }
+    });
 }
 
 /// An empty request hub - used for actors that do not have any request channels.
@@ -193,3 +951,64 @@ impl HasActivate for NullOutRequests {
 
     fn activate(&mut self) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::rate_controller::RateControllerState;
+    use crate::actors::RateController;
+    use crate::actors::RateControllerProp;
+    use crate::prelude::*;
+    use crate::testing::ActorHarness;
+    use crate::testing::Collected;
+
+    fn rate_controller(harness: &mut ActorHarness) -> (RateController, Collected<f64>) {
+        let mut actor = RateController::from_prop_and_state(
+            harness.context(),
+            RateControllerProp::default(),
+            RateControllerState::default(),
+        );
+        let requests = harness.script_replies(&mut actor.out_requests.set_period, |period| period);
+        (actor, requests)
+    }
+
+    #[tokio::test]
+    async fn requests_before_start_are_sent_in_order() {
+        let mut harness = ActorHarness::new();
+        let (actor, requests) = rate_controller(&mut harness);
+        for period in [1.0, 2.0, 3.0] {
+            actor.out_requests.set_period.send_request(period);
+        }
+        assert!(requests.is_empty());
+
+        harness.start().await;
+        assert_eq!(requests.take(), vec![1.0, 2.0, 3.0]);
+        harness.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn requests_before_start_beyond_capacity_are_dropped() {
+        let mut harness = ActorHarness::new();
+        harness
+            .context()
+            .set_pre_start_send_policy(PreStartSendPolicy::Buffer { capacity: 2 });
+        let (actor, requests) = rate_controller(&mut harness);
+        for period in [1.0, 2.0, 3.0] {
+            actor.out_requests.set_period.send_request(period);
+        }
+
+        harness.start().await;
+        assert_eq!(requests.take(), vec![1.0, 2.0]);
+        harness.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "the pipeline has not started yet")]
+    async fn requests_before_start_panic_if_disallowed() {
+        let mut harness = ActorHarness::new();
+        harness
+            .context()
+            .set_pre_start_send_policy(PreStartSendPolicy::Panic);
+        let (actor, _requests) = rate_controller(&mut harness);
+        actor.out_requests.set_period.send_request(1.0);
+    }
+}
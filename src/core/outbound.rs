@@ -1,3 +1,6 @@
+use crate::core::correlation::InboundSender;
+use crate::core::invariant::check_invariants;
+use crate::core::invariant::Invariants;
 use crate::prelude::*;
 use std::fmt::Debug;
 use std::fmt::Formatter;
@@ -30,6 +33,25 @@ impl IsOutboundHub for NullOutbound {
     }
 }
 
+/// Reference-counted message payload.
+///
+/// An outbound channel of shared payloads broadcasts each message to all connected inbound
+/// channels by cloning the [Arc] only, hence the payload itself does not need to implement [Clone].
+/// This is the preferred message mode for large payloads such as images or point clouds:
+///
+/// ``` ignore
+/// #[actor_outputs]
+/// pub struct CameraOutbound {
+///     pub image: OutboundChannel<Shared<Image>>,
+/// }
+///
+/// outbound.image.send_shared(image);
+/// ```
+///
+/// The receiving actors get read-only access to the payload. Use [Arc::try_unwrap()] or
+/// [Arc::unwrap_or_clone()] to take ownership.
+pub type Shared<T> = Arc<T>;
+
 /// OutboundChannel is a connections for messages which are sent to a downstream actor.
 pub struct OutboundChannel<T> {
     /// Unique name of the outbound.
@@ -38,9 +60,10 @@ pub struct OutboundChannel<T> {
     pub actor_name: String,
     /// register
     pub connection_register: ConnectionEnum<T>,
+    pub(crate) invariants: Invariants<T>,
 }
 
-impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
+impl<OutT: Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
     /// Create a new outbound for actor in provided context.    
     pub fn new(context: &mut Hollywood, name: String, actor_name: &str) -> Self {
         context.assert_unique_outbound_name(name.clone(), actor_name);
@@ -48,23 +71,111 @@ impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT
         Self {
             name: name.clone(),
             actor_name: actor_name.to_owned(),
-            connection_register: ConnectionEnum::new(),
+            connection_register: ConnectionEnum::with_pre_start_policy(
+                context.pre_start_send_policy,
+            ),
+            invariants: Default::default(),
         }
     }
 
+    /// Number of inbound channels this outbound channel is connected to.
+    pub fn fan_out(&self) -> usize {
+        self.connection_register.len()
+    }
+
+    /// Connect the outbound channel exclusively to a single inbound channel.
+    ///
+    /// As opposed to [OutboundChannel::connect()], the message type does not need to implement
+    /// [Clone], since messages are moved to the single downstream actor. Use
+    /// [OutboundChannel::send_exclusive()] to send messages through this channel. The inbound
+    /// message enum of the downstream actor does not need to implement [Clone] either.
+    ///
+    /// The outbound channel admits this single connection only. If it is connected otherwise as
+    /// well, before or after, the pipeline is misconfigured, see [TopologyIssue::ExclusiveFanOut].
+    pub fn connect_exclusive<M: IsInboundMessageNew<OutT>>(
+        &mut self,
+        ctx: &mut Hollywood,
+        inbound: &mut InboundChannel<OutT, M>,
+    ) {
+        ctx.set_exclusive(self, false);
+        ctx.connect_impl(
+            self,
+            inbound,
+            Arc::new(OutboundConnection::<OutT, M> {
+                sender: inbound.sender.clone(),
+                inbound_channel: inbound.name.clone(),
+                phantom: PhantomData,
+            }),
+        );
+    }
+
+    /// Connect the outbound channel to an inbound channel of [Shared] payloads.
+    ///
+    /// Each message is moved into a [Shared] payload, i.e. it is not cloned. As with
+    /// [OutboundChannel::connect_exclusive()], the message type does not need to implement [Clone]
+    /// if the channel is only connected once. To broadcast large payloads to many inbound channels
+    /// without cloning, use an outbound channel of [Shared] payloads instead, see
+    /// [OutboundChannel::send_shared()].
+    pub fn connect_shared<M: IsInboundMessageNew<Shared<OutT>>>(
+        &mut self,
+        ctx: &mut Hollywood,
+        inbound: &mut InboundChannel<Shared<OutT>, M>,
+    ) {
+        ctx.connect_impl(
+            self,
+            inbound,
+            Arc::new(OutboundConnectionWithAdapter::<OutT, Shared<OutT>, M> {
+                sender: inbound.sender.clone(),
+                inbound_channel: inbound.name.clone(),
+                adapter: Arc::new,
+            }),
+        );
+    }
+
+    /// Send a message without cloning it to the (at most one) connected inbound channel.
+    ///
+    /// Panics if the outbound channel is connected to more than one inbound channel, which is
+    /// prevented by [OutboundChannel::connect_exclusive()].
+    pub fn send_exclusive(&self, msg: OutT) {
+        check_invariants(&self.invariants, &msg);
+        self.connection_register.send_exclusive(msg);
+    }
+}
+
+impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
     /// Connect the outbound channel from this actor to the inbound channel of another actor.
     pub fn connect<M: IsInboundMessageNew<OutT>>(
         &mut self,
         ctx: &mut Hollywood,
         inbound: &mut InboundChannel<OutT, M>,
     ) {
-        ctx.connect_impl(self, inbound);
-        self.connection_register
-            .push(Arc::new(OutboundConnection::<OutT, M> {
+        ctx.connect_impl(
+            self,
+            inbound,
+            Arc::new(OutboundConnection::<OutT, M> {
                 sender: inbound.sender.clone(),
                 inbound_channel: inbound.name.clone(),
                 phantom: PhantomData,
-            }));
+            }),
+        );
+    }
+
+    /// Connects the outbound channel to all given inbound channels at once, e.g. to tap a stream
+    /// into a viewer, a recorder and a statistics actor:
+    ///
+    /// ``` ignore
+    /// camera
+    ///     .outbound
+    ///     .image
+    ///     .tee(context, &mut [&mut viewer.inbound.image, &mut recorder.inbound.image]);
+    /// ```
+    ///
+    /// The inbound channels may belong to actors of different types. This is equivalent to
+    /// calling [OutboundChannel::connect()] for each inbound channel.
+    pub fn tee(&mut self, ctx: &mut Hollywood, sinks: &mut [&mut dyn IsInboundSink<OutT>]) {
+        for sink in sinks.iter_mut() {
+            sink.connect_from(ctx, self);
+        }
     }
 
     /// Connect the outbound channel of type OutT to the inbound channel of another type InT.
@@ -78,21 +189,37 @@ impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT
         adapter: fn(OutT) -> InT,
         inbound: &mut InboundChannel<InT, M>,
     ) {
-        ctx.connect_impl(self, inbound);
-        self.connection_register
-            .push(Arc::new(OutboundConnectionWithAdapter::<OutT, InT, M> {
+        ctx.connect_impl(
+            self,
+            inbound,
+            Arc::new(OutboundConnectionWithAdapter::<OutT, InT, M> {
                 sender: inbound.sender.clone(),
                 inbound_channel: inbound.name.clone(),
                 adapter,
-            }));
+            }),
+        );
     }
 
     /// Send a message to the connected inbound channels to other actors.
+    ///
+    /// Messages sent before the pipeline is started, i.e. during configuration, are handled
+    /// according to the [PreStartSendPolicy] of the context. By default, they are buffered and
+    /// sent in order once the actor starts.
     pub fn send(&self, msg: OutT) {
+        check_invariants(&self.invariants, &msg);
         self.connection_register.send(msg);
     }
 }
 
+impl<OutT: Send + Sync + std::fmt::Debug + 'static> OutboundChannel<Shared<OutT>> {
+    /// Wraps the message into a [Shared] payload and sends it to the connected inbound channels.
+    ///
+    /// The payload is allocated once and shared by all receivers, regardless of the fan-out.
+    pub fn send_shared(&self, msg: OutT) {
+        self.send(Arc::new(msg));
+    }
+}
+
 /// Outbound/request channel activation
 pub trait HasActivate {
     /// Extract outbound/request channel and returns it.
@@ -100,6 +227,10 @@ pub trait HasActivate {
 
     /// Activates the outbound/request channel to be used.
     fn activate(&mut self);
+
+    /// Deactivates the outbound/request channel once the actor stopped, e.g. to cancel the
+    /// requests in flight.
+    fn deactivate(&mut self) {}
 }
 
 impl<T> HasActivate for OutboundChannel<T> {
@@ -112,20 +243,21 @@ impl<T> HasActivate for OutboundChannel<T> {
             name: self.name.clone(),
             actor_name: self.actor_name.clone(),
             connection_register: self.connection_register.extract(),
+            invariants: self.invariants.clone(),
         }
     }
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct OutboundConnection<Out, M: IsInboundMessage> {
-    pub(crate) sender: tokio::sync::mpsc::UnboundedSender<M>,
+    pub(crate) sender: InboundSender<M>,
     pub(crate) inbound_channel: String,
     pub(crate) phantom: std::marker::PhantomData<Out>,
 }
 
 #[derive(Clone)]
 pub(crate) struct OutboundConnectionWithAdapter<Out, InT, M: IsInboundMessage> {
-    pub(crate) sender: tokio::sync::mpsc::UnboundedSender<M>,
+    pub(crate) sender: InboundSender<M>,
     pub(crate) inbound_channel: String,
     pub(crate) adapter: fn(Out) -> InT,
 }
@@ -149,16 +281,11 @@ impl<Out: Send + Sync, M: IsInboundMessageNew<Out>> IsGenericConnection<Out>
 {
     fn send_impl(&self, msg: Out) {
         let msg = M::new(self.inbound_channel.clone(), msg);
-        let c = self.sender.clone();
-        let handler = tokio::spawn(async move {
-            match c.send(msg) {
-                Ok(_) => {}
-                Err(SendError(e)) => {
-                    warn!("Send message error: {:?}", e);
-                }
-            }
-        });
-        std::mem::drop(handler);
+        // Sending on an unbounded channel does not block, hence the message is sent right away.
+        // This preserves the order of messages sent through the same connection.
+        if let Err(SendError(e)) = self.sender.send(msg) {
+            warn!("Send message error: {:?}", e);
+        }
     }
 }
 
@@ -167,15 +294,10 @@ impl<Out: Send + Sync, InT, M: IsInboundMessageNew<InT>> IsGenericConnection<Out
 {
     fn send_impl(&self, msg: Out) {
         let msg = M::new(self.inbound_channel.clone(), (self.adapter)(msg));
-        let c = self.sender.clone();
-        let handler = tokio::spawn(async move {
-            match c.send(msg) {
-                Ok(_) => {}
-                Err(SendError(e)) => {
-                    warn!("Send message error: {:?}", e);
-                }
-            }
-        });
-        std::mem::drop(handler);
+        // Sending on an unbounded channel does not block, hence the message is sent right away.
+        // This preserves the order of messages sent through the same connection.
+        if let Err(SendError(e)) = self.sender.send(msg) {
+            warn!("Send message error: {:?}", e);
+        }
     }
 }
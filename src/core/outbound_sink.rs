@@ -0,0 +1,89 @@
+use crate::actors::BackgroundActor;
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use futures_util::Sink;
+use futures_util::SinkExt;
+use std::sync::Arc;
+use tracing::warn;
+
+struct SinkConnection<T> {
+    sender: tokio::sync::mpsc::UnboundedSender<T>,
+}
+
+impl<T: Send + Sync> IsGenericConnection<T> for SinkConnection<T> {
+    fn send_impl(&self, msg: T) {
+        // The sink might have failed, which is already logged.
+        let _ = self.sender.send(msg);
+    }
+}
+
+impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
+    /// Forwards the messages of this channel into the given sink, e.g. of an existing network or
+    /// protocol stack.
+    ///
+    /// The forwarding is done by a [BackgroundActor], which is returned. Messages are forwarded
+    /// while the pipeline is running; pending messages are forwarded and the sink is flushed once
+    /// the pipeline stops. If the sink fails, the error is logged and no more messages are
+    /// forwarded.
+    pub fn forward_to_sink<S>(&mut self, ctx: &mut Hollywood, mut sink: S) -> BackgroundActor
+    where
+        S: Sink<OutT> + Send + Unpin + 'static,
+        S::Error: std::fmt::Display + Send,
+    {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let outbound_name = self.name.clone();
+        let background =
+            BackgroundActor::from_named_task(ctx, "SinkBridge", move |mut kill| async move {
+                let mut result = Ok(());
+                while result.is_ok() {
+                    tokio::select! {
+                        _ = kill.changed() => break,
+                        msg = receiver.recv() => match msg {
+                            Some(msg) => result = sink.send(msg).await,
+                            None => break,
+                        },
+                    }
+                }
+                while let (true, Ok(msg)) = (result.is_ok(), receiver.try_recv()) {
+                    result = sink.feed(msg).await;
+                }
+                if result.is_ok() {
+                    result = sink.flush().await;
+                }
+                if let Err(err) = result {
+                    warn!("{}: sink failed: {}", outbound_name, err);
+                    let _ = kill.wait_for(|kill| *kill).await;
+                }
+            });
+        let connection = ctx.decorate_connection(
+            Connection {
+                from_actor: self.actor_name.clone(),
+                from: self.name.clone(),
+                to_actor: background.actor_name.clone(),
+                to: "sink".to_owned(),
+                type_name: std::any::type_name::<OutT>(),
+            },
+            Arc::new(SinkConnection { sender }),
+        );
+        self.connection_register.push(connection);
+        background
+    }
+
+    /// Encodes the messages of this channel with the given codec and writes them to the given
+    /// writer, e.g. a TCP stream.
+    ///
+    /// See [OutboundChannel::forward_to_sink()] for details.
+    pub fn forward_to_writer<W, E>(
+        &mut self,
+        ctx: &mut Hollywood,
+        writer: W,
+        encoder: E,
+    ) -> BackgroundActor
+    where
+        W: tokio::io::AsyncWrite + Send + Unpin + 'static,
+        E: tokio_util::codec::Encoder<OutT> + Send + 'static,
+        E::Error: std::fmt::Display + Send,
+    {
+        self.forward_to_sink(ctx, tokio_util::codec::FramedWrite::new(writer, encoder))
+    }
+}
@@ -0,0 +1,73 @@
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use std::sync::Arc;
+
+/// Handle to consume the messages of an outbound channel outside the pipeline.
+///
+/// A subscriber is obtained during configuration using [OutboundChannel::subscriber()]. It
+/// receives a copy of every message sent through the outbound channel, e.g. to feed a third-party
+/// library which owns its own event loop.
+#[derive(Debug)]
+pub struct OutboundSubscriber<T> {
+    receiver: tokio::sync::mpsc::UnboundedReceiver<T>,
+}
+
+impl<T> OutboundSubscriber<T> {
+    /// Receives the next message. Returns None once the actor stopped.
+    pub async fn recv(&mut self) -> Option<T> {
+        self.receiver.recv().await
+    }
+
+    /// Receives the next message, if one is available right away.
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Blocks the current thread until the next message is received. Returns None once the actor
+    /// stopped.
+    ///
+    /// Panics if called from within an async context.
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        self.receiver.blocking_recv()
+    }
+
+    /// Returns the underlying tokio receiver.
+    pub fn into_receiver(self) -> tokio::sync::mpsc::UnboundedReceiver<T> {
+        self.receiver
+    }
+
+    /// Returns the messages as [tokio_stream::Stream].
+    pub fn into_stream(self) -> tokio_stream::wrappers::UnboundedReceiverStream<T> {
+        tokio_stream::wrappers::UnboundedReceiverStream::new(self.receiver)
+    }
+}
+
+struct SubscriberConnection<T> {
+    sender: tokio::sync::mpsc::UnboundedSender<T>,
+}
+
+impl<T: Send + Sync> IsGenericConnection<T> for SubscriberConnection<T> {
+    fn send_impl(&self, msg: T) {
+        // The subscriber might be gone, which is not an error.
+        let _ = self.sender.send(msg);
+    }
+}
+
+impl<OutT: Clone + Send + Sync + std::fmt::Debug + 'static> OutboundChannel<OutT> {
+    /// Returns a handle to consume the messages of this channel outside the pipeline.
+    pub fn subscriber(&mut self, ctx: &mut Hollywood) -> OutboundSubscriber<OutT> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let connection = ctx.decorate_connection(
+            Connection {
+                from_actor: self.actor_name.clone(),
+                from: self.name.clone(),
+                to_actor: "external".to_owned(),
+                to: "subscriber".to_owned(),
+                type_name: std::any::type_name::<OutT>(),
+            },
+            Arc::new(SubscriberConnection { sender }),
+        );
+        self.connection_register.push(connection);
+        OutboundSubscriber { receiver }
+    }
+}
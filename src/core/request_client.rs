@@ -0,0 +1,116 @@
+use crate::core::out_request::discard_requests;
+use crate::core::out_request::next_request_id;
+use crate::core::out_request::OnDroppedReply;
+use crate::prelude::*;
+use std::sync::Arc;
+
+pub(crate) type RequestSender<Request, Reply> = Arc<
+    dyn Fn(RequestWithReplyChannel<Request, Reply>) -> Result<(), RequestClientError> + Send + Sync,
+>;
+
+/// Error returned by [RequestClient::request()].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestClientError {
+    /// The actor is not running anymore and cannot receive requests.
+    Disconnected,
+    /// The actor dropped the request without replying.
+    NoReply,
+}
+
+impl std::fmt::Display for RequestClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestClientError::Disconnected => write!(f, "actor is not running"),
+            RequestClientError::NoReply => write!(f, "actor dropped request without reply"),
+        }
+    }
+}
+
+impl std::error::Error for RequestClientError {}
+
+/// Handle to send requests to an in-request channel from outside the pipeline.
+///
+/// A client is obtained during configuration using [InRequestChannel::client()] and can be used
+/// by external (non-actor) code once the pipeline is running, e.g. to forward calls received by a
+/// server to an actor:
+///
+/// ``` ignore
+/// let mut client = None;
+/// let pipeline = Hollywood::configure(&mut |context| {
+///     let filter = Filter::from_prop_and_state(context, NullProp {}, FilterState::default());
+///     client = Some(filter.in_requests.ping_pong_request.client());
+/// });
+/// tokio::spawn(pipeline.run());
+/// let pong = client.unwrap().request(1.0).await?;
+/// ```
+pub struct RequestClient<Request, Reply> {
+    pub(crate) actor_name: String,
+    pub(crate) in_request_name: String,
+    pub(crate) sender: RequestSender<Request, Reply>,
+}
+
+impl<Request, Reply> Clone for RequestClient<Request, Reply> {
+    fn clone(&self) -> Self {
+        Self {
+            actor_name: self.actor_name.clone(),
+            in_request_name: self.in_request_name.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<Request, Reply> RequestClient<Request, Reply> {
+    /// Name of the actor the requests are sent to.
+    pub fn actor_name(&self) -> &str {
+        &self.actor_name
+    }
+
+    /// Name of the in-request channel the requests are sent to.
+    pub fn in_request_name(&self) -> &str {
+        &self.in_request_name
+    }
+
+    /// Sends a request to the actor and waits for the reply.
+    pub async fn request(&self, request: Request) -> Result<Reply, RequestClientError> {
+        let (reply_sender, reply_receiver) = tokio::sync::oneshot::channel();
+        (self.sender)(RequestWithReplyChannel::new(
+            request,
+            next_request_id(),
+            "client".to_owned(),
+            reply_sender,
+            OnDroppedReply::Panic,
+        ))?;
+        match reply_receiver.await {
+            Ok(reply) => Ok(reply.reply),
+            Err(_) => Err(RequestClientError::NoReply),
+        }
+    }
+}
+
+impl<
+        Request: Send + Sync + std::fmt::Debug + 'static,
+        Reply: Send + Sync + std::fmt::Debug + 'static,
+        M: IsInRequestMessageNew<RequestWithReplyChannel<Request, Reply>>,
+    > InRequestChannel<RequestWithReplyChannel<Request, Reply>, M>
+{
+    /// Returns a client to send requests to this channel from outside the pipeline.
+    pub fn client(&self) -> RequestClient<Request, Reply> {
+        let sender = self.sender.clone();
+        let name = self.name.clone();
+        RequestClient {
+            actor_name: self.actor_name.clone(),
+            in_request_name: self.name.clone(),
+            sender: Arc::new(move |request| {
+                if sender.is_closed() {
+                    discard_requests(request);
+                    return Err(RequestClientError::Disconnected);
+                }
+                sender
+                    .send(M::new(name.clone(), request))
+                    // The actor stopped in the meantime.
+                    .map_err(|err| discard_requests(err.0))
+                    .map_err(|_| RequestClientError::Disconnected)
+            }),
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::compute::topology::Connection;
+use crate::core::connection::request_connection::PreStartRequests;
+use crate::core::connection::request_connection::RequestConnection;
+use crate::core::connection::RequestConnectionEnum;
+use crate::core::out_request::next_request_id;
+use crate::prelude::*;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tracing::debug;
+use tracing::warn;
+
+/// Sends a message of the reply stream to the requesting actor, and returns false if it is gone.
+type ReplyStreamSink<Item> = Arc<dyn Fn(ReplyStreamMessage<Item>) -> bool + Send + Sync>;
+
+/// A request message whose reply is a stream of items, followed by a completion, e.g. to query
+/// the map tiles around a position.
+///
+/// As opposed to [RequestWithReplyChannel], which is replied to exactly once, the responder sends
+/// any number of items using [RequestWithReplyStream::send()], and completes the stream using
+/// [RequestWithReplyStream::finish()]. Each item and the completion are delivered to the requesting
+/// actor as a [ReplyStreamMessage], in order.
+///
+/// The struct can be stored and forwarded, e.g. to stream items as they become available. If it is
+/// dropped before the stream is finished, the stream is completed on drop, hence the requester is
+/// always notified of the end of the stream.
+pub struct RequestWithReplyStream<Request, Item> {
+    /// The request.
+    pub request: Request,
+    pub(crate) request_id: u64,
+    pub(crate) requester: String,
+    sink: Option<ReplyStreamSink<Item>>,
+}
+
+impl<Request: Debug, Item> Debug for RequestWithReplyStream<Request, Item> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestWithReplyStream")
+            .field("request", &self.request)
+            .field("request_id", &self.request_id)
+            .field("requester", &self.requester)
+            .finish()
+    }
+}
+
+impl<Request, Item> RequestWithReplyStream<Request, Item> {
+    /// Unique id of the request, which is passed on to each [ReplyStreamMessage].
+    pub fn request_id(&self) -> u64 {
+        self.request_id
+    }
+
+    /// Name of the requester, i.e. `actor.out_request`.
+    pub fn requester(&self) -> &str {
+        &self.requester
+    }
+
+    /// Sends an item of the reply stream.
+    ///
+    /// Returns false if the requester is gone, e.g. since the requesting actor stopped. Hence, a
+    /// long-running stream can be stopped early.
+    pub fn send(&self, item: Item) -> bool {
+        let sink = self.sink.as_ref().unwrap();
+        sink(ReplyStreamMessage::Item {
+            request_id: self.request_id,
+            item,
+        })
+    }
+
+    /// Completes the reply stream.
+    pub fn finish(mut self) {
+        self.complete();
+    }
+
+    fn complete(&mut self) {
+        if let Some(sink) = self.sink.take() {
+            if !sink(ReplyStreamMessage::Complete {
+                request_id: self.request_id,
+            }) {
+                debug!("Requester is gone, dropping completion of reply stream");
+            }
+        }
+    }
+}
+
+impl<Request, Item> Drop for RequestWithReplyStream<Request, Item> {
+    fn drop(&mut self) {
+        self.complete();
+    }
+}
+
+/// A message of a reply stream, see [RequestWithReplyStream].
+#[derive(Debug, Clone)]
+pub enum ReplyStreamMessage<Item> {
+    /// An item of the reply stream.
+    Item {
+        /// Id of the request, see [RequestWithReplyStream::request_id()].
+        request_id: u64,
+        /// The item.
+        item: Item,
+    },
+    /// The reply stream is complete, and no further items follow.
+    Complete {
+        /// Id of the request, see [RequestWithReplyStream::request_id()].
+        request_id: u64,
+    },
+}
+
+impl<Item> ReplyStreamMessage<Item> {
+    /// Id of the request, see [RequestWithReplyStream::request_id()].
+    pub fn request_id(&self) -> u64 {
+        match self {
+            ReplyStreamMessage::Item { request_id, .. } => *request_id,
+            ReplyStreamMessage::Complete { request_id } => *request_id,
+        }
+    }
+}
+
+/// OutRequestStreamChannel is a connection for sending requests to other actors, which reply with
+/// a stream of items, see [RequestWithReplyStream].
+///
+/// It is part of the out-request hub of an actor, just as [OutRequestChannel]. The items and the
+/// completion of each stream are delivered to the inbound hub of the actor as
+/// [ReplyStreamMessage]s.
+pub struct OutRequestStreamChannel<Request, Item, M: IsInboundMessage> {
+    /// Unique name of the request channel.
+    pub name: String,
+    /// Name of the actor that sends the request messages.
+    pub actor_name: String,
+
+    pub(crate) connection_register: RequestConnectionEnum<RequestWithReplyStream<Request, Item>>,
+    pub(crate) sender: tokio::sync::mpsc::UnboundedSender<M>,
+    pub(crate) next_target: Arc<AtomicUsize>,
+    /// Requests sent before the actor started, see [PreStartSendPolicy].
+    pre_start: PreStartRequests<PreStartStreamRequest<Request, Item, M>>,
+}
+
+/// A request sent before the actor started, which is sent once the channel is activated.
+type PreStartStreamRequest<Request, Item, M> =
+    Box<dyn FnOnce(&OutRequestStreamChannel<Request, Item, M>) + Send>;
+
+impl<Request, Item, M: IsInboundMessage> HasActivate for OutRequestStreamChannel<Request, Item, M> {
+    fn extract(&mut self) -> Self {
+        Self {
+            name: self.name.clone(),
+            actor_name: self.actor_name.clone(),
+            connection_register: self.connection_register.extract(),
+            sender: self.sender.clone(),
+            next_target: self.next_target.clone(),
+            pre_start: self.pre_start.clone(),
+        }
+    }
+
+    fn activate(&mut self) {
+        self.connection_register.activate();
+        for pending in self.pre_start.take() {
+            pending(self);
+        }
+    }
+}
+
+impl<
+        Request: Send + Sync + Debug + 'static,
+        Item: Send + Sync + Debug + 'static,
+        M: IsInboundMessageNew<ReplyStreamMessage<Item>>,
+    > OutRequestStreamChannel<Request, Item, M>
+{
+    /// Creates a new out-request stream channel for the actor.
+    pub fn new(
+        name: String,
+        actor_name: &str,
+        sender: &tokio::sync::mpsc::UnboundedSender<M>,
+    ) -> Self {
+        Self {
+            name,
+            actor_name: actor_name.to_owned(),
+            connection_register: RequestConnectionEnum::new(),
+            sender: sender.clone(),
+            next_target: Arc::new(AtomicUsize::new(0)),
+            pre_start: PreStartRequests::default(),
+        }
+    }
+
+    /// Connects the out-request stream channel from this actor to the in-request channel of
+    /// another actor.
+    ///
+    /// If the channel is connected to several in-request channels, the requests are sent to each
+    /// of them in turn.
+    pub fn connect<Me: IsInRequestMessageNew<RequestWithReplyStream<Request, Item>>>(
+        &mut self,
+        ctx: &mut Hollywood,
+        inbound: &mut InRequestChannel<RequestWithReplyStream<Request, Item>, Me>,
+    ) {
+        self.pre_start.set_policy(ctx.pre_start_send_policy);
+        ctx.topology.connect_request(Connection {
+            from_actor: self.actor_name.clone(),
+            from: self.name.clone(),
+            to_actor: inbound.actor_name.clone(),
+            to: inbound.name.clone(),
+            type_name: std::any::type_name::<Request>(),
+        });
+        self.connection_register.push(Arc::new(RequestConnection {
+            sender: inbound.sender.as_ref().clone(),
+            inbound_channel: inbound.name.clone(),
+            phantom: PhantomData {},
+        }));
+    }
+
+    /// Sends a request message to the connected in-request channel of another actor.
+    ///
+    /// Returns the id of the request, which is passed on to each [ReplyStreamMessage], e.g. to
+    /// tell the streams of several requests in flight apart.
+    ///
+    /// A request sent before the actor started is sent once it starts, according to the
+    /// [PreStartSendPolicy] of the context at the time the channel was connected.
+    pub fn send_request(&self, msg: Request) -> u64 {
+        let request_id = next_request_id();
+        if self.connection_register.is_config() {
+            self.pre_start.push(
+                &self.name,
+                Box::new(move |channel: &Self| channel.send_request_impl(request_id, msg)),
+            );
+        } else {
+            self.send_request_impl(request_id, msg);
+        }
+        request_id
+    }
+
+    fn send_request_impl(&self, request_id: u64, msg: Request) {
+        let connections = self.connection_register.connections();
+        if connections.is_empty() {
+            warn!(
+                "{}: request channel not connected, dropping request",
+                self.name
+            );
+            return;
+        }
+        let index = self.next_target.fetch_add(1, Ordering::Relaxed) % connections.len();
+        let sender = self.sender.clone();
+        let name = self.name.clone();
+        connections[index].send_impl(RequestWithReplyStream {
+            request: msg,
+            request_id,
+            requester: format!("{}.{}", self.actor_name, self.name),
+            sink: Some(Arc::new(move |msg| {
+                sender.send(M::new(name.clone(), msg)).is_ok()
+            })),
+        });
+    }
+}
@@ -1,5 +1,10 @@
 use crate::core::actor::ActorNodeImpl;
+use crate::core::clock::ClockTimer;
+use crate::core::error_policy::ActorErrorHandler;
+use crate::core::fused::ActorCore;
+use crate::core::fused::FusedSlot;
 use crate::prelude::*;
+use std::sync::Arc;
 
 /// Runner executes the pipeline.
 pub trait IsRunner<
@@ -14,21 +19,21 @@ pub trait IsRunner<
 >
 {
     /// Create a new actor to be stored by the context.
+    ///
+    /// The inbound messages are received through the receiver of regular messages and the
+    /// receiver of high-priority messages, see [InboundChannel::new_priority()].
     fn new_actor_node(
         name: String,
         prop: Prop,
         init_state: State,
-        forward_receiver_outbound: (
-            ForwardTable<Prop, State, Outbound, OutRequest, M>,
-            tokio::sync::mpsc::UnboundedReceiver<M>,
-            Outbound,
-        ),
+        forward_receiver_outbound: ForwardReceiverOutbound<Prop, State, Outbound, OutRequest, M>,
         forward_receiver_request: (
             ForwardRequestTable<Prop, State, Outbound, OutRequest, R>,
             tokio::sync::mpsc::UnboundedReceiver<R>,
             OutRequest,
         ),
         on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+        errors: ActorErrorHandler<State>,
     ) -> Box<dyn IsActorNode + Send + Sync>;
 }
 
@@ -91,29 +96,121 @@ impl<
         name: String,
         prop: Prop,
         init_state: State,
-        forward_receiver_outbound: (
-            ForwardTable<Prop, State, Outbound, OutRequest, M>,
-            tokio::sync::mpsc::UnboundedReceiver<M>,
-            Outbound,
+        forward_receiver_outbound: ForwardReceiverOutbound<Prop, State, Outbound, OutRequest, M>,
+        forward_receiver_request: (
+            ForwardRequestTable<Prop, State, Outbound, OutRequest, R>,
+            tokio::sync::mpsc::UnboundedReceiver<R>,
+            OutRequest,
         ),
+        on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+        errors: ActorErrorHandler<State>,
+    ) -> Box<dyn IsActorNode + Send + Sync> {
+        new_actor_node_impl(
+            name,
+            prop,
+            init_state,
+            forward_receiver_outbound,
+            forward_receiver_request,
+            on_exit_fn,
+            errors,
+            None,
+        )
+    }
+}
+
+/// The runner of time-based actors, whose state implements [HasClockTimer].
+///
+/// Besides running the actor like the [DefaultRunner], it sets the current time of the pipeline
+/// clock before each message is handled, and calls [HasClockTimer::on_deadline()] on time, see
+/// [Hollywood::set_clock()].
+pub struct ClockRunner<
+    Prop,
+    Inbound: Send + Sync,
+    InRequest,
+    State,
+    Outbound: Send + Sync + 'static,
+    Request: Send + Sync + 'static,
+> {
+    phantom: std::marker::PhantomData<(Prop, Inbound, InRequest, State, Outbound, Request)>,
+}
+
+impl<
+        Prop: std::marker::Send + std::marker::Sync + 'static,
+        Inbound: IsInboundHub<Prop, State, Outbound, OutRequest, M, R>,
+        InRequest,
+        State: HasClockTimer<Prop, Outbound>,
+        Outbound: IsOutboundHub,
+        R: IsInRequestMessage,
+        M: IsInboundMessage,
+        OutRequest: IsOutRequestHub<M>,
+    > IsRunner<Prop, Inbound, InRequest, State, Outbound, OutRequest, M, R>
+    for ClockRunner<Prop, Inbound, InRequest, State, Outbound, OutRequest>
+{
+    fn new_actor_node(
+        name: String,
+        prop: Prop,
+        init_state: State,
+        forward_receiver_outbound: ForwardReceiverOutbound<Prop, State, Outbound, OutRequest, M>,
         forward_receiver_request: (
             ForwardRequestTable<Prop, State, Outbound, OutRequest, R>,
             tokio::sync::mpsc::UnboundedReceiver<R>,
             OutRequest,
         ),
         on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+        errors: ActorErrorHandler<State>,
     ) -> Box<dyn IsActorNode + Send + Sync> {
-        Box::new(ActorNodeImpl::<Prop, State, Outbound, OutRequest, M, R> {
+        new_actor_node_impl(
             name,
             prop,
-            state: Some(init_state),
+            init_state,
+            forward_receiver_outbound,
+            forward_receiver_request,
+            on_exit_fn,
+            errors,
+            Some(ClockTimer::default()),
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn new_actor_node_impl<
+    Prop: std::marker::Send + std::marker::Sync + 'static,
+    State: std::marker::Send + std::marker::Sync + 'static,
+    Outbound: IsOutboundHub,
+    OutRequest: IsOutRequestHub<M>,
+    M: IsInboundMessage,
+    R: IsInRequestMessage,
+>(
+    name: String,
+    prop: Prop,
+    init_state: State,
+    forward_receiver_outbound: ForwardReceiverOutbound<Prop, State, Outbound, OutRequest, M>,
+    forward_receiver_request: (
+        ForwardRequestTable<Prop, State, Outbound, OutRequest, R>,
+        tokio::sync::mpsc::UnboundedReceiver<R>,
+        OutRequest,
+    ),
+    on_exit_fn: Option<Box<dyn FnOnce() + Send + Sync + 'static>>,
+    errors: ActorErrorHandler<State>,
+    timer: Option<ClockTimer<Prop, State, Outbound>>,
+) -> Box<dyn IsActorNode + Send + Sync> {
+    Box::new(ActorNodeImpl::<Prop, State, Outbound, OutRequest, M, R> {
+        name,
+        core: Arc::new(FusedSlot::new(ActorCore {
+            prop,
+            state: init_state,
             forward: forward_receiver_outbound.0,
-            receiver: Some(forward_receiver_outbound.1),
             outbound: forward_receiver_outbound.2,
-            forward_request: forward_receiver_request.0,
-            request_receiver: Some(forward_receiver_request.1),
             out_request: forward_receiver_request.2,
-            on_exit_fn,
-        })
-    }
+            errors,
+            timer,
+        })),
+        receiver: Some(forward_receiver_outbound.1),
+        priority_receiver: Some(forward_receiver_outbound.3),
+        forward_request: forward_receiver_request.0,
+        request_receiver: Some(forward_receiver_request.1),
+        on_exit_fn,
+        run_options: ActorRunOptions::default(),
+        batch_stats: BatchStats::default(),
+    })
 }
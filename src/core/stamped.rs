@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+/// Kind of clock a [Timestamp] was taken from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockKind {
+    /// Monotonic clock, such as the time elapsed since the pipeline started or simulated time.
+    #[default]
+    Monotonic,
+    /// Wall clock, i.e. seconds since the Unix epoch, which may jump.
+    WallClock,
+}
+
+/// Point in time, in seconds, tagged with the kind and domain of the clock it was taken from.
+///
+/// Timestamps of different clocks are not comparable: e.g. the elapsed time of a [Periodic] and
+/// the simulated time of a simulation actor are both monotonic, but are in different clock
+/// domains. Hence [Timestamp] only implements [PartialOrd], and [Timestamp::seconds_since()]
+/// returns None for timestamps of different clocks.
+///
+/// [Periodic]: crate::actors::Periodic
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamp {
+    /// Seconds since the origin of the clock.
+    pub seconds: f64,
+    /// Kind of the clock.
+    pub kind: ClockKind,
+    /// Label of the clock domain.
+    pub domain: Cow<'static, str>,
+}
+
+impl Default for Timestamp {
+    fn default() -> Self {
+        Self::monotonic(0.0)
+    }
+}
+
+impl Timestamp {
+    /// Clock domain of the pipeline, e.g. the time elapsed since the pipeline started.
+    pub const PIPELINE_DOMAIN: &'static str = "pipeline";
+    /// Clock domain of the system wall clock.
+    pub const SYSTEM_DOMAIN: &'static str = "system";
+
+    /// Creates a monotonic timestamp in the pipeline clock domain.
+    pub fn monotonic(seconds: f64) -> Self {
+        Self {
+            seconds,
+            kind: ClockKind::Monotonic,
+            domain: Cow::Borrowed(Self::PIPELINE_DOMAIN),
+        }
+    }
+
+    /// Creates a wall-clock timestamp of the current system time.
+    pub fn wall_clock_now() -> Self {
+        let seconds = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        Self {
+            seconds,
+            kind: ClockKind::WallClock,
+            domain: Cow::Borrowed(Self::SYSTEM_DOMAIN),
+        }
+    }
+
+    /// Sets the clock domain.
+    pub fn with_domain(mut self, domain: impl Into<Cow<'static, str>>) -> Self {
+        self.domain = domain.into();
+        self
+    }
+
+    /// Whether both timestamps are taken from the same clock.
+    pub fn is_comparable(&self, other: &Timestamp) -> bool {
+        self.kind == other.kind && self.domain == other.domain
+    }
+
+    /// Seconds elapsed since the earlier timestamp, or None if they are not comparable.
+    pub fn seconds_since(&self, earlier: &Timestamp) -> Option<f64> {
+        self.is_comparable(earlier)
+            .then_some(self.seconds - earlier.seconds)
+    }
+}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if !self.is_comparable(other) {
+            return None;
+        }
+        self.seconds.partial_cmp(&other.seconds)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}s ({})", self.seconds, self.domain)
+    }
+}
+
+/// A generic value with a timestamp and sequence number.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stamped<T> {
+    /// Timestamp of the value.
+    pub time: Timestamp,
+    /// Monotonic sequence counter of the source.
+    pub seq: u64,
+    /// The value.
+    pub value: T,
+}
+
+impl<T> Stamped<T> {
+    /// Creates a new value with a timestamp and sequence number.
+    pub fn new(time: Timestamp, seq: u64, value: T) -> Self {
+        Self { time, seq, value }
+    }
+
+    /// Creates a new value with a timestamp and sequence number, cloning the value.
+    pub fn from_stamp_counter_and_value(time: Timestamp, seq: u64, value: &T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(time, seq, value.clone())
+    }
+
+    /// Maps the value, keeping timestamp and sequence number.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Stamped<U> {
+        Stamped {
+            time: self.time,
+            seq: self.seq,
+            value: f(self.value),
+        }
+    }
+}
+
+impl<T: Display> Display for Stamped<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{} {}", self.time.seconds, self.value)
+    }
+}
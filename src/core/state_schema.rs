@@ -0,0 +1,127 @@
+/// Schema of an actor's state type, used to detect stale states between pipeline versions.
+///
+/// A state which is stored by one version of a pipeline (e.g. as part of a snapshot) and restored
+/// by another must not be reused if the state struct changed in between. To detect this, store the
+/// schema along with the state and compare it with the schema of the current state type on
+/// restore, using [StateSchema::check()] or [StateSchema::verify()].
+///
+/// The schema is derived from the type name, size and alignment of the state type. Since this
+/// does not capture all changes (e.g. swapping two fields of the same type, or changing the
+/// meaning of a field), an explicit version can be added using [StateSchema::with_version()],
+/// which should be bumped whenever the state changes in an incompatible way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateSchema {
+    /// Fully qualified name of the state type.
+    pub type_name: String,
+    /// Size of the state type in bytes.
+    pub size: usize,
+    /// Alignment of the state type in bytes.
+    pub align: usize,
+    /// Explicit version of the state schema.
+    pub version: u32,
+}
+
+impl StateSchema {
+    /// Returns the schema of the given state type, with version 0.
+    pub fn of<State: 'static>() -> Self {
+        Self {
+            type_name: std::any::type_name::<State>().to_owned(),
+            size: std::mem::size_of::<State>(),
+            align: std::mem::align_of::<State>(),
+            version: 0,
+        }
+    }
+
+    /// Sets the explicit version of the schema.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Hash of the schema.
+    ///
+    /// Unlike [std::hash::Hash], the hash is stable across Rust versions and platforms with the
+    /// same pointer width, hence it can be persisted.
+    pub fn hash(&self) -> u64 {
+        // 64-bit FNV-1a
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        feed(self.type_name.as_bytes());
+        feed(&(self.size as u64).to_le_bytes());
+        feed(&(self.align as u64).to_le_bytes());
+        feed(&self.version.to_le_bytes());
+        hash
+    }
+
+    /// Checks whether the schema of a stored state matches this schema.
+    pub fn check(&self, stored: &StateSchema) -> Result<(), StateSchemaMismatch> {
+        if self == stored {
+            return Ok(());
+        }
+        Err(StateSchemaMismatch {
+            expected: self.clone(),
+            found: stored.clone(),
+        })
+    }
+
+    /// Checks whether the schema of a stored state matches this schema, with the mismatch being
+    /// handled according to the given policy.
+    pub fn verify(
+        &self,
+        stored: &StateSchema,
+        policy: StateSchemaPolicy,
+    ) -> Result<(), StateSchemaMismatch> {
+        match (self.check(stored), policy) {
+            (Err(mismatch), StateSchemaPolicy::Warn) => {
+                tracing::warn!("Reusing stale state: {}", mismatch);
+                Ok(())
+            }
+            (result, _) => result,
+        }
+    }
+}
+
+/// Policy on how to handle a stale state, see [StateSchema::verify()].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StateSchemaPolicy {
+    /// Refuse to reuse the state.
+    #[default]
+    Refuse,
+    /// Warn, but reuse the state anyway.
+    Warn,
+}
+
+/// Error returned if the schema of a stored state does not match the current state type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateSchemaMismatch {
+    /// Schema of the current state type.
+    pub expected: StateSchema,
+    /// Schema of the stored state.
+    pub found: StateSchema,
+}
+
+impl std::fmt::Display for StateSchemaMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "state schema mismatch, expected {} (size {}, align {}, version {}) but found {} \
+             (size {}, align {}, version {})",
+            self.expected.type_name,
+            self.expected.size,
+            self.expected.align,
+            self.expected.version,
+            self.found.type_name,
+            self.found.size,
+            self.found.align,
+            self.found.version,
+        )
+    }
+}
+
+impl std::error::Error for StateSchemaMismatch {}
@@ -26,4 +26,5 @@ pub mod moving_average;
 /// |   Printable    ||   FilterEst          TruePos          TrueRange    |
 /// *Printer(filter s*                  *  DrawActor_0   *
 /// ```
+#[cfg(feature = "examples")]
 pub mod one_dim_robot;
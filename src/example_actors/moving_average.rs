@@ -28,6 +28,7 @@ pub struct MovingAverageState {
 
 /// Inbound message for the MovingAverage actor.
 ///
+#[hollywood_test(prop = MovingAverageProp { alpha: 0.3, timeout: 5.0 })]
 #[derive(Clone, Debug)]
 #[actor_inputs(
     MovingAverageInbound,
@@ -1,12 +1,14 @@
 /// One dimensional world and sensor model
 pub mod model;
+pub use crate::Stamped;
 pub use model::RangeMeasurementModel;
 pub use model::Robot;
-pub use model::Stamped;
 
 /// Simulation actor for the robot in the one dimensional world.
 pub mod sim;
 pub use sim::Sim;
+pub use sim::SimNoise;
+pub use sim::SimProp;
 pub use sim::SimState;
 
 /// Kalman filter actor for the one dimensional robot.
@@ -18,7 +18,7 @@ pub enum FilterInboundMessage {
 }
 
 /// Ping-pong request message.
-#[derive(Debug)]
+#[derive(ActorClient, Debug)]
 #[actor_in_requests(
     FilterInRequest,
     {
@@ -55,6 +55,13 @@ impl HasOnMessage for FilterInboundMessage {
             }
         }
     }
+
+    fn timestamp(&self) -> Option<f64> {
+        match self {
+            FilterInboundMessage::NoisyVelocity(v) => Some(v.time.seconds),
+            FilterInboundMessage::NoisyRange(r) => Some(r.time.seconds),
+        }
+    }
 }
 
 impl IsInboundMessageNew<Stamped<f64>> for FilterInboundMessage {
@@ -164,8 +171,8 @@ impl FilterState {
     ///
     /// Predicts the new robot's position based the previous position and the velocity measurement.
     pub fn prediction(&mut self, noisy_velocity: &Stamped<f64>, outbound: &FilterOutbound) {
-        let dt = noisy_velocity.time - self.time;
-        self.time = noisy_velocity.time;
+        let dt = noisy_velocity.time.seconds - self.time;
+        self.time = noisy_velocity.time.seconds;
 
         // 1. Random-walk acceleration motion model
         self.pos_vel_acc.mean[0] +=
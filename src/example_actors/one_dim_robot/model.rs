@@ -1,34 +1,6 @@
 use std::fmt::Debug;
 use std::fmt::Display;
 
-/// A generic value with a timestamp.
-#[derive(Clone, Debug, Default)]
-pub struct Stamped<T: Clone + Debug> {
-    /// Timestamp of the value.
-    pub time: f64,
-    /// Monotonic sequence counter
-    pub seq: u64,
-    /// The value.
-    pub value: T,
-}
-
-impl<T: Clone + Debug> Stamped<T> {
-    /// Creates a new value with a timestamp.
-    pub fn from_stamp_counter_and_value(time: f64, seq: u64, value: &T) -> Self {
-        Self {
-            time,
-            seq,
-            value: value.clone(),
-        }
-    }
-}
-
-impl<T: Display + Clone + Debug> Display for Stamped<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "@{} {}", self.time, self.value)
-    }
-}
-
 /// A robot in a one dimensional world.
 #[derive(Clone, Debug, Default)]
 pub struct Robot {
@@ -2,8 +2,9 @@ use crate::example_actors::one_dim_robot::RangeMeasurementModel;
 use crate::example_actors::one_dim_robot::Robot;
 use crate::example_actors::one_dim_robot::Stamped;
 use crate::prelude::*;
-use rand_distr::Distribution;
-use rand_distr::Normal;
+use crate::sim::Noise;
+use crate::sim::NoiseModel;
+use crate::sim::SimRng;
 use std::fmt::Debug;
 
 /// Ping-pong request message.
@@ -20,7 +21,7 @@ pub struct PingPong {
 #[actor_inputs(
     SimInbound,
     {
-        NullProp,
+        SimProp,
         SimState,
         SimOutbound,
         SimRequest,
@@ -35,20 +36,20 @@ pub enum SimInboundMessage {
 
 /// Simulation for the one-dimensional Robot.
 #[actor(SimInboundMessage, NullInRequestMessage)]
-pub type Sim = Actor<NullProp, SimInbound, NullInRequests, SimState, SimOutbound, SimRequest>;
+pub type Sim = Actor<SimProp, SimInbound, NullInRequests, SimState, SimOutbound, SimRequest>;
 
 impl HasOnMessage for SimInboundMessage {
     /// Invokes [SimState::process_time_stamp()] on TimeStamp.
     fn on_message(
         self,
-        _prop: &Self::Prop,
+        prop: &Self::Prop,
         state: &mut Self::State,
         outbound: &Self::OutboundHub,
         request: &Self::OutRequestHub,
     ) {
         match self {
             SimInboundMessage::TimeStamp(time) => {
-                state.process_time_stamp(time, outbound, request);
+                state.process_time_stamp(prop, time, outbound, request);
                 if time >= state.shutdown_time {
                     outbound.cancel_request.send(CancelRequest);
                 }
@@ -72,6 +73,47 @@ impl IsInboundMessageNew<ReplyMessage<PingPong>> for SimInboundMessage {
     }
 }
 
+/// Simulation properties.
+#[derive(Clone, Debug)]
+pub struct SimProp {
+    /// Noise model of the range measurement.
+    pub range_noise: NoiseModel,
+    /// Noise model of the velocity measurement.
+    pub velocity_noise: NoiseModel,
+    /// Seed of the random number generator. If None, the simulation is not reproducible.
+    pub seed: Option<u64>,
+}
+
+impl Default for SimProp {
+    fn default() -> Self {
+        Self {
+            range_noise: NoiseModel::gaussian(RangeMeasurementModel::RANGE_STD_DEV),
+            velocity_noise: NoiseModel::gaussian(0.01),
+            seed: None,
+        }
+    }
+}
+
+/// Noise sources of the simulation.
+#[derive(Clone, Debug)]
+pub struct SimNoise {
+    /// Noise of the range measurement.
+    pub range: Noise,
+    /// Noise of the velocity measurement.
+    pub velocity: Noise,
+}
+
+impl SimNoise {
+    /// Creates the noise sources as configured in the properties.
+    pub fn from_prop(prop: &SimProp) -> Self {
+        let rng = SimRng::new(prop.seed);
+        Self {
+            range: Noise::new(prop.range_noise, rng.fork()),
+            velocity: Noise::new(prop.velocity_noise, rng.fork()),
+        }
+    }
+}
+
 /// Simulation state
 #[derive(Clone, Debug, Default)]
 pub struct SimState {
@@ -83,65 +125,74 @@ pub struct SimState {
     pub seq: u64,
     /// True position and velocity of the robot.
     pub true_robot: Robot,
+    /// Noise sources, created from [SimProp] on the first time step if None.
+    pub noise: Option<SimNoise>,
 }
 
 impl SimState {
     const RANGE_MODEL: RangeMeasurementModel = RangeMeasurementModel {};
 
+    /// Clock domain of the simulated time.
+    pub const CLOCK_DOMAIN: &'static str = "sim";
+
     /// One step of the simulation.
-    pub fn process_time_stamp(&mut self, time: f64, outbound: &SimOutbound, request: &SimRequest) {
+    pub fn process_time_stamp(
+        &mut self,
+        prop: &SimProp,
+        time: f64,
+        outbound: &SimOutbound,
+        request: &SimRequest,
+    ) {
         let dt = time - self.time;
         self.time = time;
         self.true_robot.position += self.true_robot.velocity * dt;
         self.true_robot.velocity = 2.5 * (0.25 * time).cos();
 
         let true_range = Self::RANGE_MODEL.range(self.true_robot.position);
-        const RANGE_STD_DEV: f64 = RangeMeasurementModel::RANGE_STD_DEV;
-        let range_normal = Normal::new(0.0, RANGE_STD_DEV).unwrap();
-        let s = range_normal.sample(&mut rand::thread_rng());
-        let noisy_range = true_range + s;
-
-        const VELOCITY_STD_DEV: f64 = 0.01;
-        let noisy_velocity = self.true_robot.velocity
-            + Normal::new(0.0, VELOCITY_STD_DEV)
-                .unwrap()
-                .sample(&mut rand::thread_rng());
+        let noise = self.noise.get_or_insert_with(|| SimNoise::from_prop(prop));
+        let noisy_range = noise.range.apply(true_range, dt);
+        let noisy_velocity = noise.velocity.apply(self.true_robot.velocity, dt);
+        let stamp = Timestamp::monotonic(time).with_domain(Self::CLOCK_DOMAIN);
 
         outbound
             .true_robot
             .send(Stamped::from_stamp_counter_and_value(
-                time,
+                stamp.clone(),
                 self.seq,
                 &self.true_robot,
             ));
         outbound
             .true_range
             .send(Stamped::from_stamp_counter_and_value(
-                time,
+                stamp.clone(),
                 self.seq,
                 &true_range,
             ));
-        outbound
-            .noisy_range
-            .send(Stamped::from_stamp_counter_and_value(
-                time,
-                self.seq,
-                &noisy_range,
-            ));
+        if let Some(noisy_range) = noisy_range {
+            outbound
+                .noisy_range
+                .send(Stamped::from_stamp_counter_and_value(
+                    stamp.clone(),
+                    self.seq,
+                    &noisy_range,
+                ));
+        }
         outbound
             .true_velocity
             .send(Stamped::from_stamp_counter_and_value(
-                time,
+                stamp.clone(),
                 self.seq,
                 &self.true_robot.velocity,
             ));
-        outbound
-            .noisy_velocity
-            .send(Stamped::from_stamp_counter_and_value(
-                time,
-                self.seq,
-                &noisy_velocity,
-            ));
+        if let Some(noisy_velocity) = noisy_velocity {
+            outbound
+                .noisy_velocity
+                .send(Stamped::from_stamp_counter_and_value(
+                    stamp.clone(),
+                    self.seq,
+                    &noisy_velocity,
+                ));
+        }
 
         self.seq += 1;
 
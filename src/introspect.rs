@@ -1,2 +1,9 @@
 /// The flow graph.
+#[cfg(feature = "flow_graph")]
 pub mod flow_graph;
+
+/// Export of the topology to Graphviz and Mermaid.
+pub mod graph_export;
+
+/// Counters of the messages sent and received, and the latency of the message handlers.
+pub mod metrics;
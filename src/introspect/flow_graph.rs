@@ -3,9 +3,8 @@ use crate::compute::topology::Topology;
 use drawille::Canvas;
 use drawille::PixelColor;
 use grid::Grid;
-use rand_distr::num_traits::FromPrimitive;
-use rand_distr::num_traits::ToPrimitive;
-use rand_distr::num_traits::{self};
+use num_traits::FromPrimitive;
+use num_traits::ToPrimitive;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 
@@ -17,9 +16,10 @@ pub(crate) struct FlowSuperNode {
 }
 
 impl FlowSuperNode {
-    /// The width of the super node is the number of cells it occupies.
+    /// The width of the super node is the number of cells it occupies. It is at least one, for
+    /// the actor itself, even if the actor has neither inbound nor outbound channels.
     pub fn width(&self) -> usize {
-        self.inbound.len().max(self.outbound.len())
+        self.inbound.len().max(self.outbound.len()).max(1)
     }
 }
 
@@ -384,8 +384,8 @@ impl FlowGraph {
                 let mut start_c = line.start.to_char_coord();
                 start_c.u += Self::TOKEN_WIDTH / 2;
                 let start = start_c.to_canvas_coord::<u32>();
-                if line.end.is_some() {
-                    let mut end_c = line.end.unwrap().to_char_coord();
+                if let Some(end) = line.end {
+                    let mut end_c = end.to_char_coord();
                     end_c.u += Self::TOKEN_WIDTH / 2;
                     let end = end_c.to_canvas_coord::<u32>();
 
@@ -397,8 +397,4 @@ impl FlowGraph {
         }
         canvas
     }
-
-    pub fn print(&self) {
-        println!("{}", self.canvas().frame());
-    }
 }
@@ -0,0 +1,157 @@
+use crate::compute::topology::Connection;
+use crate::compute::topology::Topology;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Escapes the special characters of a label of a DOT record node.
+fn escape_record(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '{' | '}' | '|' | '<' | '>' | '"' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes the special characters of a quoted DOT identifier.
+fn escape_quoted(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escapes the special characters of a quoted Mermaid label.
+fn escape_mermaid(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+/// Actors of the topology in insertion order, with their inbound and outbound channels sorted by
+/// name.
+fn actors(topology: &Topology) -> Vec<(&str, Vec<&str>, Vec<&str>)> {
+    topology
+        .graph
+        .node_indices()
+        .map(|idx| {
+            let node = &topology.graph[idx];
+            (
+                node.name.as_str(),
+                node.inbound.iter().map(String::as_str).collect(),
+                node.outbound.iter().map(String::as_str).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Data connections of the topology.
+fn connections(topology: &Topology) -> impl Iterator<Item = &Connection> {
+    topology.graph.edge_weights()
+}
+
+/// Renders the topology in the DOT language of Graphviz.
+///
+/// Each actor is a record node with its inbound channels on the left and its outbound channels on
+/// the right. Data connections are solid edges between the channels, labeled with the type of the
+/// channel, and request-reply connections are dashed, bidirectional edges from the requesting
+/// actor to the in-request channel.
+pub(crate) fn to_dot(topology: &Topology) -> String {
+    let mut dot = String::new();
+    writeln!(dot, "digraph hollywood {{").unwrap();
+    writeln!(dot, "  rankdir=LR;").unwrap();
+    writeln!(dot, "  node [shape=record];").unwrap();
+    // Port ids are derived from the channel indices, since channel names might not be valid ids.
+    let mut ports = HashMap::new();
+    for (actor, inbound, outbound) in actors(topology) {
+        let inbound_ports: Vec<String> = inbound
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                ports.insert((actor, *name, true), format!("i{}", i));
+                format!("<i{}> {}", i, escape_record(name))
+            })
+            .collect();
+        let outbound_ports: Vec<String> = outbound
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                ports.insert((actor, *name, false), format!("o{}", i));
+                format!("<o{}> {}", i, escape_record(name))
+            })
+            .collect();
+        writeln!(
+            dot,
+            "  \"{}\" [label=\"{{{{{}}}|{}|{{{}}}}}\"];",
+            escape_quoted(actor),
+            inbound_ports.join("|"),
+            escape_record(actor),
+            outbound_ports.join("|")
+        )
+        .unwrap();
+    }
+    let endpoint =
+        |actor: &str, channel: &str, inbound: bool| match ports.get(&(actor, channel, inbound)) {
+            Some(port) => format!("\"{}\":{}", escape_quoted(actor), port),
+            None => format!("\"{}\"", escape_quoted(actor)),
+        };
+    for connection in connections(topology) {
+        writeln!(
+            dot,
+            "  {} -> {} [label=\"{}\"];",
+            endpoint(&connection.from_actor, &connection.from, false),
+            endpoint(&connection.to_actor, &connection.to, true),
+            escape_quoted(&connection.short_type_name())
+        )
+        .unwrap();
+    }
+    for connection in topology.request_connections.iter() {
+        writeln!(
+            dot,
+            "  \"{}\" -> {} [style=dashed, dir=both, label=\"{}\"];",
+            escape_quoted(&connection.from_actor),
+            endpoint(&connection.to_actor, &connection.to, true),
+            escape_quoted(&connection.from)
+        )
+        .unwrap();
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+/// Renders the topology as a Mermaid flowchart.
+///
+/// Each actor is a node. Data connections are solid edges, and request-reply connections are
+/// dotted edges, labeled with the names of the channels. Data connections are labeled with the
+/// type of the channel as well.
+pub(crate) fn to_mermaid(topology: &Topology) -> String {
+    let mut mermaid = String::new();
+    writeln!(mermaid, "flowchart LR").unwrap();
+    // Node ids are derived from the actor indices, since actor names might not be valid ids.
+    let mut ids = HashMap::new();
+    for (i, (actor, _, _)) in actors(topology).into_iter().enumerate() {
+        ids.insert(actor, format!("n{}", i));
+        writeln!(mermaid, "  n{}[\"{}\"]", i, escape_mermaid(actor)).unwrap();
+    }
+    for connection in connections(topology) {
+        writeln!(
+            mermaid,
+            "  {} -->|\"{} → {}: {}\"| {}",
+            ids[connection.from_actor.as_str()],
+            escape_mermaid(&connection.from),
+            escape_mermaid(&connection.to),
+            escape_mermaid(&connection.short_type_name()),
+            ids[connection.to_actor.as_str()]
+        )
+        .unwrap();
+    }
+    for connection in topology.request_connections.iter() {
+        writeln!(
+            mermaid,
+            "  {} -.->|\"{} ⇄ {}\"| {}",
+            ids[connection.from_actor.as_str()],
+            escape_mermaid(&connection.from),
+            escape_mermaid(&connection.to),
+            ids[connection.to_actor.as_str()]
+        )
+        .unwrap();
+    }
+    mermaid
+}
@@ -0,0 +1,360 @@
+use crate::compute::topology::Connection;
+use crate::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::info;
+use tracing::warn;
+
+/// Registry of the counters of a pipeline, see [Hollywood::enable_metrics()].
+///
+/// It is a lightweight handle: clones share the same counters.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Metrics {
+    inner: Arc<Mutex<MetricsRegistry>>,
+}
+
+#[derive(Debug, Default)]
+struct MetricsRegistry {
+    connections: Vec<(Connection, Arc<AtomicU64>)>,
+    actors: BTreeMap<String, Arc<ActorMetrics>>,
+}
+
+impl Metrics {
+    /// Returns the counters of the given actor, which are created on first use.
+    pub(crate) fn actor(&self, actor_name: &str) -> Arc<ActorMetrics> {
+        self.inner
+            .lock()
+            .unwrap()
+            .actors
+            .entry(actor_name.to_owned())
+            .or_insert_with(|| {
+                Arc::new(ActorMetrics {
+                    inner: Mutex::new(ActorMetricsSnapshot {
+                        actor_name: actor_name.to_owned(),
+                        ..Default::default()
+                    }),
+                })
+            })
+            .clone()
+    }
+
+    /// Wraps the connection so that the messages sent through it are counted.
+    pub(crate) fn decorate<T: Send + Sync + 'static>(
+        &self,
+        connection_info: &Connection,
+        connection: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    ) -> Arc<dyn IsGenericConnection<T> + Send + Sync> {
+        let num_sent = Arc::new(AtomicU64::new(0));
+        self.inner
+            .lock()
+            .unwrap()
+            .connections
+            .push((connection_info.clone(), num_sent.clone()));
+        Arc::new(CountingConnection {
+            inner: connection,
+            num_sent,
+        })
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+        let registry = self.inner.lock().unwrap();
+        MetricsSnapshot {
+            connections: registry
+                .connections
+                .iter()
+                .map(|(connection, num_sent)| ConnectionMetrics {
+                    connection: connection.clone(),
+                    num_sent: num_sent.load(Ordering::Relaxed),
+                })
+                .collect(),
+            actors: registry
+                .actors
+                .values()
+                .map(|actor| actor.inner.lock().unwrap().clone())
+                .collect(),
+        }
+    }
+}
+
+/// Counters of a single actor, which are updated by the actor itself.
+#[derive(Debug)]
+pub(crate) struct ActorMetrics {
+    inner: Mutex<ActorMetricsSnapshot>,
+}
+
+impl ActorMetrics {
+    /// Records a message of the given inbound or in-request channel, which was handled in the
+    /// given time.
+    pub(crate) fn record(&self, channel: &str, latency: Duration) {
+        let mut metrics = self.inner.lock().unwrap();
+        match metrics.num_received.get_mut(channel) {
+            Some(n) => *n += 1,
+            None => {
+                metrics.num_received.insert(channel.to_owned(), 1);
+            }
+        }
+        metrics.num_messages += 1;
+        metrics.total_latency += latency;
+        metrics.max_latency = metrics.max_latency.max(latency);
+    }
+}
+
+struct CountingConnection<T> {
+    inner: Arc<dyn IsGenericConnection<T> + Send + Sync>,
+    num_sent: Arc<AtomicU64>,
+}
+
+impl<T: Send + Sync> IsGenericConnection<T> for CountingConnection<T> {
+    fn send_impl(&self, msg: T) {
+        self.num_sent.fetch_add(1, Ordering::Relaxed);
+        self.inner.send_impl(msg);
+    }
+}
+
+/// Number of messages sent through a connection, see [MetricsSnapshot].
+#[derive(Clone, Debug)]
+pub struct ConnectionMetrics {
+    /// The connection between an outbound channel and an inbound channel.
+    pub connection: Connection,
+    /// Number of messages sent by the outbound channel, including messages which are dropped
+    /// later on, e.g. by a middleware or by load shedding.
+    pub num_sent: u64,
+}
+
+/// Counters and handler latency of an actor, see [MetricsSnapshot].
+#[derive(Clone, Debug, Default)]
+pub struct ActorMetricsSnapshot {
+    /// Name of the actor.
+    pub actor_name: String,
+    /// Number of handled messages and requests per inbound and in-request channel.
+    pub num_received: BTreeMap<String, u64>,
+    /// Total number of handled messages and requests.
+    pub num_messages: u64,
+    /// Cumulative time spent in the message and request handlers.
+    pub total_latency: Duration,
+    /// Longest time spent handling a single message or request.
+    pub max_latency: Duration,
+}
+
+impl ActorMetricsSnapshot {
+    /// Average time spent in the handlers per message.
+    pub fn mean_latency(&self) -> Duration {
+        if self.num_messages == 0 {
+            return Duration::ZERO;
+        }
+        self.total_latency.div_f64(self.num_messages as f64)
+    }
+}
+
+/// Snapshot of the metrics of a pipeline, see [Pipeline::metrics()].
+///
+/// It is empty unless metrics are enabled, see [Hollywood::enable_metrics()].
+#[derive(Clone, Debug, Default)]
+pub struct MetricsSnapshot {
+    /// Messages sent per connection, in the order the connections were created.
+    pub connections: Vec<ConnectionMetrics>,
+    /// Counters of the actors which ran so far, sorted by name.
+    pub actors: Vec<ActorMetricsSnapshot>,
+}
+
+impl MetricsSnapshot {
+    /// Number of messages sent by the given outbound channel, summed over its connections.
+    pub fn num_sent(&self, actor_name: &str, outbound_name: &str) -> u64 {
+        self.connections
+            .iter()
+            .filter(|c| c.connection.from_actor == actor_name && c.connection.from == outbound_name)
+            .map(|c| c.num_sent)
+            .sum()
+    }
+
+    /// Number of messages handled by the given inbound or in-request channel.
+    pub fn num_received(&self, actor_name: &str, inbound_name: &str) -> u64 {
+        self.actor(actor_name)
+            .and_then(|actor| actor.num_received.get(inbound_name).copied())
+            .unwrap_or_default()
+    }
+
+    /// Counters of the given actor, or None if it did not run yet.
+    pub fn actor(&self, actor_name: &str) -> Option<&ActorMetricsSnapshot> {
+        self.actors
+            .iter()
+            .find(|actor| actor.actor_name == actor_name)
+    }
+
+    /// Returns the metrics in the Prometheus text exposition format, e.g. to be picked up by
+    /// the textfile collector of the node exporter.
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "# HELP hollywood_messages_sent_total Messages sent per connection.\n\
+             # TYPE hollywood_messages_sent_total counter"
+        );
+        for c in self.connections.iter() {
+            let _ = writeln!(
+                text,
+                "hollywood_messages_sent_total{{from_actor=\"{}\",from=\"{}\",to_actor=\"{}\",\
+                 to=\"{}\"}} {}",
+                escape_label(&c.connection.from_actor),
+                escape_label(&c.connection.from),
+                escape_label(&c.connection.to_actor),
+                escape_label(&c.connection.to),
+                c.num_sent
+            );
+        }
+        let _ = writeln!(
+            text,
+            "# HELP hollywood_messages_received_total Messages handled per inbound channel.\n\
+             # TYPE hollywood_messages_received_total counter"
+        );
+        for actor in self.actors.iter() {
+            for (channel, n) in actor.num_received.iter() {
+                let _ = writeln!(
+                    text,
+                    "hollywood_messages_received_total{{actor=\"{}\",channel=\"{}\"}} {}",
+                    escape_label(&actor.actor_name),
+                    escape_label(channel),
+                    n
+                );
+            }
+        }
+        let _ = writeln!(
+            text,
+            "# HELP hollywood_on_message_seconds Time spent in the message handlers per actor.\n\
+             # TYPE hollywood_on_message_seconds summary"
+        );
+        for actor in self.actors.iter() {
+            let name = escape_label(&actor.actor_name);
+            let _ = writeln!(
+                text,
+                "hollywood_on_message_seconds_sum{{actor=\"{}\"}} {}\n\
+                 hollywood_on_message_seconds_count{{actor=\"{}\"}} {}",
+                name,
+                actor.total_latency.as_secs_f64(),
+                name,
+                actor.num_messages
+            );
+        }
+        let _ = writeln!(
+            text,
+            "# HELP hollywood_on_message_max_seconds Longest message handler call per actor.\n\
+             # TYPE hollywood_on_message_max_seconds gauge"
+        );
+        for actor in self.actors.iter() {
+            let _ = writeln!(
+                text,
+                "hollywood_on_message_max_seconds{{actor=\"{}\"}} {}",
+                escape_label(&actor.actor_name),
+                actor.max_latency.as_secs_f64()
+            );
+        }
+        text
+    }
+}
+
+impl std::fmt::Display for MetricsSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Messages sent:")?;
+        for c in self.connections.iter() {
+            writeln!(
+                f,
+                "  {}.{} -> {}.{}: {}",
+                c.connection.from_actor,
+                c.connection.from,
+                c.connection.to_actor,
+                c.connection.to,
+                c.num_sent
+            )?;
+        }
+        writeln!(f, "Messages handled:")?;
+        for actor in self.actors.iter() {
+            writeln!(
+                f,
+                "  {}: {} messages, {:?} mean latency, {:?} max latency",
+                actor.actor_name,
+                actor.num_messages,
+                actor.mean_latency(),
+                actor.max_latency
+            )?;
+            for (channel, n) in actor.num_received.iter() {
+                writeln!(f, "    {}: {}", channel, n)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Format of the periodic metrics dump, see [MetricsDump].
+#[derive(Clone, Debug)]
+pub enum MetricsFormat {
+    /// Human readable summary, logged at info level.
+    Console,
+    /// Prometheus text format, written to the given file, which is replaced atomically.
+    PrometheusFile(PathBuf),
+}
+
+/// Periodic dump of the metrics while the pipeline is running, see
+/// [Hollywood::set_metrics_dump()].
+#[derive(Clone, Debug)]
+pub struct MetricsDump {
+    /// Interval between two dumps.
+    pub interval: Duration,
+    /// Format and destination of the dump.
+    pub format: MetricsFormat,
+}
+
+impl MetricsDump {
+    /// Creates a dump which logs the metrics at the given interval.
+    pub fn console(interval: Duration) -> Self {
+        Self {
+            interval,
+            format: MetricsFormat::Console,
+        }
+    }
+
+    /// Creates a dump which writes the metrics in Prometheus text format to the given file at the
+    /// given interval.
+    pub fn prometheus_file(interval: Duration, path: impl Into<PathBuf>) -> Self {
+        Self {
+            interval,
+            format: MetricsFormat::PrometheusFile(path.into()),
+        }
+    }
+
+    pub(crate) fn dump(&self, snapshot: &MetricsSnapshot) {
+        match &self.format {
+            MetricsFormat::Console => info!("Pipeline metrics\n{}", snapshot),
+            MetricsFormat::PrometheusFile(path) => {
+                let tmp_path = path.with_extension("tmp");
+                let result = std::fs::write(&tmp_path, snapshot.to_prometheus())
+                    .and_then(|_| std::fs::rename(&tmp_path, path));
+                if let Err(err) = result {
+                    warn!("Failed to write metrics to {:?}: {}", path, err);
+                }
+            }
+        }
+    }
+
+    pub(crate) async fn run(self, metrics: Metrics) {
+        let mut interval = tokio::time::interval(self.interval);
+        // The first tick completes immediately, when there is nothing to report yet.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            self.dump(&metrics.snapshot());
+        }
+    }
+}
@@ -43,7 +43,7 @@
 //!   code and sets the behavior of a user-defines actor.
 //!
 //! - The [macros] module contains the macros that are used to define new actor types with minimal
-//!  boilerplate code.
+//!   boilerplate code.
 //!
 //! - The [compute] module contains the [Hollywood] context and [Pipeline] which are used to
 //!   configure a set of actors, connect them into a graph and to execute flow.
@@ -51,10 +51,17 @@
 //! - The [actors] module contains a set of predefined actors that can be used as part of a compute
 //!   pipelines.
 //!
-//! - The [introspect] module contains a some visualization tools to inspect the compute pipeline.
+//! - The [introspect] module contains a some visualization tools to inspect the compute pipeline,
+//!   and the opt-in metrics, see [Hollywood::enable_metrics()].
+//!
+//! - The [testing] module contains a harness to unit test a single actor, with injected messages,
+//!   collected outbound messages, scripted replies and a virtual clock. With the `proptest`
+//!   feature, it also fuzzes actors with random message sequences to check invariants.
 //!
 //! - The [example_actors] module contains a set of examples actors that demonstrate how to use the
-//!   library.
+//!   library. Example actors with heavy dependencies, such as nalgebra and rand_distr, are only
+//!   available with the `examples` feature, which is not enabled by default. It enables the
+//!   `flow_graph` feature to draw the flow graph, and the `sim` feature for noise models.
 //!
 //! ## Example: moving average
 //!
@@ -236,7 +243,8 @@
 //! ```
 //!
 //! The [Pipeline::print_flow_graph()] method prints the topology of the compute pipeline to the
-//! console.
+//! console, as drawn below with the `flow_graph` feature. Alternatively, [Pipeline::to_dot()] and [Pipeline::to_mermaid()] export it to
+//! Graphviz and Mermaid.
 //!
 //! ``` text
 //! *   Periodic_0   *                                     
@@ -279,8 +287,11 @@
 //! channel connections are n:m. Each outbound channel can be connected to zero, one or more inbound
 //! channels. Similarly, each inbound channel can be connected to zero, one or more outbound
 //! channels. If an outbound channel is connected to multiple inbound channels, the messages are
-//! broadcasted to all connected inbound channels. This is the main reason why
-//! [IsInboundMessage] must be [Clone].
+//! broadcasted to all connected inbound channels, hence [OutboundChannel::connect()] requires the
+//! message type to be [Clone]. Large payloads, which are expensive to clone, are best sent as
+//! [Shared] payloads, which are broadcasted by reference counting instead. Payloads which cannot
+//! be cloned at all can be moved to a single inbound channel using
+//! [OutboundChannel::connect_exclusive()].
 //!
 //! The types of connected outbound channels must match the type of the connected inbound channel.
 //! An inbound channel is uniquely identified by a **variant** of the
@@ -290,16 +301,58 @@
 //! uniform manner, regardless of the outbound channel (and actor) the message originated
 //! from.
 //!
+//! Misconfigured connections, such as an actor connected to itself or cycles, are reported when
+//! the pipeline is configured. [Hollywood::try_configure()]
+//! returns a [TopologyError] which lists all of them by actor and channel name, while
+//! [Hollywood::configure()] panics with it.
+//!
 /// The core framework concepts such as actors, state, inbound, outbound and runners.
 pub mod core;
 pub use crate::core::actor::Actor;
+pub use crate::core::actor::ActorRunOptions;
+pub use crate::core::actor::ActorStateHandle;
+pub use crate::core::actor::BatchStats;
+pub use crate::core::actor::ForwardReceiverOutbound;
 pub use crate::core::actor::ForwardRequestTable;
 pub use crate::core::actor::ForwardTable;
 pub use crate::core::actor::GenericActor;
 pub use crate::core::actor::HasFromPropState;
 pub use crate::core::actor::IsActorNode;
 pub use crate::core::actor_builder::ActorBuilder;
+pub use crate::core::address::Address;
+pub use crate::core::any_message::AnyInboundChannel;
+pub use crate::core::any_message::AnyMessage;
+pub use crate::core::any_message::AnyMessageError;
+pub use crate::core::any_message::AnyOutboundChannel;
+pub use crate::core::backpressure::BackpressurePolicy;
+pub use crate::core::backpressure::BackpressureStats;
+pub use crate::core::channel_ordering::ChannelOrdering;
+pub use crate::core::channel_stats::ChannelStats;
+pub use crate::core::channel_tap::ChannelTap;
+pub use crate::core::chaos::ChaosCommandError;
+pub use crate::core::chaos::ChaosController;
+pub use crate::core::chaos::ChaosFault;
+#[cfg(feature = "serde")]
+pub use crate::core::checkpoint::ActorCheckpoint;
+#[cfg(feature = "serde")]
+pub use crate::core::checkpoint::CheckpointError;
+#[cfg(feature = "serde")]
+pub use crate::core::checkpoint::PipelineSnapshot;
+pub use crate::core::clock::HasClockTimer;
+pub use crate::core::clock::IsClock;
+pub use crate::core::clock::RealClock;
+pub use crate::core::clock::SimClock;
+pub use crate::core::connection::middleware::IsConnectionMiddleware;
+pub use crate::core::connection::middleware::TraceMiddleware;
+pub use crate::core::connection::outbound_connection::PreStartSendPolicy;
 pub use crate::core::connection::ConnectionEnum;
+pub use crate::core::correlation::current_correlation_id;
+pub use crate::core::error_policy::ActorError;
+pub use crate::core::error_policy::ActorErrorEvent;
+pub use crate::core::error_policy::ErrorPolicy;
+pub use crate::core::frame_pool::FramePool;
+pub use crate::core::frame_pool::PooledFrame;
+pub use crate::core::frame_pool::PooledOutboundChannel;
 pub use crate::core::in_request::HasForwardRequestMessage;
 pub use crate::core::in_request::HasOnRequestMessage;
 pub use crate::core::in_request::InRequestChannel;
@@ -308,38 +361,98 @@ pub use crate::core::in_request::IsInRequestMessage;
 pub use crate::core::in_request::IsInRequestMessageNew;
 pub use crate::core::in_request::NullInRequestMessage;
 pub use crate::core::in_request::NullInRequests;
+pub use crate::core::inbound::on_message_smoke_test;
 pub use crate::core::inbound::HasForwardMessage;
 pub use crate::core::inbound::HasOnMessage;
 pub use crate::core::inbound::InboundChannel;
 pub use crate::core::inbound::IsInboundHub;
 pub use crate::core::inbound::IsInboundMessage;
 pub use crate::core::inbound::IsInboundMessageNew;
+pub use crate::core::inbound::IsInboundSink;
 pub use crate::core::inbound::NullInbound;
 pub use crate::core::inbound::NullMessage;
+pub use crate::core::inbound_injector::InboundInjector;
+pub use crate::core::inbound_injector::InboundInjectorError;
+pub use crate::core::invariant::InvariantReport;
+pub use crate::core::load_shedding::LoadSheddingPolicy;
+pub use crate::core::load_shedding::LoadSheddingStats;
+pub use crate::core::out_request::DeferredReply;
+pub use crate::core::out_request::DroppedRequestPolicy;
 pub use crate::core::out_request::IsOutRequestHub;
 pub use crate::core::out_request::IsRequestWithReplyChannel;
 pub use crate::core::out_request::NullOutRequests;
 pub use crate::core::out_request::OutRequestChannel;
+pub use crate::core::out_request::ReplyError;
 pub use crate::core::out_request::ReplyMessage;
+pub use crate::core::out_request::RequestHandle;
+pub use crate::core::out_request::RequestRouting;
+pub use crate::core::out_request::RequestTimeout;
 pub use crate::core::out_request::RequestWithReplyChannel;
 pub use crate::core::outbound::HasActivate;
 pub use crate::core::outbound::IsGenericConnection;
 pub use crate::core::outbound::IsOutboundHub;
 pub use crate::core::outbound::NullOutbound;
 pub use crate::core::outbound::OutboundChannel;
+pub use crate::core::outbound::Shared;
+pub use crate::core::outbound_subscriber::OutboundSubscriber;
+pub use crate::core::request_client::RequestClient;
+pub use crate::core::request_client::RequestClientError;
+pub use crate::core::request_stream::OutRequestStreamChannel;
+pub use crate::core::request_stream::ReplyStreamMessage;
+pub use crate::core::request_stream::RequestWithReplyStream;
+pub use crate::core::runner::ClockRunner;
 pub use crate::core::runner::DefaultRunner;
 pub use crate::core::runner::IsRunner;
+pub use crate::core::stamped::ClockKind;
+pub use crate::core::stamped::Stamped;
+pub use crate::core::stamped::Timestamp;
+pub use crate::core::state_schema::StateSchema;
+pub use crate::core::state_schema::StateSchemaMismatch;
+pub use crate::core::state_schema::StateSchemaPolicy;
 pub use crate::core::value::NullProp;
 pub use crate::core::value::NullState;
 
 /// The compute context and compute graph.
 pub mod compute;
 pub use crate::compute::context::Hollywood;
+pub use crate::compute::dynamic::DynamicConnectionId;
+pub use crate::compute::dynamic::DynamicPort;
+pub use crate::compute::dynamic::DynamicTopology;
+pub use crate::compute::dynamic::DynamicTopologyError;
+pub use crate::compute::heartbeat::Heartbeat;
+pub use crate::compute::heartbeat::HeartbeatTarget;
+pub use crate::compute::manifest::ActorManifest;
+pub use crate::compute::manifest::ConnectionManifest;
+pub use crate::compute::manifest::ManifestError;
+pub use crate::compute::manifest::ManifestIssue;
+pub use crate::compute::manifest::PipelineManifest;
+pub use crate::compute::manifest::SemVer;
+pub use crate::compute::pipeline::ActorFailure;
+pub use crate::compute::pipeline::ActorFailureReason;
+pub use crate::compute::pipeline::ActorFilter;
+pub use crate::compute::pipeline::ActorPlacement;
 pub use crate::compute::pipeline::CancelRequest;
+pub use crate::compute::pipeline::Configured;
+pub use crate::compute::pipeline::CtrlCCallback;
+pub use crate::compute::pipeline::CtrlCHandling;
+pub use crate::compute::pipeline::Finished;
+pub use crate::compute::pipeline::PipelineError;
+pub use crate::compute::pipeline::RunningPipeline;
+pub use crate::compute::pipeline::RuntimeFlavor;
+pub use crate::compute::progress::Progress;
+pub use crate::compute::progress::ProgressTracker;
+pub use crate::compute::supervisor::SupervisionStrategy;
+pub use crate::compute::topology::TopologyError;
+pub use crate::compute::topology::TopologyIssue;
 pub use compute::pipeline::Pipeline;
 
 /// Introspection
 pub mod introspect;
+pub use crate::introspect::metrics::ActorMetricsSnapshot;
+pub use crate::introspect::metrics::ConnectionMetrics;
+pub use crate::introspect::metrics::MetricsDump;
+pub use crate::introspect::metrics::MetricsFormat;
+pub use crate::introspect::metrics::MetricsSnapshot;
 
 /// Library of actors.
 pub mod actors;
@@ -347,6 +460,28 @@ pub mod actors;
 /// Library of actors.
 pub mod example_actors;
 
+/// Noise models and random number generation for simulation actors.
+#[cfg(feature = "sim")]
+pub mod sim;
+
+/// Time-related types in one place: timestamps, stamped values, clocks and the time sync actor.
+///
+/// The types are also available from the crate root and the [prelude](crate::prelude).
+pub mod time {
+    pub use crate::actors::time_sync::TimeSync;
+    pub use crate::actors::time_sync::TimeSyncMode;
+    pub use crate::actors::time_sync::TimeSyncProp;
+    pub use crate::core::clock::IsClock;
+    pub use crate::core::clock::RealClock;
+    pub use crate::core::clock::SimClock;
+    pub use crate::core::stamped::ClockKind;
+    pub use crate::core::stamped::Stamped;
+    pub use crate::core::stamped::Timestamp;
+}
+
+/// Harness to unit test single actors, and property-based fuzzing of actors.
+pub mod testing;
+
 /// Convenience macros for hollywood to define new actor types.
 ///
 /// In order to minimize potential of compile time errors, the macros are best implemented in the
@@ -430,6 +565,10 @@ pub mod macros {
     ///     variant of the INBOUND_MESSAGE enum, and implements the
     ///     [IsInboundHub](crate::IsInboundHub) trait for it.
     ///   - Implements the [IsInboundMessage](crate::IsInboundMessage) trait for INBOUND_MESSAGE.
+    ///   - Doc comments and `cfg`, `cfg_attr` and `allow` attributes of the variants are forwarded
+    ///     to the generated channel fields.
+    ///   - Variants with the `#[priority]` attribute get a high-priority inbound channel, see
+    ///     [InboundChannel::new_priority()](crate::InboundChannel::new_priority).
     ///
     pub use hollywood_macros::actor_inputs;
 
@@ -450,7 +589,8 @@ pub mod macros {
     /// after the macro invocation. (Indeed, these types of macros are called "attribute macros".
     /// They are applied to the item directly following them, in this case a struct.) The outbound
     /// struct consists of a zero, one or more outbound channels. Each outbound channel has a
-    /// user-specified name CHANNEL* and a user specified type TYPE*.
+    /// user-specified name CHANNEL* and a user specified type TYPE*. A channel may also be a
+    /// [PooledOutboundChannel](crate::PooledOutboundChannel), which recycles large buffers.
     ///
     /// Effect: The macro generates the [IsOutboundHub](crate::IsOutboundHub) and
     /// [HasActivate](crate::HasActivate) implementations for the provided struct OUTBOUND.
@@ -500,6 +640,8 @@ pub mod macros {
     ///     [IsInRequestHub](crate::IsInRequestHub) trait for it.
     ///   - Implements the [IsInRequestMessage](crate::IsInRequestMessage) trait for
     ///     IN_REQUEST_MESSAGE.
+    ///   - Doc comments and `cfg`, `cfg_attr` and `allow` attributes of the variants are forwarded
+    ///     to the generated channel fields.
     pub use hollywood_macros::actor_in_requests;
 
     /// This macro generates the boilerplate for the outbound request hub.
@@ -518,7 +660,9 @@ pub mod macros {
     /// Here, REQUEST is the user-specified name of the struct. The struct shall be defined right
     /// after the macro invocation. The request struct consists of one or more request channels.
     /// Each request channel has name CHANNEL*, a request type REQ_TYPE*, a reply type REPL_TYPE*,
-    /// and a message type M*.
+    /// and a message type M*. Request channels whose replies are streams of items are declared as
+    /// [OutRequestStreamChannel](crate::OutRequestStreamChannel)<REQ_TYPE*, ITEM_TYPE*, M*>
+    /// instead.
     ///
     /// Effect: The macro generates the [IsInRequestHub](crate::IsInRequestHub) and
     /// [HasActivate](crate::HasActivate) implementations for the provided struct REQUEST.
@@ -541,29 +685,218 @@ pub mod macros {
     /// In the hollywood library, the the [Zip2](crate::actors::Zip2), [Zip3](crate::actors::Zip3),
     /// ..., and [Zip12](crate::actors::Zip12) actors are predefined using this macro.
     pub use hollywood_macros::zip_n;
+
+    /// This macro generates an approx_zip_n actor that zips N inbound channels into a single
+    /// outbound channel, by the nearest keys within a tolerance.
+    ///
+    /// Macro template:
+    ///
+    /// ``` text
+    /// approx_zip_n!(N);
+    /// ```
+    ///
+    /// N is the number of inbound channels to be zipped.
+    ///
+    /// Effect: The macro generates a new actor type ``ApproxZipN`` that zips N inbound channels
+    /// into a single outbound channel. Unlike ``ZipN``, keys do not need to be equal, but within
+    /// the [ApproxZipProp::tolerance](crate::actors::ApproxZipProp::tolerance) of the key of the
+    /// zipped tuple. The key type shall implement [IsApproxKey](crate::actors::IsApproxKey). The
+    /// actor reuses the ``TupleN`` and ``ZipNOutbound`` types generated by [zip_n].
+    ///
+    /// In the hollywood library, the [ApproxZip2](crate::actors::ApproxZip2),
+    /// [ApproxZip3](crate::actors::ApproxZip3), ..., and
+    /// [ApproxZip12](crate::actors::ApproxZip12) actors are predefined using this macro.
+    pub use hollywood_macros::approx_zip_n;
+
+    /// This macro wires up actors declaratively within the configuration closure of
+    /// [Hollywood::configure()](crate::Hollywood::configure).
+    ///
+    /// Macro template:
+    ///
+    /// ``` text
+    /// pipeline! {
+    ///     context: CONTEXT;
+    ///     SOURCE.OUTBOUND -> SINK.INBOUND;
+    ///     SOURCE.OUTBOUND -> SINK0.INBOUND0, SINK1.INBOUND1;
+    ///     ...
+    /// }
+    /// ```
+    ///
+    /// Here, SOURCE and SINK* are actors, OUTBOUND is the name of an outbound channel of SOURCE
+    /// and INBOUND* is the name of an inbound channel of SINK*. The leading `context: CONTEXT;`
+    /// line is optional; if omitted, the context is expected to be a variable named `context`.
+    ///
+    /// Effect: Each line expands to
+    /// [OutboundChannel::connect()](crate::OutboundChannel::connect) calls, e.g.
+    /// `timer.time_stamp -> average.value;` expands to:
+    ///
+    /// ``` text
+    /// timer.outbound.time_stamp.connect(context, &mut average.inbound.value);
+    /// ```
+    ///
+    /// Hence, misspelled channel names as well as incompatible channel types are compile time
+    /// errors.
+    pub use hollywood_macros::pipeline;
+
+    /// This macro generates a smoke test for the inbound message of an actor.
+    ///
+    /// Macro template:
+    ///
+    /// ``` text
+    /// #[hollywood_test]
+    /// #[actor_inputs(INBOUND, {PROP, STATE, OUTBOUND, OUT_REQUEST, IN_REQUEST_MESSAGE})]
+    /// pub enum INBOUND_MESSAGE {
+    ///     ...
+    /// }
+    /// ```
+    ///
+    /// By default, the PROP and STATE of the actor are created using [Default]. Otherwise, they
+    /// can be provided as arguments: `#[hollywood_test(prop = PROP_EXPR, state = STATE_EXPR)]`.
+    ///
+    /// Prerequisites:
+    ///   - The INBOUND_MESSAGE enum is not generic and implements
+    ///     [HasOnMessage](crate::HasOnMessage).
+    ///   - All payloads of the INBOUND_MESSAGE variants implement [Default].
+    ///
+    /// Effect: The macro generates a `#[cfg(test)]` module with a test which constructs the
+    /// outbound and out-request hubs of the actor, and then calls
+    /// [HasOnMessage::on_message()](crate::HasOnMessage::on_message) once per variant with the
+    /// default payload. The test passes if no message handler panics.
+    pub use hollywood_macros::hollywood_test;
+
+    /// This macro generates a resource actor from a trait of resource operations.
+    ///
+    /// Macro template:
+    ///
+    /// ``` text
+    /// #[resource_actor(ACTOR, RESOURCE)]
+    /// pub trait OPERATIONS {
+    ///     fn OPERATION0(&mut self, request: REQ_TYPE0) -> REPL_TYPE0;
+    ///     fn OPERATION1(&mut self, request: REQ_TYPE1) -> REPL_TYPE1;
+    ///     ...
+    /// }
+    /// ```
+    ///
+    /// Here, ACTOR is the name of the generated actor type and RESOURCE is the type which owns
+    /// the external resource. Each operation takes exactly one request argument (use a tuple for
+    /// several values) and returns the reply.
+    ///
+    /// Prerequisites:
+    ///   - RESOURCE implements [IsResource](crate::actors::IsResource) and the OPERATIONS trait.
+    ///   - All REQ_TYPE* and REPL_TYPE* types are `Clone + Debug + Send + Sync + 'static`.
+    ///
+    /// Effect: The macro generates the actor ACTOR with the configuration of RESOURCE as prop and
+    /// [ResourceState](crate::actors::ResourceState) as state. For each OPERATION*, it has an
+    /// in-request channel of the same name. The resource is opened on the first request and
+    /// closed on shutdown, or when a message is received on the `close` inbound channel.
+    ///
+    /// The generated ACTORInboundMessage, ACTORInbound, ACTORInRequestMessage and ACTORInRequest
+    /// types follow the naming of the [actor_inputs] and [actor_in_requests] macros.
+    pub use hollywood_macros::resource_actor;
+
+    /// This derive macro generates an async client for the in-request channels of an actor.
+    ///
+    /// Macro template:
+    ///
+    /// ``` text
+    /// #[derive(ActorClient, Debug)]
+    /// #[actor_in_requests(REQUEST_HUB, {...})]
+    /// pub enum ACTORInRequestMessage {
+    ///     VARIANT0(RequestWithReplyChannel<REQ_TYPE0, REPL_TYPE0>),
+    ///     VARIANT1(RequestWithReplyChannel<REQ_TYPE1, REPL_TYPE1>),
+    ///     ...
+    /// }
+    /// ```
+    ///
+    /// Effect: The macro generates the `ACTORClient` struct (or `ENUMClient` if the enum name
+    /// does not end with `InRequestMessage`) with one async method per variant, named after the
+    /// in-request channel, e.g.:
+    ///
+    /// ``` text
+    /// pub async fn variant0(&self, request: REQ_TYPE0) -> Result<REPL_TYPE0, RequestClientError>;
+    /// ```
+    ///
+    /// If the enum is annotated with the [actor_in_requests] macro, a `client()` method is added
+    /// to the REQUEST_HUB, otherwise the client is created from the
+    /// [RequestClient](crate::RequestClient)s of the channels. The client can be cloned and used by
+    /// other actors as well as external code.
+    pub use hollywood_macros::ActorClient;
 }
 
 /// The prelude module contains the most important traits and structs of the library.
 pub mod prelude {
+    pub use crate::actors::IsResource;
+    pub use crate::actors::ResourceState;
+    pub use crate::current_correlation_id;
     pub use crate::macros::*;
+    pub use crate::on_message_smoke_test;
     pub use crate::Actor;
     pub use crate::ActorBuilder;
+    #[cfg(feature = "serde")]
+    pub use crate::ActorCheckpoint;
+    pub use crate::ActorError;
+    pub use crate::ActorErrorEvent;
+    pub use crate::ActorManifest;
+    pub use crate::ActorMetricsSnapshot;
+    pub use crate::ActorPlacement;
+    pub use crate::ActorRunOptions;
+    pub use crate::ActorStateHandle;
+    pub use crate::Address;
+    pub use crate::AnyInboundChannel;
+    pub use crate::AnyMessage;
+    pub use crate::AnyMessageError;
+    pub use crate::AnyOutboundChannel;
+    pub use crate::BackpressurePolicy;
+    pub use crate::BackpressureStats;
+    pub use crate::BatchStats;
     pub use crate::CancelRequest;
+    pub use crate::ChannelOrdering;
+    pub use crate::ChannelStats;
+    pub use crate::ChannelTap;
+    pub use crate::ChaosCommandError;
+    pub use crate::ChaosController;
+    pub use crate::ChaosFault;
+    #[cfg(feature = "serde")]
+    pub use crate::CheckpointError;
+    pub use crate::ClockKind;
+    pub use crate::ClockRunner;
+    pub use crate::Configured;
     pub use crate::ConnectionEnum;
+    pub use crate::ConnectionManifest;
+    pub use crate::ConnectionMetrics;
+    pub use crate::CtrlCCallback;
+    pub use crate::CtrlCHandling;
     pub use crate::DefaultRunner;
+    pub use crate::DeferredReply;
+    pub use crate::DroppedRequestPolicy;
+    pub use crate::DynamicConnectionId;
+    pub use crate::DynamicPort;
+    pub use crate::DynamicTopology;
+    pub use crate::DynamicTopologyError;
+    pub use crate::ErrorPolicy;
+    pub use crate::Finished;
+    pub use crate::ForwardReceiverOutbound;
     pub use crate::ForwardRequestTable;
     pub use crate::ForwardTable;
+    pub use crate::FramePool;
     pub use crate::GenericActor;
     pub use crate::HasActivate;
+    pub use crate::HasClockTimer;
     pub use crate::HasForwardMessage;
     pub use crate::HasForwardRequestMessage;
     pub use crate::HasFromPropState;
     pub use crate::HasOnMessage;
     pub use crate::HasOnRequestMessage;
+    pub use crate::Heartbeat;
+    pub use crate::HeartbeatTarget;
     pub use crate::Hollywood;
     pub use crate::InRequestChannel;
     pub use crate::InboundChannel;
+    pub use crate::InboundInjector;
+    pub use crate::InvariantReport;
     pub use crate::IsActorNode;
+    pub use crate::IsClock;
+    pub use crate::IsConnectionMiddleware;
     pub use crate::IsGenericConnection;
     pub use crate::IsInRequestHub;
     pub use crate::IsInRequestMessage;
@@ -571,10 +904,18 @@ pub mod prelude {
     pub use crate::IsInboundHub;
     pub use crate::IsInboundMessage;
     pub use crate::IsInboundMessageNew;
+    pub use crate::IsInboundSink;
     pub use crate::IsOutRequestHub;
     pub use crate::IsOutboundHub;
     pub use crate::IsRequestWithReplyChannel;
     pub use crate::IsRunner;
+    pub use crate::LoadSheddingPolicy;
+    pub use crate::LoadSheddingStats;
+    pub use crate::ManifestError;
+    pub use crate::ManifestIssue;
+    pub use crate::MetricsDump;
+    pub use crate::MetricsFormat;
+    pub use crate::MetricsSnapshot;
     pub use crate::NullInRequestMessage;
     pub use crate::NullInRequests;
     pub use crate::NullInbound;
@@ -584,8 +925,39 @@ pub mod prelude {
     pub use crate::NullProp;
     pub use crate::NullState;
     pub use crate::OutRequestChannel;
+    pub use crate::OutRequestStreamChannel;
     pub use crate::OutboundChannel;
+    pub use crate::OutboundSubscriber;
     pub use crate::Pipeline;
+    pub use crate::PipelineManifest;
+    #[cfg(feature = "serde")]
+    pub use crate::PipelineSnapshot;
+    pub use crate::PooledFrame;
+    pub use crate::PooledOutboundChannel;
+    pub use crate::PreStartSendPolicy;
+    pub use crate::Progress;
+    pub use crate::ProgressTracker;
+    pub use crate::RealClock;
+    pub use crate::ReplyError;
     pub use crate::ReplyMessage;
+    pub use crate::ReplyStreamMessage;
+    pub use crate::RequestClient;
+    pub use crate::RequestClientError;
+    pub use crate::RequestHandle;
+    pub use crate::RequestRouting;
+    pub use crate::RequestTimeout;
     pub use crate::RequestWithReplyChannel;
+    pub use crate::RequestWithReplyStream;
+    pub use crate::RunningPipeline;
+    pub use crate::SemVer;
+    pub use crate::Shared;
+    pub use crate::SimClock;
+    pub use crate::Stamped;
+    pub use crate::StateSchema;
+    pub use crate::StateSchemaMismatch;
+    pub use crate::StateSchemaPolicy;
+    pub use crate::SupervisionStrategy;
+    pub use crate::Timestamp;
+    pub use crate::TopologyError;
+    pub use crate::TopologyIssue;
 }
@@ -0,0 +1,126 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_distr::Distribution;
+use rand_distr::Normal;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Thread-safe, seedable random number generator shared by simulation actors.
+///
+/// Clones share the same underlying generator. Use [SimRng::fork()] to derive an independent,
+/// but still reproducible, generator e.g. for each noise source.
+#[derive(Clone, Debug)]
+pub struct SimRng {
+    rng: Arc<Mutex<StdRng>>,
+}
+
+impl SimRng {
+    /// Creates a generator with the given seed, for reproducible simulations.
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+        }
+    }
+
+    /// Creates a generator seeded from the operating system.
+    pub fn from_entropy() -> Self {
+        Self {
+            rng: Arc::new(Mutex::new(StdRng::from_entropy())),
+        }
+    }
+
+    /// Creates a generator with the given seed, or seeded from the operating system if None.
+    pub fn new(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Self::seeded(seed),
+            None => Self::from_entropy(),
+        }
+    }
+
+    /// Derives a new, independent generator whose seed is drawn from this one.
+    pub fn fork(&self) -> Self {
+        Self::seeded(self.with(|rng| rng.gen()))
+    }
+
+    /// Calls the function with exclusive access to the generator.
+    pub fn with<R>(&self, f: impl FnOnce(&mut StdRng) -> R) -> R {
+        f(&mut self.rng.lock().unwrap())
+    }
+
+    /// Draws a sample from a zero-mean Gaussian with the given standard deviation.
+    pub fn gaussian(&self, std_dev: f64) -> f64 {
+        if std_dev <= 0.0 {
+            return 0.0;
+        }
+        let normal = Normal::new(0.0, std_dev).unwrap();
+        self.with(|rng| normal.sample(rng))
+    }
+}
+
+/// Configuration of a noise model for a scalar measurement, e.g. as part of an actor's Prop.
+///
+/// All noise terms are disabled by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoiseModel {
+    /// Standard deviation of the white Gaussian noise.
+    pub std_dev: f64,
+    /// Initial bias.
+    pub bias: f64,
+    /// Standard deviation of the bias random walk, per square root of a second.
+    pub random_walk_std_dev: f64,
+    /// Probability that a measurement is dropped.
+    pub dropout_probability: f64,
+}
+
+impl NoiseModel {
+    /// White Gaussian noise with the given standard deviation.
+    pub fn gaussian(std_dev: f64) -> Self {
+        Self {
+            std_dev,
+            ..Default::default()
+        }
+    }
+}
+
+/// Stateful noise source which applies a [NoiseModel] to measurements.
+#[derive(Clone, Debug)]
+pub struct Noise {
+    model: NoiseModel,
+    rng: SimRng,
+    bias: f64,
+}
+
+impl Noise {
+    /// Creates a noise source for the given model, driven by the given generator.
+    pub fn new(model: NoiseModel, rng: SimRng) -> Self {
+        Self {
+            model,
+            rng,
+            bias: model.bias,
+        }
+    }
+
+    /// Current bias.
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Returns the noisy measurement of the true value, or None if the measurement is dropped.
+    ///
+    /// `dt` is the time since the previous measurement in seconds, and drives the bias random
+    /// walk.
+    pub fn apply(&mut self, true_value: f64, dt: f64) -> Option<f64> {
+        self.bias += self
+            .rng
+            .gaussian(self.model.random_walk_std_dev * dt.max(0.0).sqrt());
+        if self.model.dropout_probability > 0.0
+            && self
+                .rng
+                .with(|rng| rng.gen_bool(self.model.dropout_probability.min(1.0)))
+        {
+            return None;
+        }
+        Some(true_value + self.bias + self.rng.gaussian(self.model.std_dev))
+    }
+}
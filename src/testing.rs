@@ -0,0 +1,380 @@
+use crate::compute::topology::Connection;
+use crate::core::connection::request_connection::GenericRequestConnection;
+use crate::prelude::*;
+use crate::PipelineError;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Property-based testing of actors, see [fuzz_actor()].
+#[cfg(feature = "proptest")]
+mod fuzz;
+#[cfg(feature = "proptest")]
+pub use fuzz::fuzz_actor;
+
+/// Messages collected by an [ActorHarness], e.g. the messages sent through an outbound channel.
+///
+/// The collection is a cheap handle, i.e. clones share the same messages.
+#[derive(Debug)]
+pub struct Collected<T> {
+    messages: Arc<Mutex<Vec<T>>>,
+}
+
+impl<T> Clone for Collected<T> {
+    fn clone(&self) -> Self {
+        Self {
+            messages: self.messages.clone(),
+        }
+    }
+}
+
+impl<T> Default for Collected<T> {
+    fn default() -> Self {
+        Self {
+            messages: Arc::new(Mutex::new(vec![])),
+        }
+    }
+}
+
+impl<T> Collected<T> {
+    /// Takes all messages collected so far, i.e. the collection is empty afterwards.
+    pub fn take(&self) -> Vec<T> {
+        std::mem::take(&mut *self.messages.lock().unwrap())
+    }
+
+    /// Number of messages collected so far.
+    pub fn len(&self) -> usize {
+        self.messages.lock().unwrap().len()
+    }
+
+    /// Whether no message was collected so far.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&self, msg: T) {
+        self.messages.lock().unwrap().push(msg);
+    }
+}
+
+impl<T: Clone> Collected<T> {
+    /// Copy of the messages collected so far.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    /// The latest collected message, if any.
+    pub fn last(&self) -> Option<T> {
+        self.messages.lock().unwrap().last().cloned()
+    }
+}
+
+struct CollectingConnection<T> {
+    collected: Collected<T>,
+}
+
+impl<T: Send + Sync> IsGenericConnection<T> for CollectingConnection<T> {
+    fn send_impl(&self, msg: T) {
+        self.collected.push(msg);
+    }
+}
+
+/// Collects all messages sent through the outbound channel, e.g. to observe an actor which is
+/// tested by [ActorHarness] or `fuzz_actor`.
+pub fn collect<T: Clone + Send + Sync + std::fmt::Debug + 'static>(
+    context: &mut Hollywood,
+    outbound: &mut OutboundChannel<T>,
+) -> Collected<T> {
+    let collected = Collected::default();
+    let connection = context.decorate_connection(
+        Connection {
+            from_actor: outbound.actor_name.clone(),
+            from: outbound.name.clone(),
+            to_actor: "harness".to_owned(),
+            to: "collect".to_owned(),
+            type_name: std::any::type_name::<T>(),
+        },
+        Arc::new(CollectingConnection {
+            collected: collected.clone(),
+        }),
+    );
+    outbound.connection_register.push(connection);
+    collected
+}
+
+type ScriptFn<Request, Reply> = Box<dyn FnMut(Request) -> Reply + Send>;
+
+/// Request connection which replies right away using a script.
+struct ScriptedReplyConnection<Request, Reply> {
+    script: Mutex<ScriptFn<Request, Reply>>,
+    requests: Collected<Request>,
+}
+
+impl<Request: Clone + Send + Sync, Reply: Send + Sync + std::fmt::Debug>
+    GenericRequestConnection<RequestWithReplyChannel<Request, Reply>>
+    for ScriptedReplyConnection<Request, Reply>
+{
+    fn send_impl(&self, msg: RequestWithReplyChannel<Request, Reply>) {
+        self.requests.push(msg.request.clone());
+        let mut script = self.script.lock().unwrap();
+        msg.reply_from_request(|request| (*script)(request));
+    }
+}
+
+/// Harness to test a single actor outside of a full pipeline:
+///
+/// ``` ignore
+/// #[tokio::test]
+/// async fn moving_average() {
+///     let mut harness = ActorHarness::new();
+///     let mut actor = MovingAverage::from_prop_and_state(
+///         harness.context(),
+///         MovingAverageProp { alpha: 0.5, timeout: 10.0 },
+///         MovingAverageState::default(),
+///     );
+///     let value = actor.inbound.value.injector();
+///     let average = harness.collect(&mut actor.outbound.average);
+///     harness.start().await;
+///
+///     harness.send(&value, 2.0).await;
+///     harness.send(&value, 4.0).await;
+///     assert_eq!(average.take(), vec![1.0, 2.5]);
+/// }
+/// ```
+///
+/// The actor is created in the context of the harness, see [ActorHarness::context()]. Its
+/// outbound channels are collected into vectors using [ActorHarness::collect()], and its
+/// out-request channels are answered by scripted replies, see [ActorHarness::script_replies()].
+/// Messages are injected through [InboundInjector]s, and in-requests are sent through
+/// [RequestClient]s. Other actors can be created in the context as well, e.g. a
+/// [Periodic](crate::actors::Periodic) timer, but the harness is meant to test one actor in
+/// isolation.
+///
+/// The harness runs the actor step-wise on a virtual clock, see [Hollywood::enable_stepping()].
+/// Hence, [ActorHarness::send()] returns once the actor processed the message, and time only
+/// passes when [ActorHarness::advance()] is called. The harness must be started from within a
+/// tokio runtime, e.g. a `#[tokio::test]`.
+pub struct ActorHarness {
+    context: Option<Hollywood>,
+    running: Option<RunningPipeline>,
+    clock: Arc<SimClock>,
+}
+
+impl Default for ActorHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActorHarness {
+    /// Creates a harness with an empty context, and its virtual clock at time zero.
+    pub fn new() -> Self {
+        let clock = Arc::new(SimClock::new());
+        let mut context = Hollywood::new();
+        context.enable_stepping(clock.clone());
+        // Tests must not interfere with the signal handling of the test runner.
+        context.set_ctrl_c_handling(CtrlCHandling::Disabled);
+        Self {
+            context: Some(context),
+            running: None,
+            clock,
+        }
+    }
+
+    /// The context to create the actor under test in.
+    ///
+    /// Panics if the harness was started already.
+    pub fn context(&mut self) -> &mut Hollywood {
+        self.context
+            .as_mut()
+            .expect("ActorHarness: the actor can only be configured before the harness is started")
+    }
+
+    /// Collects all messages sent through the outbound channel, see [collect()].
+    ///
+    /// Panics if the harness was started already.
+    pub fn collect<T: Clone + Send + Sync + std::fmt::Debug + 'static>(
+        &mut self,
+        outbound: &mut OutboundChannel<T>,
+    ) -> Collected<T> {
+        collect(self.context(), outbound)
+    }
+
+    /// Answers all requests of the out-request channel using the given script, and returns the
+    /// collected requests.
+    ///
+    /// The reply is sent right away, and is processed by the actor before [ActorHarness::send()]
+    /// or [ActorHarness::advance()] return.
+    ///
+    /// Panics if the harness was started already.
+    pub fn script_replies<Request, Reply, M>(
+        &mut self,
+        out_request: &mut OutRequestChannel<Request, Reply, M>,
+        script: impl FnMut(Request) -> Reply + Send + 'static,
+    ) -> Collected<Request>
+    where
+        Request: Clone + Send + Sync + std::fmt::Debug + 'static,
+        Reply: Clone + Send + Sync + std::fmt::Debug + 'static,
+        M: IsInboundMessageNew<ReplyMessage<Reply>>,
+    {
+        let requests = Collected::default();
+        out_request
+            .pre_start
+            .set_policy(self.context().pre_start_send_policy);
+        self.context().topology.connect_request(Connection {
+            from_actor: out_request.actor_name.clone(),
+            from: out_request.name.clone(),
+            to_actor: "harness".to_owned(),
+            to: "script".to_owned(),
+            type_name: std::any::type_name::<Request>(),
+        });
+        out_request
+            .connection_register
+            .push(Arc::new(ScriptedReplyConnection {
+                script: Mutex::new(Box::new(script)),
+                requests: requests.clone(),
+            }));
+        requests
+    }
+
+    /// Starts the actor, and waits until it processed the messages which were sent before.
+    ///
+    /// Panics if the harness was started already, or if called outside of a tokio runtime.
+    pub async fn start(&mut self) {
+        let context = self
+            .context
+            .take()
+            .expect("ActorHarness: the harness was started already");
+        self.running = Some(Pipeline::from_context(context).spawn());
+        self.settle().await;
+    }
+
+    /// Waits until the actor processed all pending messages and requests.
+    ///
+    /// Panics if the harness was not started.
+    pub async fn settle(&self) {
+        self.running().quiescent().await;
+    }
+
+    /// Sends the message through the injector, and waits until the actor processed it.
+    ///
+    /// Panics if the actor is not running anymore, or if the harness was not started.
+    pub async fn send<T>(&self, injector: &InboundInjector<T>, msg: T) {
+        injector.send(msg).unwrap_or_else(|err| {
+            panic!(
+                "ActorHarness: cannot send to {}.{}: {}",
+                injector.actor_name(),
+                injector.inbound_name(),
+                err
+            )
+        });
+        self.settle().await;
+    }
+
+    /// Advances the virtual clock by the given duration. Each deadline of a time-based actor
+    /// within the duration is reached in turn, and the harness waits until the actors processed
+    /// it before the clock is advanced further.
+    ///
+    /// Panics if the harness was not started.
+    pub async fn advance(&self, duration: Duration) {
+        let target = self.clock.now() + duration;
+        loop {
+            self.settle().await;
+            match self.clock.next_deadline() {
+                Some(deadline) if deadline <= target => {
+                    self.clock.step();
+                }
+                _ => break,
+            }
+        }
+        let now = self.clock.now();
+        if now < target {
+            self.clock.advance(target - now);
+        }
+        self.settle().await;
+    }
+
+    /// Current time of the virtual clock.
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    /// Stops the actor, and returns the finished pipeline, e.g. to inspect the metrics.
+    ///
+    /// Panics if the harness was not started.
+    pub async fn stop(&mut self) -> Result<Pipeline<Finished>, PipelineError> {
+        let running = self
+            .running
+            .take()
+            .expect("ActorHarness: the harness was not started");
+        running.cancel();
+        running.join().await
+    }
+
+    fn running(&self) -> &RunningPipeline {
+        self.running
+            .as_ref()
+            .expect("ActorHarness: the harness was not started")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actors::Periodic;
+    use crate::example_actors::moving_average::MovingAverage;
+    use crate::example_actors::moving_average::MovingAverageProp;
+    use crate::example_actors::moving_average::MovingAverageState;
+    use crate::prelude::*;
+    use crate::testing::ActorHarness;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn collects_outputs() {
+        let mut harness = ActorHarness::new();
+        let mut actor = MovingAverage::from_prop_and_state(
+            harness.context(),
+            MovingAverageProp {
+                alpha: 0.5,
+                timeout: 10.0,
+            },
+            MovingAverageState::default(),
+        );
+        let value = actor.inbound.value.injector();
+        let average = harness.collect(&mut actor.outbound.average);
+        harness.start().await;
+        assert!(average.is_empty());
+
+        harness.send(&value, 2.0).await;
+        assert_eq!(average.to_vec(), vec![1.0]);
+        harness.send(&value, 4.0).await;
+        assert_eq!(average.last(), Some(2.5));
+        assert_eq!(average.take(), vec![1.0, 2.5]);
+        assert!(average.is_empty());
+        harness.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn advances_virtual_clock() {
+        let mut harness = ActorHarness::new();
+        let mut timer = Periodic::new_with_period(harness.context(), 1.0);
+        let time_stamps = harness.collect(&mut timer.outbound.time_stamp);
+        harness.start().await;
+        assert_eq!(harness.now(), Duration::ZERO);
+
+        harness.advance(Duration::from_secs_f64(0.5)).await;
+        assert_eq!(harness.now(), Duration::from_secs_f64(0.5));
+        let before = time_stamps.take();
+
+        // Each deadline within the duration is reached in turn.
+        harness.advance(Duration::from_secs(3)).await;
+        assert_eq!(harness.now(), Duration::from_secs_f64(3.5));
+        let ticks = time_stamps.take();
+        assert_eq!(ticks.len(), 3, "{:?} after {:?}", ticks, before);
+        assert!(ticks.windows(2).all(|w| w[1] - w[0] == 1.0), "{:?}", ticks);
+
+        // No time passes unless the clock is advanced.
+        harness.settle().await;
+        assert!(time_stamps.is_empty());
+        harness.stop().await.unwrap();
+    }
+}
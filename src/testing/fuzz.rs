@@ -0,0 +1,135 @@
+use crate::prelude::*;
+use proptest::strategy::Strategy;
+use proptest::test_runner::TestCaseError;
+use proptest::test_runner::TestRunner;
+
+/// Fuzzes the message handler of an actor with random sequences of inbound messages, and checks
+/// the invariant after each message:
+///
+/// ``` ignore
+/// fuzz_actor::<MovingAverageMessage, _>(
+///     MovingAverageProp { alpha: 0.3, timeout: 100.0 },
+///     MovingAverageState::default(),
+///     proptest::collection::vec((-10.0..10.0).prop_map(MovingAverageMessage::Value), 1..50),
+///     |context, outbound| collect(context, &mut outbound.average),
+///     |state, average| {
+///         if state.moving_average.abs() > 10.0 {
+///             return Err(format!("average {} out of range", state.moving_average));
+///         }
+///         match average.take().pop() {
+///             Some(value) if value == state.moving_average => Ok(()),
+///             value => Err(format!("sent {:?} instead of the average", value)),
+///         }
+///     },
+/// );
+/// ```
+///
+/// Each message sequence is generated by the `messages` strategy, and passed to a fresh actor
+/// with the given properties and initial state, see [HasOnMessage::on_message()]. Before the
+/// first message, `observe` is called with the outbound hub of the actor, e.g. to
+/// [collect()](crate::testing::collect) the messages of its outbound channels. The invariant is
+/// called with the state of the actor and the observation after each message, and returns an
+/// error message if it is violated.
+///
+/// If the invariant is violated or a message handler panics, the message sequence is shrunk to
+/// a minimal failing one, and this function panics with it. The number of sequences is taken
+/// from the `PROPTEST_CASES` environment variable, see [proptest::test_runner::Config].
+///
+/// As for [hollywood_test](crate::macros::hollywood_test) smoke tests, the out-request channels
+/// of the actor are not connected, i.e. requests are never replied to.
+pub fn fuzz_actor<M, O>(
+    prop: M::Prop,
+    initial_state: M::State,
+    messages: impl Strategy<Value = Vec<M>>,
+    observe: impl Fn(&mut Hollywood, &mut M::OutboundHub) -> O,
+    invariant: impl Fn(&M::State, &O) -> Result<(), String>,
+) where
+    M: HasOnMessage + Clone + std::fmt::Debug,
+    M::State: Clone,
+    M::OutboundHub: IsOutboundHub,
+    M::OutRequestHub: IsOutRequestHub<M>,
+{
+    let mut runner = TestRunner::default();
+    let result = runner.run(&messages, |messages| {
+        let mut context = Hollywood::new();
+        let actor_name = context.add_unique_label("fuzz_actor");
+        let mut outbound = M::OutboundHub::from_context_and_parent(&mut context, &actor_name);
+        let observation = observe(&mut context, &mut outbound);
+        let mut outbound = outbound.extract();
+        outbound.activate();
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel::<M>();
+        let request = M::OutRequestHub::from_parent_and_sender(&actor_name, &sender);
+
+        let mut state = initial_state.clone();
+        for (i, msg) in messages.into_iter().enumerate() {
+            let description = format!("{:?}", msg);
+            msg.on_message(&prop, &mut state, &outbound, &request);
+            invariant(&state, &observation).map_err(|reason| {
+                TestCaseError::fail(format!(
+                    "invariant violated after message #{} {}: {}",
+                    i, description, reason
+                ))
+            })?;
+        }
+        Ok(())
+    });
+    if let Err(err) = result {
+        panic!("fuzz_actor: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example_actors::moving_average::MovingAverageMessage;
+    use crate::example_actors::moving_average::MovingAverageProp;
+    use crate::example_actors::moving_average::MovingAverageState;
+    use crate::testing::collect;
+    use crate::testing::fuzz_actor;
+    use proptest::prelude::*;
+
+    fn values() -> impl Strategy<Value = Vec<MovingAverageMessage>> {
+        proptest::collection::vec((-10.0..10.0).prop_map(MovingAverageMessage::Value), 1..50)
+    }
+
+    #[test]
+    fn moving_average_stays_in_range() {
+        fuzz_actor::<MovingAverageMessage, _>(
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: 100.0,
+            },
+            MovingAverageState::default(),
+            values(),
+            |context, outbound| collect(context, &mut outbound.average),
+            |state, average| {
+                if state.moving_average.abs() > 10.0 {
+                    return Err(format!("average {} out of range", state.moving_average));
+                }
+                match average.take().pop() {
+                    Some(value) if value == state.moving_average => Ok(()),
+                    value => Err(format!("sent {:?} instead of the average", value)),
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invariant violated")]
+    fn violated_invariant_panics() {
+        fuzz_actor::<MovingAverageMessage, _>(
+            MovingAverageProp {
+                alpha: 0.3,
+                timeout: 100.0,
+            },
+            MovingAverageState::default(),
+            values(),
+            |context, outbound| collect(context, &mut outbound.average),
+            |state, _average| {
+                if state.moving_average.abs() > 1.0 {
+                    return Err(format!("average {} above one", state.moving_average));
+                }
+                Ok(())
+            },
+        );
+    }
+}